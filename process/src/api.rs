@@ -0,0 +1,531 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The JSON P2Pool's API files and XMRig's HTTP API hand back, read
+// through a [StatsSource] and turned into Gupax's public APIs in [helper.rs].
+
+//---------------------------------------------------------------------------------------------------- Use
+use log::*;
+use serde::{Deserialize, Serialize};
+
+//---------------------------------------------------------------------------------------------------- Private P2Pool "Local" Api
+// This matches directly to P2Pool's [local/stratum] JSON API file (excluding a few stats).
+// P2Pool seems to initialize all stats at 0 (or 0.0), so no [Option] wrapper seems needed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PrivP2poolLocalApi {
+    pub hashrate_15m: u64,
+    pub hashrate_1h: u64,
+    pub hashrate_24h: u64,
+    pub shares_found: u64,
+    pub average_effort: f32,
+    pub current_effort: f32,
+    pub connections: u32, // This is a `uint32_t` in `p2pool`
+}
+
+impl Default for PrivP2poolLocalApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrivP2poolLocalApi {
+    pub fn new() -> Self {
+        Self {
+            hashrate_15m: 0,
+            hashrate_1h: 0,
+            hashrate_24h: 0,
+            shares_found: 0,
+            average_effort: 0.0,
+            current_effort: 0.0,
+            connections: 0,
+        }
+    }
+}
+
+impl std::str::FromStr for PrivP2poolLocalApi {
+    type Err = serde_json::Error;
+
+    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
+        match serde_json::from_str::<Self>(string) {
+            Ok(a) => Ok(a),
+            Err(e) => {
+                warn!("P2Pool Local API | Could not deserialize API data: {}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Private P2Pool "Network" API
+// This matches P2Pool's [network/stats] JSON API file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrivP2poolNetworkApi {
+    pub difficulty: u64,
+    pub hash: String,
+    pub height: u32,
+    pub reward: u64,
+    pub timestamp: u32,
+}
+
+impl Default for PrivP2poolNetworkApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrivP2poolNetworkApi {
+    pub fn new() -> Self {
+        Self {
+            difficulty: 0,
+            hash: String::from("???"),
+            height: 0,
+            reward: 0,
+            timestamp: 0,
+        }
+    }
+}
+
+impl std::str::FromStr for PrivP2poolNetworkApi {
+    type Err = serde_json::Error;
+
+    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
+        match serde_json::from_str::<Self>(string) {
+            Ok(a) => Ok(a),
+            Err(e) => {
+                warn!("P2Pool Network API | Could not deserialize API data: {}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Private P2Pool "Pool" API
+// This matches P2Pool's [pool/stats] JSON API file.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PrivP2poolPoolApi {
+    pub pool_statistics: PoolStatistics,
+}
+
+impl Default for PrivP2poolPoolApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrivP2poolPoolApi {
+    pub fn new() -> Self {
+        Self {
+            pool_statistics: PoolStatistics::new(),
+        }
+    }
+}
+
+impl std::str::FromStr for PrivP2poolPoolApi {
+    type Err = serde_json::Error;
+
+    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
+        match serde_json::from_str::<Self>(string) {
+            Ok(a) => Ok(a),
+            Err(e) => {
+                warn!("P2Pool Pool API | Could not deserialize API data: {}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PoolStatistics {
+    pub hashRate: u64,
+    pub miners: u32,
+}
+impl Default for PoolStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl PoolStatistics {
+    pub fn new() -> Self {
+        Self {
+            hashRate: 0,
+            miners: 0,
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Private XMRig API
+// This matches to some JSON stats in the HTTP call [summary],
+// e.g: [wget -qO- localhost:18085/1/summary].
+// XMRig doesn't initialize stats at 0 (or 0.0) and instead opts for [null]
+// which means some elements need to be wrapped in an [Option] or else serde will [panic!].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrivXmrigApi {
+    pub worker_id: String,
+    pub resources: Resources,
+    pub connection: Connection,
+    pub hashrate: Hashrate,
+    // These are only used for the [XMRig details] in the [Status] tab.
+    // They don't exist in older XMRig versions, so default instead of erroring.
+    #[serde(default)]
+    pub cpu: Cpu,
+    #[serde(default)]
+    pub hugepages: Hugepages,
+    #[serde(default)]
+    pub donate_level: Option<u32>,
+    #[serde(default)]
+    pub paused: bool,
+    // Only used in watch-only mode, Gupax knows the uptime of its own XMRig.
+    #[serde(default, skip_serializing)]
+    pub uptime: u64,
+}
+
+impl Default for PrivXmrigApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrivXmrigApi {
+    pub fn new() -> Self {
+        Self {
+            worker_id: String::new(),
+            resources: Resources::new(),
+            connection: Connection::new(),
+            hashrate: Hashrate::new(),
+            cpu: Cpu::default(),
+            hugepages: Hugepages::default(),
+            donate_level: None,
+            paused: false,
+            uptime: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct Resources {
+    pub load_average: [Option<f32>; 3],
+    #[serde(default)]
+    pub memory: Memory,
+}
+impl Default for Resources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Resources {
+    pub fn new() -> Self {
+        Self {
+            load_average: [Some(0.0), Some(0.0), Some(0.0)],
+            memory: Memory::default(),
+        }
+    }
+}
+
+// All in bytes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Memory {
+    pub free: u64,
+    pub total: u64,
+    pub resident_set_memory: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Connection {
+    pub diff: u128,
+    pub accepted: u128,
+    pub rejected: u128,
+}
+impl Default for Connection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Connection {
+    pub fn new() -> Self {
+        Self {
+            diff: 0,
+            accepted: 0,
+            rejected: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Hashrate {
+    pub total: [Option<f32>; 3],
+    // Per-thread [10s, 60s, 15m] hashrate.
+    #[serde(default)]
+    pub threads: Vec<[Option<f32>; 3]>,
+}
+impl Default for Hashrate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Hashrate {
+    pub fn new() -> Self {
+        Self {
+            total: [Some(0.0), Some(0.0), Some(0.0)],
+            threads: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Cpu {
+    pub brand: String,
+    pub aes: bool,
+    pub avx2: bool,
+    pub l2: u64,
+    pub l3: u64,
+    pub cores: u32,
+    pub threads: u32,
+    pub msr: String, // MSR preset XMRig applied, e.g: [ryzen_19h], [intel], [none]
+}
+
+// Older XMRig versions report [true/false],
+// newer ones report [allocated, total] pages.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(untagged)]
+pub enum Hugepages {
+    Bool(bool),
+    Pages([u64; 2]),
+}
+impl Default for Hugepages {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serde_priv_p2pool_local_api() {
+        let data = r#"{
+				"hashrate_15m": 12,
+				"hashrate_1h": 11111,
+				"hashrate_24h": 468967,
+				"total_hashes": 2019283840922394082390,
+				"shares_found": 289037,
+				"average_effort": 915.563,
+				"current_effort": 129.297,
+				"connections": 123,
+				"incoming_connections": 96
+			}"#;
+        let priv_api = data.parse::<PrivP2poolLocalApi>().unwrap();
+        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
+        println!("{}", json);
+        let data_after_ser = r#"{
+  "hashrate_15m": 12,
+  "hashrate_1h": 11111,
+  "hashrate_24h": 468967,
+  "shares_found": 289037,
+  "average_effort": 915.563,
+  "current_effort": 129.297,
+  "connections": 123
+}"#;
+        assert_eq!(data_after_ser, json)
+    }
+
+    #[test]
+    fn serde_priv_p2pool_network_api() {
+        let data = r#"{
+				"difficulty": 319028180924,
+				"hash": "22ae1b83d727bb2ff4efc17b485bc47bc8bf5e29a7b3af65baf42213ac70a39b",
+				"height": 2776576,
+				"reward": 600499860000,
+				"timestamp": 1670953659
+			}"#;
+        let priv_api = data.parse::<PrivP2poolNetworkApi>().unwrap();
+        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
+        println!("{}", json);
+        let data_after_ser = r#"{
+  "difficulty": 319028180924,
+  "hash": "22ae1b83d727bb2ff4efc17b485bc47bc8bf5e29a7b3af65baf42213ac70a39b",
+  "height": 2776576,
+  "reward": 600499860000,
+  "timestamp": 1670953659
+}"#;
+        assert_eq!(data_after_ser, json)
+    }
+
+    #[test]
+    fn serde_priv_p2pool_pool_api() {
+        let data = r#"{
+				"pool_list": ["pplns"],
+				"pool_statistics": {
+					"hashRate": 10225772,
+					"miners": 713,
+					"totalHashes": 487463929193948,
+					"lastBlockFoundTime": 1670453228,
+					"lastBlockFound": 2756570,
+					"totalBlocksFound": 4
+				}
+			}"#;
+        let priv_api = data.parse::<PrivP2poolPoolApi>().unwrap();
+        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
+        println!("{}", json);
+        let data_after_ser = r#"{
+  "pool_statistics": {
+    "hashRate": 10225772,
+    "miners": 713
+  }
+}"#;
+        assert_eq!(data_after_ser, json)
+    }
+
+    #[test]
+    fn serde_priv_xmrig_api() {
+        let data = r#"{
+		    "id": "6226e3sd0cd1a6es",
+		    "worker_id": "hinto",
+		    "uptime": 123,
+		    "restricted": true,
+		    "resources": {
+		        "memory": {
+		            "free": 123,
+		            "total": 123123,
+		            "resident_set_memory": 123123123
+		        },
+		        "load_average": [10.97, 10.58, 10.47],
+		        "hardware_concurrency": 12
+		    },
+		    "features": ["api", "asm", "http", "hwloc", "tls", "opencl", "cuda"],
+		    "results": {
+		        "diff_current": 123,
+		        "shares_good": 123,
+		        "shares_total": 123,
+		        "avg_time": 123,
+		        "avg_time_ms": 123,
+		        "hashes_total": 123,
+		        "best": [123, 123, 123, 13, 123, 123, 123, 123, 123, 123],
+		        "error_log": []
+		    },
+		    "algo": "rx/0",
+		    "connection": {
+		        "pool": "localhost:3333",
+		        "ip": "127.0.0.1",
+		        "uptime": 123,
+		        "uptime_ms": 123,
+		        "ping": 0,
+		        "failures": 0,
+		        "tls": null,
+		        "tls-fingerprint": null,
+		        "algo": "rx/0",
+		        "diff": 123,
+		        "accepted": 123,
+		        "rejected": 123,
+		        "avg_time": 123,
+		        "avg_time_ms": 123,
+		        "hashes_total": 123,
+		        "error_log": []
+		    },
+		    "version": "6.18.0",
+		    "kind": "miner",
+		    "ua": "XMRig/6.18.0 (Linux x86_64) libuv/2.0.0-dev gcc/10.2.1",
+		    "cpu": {
+		        "brand": "blah blah blah",
+		        "family": 1,
+		        "model": 2,
+		        "stepping": 0,
+		        "proc_info": 123,
+		        "aes": true,
+		        "avx2": true,
+		        "x64": true,
+		        "64_bit": true,
+		        "l2": 123123,
+		        "l3": 123123,
+		        "cores": 12,
+		        "threads": 24,
+		        "packages": 1,
+		        "nodes": 1,
+		        "backend": "hwloc/2.8.0a1-git",
+		        "msr": "ryzen_19h",
+		        "assembly": "ryzen",
+		        "arch": "x86_64",
+		        "flags": ["aes", "vaes", "avx", "avx2", "bmi2", "osxsave", "pdpe1gb", "sse2", "ssse3", "sse4.1", "popcnt", "cat_l3"]
+		    },
+		    "donate_level": 0,
+		    "paused": false,
+		    "algorithms": ["cn/1", "cn/2", "cn/r", "cn/fast", "cn/half", "cn/xao", "cn/rto", "cn/rwz", "cn/zls", "cn/double", "cn/ccx", "cn-lite/1", "cn-heavy/0", "cn-heavy/tube", "cn-heavy/xhv", "cn-pico", "cn-pico/tlo", "cn/upx2", "rx/0", "rx/wow", "rx/arq", "rx/graft", "rx/sfx", "rx/keva", "argon2/chukwa", "argon2/chukwav2", "argon2/ninja", "astrobwt", "astrobwt/v2", "ghostrider"],
+		    "hashrate": {
+		        "total": [111.11, 111.11, 111.11],
+		        "highest": 111.11,
+		        "threads": [
+		            [111.11, 111.11, 111.11]
+		        ]
+		    },
+		    "hugepages": true
+		}"#;
+        let priv_api = serde_json::from_str::<PrivXmrigApi>(data).unwrap();
+        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
+        println!("{}", json);
+        let data_after_ser = r#"{
+  "worker_id": "hinto",
+  "resources": {
+    "load_average": [
+      10.97,
+      10.58,
+      10.47
+    ],
+    "memory": {
+      "free": 123,
+      "total": 123123,
+      "resident_set_memory": 123123123
+    }
+  },
+  "connection": {
+    "diff": 123,
+    "accepted": 123,
+    "rejected": 123
+  },
+  "hashrate": {
+    "total": [
+      111.11,
+      111.11,
+      111.11
+    ],
+    "threads": [
+      [
+        111.11,
+        111.11,
+        111.11
+      ]
+    ]
+  },
+  "cpu": {
+    "brand": "blah blah blah",
+    "aes": true,
+    "avx2": true,
+    "l2": 123123,
+    "l3": 123123,
+    "cores": 12,
+    "threads": 24,
+    "msr": "ryzen_19h"
+  },
+  "hugepages": true,
+  "donate_level": 0,
+  "paused": false
+}"#;
+        assert_eq!(data_after_ser, json)
+    }
+}
@@ -0,0 +1,250 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Why a process exited on its own.
+//
+// A PTY only hands back [Success], [Exited with code N] or
+// [Terminated by <strsignal() text>], which by itself just shows up as
+// [Failed]. This turns that into something readable and checks the
+// usual suspect for a [SIGKILL] nobody asked for: the kernel's OOM killer.
+// On Linux [/proc/vmstat] counts OOM kills (no root needed), so the count
+// at start is compared to the count at exit, and the kernel log is
+// searched for the PID if it's readable.
+
+//---------------------------------------------------------------------------------------------------- Use
+use portable_pty::ExitStatus;
+use std::fmt;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// [strsignal()] texts (glibc/musl, macOS adds [: N]) -> signal number & name.
+const SIGNALS: [(&str, i32, &str); 12] = [
+    ("Hangup", 1, "SIGHUP"),
+    ("Interrupt", 2, "SIGINT"),
+    ("Quit", 3, "SIGQUIT"),
+    ("Illegal instruction", 4, "SIGILL"),
+    ("Trace/breakpoint trap", 5, "SIGTRAP"),
+    ("Aborted", 6, "SIGABRT"),
+    ("Bus error", 7, "SIGBUS"),
+    ("Floating point exception", 8, "SIGFPE"),
+    ("Killed", 9, "SIGKILL"),
+    ("Segmentation fault", 11, "SIGSEGV"),
+    ("Broken pipe", 13, "SIGPIPE"),
+    ("Terminated", 15, "SIGTERM"),
+];
+const SIGKILL: i32 = 9;
+
+// Windows [NTSTATUS] exit codes worth naming.
+const NTSTATUS: [(u32, &str); 6] = [
+    (0xC0000005, "access violation"),
+    (0xC0000017, "out of memory"),
+    (0xC000001D, "illegal instruction"),
+    (0xC00000FD, "stack overflow"),
+    (0xC000012D, "out of memory (commit limit)"),
+    (0xC0000409, "stack buffer overrun"),
+];
+
+//---------------------------------------------------------------------------------------------------- ExitCause
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExitCause {
+    Success,
+    OutOfMemory,
+    Signal(Option<i32>, String), // Number (if known) & name/description
+    Code(u32),
+}
+
+impl ExitCause {
+    // [oom_before] is [oom_kills()] from when the process started.
+    pub fn new(status: &ExitStatus, pid: Option<u32>, oom_before: Option<u64>) -> Self {
+        let cause = Self::from_status(status);
+        let oom = match (oom_before, oom_kills()) {
+            (Some(before), Some(now)) => now > before,
+            _ => false,
+        } || pid.is_some_and(kernel_log_oom);
+        match cause {
+            Self::Signal(Some(SIGKILL), _) if oom => Self::OutOfMemory,
+            Self::Code(137) if oom => Self::OutOfMemory,
+            cause => cause,
+        }
+    }
+
+    // Just the exit status, no OOM checks.
+    pub fn from_status(status: &ExitStatus) -> Self {
+        if status.success() {
+            return Self::Success;
+        }
+        match status.to_string().strip_prefix("Terminated by ") {
+            Some(signal) => Self::parse_signal(signal),
+            None => Self::Code(status.exit_code()),
+        }
+    }
+
+    fn parse_signal(text: &str) -> Self {
+        // macOS: [Killed: 9], glibc: [Killed], unknown: [Signal 9]
+        let (text, number) = match text.rsplit_once(": ") {
+            Some((text, n)) => (text, n.trim().parse().ok()),
+            None => match text.strip_prefix("Signal ") {
+                Some(n) => (text, n.trim().parse().ok()),
+                None => (text, None),
+            },
+        };
+        match SIGNALS
+            .iter()
+            .find(|(desc, n, _)| *desc == text || Some(*n) == number)
+        {
+            Some((_, n, name)) => Self::Signal(Some(*n), name.to_string()),
+            None => Self::Signal(number, text.to_string()),
+        }
+    }
+
+    pub const fn success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    // What the user could do about it, if there's anything to say.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Success => None,
+            Self::OutOfMemory => Some("The system ran out of memory, use fewer threads, disable huge pages or close other programs"),
+            Self::Signal(Some(SIGKILL), _) => Some("Something outside of Gupax force-killed it (e.g: [kill -9], a task manager, or the OOM killer if the kernel log mentions it)"),
+            Self::Signal(Some(4 | 7 | 11), _) => Some("It crashed, an unstable overclock/undervolt or a build for the wrong CPU can cause this"),
+            Self::Signal(..) => Some("It was stopped by a signal from outside of Gupax"),
+            Self::Code(code) if Self::ntstatus(*code).is_some() => Some("Check [Event Viewer -> Windows Logs -> Application] around this time for details"),
+            Self::Code(_) => None,
+        }
+    }
+
+    fn ntstatus(code: u32) -> Option<&'static str> {
+        NTSTATUS.iter().find(|(c, _)| *c == code).map(|(_, s)| *s)
+    }
+}
+
+impl fmt::Display for ExitCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Success => write!(f, "Successful"),
+            Self::OutOfMemory => write!(f, "Killed by the OOM killer (out of memory)"),
+            Self::Signal(Some(n), name) => write!(f, "Terminated by signal {} ({})", n, name),
+            Self::Signal(None, name) => write!(f, "Terminated by signal ({})", name),
+            Self::Code(code) => match Self::ntstatus(*code) {
+                Some(s) => write!(f, "Exited with code 0x{:X} ({})", code, s),
+                // A shell/[sudo] in between reports a signal as [128 + N].
+                None if cfg!(unix) && (129..=192).contains(code) => {
+                    match SIGNALS.iter().find(|(_, n, _)| *n as u32 == code - 128) {
+                        Some((_, n, name)) => {
+                            write!(f, "Exited with code {} (signal {}, {})", code, n, name)
+                        }
+                        None => write!(f, "Exited with code {} (signal {})", code, code - 128),
+                    }
+                }
+                None => write!(f, "Exited with code {}", code),
+            },
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- OOM
+// How many processes the kernel's OOM killer has killed since boot, Linux only.
+pub fn oom_kills() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+    parse_vmstat(&vmstat)
+}
+
+fn parse_vmstat(vmstat: &str) -> Option<u64> {
+    vmstat
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))
+        .and_then(|n| n.trim().parse().ok())
+}
+
+// Does the kernel log say [pid] was OOM killed? Usually needs
+// root ([dmesg_restrict]), so [false] just means "don't know".
+fn kernel_log_oom(pid: u32) -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    match std::process::Command::new("dmesg")
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            log_has_oom(&String::from_utf8_lossy(&output.stdout), pid)
+        }
+        _ => false,
+    }
+}
+
+fn log_has_oom(log: &str, pid: u32) -> bool {
+    let needle = format!("Killed process {} ", pid);
+    log.lines()
+        .any(|line| line.contains(&needle) && line.to_lowercase().contains("out of memory"))
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exit_causes() {
+        let cause = |s: &ExitStatus| ExitCause::from_status(s);
+        assert_eq!(cause(&ExitStatus::with_exit_code(0)), ExitCause::Success);
+        assert_eq!(cause(&ExitStatus::with_exit_code(1)), ExitCause::Code(1));
+        let killed = cause(&ExitStatus::with_signal("Killed"));
+        assert_eq!(killed, ExitCause::Signal(Some(9), "SIGKILL".to_string()));
+        assert_eq!(killed.to_string(), "Terminated by signal 9 (SIGKILL)");
+        assert!(killed.hint().is_some());
+        // macOS & unknown [strsignal()] texts.
+        assert_eq!(
+            cause(&ExitStatus::with_signal("Segmentation fault: 11")),
+            ExitCause::Signal(Some(11), "SIGSEGV".to_string())
+        );
+        assert_eq!(
+            cause(&ExitStatus::with_signal("Signal 40")),
+            ExitCause::Signal(Some(40), "Signal 40".to_string())
+        );
+        assert_eq!(
+            ExitCause::Code(0xC0000005).to_string(),
+            "Exited with code 0xC0000005 (access violation)"
+        );
+        assert_eq!(
+            ExitCause::OutOfMemory.to_string(),
+            "Killed by the OOM killer (out of memory)"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            ExitCause::Code(137).to_string(),
+            "Exited with code 137 (signal 9, SIGKILL)"
+        );
+    }
+
+    #[test]
+    fn oom_detection() {
+        assert_eq!(
+            parse_vmstat("nr_free_pages 1\noom_kill 3\nnr_zone 2\n"),
+            Some(3)
+        );
+        assert_eq!(parse_vmstat("nr_free_pages 1\n"), None);
+        let log = "[123.4] Out of memory: Killed process 4242 (xmrig) total-vm:1234kB\n";
+        assert!(log_has_oom(log, 4242));
+        assert!(!log_has_oom(log, 424));
+        assert!(!log_has_oom("[1.0] Killed process 4242 (xmrig)\n", 4242));
+    }
+}
@@ -0,0 +1,46 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Process supervision for Gupax.
+//
+// The parts of the old [helper.rs] that don't care whether the
+// process is P2Pool, XMRig or XMRig-Proxy (or in the future, [monerod]):
+//
+// | MODULE   | PURPOSE                                                                  |
+// |----------|--------------------------------------------------------------------------|
+// | process  | The [Process] state shared with the GUI + the [ManagedProcess] trait      |
+// | watchdog | Spawning into a PTY, the watchdog loop + the [Watchdog] trait, restarting |
+// | exit     | Why a process exited: signal, exit code or the OOM killer                 |
+// | output   | Reading a PTY into the [parse/pub] output buffers, capping the GUI output  |
+// | source   | The [StatsSource] trait for the APIs stats are read from (files, HTTP)    |
+// | api      | The P2Pool/XMRig API JSON those sources return                            |
+//
+// The actual P2Pool/XMRig/XMRig-Proxy implementations live in Gupax's [helper.rs].
+
+mod api;
+mod exit;
+mod output;
+mod process;
+mod source;
+mod watchdog;
+
+pub use api::*;
+pub use exit::*;
+pub use output::*;
+pub use process::*;
+pub use source::*;
+pub use watchdog::*;
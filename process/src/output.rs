@@ -0,0 +1,362 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//---------------------------------------------------------------------------------------------------- Use
+use crate::ProcessName;
+use log::*;
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// The max amount of bytes of process output we are willing to
+// hold in memory before it's too much and we need to reset.
+pub const MAX_GUI_OUTPUT_BYTES: usize = 500_000;
+// Just a little leeway so a reset will go off before the [String] allocates more memory.
+pub const GUI_OUTPUT_LEEWAY: usize = MAX_GUI_OUTPUT_BYTES - 1000;
+// A single line longer than this is cut, the rest of it is thrown away.
+pub const MAX_LINE_BYTES: usize = 16_384;
+
+// Output faster than this (per [FLOOD_WINDOW]) is a flood...
+pub const FLOOD_BYTES: usize = 256 * 1024;
+pub const FLOOD_WINDOW: Duration = Duration::from_secs(1);
+// ...and only every Nth line of it makes it into the GUI console.
+pub const FLOOD_KEEP_EVERY: u64 = 100;
+
+// The current console cap, [MAX_GUI_OUTPUT_BYTES] unless changed in the settings.
+static CONSOLE_CAP: AtomicUsize = AtomicUsize::new(MAX_GUI_OUTPUT_BYTES);
+// Bytes thrown away from the unread [parse/pub] buffers, since Gupax started.
+static TRIMMED: AtomicU64 = AtomicU64::new(0);
+// Lines left out of the GUI consoles by [FloodGuard], since Gupax started.
+static SKIPPED: AtomicU64 = AtomicU64::new(0);
+
+// Set the max bytes a console holds before it resets.
+pub fn set_console_cap(bytes: usize) {
+    CONSOLE_CAP.store(bytes.max(10_000), Ordering::Relaxed);
+}
+
+pub fn console_cap() -> usize {
+    CONSOLE_CAP.load(Ordering::Relaxed)
+}
+
+// The [parse/pub] buffers are emptied by the watchdog every second, so they
+// only get near this when it's stuck or a process floods its output.
+pub fn pending_cap() -> usize {
+    console_cap() * 2
+}
+
+pub fn trimmed_bytes() -> u64 {
+    TRIMMED.load(Ordering::Relaxed)
+}
+
+pub fn skipped_lines() -> u64 {
+    SKIPPED.load(Ordering::Relaxed)
+}
+
+//---------------------------------------------------------------------------------------------------- [FloodGuard]
+// A process printing megabytes per second would keep the PTY reader and the
+// GUI busy appending/rendering text nobody can read. While the output rate is
+// over [FLOOD_BYTES], only every [FLOOD_KEEP_EVERY]th line goes to the GUI
+// console. [output_parse] still gets every line, nothing parsed is missed.
+#[derive(Debug)]
+pub struct FloodGuard {
+    start: Instant, // Start of the current [FLOOD_WINDOW]
+    bytes: usize,   // ...and the bytes read in it
+    flooding: bool,
+    lines: u64,          // Lines seen while flooding
+    skipped: u64,        // Lines skipped in the current window
+    report: Option<u64>, // Lines skipped in the last window, see [take_report()]
+}
+
+impl FloodGuard {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            start: now,
+            bytes: 0,
+            flooding: false,
+            lines: 0,
+            skipped: 0,
+            report: None,
+        }
+    }
+
+    // Should a line of [bytes] read at [now] go to the GUI console?
+    pub fn keep(&mut self, now: Instant, bytes: usize) -> bool {
+        if now.duration_since(self.start) >= FLOOD_WINDOW {
+            // The last window's rate decides if this one is still a flood.
+            self.flooding = self.bytes >= FLOOD_BYTES;
+            if self.skipped != 0 {
+                self.report = Some(self.skipped);
+            }
+            self.start = now;
+            self.bytes = 0;
+            self.skipped = 0;
+        }
+        self.bytes += bytes;
+        if !self.flooding && self.bytes >= FLOOD_BYTES {
+            self.flooding = true;
+            self.lines = 0;
+        }
+        if !self.flooding {
+            return true;
+        }
+        self.lines += 1;
+        if self.lines % FLOOD_KEEP_EVERY == 1 {
+            true
+        } else {
+            self.skipped += 1;
+            false
+        }
+    }
+
+    // How many lines were skipped in the last window, once.
+    pub fn take_report(&mut self) -> Option<u64> {
+        self.report.take()
+    }
+}
+
+// Drop the oldest whole lines of [output] so at most [max] bytes are left.
+// Returns how many bytes were removed.
+pub fn trim_front(output: &mut String, max: usize) -> usize {
+    if output.len() <= max {
+        return 0;
+    }
+    let mut cut = output.len() - max;
+    cut = match output[cut..].find('\n') {
+        Some(i) => cut + i + 1,
+        None => output.len(),
+    };
+    output.drain(..cut);
+    cut
+}
+
+//---------------------------------------------------------------------------------------------------- Functions
+// Read a PTY (STDOUT+STDERR) line by line until it closes.
+//
+// The GUI console renders colors, so [output_pub] keeps the escape
+// sequences, everything parsed gets them stripped. [on_line] sees
+// each stripped line first, for things that can't wait for the
+// watchdog loop (e.g: P2Pool payouts).
+pub fn read_pty(
+    name: ProcessName,
+    output_parse: Arc<Mutex<String>>,
+    output_pub: Arc<Mutex<String>>,
+    reader: Box<dyn std::io::Read + Send>,
+    mut on_line: impl FnMut(&str),
+) {
+    let mut stdout = std::io::BufReader::new(reader);
+    let mut buf = Vec::new();
+    let mut guard = FloodGuard::new(Instant::now());
+
+    while read_line(&mut stdout, &mut buf) {
+        let line = String::from_utf8_lossy(&buf);
+        //			println!("{}", line); // For debugging.
+        let plain = strip_ansi_escapes::strip_str(&*line);
+        on_line(&plain);
+        let keep = guard.keep(Instant::now(), buf.len() + 1);
+        if let Some(skipped) = guard.take_report() {
+            warn!(
+                "{} PTY | Output flood, left {} lines out of the console",
+                name, skipped
+            );
+            SKIPPED.fetch_add(skipped, Ordering::Relaxed);
+            let mut output = output_pub.lock().unwrap();
+            if let Err(e) = writeln!(
+                output,
+                "[{} is flooding its output, {} lines were left out of this console]",
+                name, skipped
+            ) {
+                error!("{} PTY Pub | Output error: {}", name, e);
+            }
+        }
+        for (output, line, kind) in [
+            (&output_parse, &*plain, "Parse"),
+            (&output_pub, &*line, "Pub"),
+        ] {
+            if !keep && kind == "Pub" {
+                continue;
+            }
+            let mut output = output.lock().unwrap();
+            if let Err(e) = writeln!(output, "{}", line) {
+                error!("{} PTY {} | Output error: {}", name, kind, e);
+            }
+            let trimmed = trim_front(&mut output, pending_cap());
+            if trimmed != 0 {
+                warn!(
+                    "{} PTY {} | Unread output over the cap, dropped {} bytes",
+                    name, kind, trimmed
+                );
+                TRIMMED.fetch_add(trimmed as u64, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+// Read the next line into [buf] (without the newline), at most [MAX_LINE_BYTES]
+// of it. Returns [false] once the PTY is closed.
+fn read_line(reader: &mut impl std::io::BufRead, buf: &mut Vec<u8>) -> bool {
+    use std::io::{BufRead, Read};
+    buf.clear();
+    match reader
+        .by_ref()
+        .take(MAX_LINE_BYTES as u64)
+        .read_until(b'\n', buf)
+    {
+        Ok(0) | Err(_) => return false,
+        Ok(_) => (),
+    }
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+    } else if buf.len() == MAX_LINE_BYTES {
+        // Skip the rest of the line.
+        let mut rest = Vec::new();
+        loop {
+            rest.clear();
+            match reader
+                .by_ref()
+                .take(MAX_LINE_BYTES as u64)
+                .read_until(b'\n', &mut rest)
+            {
+                Ok(0) | Err(_) => break,
+                Ok(_) if rest.last() == Some(&b'\n') => break,
+                Ok(_) => (),
+            }
+        }
+    }
+    if buf.last() == Some(&b'\r') {
+        buf.pop();
+    }
+    true
+}
+
+// Reset output if larger than max bytes.
+// This will also append a message (between [separator]s) showing it was reset.
+pub fn check_reset_gui_output(output: &mut String, name: ProcessName, separator: &str) {
+    let len = output.len();
+    let max = console_cap();
+    if len > max - 1000 {
+        info!(
+            "{} Watchdog | Output is nearing {} bytes, resetting!",
+            name, max
+        );
+        let text = format!(
+            "{}\n{} GUI log is exceeding the maximum: {} bytes!\nResetting the logs...\n{}\n\n\n\n",
+            separator, name, max, separator
+        );
+        output.clear();
+        output.push_str(&text);
+        debug!("{} Watchdog | Resetting GUI output ... OK", name);
+    } else {
+        debug!(
+            "{} Watchdog | GUI output reset not needed! Current byte length ... {}",
+            name, len
+        );
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reset_gui_output() {
+        let max = GUI_OUTPUT_LEEWAY;
+        let mut string = String::with_capacity(max);
+        for _ in 0..=max {
+            string.push('0');
+        }
+        check_reset_gui_output(&mut string, ProcessName::P2pool, "---");
+        // Some text gets added, so just check for less than 500 bytes.
+        assert!(string.len() < 500);
+    }
+
+    #[test]
+    fn trim_front_whole_lines() {
+        let mut string = "one\ntwo\nthree\n".to_string();
+        assert_eq!(trim_front(&mut string, 100), 0);
+        assert_eq!(trim_front(&mut string, 8), 8);
+        assert_eq!(string, "three\n");
+        let mut string = "no newline".to_string();
+        assert_eq!(trim_front(&mut string, 4), 10);
+        assert!(string.is_empty());
+    }
+
+    #[test]
+    fn flood_guard() {
+        let now = Instant::now();
+        let mut guard = FloodGuard::new(now);
+        // Normal output.
+        assert!((0..1000).all(|_| guard.keep(now, 100)));
+        // Past [FLOOD_BYTES], 1 in [FLOOD_KEEP_EVERY] lines is kept.
+        assert!(guard.keep(now, FLOOD_BYTES));
+        let kept = (0..1000).filter(|_| guard.keep(now, 100)).count() as u64;
+        assert_eq!(kept, 1000 / FLOOD_KEEP_EVERY);
+        assert_eq!(guard.take_report(), None);
+        // Still a flood in the next window, the last one's skipped lines get reported once.
+        let next = now + FLOOD_WINDOW;
+        assert!(!guard.keep(next, 100));
+        assert_eq!(guard.take_report(), Some(1000 - kept));
+        assert_eq!(guard.take_report(), None);
+        // A quiet window ends it.
+        let later = next + FLOOD_WINDOW;
+        assert!((0..100).all(|_| guard.keep(later, 100)));
+        assert_eq!(guard.take_report(), Some(1));
+    }
+
+    #[test]
+    fn read_pty_cuts_long_lines() {
+        let output_parse = Arc::new(Mutex::new(String::new()));
+        let output_pub = Arc::new(Mutex::new(String::new()));
+        let mut input = format!("{}\r\nnext\n", "a".repeat(MAX_LINE_BYTES * 3)).into_bytes();
+        input.extend_from_slice(b"\xff\n");
+        let reader = Box::new(std::io::Cursor::new(input));
+        let mut lines = vec![];
+        read_pty(
+            ProcessName::P2pool,
+            Arc::clone(&output_parse),
+            Arc::clone(&output_pub),
+            reader,
+            |line| lines.push(line.to_string()),
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].len(), MAX_LINE_BYTES);
+        assert_eq!(lines[1], "next");
+        assert_eq!(lines[2], "\u{fffd}");
+    }
+
+    #[test]
+    fn read_pty_strips_ansi() {
+        let output_parse = Arc::new(Mutex::new(String::new()));
+        let output_pub = Arc::new(Mutex::new(String::new()));
+        let reader = Box::new(std::io::Cursor::new("\x1b[32mgreen\x1b[0m\nplain\n"));
+        let mut lines = vec![];
+        read_pty(
+            ProcessName::Xmrig,
+            Arc::clone(&output_parse),
+            Arc::clone(&output_pub),
+            reader,
+            |line| lines.push(line.to_string()),
+        );
+        assert_eq!(lines, ["green", "plain"]);
+        assert_eq!(*output_parse.lock().unwrap(), "green\nplain\n");
+        assert_eq!(*output_pub.lock().unwrap(), "\x1b[32mgreen\x1b[0m\nplain\n");
+    }
+}
@@ -0,0 +1,220 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//---------------------------------------------------------------------------------------------------- Use
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+//---------------------------------------------------------------------------------------------------- [ManagedProcess]
+// What makes P2Pool a P2Pool and XMRig an XMRig, as far as the
+// generic process state goes. Implemented on marker structs in [helper.rs].
+pub trait ManagedProcess {
+    const NAME: ProcessName;
+    // The state right after spawning, before the output says otherwise.
+    const START_STATE: ProcessState;
+
+    // Given new (ANSI stripped) output and the current
+    // state, return the new state if the output changes it.
+    fn state_from_output(output: &str, state: ProcessState) -> Option<ProcessState>;
+}
+
+//---------------------------------------------------------------------------------------------------- [Process] Struct
+// This holds all the state of a (child) process.
+// The main GUI thread will use this to display console text, online state, etc.
+#[derive(Debug)]
+pub struct Process {
+    pub name: ProcessName,     // P2Pool or XMRig?
+    pub state: ProcessState,   // The state of the process (alive, dead, etc)
+    pub signal: ProcessSignal, // Did the user click [Start/Stop/Restart]?
+    // STDIN Problem:
+    //     - User can input many many commands in 1 second
+    //     - The process loop only processes every 1 second
+    //     - If there is only 1 [String] holding the user input,
+    //       the user could overwrite their last input before
+    //       the loop even has a chance to process their last command
+    // STDIN Solution:
+    //     - When the user inputs something, push it to a [Vec]
+    //     - In the process loop, loop over every [Vec] element and
+    //       send each one individually to the process stdin
+    //
+    pub input: Vec<String>,
+
+    // This is the process's private output [String], used by both [Simple] and [Advanced].
+    // "parse" contains the output that will be parsed, then tossed out. "pub" will be written to
+    // the same as parse, but it will be [swap()]'d by the "helper" thread into the GUIs [String].
+    // The "helper" thread synchronizes this swap so that the data in here is moved there
+    // roughly once a second. GUI thread never touches this.
+    pub output_parse: Arc<Mutex<String>>,
+    pub output_pub: Arc<Mutex<String>>,
+
+    // Start time of process.
+    pub start: Instant,
+
+    // Why it last exited, if it wasn't asked to (e.g: [Killed by the OOM killer]).
+    pub exit: String,
+
+    // The arguments it was last spawned with, empty if Gupax didn't spawn it.
+    pub args: Vec<String>,
+}
+
+//---------------------------------------------------------------------------------------------------- [Process] Impl
+impl Process {
+    pub fn new(name: ProcessName) -> Self {
+        Self {
+            name,
+            state: ProcessState::Dead,
+            signal: ProcessSignal::None,
+            start: Instant::now(),
+            output_parse: Arc::new(Mutex::new(String::with_capacity(500))),
+            output_pub: Arc::new(Mutex::new(String::with_capacity(500))),
+            input: vec![String::new()],
+            exit: String::new(),
+            args: vec![],
+        }
+    }
+
+    // Borrow a [&str], return an owned split collection
+    #[inline]
+    pub fn parse_args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|s| s.to_owned()).collect()
+    }
+
+    // Reset the state for a freshly spawned [M].
+    pub fn spawned<M: ManagedProcess>(&mut self) {
+        self.state = M::START_STATE;
+        self.signal = ProcessSignal::None;
+        self.start = Instant::now();
+        self.exit.clear();
+        self.args.clear();
+    }
+
+    // Update the state from new output, see [ManagedProcess::state_from_output()].
+    pub fn update_state<M: ManagedProcess>(&mut self, output: &str) {
+        if let Some(state) = M::state_from_output(output, self.state) {
+            self.state = state;
+        }
+    }
+
+    #[inline]
+    // Convenience functions
+    pub fn is_alive(&self) -> bool {
+        self.state == ProcessState::Alive
+            || self.state == ProcessState::Middle
+            || self.state == ProcessState::Syncing
+            || self.state == ProcessState::NotMining
+    }
+
+    #[inline]
+    pub fn is_waiting(&self) -> bool {
+        self.state == ProcessState::Middle || self.state == ProcessState::Waiting
+    }
+
+    #[inline]
+    pub fn is_syncing(&self) -> bool {
+        self.state == ProcessState::Syncing
+    }
+
+    #[inline]
+    pub fn is_not_mining(&self) -> bool {
+        self.state == ProcessState::NotMining
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Process*] Enum
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ProcessState {
+    Alive, // Process is online, GREEN!
+    #[default]
+    Dead, // Process is dead, BLACK!
+    Failed, // Process is dead AND exited with a bad code, RED!
+    Middle, // Process is in the middle of something ([re]starting/stopping), YELLOW!
+    Waiting, // Process was successfully killed by a restart, and is ready to be started again, YELLOW!
+
+    // Only for P2Pool, ORANGE.
+    Syncing,
+
+    // Only for XMRig (and XMRig-Proxy), ORANGE.
+    NotMining,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ProcessSignal {
+    #[default]
+    None,
+    Start,
+    Stop,
+    Restart,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ProcessName {
+    P2pool,
+    Xmrig,
+    Proxy,
+}
+
+impl std::fmt::Display for ProcessState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+impl std::fmt::Display for ProcessSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+impl std::fmt::Display for ProcessName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ProcessName::P2pool => write!(f, "P2Pool"),
+            ProcessName::Xmrig => write!(f, "XMRig"),
+            ProcessName::Proxy => write!(f, "XMRig-Proxy"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Stub;
+    impl ManagedProcess for Stub {
+        const NAME: ProcessName = ProcessName::P2pool;
+        const START_STATE: ProcessState = ProcessState::Syncing;
+        fn state_from_output(output: &str, _: ProcessState) -> Option<ProcessState> {
+            output.contains("ready").then_some(ProcessState::Alive)
+        }
+    }
+
+    #[test]
+    fn managed_process_state() {
+        let mut process = Process::new(Stub::NAME);
+        assert_eq!(process.state, ProcessState::Dead);
+        process.signal = ProcessSignal::Start;
+        process.spawned::<Stub>();
+        assert_eq!(process.state, ProcessState::Syncing);
+        assert_eq!(process.signal, ProcessSignal::None);
+        assert!(process.is_alive() && process.is_syncing());
+        process.update_state::<Stub>("still loading");
+        assert_eq!(process.state, ProcessState::Syncing);
+        process.update_state::<Stub>("ready");
+        assert_eq!(process.state, ProcessState::Alive);
+    }
+}
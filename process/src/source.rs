@@ -0,0 +1,204 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//---------------------------------------------------------------------------------------------------- Use
+use crate::ProcessName;
+use log::*;
+use serde::de::DeserializeOwned;
+use std::{marker::PhantomData, path::PathBuf, time::Duration};
+
+//---------------------------------------------------------------------------------------------------- [StatsSource]
+// Somewhere a process's stats can be read from, e.g: P2Pool's API files.
+pub trait StatsSource {
+    type Stats;
+    type Error;
+
+    fn fetch(&mut self) -> Result<Self::Stats, Self::Error>;
+}
+
+//---------------------------------------------------------------------------------------------------- [JsonFile]
+// A JSON file the process (re)writes, deserialized into [T] on every [fetch()].
+#[derive(Debug, Clone)]
+pub struct JsonFile<T> {
+    name: ProcessName,
+    path: PathBuf,
+    _stats: PhantomData<T>,
+}
+
+impl<T> JsonFile<T> {
+    pub const fn new(name: ProcessName, path: PathBuf) -> Self {
+        Self {
+            name,
+            path,
+            _stats: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum JsonFileError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for JsonFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Json(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> StatsSource for JsonFile<T> {
+    type Stats = T;
+    type Error = JsonFileError;
+
+    fn fetch(&mut self) -> Result<T, JsonFileError> {
+        let string = std::fs::read_to_string(&self.path).map_err(|e| {
+            warn!(
+                "{} API | [{}] read error: {}",
+                self.name,
+                self.path.display(),
+                e
+            );
+            JsonFileError::Io(e)
+        })?;
+        serde_json::from_str(&string).map_err(|e| {
+            warn!(
+                "{} API | [{}] could not deserialize API data: {}",
+                self.name,
+                self.path.display(),
+                e
+            );
+            JsonFileError::Json(e)
+        })
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [HttpJson]
+type Client = hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>;
+
+// An HTTP API (e.g: XMRig's [summary]), a GET is sent and deserialized into [T] on every [fetch()].
+// The watchdogs aren't async, so it brings its own (single thread) tokio runtime.
+pub struct HttpJson<T> {
+    uri: String,
+    authorization: Option<String>,
+    // Plain HTTP for a local XMRig, HTTPS for ones behind a reverse proxy (watch-only).
+    client: Client,
+    runtime: tokio::runtime::Runtime,
+    _stats: PhantomData<T>,
+}
+
+impl<T> HttpJson<T> {
+    pub fn new(uri: String) -> Self {
+        Self {
+            uri,
+            authorization: None,
+            client: hyper::Client::builder().build(hyper_tls::HttpsConnector::new()),
+            runtime: tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+            _stats: PhantomData,
+        }
+    }
+
+    // Send [token] with every request, see [authorization()].
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.authorization = authorization(token);
+        self
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    async fn request(&self) -> anyhow::Result<hyper::body::Bytes> {
+        let mut request = hyper::Request::builder().method("GET").uri(&self.uri);
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let request = request.body(hyper::Body::empty())?;
+        // A TLS handshake with a remote host takes longer than a local request.
+        let timeout = if self.uri.starts_with("https://") {
+            2000
+        } else {
+            500
+        };
+        let response =
+            tokio::time::timeout(Duration::from_millis(timeout), self.client.request(request))
+                .await?;
+        Ok(hyper::body::to_bytes(response?.into_body()).await?)
+    }
+}
+
+impl<T: DeserializeOwned> StatsSource for HttpJson<T> {
+    type Stats = T;
+    type Error = anyhow::Error;
+
+    fn fetch(&mut self) -> anyhow::Result<T> {
+        let body = self.runtime.block_on(self.request())?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+// The [Authorization] header for [token]: a bare token is XMRig's
+// [access-token] (Bearer), anything with a scheme (e.g: [Basic dXNlcjpwYXNz])
+// is sent as is, for reverse proxies with their own auth.
+fn authorization(token: &str) -> Option<String> {
+    let token = token.trim();
+    if token.is_empty() {
+        None
+    } else if token.contains(char::is_whitespace) {
+        Some(token.to_string())
+    } else {
+        Some(format!("Bearer {}", token))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("json_file");
+        let mut source = JsonFile::<Vec<u32>>::new(ProcessName::P2pool, path.clone());
+        assert!(matches!(source.fetch(), Err(JsonFileError::Io(_))));
+        std::fs::write(&path, "[1, 2").unwrap();
+        assert!(matches!(source.fetch(), Err(JsonFileError::Json(_))));
+        std::fs::write(&path, "[1, 2, 3]").unwrap();
+        assert_eq!(source.fetch().unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn authorization() {
+        assert_eq!(super::authorization(""), None);
+        assert_eq!(super::authorization("  "), None);
+        assert_eq!(
+            super::authorization(" secret "),
+            Some("Bearer secret".to_string())
+        );
+        assert_eq!(
+            super::authorization("Basic dXNlcjpwYXNz"),
+            Some("Basic dXNlcjpwYXNz".to_string())
+        );
+    }
+}
@@ -0,0 +1,397 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The watchdog every spawned process gets.
+//
+// [Pty::spawn()] starts the process inside a PTY (so STDIN actually works),
+// [watch()] reads its output in another thread and loops every 900ms until it
+// exits or is told to [Stop/Restart], and [restart()] waits for it to be
+// gone before starting a new one. What's left to P2Pool/XMRig/XMRig-Proxy
+// is the [Watchdog] trait: what to do with the output, APIs and STDIN.
+
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{read_pty, ExitCause, ManagedProcess, Process, ProcessSignal, ProcessState};
+use log::*;
+use portable_pty::{Child, CommandBuilder, MasterPty, PtySize};
+use std::{
+    io::{Read, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How often the watchdog loops.
+const LOOP: Duration = Duration::from_millis(900);
+
+//---------------------------------------------------------------------------------------------------- [Pty]
+// A child process spawned inside a PTY.
+pub struct Pty {
+    child: Box<dyn Child + Send + Sync>,
+    // The reader/writer are clones of this, it's held as long as the child.
+    _master: Box<dyn MasterPty + Send>,
+    reader: Option<Box<dyn Read + Send>>,
+    stdin: Box<dyn Write + Send>,
+    pid: Option<u32>,
+    // To tell an OOM kill from any other [SIGKILL] if it dies on its own.
+    oom_kills: Option<u64>,
+}
+
+impl Pty {
+    // Spawn [cmd], unless [path] disappeared since it was validated.
+    pub fn spawn(cmd: CommandBuilder, path: &Path) -> anyhow::Result<Self> {
+        let pair = portable_pty::native_pty_system().openpty(PtySize {
+            rows: 100,
+            cols: 1000,
+            pixel_width: 0,
+            pixel_height: 0,
+        })?;
+        if !path.exists() {
+            anyhow::bail!("[{}] does not exist", path.display());
+        }
+        let child = pair.slave.spawn_command(cmd)?;
+        drop(pair.slave);
+        let reader = pair.master.try_clone_reader()?; // STDOUT/STDERR
+        let stdin = pair.master.take_writer()?;
+        let pid = child.process_id();
+        Ok(Self {
+            child,
+            _master: pair.master,
+            reader: Some(reader),
+            stdin,
+            pid,
+            oom_kills: crate::oom_kills(),
+        })
+    }
+
+    pub const fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    // Write a line to STDIN and flush it.
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        // Windows terminals (or at least the PTY abstraction I'm using, portable_pty)
+        // requires a [\r\n] to end a line, whereas Unix is okay with just a [\n].
+        //
+        // I have literally read all of [portable_pty]'s source code, dug into Win32 APIs,
+        // even rewrote some of the actual PTY code in order to understand why STDIN doesn't work
+        // on Windows. It's because of a fucking missing [\r]. Another reason to hate Windows :D
+        //
+        // XMRig did actually work before though, since it reads STDIN directly without needing a newline.
+        #[cfg(target_os = "windows")]
+        write!(self.stdin, "{}\r\n", line)?;
+        #[cfg(not(target_os = "windows"))]
+        writeln!(self.stdin, "{}", line)?;
+        self.stdin.flush()
+    }
+
+    // This actually sends a SIGHUP to the process (closes the PTY, hangs up on it).
+    pub fn kill(&mut self) -> std::io::Result<()> {
+        self.child.kill()
+    }
+
+    // Why it exited, if it did.
+    fn exited(&mut self) -> Option<ExitCause> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(ExitCause::new(&status, self.pid, self.oom_kills)),
+            _ => None,
+        }
+    }
+
+    // Wait for it to exit after a [kill()], and return its state and exit status.
+    fn wait(&mut self) -> (ProcessState, &'static str) {
+        match self.child.wait() {
+            Ok(status) if status.success() => (ProcessState::Dead, "Successful"),
+            Ok(_) => (ProcessState::Failed, "Failed"),
+            Err(_) => (ProcessState::Failed, "Unknown Error"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Watchdog]
+// What a process does in its watchdog besides what [watch()] does for all of them.
+pub trait Watchdog {
+    type Managed: ManagedProcess;
+
+    // Every loop, after STDIN is written: update the stats from the output and APIs.
+    // [elapsed] is how long the process has been up.
+    fn tick(&mut self, process: &Arc<Mutex<Process>>, elapsed: Duration);
+
+    // A line of user input, right before it's written to STDIN.
+    fn input(&mut self, _line: &str) {}
+
+    // Kill the process on a [Stop/Restart] signal.
+    fn kill(&mut self, pty: &mut Pty, _signal: ProcessSignal) -> std::io::Result<()> {
+        pty.kill()
+    }
+
+    // It exited (or was killed), the GUI should know about it.
+    // [hint] is what the user could do about it, if there's anything to say.
+    fn stopped(&mut self, uptime: Duration, exit_status: &str, hint: Option<&str>);
+}
+
+//---------------------------------------------------------------------------------------------------- [watch()]
+// Set the process state for [W], spawn the PTY read thread
+// ([on_line] gets every ANSI stripped line), then loop as watchdog.
+pub fn watch<W: Watchdog>(
+    process: &Arc<Mutex<Process>>,
+    mut pty: Pty,
+    args: Vec<String>,
+    on_line: impl FnMut(&str) + Send + 'static,
+    mut watchdog: W,
+) {
+    let name = W::Managed::NAME;
+
+    // 1. Set process state
+    debug!("{} | Setting process state...", name);
+    let (output_parse, output_pub) = {
+        let mut lock = process.lock().unwrap();
+        lock.spawned::<W::Managed>();
+        lock.args = args;
+        (Arc::clone(&lock.output_parse), Arc::clone(&lock.output_pub))
+    };
+
+    // 2. Spawn PTY read thread
+    debug!("{} | Spawning PTY read thread...", name);
+    if let Some(reader) = pty.reader.take() {
+        thread::spawn(move || read_pty(name, output_parse, output_pub, reader, on_line));
+    }
+
+    // 3. Loop as watchdog
+    info!("{} | Entering watchdog mode... woof!", name);
+    loop {
+        // Set timer
+        let now = Instant::now();
+        debug!("{} Watchdog | ----------- Start of loop -----------", name);
+        // The helper moves this forward after a system sleep.
+        let start = process.lock().unwrap().start;
+
+        // Check if the process secretly died without us knowing :)
+        if let Some(cause) = pty.exited() {
+            debug!(
+                "{} Watchdog | Process secretly died! Getting exit status",
+                name
+            );
+            let exit_status = cause.to_string();
+            {
+                let mut lock = process.lock().unwrap();
+                if cause.success() {
+                    lock.state = ProcessState::Dead;
+                } else {
+                    lock.state = ProcessState::Failed;
+                    lock.exit = exit_status.clone();
+                }
+            }
+            watchdog.stopped(start.elapsed(), &exit_status, cause.hint());
+            process.lock().unwrap().signal = ProcessSignal::None;
+            debug!("{} Watchdog | Secret dead process reap OK, breaking", name);
+            break;
+        }
+
+        // Stop on [Stop/Restart] SIGNAL
+        let signal = process.lock().unwrap().signal;
+        if signal == ProcessSignal::Stop || signal == ProcessSignal::Restart {
+            debug!("{} Watchdog | {} SIGNAL caught", name, signal);
+            if let Err(e) = watchdog.kill(&mut pty, signal) {
+                error!("{} Watchdog | Kill error: {}", name, e);
+            }
+            let (state, exit_status) = pty.wait();
+            watchdog.stopped(start.elapsed(), exit_status, None);
+            let mut lock = process.lock().unwrap();
+            if signal == ProcessSignal::Stop {
+                lock.state = state;
+                lock.signal = ProcessSignal::None;
+            } else {
+                lock.state = ProcessState::Waiting;
+            }
+            debug!("{} Watchdog | {} SIGNAL done, breaking", name, signal);
+            break;
+        }
+
+        // Check vector of user input
+        let input = std::mem::take(&mut process.lock().unwrap().input);
+        for line in input {
+            if line.is_empty() {
+                continue;
+            }
+            debug!(
+                "{} Watchdog | User input not empty, writing to STDIN: [{}]",
+                name, line
+            );
+            watchdog.input(&line);
+            if let Err(e) = pty.write_line(&line) {
+                error!("{} Watchdog | STDIN error: {}", name, e);
+            }
+        }
+
+        watchdog.tick(process, start.elapsed());
+
+        // Sleep (only if 900ms hasn't passed)
+        let elapsed = now.elapsed();
+        if elapsed < LOOP {
+            debug!(
+                "{} Watchdog | END OF LOOP - Sleeping for [{}]ms...",
+                name,
+                (LOOP - elapsed).as_millis()
+            );
+            thread::sleep(LOOP - elapsed);
+        } else {
+            debug!("{} Watchdog | END OF LOOP - Not sleeping!", name);
+        }
+    }
+
+    // 4. If loop broke, we must be done here.
+    info!("{} Watchdog | Watchdog thread exiting... Goodbye!", name);
+}
+
+//---------------------------------------------------------------------------------------------------- [restart()]
+// Tell the watchdog to [Restart], then call [start]
+// from a new thread once the old process is gone.
+pub fn restart(process: &Arc<Mutex<Process>>, start: impl FnOnce() + Send + 'static) {
+    let name = {
+        let mut lock = process.lock().unwrap();
+        lock.signal = ProcessSignal::Restart;
+        lock.state = ProcessState::Middle;
+        lock.name
+    };
+    info!("{} | Attempting to restart...", name);
+
+    let process = Arc::clone(process);
+    // This thread lives to wait, start the new process then die.
+    thread::spawn(move || {
+        while process.lock().unwrap().is_alive() {
+            warn!(
+                "{} | Want to restart but process is still alive, waiting...",
+                name
+            );
+            thread::sleep(Duration::from_secs(1));
+        }
+        // Ok, process is not alive, start the new one!
+        info!("{} | Old process seems dead, starting new one!", name);
+        start();
+    });
+    info!("{} | Restart ... OK", name);
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+    use crate::ProcessName;
+
+    struct Sh;
+    impl ManagedProcess for Sh {
+        const NAME: ProcessName = ProcessName::P2pool;
+        const START_STATE: ProcessState = ProcessState::Syncing;
+        fn state_from_output(output: &str, _: ProcessState) -> Option<ProcessState> {
+            output.contains("ready").then_some(ProcessState::Alive)
+        }
+    }
+
+    #[derive(Default)]
+    struct Stub {
+        input: Arc<Mutex<Vec<String>>>,
+        stopped: Arc<Mutex<Vec<String>>>,
+    }
+    impl Watchdog for Stub {
+        type Managed = Sh;
+        fn tick(&mut self, process: &Arc<Mutex<Process>>, _: Duration) {
+            let mut lock = process.lock().unwrap();
+            let output = std::mem::take(&mut *lock.output_parse.lock().unwrap());
+            lock.update_state::<Sh>(&output);
+        }
+        fn input(&mut self, line: &str) {
+            self.input.lock().unwrap().push(line.to_string());
+        }
+        fn stopped(&mut self, _: Duration, exit_status: &str, _: Option<&str>) {
+            self.stopped.lock().unwrap().push(exit_status.to_string());
+        }
+    }
+
+    // Polls [f] for up to 10 seconds.
+    fn wait_for(what: &str, f: impl Fn() -> bool) {
+        let now = Instant::now();
+        while !f() {
+            assert!(
+                now.elapsed() < Duration::from_secs(10),
+                "timed out waiting for: {}",
+                what
+            );
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    // Spawn [sh -c script] under a [Stub] watchdog in another thread.
+    fn spawn(script: &str) -> (Arc<Mutex<Process>>, Stub) {
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.args(["-c", script]);
+        let pty = Pty::spawn(cmd, Path::new("/bin/sh")).unwrap();
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::P2pool)));
+        let stub = Stub::default();
+        let watchdog = Stub {
+            input: Arc::clone(&stub.input),
+            stopped: Arc::clone(&stub.stopped),
+        };
+        let p = Arc::clone(&process);
+        thread::spawn(move || watch(&p, pty, vec!["-c".into()], |_| {}, watchdog));
+        (process, stub)
+    }
+
+    #[test]
+    fn spawn_missing_path() {
+        let err = Pty::spawn(
+            CommandBuilder::new("sh"),
+            Path::new("/gupax/does/not/exist"),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn watch_exit() {
+        let (process, stub) = spawn("echo ready; read line; exit 3");
+        wait_for("ready", || {
+            process.lock().unwrap().state == ProcessState::Alive
+        });
+        assert_eq!(process.lock().unwrap().args, ["-c"]);
+        process.lock().unwrap().input.push("bye".to_string());
+        wait_for("exit", || {
+            process.lock().unwrap().state == ProcessState::Failed
+        });
+        let lock = process.lock().unwrap();
+        assert_eq!(lock.exit, "Exited with code 3");
+        assert_eq!(lock.signal, ProcessSignal::None);
+        assert_eq!(*stub.input.lock().unwrap(), ["bye"]);
+        assert_eq!(*stub.stopped.lock().unwrap(), ["Exited with code 3"]);
+    }
+
+    #[test]
+    fn watch_restart() {
+        let (process, stub) = spawn("echo ready; sleep 60");
+        wait_for("ready", || {
+            process.lock().unwrap().state == ProcessState::Alive
+        });
+        let started = Arc::new(Mutex::new(false));
+        let s = Arc::clone(&started);
+        restart(&process, move || *s.lock().unwrap() = true);
+        assert_eq!(process.lock().unwrap().state, ProcessState::Middle);
+        wait_for("restart", || *started.lock().unwrap());
+        assert_eq!(process.lock().unwrap().state, ProcessState::Waiting);
+        assert_eq!(stub.stopped.lock().unwrap().len(), 1);
+    }
+}
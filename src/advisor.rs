@@ -0,0 +1,365 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// P2Pool Main vs Mini advisor.
+//
+// Both sidechains pay the same on average for the same hashrate, what
+// differs is how often a share lands in the PPLNS window. Without a share
+// in the window a found block pays nothing, so a small miner on Main sees
+// long dry spells that Mini would have smoothed out. This fetches both
+// sidechain difficulties from p2pool.observer, works out the expected
+// shares per window & day for the user's hashrate and recommends a chain.
+// It's re-evaluated every [INTERVAL] while mining.
+
+use crate::macros::*;
+use hyper::{Body, Request};
+use log::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Sidechain difficulty moves slowly, once an hour is plenty.
+pub const INTERVAL: Duration = Duration::from_secs(60 * 60);
+const TIMEOUT: Duration = Duration::from_secs(15);
+const MAIN_URL: &str = "https://p2pool.observer/api/pool_info";
+const MINI_URL: &str = "https://mini.p2pool.observer/api/pool_info";
+// Both sidechains use these, only used if the API doesn't say.
+const BLOCK_TIME: f64 = 10.0;
+const WINDOW: f64 = 2160.0;
+// Expected shares in the Main window needed to recommend Main. 3 shares
+// means a ~95% chance of always having one in the window. Once on Main,
+// stay until it drops below [MAIN_STAY] so the advice doesn't flap.
+pub const MAIN_MIN: f64 = 3.0;
+pub const MAIN_STAY: f64 = 2.0;
+const DAY: f64 = 86_400.0;
+
+//---------------------------------------------------------------------------------------------------- Sidechain
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sidechain {
+    Main,
+    Mini,
+}
+
+impl Sidechain {
+    pub const fn from_mini(mini: bool) -> Self {
+        if mini {
+            Self::Mini
+        } else {
+            Self::Main
+        }
+    }
+
+    pub const fn is_mini(&self) -> bool {
+        matches!(self, Self::Mini)
+    }
+}
+
+impl std::fmt::Display for Sidechain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Main => write!(f, "P2Pool Main"),
+            Self::Mini => write!(f, "P2Pool Mini"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Chain
+// One sidechain's current stats.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chain {
+    pub difficulty: f64,
+    pub block_time: f64, // Seconds per share
+    pub window: f64,     // PPLNS window in shares
+}
+
+impl Chain {
+    // The sidechain's total hashrate (H/s).
+    pub fn hashrate(&self) -> f64 {
+        self.difficulty / self.block_time
+    }
+
+    // Out of p2pool.observer's [/api/pool_info]. Difficulties are 128-bit
+    // so they may come as a (hex) string instead of a number.
+    pub fn parse(json: &serde_json::Value) -> Option<Self> {
+        let sidechain = json.get("sidechain")?;
+        let difficulty = match sidechain.get("difficulty")? {
+            serde_json::Value::Number(n) => n.as_f64()?,
+            serde_json::Value::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => u128::from_str_radix(hex, 16).ok()? as f64,
+                None => s
+                    .parse::<u128>()
+                    .or_else(|_| u128::from_str_radix(s, 16))
+                    .ok()? as f64,
+            },
+            _ => return None,
+        };
+        let consensus = sidechain.get("consensus");
+        let field = |consensus_key: &str, key: &str| {
+            consensus
+                .and_then(|c| c.get(consensus_key))
+                .or_else(|| sidechain.get(key))
+                .and_then(|v| v.as_f64())
+                .filter(|f| *f > 0.0)
+        };
+        let block_time = field("target_block_time", "block_time").unwrap_or(BLOCK_TIME);
+        let window = field("chain_window_size", "window_size").unwrap_or(WINDOW);
+        (difficulty > 0.0).then_some(Self {
+            difficulty,
+            block_time,
+            window,
+        })
+    }
+
+    // What [hashrate] (H/s) can expect on this sidechain.
+    pub fn estimate(&self, hashrate: f64) -> Estimate {
+        let shares_per_day = hashrate * DAY / self.difficulty;
+        let window_shares = hashrate / self.hashrate() * self.window;
+        Estimate {
+            shares_per_day,
+            window_shares,
+            // Poisson, the chance of at least one share in the window.
+            window_chance: 1.0 - (-window_shares).exp(),
+            // Poisson, the daily payout's standard deviation relative to its mean.
+            variance: if shares_per_day > 0.0 {
+                1.0 / shares_per_day.sqrt()
+            } else {
+                f64::INFINITY
+            },
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Estimate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub shares_per_day: f64,
+    pub window_shares: f64,
+    pub window_chance: f64, // 0.0..=1.0
+    pub variance: f64,      // Coefficient of variation of daily payouts
+}
+
+//---------------------------------------------------------------------------------------------------- Advice
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Advice {
+    pub pick: Sidechain,
+    pub main: Estimate,
+    pub mini: Estimate,
+}
+
+impl Advice {
+    // Recommend Main only if its window reliably holds a share, [current]
+    // adds some hysteresis so borderline hashrates don't flip every hour.
+    pub fn new(hashrate: f64, main: &Chain, mini: &Chain, current: Sidechain) -> Self {
+        let main = main.estimate(hashrate);
+        let mini = mini.estimate(hashrate);
+        let needed = match current {
+            Sidechain::Main => MAIN_STAY,
+            Sidechain::Mini => MAIN_MIN,
+        };
+        let pick = if main.window_shares >= needed {
+            Sidechain::Main
+        } else {
+            Sidechain::Mini
+        };
+        Self { pick, main, mini }
+    }
+
+    // One line for the [P2Pool] tab.
+    pub fn summary(&self) -> String {
+        format!(
+            "Advice: {} | Main: {:.1} shares/window ({:.0}%) | Mini: {:.1} ({:.0}%)",
+            self.pick,
+            self.main.window_shares,
+            self.main.window_chance * 100.0,
+            self.mini.window_shares,
+            self.mini.window_chance * 100.0,
+        )
+    }
+
+    // The full numbers, for the hover text.
+    pub fn details(&self) -> String {
+        let line = |name: &str, e: &Estimate| {
+            format!(
+                "{}: {:.2} shares/day, {:.2} shares in the PPLNS window ({:.1}% chance of at least one), daily payout variance ±{:.0}%",
+                name,
+                e.shares_per_day,
+                e.window_shares,
+                e.window_chance * 100.0,
+                (e.variance * 100.0).min(999.0),
+            )
+        };
+        format!("{}\n{}", line("Main", &self.main), line("Mini", &self.mini))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Advisor
+#[derive(Debug)]
+pub struct Advisor {
+    pub main: Option<Chain>,
+    pub mini: Option<Chain>,
+    pub hashrate: f64, // The user's hashrate (H/s), kept up to date by [App]
+    pub error: String,
+    pub fetching: bool,
+    pub last: Option<Instant>,
+}
+
+impl Default for Advisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Advisor {
+    pub const fn new() -> Self {
+        Self {
+            main: None,
+            mini: None,
+            hashrate: 0.0,
+            error: String::new(),
+            fetching: false,
+            last: None,
+        }
+    }
+
+    pub fn due(&self) -> bool {
+        !self.fetching && self.last.is_none_or(|last| last.elapsed() >= INTERVAL)
+    }
+
+    // [None] until both sidechains and the user's hashrate are known.
+    pub fn advice(&self, current: Sidechain) -> Option<Advice> {
+        if self.hashrate <= 0.0 {
+            return None;
+        }
+        Some(Advice::new(
+            self.hashrate,
+            self.main.as_ref()?,
+            self.mini.as_ref()?,
+            current,
+        ))
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(advisor: &Arc<Mutex<Self>>) {
+        let advisor = Arc::clone(advisor);
+        {
+            let mut lock = lock!(advisor);
+            lock.fetching = true;
+            lock.last = Some(Instant::now());
+        }
+        std::thread::spawn(move || {
+            let get = |url: &str| {
+                Self::fetch(url).and_then(|json| {
+                    Chain::parse(&json)
+                        .ok_or_else(|| anyhow::anyhow!("unexpected response from [{}]", url))
+                })
+            };
+            let main = get(MAIN_URL);
+            let mini = get(MINI_URL);
+            let mut guard = lock!(advisor);
+            let lock = &mut *guard;
+            lock.fetching = false;
+            lock.error.clear();
+            for (name, result, chain) in [
+                ("Main", main, &mut lock.main),
+                ("Mini", mini, &mut lock.mini),
+            ] {
+                match result {
+                    Ok(c) => {
+                        info!("Advisor | [{}] difficulty: {}", name, c.difficulty);
+                        *chain = Some(c);
+                    }
+                    Err(e) => {
+                        warn!("Advisor | [{}] ... FAIL ... {}", name, e);
+                    }
+                }
+            }
+            if lock.main.is_none() || lock.mini.is_none() {
+                lock.error = "Couldn't fetch the sidechain stats from p2pool.observer".to_string();
+            }
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[tokio::main(flavor = "current_thread")]
+    async fn fetch(url: &str) -> Result<serde_json::Value, anyhow::Error> {
+        let client = crate::net::https_client();
+        let request = Request::builder()
+            .method("GET")
+            .uri(url)
+            .header("User-Agent", crate::Pkg::get_user_agent())
+            .body(Body::empty())?;
+        let response = tokio::time::timeout(TIMEOUT, client.request(request))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out"))??;
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse() {
+        use super::Chain;
+        let json = serde_json::json!({"sidechain": {
+            "difficulty": 1_000_000_000u64,
+            "consensus": {"target_block_time": 10, "chain_window_size": 2160},
+        }});
+        let chain = Chain::parse(&json).unwrap();
+        assert_eq!(chain.difficulty, 1_000_000_000.0);
+        assert_eq!(chain.hashrate(), 100_000_000.0);
+        let json = serde_json::json!({"sidechain": {"difficulty": "0x3b9aca00"}});
+        assert_eq!(Chain::parse(&json), Some(chain));
+        let json = serde_json::json!({"sidechain": {"difficulty": "1000000000"}});
+        assert_eq!(Chain::parse(&json), Some(chain));
+        assert_eq!(Chain::parse(&serde_json::json!({"sidechain": {}})), None);
+        assert_eq!(Chain::parse(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn advice() {
+        use super::{Advice, Chain, Sidechain};
+        // ~1 GH/s Main, ~10 MH/s Mini.
+        let main = Chain {
+            difficulty: 10_000_000_000.0,
+            block_time: 10.0,
+            window: 2160.0,
+        };
+        let mini = Chain {
+            difficulty: 100_000_000.0,
+            ..main
+        };
+        // 10 kH/s: 0.0216 shares per Main window, Mini it is.
+        let advice = Advice::new(10_000.0, &main, &mini, Sidechain::Main);
+        assert_eq!(advice.pick, Sidechain::Mini);
+        assert!((advice.mini.window_shares - 2.16).abs() < 1e-9);
+        assert!((advice.mini.shares_per_day - 8.64).abs() < 1e-9);
+        assert!(advice.main.variance > advice.mini.variance);
+        // 2 MH/s: 4.32 shares per Main window.
+        let advice = Advice::new(2_000_000.0, &main, &mini, Sidechain::Mini);
+        assert_eq!(advice.pick, Sidechain::Main);
+        assert!(advice.main.window_chance > 0.95);
+        // 1.2 MH/s: 2.59 shares, enough to stay on Main but not to move there.
+        let advice = Advice::new(1_200_000.0, &main, &mini, Sidechain::Main);
+        assert_eq!(advice.pick, Sidechain::Main);
+        let advice = Advice::new(1_200_000.0, &main, &mini, Sidechain::Mini);
+        assert_eq!(advice.pick, Sidechain::Mini);
+    }
+}
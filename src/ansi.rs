@@ -0,0 +1,382 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// ANSI color rendering for the P2Pool/XMRig consoles.
+//
+// The PTY output is kept raw (with escape codes), this parses it into
+// plain text + colored sections that egui can lay out. Only SGR color
+// codes ([ESC[...m]) are used, every other escape sequence is dropped.
+//
+// The output [String] only ever grows (until it gets reset), so
+// [Console] caches what it parsed and only parses the new bytes.
+
+use crate::constants::*;
+use egui::{text::LayoutJob, Color32, TextEdit, TextFormat, TextStyle};
+use std::ops::Range;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How many bytes of the last parsed output to keep around
+// to detect if the output was reset/replaced.
+const TAIL_LEN: usize = 128;
+
+//---------------------------------------------------------------------------------------------------- [Color]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Color {
+    Index(u8), // [0..=15] basic/bright, [16..=255] xterm 256 colors
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    // The console background is always dark, but
+    // light mode gets darker colors for other backgrounds.
+    pub fn to_color32(self, dark: bool) -> Color32 {
+        match self {
+            Self::Rgb(r, g, b) => Color32::from_rgb(r, g, b),
+            Self::Index(i @ 0..=15) => {
+                if dark {
+                    match i {
+                        0 => GRAY,
+                        1 => RED,
+                        2 => GREEN,
+                        3 => YELLOW,
+                        4 => BLUE,
+                        5 => Color32::from_rgb(200, 120, 255),
+                        6 => Color32::from_rgb(80, 220, 220),
+                        7 => LIGHT_GRAY,
+                        8 => Color32::from_gray(140),
+                        9 => Color32::from_rgb(255, 90, 90),
+                        10 => Color32::from_rgb(140, 255, 140),
+                        11 => BRIGHT_YELLOW,
+                        12 => Color32::from_rgb(140, 200, 255),
+                        13 => Color32::from_rgb(230, 150, 255),
+                        14 => Color32::from_rgb(120, 255, 255),
+                        _ => WHITE,
+                    }
+                } else {
+                    match i {
+                        0 | 8 => Color32::from_gray(60),
+                        1 | 9 => Color32::from_rgb(170, 20, 20),
+                        2 | 10 => Color32::from_rgb(20, 120, 20),
+                        3 | 11 => Color32::from_rgb(140, 110, 0),
+                        4 | 12 => Color32::from_rgb(20, 70, 190),
+                        5 | 13 => Color32::from_rgb(130, 40, 170),
+                        6 | 14 => Color32::from_rgb(0, 120, 130),
+                        _ => BLACK,
+                    }
+                }
+            }
+            // xterm 6x6x6 color cube.
+            Self::Index(i @ 16..=231) => {
+                let i = i - 16;
+                let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+                Color32::from_rgb(level(i / 36), level((i / 6) % 6), level(i % 6))
+            }
+            // Grayscale ramp.
+            Self::Index(i) => Color32::from_gray(8 + (i - 232) * 10),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Console]
+#[derive(Clone, Debug, Default)]
+pub struct Console {
+    raw_len: usize, // How many bytes of the raw output were parsed
+    tail: String,   // The last [TAIL_LEN] parsed bytes
+    plain: String,  // The output without escape codes
+    sections: Vec<(Range<usize>, Option<Color>)>, // Colored byte ranges into [plain]
+    color: Option<Color>, // The current SGR color
+    bold: bool,     // Bold turns basic colors bright
+    pending: String, // An incomplete escape sequence
+    // [Status/Consoles] view state, kept across output resets.
+    pub paused: bool,       // Don't follow new output
+    offset: f32,            // Last seen scroll offset
+    scroll_to: Option<f32>, // Scroll here on the next frame
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The output without escape codes.
+    pub fn plain(&self) -> &str {
+        &self.plain
+    }
+
+    // Parse any new output in [raw].
+    pub fn update(&mut self, raw: &str) {
+        let start = self.raw_len.saturating_sub(self.tail.len());
+        let same = raw.len() >= self.raw_len && raw.get(start..self.raw_len) == Some(&self.tail);
+        if !same {
+            *self = Self {
+                paused: self.paused,
+                offset: self.offset,
+                ..Self::new()
+            };
+        }
+        if raw.len() == self.raw_len {
+            return;
+        }
+        let new = &raw[self.raw_len..];
+        self.parse(new);
+        self.raw_len = raw.len();
+        let mut start = raw.len().saturating_sub(TAIL_LEN);
+        while !raw.is_char_boundary(start) {
+            start += 1;
+        }
+        self.tail = raw[start..].to_string();
+    }
+
+    fn push(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let start = self.plain.len();
+        self.plain.push_str(text);
+        let end = self.plain.len();
+        let color = match self.color {
+            // Bold basic colors render as their bright variant, like most terminals.
+            Some(Color::Index(i @ 0..=7)) if self.bold => Some(Color::Index(i + 8)),
+            c => c,
+        };
+        match self.sections.last_mut() {
+            Some((range, c)) if *c == color && range.end == start => range.end = end,
+            _ => self.sections.push((start..end, color)),
+        }
+    }
+
+    fn parse(&mut self, new: &str) {
+        let mut input = std::mem::take(&mut self.pending);
+        input.push_str(new);
+        let mut rest = input.as_str();
+        while let Some(i) = rest.find('\x1b') {
+            self.push(&rest[..i]);
+            let seq = &rest[i + 1..];
+            match seq.chars().next() {
+                // CSI, e.g: [ESC[1;32m]
+                Some('[') => {
+                    let body = &seq[1..];
+                    match body.find(|c: char| ('\x40'..='\x7e').contains(&c)) {
+                        Some(end) => {
+                            if body.as_bytes()[end] == b'm' {
+                                self.sgr(&body[..end]);
+                            }
+                            rest = &body[end + 1..];
+                        }
+                        None => {
+                            self.pending = rest[i..].to_string();
+                            return;
+                        }
+                    }
+                }
+                // OSC, e.g: [ESC]0;title BEL]
+                Some(']') => match seq.find(['\x07', '\x1b']) {
+                    Some(end) => {
+                        let skip = if seq[end..].starts_with("\x1b\\") {
+                            2
+                        } else {
+                            1
+                        };
+                        rest = &seq[end + skip..];
+                    }
+                    None => {
+                        self.pending = rest[i..].to_string();
+                        return;
+                    }
+                },
+                // Other 2 byte sequences.
+                Some(c) => rest = &seq[c.len_utf8()..],
+                None => {
+                    self.pending = rest[i..].to_string();
+                    return;
+                }
+            }
+        }
+        self.push(rest);
+    }
+
+    // Select Graphic Rendition, only colors/bold are used.
+    fn sgr(&mut self, params: &str) {
+        let mut params = params
+            .split(';')
+            .map(|p| p.parse::<u8>().unwrap_or(0))
+            .peekable();
+        if params.peek().is_none() {
+            self.color = None;
+            self.bold = false;
+        }
+        while let Some(p) = params.next() {
+            match p {
+                0 => {
+                    self.color = None;
+                    self.bold = false;
+                }
+                1 => self.bold = true,
+                22 => self.bold = false,
+                30..=37 => self.color = Some(Color::Index(p - 30)),
+                90..=97 => self.color = Some(Color::Index(p - 90 + 8)),
+                39 => self.color = None,
+                38 => match params.next() {
+                    Some(5) => self.color = params.next().map(Color::Index),
+                    Some(2) => {
+                        let (r, g, b) = (params.next(), params.next(), params.next());
+                        if let (Some(r), Some(g), Some(b)) = (r, g, b) {
+                            self.color = Some(Color::Rgb(r, g, b));
+                        }
+                    }
+                    _ => (),
+                },
+                // Skip background colors' arguments.
+                48 => match params.next() {
+                    Some(5) => {
+                        params.next();
+                    }
+                    Some(2) => {
+                        params.nth(2);
+                    }
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+    }
+
+    // Colored [LayoutJob] of the plain text.
+    fn layout_job(&self, ui: &egui::Ui) -> LayoutJob {
+        let dark = ui.visuals().dark_mode;
+        let default = ui.visuals().text_color();
+        let font_id = ui
+            .style()
+            .override_text_style
+            .clone()
+            .unwrap_or(TextStyle::Monospace)
+            .resolve(ui.style());
+        let mut job = LayoutJob {
+            text: self.plain.clone(),
+            ..Default::default()
+        };
+        for (range, color) in &self.sections {
+            job.sections.push(egui::text::LayoutSection {
+                leading_space: 0.0,
+                byte_range: range.clone(),
+                format: TextFormat {
+                    font_id: font_id.clone(),
+                    color: color.map_or(default, |c| c.to_color32(dark)),
+                    ..Default::default()
+                },
+            });
+        }
+        job
+    }
+
+    // Update with [raw] and show it as a read-only [TextEdit].
+    // [colors] false shows plain text.
+    pub fn show(&mut self, ui: &mut egui::Ui, raw: &str, colors: bool, size: [f32; 2]) {
+        self.update(raw);
+        if !colors {
+            ui.add_sized(size, TextEdit::multiline(&mut self.plain.as_str()));
+            return;
+        }
+        let job = self.layout_job(ui);
+        let mut layouter = |ui: &egui::Ui, _: &str, wrap_width: f32| {
+            let mut job = job.clone();
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|f| f.layout_job(job))
+        };
+        ui.add_sized(
+            size,
+            TextEdit::multiline(&mut self.plain.as_str()).layouter(&mut layouter),
+        );
+    }
+}
+
+impl Console {
+    // Show inside a [ScrollArea] that follows new output unless [paused].
+    // Returns how far the user scrolled this console this frame, so
+    // that it can be applied to another console with [scroll_by()].
+    pub fn show_scroll(
+        &mut self,
+        ui: &mut egui::Ui,
+        id: &str,
+        raw: &str,
+        colors: bool,
+        size: [f32; 2],
+    ) -> f32 {
+        let mut area = egui::ScrollArea::vertical()
+            .id_source(id)
+            .stick_to_bottom(!self.paused)
+            .max_width(size[0])
+            .max_height(size[1])
+            .auto_shrink([false; 2]);
+        if let Some(y) = self.scroll_to.take() {
+            area = area.vertical_scroll_offset(y.max(0.0));
+        }
+        let output = area.show_viewport(ui, |ui, _| self.show(ui, raw, colors, size));
+        let y = output.state.offset.y;
+        // Only count scrolling the user did on this console (includes the scroll bar).
+        let hovered = ui.rect_contains_pointer(output.inner_rect.expand2(egui::vec2(12.0, 0.0)));
+        let delta = if hovered { y - self.offset } else { 0.0 };
+        self.offset = y;
+        delta
+    }
+
+    // Scroll by [delta] on the next frame.
+    pub fn scroll_by(&mut self, delta: f32) {
+        if delta != 0.0 {
+            self.scroll_to = Some(self.offset + delta);
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse_ansi() {
+        use super::*;
+        let mut console = Console::new();
+        let raw = "\x1b[?25l\x1b[0;32mgreen\x1b[0m plain \x1b[1;31mbright red\x1b[0m\n";
+        console.update(raw);
+        assert_eq!(console.plain(), "green plain bright red\n");
+        assert_eq!(
+            console.sections,
+            [
+                (0..5, Some(Color::Index(2))),
+                (5..12, None),
+                (12..22, Some(Color::Index(9))),
+                (22..23, None),
+            ]
+        );
+
+        // Escape codes split across updates.
+        let raw = format!("{}\x1b[38;5;", raw);
+        console.update(&raw);
+        assert_eq!(console.plain(), "green plain bright red\n");
+        let raw = format!("{}208mX\x1b]0;title\x07", raw);
+        console.update(&raw);
+        assert_eq!(console.plain(), "green plain bright red\nX");
+        assert_eq!(
+            console.sections.last(),
+            Some(&(23..24, Some(Color::Index(208))))
+        );
+
+        // Output was reset.
+        console.update("reset\n");
+        assert_eq!(console.plain(), "reset\n");
+        assert_eq!(console.sections, [(0..6, None)]);
+    }
+}
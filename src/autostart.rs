@@ -0,0 +1,301 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Start-on-login, using whatever each OS expects:
+//
+// Linux/BSD | $XDG_CONFIG_HOME/autostart/gupax.desktop
+// macOS     | $HOME/Library/LaunchAgents/com.github.hinto-janai.gupax.plist
+// Windows   | HKCU\Software\Microsoft\Windows\CurrentVersion\Run [Gupax]
+//
+// Like [hotkey.rs], the user settings are [sync()]'d every frame and the
+// OS is only touched when they actually change. The first [sync()] refreshes
+// the entry (Gupax may have been moved/updated since), unless [--no-startup]
+// was passed, which leaves the OS alone just like every other auto-startup setting.
+
+use log::*;
+#[cfg(not(target_os = "windows"))]
+use std::path::PathBuf;
+
+//---------------------------------------------------------------------------------------------------- Constants
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+const DESKTOP_FILE: &str = "gupax.desktop";
+#[cfg(any(target_os = "macos", test))]
+const LAUNCH_AGENT_LABEL: &str = "com.github.hinto-janai.gupax";
+#[cfg(target_os = "windows")]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+#[cfg(target_os = "windows")]
+const RUN_VALUE: &str = "Gupax";
+
+//---------------------------------------------------------------------------------------------------- [Autostart]
+#[derive(Debug, Default)]
+pub struct Autostart {
+    // What the user settings looked like on the last [sync()].
+    last: Option<(bool, bool)>,
+    // The last (un)register error, shown in the [Gupax] tab.
+    pub error: String,
+}
+
+impl Autostart {
+    pub fn sync(&mut self, enabled: bool, quiet: bool, no_startup: bool, profile: Option<&str>) {
+        let settings = (enabled, quiet);
+        let first = match self.last {
+            Some(last) if last == settings => return,
+            Some(_) => false,
+            None => true,
+        };
+        self.last = Some(settings);
+        self.error.clear();
+
+        // Don't touch the OS on startup if it's disabled
+        // (nothing to refresh) or [--no-startup] was passed.
+        if first && (!enabled || no_startup) {
+            return;
+        }
+
+        let result = if enabled {
+            exe().and_then(|exe| register(&exe, quiet, profile))
+        } else {
+            unregister()
+        };
+        match result {
+            Ok(()) => info!("Autostart | Set to [{}] (quiet: {}) ... OK", enabled, quiet),
+            Err(e) => {
+                warn!("Autostart | Set to [{}] ... FAIL: {}", enabled, e);
+                self.error = e;
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Functions
+// [--profile] names are [A-Za-z0-9_-] only, no quoting needed.
+fn args(quiet: bool, profile: Option<&str>) -> Vec<&str> {
+    let mut args = vec![];
+    if quiet {
+        args.push("--no-startup");
+    }
+    if let Some(profile) = profile {
+        args.extend(["--profile", profile]);
+    }
+    args
+}
+
+// The [Gupax] binary to start on login.
+fn exe() -> Result<String, String> {
+    // An AppImage runs from a temporary mount, point to the [.AppImage] itself.
+    #[cfg(target_os = "linux")]
+    if let Ok(appimage) = std::env::var("APPIMAGE") {
+        return Ok(appimage);
+    }
+    crate::get_exe().map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn entry_path() -> Result<PathBuf, String> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let path = dirs::config_dir().map(|p| p.join("autostart").join(DESKTOP_FILE));
+    #[cfg(target_os = "macos")]
+    let path = dirs::home_dir().map(|p| {
+        p.join("Library/LaunchAgents")
+            .join(format!("{}.plist", LAUNCH_AGENT_LABEL))
+    });
+    path.ok_or_else(|| "Could not find the OS autostart directory".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn register(exe: &str, quiet: bool, profile: Option<&str>) -> Result<(), String> {
+    let path = entry_path()?;
+    #[cfg(target_os = "macos")]
+    let text = launch_agent(exe, quiet, profile);
+    #[cfg(not(target_os = "macos"))]
+    let text = desktop_entry(exe, quiet, profile);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, text).map_err(|e| format!("[{}]: {}", path.display(), e))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn unregister() -> Result<(), String> {
+    let path = entry_path()?;
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("[{}]: {}", path.display(), e))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn register(exe: &str, quiet: bool, profile: Option<&str>) -> Result<(), String> {
+    reg(&[
+        "add",
+        RUN_KEY,
+        "/v",
+        RUN_VALUE,
+        "/t",
+        "REG_SZ",
+        "/d",
+        &run_value(exe, quiet, profile),
+        "/f",
+    ])
+}
+
+#[cfg(target_os = "windows")]
+fn unregister() -> Result<(), String> {
+    // [reg delete] fails if the value doesn't exist, check first.
+    if reg(&["query", RUN_KEY, "/v", RUN_VALUE]).is_err() {
+        return Ok(());
+    }
+    reg(&["delete", RUN_KEY, "/v", RUN_VALUE, "/f"])
+}
+
+#[cfg(target_os = "windows")]
+fn reg(args: &[&str]) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    let output = std::process::Command::new("reg")
+        .args(args)
+        // [CREATE_NO_WINDOW], don't flash a console window.
+        .creation_flags(0x08000000)
+        .output()
+        .map_err(|e| format!("[reg {}]: {}", args[0], e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "[reg {}]: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+// XDG [.desktop] file, [Exec] arguments are quoted per the spec.
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+fn desktop_entry(exe: &str, quiet: bool, profile: Option<&str>) -> String {
+    let mut exec = String::from("\"");
+    for c in exe.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            exec.push('\\');
+        }
+        exec.push(c);
+    }
+    exec.push('"');
+    for arg in args(quiet, profile) {
+        exec.push(' ');
+        exec.push_str(arg);
+    }
+    format!(
+        "[Desktop Entry]\nType=Application\nName=Gupax\nComment=GUI for P2Pool+XMRig\nExec={}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n",
+        // [%] is a field code, a literal one must be doubled.
+        exec.replace('%', "%%")
+    )
+}
+
+// macOS [LaunchAgent] plist.
+#[cfg(any(target_os = "macos", test))]
+fn launch_agent(exe: &str, quiet: bool, profile: Option<&str>) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let mut program = format!("\t\t<string>{}</string>\n", escape(exe));
+    for arg in args(quiet, profile) {
+        program += &format!("\t\t<string>{}</string>\n", arg);
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Label</key>
+	<string>{}</string>
+	<key>ProgramArguments</key>
+	<array>
+{}	</array>
+	<key>RunAtLoad</key>
+	<true/>
+</dict>
+</plist>
+"#,
+        LAUNCH_AGENT_LABEL, program
+    )
+}
+
+// Windows [Run] value, a full command line.
+#[cfg(any(target_os = "windows", test))]
+fn run_value(exe: &str, quiet: bool, profile: Option<&str>) -> String {
+    let mut value = format!("\"{}\"", exe);
+    for arg in args(quiet, profile) {
+        value.push(' ');
+        value.push_str(arg);
+    }
+    value
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn autostart_entries() {
+        let entry = desktop_entry("/home/user/my $dir/100%/gupax", true, None);
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("\nExec=\"/home/user/my \\$dir/100%%/gupax\" --no-startup\n"));
+        assert!(
+            desktop_entry("/usr/bin/gupax", false, None).contains("\nExec=\"/usr/bin/gupax\"\n")
+        );
+
+        let plist = launch_agent("/Applications/Gupax & Co.app/gupax", true, None);
+        assert!(plist.contains("<string>/Applications/Gupax &amp; Co.app/gupax</string>"));
+        assert!(plist.contains("<string>--no-startup</string>"));
+        assert!(plist.contains("<key>RunAtLoad</key>\n\t<true/>"));
+        assert!(!launch_agent("/gupax", false, None).contains("--no-startup"));
+
+        assert_eq!(
+            run_value(r"C:\Program Files\Gupax\Gupax.exe", true, None),
+            r#""C:\Program Files\Gupax\Gupax.exe" --no-startup"#
+        );
+
+        // The [--profile] Gupax was started with is kept.
+        assert!(desktop_entry("/usr/bin/gupax", false, Some("rig-01"))
+            .contains("\nExec=\"/usr/bin/gupax\" --profile rig-01\n"));
+        assert!(launch_agent("/gupax", true, Some("rig-01"))
+            .contains("<string>--no-startup</string>\n\t\t<string>--profile</string>\n\t\t<string>rig-01</string>\n"));
+        assert_eq!(
+            run_value("gupax.exe", false, Some("rig-01")),
+            r#""gupax.exe" --profile rig-01"#
+        );
+    }
+
+    #[test]
+    fn autostart_sync() {
+        // Disabled or [--no-startup] on the first sync must not touch the OS.
+        let mut autostart = Autostart::default();
+        autostart.sync(false, true, false, None);
+        assert_eq!(autostart.last, Some((false, true)));
+        let mut autostart = Autostart::default();
+        autostart.sync(true, true, true, None);
+        assert_eq!(autostart.last, Some((true, true)));
+        assert!(autostart.error.is_empty());
+        // Same settings, nothing to do.
+        autostart.sync(true, true, false, None);
+        assert!(autostart.error.is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Per-process availability over the last 24h/7d.
+//
+// Every frame [App] adds the time since the last frame to the current
+// state of P2Pool/XMRig, in hourly buckets kept for a week. The buckets
+// are saved to [availability.toml] once a minute so the numbers carry
+// over between sessions. Only time Gupax itself was open is counted.
+
+use crate::helper::{ProcessName, ProcessState};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+const HOUR: u64 = 3600;
+pub const DAY: u64 = 24;
+pub const WEEK: u64 = 7 * DAY;
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+//---------------------------------------------------------------------------------------------------- Bucket
+// Seconds spent [up, failed, dead] during one hour.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Bucket {
+    pub hour: u64, // UNIX time / 3600
+    pub p2pool: [u64; 3],
+    pub xmrig: [u64; 3],
+}
+
+// Which of [up, failed, dead] [state] counts as. Everything
+// that isn't offline (syncing, restarting, etc) is up.
+fn slot(state: ProcessState) -> usize {
+    match state {
+        ProcessState::Failed => 1,
+        ProcessState::Dead => 2,
+        _ => 0,
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Availability
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Availability {
+    #[serde(default)]
+    buckets: Vec<Bucket>, // Oldest first
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    last: Option<Instant>, // Last [tick()]
+    #[serde(skip)]
+    last_save: Option<Instant>,
+}
+
+impl Availability {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: PathBuf) -> Self {
+        let mut this = match std::fs::read_to_string(&path) {
+            Ok(s) => toml::from_str(&s).unwrap_or_else(|e| {
+                warn!(
+                    "Availability | [{}] is corrupt, starting over: {}",
+                    path.display(),
+                    e
+                );
+                Self::new()
+            }),
+            Err(_) => Self::new(),
+        };
+        this.path = Some(path);
+        this
+    }
+
+    // Called every frame with the current states, [save] is off in demo/read-only mode.
+    pub fn tick(&mut self, now: Instant, p2pool: ProcessState, xmrig: ProcessState, save: bool) {
+        let Some(last) = self.last else {
+            self.last = Some(now);
+            self.last_save = Some(now);
+            return;
+        };
+        let secs = now.saturating_duration_since(last).as_secs();
+        if secs == 0 {
+            return;
+        }
+        // Keep the sub-second remainder for the next tick.
+        self.last = Some(last + Duration::from_secs(secs));
+        self.add(unix_hour(), secs, p2pool, xmrig);
+        if save
+            && self
+                .last_save
+                .is_none_or(|t| now.duration_since(t) >= SAVE_INTERVAL)
+        {
+            self.last_save = Some(now);
+            self.save();
+        }
+    }
+
+    fn add(&mut self, hour: u64, secs: u64, p2pool: ProcessState, xmrig: ProcessState) {
+        if self.buckets.last().is_none_or(|b| b.hour != hour) {
+            self.buckets.push(Bucket {
+                hour,
+                ..Default::default()
+            });
+            self.buckets.retain(|b| b.hour + WEEK > hour);
+        }
+        if let Some(bucket) = self.buckets.last_mut() {
+            bucket.p2pool[slot(p2pool)] += secs;
+            bucket.xmrig[slot(xmrig)] += secs;
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = toml::to_string(self)
+            .map_err(|e| e.to_string())
+            .and_then(|s| std::fs::write(path, s).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Availability | Save [{}] ... FAIL: {}", path.display(), e);
+        }
+    }
+
+    // [up, failed, dead] percentages of [name] over the last [hours],
+    // [None] if Gupax wasn't open during them.
+    pub fn percent(&self, name: ProcessName, hours: u64, now_hour: u64) -> Option<[f32; 3]> {
+        let mut total = [0_u64; 3];
+        for bucket in self.buckets.iter().filter(|b| b.hour + hours > now_hour) {
+            let secs = match name {
+                ProcessName::P2pool => bucket.p2pool,
+                ProcessName::Xmrig => bucket.xmrig,
+                // Not tracked, it's only a relay for the other rigs.
+                ProcessName::Proxy => return None,
+            };
+            for (t, s) in total.iter_mut().zip(secs) {
+                *t += s;
+            }
+        }
+        let sum: u64 = total.iter().sum();
+        if sum == 0 {
+            return None;
+        }
+        Some(total.map(|t| t as f32 / sum as f32 * 100.0))
+    }
+
+    // e.g: [24h: 99.5% up, 0.5% failed | 7d: 87.1% up, 0.2% failed]
+    pub fn text(&self, name: ProcessName) -> String {
+        let now = unix_hour();
+        let window = |label: &str, hours: u64| match self.percent(name, hours, now) {
+            Some([up, failed, _]) => format!("{}: {:.1}% up, {:.1}% failed", label, up, failed),
+            None => format!("{}: no data", label),
+        };
+        format!("{} | {}", window("24h", DAY), window("7d", WEEK))
+    }
+}
+
+fn unix_hour() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / HOUR)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn availability() {
+        use crate::availability::*;
+        let mut a = Availability::new();
+        assert_eq!(a.percent(ProcessName::P2pool, DAY, 1000), None);
+
+        // 2 days ago: everything failed.
+        a.add(1000 - 48, 3600, ProcessState::Failed, ProcessState::Dead);
+        // Last hour: P2Pool up 3/4, dead 1/4. XMRig up the whole time.
+        a.add(1000, 2700, ProcessState::Syncing, ProcessState::NotMining);
+        a.add(1000, 900, ProcessState::Dead, ProcessState::Alive);
+
+        assert_eq!(
+            a.percent(ProcessName::P2pool, DAY, 1000),
+            Some([75.0, 0.0, 25.0])
+        );
+        assert_eq!(
+            a.percent(ProcessName::Xmrig, DAY, 1000),
+            Some([100.0, 0.0, 0.0])
+        );
+        // The week includes the failed hour.
+        assert_eq!(
+            a.percent(ProcessName::P2pool, WEEK, 1000),
+            Some([37.5, 50.0, 12.5])
+        );
+
+        // Older than a week is dropped when a new hour starts.
+        a.add(
+            1000 + WEEK - 48,
+            60,
+            ProcessState::Alive,
+            ProcessState::Alive,
+        );
+        assert_eq!(a.buckets.len(), 2);
+
+        // Round trip.
+        let toml = toml::to_string(&a).unwrap();
+        let b: Availability = toml::from_str(&toml).unwrap();
+        assert_eq!(a.buckets, b.buckets);
+    }
+}
@@ -0,0 +1,186 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// System clock skew check.
+//
+// P2Pool's sidechain blocks carry timestamps, a badly skewed clock
+// makes P2Pool reject (and get its own shares rejected by) peers.
+// This sends a single SNTP request (one UDP packet each way) every
+// few hours and keeps the measured offset so the GUI can warn about it.
+
+use crate::macros::*;
+use log::*;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+//---------------------------------------------------------------------------------------------------- Constants
+const SERVERS: [&str; 3] = [
+    "pool.ntp.org:123",
+    "time.cloudflare.com:123",
+    "time.google.com:123",
+];
+const TIMEOUT: Duration = Duration::from_secs(5);
+// How often to re-check.
+const INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+// Seconds between the NTP epoch (1900) and the UNIX epoch (1970).
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+// Skew (in seconds) that gets a warning.
+pub const MAX_SKEW: i64 = 60;
+
+//---------------------------------------------------------------------------------------------------- Clock
+#[derive(Debug, Default)]
+pub struct Clock {
+    pub skew: Option<i64>, // Milliseconds the system clock is ahead (+) or behind (-)
+    pub server: String,    // The server that answered
+    pub checking: bool,
+    checked: Option<Instant>,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Is it time for another check?
+    pub fn due(&self) -> bool {
+        !self.checking && self.checked.is_none_or(|i| i.elapsed() >= INTERVAL)
+    }
+
+    // The skew, if it's bad enough to warn about.
+    pub fn warning(&self) -> Option<i64> {
+        self.skew.filter(|ms| ms.abs() / 1000 >= MAX_SKEW)
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(clock: &Arc<Mutex<Self>>) {
+        lock!(clock).checking = true;
+        let clock = Arc::clone(clock);
+        std::thread::spawn(move || {
+            let mut result = None;
+            for server in SERVERS {
+                match sntp(server) {
+                    Ok(offset) => {
+                        result = Some((offset, server));
+                        break;
+                    }
+                    Err(e) => warn!("Clock | [{}] failed: {}", server, e),
+                }
+            }
+            let mut clock = lock!(clock);
+            match result {
+                Some((offset, server)) => {
+                    // [offset] is how far the server is ahead of us.
+                    let skew = -offset;
+                    if skew.abs() / 1000 >= MAX_SKEW {
+                        warn!("Clock | System clock is off by [{}ms] ... {}", skew, server);
+                    } else {
+                        info!("Clock | System clock is off by [{}ms] ... {}", skew, server);
+                    }
+                    clock.skew = Some(skew);
+                    clock.server = server.trim_end_matches(":123").to_string();
+                }
+                None => warn!("Clock | No NTP server answered, skipping"),
+            }
+            clock.checked = Some(Instant::now());
+            clock.checking = false;
+        });
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SNTP
+// The milliseconds the server's clock is ahead of ours.
+fn sntp(server: &str) -> std::io::Result<i64> {
+    let addr = server
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::other("no address"))?;
+    let bind = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(bind)?;
+    socket.set_read_timeout(Some(TIMEOUT))?;
+    socket.set_write_timeout(Some(TIMEOUT))?;
+    // LI = 0, Version = 4, Mode = 3 (client).
+    let mut packet = [0_u8; 48];
+    packet[0] = 0x23;
+    let t1 = unix_ms(SystemTime::now());
+    socket.send_to(&packet, addr)?;
+    let (len, _) = socket.recv_from(&mut packet)?;
+    let t4 = unix_ms(SystemTime::now());
+    // Mode 4 (server), and a non-zero stratum (zero is a "kiss-o'-death").
+    if len < 48 || packet[0] & 0x7 != 4 || packet[1] == 0 {
+        return Err(std::io::Error::other("invalid response"));
+    }
+    let t2 = ntp_to_unix_ms(&packet[32..40]);
+    let t3 = ntp_to_unix_ms(&packet[40..48]);
+    Ok(offset(t1, t2, t3, t4))
+}
+
+fn unix_ms(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_millis() as i64,
+        Err(e) => -(e.duration().as_millis() as i64),
+    }
+}
+
+// 32-bit seconds + 32-bit fraction since 1900.
+fn ntp_to_unix_ms(bytes: &[u8]) -> i64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let frac = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as i64;
+    (secs - NTP_UNIX_OFFSET) * 1000 + ((frac * 1000) >> 32)
+}
+
+// Standard NTP clock offset: ((t2 - t1) + (t3 - t4)) / 2
+const fn offset(t1: i64, t2: i64, t3: i64, t4: i64) -> i64 {
+    ((t2 - t1) + (t3 - t4)) / 2
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn ntp_offset() {
+        use super::{ntp_to_unix_ms, offset};
+        // 2023-01-01 00:00:00.5 UTC
+        let secs: u32 = (1_672_531_200 + 2_208_988_800_i64) as u32;
+        let mut bytes = secs.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&(u32::MAX / 2 + 1).to_be_bytes());
+        assert_eq!(ntp_to_unix_ms(&bytes), 1_672_531_200_500);
+        // Our clock is 90 seconds behind, with a 100ms round trip.
+        assert_eq!(offset(1_000, 91_050, 91_050, 1_100), 90_000);
+        // And ahead.
+        assert_eq!(offset(91_000, 1_050, 1_050, 91_100), -90_000);
+    }
+
+    #[test]
+    fn clock_warning() {
+        use super::Clock;
+        let mut clock = Clock::new();
+        assert!(clock.due());
+        assert_eq!(clock.warning(), None);
+        clock.skew = Some(59_999);
+        assert_eq!(clock.warning(), None);
+        clock.skew = Some(-61_000);
+        assert_eq!(clock.warning(), Some(-61_000));
+        clock.checking = true;
+        assert!(!clock.due());
+    }
+}
@@ -0,0 +1,146 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Error codes for the [ErrorState] screen.
+//
+// Every error Gupax shows gets a short, stable code so issue reports and
+// support can point at the exact failure instead of paraphrasing the message.
+// The codes are grouped by hundreds:
+//
+//   GX-1xx | Gupax's own files (state/node/pool/stats)
+//   GX-3xx | P2Pool/XMRig binaries
+//   GX-4xx | Platform/permissions
+//
+// Codes never change meaning once released, new ones get a new number.
+// Each one has an entry in [help/codes.md].
+
+//---------------------------------------------------------------------------------------------------- ErrorCode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    StateRead,
+    StateSave,
+    StateReset,
+    NodeRead,
+    NodeSave,
+    NodeReset,
+    PoolRead,
+    PoolSave,
+    StatsRead,
+    P2poolPath,
+    XmrigPath,
+    BinaryChanged,
+    BinaryTrust,
+    Quarantined,
+    NotAdmin,
+    Root,
+    Translocated,
+}
+
+impl ErrorCode {
+    pub const ALL: [Self; 17] = [
+        Self::StateRead,
+        Self::StateSave,
+        Self::StateReset,
+        Self::NodeRead,
+        Self::NodeSave,
+        Self::NodeReset,
+        Self::PoolRead,
+        Self::PoolSave,
+        Self::StatsRead,
+        Self::P2poolPath,
+        Self::XmrigPath,
+        Self::BinaryChanged,
+        Self::BinaryTrust,
+        Self::Quarantined,
+        Self::NotAdmin,
+        Self::Root,
+        Self::Translocated,
+    ];
+
+    pub const fn number(&self) -> u16 {
+        match self {
+            Self::StateRead => 101,
+            Self::StateSave => 102,
+            Self::StateReset => 103,
+            Self::NodeRead => 111,
+            Self::NodeSave => 112,
+            Self::NodeReset => 113,
+            Self::PoolRead => 121,
+            Self::PoolSave => 122,
+            Self::StatsRead => 131,
+            Self::P2poolPath => 301,
+            Self::XmrigPath => 302,
+            Self::BinaryChanged => 311,
+            Self::BinaryTrust => 312,
+            Self::Quarantined => 321,
+            Self::NotAdmin => 401,
+            Self::Root => 402,
+            Self::Translocated => 403,
+        }
+    }
+
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::StateRead => "Settings file could not be read",
+            Self::StateSave => "Settings file could not be saved",
+            Self::StateReset => "Settings file could not be reset",
+            Self::NodeRead => "Node list could not be read",
+            Self::NodeSave => "Node list could not be saved",
+            Self::NodeReset => "Node list could not be reset",
+            Self::PoolRead => "Pool list could not be read",
+            Self::PoolSave => "Pool list could not be saved",
+            Self::StatsRead => "P2Pool payout stats could not be read",
+            Self::P2poolPath => "P2Pool path is invalid",
+            Self::XmrigPath => "XMRig path is invalid",
+            Self::BinaryChanged => "Binary changed outside of Gupax",
+            Self::BinaryTrust => "Binary hash could not be recorded",
+            Self::Quarantined => "Binary removed or blocked by antivirus",
+            Self::NotAdmin => "Not running as Administrator",
+            Self::Root => "Running as root",
+            Self::Translocated => "Relocated by macOS",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "GX-{}", self.number())
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn codes_are_unique_and_documented() {
+        use super::ErrorCode;
+        use crate::help::Page;
+        let page = Page::Codes.markdown();
+        for (i, code) in ErrorCode::ALL.iter().enumerate() {
+            assert!(
+                !ErrorCode::ALL[..i]
+                    .iter()
+                    .any(|c| c.number() == code.number()),
+                "{code} is used twice"
+            );
+            assert!(
+                page.contains(&format!("## {} | {}", code, code.title())),
+                "{code}"
+            );
+        }
+    }
+}
@@ -60,6 +60,7 @@ pub const HORI_CONSOLE: &str = "------------------------------------------------
 pub const KEYBOARD_SHORTCUTS: &str = r#"*---------------------------------------*
 |             Key shortcuts             |
 |---------------------------------------|
+|              F1 | Help                |
 |             F11 | Fullscreen          |
 |          Escape | Quit screen         |
 |              Up | Start/Restart       |
@@ -85,6 +86,7 @@ pub const P2POOL_API_PATH_NETWORK: &str = "network/stats";
 #[cfg(target_family = "unix")]
 pub const P2POOL_API_PATH_POOL: &str = "pool/stats";
 pub const XMRIG_API_URI: &str = "1/summary"; // The default relative URI of XMRig's API
+pub const PROXY_API_WORKERS_URI: &str = "1/workers"; // XMRig-Proxy's per-rig stats
 
 // Process state tooltips (online, offline, etc)
 pub const P2POOL_ALIVE: &str = "P2Pool is online and fully synchronized";
@@ -97,8 +99,14 @@ pub const P2POOL_SYNCING: &str =
 pub const XMRIG_ALIVE: &str = "XMRig is online and mining";
 pub const XMRIG_DEAD: &str = "XMRig is offline";
 pub const XMRIG_FAILED: &str = "XMRig is offline and failed when exiting";
+pub const UNSAVED_START: &str =
+    "Use the current settings without saving them, they'll still show as unsaved changes";
+pub const UNSAVED_SAVE_START: &str =
+    "Save the changes this process uses, then start it. Other unsaved changes are left alone";
+pub const ERROR_COPY_DIAGNOSTIC: &str = "Copy this error with the P2Pool/XMRig states, paths, versions and the last 50 lines of both consoles, formatted as a GitHub bug report. Check it for anything private (e.g: your wallet address) before posting";
 pub const XMRIG_MIDDLE: &str = "XMRig is in the middle of (re)starting/stopping";
 pub const XMRIG_NOT_MINING: &str = "XMRig is online, but not mining to any pool";
+pub const RESTART_NEEDED: &str = "(*) Settings changed since start, restart to apply:";
 
 // This is the typical space added when using
 // [ui.separator()] or [ui.group()]
@@ -119,9 +127,16 @@ pub const GRAY: egui::Color32 = egui::Color32::GRAY;
 pub const LIGHT_GRAY: egui::Color32 = egui::Color32::LIGHT_GRAY;
 pub const BLACK: egui::Color32 = egui::Color32::BLACK;
 pub const DARK_GRAY: egui::Color32 = egui::Color32::from_gray(13);
+// Color-blind friendly status colors (Okabe-Ito palette)
+pub const CB_BLUE: egui::Color32 = egui::Color32::from_rgb(86, 180, 233);
+pub const CB_ORANGE: egui::Color32 = egui::Color32::from_rgb(230, 159, 0);
+pub const CB_YELLOW: egui::Color32 = egui::Color32::from_rgb(240, 228, 66);
+pub const CB_VERMILLION: egui::Color32 = egui::Color32::from_rgb(213, 94, 0);
 
 // [Duration] constants
 pub const SECOND: std::time::Duration = std::time::Duration::from_secs(1);
+// Repeated [Save]s within this are merged into one write.
+pub const SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 // The explanation given to the user on why XMRig needs sudo.
 pub const XMRIG_ADMIN_REASON: &str = r#"The large hashrate difference between XMRig and other miners like Monero and P2Pool's built-in miners is mostly due to XMRig configuring CPU MSRs and setting up hugepages. Other miners like Monero or P2Pool's built-in miner do not do this. It can be done manually but it isn't recommended since XMRig does this for you automatically, but only if it has the proper admin privileges."#;
@@ -155,6 +170,7 @@ pub const STATUS_GUPAX_UPTIME: &str = "How long Gupax has been online";
 pub const STATUS_GUPAX_CPU_USAGE: &str =
     "How much CPU Gupax is currently using. This accounts for all your threads (it is out of 100%)";
 pub const STATUS_GUPAX_MEMORY_USAGE: &str = "How much memory Gupax is currently using in Megabytes";
+pub const STATUS_GUPAX_BUFFERS: &str = "How much of Gupax's memory the P2Pool/XMRig output takes: the consoles, output not read yet, and the parsed API stats. The console cap is set in [Gupax -> Console buffer]";
 pub const STATUS_GUPAX_SYSTEM_CPU_USAGE: &str = "How much CPU your entire system is currently using. This accounts for all your threads (it is out of 100%)";
 pub const STATUS_GUPAX_SYSTEM_MEMORY: &str =
     "How much memory your entire system has (including swap) and is currently using in Gigabytes";
@@ -162,6 +178,7 @@ pub const STATUS_GUPAX_SYSTEM_CPU_MODEL: &str =
     "The detected model of your system's CPU and its current frequency";
 //--
 pub const STATUS_P2POOL_UPTIME: &str = "How long P2Pool has been online";
+pub const STATUS_P2POOL_PAYOUTS_MISSING: &str = "Shares have been found for over a day but Gupax hasn't seen a single payout in P2Pool's output. P2Pool may have changed how it logs payouts (this P2Pool version might be newer than this Gupax), check P2Pool's console for payout lines and update Gupax";
 pub const STATUS_P2POOL_PAYOUTS:     &str = "The total amount of payouts received in this instance of P2Pool and an extrapolated estimate of how many you will receive.
 
 Note: these stats will be quite inaccurate if your P2Pool hasn't been running for a long time.";
@@ -170,6 +187,14 @@ pub const STATUS_P2POOL_XMR:         &str = "The total amount of XMR mined in th
 Note: these stats will be quite inaccurate if your P2Pool hasn't been running for a long time.";
 pub const STATUS_P2POOL_HASHRATE:    &str = "The total amount of hashrate your P2Pool has pointed at it in 15 minute, 1 hour, and 24 hour averages";
 pub const STATUS_P2POOL_SHARES: &str = "The total amount of shares found on P2Pool";
+pub const STATUS_P2POOL_SHARE_EFFICIENCY: &str = "Shares found compared to the amount of shares statistically expected for your hashrate and the P2Pool difficulty while this P2Pool has been running:
+
+    efficiency = shares_found / expected_shares
+    expected_shares = sum(hashrate * seconds / p2pool_difficulty)
+
+Around 100% is normal. Short-term luck causes large swings, but an efficiency that stays far below 100% over many hours can mean a misconfigured rig, stale shares from a slow connection, or miners not actually pointed at this P2Pool.
+
+This is unknown until at least 1 share is expected.";
 pub const STATUS_P2POOL_EFFORT: &str =
     "The average amount of effort needed to find a share, and the current effort";
 pub const STATUS_P2POOL_CONNECTIONS: &str = "The total amount of miner connections on this P2Pool";
@@ -181,14 +206,49 @@ pub const STATUS_XMRIG_UPTIME: &str = "How long XMRig has been online";
 pub const STATUS_XMRIG_CPU:         &str = "The average CPU load of XMRig. [1.0] represents 1 thread is maxed out, e.g: If you have 8 threads, [4.0] means half your threads are maxed out.";
 pub const STATUS_XMRIG_HASHRATE: &str = "The average hashrate of XMRig";
 pub const STATUS_XMRIG_DIFFICULTY: &str = "The current difficulty of the job XMRig is working on";
+pub const STATUS_CARD_CONSOLE: &str = "Open this process's console in the [Consoles] submenu";
+pub const STATUS_CARD_AVAILABILITY: &str = "How much of the time Gupax was open in the last 24 hours/7 days this process was running (up) or had failed. The rest it was stopped. Saved across restarts";
+pub const STATUS_CARD_EXIT: &str = "How the process last exited when Gupax didn't stop it, e.g: a crash or getting killed by the OS";
 pub const STATUS_XMRIG_SHARES: &str = "The amount of accepted and rejected shares";
+pub const STATUS_XMRIG_SHARE_LATENCY: &str = "How long the pool took to accept each share, parsed from XMRig's [accepted ... (N ms)] lines. The median (p50) and 95th percentile (p95) are over the last 50 shares. A sustained rise points at the network or the P2Pool node before shares start getting rejected";
 pub const STATUS_XMRIG_POOL: &str = "The pool XMRig is currently mining to";
+pub const STATUS_XMRIG_INSTANCES: &str = "The extra XMRig instances started next to the main one (see [Instances] in the XMRig tab), their 10s hashrates, and the combined hashrate of all running XMRig's";
+pub const STATUS_XMRIG_POOL_HASHRATE: &str = "The hashrate the pool credits your address with, from its public API, checked every 2 minutes. It's averaged from the shares that actually arrived, so if it's far below XMRig's own hashrate (after ~20 minutes of mining), shares are getting lost on the way: check NAT/firewalls, TLS settings and proxies between XMRig and the pool";
+pub const STATUS_WORKDIR: &str = "The working directory the process was started in, only shown if it's not the binary's directory";
 pub const STATUS_XMRIG_THREADS: &str = "The amount of threads XMRig is currently using";
+pub const STATUS_XMRIG_DETAILS_CPU: &str =
+    "The CPU XMRig detected, its core/thread count and supported instruction sets";
+pub const STATUS_XMRIG_DETAILS_CACHE: &str = "The size of the CPU's L2/L3 cache. RandomX needs 2 MB of L3 cache per thread for the best hashrate";
+pub const STATUS_XMRIG_DETAILS_MSR: &str = "The MSR (Model Specific Register) preset XMRig applied to the CPU, [none] means no MSR mod was applied. MSR mods require admin/root and can increase hashrate by 10-15%";
+pub const STATUS_XMRIG_DETAILS_MEMORY: &str =
+    "The memory XMRig is using (resident set size) and the used/total system memory";
+pub const STATUS_XMRIG_DETAILS_HUGEPAGES: &str = "Whether XMRig could allocate huge pages, and how many of the requested pages were allocated. Huge pages can increase hashrate by 20-30%, XMRig needs admin/root to allocate them";
+pub const STATUS_XMRIG_DETAILS_THREAD: &str =
+    "The 10 second hashrate of this XMRig thread, the bar is relative to the fastest thread";
 // Status Submenus
+pub const STATUS_MODE: &str = "Apply a bundle of P2Pool/XMRig settings in one click, a list of what will change is shown before anything is applied. The new settings take effect the next time P2Pool/XMRig are started";
+pub const STATUS_LAYOUT: &str =
+    "Drag a group onto another to move it there, click it to hide/show it. The layout is saved";
+pub const STATUS_MODE_MAX_PROFIT: &str =
+    "All threads, no pausing, more P2Pool peers and a higher XMRig priority";
+pub const STATUS_MODE_BALANCED: &str =
+    "Half the threads with default P2Pool peers and XMRig priority";
+pub const STATUS_MODE_BACKGROUND: &str = "A quarter of the threads at idle priority, pausing while the computer is in use (not on Linux)";
 pub const STATUS_SUBMENU_PROCESSES: &str =
     "View the status of process related data for [Gupax|P2Pool|XMRig]";
 pub const STATUS_SUBMENU_P2POOL: &str = "View P2Pool specific data";
 pub const STATUS_SUBMENU_HASHRATE: &str = "Compare your CPU hashrate with others";
+pub const STATUS_SUBMENU_CONSOLES: &str = "View the P2Pool and XMRig consoles together";
+pub const STATUS_SUBMENU_EVENTS: &str = "A timeline of what happened: P2Pool/XMRig starts/stops/crashes, payouts, shares, node switches, updates and warnings";
+pub const STATUS_EVENTS_FILTER: &str = "Show/hide this kind of event";
+pub const STATUS_EVENTS_CLEAR: &str =
+    "Clear the timeline (and [events.log] if the event log is on)";
+//-- Consoles
+pub const STATUS_CONSOLES_SIDE_BY_SIDE: &str = "Show P2Pool on the left and XMRig on the right";
+pub const STATUS_CONSOLES_STACKED: &str = "Show P2Pool on top and XMRig on the bottom";
+pub const STATUS_CONSOLES_SYNC: &str = "Scrolling one console scrolls the other by the same amount";
+pub const STATUS_CONSOLES_PAUSE: &str =
+    "Stop this console from following new output so it can be read while the process keeps running";
 //-- P2Pool
 pub const STATUS_SUBMENU_PAYOUT:    &str = "The total amount of payouts received via P2Pool across all time. This includes all payouts you have ever received using Gupax and P2Pool.";
 pub const STATUS_SUBMENU_XMR:       &str = "The total of XMR mined via P2Pool across all time. This includes all the XMR you have ever mined using Gupax and P2Pool.";
@@ -196,6 +256,18 @@ pub const STATUS_SUBMENU_LATEST: &str = "Sort the payouts from latest to oldest"
 pub const STATUS_SUBMENU_OLDEST: &str = "Sort the payouts from oldest to latest";
 pub const STATUS_SUBMENU_BIGGEST: &str = "Sort the payouts from biggest to smallest";
 pub const STATUS_SUBMENU_SMALLEST: &str = "Sort the payouts from smallest to biggest";
+pub const STATUS_SUBMENU_TIMEZONE: &str = "Toggle between showing payout dates in your local timezone or UTC. Payouts are always saved in UTC.";
+pub const STATUS_SUBMENU_WEEKLY: &str =
+    "Summarize payouts, XMR, average hashrate and uptime per week";
+pub const STATUS_SUBMENU_MONTHLY: &str =
+    "Summarize payouts, XMR, average hashrate and uptime per month";
+pub const STATUS_SUBMENU_RECENT: &str =
+    "List the latest payouts as a table (time, XMR, atomic units, block) with a total for each day";
+pub const STATUS_SUBMENU_EXPORT_MARKDOWN: &str = "Save this report as a Markdown file";
+pub const STATUS_SUBMENU_EXPORT_HTML: &str = "Save this report as an HTML file";
+pub const STATUS_SUBMENU_EXPLORER: &str = "Block explorer the payout blocks link to, [{height}] is replaced with the block height. A local explorer works too, e.g: [http://127.0.0.1:8081/block/{height}]";
+pub const STATUS_SUBMENU_COPY_HASH: &str =
+    "Ask the node selected in the [P2Pool] tab for this block's hash and copy it to the clipboard";
 pub const STATUS_SUBMENU_AUTOMATIC: &str =
     "Automatically calculate share/block time with your current P2Pool 1 hour average hashrate";
 pub const STATUS_SUBMENU_MANUAL:    &str = "Manually input a hashrate to calculate share/block time with current P2Pool/Monero network stats";
@@ -210,10 +282,12 @@ pub const STATUS_SUBMENU_P2POOL_SHARE_MEAN: &str =
     "The average time it takes for your hashrate to find a share on P2Pool";
 pub const STATUS_SUBMENU_SOLO_BLOCK_MEAN: &str =
     "The average time it would take for your hashrate to find a block solo mining Monero";
-pub const STATUS_SUBMENU_MONERO_DIFFICULTY:     &str = "The current Monero network's difficulty (how many hashes it will take on average to find a block)";
+pub const STATUS_SUBMENU_MONERO_DIFFICULTY:     &str = "The current Monero network's difficulty (how many hashes it will take on average to find a block). It's re-adjusted every block (~2 minutes) from the last 720 blocks, so it follows the network's hashrate within about a day";
 pub const STATUS_SUBMENU_MONERO_HASHRATE: &str = "The current Monero network's hashrate";
-pub const STATUS_SUBMENU_P2POOL_DIFFICULTY:     &str = "The current P2Pool network's difficulty (how many hashes it will take on average to find a share)";
+pub const STATUS_SUBMENU_P2POOL_DIFFICULTY:     &str = "The current P2Pool network's difficulty (how many hashes it will take on average to find a share). It's re-adjusted every share to keep shares ~10 seconds apart";
 pub const STATUS_SUBMENU_P2POOL_HASHRATE: &str = "The current P2Pool network's hashrate";
+pub const STATUS_SUBMENU_BLOCK_REWARD: &str = "What the latest Monero block paid. Monero's emission is in its tail phase: every block pays a fixed 0.6 XMR forever, plus the transaction fees in it. P2Pool splits each block it finds between the shares in its PPLNS window";
+pub const STATUS_SUBMENU_REWARD_PER_SHARE: &str = "What a single P2Pool share earns on average over its time in the PPLNS window: [Block Reward] x [P2Pool Difficulty] / [Monero Difficulty]. Your expected earnings are this times the shares you find";
 pub const STATUS_SUBMENU_P2POOL_MINERS: &str = "The current amount of miners on P2Pool";
 pub const STATUS_SUBMENU_P2POOL_DOMINANCE: &str =
     "The percent of hashrate P2Pool accounts for in the entire Monero network";
@@ -234,8 +308,15 @@ pub const STATUS_SUBMENU_YOUR_AVERAGE: &str =
     "The average hashrate of your CPU based off the data at [https://xmrig.com/benchmark]";
 pub const STATUS_SUBMENU_YOUR_LOW: &str =
     "The lowest hashrate recorded for your CPU on [https://xmrig.com/benchmark]";
-pub const STATUS_SUBMENU_OTHER_CPUS:       &str = "A list of ALL the recorded CPU benchmarks. The CPUs most similar to yours are listed first. All this data is taken from [https://xmrig.com/benchmark].";
-pub const STATUS_SUBMENU_OTHER_CPU: &str = "The CPU name";
+pub const STATUS_SUBMENU_OTHER_CPUS:       &str = "A list of ALL the recorded CPU benchmarks. The CPUs most similar to yours are listed first, click a column header to sort by it. All this data is taken from [https://xmrig.com/benchmark].";
+pub const STATUS_SUBMENU_OTHER_SEARCH: &str =
+    "Only show CPUs whose name contains every word typed here, e.g: [ryzen 5800]";
+pub const STATUS_SUBMENU_OTHER_CLEAR_PINS: &str = "Unpin all pinned CPUs";
+pub const STATUS_SUBMENU_OTHER_PIN: &str =
+    "Pin up to 3 CPUs to compare them side by side with your own hashrate";
+pub const STATUS_SUBMENU_OTHER_CPU: &str =
+    "The CPU name, sorting by this lists the CPUs most similar to yours first";
+pub const STATUS_SUBMENU_OTHER_PER_CORE: &str = "Average hashrate divided by the core count. Only known for CPUs that have their core count in their name, the rest sort last";
 pub const STATUS_SUBMENU_OTHER_RELATIVE:   &str = "The relative hashrate power compared to the fastest recorded CPU, which is current: [AMD EPYC 7T83 64-Core Processor]";
 pub const STATUS_SUBMENU_OTHER_HIGH: &str = "Highest hashrate record";
 pub const STATUS_SUBMENU_OTHER_AVERAGE: &str = "Average hashrate";
@@ -248,10 +329,20 @@ pub const STATUS_SUBMENU_OTHER_BENCHMARKS: &str =
 pub const GUPAX_UPDATE: &str =
     "Check for updates on Gupax, P2Pool, and XMRig via GitHub's API and upgrade automatically";
 pub const GUPAX_AUTO_UPDATE: &str = "Automatically check for updates at startup";
+pub const GUPAX_UPDATE_GUPAX: &str =
+    "Let [Check for updates] and [Auto-Update] replace Gupax itself";
+pub const GUPAX_UPDATE_P2POOL: &str = "Let [Check for updates] and [Auto-Update] replace the P2Pool binary. P2Pool hardforks need an updated binary, so leaving this on is recommended";
+pub const GUPAX_UPDATE_XMRIG: &str = "Let [Check for updates] and [Auto-Update] replace the XMRig binary. Turn this off to keep a custom XMRig build untouched";
+pub const GUPAX_START_DELAY: &str = "Seconds [Auto-P2Pool]/[Auto-XMRig] wait after Gupax starts before starting the process. Useful at login, when the network or a local monerod isn't ready yet. 0 starts right away";
+pub const GUPAX_WAIT_FOR_NETWORK: &str = "Don't auto-start P2Pool/XMRig until the network is up (checked every second, given up on after 3 minutes)";
 pub const GUPAX_SHOULD_RESTART: &str =
     "Gupax was updated. A restart is recommended but not required";
 pub const GUPAX_UP_TO_DATE: &str = "Gupax is up-to-date";
 #[cfg(not(target_os = "macos"))]
+pub const GUPAX_IP_PREFERENCE: &str = "Which address family Gupax's own connections (updates, node pings, P2Pool stats, usage stats) try first. Use [Prefer IPv4] on networks with broken IPv6. P2Pool/XMRig are not affected";
+pub const GUPAX_HOOKS: &str = "A script/executable run when P2Pool/XMRig starts, stops, crashes or P2Pool finds a payout, e.g: to switch a smart plug or send your own notification. It gets the event in [GUPAX_EVENT], [GUPAX_EVENT_MESSAGE], [GUPAX_PROCESS], [GUPAX_EXIT], [GUPAX_XMR] and [GUPAX_BLOCK] environment variables, and as 1 line of JSON on STDIN. Hooks are killed after 60 seconds, failures show up in [Status/Events]. Leave empty to disable";
+pub const GUPAX_HOOK_TEST: &str = "Run this hook now with a test event ([GUPAX_TEST=true])";
+pub const GUPAX_DOH: &str = "A DNS-over-HTTPS server Gupax's own connections look up hosts with, e.g: [https://cloudflare-dns.com/dns-query] or [https://dns.google/resolve]. Useful if your ISP's DNS is broken or hijacked. Empty uses the system resolver, which is also used if the server doesn't answer";
 pub const GUPAX_UPDATE_VIA_TOR:   &str = "Update through the Tor network. Tor is embedded within Gupax; a Tor system proxy is not required";
 #[cfg(target_os = "macos")] // Arti library has issues on macOS
 pub const GUPAX_UPDATE_VIA_TOR:   &str = "Update through the Tor network. Tor is embedded within Gupax; a Tor system proxy is not required.
@@ -259,13 +350,73 @@ pub const GUPAX_UPDATE_VIA_TOR:   &str = "Update through the Tor network. Tor is
 Note: This option is unstable on macOS.";
 pub const GUPAX_ASK_BEFORE_QUIT: &str = "Ask before quitting Gupax";
 pub const GUPAX_SAVE_BEFORE_QUIT: &str = "Automatically save any changed settings before quitting";
+pub const GUPAX_HOTKEYS: &str = "Enable OS-global hotkeys that work even when Gupax is unfocused or minimized. Hotkeys are written like [Ctrl+Shift+F9], modifiers are [Ctrl], [Shift], [Alt] and [Super]";
+pub const GUPAX_PAYOUT_SOUND: &str = "Play a short sound when P2Pool finds a payout for you";
+pub const GUPAX_PAYOUT_VOLUME: &str = "The volume of the payout sound";
+pub const GUPAX_PAYOUT_TEST: &str = "Play the payout sound";
+pub const GUPAX_QUIET_HOURS: &str =
+    "Don't play the payout sound between these hours (local time), e.g: [22:00 -> 08:00]";
+pub const GUPAX_PAYOUT_BACKUPS: &str = "Every 6 hours, copy the P2Pool payout/XMR stats into a [backup] folder next to them, keeping this many copies. [0] turns backups off. [--reset-payouts] always takes a backup first";
+pub const GUPAX_PAYOUT_BACKUP_NOW: &str = "Take a backup right now";
+pub const GUPAX_HELP: &str = "Open the help for this tab, common errors and their fixes are under [Troubleshooting]. [F1] does the same";
+pub const GUPAX_CONSOLE_CAP: &str = "How many kilobytes of output each P2Pool/XMRig console keeps before it's reset. Output that isn't read yet is capped at twice this, the oldest lines are dropped first. The memory used is shown in [Status -> Processes]";
+pub const GUPAX_ESCALATION: &str = "What XMRig is started through to get admin privileges. [Auto] uses the first of [sudo], [doas] or [pkexec] found in the PATH. [pkexec] asks for the password itself through the system's polkit agent";
+pub const GUPAX_GFX: &str = "The graphics backend Gupax is running with. If the window is blank, flickers or Gupax crashes on start, try forcing X11 or another renderer. These can also be set for a single run with the [GUPAX_X11] and [GUPAX_RENDERER] environment variables, see [--help]";
+pub const GUPAX_GFX_X11: &str = "Relaunch Gupax without Wayland on start so it runs under X11 (or XWayland). Fixes blank windows on some Wayland compositors";
+pub const GUPAX_GFX_RENDERER: &str = "Which eframe renderer to draw the window with. [Auto] uses the default, [OpenGL (glow)] and [wgpu] force one of them";
+pub const GUPAX_GFX_RESTART: &str = "Save and restart Gupax to apply the graphics settings";
+pub const GUPAX_PAYOUT_RESTORE: &str = "Replace the current payout/XMR stats with the selected backup. The current stats are backed up first, so this can be undone";
+pub const GUPAX_MIGRATE: &str = "Move this whole mining setup to another machine: the settings, node/pool lists and payout history in one file, imported on the new machine with its paths adapted";
+pub const GUPAX_MIGRATE_BINARIES: &str = "Also save the P2Pool/XMRig/XMRig-Proxy paths' SHA-256 hashes, so the import can check the binaries on the new machine are the same ones";
+pub const GUPAX_MIGRATE_EXPORT: &str = "Save the saved settings, node/pool lists and payout history as one snapshot file. Unsaved changes aren't included";
+pub const GUPAX_MIGRATE_IMPORT: &str = "Select a snapshot from another machine. It's checked and its paths adapted to this machine first, nothing changes until [Apply]";
+pub const GUPAX_MIGRATE_APPLY: &str = "Replace all settings, the node/pool lists and the payout history with the snapshot's. The current payout history is backed up first";
+pub const GUPAX_MIGRATE_CANCEL: &str = "Discard this snapshot, nothing was changed";
+pub const GUPAX_READ_ONLY: &str = "Gupax's data folder can't be written to (e.g: macOS App Translocation, a locked-down profile or a read-only mount). Gupax still works, but settings and P2Pool payout stats only last until it closes";
+pub const GUPAX_READ_ONLY_PICK: &str = "Pick another folder to save Gupax's data in for this session. To use it every time, start Gupax with the [GUPAX_DATA_DIR] environment variable set to it";
+pub const GUPAX_START_ON_LOGIN: &str = "Start Gupax when you log in to your computer (XDG autostart on Linux, a LaunchAgent on macOS, the [Run] registry key on Windows)";
+pub const GUPAX_START_ON_LOGIN_QUIET: &str = "Start with [--no-startup] when launched on login, so auto-update, auto-ping, Auto-P2Pool and Auto-XMRig are all skipped and Gupax just opens quietly";
+pub const GUPAX_HOTKEYS_UNSUPPORTED: &str = "Global hotkeys are not supported on this system";
+pub const GUPAX_HOTKEY_XMRIG: &str = "Pause XMRig if it is mining, resume it if it is paused, start it if it is not running. On Linux/macOS, Gupax will pop up and ask for the [sudo] password when starting XMRig";
+pub const GUPAX_HOTKEY_WINDOW: &str =
+    "Minimize Gupax if it is focused, otherwise bring it to the front";
+pub const GUPAX_RESTART_ON_RESUME: &str = "Restart P2Pool/XMRig after the computer wakes up from sleep, their connections are usually dead by then. Uptimes are re-baselined and nodes re-pinged either way";
+pub const GUPAX_COLORBLIND: &str = "Use a color-blind friendly palette for the P2Pool/XMRig status in the bottom bar and tabs, and also show the state as a letter: [A]live, [S]yncing/not mining, [W]orking (starting/stopping), [D]ead, [F]ailed";
+pub const GUPAX_TIMER: &str = "Stop or pause mining once, after a set number of hours. Counts down in the bottom bar, closing Gupax cancels it";
+pub const GUPAX_TIMER_HOURS: &str = "How long to keep mining, starting from when you press [Start]";
+pub const GUPAX_TIMER_ACTION: &str = "What to do when the timer runs out. [Pause XMRig] keeps XMRig and P2Pool running but stops hashing, resume it with [r] in the XMRig console";
+pub const GUPAX_TIME_CHECK: &str = "Every few hours, compare the system clock against an NTP server (pool.ntp.org) and show a warning if it's off by more than a minute. P2Pool rejects shares/peers with badly skewed timestamps";
+pub const BINARY_CHANGED: &str = "This binary is not the one Gupax started (or installed) last time, it was replaced outside of Gupax. If you didn't update it yourself, it may have been tampered with: trojanized miners are a common way malware spreads. Only trust it if you know where it came from.";
+pub const BINARY_CHANGED_TRUST: &str = "Remember the new binary's SHA-256 as trusted and start it";
+pub const QUARANTINE_STEPS_WINDOWS: &str = r#"To add an exclusion in Windows Defender:
+1. Open [Windows Security] -> [Virus & threat protection] -> [Protection history] and [Restore] the quarantined file
+2. Go to [Virus & threat protection settings] -> [Manage settings] -> [Exclusions] -> [Add or remove exclusions]
+3. Add the folder Gupax is in as a [Folder] exclusion
+4. Press [Re-check path], or re-extract the bundle if the file was deleted
+Other antiviruses have the same steps under a different name."#;
+pub const QUARANTINE_STEPS: &str = "Restore the file from your antivirus' quarantine (or reinstall it), add the folder it's in as an exclusion, then press [Re-check path].";
+pub const QUARANTINE_RECHECK: &str =
+    "Check if the binary is back (restored from quarantine) and start it again";
+pub const GUPAX_USAGE_STATS: &str = "Send anonymous usage stats once on startup to help decide what to work on: Gupax version, OS, CPU architecture, CPU family and which features are on. No addresses, IPs, nodes, pools, hashrates or IDs are ever sent. Goes over Tor if [Update via Tor] is on. Off by default, see [Preview] for the exact data";
+pub const GUPAX_TOML: &str = "Edit [state.toml] directly. It's checked the same way the file is on startup and only written once it's valid";
+pub const GUPAX_TOML_RELOAD: &str =
+    "Replace the text with the saved settings, discarding edits made here";
+pub const GUPAX_TOML_APPLY: &str =
+    "Save this as [state.toml] and use it right away, replacing all saved and unsaved settings";
+pub const GUPAX_TOML_MERGED: &str =
+    "missing settings will use their defaults and unknown ones are dropped";
+pub const GUPAX_USAGE_STATS_PREVIEW: &str = "This is exactly what gets sent on startup if [Usage stats] is on, built from your saved settings:";
+pub const GUPAX_CONSOLE_HISTORY: &str = "Save the commands sent to P2Pool/XMRig to [p2pool_history.txt]/[xmrig_history.txt] in the Gupax data folder, so Up/Down in the console input still finds them after restarting Gupax";
+pub const GUPAX_EVENT_LOG: &str = "Also save the [Status/Events] timeline to [events.log] in the Gupax data folder, so it's still there after restarting Gupax";
+pub const GUPAX_CONSOLE_COLORS: &str = "Render the colors P2Pool/XMRig print in the consoles (green for accepted shares, red for errors, etc). Disable to show plain text";
+pub const GUPAX_WINDOW_STATS: &str = "Show live stats (hashrate, expected share time, failed processes) in the window title so they are visible in the taskbar while Gupax is in the background. Gupax will also ask for attention if P2Pool/XMRig fails while unfocused";
 pub const GUPAX_AUTO_P2POOL:      &str = "Automatically start P2Pool on Gupax startup. If you are using [P2Pool Simple], this will NOT wait for your [Auto-Ping] to finish, it will start P2Pool on the pool you already have selected. This option will fail if your P2Pool settings aren't valid.";
 pub const GUPAX_AUTO_XMRIG:       &str = "Automatically start XMRig on Gupax startup. This option will fail if your XMRig settings aren't valid.";
 pub const GUPAX_ADJUST: &str = "Adjust and set the width/height of the Gupax window";
 pub const GUPAX_WIDTH: &str = "Set the width of the Gupax window";
 pub const GUPAX_HEIGHT: &str = "Set the height of the Gupax window";
 pub const GUPAX_SCALE: &str =
-    "Set the resolution scaling of the Gupax window (resize window to re-apply scaling)";
+    "Set the resolution scaling of the Gupax window on the current display (resize window to re-apply scaling). Each display remembers its own scaling and it's re-applied when the window moves to that display";
 pub const GUPAX_LOCK_WIDTH: &str =
     "Automatically match the HEIGHT against the WIDTH in a 4:3 ratio";
 pub const GUPAX_LOCK_HEIGHT: &str =
@@ -273,11 +424,21 @@ pub const GUPAX_LOCK_HEIGHT: &str =
 pub const GUPAX_NO_LOCK: &str = "Allow individual selection of width and height";
 pub const GUPAX_SET: &str = "Set the width/height of the Gupax window to the current values";
 pub const GUPAX_TAB: &str = "Set the default tab Gupax starts on";
+pub const ABOUT_CONFIGURED_VERSION: &str = "The version of the binary at the configured P2Pool/XMRig path, next to the version this Gupax was originally bundled with. Include both when reporting issues";
 pub const GUPAX_TAB_ABOUT: &str = "Set the tab Gupax starts on to: About";
 pub const GUPAX_TAB_STATUS: &str = "Set the tab Gupax starts on to: Status";
 pub const GUPAX_TAB_GUPAX: &str = "Set the tab Gupax starts on to: Gupax";
 pub const GUPAX_TAB_P2POOL: &str = "Set the tab Gupax starts on to: P2Pool";
 pub const GUPAX_TAB_XMRIG: &str = "Set the tab Gupax starts on to: XMRig";
+pub const GUPAX_NUMBER_FORMAT: &str = "How numbers are displayed in the [Status] tab, payout logs and benchmarks. This only changes the display, files on disk always use [1,234.5]";
+pub const GUPAX_WORKDIR: &str = "The directory the process runs in, where it keeps its cache/logs, e.g: a dedicated disk. Leave empty to use the directory the binary is in. Relative paths are relative to the binary. It must already exist";
+pub const GUPAX_WORKDIR_MISSING: &str =
+    "The working directory doesn't exist, create it or leave it empty (Advanced tab)";
+pub const GUPAX_ENV_KEY: &str =
+    "The variable's name, letters, digits and [_] only. Red names are ignored";
+pub const GUPAX_ENV_ADD: &str = "Add an environment variable that's set when starting the process, e.g: [LD_PRELOAD] to use another memory allocator, or [LC_ALL=C] to fix locale issues. On Linux/macOS, XMRig gets them through [env] since [sudo/doas/pkexec] don't pass the environment along";
+pub const GUPAX_HASHRATE_UNIT: &str = "Which unit hashrates are displayed in everywhere (Status, benchmarks, window title, reports). [Auto] lets each stat pick its own, [Raw] shows plain H/s without scaling or separators";
+pub const GUPAX_SHORT_TIME: &str = "Display uptime and other durations in a compact form, e.g: [1d 2h 3m] instead of [1 day, 2 hours, 3 minutes]";
 
 pub const GUPAX_SIMPLE: &str = r#"Use simple Gupax settings:
   - Update button
@@ -289,6 +450,7 @@ pub const GUPAX_ADVANCED: &str = r#"Use advanced Gupax settings:
   - Gupax resolution sliders
   - Gupax start-up tab selector"#;
 pub const GUPAX_SELECT: &str = "Open a file explorer to select a file";
+pub const GUPAX_DETECT: &str = "Search for P2Pool/XMRig binaries next to Gupax (bundled), in the PATH and in the common install folders (system). Missing paths are also filled in from these on startup";
 pub const GUPAX_PATH: &str = "Use custom PATHs when looking for P2Pool/XMRig";
 pub const GUPAX_PATH_P2POOL: &str = "The location of the P2Pool binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
 pub const GUPAX_PATH_XMRIG: &str = "The location of the XMRig binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
@@ -296,9 +458,48 @@ pub const GUPAX_PATH_XMRIG: &str = "The location of the XMRig binary: Both absol
 // P2Pool
 pub const P2POOL_MAIN:                   &str = "Use the P2Pool main-chain. This P2Pool finds blocks faster, but has a higher difficulty. Suitable for miners with more than 50kH/s";
 pub const P2POOL_MINI:                   &str = "Use the P2Pool mini-chain. This P2Pool finds blocks slower, but has a lower difficulty. Suitable for miners with less than 50kH/s";
+pub const P2POOL_ADVISOR: &str = "Compares P2Pool Main & Mini for your current hashrate using the sidechain difficulties from p2pool.observer, re-checked every hour while mining. Both pay the same on average, but a block only pays you if you have a share in the PPLNS window, so Main is only recommended if you can expect at least 3 shares in its window at all times";
+pub const P2POOL_ADVISOR_SWITCH: &str =
+    "Select the recommended sidechain, restart P2Pool to apply it";
 pub const P2POOL_OUT: &str = "How many out-bound peers to connect to? (you connecting to others)";
 pub const P2POOL_IN: &str = "How many in-bound peers to allow? (others connecting to you)";
 pub const P2POOL_LOG: &str = "Verbosity of the console log";
+pub const P2POOL_SIMPLE_PEERS: &str = "How many P2Pool peers to connect out to and accept in. Fewer peers use less bandwidth on slow or metered connections, more peers spread shares faster on a good connection. Default: [10]";
+pub const P2POOL_SIMPLE_PEERS_RANGE: std::ops::RangeInclusive<u16> = 10..=50;
+pub const P2POOL_MODE: &str = "P2Pool's [--light-mode] skips keeping its own ~2.3GB RandomX dataset (XMRig has its own), full mode verifies blocks faster and is needed for P2Pool's built-in miner to hash at full speed. [Auto] picks full mode only with 16GB+ of RAM";
+pub const P2POOL_MINER: &str = "Mine with P2Pool's own built-in CPU miner instead of XMRig ([--start-mining]), no admin rights or extra program needed but it's slower than XMRig (no MSR mod/huge pages). Turning it on/off or changing threads while P2Pool is running sends [start_mining]/[stop_mining] to P2Pool";
+pub const P2POOL_MINER_THREADS: &str = "How many CPU threads the built-in miner uses";
+pub const P2POOL_MINER_HASHRATE: &str =
+    "The built-in miner's hashrate, Gupax types [status] into P2Pool every minute to get it";
+pub const STATUS_P2POOL_MINER: &str = "The hashrate and threads of P2Pool's built-in miner";
+pub const P2POOL_NO_CACHE: &str = "Start P2Pool with [--no-cache]: don't save the sidechain to [p2pool.cache], P2Pool will re-sync it from peers on every start";
+pub const P2POOL_NO_DNS: &str = "Start P2Pool with [--no-dns]: don't use DNS queries, the node and peers have to be IP addresses. Useful with Tor/proxies to avoid DNS leaks";
+pub const P2POOL_P2P_PORT: &str = "Start P2Pool with [--p2p 0.0.0.0:<PORT>] to listen for other P2Pool peers on this port; [1-65535]. Leave empty to use P2Pool's default (37889 Main, 37888 Mini)";
+pub const P2POOL_TLS: &str = "Run a TLS terminator in front of P2Pool's stratum while P2Pool is running, so other rigs can mine to it over an untrusted network with XMRig's [--tls --tls-fingerprint=<FINGERPRINT>]. The certificate is self-signed. P2Pool's plain stratum [3333] is still open, firewall it (or add [--stratum 127.0.0.1:3333] to the custom arguments) so rigs can't skip TLS";
+pub const P2POOL_TLS_PORT: &str =
+    "The port the TLS terminator listens on, [1-65535]. It can't be P2Pool's stratum [3333]";
+pub const P2POOL_TLS_FINGERPRINT: &str = "SHA-256 fingerprint of the certificate, pass this to XMRig's [--tls-fingerprint] on the other rigs";
+pub const P2POOL_TLS_NEW_CERT: &str = "Generate a new self-signed certificate. Rigs pinned to the old fingerprint will have to be updated";
+pub const P2POOL_SSH: &str = "Reach the manual node through an SSH tunnel, for a trusted node that's firewalled except for SSH. Gupax forwards random local ports to the node's RPC/ZMQ ports on the SSH host and starts P2Pool with [--host 127.0.0.1] and those ports instead of the selected node's. Reconnects automatically if the connection drops. Only used in [Advanced] without custom arguments";
+pub const P2POOL_SSH_HOST: &str = "The SSH server's IP/domain, and its SSH port";
+pub const P2POOL_SSH_USER: &str = "The SSH user to log in as";
+pub const P2POOL_SSH_KEY: &str = "Path to the SSH private key, only key auth is supported and the key can't have a passphrase. Empty uses [~/.ssh/id_ed25519]";
+pub const P2POOL_SSH_PORTS: &str =
+    "The node's RPC/ZMQ ports on the SSH host, they're reached as [127.0.0.1:<PORT>] from there";
+pub const P2POOL_NODE_RPC_CALL: &str = "Read-only RPC call to send to the node P2Pool is using";
+pub const P2POOL_CONTRIBUTE: &str = "Running a public Monero node with ZMQ? Check it from here and send it in for the community node list";
+pub const P2POOL_CONTRIBUTE_IP: &str =
+    "The node's public domain or IP, the checks connect to it the way other Gupax users would";
+pub const P2POOL_CONTRIBUTE_CHECK: &str = "Checks the address is public and not listed yet, then runs [get_info] against the RPC port (mainnet, synced, restricted RPC) and does a ZMQ handshake on the ZMQ port";
+pub const P2POOL_CONTRIBUTE_SUBMIT: &str =
+    "Open a GitHub issue, paste the copied line and a way to contact you";
+pub const P2POOL_NODE_RPC_RUN: &str = "Send the RPC call and show the result. Useful for telling whether the node (not P2Pool) is the problem: not synced, no peers, stuck on an old block, or restricted RPC";
+pub const P2POOL_NODE_RPC_INVALID: &str = "The selected node's IP/RPC port is invalid";
+pub const P2POOL_NODE_CHANGED: &str = "The selected node was changed while P2Pool was running. P2Pool keeps using the node it was started with until it's restarted";
+pub const P2POOL_NODE_CHANGED_RESTART: &str =
+    "Save the P2Pool settings and restart P2Pool with the selected node";
+pub const P2POOL_SSH_TRUST: &str = "The server's host key isn't in [~/.ssh/known_hosts]. If this fingerprint matches the server's (e.g: [ssh-keygen -lf /etc/ssh/ssh_host_ed25519_key.pub] on it), trust it";
+pub const P2POOL_LOG_MAX: &str = "P2Pool's [p2pool.log] grows forever. If it's bigger than this when P2Pool starts, Gupax moves it to [p2pool.log.old] first. [0] never does";
 pub const P2POOL_AUTO_NODE: &str = "Automatically ping the remote Monero nodes at Gupax startup";
 pub const P2POOL_AUTO_SELECT: &str =
     "Automatically select the fastest remote Monero node after pinging";
@@ -307,7 +508,11 @@ pub const P2POOL_BACKUP_HOST_SIMPLE: &str = r#"Automatically switch to the other
 Note: you must ping the remote nodes or this feature will default to only using the currently selected node."#;
 pub const P2POOL_BACKUP_HOST_ADVANCED: &str =
     "Automatically switch to the other nodes in your list if the current one is down.";
+pub const P2POOL_BACKUP_HOST_UNSUPPORTED: &str =
+    "This P2Pool version does not support backup hosts";
 pub const P2POOL_SELECT_FASTEST: &str = "Select the fastest remote Monero node";
+pub const P2POOL_LOCAL_NODE_GUIDE: &str =
+    "Open the guide on running your own Monero node, a local node has almost no latency";
 pub const P2POOL_SELECT_RANDOM: &str = "Select a random remote Monero node";
 pub const P2POOL_SELECT_LAST: &str = "Select the previous remote Monero node";
 pub const P2POOL_SELECT_NEXT: &str = "Select the next remote Monero node";
@@ -323,14 +528,37 @@ Running and using your own local Monero node improves privacy and ensures your c
 
 For a simple guide, see the [Running a Local Monero Node] documentation by clicking this message."#;
 
-pub const P2POOL_INPUT: &str = "Send a command to P2Pool";
-pub const P2POOL_ARGUMENTS: &str = r#"Note: [--no-color] & [--data-api <PATH>] & [--local-api] must be set so that the [Status] tab can work!
+pub const P2POOL_INPUT: &str =
+    "Send a command to P2Pool. Up/Down go through the previous commands, Tab completes a command";
+// P2Pool's console commands, for [Tab] completion.
+pub const P2POOL_COMMANDS: [&str; 14] = [
+    "help",
+    "status",
+    "loglevel",
+    "addpeers",
+    "droppeers",
+    "peers",
+    "workers",
+    "bans",
+    "outpeers",
+    "inpeers",
+    "start_mining",
+    "stop_mining",
+    "exit",
+    "version",
+];
+pub const P2POOL_NETWORK: &str = "Which Monero network to use. Testnet/Stagenet are for development and their XMR is worthless. P2Pool follows the network of the wallet address, so the address and the Monero node must be on this network too. Switching moves the node ports to the network's defaults (18081/28081/38081)";
+pub const P2POOL_API_DIR: &str = "The directory P2Pool writes its API files to with [--data-api], which the [Status] tab reads from. Leave empty to use the directory the P2Pool binary is in. Relative paths are relative to the P2Pool binary.
+
+Set a different directory per instance to run multiple Gupax/P2Pool instances side-by-side (each P2Pool also needs its own ports).";
+pub const P2POOL_ARGUMENTS: &str = r#"Note: [--data-api <PATH>] & [--local-api] must be set so that the [Status] tab can work!
 
 Start P2Pool with these arguments and override all below settings"#;
 pub const P2POOL_SIMPLE: &str = r#"Use simple P2Pool settings:
   - Remote remote Monero node
   - Default P2Pool settings + Mini
-  - Backup host setting"#;
+  - Backup host setting
+  - Peers setting"#;
 pub const P2POOL_ADVANCED: &str = r#"Use advanced P2Pool settings:
   - Terminal input
   - Overriding command arguments
@@ -343,6 +571,8 @@ pub const P2POOL_NAME: &str = "Add a unique name to identify this node; Only [A-
 pub const P2POOL_NODE_IP: &str = "Specify the Monero Node IP to connect to with P2Pool; It must be a valid IPv4 address or a valid domain name; Max length = 255 characters";
 pub const P2POOL_RPC_PORT: &str = "Specify the RPC port of the Monero node; [1-65535]";
 pub const P2POOL_ZMQ_PORT: &str = "Specify the ZMQ port of the Monero node; [1-65535]";
+pub const P2POOL_OWN_NODE: &str = "Use your own Monero node instead of the community nodes below. Its ZMQ port must be enabled, e.g: [--zmq-pub tcp://0.0.0.0:18083] for monerod. Saved separately from the [Advanced] node list";
+pub const P2POOL_ZMQ_HEALTH: &str = "ZMQ health of the running P2Pool, taken from its log. A node can answer RPC while its ZMQ port is closed or not enabled, then P2Pool never hears about new blocks. Green = ZMQ messages are arriving, Yellow = nothing from ZMQ in the last 10 minutes, Red = P2Pool reported a ZMQ error, Gray = nothing seen yet";
 pub const P2POOL_PATH_NOT_FILE: &str = "P2Pool binary not found at the given PATH in the Gupax tab! To fix: goto the [Gupax Advanced] tab, select [Open] and specify where P2Pool is located.";
 pub const P2POOL_PATH_NOT_VALID: &str = "P2Pool binary at the given PATH in the Gupax tab doesn't look like P2Pool! To fix: goto the [Gupax Advanced] tab, select [Open] and specify where P2Pool is located.";
 pub const P2POOL_PATH_OK: &str = "P2Pool was found at the given PATH";
@@ -353,6 +583,19 @@ pub const LIST_ADD: &str = "Add the current values to the list";
 pub const LIST_SAVE: &str = "Save the current values to the already existing entry";
 pub const LIST_DELETE: &str = "Delete the currently selected entry";
 pub const LIST_CLEAR: &str = "Clear all current values";
+pub const LIST_DUPLICATE: &str = "Copy the currently selected entry into the fields above under a new name, change what's needed then [Add] it";
+pub const LIST_TEMPLATE: &str =
+    "Fill the fields above with a common setup, fill in what's missing then [Add] it";
+pub const LIST_TAGS: &str = "Optional tags to group this entry, separated by commas, e.g: [home, fast]; Same characters as names, max 10 tags";
+pub const LIST_FILTER: &str = "Only show entries whose name, IP or tags contain every word typed here; [#tag] only matches that exact tag";
+pub const LIST_SORT_INDEX: &str = "Show the list in its saved order";
+pub const LIST_SORT_NAME: &str = "Sort the list by name";
+pub const LIST_SORT_LATENCY: &str =
+    "Sort the list by the last measured latency, entries that weren't pinged go last";
+pub const XMRIG_PING_POOLS: &str = "Measure how long it takes to connect to every pool in the list. Green = <300ms, Yellow = <500ms, Red = <5000ms, Black = unreachable";
+pub const XMRIG_PING_STRATUM: &str = "Also send a stratum login and wait for the answer, to check that a pool (and not just an open port) is listening. Takes a little longer";
+pub const LIST_IMPORT_NODE: &str = "Import nodes from a CSV file with the columns [name,ip,rpc,zmq,tags] (tags are optional, separated by [;]). Duplicate entries are skipped, names that already exist with different values get a [_2] suffix";
+pub const LIST_IMPORT_POOL: &str = "Import pools from an XMRig [config.json] or a CSV file with the columns [name,ip,port,rig,tags] (rig and tags are optional, tags separated by [;]). Duplicate entries are skipped, names that already exist with different values get a [_2] suffix";
 
 // XMRig
 pub const XMRIG_SIMPLE: &str = r#"Use simple XMRig settings:
@@ -368,15 +611,22 @@ pub const XMRIG_ADVANCED: &str = r#"Use advanced XMRig settings:
   - Custom HTTP API IP/Port
   - TLS setting
   - Keepalive setting"#;
-pub const XMRIG_INPUT: &str = "Send a command to XMRig";
-pub const XMRIG_ARGUMENTS: &str = r#"Note: [--no-color] & [--http-host <IP>] & [--http-port <PORT>] must be setso that the [Status] tab can work!
+pub const XMRIG_INPUT: &str = "Send a command to XMRig. Up/Down go through the previous commands, Tab completes one from them";
+pub const XMRIG_WATCH_ONLY: &str = "Don't start XMRig, only show the stats of an already running XMRig (e.g: a system service) by reading its HTTP API. [Start] begins watching, [Stop] stops watching, the XMRig itself is never started or stopped by Gupax. The settings below are ignored in this mode.";
+pub const XMRIG_WATCH_URL: &str = "The [IP:Port] of the XMRig HTTP API to watch, set with [--http-host] & [--http-port] or in XMRig's config [http] section. XMRigs on other machines behind a reverse proxy can be watched over HTTPS with a full URL, e.g: [https://rigs.example.com/rig1], the certificate must be valid";
+pub const XMRIG_WATCH_TOKEN: &str = "The [access-token] of the XMRig HTTP API, leave empty if it doesn't have one. A reverse proxy with its own auth can be given the whole header value instead, e.g: [Basic dXNlcjpwYXNz]. This is saved in [state.toml] as plain text.";
+pub const XMRIG_ARGUMENTS: &str = r#"Note: [--http-host <IP>] & [--http-port <PORT>] must be set so that the [Status] tab can work!
 
 Start XMRig with these arguments and override all below settings"#;
 pub const XMRIG_ADDRESS:        &str = "Specify which Monero address to payout to. This does nothing if mining to P2Pool since the address being paid out to will be the one P2Pool started with. This doubles as a rig identifier for P2Pool and some pools.";
 pub const XMRIG_NAME:           &str = "Add a unique name to identify this pool; Only [A-Za-z0-9-_.] and spaces allowed; Max length = 30 characters";
 pub const XMRIG_IP:             &str = "Specify the pool IP to connect to with XMRig; It must be a valid IPv4 address or a valid domain name; Max length = 255 characters";
 pub const XMRIG_PORT: &str = "Specify the port of the pool; [1-65535]";
-pub const XMRIG_RIG:            &str = "Add an optional rig ID. This will be the name shown on the pool; Only [A-Za-z0-9-_] and spaces allowed; Max length = 30 characters";
+pub const XMRIG_RIG:            &str = "Add an optional rig ID. This will be the name shown on the pool; Only [A-Za-z0-9-_] and spaces allowed; Max length = 30 characters. [{hostname}], [{profile}] and [{threads}] are filled in when XMRig starts, e.g: [{hostname}-{threads}t]";
+pub const XMRIG_LOAD_PAUSE: &str = "Pause mining while other programs (backups, compiles, Windows Update, etc) use more than this % of the CPU, and resume once they use less for the same amount of time. XMRig's own usage isn't counted. [0] turns this off";
+pub const XMRIG_LOAD_PAUSE_SECS: &str =
+    "How many seconds the load has to stay above (or below) the limit before pausing (or resuming)";
+pub const XMRIG_PRIORITY: &str = "Set XMRig's process priority: [0] is idle, [2] is normal, up to [5] highest. Unchecked leaves it to XMRig";
 #[cfg(not(target_os = "linux"))]
 pub const XMRIG_PAUSE: &str =
     "THIS SETTING IS DISABLED IF SET TO [0]. Pause mining if user is active, resume after";
@@ -384,14 +634,71 @@ pub const XMRIG_API_IP: &str =
     "Specify which IP to bind to for XMRig's HTTP API; If empty: [localhost/127.0.0.1]";
 pub const XMRIG_API_PORT: &str =
     "Specify which port to bind to for XMRig's HTTP API; If empty: [18088]";
+pub const XMRIG_API_ENABLED: &str = "Turn on XMRig's HTTP API. Without it Gupax falls back to reading the hashrate/shares XMRig prints to its console, the [XMRig details] in the [Status] tab will be missing";
+pub const XMRIG_API_RESTRICTED: &str = "Only allow reading stats from XMRig's HTTP API. Unchecking this passes [--http-no-restricted] which lets anyone who can reach the API control (and reconfigure) XMRig, only do this on a trusted network";
+pub const XMRIG_API_CONFLICT: &str = "XMRig's HTTP API port is already used by";
 pub const XMRIG_TLS: &str = "Enable SSL/TLS connections (needs pool support)";
+pub const XMRIG_TLS_UNSUPPORTED: &str =
+    "This XMRig binary was built without TLS support (no OpenSSL)";
 pub const XMRIG_KEEPALIVE: &str = "Send keepalive packets to prevent timeout (needs pool support)";
+pub const XMRIG_1GB_PAGES: &str = "Use 1GB huge pages for the RandomX dataset with [--randomx-1gb-pages], a few % faster. Linux only, needs root and 1GB page support from the CPU. The result XMRig reports on startup is shown next to it";
+pub const XMRIG_HUGE_PAGES_JIT: &str = "Use huge pages for RandomX JIT code with [--huge-pages-jit], slightly faster but can be unstable on some systems. XMRig doesn't report a result for this";
+pub const XMRIG_AFFINITY: &str = "The CPUs this XMRig may run on, as a list like [0-15,32-47] (XMRig's [--cpu-affinity] only goes up to CPU 63). On multi-socket machines, pinning each instance to one NUMA node avoids slow cross-node memory access. Empty lets the OS decide";
+pub const XMRIG_INSTANCE_API_PORT: &str = "The HTTP API port of this instance, it has to differ from the main XMRig's and the other instances'. Empty turns its API off, its stats then only come from its output";
+pub const XMRIG_INSTANCE_ADD: &str = "Add another XMRig that's started, stopped and restarted together with the main one, e.g: one per NUMA node on multi-socket machines. It mines to the same pool with the same settings, only its threads, CPU affinity and API port differ, and its rig ID gets a [_N] suffix";
+pub const XMRIG_MSR_MOD: &str = "Let XMRig apply the MSR mod, which can increase hashrate by up to 15%. Needs root/admin. Unchecking this passes [--randomx-no-msr]. The result XMRig reports on startup is shown next to it";
+pub const XMRIG_VIRT: &str =
+    "RandomX loses a lot of hashrate in a VM/container that isn't set up for it:";
+pub const STATUS_SUBMENU_VIRT: &str =
+    "Gupax is running in a VM, which usually reaches ~85% of the same CPU on bare metal at best";
+pub const XMRIG_DONATE_LEVEL: &str = "The % of time XMRig mines for its developers instead of you (e.g: [1%] is 1 minute out of every 100), passed with [--donate-level]. Official XMRig builds won't go below 1%";
+pub const XMRIG_DONATE_NONZERO: &str = "XMRig is mining this % of the time for its developers. Lower it with [Donate level] in [XMRig Advanced] (or [--donate-level] in your own arguments/config). Official XMRig builds have a 1% minimum, going to 0% needs an XMRig built from source with [kMinimumDonateLevel] and [kDefaultDonateLevel] set to 0 in [src/donate.h]";
+pub const XMRIG_DONATE_ZERO: &str = "XMRig isn't donating any of its hashrate";
+pub const XMRIG_DONATE_UNKNOWN: &str =
+    "XMRig hasn't reported its donation level yet (it's not running, or it's too old to report it)";
 pub const XMRIG_THREADS: &str = "Number of CPU threads to use for mining";
+pub const XMRIG_PRESET: &str = "Eco, Standard and Max set the CPU usage to 25%, 50% and 100%. The hashrate and power draw next to them are rough guesses from the CPU benchmarks, not measurements";
+pub const XMRIG_CPU_USAGE: &str = "How much of your CPU to use for mining, like XMRig's [--cpu-max-threads-hint]. This is turned into a thread count and moves the [Threads] slider with it";
 pub const XMRIG_PATH_NOT_FILE:  &str = "XMRig binary not found at the given PATH in the Gupax tab! To fix: goto the [Gupax Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_NOT_VALID: &str = "XMRig binary at the given PATH in the Gupax tab doesn't look like XMRig! To fix: goto the [Gupax Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_OK: &str = "XMRig was found at the given PATH";
 pub const XMRIG_PATH_EMPTY:     &str = "XMRig PATH is empty! To fix: goto the [Gupax Advanced] tab, select [Open] and specify where XMRig is located.";
 
+// XMRig-Proxy
+pub const PROXY_ALIVE: &str = "XMRig-Proxy is online and connected to its pool";
+pub const PROXY_DEAD: &str = "XMRig-Proxy is offline";
+pub const PROXY_FAILED: &str = "XMRig-Proxy is offline and failed when exiting";
+pub const PROXY_MIDDLE: &str = "XMRig-Proxy is in the middle of (re)starting/stopping";
+pub const PROXY_NOT_MINING: &str = "XMRig-Proxy is online, but not connected to any pool";
+pub const PROXY_CONNECT: &str = "Point the XMRig's on your other rigs at this [IP:Port] (e.g: [--url 192.168.1.2:3355]). If it shows [0.0.0.0], the LAN address of this machine couldn't be found, use its IP instead";
+pub const PROXY_INPUT: &str = "Send a command to XMRig-Proxy. Up/Down go through the previous commands, Tab completes one from them";
+pub const PROXY_ARGUMENTS: &str = r#"WARNING: Use [--no-color] and make sure to set [--http-host <IP>] & [--http-port <PORT>] so that the stats can work!
+This will override the rest of the XMRig-Proxy settings"#;
+pub const PROXY_ADDRESS: &str = "The Monero address sent to the pool as the [--user]. Leave empty when mining to P2Pool, it pays out to the address P2Pool was started with";
+pub const PROXY_UPSTREAM_IP: &str =
+    "The pool XMRig-Proxy connects to. To mine to P2Pool on this machine, use [localhost]";
+pub const PROXY_UPSTREAM_PORT: &str =
+    "The port of the pool XMRig-Proxy connects to. P2Pool's stratum is [3333] by default";
+pub const PROXY_BIND_IP: &str = "The IP your rigs connect to. [0.0.0.0] accepts connections from every network interface, [127.0.0.1] only from this machine";
+pub const PROXY_BIND_PORT: &str =
+    "The port your rigs connect to. Allow it through this machine's firewall";
+pub const PROXY_API_IP: &str =
+    "The IP XMRig-Proxy's HTTP API listens on, Gupax reads the stats from it";
+pub const PROXY_API_PORT: &str =
+    "The port XMRig-Proxy's HTTP API listens on, Gupax reads the stats from it";
+pub const PROXY_MODE: &str = "NiceHash: many rigs share one pool connection, each gets its own nonce space (recommended). Simple: one pool connection per rig, for pools that need that";
+pub const PROXY_TLS: &str = "Connect to the pool with SSL/TLS (needs pool support)";
+pub const PROXY_KEEPALIVE: &str =
+    "Send keepalive packets to the pool to prevent timeout (needs pool support)";
+pub const PROXY_WORKERS: &str =
+    "The rigs XMRig-Proxy has seen since it started, greyed out if they aren't connected anymore";
+pub const PROXY_PATH: &str = "The location of the XMRig-Proxy binary: Both absolute and relative paths are accepted; A red [X] will appear if there is no file found at the given path";
+pub const PROXY_PATH_OK: &str = "XMRig-Proxy was found at the given PATH";
+pub const PROXY_PATH_EMPTY: &str = "XMRig-Proxy PATH is empty! To fix: select [Open] in the [XMRig-Proxy] tab and specify where XMRig-Proxy is located.";
+pub const PROXY_PATH_NOT_FILE: &str = "XMRig-Proxy binary not found at the given PATH! To fix: select [Open] in the [XMRig-Proxy] tab and specify where XMRig-Proxy is located.";
+pub const PROXY_PATH_NOT_VALID: &str = "XMRig-Proxy binary at the given PATH doesn't look like XMRig-Proxy! To fix: select [Open] in the [XMRig-Proxy] tab and specify where XMRig-Proxy is located.";
+pub const PROXY_PORT: &str = "The upstream or bind port isn't valid";
+
 // CLI argument messages
 pub const ARG_HELP: &str = r#"USAGE: ./gupax [--flag]
 
@@ -401,15 +708,32 @@ pub const ARG_HELP: &str = r#"USAGE: ./gupax [--flag]
     --nodes           Print the manual node list
     --payouts         Print the P2Pool payout log, payout count, and total XMR mined
     --no-startup      Disable all auto-startup settings for this instance (auto-update, auto-ping, etc)
+    --demo            Simulate P2Pool/XMRig with fake stats instead of starting them, no binaries needed
+    --profile <name>  Use the settings saved as profile [name] instead of [state.toml], changes are saved to it
+    --save-profile <name>  Save the current settings (or the [--profile] before it) as profile [name]
+    --profiles        Print the saved profile names
     --reset-state     Reset all Gupax state (your settings)
     --reset-nodes     Reset the manual node list in the [P2Pool] tab
     --reset-pools     Reset the manual pool list in the [XMRig] tab
     --reset-payouts   Reset the permanent P2Pool stats that appear in the [Status] tab
     --reset-all       Reset the state, manual node list, manual pool list, and P2Pool stats
 
+Profiles are saved as [profiles/<name>.toml] in the data folder, machines can
+share the same Gupax install & data and only differ by the [--profile] they're
+started with. Start-on-login keeps the [--profile] Gupax was started with.
+
+To use a different data folder (e.g: if the default one is read-only),
+start Gupax with the environment variable [GUPAX_DATA_DIR] set to it:
+    GUPAX_DATA_DIR=/path/to/folder ./gupax
+
 To view more detailed console debug information, start Gupax with
 the environment variable [RUST_LOG] set to a log level like so:
-    RUST_LOG=(trace|debug|info|warn|error) ./gupax"#;
+    RUST_LOG=(trace|debug|info|warn|error) ./gupax
+
+If the window is blank or Gupax crashes on start, the graphics settings
+in the [Gupax] tab can be overridden for one run with:
+    GUPAX_X11=1 ./gupax                     (Linux: use X11/XWayland instead of Wayland)
+    GUPAX_RENDERER=(auto|glow|wgpu) ./gupax"#;
 pub const ARG_COPYRIGHT: &str = r#"Gupax is licensed under GPLv3.
 For more information, see link below:
 <https://github.com/hinto-janai/gupax>"#;
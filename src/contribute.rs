@@ -0,0 +1,365 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Community node contribution helper.
+//
+// [REMOTE_NODES] only grows when someone running a public, ZMQ-enabled node
+// sends it in. This runs the same checks a maintainer would against the
+// node's public address (RPC [get_info] + a ZMQ handshake) and, if they all
+// pass, prints the line to add to [REMOTE_NODES] in [src/node.rs].
+
+use crate::{constants::*, macros::*, node::REMOTE_NODES};
+use egui::{Label, RichText, TextEdit};
+use log::*;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+const ZMQ_TIMEOUT: Duration = Duration::from_secs(10);
+// What [REMOTE_NODES] accepts, see the [validate_node_ips] test.
+const RPC_PORTS: [&str; 2] = ["18081", "18089"];
+const ZMQ_PORTS: [&str; 2] = ["18083", "18084"];
+// ZMTP 3.x greeting signature, [0xFF] + 8 padding bytes + [0x7F].
+const ZMTP_SIGNATURE: [u8; 10] = [0xFF, 0, 0, 0, 0, 0, 0, 0, 1, 0x7F];
+const SUBMIT_URL: &str = "https://github.com/hinto-janai/gupax/issues/new";
+
+//---------------------------------------------------------------------------------------------------- Check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, ok: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok,
+            detail: detail.into(),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- NodeCheck
+#[derive(Debug, Default)]
+pub struct NodeCheck {
+    pub ip: String,
+    pub location: String,
+    pub rpc: String,
+    pub zmq: String,
+    pub running: bool,
+    pub checks: Vec<Check>,
+    pub snippet: String, // Empty until every check passed
+}
+
+impl NodeCheck {
+    pub fn new() -> Self {
+        Self {
+            rpc: RPC_PORTS[0].to_string(),
+            zmq: ZMQ_PORTS[0].to_string(),
+            ..Default::default()
+        }
+    }
+
+    // Checks that don't need the network.
+    pub fn local_checks(ip: &str, location: &str, rpc: &str, zmq: &str) -> Vec<Check> {
+        let ip = ip.trim();
+        let listed = REMOTE_NODES
+            .iter()
+            .any(|(i, _, _, _)| i.eq_ignore_ascii_case(ip));
+        vec![
+            Check::new(
+                "Public address",
+                is_public(ip),
+                "Must be a domain or public IP others can reach, not a LAN/localhost address",
+            ),
+            Check::new(
+                "Not listed yet",
+                !listed,
+                format!("[{}] is already in the community node list", ip),
+            ),
+            Check::new(
+                "Location",
+                !location.trim().is_empty(),
+                "The country the node is in, e.g: [Germany]",
+            ),
+            Check::new(
+                "Standard ports",
+                RPC_PORTS.contains(&rpc) && ZMQ_PORTS.contains(&zmq),
+                format!(
+                    "The list only takes RPC {:?} and ZMQ {:?}",
+                    RPC_PORTS, ZMQ_PORTS
+                ),
+            ),
+        ]
+    }
+
+    // What a node in the list has to look like over RPC.
+    pub fn rpc_checks(json: &serde_json::Value) -> Vec<Check> {
+        let result = json.get("result");
+        let str = |key: &str| result.and_then(|r| r.get(key)).and_then(|v| v.as_str());
+        let bool = |key: &str| result.and_then(|r| r.get(key)).and_then(|v| v.as_bool());
+        vec![
+            Check::new(
+                "RPC status",
+                str("status") == Some("OK"),
+                format!("[get_info] status: {}", str("status").unwrap_or("none")),
+            ),
+            Check::new(
+                "Mainnet",
+                str("nettype") == Some("mainnet"),
+                format!("Network: {}", str("nettype").unwrap_or("unknown")),
+            ),
+            Check::new(
+                "Synchronized",
+                bool("synchronized") == Some(true) && bool("busy_syncing") != Some(true),
+                "The node has to be fully synced",
+            ),
+            Check::new(
+                "Restricted RPC",
+                bool("restricted") == Some(true),
+                "Public nodes should run [--restricted-rpc]",
+            ),
+        ]
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(check: &Arc<Mutex<Self>>) {
+        let (ip, location, rpc, zmq) = {
+            let mut lock = lock!(check);
+            lock.running = true;
+            lock.checks.clear();
+            lock.snippet.clear();
+            (
+                lock.ip.trim().to_string(),
+                lock.location.trim().to_string(),
+                lock.rpc.trim().to_string(),
+                lock.zmq.trim().to_string(),
+            )
+        };
+        info!(
+            "Contribute | Checking [{}] RPC [{}] ZMQ [{}]...",
+            ip, rpc, zmq
+        );
+        let check = Arc::clone(check);
+        std::thread::spawn(move || {
+            let mut checks = Self::local_checks(&ip, &location, &rpc, &zmq);
+            let node = format!("{}:{}", ip, rpc);
+            match crate::node::NodeRpc::request(&node, "get_info", "{}") {
+                Ok(json) => checks.extend(Self::rpc_checks(&json)),
+                Err(e) => checks.push(Check::new("RPC reachable", false, e.to_string())),
+            }
+            checks.push(match zmq_handshake(&ip, &zmq) {
+                Ok(()) => Check::new("ZMQ reachable", true, "ZMTP greeting received"),
+                Err(e) => Check::new("ZMQ reachable", false, e.to_string()),
+            });
+            let passed = checks.iter().all(|c| c.ok);
+            info!(
+                "Contribute | [{}] ... {}",
+                ip,
+                if passed { "OK" } else { "FAIL" }
+            );
+            let mut lock = lock!(check);
+            lock.snippet = if passed {
+                snippet(&ip, &location, &rpc, &zmq)
+            } else {
+                String::new()
+            };
+            lock.checks = checks;
+            lock.running = false;
+        });
+    }
+
+    pub fn show(check: &Arc<Mutex<Self>>, width: f32, ui: &mut egui::Ui) {
+        let mut lock = lock!(check);
+        let mut run = false;
+        ui.label(RichText::new(P2POOL_CONTRIBUTE).color(LIGHT_GRAY));
+        ui.horizontal(|ui| {
+            let width = (width / 8.0) - SPACE;
+            ui.add_sized(
+                [width * 3.0, 0.0],
+                TextEdit::hint_text(TextEdit::singleline(&mut lock.ip), "node.example.com"),
+            )
+            .on_hover_text(P2POOL_CONTRIBUTE_IP);
+            ui.add_sized(
+                [width * 1.5, 0.0],
+                TextEdit::hint_text(TextEdit::singleline(&mut lock.location), "Country"),
+            );
+            ui.add_sized([width / 1.5, 0.0], TextEdit::singleline(&mut lock.rpc))
+                .on_hover_text("RPC port");
+            ui.add_sized([width / 1.5, 0.0], TextEdit::singleline(&mut lock.zmq))
+                .on_hover_text("ZMQ port");
+            ui.add_enabled_ui(!lock.running && !lock.ip.trim().is_empty(), |ui| {
+                if ui
+                    .button("Check node")
+                    .on_hover_text(P2POOL_CONTRIBUTE_CHECK)
+                    .clicked()
+                {
+                    run = true;
+                }
+            });
+            if lock.running {
+                ui.spinner();
+            }
+        });
+        lock.ip.truncate(255);
+        lock.location.truncate(64);
+        lock.rpc.truncate(5);
+        lock.zmq.truncate(5);
+        if run {
+            drop(lock);
+            Self::spawn_thread(check);
+            return;
+        }
+        for c in &lock.checks {
+            let (mark, color) = if c.ok { ("✔", GREEN) } else { ("✖", RED) };
+            ui.add(Label::new(
+                RichText::new(format!("{} {} | {}", mark, c.name, c.detail)).color(color),
+            ));
+        }
+        if !lock.snippet.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&lock.snippet).monospace());
+                if ui.button("Copy").clicked() {
+                    ui.output_mut(|o| o.copied_text = lock.snippet.clone());
+                }
+                ui.hyperlink_to("Submit", SUBMIT_URL)
+                    .on_hover_text(P2POOL_CONTRIBUTE_SUBMIT);
+            });
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Helpers
+// Is [ip] something others can connect to? Domains are
+// assumed public, the RPC/ZMQ checks catch the rest.
+pub fn is_public(ip: &str) -> bool {
+    if ip.is_empty() || ip.eq_ignore_ascii_case("localhost") || ip.contains(char::is_whitespace) {
+        return false;
+    }
+    match ip.trim_matches(|c| c == '[' || c == ']').parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast())
+        }
+        Ok(IpAddr::V6(v6)) => {
+            !(v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xfe00) == 0xfc00)
+        }
+        Err(_) => ip.contains('.'),
+    }
+}
+
+// The line to add to [REMOTE_NODES].
+pub fn snippet(ip: &str, location: &str, rpc: &str, zmq: &str) -> String {
+    format!(r#"("{}", "{}", "{}", "{}"),"#, ip, location, rpc, zmq)
+}
+
+// Does [greeting] start like a ZMTP 3.x peer's?
+pub fn zmtp_greeting_ok(greeting: &[u8]) -> bool {
+    greeting.len() >= 10 && greeting[0] == 0xFF && greeting[9] & 0x01 == 0x01
+}
+
+// Connect to the ZMQ port and trade greetings, a plain open
+// port (or the RPC port by mistake) doesn't answer with one.
+fn zmq_handshake(ip: &str, port: &str) -> Result<(), anyhow::Error> {
+    let addr = (ip, port.parse::<u16>()?)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("[{}] did not resolve", ip))?;
+    let mut stream = TcpStream::connect_timeout(&addr, ZMQ_TIMEOUT)?;
+    stream.set_read_timeout(Some(ZMQ_TIMEOUT))?;
+    stream.write_all(&ZMTP_SIGNATURE)?;
+    let mut greeting = [0; 10];
+    stream.read_exact(&mut greeting)?;
+    if zmtp_greeting_ok(&greeting) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "port [{}] answered, but not with ZMQ",
+            port
+        ))
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn contribute_checks() {
+        use crate::contribute::*;
+        assert!(is_public("node.example.com"));
+        assert!(is_public("95.216.1.1"));
+        assert!(!is_public("192.168.1.5"));
+        assert!(!is_public("127.0.0.1"));
+        assert!(!is_public("localhost"));
+        assert!(!is_public("[::1]"));
+        assert!(!is_public("fd00::1"));
+        assert!(!is_public("mynode"));
+
+        let ok = |checks: Vec<Check>| checks.iter().all(|c| c.ok);
+        assert!(ok(NodeCheck::local_checks(
+            "node.example.com",
+            "Germany",
+            "18089",
+            "18084"
+        )));
+        assert!(!ok(NodeCheck::local_checks(
+            "node.example.com",
+            "",
+            "18089",
+            "18084"
+        )));
+        assert!(!ok(NodeCheck::local_checks(
+            "node.example.com",
+            "Germany",
+            "18080",
+            "18084"
+        )));
+        assert!(!ok(NodeCheck::local_checks(
+            "p2pool.uk",
+            "United Kingdom",
+            "18089",
+            "18084"
+        )));
+
+        let json = serde_json::json!({"result": {
+            "status": "OK", "nettype": "mainnet", "synchronized": true,
+            "busy_syncing": false, "restricted": true,
+        }});
+        assert!(ok(NodeCheck::rpc_checks(&json)));
+        let json = serde_json::json!({"result": {
+            "status": "OK", "nettype": "mainnet", "synchronized": false, "restricted": true,
+        }});
+        assert!(!ok(NodeCheck::rpc_checks(&json)));
+        assert!(!ok(NodeCheck::rpc_checks(&serde_json::json!({}))));
+
+        assert_eq!(
+            snippet("node.example.com", "Germany", "18089", "18084"),
+            r#"("node.example.com", "Germany", "18089", "18084"),"#
+        );
+        assert!(zmtp_greeting_ok(&ZMTP_SIGNATURE));
+        assert!(!zmtp_greeting_ok(b"HTTP/1.1 4"));
+        assert!(!zmtp_greeting_ok(&[0xFF]));
+    }
+}
@@ -0,0 +1,177 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Delayed [Auto-P2Pool]/[Auto-XMRig].
+//
+// Started at login, the auto-started processes can race the network stack
+// or a local monerod that is still starting. Each one can wait a number of
+// seconds and/or until the network is up: [init_auto()] queues them here
+// and [App] starts each one once it's due.
+
+use crate::helper::ProcessName;
+use log::*;
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const MAX_DELAY: u16 = 600;
+// Don't hold the processes back forever on a network that never comes up,
+// they retry/fail on their own after this.
+const NETWORK_GIVE_UP: Duration = Duration::from_secs(180);
+const NETWORK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+//---------------------------------------------------------------------------------------------------- DelayedStart
+#[derive(Debug)]
+pub struct DelayedStart {
+    queue: Vec<(ProcessName, Instant)>, // When each queued process may start
+    wait_network: bool,
+    network_up: bool,
+    last_check: Option<Instant>,
+    since: Instant, // When the network wait started
+}
+
+impl Default for DelayedStart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DelayedStart {
+    pub fn new() -> Self {
+        Self {
+            queue: vec![],
+            wait_network: false,
+            network_up: false,
+            last_check: None,
+            since: Instant::now(),
+        }
+    }
+
+    // Queue [name] to start [delay] seconds from [now],
+    // and only once the network is up if [wait_network].
+    pub fn queue(&mut self, name: ProcessName, delay: u16, wait_network: bool, now: Instant) {
+        let delay = delay.min(MAX_DELAY);
+        info!(
+            "Delay | Queueing auto-{} in [{}s], wait for network: [{}]",
+            name, delay, wait_network
+        );
+        if wait_network && !self.wait_network {
+            self.wait_network = true;
+            self.since = now;
+        }
+        self.queue.retain(|(n, _)| *n != name);
+        self.queue
+            .push((name, now + Duration::from_secs(delay as u64)));
+    }
+
+    // Forget [name], e.g. the user started or stopped it by hand.
+    pub fn cancel(&mut self, name: ProcessName) {
+        self.queue.retain(|(n, _)| *n != name);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    // Seconds until [name] starts, [None] if it isn't queued.
+    pub fn remaining(&self, name: ProcessName, now: Instant) -> Option<u64> {
+        self.queue
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, at)| at.saturating_duration_since(now).as_secs())
+    }
+
+    // Called every frame, returns the next process that should start now.
+    pub fn due(&mut self, now: Instant) -> Option<ProcessName> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        if self.wait_network
+            && !self.network_up
+            && self
+                .last_check
+                .is_none_or(|t| now.duration_since(t) >= NETWORK_CHECK_INTERVAL)
+        {
+            self.last_check = Some(now);
+            self.network_up = network_up();
+        }
+        let network_up = self.network_up;
+        self.due_with(now, network_up)
+    }
+
+    fn due_with(&mut self, now: Instant, network_up: bool) -> Option<ProcessName> {
+        if self.wait_network && !network_up {
+            if now.duration_since(self.since) < NETWORK_GIVE_UP {
+                return None;
+            }
+            warn!(
+                "Delay | Network still down after [{}s], starting anyway",
+                NETWORK_GIVE_UP.as_secs()
+            );
+            self.wait_network = false;
+        }
+        let index = self.queue.iter().position(|(_, at)| now >= *at)?;
+        Some(self.queue.remove(index).0)
+    }
+}
+
+// Is there a route to the internet? Connecting a UDP socket sends
+// nothing, it only fails when there's no usable route/address yet.
+pub fn network_up() -> bool {
+    let v4 = UdpSocket::bind("0.0.0.0:0").and_then(|s| s.connect("1.1.1.1:53"));
+    let v6 = || UdpSocket::bind("[::]:0").and_then(|s| s.connect("[2606:4700:4700::1111]:53"));
+    v4.is_ok() || v6().is_ok()
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn delayed_start() {
+        use crate::delay::*;
+        let now = Instant::now();
+        let secs = |s| now + Duration::from_secs(s);
+
+        let mut delay = DelayedStart::new();
+        assert_eq!(delay.due_with(now, true), None);
+        delay.queue(ProcessName::P2pool, 10, false, now);
+        delay.queue(ProcessName::Xmrig, 20, false, now);
+        assert_eq!(delay.remaining(ProcessName::P2pool, secs(4)), Some(6));
+        assert_eq!(delay.due_with(secs(9), true), None);
+        assert_eq!(delay.due_with(secs(10), true), Some(ProcessName::P2pool));
+        assert_eq!(delay.due_with(secs(10), true), None);
+        assert_eq!(delay.remaining(ProcessName::P2pool, secs(10)), None);
+        delay.cancel(ProcessName::Xmrig);
+        assert!(delay.is_empty());
+
+        // Waits for the network, gives up eventually.
+        let mut delay = DelayedStart::new();
+        delay.queue(ProcessName::P2pool, 0, true, now);
+        assert_eq!(delay.due_with(secs(5), false), None);
+        assert_eq!(delay.due_with(secs(6), true), Some(ProcessName::P2pool));
+        delay.queue(ProcessName::Xmrig, 0, true, now);
+        assert_eq!(delay.due_with(secs(179), false), None);
+        assert_eq!(delay.due_with(secs(180), false), Some(ProcessName::Xmrig));
+
+        // Capped.
+        delay.queue(ProcessName::P2pool, u16::MAX, false, now);
+        assert_eq!(
+            delay.remaining(ProcessName::P2pool, now),
+            Some(MAX_DELAY as u64)
+        );
+    }
+}
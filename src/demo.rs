@@ -0,0 +1,162 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Demo mode ([--demo]).
+//
+// Instead of spawning P2Pool/XMRig, the helper runs fake watchdogs that
+// feed it the data below. The data is in the same shape as the real
+// P2Pool API files, XMRig HTTP API and console output, so it goes through
+// the exact same parsing as the real thing and every tab can be explored
+// (and screenshotted) on a machine without the binaries.
+//
+// Nothing here touches the disk, demo payouts aren't added to the real payout log.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+//---------------------------------------------------------------------------------------------------- Constants
+static DEMO: AtomicBool = AtomicBool::new(false);
+
+// Roughly a mid-range desktop CPU mining on P2Pool Mini.
+const HASHRATE: f64 = 12_000.0;
+const P2POOL_HASHRATE: u64 = 25_000_000;
+const P2POOL_MINERS: u32 = 1_200;
+const MONERO_DIFFICULTY: u64 = 300_000_000_000;
+const MONERO_HEIGHT: u64 = 3_100_000;
+const MONERO_REWARD: u64 = 600_000_000_000;
+// A share every ~10 minutes, a payout every ~40 minutes.
+const SHARE_SECS: u64 = 600;
+const PAYOUT_SECS: u64 = 2_400;
+const THREADS: usize = 8;
+
+//---------------------------------------------------------------------------------------------------- Global
+pub fn enable() {
+    DEMO.store(true, Ordering::Release);
+}
+
+pub fn enabled() -> bool {
+    DEMO.load(Ordering::Acquire)
+}
+
+//---------------------------------------------------------------------------------------------------- Data
+// Slowly wobbling hashrate so the numbers look alive.
+fn hashrate(secs: u64) -> f64 {
+    HASHRATE * (1.0 + 0.03 * (secs as f64 / 30.0).sin())
+}
+
+fn shares(secs: u64) -> u64 {
+    secs / SHARE_SECS
+}
+
+// P2Pool's [local/stratum].
+pub fn p2pool_local(secs: u64) -> String {
+    let effort = (secs % SHARE_SECS) as f64 / SHARE_SECS as f64 * 100.0;
+    format!(
+        r#"{{"hashrate_15m":{},"hashrate_1h":{},"hashrate_24h":{},"shares_found":{},"average_effort":95.3,"current_effort":{:.1},"connections":10}}"#,
+        hashrate(secs) as u64,
+        HASHRATE as u64,
+        HASHRATE as u64,
+        shares(secs),
+        effort,
+    )
+}
+
+// P2Pool's [network/stats].
+pub fn p2pool_network(secs: u64) -> String {
+    format!(
+        r#"{{"difficulty":{},"hash":"{:064x}","height":{},"reward":{},"timestamp":{}}}"#,
+        MONERO_DIFFICULTY,
+        MONERO_HEIGHT + secs / 120,
+        MONERO_HEIGHT + secs / 120,
+        MONERO_REWARD,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+// P2Pool's [pool/stats].
+pub fn p2pool_pool() -> String {
+    format!(
+        r#"{{"pool_statistics":{{"hashRate":{},"miners":{}}}}}"#,
+        P2POOL_HASHRATE, P2POOL_MINERS
+    )
+}
+
+// New P2Pool console lines for this second, if any.
+pub fn p2pool_output(secs: u64) -> String {
+    // P2Pool prints 4 digits of sub-seconds, chrono can't.
+    let now = chrono::Utc::now();
+    let date = format!(
+        "{}.{:04}",
+        now.format("%Y-%m-%d %H:%M:%S"),
+        now.timestamp_subsec_micros() / 100
+    );
+    let height = MONERO_HEIGHT + secs / 120;
+    match secs {
+        0 => format!("NOTICE  {date} P2Pool Demo mode, this is not a real P2Pool\n"),
+        3 => format!("NOTICE  {date} SideChain SYNCHRONIZED\n"),
+        _ if secs.is_multiple_of(PAYOUT_SECS) => format!(
+            "NOTICE  {date} P2Pool You received a payout of 0.000{:09} XMR in block {height}\n",
+            411_111_111 + secs % 1_000
+        ),
+        _ if secs.is_multiple_of(SHARE_SECS) => {
+            format!("NOTICE  {date} StratumServer SHARE FOUND: mainchain height {height}\n")
+        }
+        _ if secs.is_multiple_of(120) => {
+            format!("NOTICE  {date} P2Pool new chain main tip: height = {height}\n")
+        }
+        _ => String::new(),
+    }
+}
+
+// XMRig's [/1/summary].
+pub fn xmrig_summary(secs: u64) -> String {
+    let total = hashrate(secs);
+    let thread = total / THREADS as f64;
+    let threads = (0..THREADS)
+        .map(|i| {
+            let h = thread * (1.0 + 0.02 * (i as f64 - THREADS as f64 / 2.0) / THREADS as f64);
+            format!("[{h:.2},{h:.2},{h:.2}]")
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    format!(
+        r#"{{"worker_id":"gupax-demo","resources":{{"load_average":[4.1,4.0,3.9],"memory":{{"free":17179869184,"total":34359738368,"resident_set_memory":2516582400}}}},"connection":{{"diff":{},"accepted":{},"rejected":0}},"hashrate":{{"total":[{total:.2},{total:.2},{HASHRATE:.2}],"threads":[{threads}]}},"cpu":{{"brand":"Demo CPU 8-Core Processor","aes":true,"avx2":true,"l2":4194304,"l3":33554432,"cores":8,"threads":16,"msr":"ryzen_19h"}},"hugepages":[1168,1168],"donate_level":1}}"#,
+        HASHRATE as u64 * 30,
+        secs / 30,
+    )
+}
+
+// New XMRig console lines for this second, if any.
+pub fn xmrig_output(secs: u64) -> String {
+    let date = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+    match secs {
+        0 => format!("[{date}]  config   Demo mode, this is not a real XMRig\n"),
+        1 => format!("[{date}]  net      use pool 127.0.0.1:3333  127.0.0.1\n[{date}]  net      new job from 127.0.0.1:3333 diff {}\n", HASHRATE as u64 * 30),
+        _ if secs.is_multiple_of(30) => format!(
+            "[{date}]  cpu      accepted ({}/0) diff {}\n",
+            secs / 30,
+            HASHRATE as u64 * 30
+        ),
+        _ if secs % 60 == 1 => format!(
+            "[{date}]  miner    speed 10s/60s/15m {:.1} {:.1} {:.1} H/s max {:.1} H/s\n",
+            hashrate(secs),
+            hashrate(secs),
+            HASHRATE,
+            HASHRATE * 1.05
+        ),
+        _ => String::new(),
+    }
+}
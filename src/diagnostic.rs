@@ -0,0 +1,129 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// "Copy diagnostic" for the error screen.
+//
+// Builds a bug report in the same structure as the GitHub issue template
+// ([.github/ISSUE_TEMPLATE/bug_report.md]) with the error, the P2Pool/XMRig
+// states, paths and versions, and the tail of both consoles filled in,
+// so users can paste it as-is instead of describing their setup.
+
+use crate::{code::ErrorCode, constants::*};
+use std::fmt::Write;
+use std::path::Path;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How many console lines of each process get included.
+pub const CONSOLE_LINES: usize = 50;
+
+//---------------------------------------------------------------------------------------------------- Diagnostic
+pub struct Process<'a> {
+    pub name: &'a str,
+    pub state: String,
+    pub path: &'a Path,
+    pub version: &'a str,
+    pub output: &'a str,
+}
+
+// The last [n] lines of [output].
+fn tail(output: &str, n: usize) -> &str {
+    if n == 0 {
+        return "";
+    }
+    let output = output.trim_end();
+    match output.rmatch_indices('\n').nth(n - 1) {
+        Some((i, _)) => &output[i + 1..],
+        None => output,
+    }
+}
+
+// The whole report, ready to paste into a new GitHub issue.
+pub fn report(
+    code: Option<ErrorCode>,
+    msg: &str,
+    processes: &[Process],
+    data_path: &Path,
+) -> String {
+    let mut s = String::with_capacity(4096);
+    let _ = writeln!(
+        s,
+        "## OS & Version:\n{}, Gupax {} ({})\n",
+        OS_NAME, GUPAX_VERSION, COMMIT
+    );
+    let _ = writeln!(s, "## Bug");
+    if let Some(code) = code {
+        let _ = writeln!(s, "[{}] {}", code, code.title());
+    }
+    let _ = writeln!(s, "{}\n", msg.trim());
+    let _ = writeln!(s, "## Steps\n1. \n\n## Context");
+    let _ = writeln!(s, "- OS data path: `{}`", data_path.display());
+    for p in processes {
+        let _ = writeln!(
+            s,
+            "- {}: {} | {} | `{}`",
+            p.name,
+            p.state,
+            p.version,
+            p.path.display()
+        );
+    }
+    for p in processes {
+        let _ = writeln!(
+            s,
+            "\n## {} console (last {} lines)\n```\n{}\n```",
+            p.name,
+            CONSOLE_LINES,
+            tail(p.output, CONSOLE_LINES)
+        );
+    }
+    s
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn diagnostic() {
+        use crate::diagnostic::*;
+        assert_eq!(tail("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail("a\nb\nc", 5), "a\nb\nc");
+        assert_eq!(tail("a\nb\nc", 0), "");
+
+        let output: String = (1..=60).map(|i| format!("line {}\n", i)).collect();
+        let p2pool = Process {
+            name: "P2Pool",
+            state: "Alive".to_string(),
+            path: Path::new("/opt/p2pool/p2pool"),
+            version: "v3.10",
+            output: &output,
+        };
+        let report = report(
+            Some(ErrorCode::StateRead),
+            "State read fail: permission denied",
+            &[p2pool],
+            Path::new("/home/user/.local/share/gupax"),
+        );
+        // Same headings as the issue template.
+        assert!(report.starts_with("## OS & Version:\n"));
+        assert!(report.contains("## Bug\n[GX-"));
+        assert!(report.contains("State read fail: permission denied"));
+        assert!(report.contains("- P2Pool: Alive | v3.10 | `/opt/p2pool/p2pool`"));
+        assert!(report.contains("```\nline 11\n"));
+        assert!(!report.contains("line 10\n"));
+        assert!(report.contains("line 60\n```"));
+    }
+}
@@ -0,0 +1,269 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// P2Pool/XMRig binary auto-discovery.
+//
+// Looks for P2Pool/XMRig in the usual places so distro/package installs
+// don't need their paths typed in:
+//
+//   Bundled | Next to the Gupax executable, or in its [p2pool/xmrig] folders
+//   System  | [PATH], then the common install folders of each OS
+//
+// Every candidate is checked with [check_p2pool_path/check_xmrig_path], the
+// same check the path boxes use. Missing paths are filled in on startup with
+// the first candidate found, the rest are listed in the Gupax tab to pick from.
+
+use crate::{
+    disk::Gupax,
+    helper::ProcessName,
+    macros::*,
+    update::{check_p2pool_path, check_proxy_path, check_xmrig_path},
+};
+use log::*;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// The names a binary is searched for, the same ones [check_*_path] accepts.
+#[cfg(target_os = "windows")]
+const P2POOL_NAMES: [&str; 2] = ["p2pool.exe", "P2Pool.exe"];
+#[cfg(target_os = "windows")]
+const XMRIG_NAMES: [&str; 2] = ["xmrig.exe", "XMRig.exe"];
+#[cfg(target_os = "windows")]
+const PROXY_NAMES: [&str; 2] = ["xmrig-proxy.exe", "XMRig-Proxy.exe"];
+#[cfg(target_family = "unix")]
+const P2POOL_NAMES: [&str; 2] = ["p2pool", "P2Pool"];
+#[cfg(target_family = "unix")]
+const XMRIG_NAMES: [&str; 2] = ["xmrig", "XMRig"];
+#[cfg(target_family = "unix")]
+const PROXY_NAMES: [&str; 2] = ["xmrig-proxy", "XMRig-Proxy"];
+
+// Sub-folders of the Gupax folder that bundled binaries live in.
+const BUNDLED_DIRS: [&str; 4] = ["p2pool", "P2Pool", "xmrig", "XMRig"];
+
+// Common install folders that usually aren't in [PATH].
+#[cfg(target_os = "linux")]
+const SYSTEM_DIRS: [&str; 5] = [
+    "/usr/bin",
+    "/usr/local/bin",
+    "/opt/p2pool",
+    "/opt/xmrig",
+    "/snap/bin",
+];
+#[cfg(target_os = "macos")]
+const SYSTEM_DIRS: [&str; 3] = ["/opt/homebrew/bin", "/usr/local/bin", "/opt/local/bin"];
+#[cfg(target_os = "windows")]
+const SYSTEM_DIRS: [&str; 0] = [];
+
+//---------------------------------------------------------------------------------------------------- Source
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Source {
+    Bundled,
+    System,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Bundled => write!(f, "Bundled"),
+            Self::System => write!(f, "System"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Candidate
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    pub path: String,
+    pub source: Source,
+}
+
+// The folders to search, in order of preference.
+fn search_dirs() -> Vec<(PathBuf, Source)> {
+    let mut dirs = vec![];
+    if let Ok(mut exe) = std::env::current_exe() {
+        exe.pop();
+        for sub in BUNDLED_DIRS {
+            dirs.push((exe.join(sub), Source::Bundled));
+        }
+        dirs.push((exe, Source::Bundled));
+    }
+    if let Some(path) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path) {
+            dirs.push((dir, Source::System));
+        }
+    }
+    for dir in SYSTEM_DIRS {
+        dirs.push((PathBuf::from(dir), Source::System));
+    }
+    #[cfg(target_os = "windows")]
+    for var in ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"] {
+        if let Some(root) = std::env::var_os(var) {
+            let root = PathBuf::from(root);
+            for sub in ["P2Pool", "XMRig", r"Gupax\P2Pool", r"Gupax\XMRig"] {
+                dirs.push((root.join(sub), Source::System));
+            }
+        }
+    }
+    dirs
+}
+
+// Every valid [name] binary found in [dirs], duplicates (symlinks, case-insensitive
+// file systems, the same folder twice in [PATH]) are only listed once.
+fn find_in(name: ProcessName, dirs: &[(PathBuf, Source)]) -> Vec<Candidate> {
+    let (names, check): (_, fn(&str) -> bool) = match name {
+        ProcessName::P2pool => (P2POOL_NAMES, check_p2pool_path),
+        ProcessName::Xmrig => (XMRIG_NAMES, check_xmrig_path),
+        ProcessName::Proxy => (PROXY_NAMES, check_proxy_path),
+    };
+    let mut seen: Vec<PathBuf> = vec![];
+    let mut found = vec![];
+    for (dir, source) in dirs {
+        for file in names {
+            let path = dir.join(file);
+            if !path.is_file() {
+                continue;
+            }
+            let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if seen.contains(&real) {
+                continue;
+            }
+            let path = path.display().to_string();
+            if !check(&path) {
+                continue;
+            }
+            seen.push(real);
+            found.push(Candidate {
+                path,
+                source: *source,
+            });
+        }
+    }
+    found
+}
+
+pub fn find(name: ProcessName) -> Vec<Candidate> {
+    find_in(name, &search_dirs())
+}
+
+// Is [path] set to a usable binary?
+fn path_ok(name: ProcessName, path: &str) -> bool {
+    Gupax::path_is_file(path)
+        && match name {
+            ProcessName::P2pool => check_p2pool_path(path),
+            ProcessName::Xmrig => check_xmrig_path(path),
+            ProcessName::Proxy => check_proxy_path(path),
+        }
+}
+
+// Replace missing/invalid P2Pool/XMRig paths with the first binary found.
+pub fn fill_missing(gupax: &mut Gupax) {
+    for (name, path) in [
+        (ProcessName::P2pool, &mut gupax.p2pool_path),
+        (ProcessName::Xmrig, &mut gupax.xmrig_path),
+    ] {
+        if path_ok(name, path) {
+            continue;
+        }
+        match find(name).into_iter().next() {
+            Some(found) => {
+                info!(
+                    "Discover | {} path [{}] not found, using [{}] ({})",
+                    name, path, found.path, found.source
+                );
+                *path = found.path;
+            }
+            None => warn!(
+                "Discover | {} path [{}] not found, no other found",
+                name, path
+            ),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Discovery
+// The [Detect] button's results in the Gupax tab.
+#[derive(Clone, Debug, Default)]
+pub struct Discovery {
+    pub thread: bool, // Is a search running?
+    pub done: bool,   // Has a search finished at least once?
+    pub p2pool: Vec<Candidate>,
+    pub xmrig: Vec<Candidate>,
+}
+
+impl Discovery {
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(discovery: &Arc<Mutex<Self>>) {
+        lock!(discovery).thread = true;
+        let discovery = Arc::clone(discovery);
+        std::thread::spawn(move || {
+            let dirs = search_dirs();
+            let p2pool = find_in(ProcessName::P2pool, &dirs);
+            let xmrig = find_in(ProcessName::Xmrig, &dirs);
+            info!(
+                "Discover | Found [{}] P2Pool and [{}] XMRig binaries",
+                p2pool.len(),
+                xmrig.len()
+            );
+            let mut discovery = lock!(discovery);
+            discovery.p2pool = p2pool;
+            discovery.xmrig = xmrig;
+            discovery.thread = false;
+            discovery.done = true;
+        });
+    }
+}
+
+// [path] as it's shown in the picker.
+pub fn display(candidate: &Candidate) -> String {
+    format!(
+        "[{}] {}",
+        candidate.source,
+        Path::new(&candidate.path).display()
+    )
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn find_and_dedup() {
+        use super::{find_in, Source, P2POOL_NAMES};
+        use crate::helper::ProcessName;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let bundled = dir.join("bundled");
+        let system = dir.join("system");
+        std::fs::create_dir_all(&bundled).unwrap();
+        std::fs::create_dir_all(&system).unwrap();
+        std::fs::write(bundled.join(P2POOL_NAMES[0]), "").unwrap();
+        std::fs::write(system.join(P2POOL_NAMES[0]), "").unwrap();
+        std::fs::write(system.join("not_xmrig"), "").unwrap();
+        let dirs = [
+            (bundled.clone(), Source::Bundled),
+            (system.clone(), Source::System),
+            (system.clone(), Source::System),
+        ];
+        let found = find_in(ProcessName::P2pool, &dirs);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].source, Source::Bundled);
+        assert_eq!(found[1].source, Source::System);
+        assert!(found[0].path.ends_with(P2POOL_NAMES[0]));
+        assert!(find_in(ProcessName::Xmrig, &dirs).is_empty());
+    }
+}
@@ -30,7 +30,7 @@
 //   ├─ Version/
 //      ├─ ...
 
-use crate::{constants::*, gupax::Ratio, human::*, macros::*, xmr::*, Tab};
+use crate::{constants::*, gupax::Ratio, human::*, macros::*, report::HistoryDay, xmr::*, Tab};
 use figment::providers::{Format, Toml};
 use figment::Figment;
 use log::*;
@@ -41,7 +41,7 @@ use std::{
     fmt::Display,
     fmt::Write,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     result::Result,
     sync::{Arc, Mutex},
 };
@@ -58,10 +58,21 @@ const DIRECTORY: &str = "Gupax/";
 #[cfg(target_os = "linux")]
 const DIRECTORY: &str = "gupax/";
 
+// Overrides the OS data path, e.g: when that one is read-only.
+pub const DATA_DIR_ENV: &str = "GUPAX_DATA_DIR";
+
 // File names
 pub const STATE_TOML: &str = "state.toml";
 pub const NODE_TOML: &str = "node.toml";
 pub const POOL_TOML: &str = "pool.toml";
+pub const EVENTS_LOG: &str = "events.log";
+pub const BINARIES_TOML: &str = "binaries.toml";
+pub const LAST_RUN_TOML: &str = "last_run.toml";
+pub const AVAILABILITY_TOML: &str = "availability.toml";
+pub const P2POOL_HISTORY: &str = "p2pool_history.txt";
+pub const XMRIG_HISTORY: &str = "xmrig_history.txt";
+// Named [state.toml]'s for [--profile], see [profile_path()].
+pub const PROFILE_DIRECTORY: &str = "profiles";
 
 // P2Pool API
 // Lives within the Gupax OS data directory.
@@ -69,6 +80,7 @@ pub const POOL_TOML: &str = "pool.toml";
 // ├─ payout_log  // Raw log lines of payouts received
 // ├─ payout      // Single [u64] representing total payouts
 // ├─ xmr         // Single [u64] representing total XMR mined in atomic units
+// ├─ history     // Daily mining uptime & average hashrate, see [report.rs]
 #[cfg(target_os = "windows")]
 pub const GUPAX_P2POOL_API_DIRECTORY: &str = r"p2pool\";
 #[cfg(target_family = "unix")]
@@ -76,10 +88,13 @@ pub const GUPAX_P2POOL_API_DIRECTORY: &str = "p2pool/";
 pub const GUPAX_P2POOL_API_LOG: &str = "log";
 pub const GUPAX_P2POOL_API_PAYOUT: &str = "payout";
 pub const GUPAX_P2POOL_API_XMR: &str = "xmr";
-pub const GUPAX_P2POOL_API_FILE_ARRAY: [&str; 3] = [
+pub const GUPAX_P2POOL_API_HISTORY: &str = "history";
+pub const GUPAX_P2POOL_API_BACKUP: &str = "backup";
+pub const GUPAX_P2POOL_API_FILE_ARRAY: [&str; 4] = [
     GUPAX_P2POOL_API_LOG,
     GUPAX_P2POOL_API_PAYOUT,
     GUPAX_P2POOL_API_XMR,
+    GUPAX_P2POOL_API_HISTORY,
 ];
 
 #[cfg(target_os = "windows")]
@@ -105,20 +120,38 @@ pub const DEFAULT_P2POOL_PATH: &str = "/usr/bin/p2pool";
 #[cfg(feature = "distro")]
 pub const DEFAULT_XMRIG_PATH: &str = "/usr/bin/xmrig";
 
+// XMRig-Proxy isn't bundled, these are just where it'd usually be unpacked.
+#[cfg(target_os = "windows")]
+pub const DEFAULT_PROXY_PATH: &str = r"XMRig-Proxy\xmrig-proxy.exe";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_PROXY_PATH: &str = "xmrig-proxy/xmrig-proxy";
+#[cfg(target_os = "linux")]
+#[cfg(not(feature = "distro"))]
+pub const DEFAULT_PROXY_PATH: &str = "xmrig-proxy/xmrig-proxy";
+#[cfg(target_os = "linux")]
+#[cfg(feature = "distro")]
+pub const DEFAULT_PROXY_PATH: &str = "/usr/bin/xmrig-proxy";
+
 //---------------------------------------------------------------------------------------------------- General functions for all [File]'s
 // get_file_path()      | Return absolute path to OS data path + filename
 // read_to_string()     | Convert the file at a given path into a [String]
 // create_new()         | Write a default TOML Struct into the appropriate file (in OS data path)
 // into_absolute_path() | Convert relative -> absolute path
 
+// Get OS data folder, or [$GUPAX_DATA_DIR] if set.
+// Linux   | $XDG_DATA_HOME or $HOME/.local/share/gupax  | /home/alice/.local/state/gupax
+// macOS   | $HOME/Library/Application Support/Gupax     | /Users/Alice/Library/Application Support/Gupax
+// Windows | {FOLDERID_RoamingAppData}\Gupax             | C:\Users\Alice\AppData\Roaming\Gupax
+pub fn gupax_data_path() -> Option<PathBuf> {
+    match std::env::var_os(DATA_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::data_dir().map(|path| path.join(DIRECTORY)),
+    }
+}
+
 pub fn get_gupax_data_path() -> Result<PathBuf, TomlError> {
-    // Get OS data folder
-    // Linux   | $XDG_DATA_HOME or $HOME/.local/share/gupax  | /home/alice/.local/state/gupax
-    // macOS   | $HOME/Library/Application Support/Gupax     | /Users/Alice/Library/Application Support/Gupax
-    // Windows | {FOLDERID_RoamingAppData}\Gupax             | C:\Users\Alice\AppData\Roaming\Gupax
-    match dirs::data_dir() {
-        Some(mut path) => {
-            path.push(DIRECTORY);
+    match gupax_data_path() {
+        Some(path) => {
             info!("OS | Data path ... {}", path.display());
             create_gupax_dir(&path)?;
             let mut gupax_p2pool_dir = path.clone();
@@ -133,6 +166,58 @@ pub fn get_gupax_data_path() -> Result<PathBuf, TomlError> {
     }
 }
 
+// Can files actually be created in [dir]?
+// Permissions alone don't catch read-only mounts (e.g: macOS App Translocation).
+pub fn is_writable(dir: &Path) -> bool {
+    let test = dir.join(".gupax_write_test");
+    match fs::write(&test, b"") {
+        Ok(_) => {
+            let _ = fs::remove_file(&test);
+            true
+        }
+        Err(e) => {
+            warn!("OS | Data path [{}] is not writable: {}", dir.display(), e);
+            false
+        }
+    }
+}
+
+// A [--profile] is a whole [state.toml] saved as [profiles/<name>.toml],
+// the node/pool lists and payout stats stay shared. Names are kept to
+// [A-Za-z0-9_-] so they're safe as file names and on a command line.
+pub fn profile_path(os_data_path: &Path, name: &str) -> Option<PathBuf> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| {
+        os_data_path
+            .join(PROFILE_DIRECTORY)
+            .join(format!("{}.toml", name))
+    })
+}
+
+// The names of the saved profiles, sorted.
+pub fn list_profiles(os_data_path: &Path) -> Vec<String> {
+    let Ok(dir) = fs::read_dir(os_data_path.join(PROFILE_DIRECTORY)) else {
+        return vec![];
+    };
+    let mut names: Vec<String> = dir
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            profile_path(os_data_path, &name).map(|_| name)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
 pub fn set_unix_750_perms(path: &PathBuf) -> Result<(), TomlError> {
     #[cfg(target_os = "windows")]
     return Ok(());
@@ -232,6 +317,79 @@ pub fn read_to_string(file: File, path: &PathBuf) -> Result<String, TomlError> {
     }
 }
 
+// The temporary file a TOML is written to before it replaces [path].
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
+}
+
+// Is [path] already exactly [string]? Saves that wouldn't change anything are skipped.
+fn unchanged(path: &Path, string: &str) -> bool {
+    fs::read(path).is_ok_and(|old| old == string.as_bytes())
+}
+
+// Write [string] to [path] through a temporary file, so a crash or a full
+// disk mid-write never leaves a half-written TOML behind.
+// Returns [false] if the file already had this exact content.
+pub fn write_toml(file: File, path: &Path, string: &str) -> Result<bool, TomlError> {
+    if unchanged(path, string) {
+        info!("{:?} | Unchanged, skipping write", file);
+        return Ok(false);
+    }
+    let tmp = tmp_path(path);
+    if let Err(e) = fs::write(&tmp, string).and_then(|_| fs::rename(&tmp, path)) {
+        error!("{:?} | Couldn't overwrite file", file);
+        let _ = fs::remove_file(&tmp);
+        return Err(TomlError::Io(e));
+    }
+    info!("{:?} | Save ... OK", file);
+    Ok(true)
+}
+
+// Save [state.toml/node.toml/pool.toml] together: everything is serialized and
+// written to temporary files first, the real files are only replaced once
+// all of those succeeded. Unchanged files aren't touched.
+pub fn save_all(
+    state: &mut State,
+    state_path: &Path,
+    nodes: &[(String, Node)],
+    node_path: &Path,
+    pools: &[(String, Pool)],
+    pool_path: &Path,
+) -> Result<(), TomlError> {
+    info!("Disk | Saving state/node/pool...");
+    state.gupax.absolute_p2pool_path = into_absolute_path(state.gupax.p2pool_path.clone())?;
+    state.gupax.absolute_xmrig_path = into_absolute_path(state.gupax.xmrig_path.clone())?;
+    let files = [
+        (File::State, state_path, state.to_string()?),
+        (File::Node, node_path, Node::to_string(nodes)?),
+        (File::Pool, pool_path, Pool::to_string(pools)?),
+    ];
+    let mut staged = vec![];
+    for (file, path, string) in files {
+        if unchanged(path, &string) {
+            info!("{:?} | Unchanged, skipping write", file);
+            continue;
+        }
+        let tmp = tmp_path(path);
+        if let Err(e) = fs::write(&tmp, string) {
+            error!("{:?} | Couldn't write [{}]", file, tmp.display());
+            let _ = fs::remove_file(&tmp);
+            for (_, _, tmp) in staged {
+                let _ = fs::remove_file(tmp);
+            }
+            return Err(TomlError::Io(e));
+        }
+        staged.push((file, path, tmp));
+    }
+    for (file, path, tmp) in staged {
+        fs::rename(&tmp, path)?;
+        info!("{:?} | Save ... OK", file);
+    }
+    Ok(())
+}
+
 // Write str to console with [info!] surrounded by "---"
 pub fn print_dash(toml: &str) {
     info!("{}", HORIZONTAL);
@@ -241,9 +399,57 @@ pub fn print_dash(toml: &str) {
     info!("{}", HORIZONTAL);
 }
 
+// The names of the [start_settings()] that changed since a process was started
+// with [started]. Empty if it wasn't started by Gupax (nothing to compare to).
+pub fn restart_needed(
+    started: &[(&'static str, String)],
+    now: &[(&'static str, String)],
+) -> Vec<&'static str> {
+    if started.is_empty() {
+        return Vec::new();
+    }
+    // A different mode changes the whole list, no need to be more specific.
+    if started.first() != now.first() {
+        return vec!["Mode"];
+    }
+    let mut changed: Vec<&'static str> = now
+        .iter()
+        .filter(|setting| !started.contains(setting))
+        .map(|(name, _)| *name)
+        .collect();
+    for (name, _) in started {
+        if !changed.contains(name) && !now.iter().any(|(n, _)| n == name) {
+            changed.push(name);
+        }
+    }
+    changed
+}
+
+// Clean up a typed/pasted binary path before it's used. Mostly Windows shapes:
+// - ["C:\Program Files\XMRig\xmrig.exe"], [Copy as path] keeps the quotes
+// - [\\?\C:\...] and [\\?\UNC\server\share\...], verbatim paths that the
+//   PTY's working directory (and XMRig/P2Pool themselves) don't always accept
+// Spaces and non-ASCII characters are kept as they are, they're never split
+// or re-quoted since the path is passed to the PTY as a single argument.
+pub fn clean_path(path: &str) -> String {
+    let mut path = path.trim();
+    for quote in ['"', '\''] {
+        if path.len() >= 2 && path.starts_with(quote) && path.ends_with(quote) {
+            path = path[1..path.len() - 1].trim();
+        }
+    }
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", unc)
+    } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
 // Turn relative paths into absolute paths
 pub fn into_absolute_path(path: String) -> Result<PathBuf, TomlError> {
-    let path = PathBuf::from(path);
+    let path = PathBuf::from(clean_path(&path));
     if path.is_relative() {
         let mut dir = std::env::current_exe()?;
         dir.pop();
@@ -266,10 +472,12 @@ impl State {
         let max_threads = benri::threads!();
         let current_threads = if max_threads == 1 { 1 } else { max_threads / 2 };
         Self {
+            schema: crate::migrate::SCHEMA,
             status: Status::default(),
             gupax: Gupax::default(),
             p2pool: P2pool::default(),
             xmrig: Xmrig::with_threads(max_threads, current_threads),
+            proxy: Proxy::default(),
             version: arc_mut!(Version::default()),
         }
     }
@@ -290,7 +498,7 @@ impl State {
             }
             Err(err) => {
                 warn!("State | String -> State ... FAIL ... {}", err);
-                Err(TomlError::Deserialize(err))
+                Err(TomlError::Deserialize(Box::new(err)))
             }
         }
     }
@@ -309,13 +517,21 @@ impl State {
     // Combination of multiple functions:
     //   1. Attempt to read file from path into [String]
     //      |_ Create a default file if not found
-    //   2. Deserialize [String] into a proper [Struct]
+    //   2. Migrate an older [schema], keeping a backup of the old file
+    //   3. Deserialize [String] into a proper [Struct]
     //      |_ Attempt to merge if deserialization fails
-    pub fn get(path: &PathBuf) -> Result<Self, TomlError> {
+    // Writing the migrated/merged file is only best-effort: it's just done again
+    // next time. It's skipped with [read_only] (unwritable data path) or if the
+    // pre-migration backup couldn't be written.
+    pub fn get(path: &PathBuf, read_only: bool) -> Result<Self, TomlError> {
         // Read
         let file = File::State;
         let string = match read_to_string(file, path) {
             Ok(string) => string,
+            Err(_) if read_only => {
+                warn!("State | Read-only data path, using defaults");
+                return Ok(Self::new());
+            }
             // Create
             _ => {
                 Self::create_new(path)?;
@@ -325,18 +541,49 @@ impl State {
                 }
             }
         };
+        // Migrate
+        let mut write = !read_only;
+        let string = match crate::migrate::migrate(&string) {
+            Some((schema, migrated)) => {
+                if write {
+                    let backup = crate::migrate::backup_path(path, schema);
+                    match fs::write(&backup, &string) {
+                        Ok(_) => {
+                            info!(
+                                "State | Backed up pre-migration file to {}",
+                                backup.display()
+                            );
+                            if let Err(e) = write_toml(file, path, &migrated) {
+                                warn!("State | Couldn't save the migrated file: {}", e);
+                            }
+                        }
+                        // Without a backup the old file is left alone.
+                        Err(e) => {
+                            warn!(
+                                "State | Couldn't back up pre-migration file to {}: {}",
+                                backup.display(),
+                                e
+                            );
+                            write = false;
+                        }
+                    }
+                }
+                migrated
+            }
+            None => string,
+        };
         // Deserialize, attempt merge if failed
         match Self::from_str(&string) {
             Ok(s) => Ok(s),
             Err(_) => {
                 warn!("State | Attempting merge...");
-                match Self::merge(&string) {
-                    Ok(mut new) => {
-                        Self::save(&mut new, path)?;
-                        Ok(new)
+                let mut new = Self::merge(&string)?;
+                if write {
+                    if let Err(e) = Self::save(&mut new, path) {
+                        warn!("State | Couldn't save the merged file: {}", e);
                     }
-                    Err(e) => Err(e),
                 }
+                Ok(new)
             }
         }
     }
@@ -353,7 +600,7 @@ impl State {
     }
 
     // Save [State] onto disk file [gupax.toml]
-    pub fn save(&mut self, path: &PathBuf) -> Result<(), TomlError> {
+    pub fn save(&mut self, path: &Path) -> Result<(), TomlError> {
         info!("State | Saving to disk...");
         // Convert path to absolute
         self.gupax.absolute_p2pool_path = into_absolute_path(self.gupax.p2pool_path.clone())?;
@@ -369,16 +616,7 @@ impl State {
                 return Err(TomlError::Serialize(err));
             }
         };
-        match fs::write(path, string) {
-            Ok(_) => {
-                info!("State | Save ... OK");
-                Ok(())
-            }
-            Err(err) => {
-                error!("State | Couldn't overwrite TOML file ... FAIL");
-                Err(TomlError::Io(err))
-            }
-        }
+        write_toml(File::State, path, &string).map(|_| ())
     }
 
     // Take [String] as input, merge it with whatever the current [default] is,
@@ -395,7 +633,7 @@ impl State {
             }
             Err(err) => {
                 error!("State | Couldn't merge default + old TOML");
-                return Err(TomlError::Merge(err));
+                return Err(TomlError::Merge(Box::new(err)));
             }
         };
         Ok(new)
@@ -409,6 +647,7 @@ impl Node {
             ip: "localhost".to_string(),
             rpc: "18081".to_string(),
             zmq: "18083".to_string(),
+            tags: vec![],
         }
     }
 
@@ -420,6 +659,21 @@ impl Node {
         ("Local Monero Node".to_string(), Self::localhost())
     }
 
+    // Starting points for [New from template], empty fields are left for the user.
+    pub fn templates() -> [(&'static str, Self); 3] {
+        let node = |ip: &str, rpc: &str| Self {
+            ip: ip.to_string(),
+            rpc: rpc.to_string(),
+            zmq: "18083".to_string(),
+            tags: vec![],
+        };
+        [
+            ("Local Monero Node", node("localhost", "18081")),
+            ("Local Restricted Node", node("localhost", "18089")),
+            ("Remote Node", node("", "18089")),
+        ]
+    }
+
     // Convert [String] to [Node] Vec
     pub fn from_str_to_vec(string: &str) -> Result<Vec<(String, Self)>, TomlError> {
         let nodes: toml::map::Map<String, toml::Value> = match toml::de::from_str(string) {
@@ -429,7 +683,7 @@ impl Node {
             }
             Err(err) => {
                 error!("Node | String parse ... FAIL ... {}", err);
-                return Err(TomlError::Deserialize(err));
+                return Err(TomlError::Deserialize(Box::new(err)));
             }
         };
         let size = nodes.keys().len();
@@ -474,7 +728,8 @@ impl Node {
                     return Err(TomlError::Parse("[None] at [zmq] parse"));
                 }
             };
-            let node = Node { ip, rpc, zmq };
+            let tags = toml_tags(values);
+            let node = Node { ip, rpc, zmq, tags };
             vec.push((key.clone(), node));
         }
         Ok(vec)
@@ -487,8 +742,12 @@ impl Node {
         for (key, value) in vec.iter() {
             write!(
                 toml,
-                "[\'{}\']\nip = {:#?}\nrpc = {:#?}\nzmq = {:#?}\n\n",
-                key, value.ip, value.rpc, value.zmq,
+                "[\'{}\']\nip = {:#?}\nrpc = {:#?}\nzmq = {:#?}\n{}\n",
+                key,
+                value.ip,
+                value.rpc,
+                value.zmq,
+                tags_to_toml(&value.tags),
             )?;
         }
         Ok(toml)
@@ -526,19 +785,10 @@ impl Node {
     }
 
     // Save [Node] onto disk file [node.toml]
-    pub fn save(vec: &[(String, Self)], path: &PathBuf) -> Result<(), TomlError> {
+    pub fn save(vec: &[(String, Self)], path: &Path) -> Result<(), TomlError> {
         info!("Node | Saving to disk ... [{}]", path.display());
         let string = Self::to_string(vec)?;
-        match fs::write(path, string) {
-            Ok(_) => {
-                info!("Node | Save ... OK");
-                Ok(())
-            }
-            Err(err) => {
-                error!("Node | Couldn't overwrite file");
-                Err(TomlError::Io(err))
-            }
-        }
+        write_toml(File::Node, path, &string).map(|_| ())
     }
 
     //	pub fn merge(old: &String) -> Result<Self, TomlError> {
@@ -564,6 +814,7 @@ impl Pool {
             rig: GUPAX_VERSION_UNDERSCORE.to_string(),
             ip: "localhost".to_string(),
             port: "3333".to_string(),
+            tags: vec![],
         }
     }
 
@@ -575,6 +826,21 @@ impl Pool {
         ("Local P2Pool".to_string(), Self::p2pool())
     }
 
+    // Starting points for [New from template], empty fields are left for the user.
+    pub fn templates() -> [(&'static str, Self); 3] {
+        let pool = |ip: &str, port: &str| Self {
+            rig: GUPAX_VERSION_UNDERSCORE.to_string(),
+            ip: ip.to_string(),
+            port: port.to_string(),
+            tags: vec![],
+        };
+        [
+            ("Local P2Pool", pool("localhost", "3333")),
+            ("Remote P2Pool", pool("", "3333")),
+            ("TLS Pool", pool("", "443")),
+        ]
+    }
+
     pub fn from_str_to_vec(string: &str) -> Result<Vec<(String, Self)>, TomlError> {
         let pools: toml::map::Map<String, toml::Value> = match toml::de::from_str(string) {
             Ok(map) => {
@@ -583,7 +849,7 @@ impl Pool {
             }
             Err(err) => {
                 error!("Pool | String parse ... FAIL ... {}", err);
-                return Err(TomlError::Deserialize(err));
+                return Err(TomlError::Deserialize(Box::new(err)));
             }
         };
         let size = pools.keys().len();
@@ -629,7 +895,13 @@ impl Pool {
                     return Err(TomlError::Parse("[None] at [port] parse"));
                 }
             };
-            let pool = Pool { rig, ip, port };
+            let tags = toml_tags(values);
+            let pool = Pool {
+                rig,
+                ip,
+                port,
+                tags,
+            };
             vec.push((key.clone(), pool));
         }
         Ok(vec)
@@ -640,8 +912,12 @@ impl Pool {
         for (key, value) in vec.iter() {
             write!(
                 toml,
-                "[\'{}\']\nrig = {:#?}\nip = {:#?}\nport = {:#?}\n\n",
-                key, value.rig, value.ip, value.port,
+                "[\'{}\']\nrig = {:#?}\nip = {:#?}\nport = {:#?}\n{}\n",
+                key,
+                value.rig,
+                value.ip,
+                value.port,
+                tags_to_toml(&value.tags),
             )?;
         }
         Ok(toml)
@@ -671,36 +947,392 @@ impl Pool {
         Ok(new)
     }
 
-    pub fn save(vec: &[(String, Self)], path: &PathBuf) -> Result<(), TomlError> {
+    pub fn save(vec: &[(String, Self)], path: &Path) -> Result<(), TomlError> {
         info!("Pool | Saving to disk ... [{}]", path.display());
         let string = Self::to_string(vec)?;
-        match fs::write(path, string) {
-            Ok(_) => {
-                info!("Pool | Save ... OK");
-                Ok(())
+        write_toml(File::Pool, path, &string).map(|_| ())
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Node]/[Pool] Import
+// For users migrating from other setups, these parse external
+// lists into [Node]/[Pool] vectors. Supported formats:
+//   - CSV (node)   | "name,ip,rpc,zmq,tags" (tags are optional, separated by [;])
+//   - CSV (pool)   | "name,ip,port,rig,tags" (rig and tags are optional)
+//   - XMRig config | The [pools] array inside an XMRig [config.json]
+//
+// Empty lines, [#] comments and a header line starting with [name] are ignored.
+// Invalid entries are skipped (and logged) instead of failing the whole import.
+
+// Split CSV text into trimmed fields per line.
+fn csv_to_fields(string: &str) -> Vec<Vec<String>> {
+    let mut vec = Vec::new();
+    for line in string.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<String> = line
+            .split(',')
+            .map(|f| f.trim().trim_matches('"').to_string())
+            .collect();
+        if fields[0].eq_ignore_ascii_case("name") {
+            continue;
+        }
+        vec.push(fields);
+    }
+    vec
+}
+
+fn import_name_ok(name: &str) -> bool {
+    name.len() <= 30 && crate::regex::REGEXES.name.is_match(name)
+}
+
+fn import_ip_ok(ip: &str) -> bool {
+    ip.len() <= 255
+        && (ip == "localhost"
+            || crate::regex::REGEXES.ipv4.is_match(ip)
+            || crate::regex::REGEXES.domain.is_match(ip))
+}
+
+fn import_port_ok(port: &str) -> bool {
+    crate::regex::REGEXES.port.is_match(port)
+}
+
+impl Node {
+    // Convert CSV lines of "name,ip,rpc,zmq[,tags]" into a [Node] Vec.
+    pub fn from_csv(string: &str) -> Result<Vec<(String, Self)>, TomlError> {
+        let mut vec = Vec::new();
+        for fields in csv_to_fields(string) {
+            if fields.len() < 4 {
+                warn!("Node | Import: skipping line with {} fields", fields.len());
+                continue;
+            }
+            let (name, ip, rpc, zmq) = (&fields[0], &fields[1], &fields[2], &fields[3]);
+            if !import_name_ok(name)
+                || !import_ip_ok(ip)
+                || !import_port_ok(rpc)
+                || !import_port_ok(zmq)
+            {
+                warn!(
+                    "Node | Import: skipping invalid entry [{}]",
+                    fields.join(",")
+                );
+                continue;
+            }
+            let node = Self {
+                ip: ip.clone(),
+                rpc: rpc.clone(),
+                zmq: zmq.clone(),
+                tags: parse_tags(&fields.get(4).cloned().unwrap_or_default().replace(';', ",")),
+            };
+            vec.push((name.clone(), node));
+        }
+        if vec.is_empty() {
+            error!("Node | Import: no valid entries found");
+            return Err(TomlError::Parse("no valid node entries found"));
+        }
+        info!("Node | Import: parsed {} entries ... OK", vec.len());
+        Ok(vec)
+    }
+}
+
+impl Pool {
+    // Convert CSV lines of "name,ip,port[,rig[,tags]]" into a [Pool] Vec.
+    pub fn from_csv(string: &str) -> Result<Vec<(String, Self)>, TomlError> {
+        let mut vec = Vec::new();
+        for fields in csv_to_fields(string) {
+            if fields.len() < 3 {
+                warn!("Pool | Import: skipping line with {} fields", fields.len());
+                continue;
+            }
+            let rig = fields.get(3).cloned().unwrap_or_default();
+            let (name, ip, port) = (&fields[0], &fields[1], &fields[2]);
+            if !import_name_ok(name)
+                || !import_ip_ok(ip)
+                || !import_port_ok(port)
+                || !(rig.is_empty() || import_name_ok(&rig))
+            {
+                warn!(
+                    "Pool | Import: skipping invalid entry [{}]",
+                    fields.join(",")
+                );
+                continue;
             }
+            let pool = Self {
+                rig,
+                ip: ip.clone(),
+                port: port.clone(),
+                tags: parse_tags(&fields.get(4).cloned().unwrap_or_default().replace(';', ",")),
+            };
+            vec.push((name.clone(), pool));
+        }
+        if vec.is_empty() {
+            error!("Pool | Import: no valid entries found");
+            return Err(TomlError::Parse("no valid pool entries found"));
+        }
+        info!("Pool | Import: parsed {} entries ... OK", vec.len());
+        Ok(vec)
+    }
+
+    // Convert the [pools] array of an XMRig [config.json] into a [Pool] Vec.
+    // The pool's host is used as the name since XMRig pools don't have one.
+    pub fn from_xmrig_config(string: &str) -> Result<Vec<(String, Self)>, TomlError> {
+        let json: serde_json::Value = match serde_json::from_str(string) {
+            Ok(json) => json,
             Err(err) => {
-                error!("Pool | Couldn't overwrite file");
-                Err(TomlError::Io(err))
+                error!("Pool | Import: XMRig config parse ... FAIL ... {}", err);
+                return Err(TomlError::Parse("XMRig config is not valid JSON"));
+            }
+        };
+        let pools = match json.get("pools").and_then(|p| p.as_array()) {
+            Some(pools) => pools,
+            None => {
+                error!("Pool | Import: XMRig config has no [pools] array");
+                return Err(TomlError::Parse("XMRig config has no [pools] array"));
+            }
+        };
+        let mut vec = Vec::with_capacity(pools.len());
+        for pool in pools {
+            let url = match pool.get("url").and_then(|u| u.as_str()) {
+                Some(url) => url,
+                None => {
+                    warn!("Pool | Import: skipping XMRig pool without [url]");
+                    continue;
+                }
+            };
+            // Strip [stratum+tcp://] and friends.
+            let url = url.rsplit("://").next().unwrap_or(url);
+            let (ip, port) = match url.rsplit_once(':') {
+                Some((ip, port)) => (ip.to_string(), port.to_string()),
+                None => {
+                    warn!("Pool | Import: skipping XMRig pool without port [{}]", url);
+                    continue;
+                }
+            };
+            let rig = pool
+                .get("rig-id")
+                .and_then(|r| r.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let name: String = ip.chars().take(30).collect();
+            if !import_name_ok(&name)
+                || !import_ip_ok(&ip)
+                || !import_port_ok(&port)
+                || !(rig.is_empty() || import_name_ok(&rig))
+            {
+                warn!("Pool | Import: skipping invalid XMRig pool [{}]", url);
+                continue;
             }
+            vec.push((
+                name,
+                Self {
+                    rig,
+                    ip,
+                    port,
+                    tags: vec![],
+                },
+            ));
+        }
+        if vec.is_empty() {
+            error!("Pool | Import: no valid pools found in XMRig config");
+            return Err(TomlError::Parse("no valid pools found in XMRig config"));
+        }
+        info!("Pool | Import: parsed {} XMRig pools ... OK", vec.len());
+        Ok(vec)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Node]/[Pool] Tags + Filter
+// Entries can have a few optional tags, e.g: [home, fast] to group them.
+// Old [node.toml/pool.toml] files without [tags] still parse (as no tags),
+// and older Gupax versions ignore the extra [tags] key.
+pub const MAX_TAGS: usize = 10;
+
+// Parse user input like [home, fast] into tags. Tags follow the same
+// rules as names, invalid and duplicate ones are dropped.
+pub fn parse_tags(string: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in string.split(',') {
+        let tag = tag.trim();
+        if tag.is_empty() || !import_name_ok(tag) || tags.iter().any(|t| t == tag) {
+            continue;
+        }
+        if tags.len() == MAX_TAGS {
+            break;
         }
+        tags.push(tag.to_string());
     }
+    tags
+}
+
+fn toml_tags(values: &toml::Value) -> Vec<String> {
+    match values.get("tags").and_then(|t| t.as_array()) {
+        Some(array) => parse_tags(
+            &array
+                .iter()
+                .filter_map(|t| t.as_str())
+                .collect::<Vec<&str>>()
+                .join(","),
+        ),
+        None => vec![],
+    }
+}
+
+// Nothing at all if there are no tags, so untagged lists look exactly like before.
+fn tags_to_toml(tags: &[String]) -> String {
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!("tags = {:?}\n", tags)
+    }
+}
+
+pub trait ListEntry {
+    fn ip(&self) -> &str;
+    fn tags(&self) -> &[String];
+}
+impl ListEntry for Node {
+    fn ip(&self) -> &str {
+        &self.ip
+    }
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+}
+impl ListEntry for Pool {
+    fn ip(&self) -> &str {
+        &self.ip
+    }
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListSort {
+    #[default]
+    Index,
+    Name,
+    Latency,
+}
+
+// The filter box + sort order above a [Node]/[Pool] list.
+#[derive(Clone, Debug, Default)]
+pub struct ListFilter {
+    pub text: String,
+    pub sort: ListSort,
+}
+
+impl ListFilter {
+    // Does [name]/[entry] match every word of the filter?
+    // A word matches the name, IP or a tag (case-insensitive),
+    // [#tag] only matches that exact tag.
+    fn matches<T: ListEntry>(&self, name: &str, entry: &T) -> bool {
+        let lower = |s: &str| s.to_lowercase();
+        self.text.split_whitespace().all(|word| {
+            let word = lower(word);
+            match word.strip_prefix('#') {
+                Some(tag) => entry.tags().iter().any(|t| lower(t) == tag),
+                None => {
+                    lower(name).contains(&word)
+                        || lower(entry.ip()).contains(&word)
+                        || entry.tags().iter().any(|t| lower(t).contains(&word))
+                }
+            }
+        })
+    }
+
+    // The indices into [vec] to show, in order. [latency] returns
+    // the last known ping (in milliseconds) of an IP, unknown ones go last.
+    pub fn indices<T: ListEntry>(
+        &self,
+        vec: &[(String, T)],
+        latency: impl Fn(&str) -> Option<u128>,
+    ) -> Vec<usize> {
+        let mut indices: Vec<usize> = vec
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, entry))| self.matches(name, entry))
+            .map(|(i, _)| i)
+            .collect();
+        match self.sort {
+            ListSort::Index => (),
+            ListSort::Name => {
+                indices.sort_by_cached_key(|i| vec[*i].0.to_lowercase());
+            }
+            ListSort::Latency => {
+                indices.sort_by_cached_key(|i| latency(vec[*i].1.ip()).unwrap_or(u128::MAX));
+            }
+        }
+        indices
+    }
+}
+
+// [name] if it isn't taken in [vec] yet, else [name_2], [name_3], etc.
+// The name is shortened to fit the suffix within the 30 character limit.
+pub fn unique_name<T>(vec: &[(String, T)], name: &str) -> String {
+    let mut new_name = name.to_string();
+    let mut n = 2;
+    while vec.iter().any(|(n, _)| *n == new_name) {
+        let suffix = format!("_{}", n);
+        let mut base = name.to_string();
+        base.truncate(30 - suffix.len());
+        new_name = format!("{}{}", base, suffix);
+        n += 1;
+    }
+    new_name
+}
+
+// Merge imported entries into an existing [Node]/[Pool] list.
+// Exact duplicates (same name + values) are skipped, entries
+// with a taken name but different values are renamed with
+// a [_2], [_3], etc. suffix. The list is capped at 1000 entries.
+// Returns [(added, skipped)].
+pub fn merge_imported<T: PartialEq>(
+    vec: &mut Vec<(String, T)>,
+    imported: Vec<(String, T)>,
+) -> (usize, usize) {
+    let mut added = 0;
+    let mut skipped = 0;
+    for (name, value) in imported {
+        if vec.len() >= 1000 {
+            skipped += 1;
+            continue;
+        }
+        if vec.iter().any(|(n, v)| *n == name && *v == value) {
+            skipped += 1;
+            continue;
+        }
+        let new_name = unique_name(vec, &name);
+        if new_name != name {
+            info!(
+                "Import | Name conflict, renamed [{}] -> [{}]",
+                name, new_name
+            );
+        }
+        vec.push((new_name, value));
+        added += 1;
+    }
+    (added, skipped)
 }
 
 //---------------------------------------------------------------------------------------------------- Gupax-P2Pool API
 #[derive(Clone, Debug)]
 pub struct GupaxP2poolApi {
-    pub log: String,           // Log file only containing full payout lines
-    pub log_rev: String,       // Same as above but reversed based off lines
-    pub payout: HumanNumber,   // Human-friendly display of payout count
-    pub payout_u64: u64,       // [u64] version of above
-    pub payout_ord: PayoutOrd, // Ordered Vec of payouts, see [PayoutOrd]
+    pub log: String,              // Log file only containing full payout lines
+    pub log_rev: String,          // Same as above but reversed based off lines
+    pub payout: HumanNumber,      // Human-friendly display of payout count
+    pub payout_u64: u64,          // [u64] version of above
+    pub payout_ord: PayoutOrd,    // Ordered Vec of payouts, see [PayoutOrd]
     pub payout_low: String, // A pre-allocated/computed [String] of the above Vec from low payout to high
     pub payout_high: String, // Same as above but high -> low
     pub xmr: AtomicUnit,    // XMR stored as atomic units
+    pub history: Vec<HistoryDay>, // Daily uptime/hashrate, for reports
     pub path_log: PathBuf,  // Path to [log]
     pub path_payout: PathBuf, // Path to [payout]
     pub path_xmr: PathBuf,  // Path to [xmr]
+    pub path_history: PathBuf, // Path to [history]
 }
 
 impl Default for GupaxP2poolApi {
@@ -721,9 +1353,11 @@ impl GupaxP2poolApi {
             payout_low: String::new(),
             payout_high: String::new(),
             xmr: AtomicUnit::new(),
+            history: vec![],
             path_xmr: PathBuf::new(),
             path_payout: PathBuf::new(),
             path_log: PathBuf::new(),
+            path_history: PathBuf::new(),
         }
     }
 
@@ -731,13 +1365,16 @@ impl GupaxP2poolApi {
         let mut path_log = gupax_p2pool_dir.clone();
         let mut path_payout = gupax_p2pool_dir.clone();
         let mut path_xmr = gupax_p2pool_dir.clone();
+        let mut path_history = gupax_p2pool_dir.clone();
         path_log.push(GUPAX_P2POOL_API_LOG);
         path_payout.push(GUPAX_P2POOL_API_PAYOUT);
         path_xmr.push(GUPAX_P2POOL_API_XMR);
+        path_history.push(GUPAX_P2POOL_API_HISTORY);
         *self = Self {
             path_log,
             path_payout,
             path_xmr,
+            path_history,
             ..std::mem::take(self)
         };
     }
@@ -797,7 +1434,23 @@ impl GupaxP2poolApi {
             }
         };
         let payout = HumanNumber::from_u64(payout_u64);
-        let log = read_to_string(File::Log, &self.path_log)?;
+        let mut log = read_to_string(File::Log, &self.path_log)?;
+        // Older Gupax's stored payout dates in local time, convert them to UTC once.
+        // Failing to write the converted file isn't fatal, it's converted in memory again next time.
+        if PayoutOrd::log_has_local_dates(&log) {
+            info!("GupaxP2poolApi | Converting [log] payout dates to UTC");
+            let converted = PayoutOrd::map_log_dates(&log, PayoutOrd::date_to_utc);
+            if let Err(e) = Self::rewrite_log(&self.path_log, &log, &converted) {
+                warn!("GupaxP2poolApi | Couldn't save the converted [log]: {}", e);
+            }
+            log = converted;
+        }
+        // Older Gupax's didn't have this file, it's
+        // optional (only used for reports) so don't error.
+        let history = match read_to_string(File::History, &self.path_history) {
+            Ok(s) => HistoryDay::parse(&s),
+            Err(_) => vec![],
+        };
         self.payout_ord.update_from_payout_log(&log);
         self.update_payout_strings();
         *self = Self {
@@ -805,19 +1458,49 @@ impl GupaxP2poolApi {
             payout,
             payout_u64,
             xmr,
+            history,
             ..std::mem::take(self)
         };
         self.update_log_rev();
         Ok(())
     }
 
+    // Replace [log] with its UTC version. Old lines can't tell if P2Pool printed
+    // local time or UTC, so the original is kept as [log.bak] in case the guess was wrong.
+    fn rewrite_log(path: &Path, old: &str, new: &str) -> Result<(), String> {
+        let bak = path.with_extension("bak");
+        if !bak.exists() {
+            fs::write(&bak, old).map_err(|e| e.to_string())?;
+            info!(
+                "GupaxP2poolApi | Original [log] kept as [{}]",
+                bak.display()
+            );
+        }
+        write_toml(File::Log, path, new).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     // Completely delete the [p2pool] folder and create defaults.
+    // The old files are backed up first and [backup/] is kept, so this can be undone.
     pub fn create_new(path: &PathBuf) -> Result<(), TomlError> {
+        if let Err(e) = Self::backup(path, usize::MAX) {
+            warn!("GupaxP2poolApi | Backup before reset ... FAIL: {}", e);
+        }
         info!(
             "GupaxP2poolApi | Deleting old folder at [{}]...",
             path.display()
         );
-        std::fs::remove_dir_all(path)?;
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            if entry.file_name() == GUPAX_P2POOL_API_BACKUP {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())?;
+            } else {
+                fs::remove_file(entry.path())?;
+            }
+        }
         info!(
             "GupaxP2poolApi | Creating new default folder at [{}]...",
             path.display()
@@ -827,6 +1510,70 @@ impl GupaxP2poolApi {
         Ok(())
     }
 
+    //---------------------------------------------------------------------------------------------------- Backups
+    // Copy the API files into [backup/<UTC date>/], then delete all but the newest [keep] backups.
+    // Nothing is copied if the newest backup already has the exact same files.
+    pub fn backup(gupax_p2pool_dir: &Path, keep: usize) -> Result<Option<PathBuf>, TomlError> {
+        let read =
+            |dir: &Path| GUPAX_P2POOL_API_FILE_ARRAY.map(|file| fs::read(dir.join(file)).ok());
+        let current = read(gupax_p2pool_dir);
+        let backups = Self::backups(gupax_p2pool_dir);
+        let new = if backups
+            .first()
+            .is_some_and(|newest| read(newest) == current)
+        {
+            debug!("GupaxP2poolApi | Backup ... unchanged, skipping");
+            None
+        } else {
+            let name = chrono::Utc::now()
+                .format("%Y-%m-%d_%H-%M-%S%.3f")
+                .to_string();
+            let dir = gupax_p2pool_dir.join(GUPAX_P2POOL_API_BACKUP).join(name);
+            fs::create_dir_all(&dir)?;
+            for (file, bytes) in GUPAX_P2POOL_API_FILE_ARRAY.iter().zip(current) {
+                if let Some(bytes) = bytes {
+                    fs::write(dir.join(file), bytes)?;
+                }
+            }
+            info!("GupaxP2poolApi | Backup [{}] ... OK", dir.display());
+            Some(dir)
+        };
+        for old in Self::backups(gupax_p2pool_dir).iter().skip(keep) {
+            info!("GupaxP2poolApi | Deleting old backup [{}]", old.display());
+            fs::remove_dir_all(old)?;
+        }
+        Ok(new)
+    }
+
+    // All backups, newest first.
+    pub fn backups(gupax_p2pool_dir: &Path) -> Vec<PathBuf> {
+        let Ok(dir) = fs::read_dir(gupax_p2pool_dir.join(GUPAX_P2POOL_API_BACKUP)) else {
+            return vec![];
+        };
+        let mut backups: Vec<PathBuf> = dir
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .map(|e| e.path())
+            .collect();
+        // The names are dates, so this is also by time.
+        backups.sort_unstable_by(|a, b| b.cmp(a));
+        backups
+    }
+
+    // Copy a backup's files back. The current files get backed up first.
+    // [read_all_files_and_update()] has to be called after.
+    pub fn restore(gupax_p2pool_dir: &Path, backup: &Path) -> Result<(), TomlError> {
+        Self::backup(gupax_p2pool_dir, usize::MAX)?;
+        for file in GUPAX_P2POOL_API_FILE_ARRAY {
+            let from = backup.join(file);
+            if from.exists() {
+                fs::copy(&from, gupax_p2pool_dir.join(file))?;
+            }
+        }
+        info!("GupaxP2poolApi | Restore [{}] ... OK", backup.display());
+        Ok(())
+    }
+
     //---------------------------------------------------------------------------------------------------- Live, functions that actually update/write live stats
     pub fn update_log_rev(&mut self) {
         let mut log_rev = String::with_capacity(self.log.len());
@@ -837,8 +1584,14 @@ impl GupaxP2poolApi {
         self.log_rev = log_rev;
     }
 
+    // This gets written to disk, so always English (not [NumberFormat]).
     pub fn format_payout(date: &str, atomic_unit: &AtomicUnit, block: &HumanNumber) -> String {
-        format!("{} | {} XMR | Block {}", date, atomic_unit, block)
+        format!(
+            "{} | {} XMR | Block {}",
+            date,
+            atomic_unit.to_human_number_12_point().as_str(),
+            block.as_str()
+        )
     }
 
     pub fn append_log(&mut self, formatted_log_line: &str) {
@@ -882,6 +1635,13 @@ impl GupaxP2poolApi {
         self.update_payout_strings();
     }
 
+    // Add [secs] of mining at [hashrate] to today's history and write it.
+    pub fn add_history(&mut self, secs: u64, hashrate: f64) -> Result<(), TomlError> {
+        let today = chrono::Local::now().date_naive();
+        HistoryDay::add_sample(&mut self.history, today, secs, hashrate);
+        Self::disk_overwrite(&HistoryDay::to_lines(&self.history), &self.path_history)
+    }
+
     pub fn write_to_all_files(&self, formatted_log_line: &str) -> Result<(), TomlError> {
         Self::disk_overwrite(&self.payout_u64.to_string(), &self.path_payout)?;
         Self::disk_overwrite(&self.xmr.to_string(), &self.path_xmr)?;
@@ -955,12 +1715,14 @@ impl GupaxP2poolApi {
 
 //---------------------------------------------------------------------------------------------------- Custom Error [TomlError]
 #[derive(Debug)]
+// The [toml]/[figment] errors are boxed, they're much bigger
+// than the rest and every [Result<_, TomlError>] would carry that.
 pub enum TomlError {
     Io(std::io::Error),
     Path(String),
     Serialize(toml::ser::Error),
-    Deserialize(toml::de::Error),
-    Merge(figment::Error),
+    Deserialize(Box<toml::de::Error>),
+    Merge(Box<figment::Error>),
     Format(std::fmt::Error),
     Parse(&'static str),
 }
@@ -980,6 +1742,18 @@ impl Display for TomlError {
     }
 }
 
+impl TomlError {
+    // Couldn't write because of permissions or a read-only mount, expected
+    // with a read-only data path (the banner explains it), unlike parse errors.
+    pub fn is_write(&self) -> bool {
+        use std::io::ErrorKind;
+        matches!(self, TomlError::Io(e) if matches!(
+            e.kind(),
+            ErrorKind::PermissionDenied | ErrorKind::ReadOnlyFilesystem
+        ))
+    }
+}
+
 impl From<std::io::Error> for TomlError {
     fn from(err: std::io::Error) -> Self {
         TomlError::Io(err)
@@ -1001,9 +1775,10 @@ pub enum File {
     Pool,  // pool.toml    | XMRig manual pool selector
 
     // Gupax-P2Pool API
-    Log,    // log    | Raw log lines of P2Pool payouts received
-    Payout, // payout | Single [u64] representing total payouts
-    Xmr,    // xmr    | Single [u64] representing total XMR mined in atomic units
+    Log,     // log    | Raw log lines of P2Pool payouts received
+    Payout,  // payout | Single [u64] representing total payouts
+    Xmr,     // xmr    | Single [u64] representing total XMR mined in atomic units
+    History, // history | Daily mining uptime & average hashrate
 }
 
 //---------------------------------------------------------------------------------------------------- [Submenu] enum for [Status] tab
@@ -1012,6 +1787,8 @@ pub enum Submenu {
     Processes,
     P2pool,
     Benchmarks,
+    Consoles,
+    Events,
 }
 
 impl Default for Submenu {
@@ -1038,12 +1815,24 @@ pub enum PayoutView {
     Oldest,   // Shows the oldest logs first
     Biggest,  // Shows highest to lowest payouts
     Smallest, // Shows lowest to highest payouts
+    Weekly,   // Shows a per-week summary report
+    Monthly,  // Shows a per-month summary report
+    Recent,   // Shows the latest payouts as a table with daily totals
 }
 
 impl PayoutView {
     fn new() -> Self {
         Self::Latest
     }
+
+    // The report [Period] if this is a summary view.
+    pub const fn period(self) -> Option<crate::report::Period> {
+        match self {
+            Self::Weekly => Some(crate::report::Period::Week),
+            Self::Monthly => Some(crate::report::Period::Month),
+            _ => None,
+        }
+    }
 }
 
 impl Default for PayoutView {
@@ -1058,6 +1847,227 @@ impl Display for PayoutView {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- [PayoutTimezone] enum for [Status/P2Pool] tab
+// Which timezone payout dates are shown in, they're always stored in UTC.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum PayoutTimezone {
+    #[default]
+    Local, // e.g: [2022-04-11 02:20:17.2571 +02:00]
+    Utc, // e.g: [2022-04-11 00:20:17.2571 UTC]
+}
+
+impl Display for PayoutTimezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Local => write!(f, "Local"),
+            Self::Utc => write!(f, "UTC"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [StatusGroup] enum for [Status/Processes|P2Pool] tabs
+// The stat groups that can be reordered and collapsed.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub enum StatusGroup {
+    Gupax,      // [Processes] column
+    P2pool,     // [Processes] column
+    Xmrig,      // [Processes] column
+    Payouts,    // [P2Pool] payout log
+    Calculator, // [P2Pool] share/block calculator + stats
+}
+
+impl StatusGroup {
+    pub const PROCESSES: [Self; 3] = [Self::Gupax, Self::P2pool, Self::Xmrig];
+    pub const P2POOL: [Self; 2] = [Self::Payouts, Self::Calculator];
+
+    // The saved order, cleaned up: unknown/duplicate groups are dropped
+    // and ones missing (e.g: added in a newer Gupax) go at the end.
+    pub fn layout(saved: &[Self], all: &[Self]) -> Vec<Self> {
+        let mut layout: Vec<Self> = Vec::with_capacity(all.len());
+        for group in saved.iter().chain(all) {
+            if all.contains(group) && !layout.contains(group) {
+                layout.push(*group);
+            }
+        }
+        layout
+    }
+
+    // The groups to actually render, in order.
+    pub fn shown(saved: &[Self], collapsed: &[Self], all: &[Self]) -> Vec<Self> {
+        Self::layout(saved, all)
+            .into_iter()
+            .filter(|group| !collapsed.contains(group))
+            .collect()
+    }
+
+    // Move [group] to where [target] is (drag & drop).
+    pub fn move_to(layout: &mut Vec<Self>, group: Self, target: Self) {
+        if group == target {
+            return;
+        }
+        let (Some(from), Some(to)) = (
+            layout.iter().position(|g| *g == group),
+            layout.iter().position(|g| *g == target),
+        ) else {
+            return;
+        };
+        layout.remove(from);
+        layout.insert(to, group);
+    }
+}
+
+impl Display for StatusGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Gupax => write!(f, "Gupax"),
+            Self::P2pool => write!(f, "P2Pool"),
+            Self::Xmrig => write!(f, "XMRig"),
+            Self::Payouts => write!(f, "Payouts"),
+            Self::Calculator => write!(f, "Calculator"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [MoneroNetwork] enum for [P2Pool] tab
+// Which Monero network to mine on, [Testnet/Stagenet] are for development.
+// P2Pool itself has no flag for this, it picks the network from the
+// wallet address prefix and refuses a [monerod] on a different one.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum MoneroNetwork {
+    #[default]
+    Mainnet,
+    Testnet,
+    Stagenet,
+}
+
+impl MoneroNetwork {
+    pub const ALL: [Self; 3] = [Self::Mainnet, Self::Testnet, Self::Stagenet];
+
+    // Default [monerod] RPC/ZMQ ports.
+    pub const fn rpc(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "18081",
+            Self::Testnet => "28081",
+            Self::Stagenet => "38081",
+        }
+    }
+
+    pub const fn zmq(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "18083",
+            Self::Testnet => "28083",
+            Self::Stagenet => "38083",
+        }
+    }
+
+    // First character(s) of a primary address on this network.
+    pub const fn address_prefixes(&self) -> &'static [char] {
+        match self {
+            Self::Mainnet => &['4'],
+            Self::Testnet => &['9', 'A'],
+            Self::Stagenet => &['5'],
+        }
+    }
+
+    pub const fn is_mainnet(&self) -> bool {
+        matches!(self, Self::Mainnet)
+    }
+}
+
+impl Display for MoneroNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "Mainnet"),
+            Self::Testnet => write!(f, "Testnet"),
+            Self::Stagenet => write!(f, "Stagenet"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [P2poolMode] enum for P2Pool's [--light-mode]
+// P2Pool's full mode keeps its own copy of the RandomX dataset (~2.3GB, on
+// top of XMRig's) for faster block verification and its built-in miner.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum P2poolMode {
+    #[default]
+    Auto, // Full if there's enough RAM, see [P2poolMode::FULL_MIN_RAM]
+    Light,
+    Full,
+}
+
+impl P2poolMode {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Light, Self::Full];
+    // Total RAM (in bytes) [Auto] needs before picking full mode,
+    // a little under 16GB since some of it is always reserved.
+    pub const FULL_MIN_RAM: u64 = 15_000_000_000;
+
+    pub const fn recommended(total_ram: u64) -> Self {
+        if total_ram >= Self::FULL_MIN_RAM {
+            Self::Full
+        } else {
+            Self::Light
+        }
+    }
+
+    // Should P2Pool be started with [--light-mode]?
+    pub const fn is_light(self, total_ram: u64) -> bool {
+        match self {
+            Self::Auto => matches!(Self::recommended(total_ram), Self::Light),
+            Self::Light => true,
+            Self::Full => false,
+        }
+    }
+}
+
+impl Display for P2poolMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "Auto"),
+            Self::Light => write!(f, "Light"),
+            Self::Full => write!(f, "Full"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [ProxyMode] enum for XMRig-Proxy's [--mode]
+// [Nicehash] splits the nonce space so up to 256 rigs share one upstream
+// connection, [Simple] gives every rig its own upstream connection.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum ProxyMode {
+    #[default]
+    Nicehash,
+    Simple,
+}
+
+impl ProxyMode {
+    pub const ALL: [Self; 2] = [Self::Nicehash, Self::Simple];
+
+    // The value for [--mode].
+    pub const fn arg(self) -> &'static str {
+        match self {
+            Self::Nicehash => "nicehash",
+            Self::Simple => "simple",
+        }
+    }
+}
+
+impl Display for ProxyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Nicehash => write!(f, "NiceHash"),
+            Self::Simple => write!(f, "Simple"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [ConsoleLayout] enum for [Status/Consoles] tab
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum ConsoleLayout {
+    #[default]
+    SideBySide, // P2Pool on the left, XMRig on the right
+    Stacked, // P2Pool on top, XMRig on the bottom
+}
+
 //---------------------------------------------------------------------------------------------------- [Hash] enum for [Status/P2Pool]
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize)]
 pub enum Hash {
@@ -1128,6 +2138,8 @@ pub struct Node {
     pub ip: String,
     pub rpc: String,
     pub zmq: String,
+    #[serde(default)]
+    pub tags: Vec<String>, // Optional, for filtering long lists
 }
 
 //---------------------------------------------------------------------------------------------------- [Pool] Struct
@@ -1136,15 +2148,19 @@ pub struct Pool {
     pub rig: String,
     pub ip: String,
     pub port: String,
+    #[serde(default)]
+    pub tags: Vec<String>, // Optional, for filtering long lists
 }
 
 //---------------------------------------------------------------------------------------------------- [State] Struct
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct State {
+    pub schema: u32,
     pub status: Status,
     pub gupax: Gupax,
     pub p2pool: P2pool,
     pub xmrig: Xmrig,
+    pub proxy: Proxy,
     pub version: Arc<Mutex<Version>>,
 }
 
@@ -1152,10 +2168,19 @@ pub struct State {
 pub struct Status {
     pub submenu: Submenu,
     pub payout_view: PayoutView,
+    pub payout_timezone: PayoutTimezone,
+    pub console_layout: ConsoleLayout,
+    pub console_split: f32,
+    pub console_sync: bool,
     pub monero_enabled: bool,
     pub manual_hash: bool,
     pub hashrate: f64,
     pub hash_metric: Hash,
+    pub processes_layout: Vec<StatusGroup>,
+    pub p2pool_layout: Vec<StatusGroup>,
+    pub collapsed: Vec<StatusGroup>,
+    pub events_hidden: Vec<crate::event::Kind>,
+    pub explorer: String, // Block explorer URL template for the payout log
 }
 
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
@@ -1167,16 +2192,62 @@ pub struct Gupax {
     //	pub auto_monero: bool,
     pub ask_before_quit: bool,
     pub save_before_quit: bool,
+    pub window_stats: bool,
+    pub colorblind: bool,
+    pub time_check: bool,
+    pub event_log: bool,
+    pub console_history: bool, // Save the console command history
+    pub console_cap: u32,      // Kilobytes a console holds before it resets
+    pub usage_stats: bool,     // Opt-in anonymous usage stats, see [telemetry.rs]
+    pub escalation: crate::sudo::Escalation, // What XMRig is started through on Unix
+    pub console_colors: bool,
+    pub restart_on_resume: bool,
+    pub start_on_login: bool,
+    pub start_on_login_quiet: bool,
+    pub number_format: NumberFormat,
+    pub short_time: bool,
+    pub hashrate_unit: HashrateUnit,
+    pub global_hotkeys: bool,
+    pub hotkey_xmrig: String,
+    pub hotkey_window: String,
+    pub payout_sound: bool,
+    pub payout_volume: u8,
+    pub payout_backups: u8, // How many [GupaxP2poolApi] backups to keep, [0] is off
+    pub quiet_hours: bool,
+    pub quiet_start: u8,
+    pub quiet_end: u8,
     pub update_via_tor: bool,
+    pub update_gupax: bool, // Which packages [Check for updates] and [Auto-Update] may replace
+    pub update_p2pool: bool,
+    pub update_xmrig: bool,
+    pub p2pool_start_delay: u16, // Seconds [Auto-P2Pool] waits, see [delay.rs]
+    pub xmrig_start_delay: u16,
+    pub wait_for_network: bool, // [Auto-P2Pool/XMRig] wait for a network route
+    pub ip_preference: crate::net::IpPreference, // For Gupax's own connections, see [net.rs]
+    pub dns_over_https: String, // DoH server URL, empty uses the system resolver
+    pub hook_start: String,     // Scripts run on process events, see [hook.rs]
+    pub hook_stop: String,
+    pub hook_crash: String,
+    pub hook_payout: String,
+    pub gfx_x11: bool, // Relaunch without Wayland, see [gfx.rs]
+    pub gfx_renderer: crate::gfx::Renderer,
     pub p2pool_path: String,
     pub xmrig_path: String,
     pub absolute_p2pool_path: PathBuf,
     pub absolute_xmrig_path: PathBuf,
     pub selected_width: u16,
     pub selected_height: u16,
-    pub selected_scale: f32,
+    pub selected_scale: f32, // For displays without their own scale in [display_scales]
     pub tab: Tab,
     pub ratio: Ratio,
+    pub display_scales: Vec<DisplayScale>, // See [display.rs]
+}
+
+// The window scale used on one display, see [display.rs].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct DisplayScale {
+    pub display: String, // e.g: [3840x2160@2]
+    pub scale: f32,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
@@ -1186,45 +2257,147 @@ pub struct P2pool {
     pub auto_ping: bool,
     pub auto_select: bool,
     pub backup_host: bool,
+    pub api_dir: String,
+    pub workdir: String, // The directory P2Pool runs in, see [Helper::workdir()]
+    pub network: MoneroNetwork,
     pub out_peers: u16,
     pub in_peers: u16,
+    pub simple_peers: u16, // Both out/in peers in [Simple]
     pub log_level: u8,
+    pub no_cache: bool,       // [--no-cache]
+    pub no_dns: bool,         // [--no-dns]
+    pub mode: P2poolMode,     // [--light-mode] or not
+    pub miner: bool,          // P2Pool's built-in miner, [--start-mining]
+    pub miner_threads: usize, // ...with this many threads
+    pub p2p_port: String,     // [--p2p], empty uses P2Pool's default
+    pub log_max_mb: u16,      // Rotate [p2pool.log] on start if bigger, [0] never does
+    pub tls: bool,            // Run the stratum TLS terminator, see [tls.rs]
+    pub tls_port: String,     // ...listening on this port
+    pub ssh: bool,            // Reach the manual node through an SSH tunnel, see [ssh.rs]
+    pub ssh_host: String,
+    pub ssh_port: String,
+    pub ssh_user: String,
+    pub ssh_key: String,      // Private key path, empty is [~/.ssh/id_ed25519]
+    pub ssh_host_key: String, // Trusted host key fingerprint, if not in [known_hosts]
+    pub ssh_rpc: String,      // The node's RPC/ZMQ ports on the SSH host
+    pub ssh_zmq: String,
     pub node: String,
+    pub own_node: bool, // [Simple] uses [own_ip/rpc/zmq] instead of [node]
+    pub own_ip: String,
+    pub own_rpc: String,
+    pub own_zmq: String,
     pub arguments: String,
     pub address: String,
     pub name: String,
     pub ip: String,
     pub rpc: String,
     pub zmq: String,
+    pub tags: String, // Comma separated, see [parse_tags()]
     pub selected_index: usize,
     pub selected_name: String,
     pub selected_ip: String,
     pub selected_rpc: String,
     pub selected_zmq: String,
+    pub env: Vec<EnvVar>, // Extra environment variables, see [envvar.rs]
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 pub struct Xmrig {
     pub simple: bool,
     pub pause: u8,
+    pub priority: Option<u8>, // XMRig's [--cpu-priority], [None] leaves it to XMRig
+    pub load_pause: u8,       // Pause if other programs use more than this % of the CPU, [0] is off
+    pub load_pause_secs: u16, // ...for this many seconds, see [helper::LoadGuard]
     pub simple_rig: String,
     pub arguments: String,
     pub tls: bool,
     pub keepalive: bool,
+    pub pages_1gb: bool,      // [--randomx-1gb-pages]
+    pub huge_pages_jit: bool, // [--huge-pages-jit]
+    pub msr_mod: bool,        // [false] passes [--randomx-no-msr]
+    pub donate_level: u8,     // [--donate-level], XMRig's dev fee in %
     pub max_threads: usize,
     pub current_threads: usize,
+    pub cpu_usage: u8, // [Simple]'s CPU usage in %, kept in sync with [current_threads]
     pub address: String,
     pub api_ip: String,
     pub api_port: String,
+    pub api_enabled: bool, // [false] leaves out [--http-host/--http-port], stats come from STDOUT only
+    pub api_restricted: bool, // [false] passes [--http-no-restricted]
+    pub watch_only: bool, // Only read an existing XMRig's HTTP API, see [Helper::start_xmrig_watch()]
+    pub watch_url: String, // e.g: [127.0.0.1:18088]
+    pub watch_token: String, // XMRig's [access-token], empty if none
     pub name: String,
     pub rig: String,
     pub ip: String,
     pub port: String,
+    pub tags: String, // Comma separated, see [parse_tags()]
     pub selected_index: usize,
     pub selected_name: String,
     pub selected_rig: String,
     pub selected_ip: String,
     pub selected_port: String,
+    pub affinity: String, // [--cpu-affinity] as a CPU list, e.g: [0-15,32-47], empty lets the OS decide
+    pub workdir: String,  // The directory XMRig runs in, see [Helper::workdir()]
+    pub instances: Vec<XmrigInstance>, // Extra XMRig's next to this one, see [helper::ExtraXmrig]
+    pub env: Vec<EnvVar>, // Extra environment variables, see [envvar.rs]
+}
+
+// An extra environment variable for P2Pool/XMRig, see [envvar.rs].
+#[derive(Clone, Default, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+}
+
+// An extra XMRig started next to the main one, e.g: one per NUMA node.
+// Everything else (pool, address, TLS...) is the main XMRig's.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct XmrigInstance {
+    pub threads: usize,
+    pub affinity: String, // Same format as [Xmrig::affinity]
+    pub api_port: String, // Empty turns its HTTP API off, stats come from STDOUT only
+}
+
+impl XmrigInstance {
+    // The next free API port after the main XMRig's and the other instances'.
+    pub fn new(threads: usize, xmrig: &Xmrig) -> Self {
+        let mut port = xmrig.api_port.trim().parse::<u16>().unwrap_or(18088);
+        loop {
+            port = port.saturating_add(1);
+            let taken = xmrig
+                .instances
+                .iter()
+                .any(|i| i.api_port == port.to_string());
+            if !taken || port == u16::MAX {
+                break;
+            }
+        }
+        Self {
+            threads,
+            affinity: String::new(),
+            api_port: port.to_string(),
+        }
+    }
+}
+
+// XMRig-Proxy, for rigs on the LAN to mine through this machine.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Proxy {
+    pub path: String, // It isn't bundled, so the binary path lives here instead of [Gupax]
+    pub mode: ProxyMode,
+    pub bind_ip: String, // What the rigs connect to, [--bind]
+    pub bind_port: String,
+    pub ip: String, // The upstream pool, the local P2Pool by default
+    pub port: String,
+    pub address: String, // Upstream [--user], P2Pool ignores it so it can be empty
+    pub tls: bool,
+    pub keepalive: bool,
+    pub api_ip: String,
+    pub api_port: String,
+    pub arguments: String, // Overrides everything above
+    pub workdir: String,   // The directory XMRig-Proxy runs in, see [Helper::workdir()]
+    pub env: Vec<EnvVar>,  // Extra environment variables, see [envvar.rs]
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -1240,10 +2413,19 @@ impl Default for Status {
         Self {
             submenu: Submenu::default(),
             payout_view: PayoutView::default(),
+            payout_timezone: PayoutTimezone::default(),
+            console_layout: ConsoleLayout::default(),
+            console_split: 0.5,
+            console_sync: false,
             monero_enabled: false,
             manual_hash: false,
             hashrate: 1.0,
             hash_metric: Hash::default(),
+            processes_layout: StatusGroup::PROCESSES.to_vec(),
+            p2pool_layout: StatusGroup::P2POOL.to_vec(),
+            collapsed: Vec::new(),
+            events_hidden: Vec::new(),
+            explorer: crate::xmr::DEFAULT_EXPLORER.to_string(),
         }
     }
 }
@@ -1257,7 +2439,45 @@ impl Default for Gupax {
             auto_xmrig: false,
             ask_before_quit: true,
             save_before_quit: true,
+            window_stats: true,
+            colorblind: false,
+            time_check: true,
+            event_log: true,
+            console_history: false,
+            console_cap: 500,
+            usage_stats: false,
+            escalation: crate::sudo::Escalation::Auto,
+            console_colors: true,
+            restart_on_resume: false,
+            start_on_login: false,
+            start_on_login_quiet: true,
+            number_format: NumberFormat::default(),
+            short_time: false,
+            hashrate_unit: HashrateUnit::default(),
+            global_hotkeys: false,
+            hotkey_xmrig: crate::hotkey::DEFAULT_HOTKEY_XMRIG.to_string(),
+            hotkey_window: crate::hotkey::DEFAULT_HOTKEY_WINDOW.to_string(),
+            payout_sound: false,
+            payout_volume: 50,
+            payout_backups: 10,
+            quiet_hours: false,
+            quiet_start: 22,
+            quiet_end: 8,
             update_via_tor: true,
+            update_gupax: true,
+            update_p2pool: true,
+            update_xmrig: true,
+            p2pool_start_delay: 0,
+            xmrig_start_delay: 0,
+            wait_for_network: false,
+            ip_preference: crate::net::IpPreference::Auto,
+            dns_over_https: String::new(),
+            hook_start: String::new(),
+            hook_stop: String::new(),
+            hook_crash: String::new(),
+            hook_payout: String::new(),
+            gfx_x11: false,
+            gfx_renderer: crate::gfx::Renderer::Auto,
             p2pool_path: DEFAULT_P2POOL_PATH.to_string(),
             xmrig_path: DEFAULT_XMRIG_PATH.to_string(),
             absolute_p2pool_path: into_absolute_path(DEFAULT_P2POOL_PATH.to_string()).unwrap(),
@@ -1267,6 +2487,35 @@ impl Default for Gupax {
             selected_scale: APP_DEFAULT_SCALE,
             ratio: Ratio::Width,
             tab: Tab::About,
+            display_scales: Vec::new(),
+        }
+    }
+}
+
+impl Gupax {
+    // Which of [Gupax, P2Pool, XMRig] an update is allowed to touch.
+    pub fn update_scope(&self) -> [bool; 3] {
+        [self.update_gupax, self.update_p2pool, self.update_xmrig]
+    }
+
+    // The hook paths in the order of [hook::KINDS].
+    pub fn hooks(&self) -> [&str; 4] {
+        [
+            &self.hook_start,
+            &self.hook_stop,
+            &self.hook_crash,
+            &self.hook_payout,
+        ]
+    }
+
+    pub fn hook_mut(&mut self, kind: crate::event::Kind) -> Option<&mut String> {
+        use crate::event::Kind;
+        match kind {
+            Kind::Start => Some(&mut self.hook_start),
+            Kind::Stop => Some(&mut self.hook_stop),
+            Kind::Crash => Some(&mut self.hook_crash),
+            Kind::Payout => Some(&mut self.hook_payout),
+            _ => None,
         }
     }
 }
@@ -1279,39 +2528,341 @@ impl Default for P2pool {
             auto_ping: true,
             auto_select: true,
             backup_host: true,
+            api_dir: String::new(),
+            workdir: String::new(),
+            network: MoneroNetwork::default(),
             out_peers: 10,
             in_peers: 10,
+            simple_peers: 10,
             log_level: 3,
+            no_cache: false,
+            no_dns: false,
+            mode: P2poolMode::Auto,
+            miner: false,
+            miner_threads: 1,
+            p2p_port: String::new(),
+            log_max_mb: 0,
+            tls: false,
+            tls_port: "3334".to_string(),
+            ssh: false,
+            ssh_host: String::new(),
+            ssh_port: "22".to_string(),
+            ssh_user: String::new(),
+            ssh_key: String::new(),
+            ssh_host_key: String::new(),
+            ssh_rpc: "18081".to_string(),
+            ssh_zmq: "18083".to_string(),
             node: crate::RemoteNode::new().to_string(),
+            own_node: false,
+            own_ip: "127.0.0.1".to_string(),
+            own_rpc: "18081".to_string(),
+            own_zmq: "18083".to_string(),
             arguments: String::new(),
             address: String::with_capacity(96),
             name: "Local Monero Node".to_string(),
             ip: "localhost".to_string(),
             rpc: "18081".to_string(),
             zmq: "18083".to_string(),
+            tags: String::new(),
             selected_index: 0,
             selected_name: "Local Monero Node".to_string(),
             selected_ip: "localhost".to_string(),
             selected_rpc: "18081".to_string(),
             selected_zmq: "18083".to_string(),
+            env: Vec::new(),
         }
     }
 }
+impl P2pool {
+    // The network is an [Advanced] setting, [Simple] is always [Mainnet].
+    pub const fn network(&self) -> MoneroNetwork {
+        if self.simple {
+            MoneroNetwork::Mainnet
+        } else {
+            self.network
+        }
+    }
+
+    // Switch networks, moving the node ports along
+    // if they're still the old network's defaults.
+    pub fn set_network(&mut self, network: MoneroNetwork) {
+        let prev = self.network;
+        for (port, old, new) in [
+            (&mut self.rpc, prev.rpc(), network.rpc()),
+            (&mut self.selected_rpc, prev.rpc(), network.rpc()),
+            (&mut self.zmq, prev.zmq(), network.zmq()),
+            (&mut self.selected_zmq, prev.zmq(), network.zmq()),
+        ] {
+            if *port == old {
+                *port = new.to_string();
+            }
+        }
+        self.network = network;
+    }
+
+    // [Auto] always goes full for the built-in miner, it's unusably slow in light mode.
+    pub const fn light_mode(&self, total_ram: u64) -> bool {
+        match self.mode {
+            P2poolMode::Auto if self.miner => false,
+            mode => mode.is_light(total_ram),
+        }
+    }
+
+    // The SSH tunnel settings, [None] if any of them are invalid.
+    pub fn ssh_config(&self) -> Option<crate::ssh::TunnelConfig> {
+        let port = |s: &str| s.trim().parse::<u16>().ok().filter(|p| *p != 0);
+        let host = self.ssh_host.trim();
+        let user = self.ssh_user.trim();
+        if host.is_empty() || user.is_empty() {
+            return None;
+        }
+        Some(crate::ssh::TunnelConfig {
+            host: host.to_string(),
+            port: port(&self.ssh_port)?,
+            user: user.to_string(),
+            key: crate::ssh::key_path(&self.ssh_key),
+            host_key: self.ssh_host_key.trim().to_string(),
+            rpc: port(&self.ssh_rpc)?,
+            zmq: port(&self.ssh_zmq)?,
+        })
+    }
+
+    // Are the [Simple] own node's [IP, RPC, ZMQ] valid?
+    pub fn own_node_valid(&self) -> [bool; 3] {
+        let regex = &crate::regex::REGEXES;
+        let ip = self.own_ip.trim();
+        [
+            ip == "localhost" || regex.ipv4.is_match(ip) || regex.domain.is_match(ip),
+            regex.port.is_match(self.own_rpc.trim()),
+            regex.port.is_match(self.own_zmq.trim()),
+        ]
+    }
+
+    // The node [Simple] starts P2Pool with as ([IP], [RPC], [ZMQ]): the user's
+    // own node if it's on and valid, else the selected remote node.
+    pub fn simple_node(&self) -> (String, String, String) {
+        if self.own_node && self.own_node_valid().iter().all(|ok| *ok) {
+            let ip = match self.own_ip.trim() {
+                "localhost" => "127.0.0.1",
+                ip => ip,
+            };
+            return (
+                ip.to_string(),
+                self.own_rpc.trim().to_string(),
+                self.own_zmq.trim().to_string(),
+            );
+        }
+        let (ip, rpc, zmq) = crate::node::RemoteNode::get_ip_rpc_zmq(&self.node);
+        (ip.to_string(), rpc.to_string(), zmq.to_string())
+    }
+
+    // The node P2Pool is pointed at, as ([IP], [RPC port]).
+    pub fn rpc_target(&self) -> (String, String) {
+        if self.simple {
+            let (ip, rpc, _) = self.simple_node();
+            (ip, rpc)
+        } else {
+            (self.ip.trim().to_string(), self.rpc.trim().to_string())
+        }
+    }
+
+    // The node P2Pool would be started with, as ([IP], [RPC], [ZMQ]) the way
+    // [ImgP2pool] records it. [None] with custom arguments, they pick the node.
+    pub fn img_node(&self) -> Option<(String, String, String)> {
+        if self.simple {
+            Some(self.simple_node())
+        } else if self.arguments.is_empty() {
+            Some((
+                self.selected_ip.clone(),
+                self.selected_rpc.clone(),
+                self.selected_zmq.clone(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    // The settings P2Pool only reads on start, as ([name], [value]). A snapshot
+    // is kept in [ImgP2pool], see [restart_needed()].
+    pub fn start_settings(&self) -> Vec<(&'static str, String)> {
+        let mut settings = vec![
+            ("Mode", self.simple.to_string()),
+            ("Environment", crate::envvar::summary(&self.env)),
+            ("Working directory", self.workdir.clone()),
+        ];
+        if !self.simple && !self.arguments.is_empty() {
+            settings.push(("Command arguments", self.arguments.clone()));
+            return settings;
+        }
+        settings.push(("Wallet address", self.address.clone()));
+        if let Some((ip, rpc, zmq)) = self.img_node() {
+            settings.push(("Node", format!("{}:{}:{}", ip, rpc, zmq)));
+        }
+        settings.push(("Backup hosts", self.backup_host.to_string()));
+        settings.push(("Light mode", format!("{:?}", self.mode)));
+        settings.push((
+            "Built-in miner",
+            format!("{} {}", self.miner, self.miner_threads),
+        ));
+        settings.push(("API folder", self.api_dir.clone()));
+        if self.simple {
+            settings.push(("Peers", self.simple_peers.to_string()));
+        } else {
+            settings.push(("P2Pool Mini", self.mini.to_string()));
+            settings.push(("Network", self.network.to_string()));
+            settings.push(("Peers", format!("{}/{}", self.out_peers, self.in_peers)));
+            settings.push(("Log level", self.log_level.to_string()));
+            settings.push(("Extra flags", self.extra_args().join(" ")));
+        }
+        settings
+    }
+
+    // Is the running P2Pool (started as [img]) using a different node than the selected one?
+    pub fn node_changed(&self, img: &crate::helper::ImgP2pool) -> bool {
+        match self.img_node() {
+            Some((ip, rpc, zmq)) if img.host != "???" => {
+                (img.host.as_str(), img.rpc.as_str(), img.zmq.as_str())
+                    != (ip.as_str(), rpc.as_str(), zmq.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    // The structured [Advanced] flags that don't have a simpler home in
+    // [Helper::build_p2pool_args_and_mutate_img()], an invalid port is left out.
+    pub fn extra_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.no_cache {
+            args.push("--no-cache".to_string());
+        }
+        if self.no_dns {
+            args.push("--no-dns".to_string());
+        }
+        let port = self.p2p_port.trim();
+        if !port.is_empty() && crate::regex::REGEXES.port.is_match(port) {
+            args.push("--p2p".to_string());
+            args.push(format!("0.0.0.0:{}", port));
+        }
+        args
+    }
+}
+
 impl Xmrig {
+    // [Simple]'s CPU usage % -> threads, at least 1.
+    pub fn threads_for_usage(usage: u8, max_threads: usize) -> usize {
+        let max_threads = max_threads.max(1);
+        ((max_threads * usage as usize + 50) / 100).clamp(1, max_threads)
+    }
+
+    // Threads -> the closest CPU usage %.
+    pub fn usage_for_threads(threads: usize, max_threads: usize) -> u8 {
+        let max_threads = max_threads.max(1);
+        ((threads * 100 + max_threads / 2) / max_threads).clamp(1, 100) as u8
+    }
+
     fn with_threads(max_threads: usize, current_threads: usize) -> Self {
         let xmrig = Self::default();
         Self {
             max_threads,
             current_threads,
+            cpu_usage: Self::usage_for_threads(current_threads, max_threads),
             ..xmrig
         }
     }
+
+    // The settings XMRig only reads on start, as ([name], [value]). A snapshot
+    // is kept in [ImgXmrig], see [restart_needed()].
+    pub fn start_settings(&self) -> Vec<(&'static str, String)> {
+        let mut settings = vec![("Mode", self.simple.to_string())];
+        if self.is_watch_only() {
+            settings.push((
+                "Watch-only",
+                format!("{} {}", self.watch_url, self.watch_token),
+            ));
+            return settings;
+        }
+        settings.push(("Environment", crate::envvar::summary(&self.env)));
+        settings.push(("Working directory", self.workdir.clone()));
+        settings.push(("Threads", self.current_threads.to_string()));
+        settings.push(("Pause on active", self.pause.to_string()));
+        settings.push(("CPU priority", format!("{:?}", self.priority)));
+        settings.push((
+            "Load pause",
+            format!("{} {}", self.load_pause, self.load_pause_secs),
+        ));
+        if self.simple {
+            settings.push(("Rig name", self.simple_rig.clone()));
+        } else if !self.arguments.is_empty() {
+            settings.push(("Command arguments", self.arguments.clone()));
+        } else {
+            settings.push(("Wallet address", self.address.clone()));
+            settings.push(("Pool", format!("{}:{} {}", self.ip, self.port, self.rig)));
+            settings.push((
+                "HTTP API",
+                format!(
+                    "{} {}:{} {}",
+                    self.api_enabled, self.api_ip, self.api_port, self.api_restricted
+                ),
+            ));
+            settings.push(("TLS", self.tls.to_string()));
+            settings.push(("Keepalive", self.keepalive.to_string()));
+            settings.push((
+                "Huge pages",
+                format!("{} {}", self.pages_1gb, self.huge_pages_jit),
+            ));
+            settings.push(("MSR mod", self.msr_mod.to_string()));
+            settings.push(("Donate level", self.donate_level.to_string()));
+            settings.push(("CPU affinity", self.affinity.clone()));
+            settings.push(("Instances", format!("{:?}", self.instances)));
+        }
+        settings
+    }
+
+    // Watch-only is an [Advanced] setting.
+    pub const fn is_watch_only(&self) -> bool {
+        self.watch_only && !self.simple
+    }
+
+    // Watch-only and [--demo] don't spawn XMRig,
+    // so there's no binary (or sudo) needed.
+    pub fn needs_binary(&self) -> bool {
+        !self.is_watch_only() && !crate::demo::enabled()
+    }
+
+    // What else in Gupax's own settings uses the [Advanced] HTTP API port.
+    pub fn api_port_conflict(&self, p2pool: &P2pool) -> Option<&'static str> {
+        if self.simple || !self.api_enabled || !self.arguments.is_empty() || self.watch_only {
+            return None;
+        }
+        let port = match self.api_port.trim() {
+            "" => "18088",
+            port => port,
+        };
+        let p2p = match p2pool.p2p_port.trim() {
+            "" if p2pool.mini => "37888",
+            "" => "37889",
+            p2p => p2p,
+        };
+        let local_pool = matches!(self.ip.as_str(), "localhost" | "127.0.0.1" | "");
+        if port == "3333" {
+            Some("P2Pool's stratum [3333]")
+        } else if port == p2p {
+            Some("P2Pool's P2P port")
+        } else if local_pool && port == self.port.trim() {
+            Some("the selected (local) pool")
+        } else {
+            None
+        }
+    }
 }
 impl Default for Xmrig {
     fn default() -> Self {
         Self {
             simple: true,
             pause: 0,
+            priority: None,
+            load_pause: 0,
+            load_pause_secs: 30,
             simple_rig: String::with_capacity(30),
             arguments: String::with_capacity(300),
             address: String::with_capacity(96),
@@ -1319,6 +2870,7 @@ impl Default for Xmrig {
             rig: GUPAX_VERSION_UNDERSCORE.to_string(),
             ip: "localhost".to_string(),
             port: "3333".to_string(),
+            tags: String::new(),
             selected_index: 0,
             selected_name: "Local P2Pool".to_string(),
             selected_ip: "localhost".to_string(),
@@ -1326,13 +2878,53 @@ impl Default for Xmrig {
             selected_port: "3333".to_string(),
             api_ip: "localhost".to_string(),
             api_port: "18088".to_string(),
+            api_enabled: true,
+            api_restricted: true,
+            watch_only: false,
+            watch_url: "127.0.0.1:18088".to_string(),
+            watch_token: String::new(),
             tls: false,
             keepalive: false,
+            pages_1gb: false,
+            huge_pages_jit: false,
+            msr_mod: true,
+            donate_level: 1,
             current_threads: 1,
             max_threads: 1,
+            cpu_usage: 50,
+            affinity: String::new(),
+            workdir: String::new(),
+            instances: Vec::new(),
+            env: Vec::new(),
         }
     }
 }
+impl Default for Proxy {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_PROXY_PATH.to_string(),
+            mode: ProxyMode::default(),
+            bind_ip: "0.0.0.0".to_string(),
+            bind_port: "3355".to_string(),
+            ip: "localhost".to_string(),
+            port: "3333".to_string(),
+            address: String::with_capacity(96),
+            tls: false,
+            keepalive: false,
+            api_ip: "localhost".to_string(),
+            api_port: "18087".to_string(),
+            arguments: String::with_capacity(300),
+            workdir: String::new(),
+            env: Vec::new(),
+        }
+    }
+}
+impl Proxy {
+    // [path] made absolute, relative ones are relative to Gupax.
+    pub fn absolute_path(&self) -> PathBuf {
+        into_absolute_path(self.path.clone()).unwrap_or_else(|_| PathBuf::from(&self.path))
+    }
+}
 impl Default for Version {
     fn default() -> Self {
         Self {
@@ -1368,6 +2960,8 @@ mod test {
     #[test]
     fn serde_custom_state() {
         let state = r#"
+			schema = 2
+
 			[gupax]
 			simple = true
 			auto_update = true
@@ -1375,7 +2969,45 @@ mod test {
 			auto_xmrig = false
 			ask_before_quit = true
 			save_before_quit = true
+			window_stats = true
+			colorblind = true
+			time_check = true
+			event_log = true
+			console_history = true
+			console_cap = 2000
+			usage_stats = false
+			escalation = "Doas"
+			console_colors = true
+			restart_on_resume = false
+			start_on_login = false
+			start_on_login_quiet = true
+			number_format = "Comma"
+			short_time = false
+			hashrate_unit = "Kilo"
+			global_hotkeys = false
+			hotkey_xmrig = "Ctrl+Shift+F9"
+			hotkey_window = "Ctrl+Shift+F10"
+			payout_sound = false
+			payout_volume = 50
+			payout_backups = 10
+			quiet_hours = true
+			quiet_start = 22
+			quiet_end = 8
 			update_via_tor = true
+			update_gupax = false
+			update_p2pool = true
+			update_xmrig = true
+			p2pool_start_delay = 30
+			xmrig_start_delay = 45
+			wait_for_network = true
+			ip_preference = "Ipv4"
+			dns_over_https = "https://cloudflare-dns.com/dns-query"
+			hook_start = ""
+			hook_stop = ""
+			hook_crash = "/home/hinto/crash.sh"
+			hook_payout = ""
+			gfx_x11 = true
+			gfx_renderer = "Glow"
 			p2pool_path = "p2pool/p2pool"
 			xmrig_path = "xmrig/xmrig"
 			absolute_p2pool_path = "/home/hinto/p2pool/p2pool"
@@ -1386,13 +3018,26 @@ mod test {
 			tab = "About"
 			ratio = "Width"
 
+			[[gupax.display_scales]]
+			display = "3840x2160@2"
+			scale = 1.5
+
 			[status]
 			submenu = "P2pool"
 			payout_view = "Oldest"
+			payout_timezone = "Utc"
+			console_layout = "Stacked"
+			console_split = 0.5
+			console_sync = false
 			monero_enabled = true
 			manual_hash = false
 			hashrate = 1241.23
 			hash_metric = "Hash"
+			processes_layout = ["Xmrig", "Gupax", "P2pool"]
+			p2pool_layout = ["Payouts", "Calculator"]
+			collapsed = ["Gupax"]
+			events_hidden = ["Share"]
+			explorer = "http://127.0.0.1:8081/block/{height}"
 
 			[p2pool]
 			simple = true
@@ -1400,43 +3045,114 @@ mod test {
 			auto_ping = true
 			auto_select = true
 			backup_host = true
+			api_dir = ""
+			workdir = "/mnt/p2pool"
+			network = "Mainnet"
 			out_peers = 10
 			in_peers = 450
+			simple_peers = 10
 			log_level = 3
+			no_cache = false
+			no_dns = false
+			mode = "Auto"
+			miner = false
+			miner_threads = 1
+			p2p_port = ""
+			log_max_mb = 0
+			tls = false
+			tls_port = "3334"
+			ssh = false
+			ssh_host = ""
+			ssh_port = "22"
+			ssh_user = ""
+			ssh_key = ""
+			ssh_host_key = ""
+			ssh_rpc = "18081"
+			ssh_zmq = "18083"
 			node = "Seth"
+			own_node = true
+			own_ip = "192.168.1.2"
+			own_rpc = "18089"
+			own_zmq = "18084"
 			arguments = ""
 			address = "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW"
 			name = "Local Monero Node"
 			ip = "192.168.1.123"
 			rpc = "18089"
 			zmq = "18083"
+			tags = "home"
 			selected_index = 0
 			selected_name = "Local Monero Node"
 			selected_ip = "192.168.1.123"
 			selected_rpc = "18089"
 			selected_zmq = "18083"
 
+			[[p2pool.env]]
+			key = "LC_ALL"
+			value = "C"
+
 			[xmrig]
 			simple = true
 			pause = 0
+			priority = 3
+			load_pause = 80
+			load_pause_secs = 30
 			simple_rig = ""
 			arguments = ""
 			tls = false
 			keepalive = false
+			pages_1gb = false
+			huge_pages_jit = false
+			msr_mod = true
+			donate_level = 0
+			api_enabled = true
+			api_restricted = true
 			max_threads = 32
 			current_threads = 16
+			cpu_usage = 50
 			address = ""
 			api_ip = "localhost"
 			api_port = "18088"
+			watch_only = false
+			watch_url = "127.0.0.1:18088"
+			watch_token = ""
 			name = "linux"
 			rig = "Gupax"
 			ip = "192.168.1.122"
 			port = "3333"
+			tags = ""
 			selected_index = 1
 			selected_name = "linux"
 			selected_rig = "Gupax"
 			selected_ip = "192.168.1.122"
 			selected_port = "3333"
+			affinity = "0-15"
+			workdir = ""
+
+			[[xmrig.instances]]
+			threads = 16
+			affinity = "16-31"
+			api_port = "18089"
+
+			[[xmrig.env]]
+			key = "LD_PRELOAD"
+			value = "/usr/lib/libjemalloc.so"
+
+			[proxy]
+			path = "xmrig-proxy/xmrig-proxy"
+			mode = "Simple"
+			bind_ip = "0.0.0.0"
+			bind_port = "3355"
+			ip = "localhost"
+			port = "3333"
+			address = ""
+			tls = false
+			keepalive = true
+			api_ip = "localhost"
+			api_port = "18087"
+			arguments = ""
+			workdir = ""
+			env = []
 
 			[version]
 			gupax = "v1.3.0"
@@ -1444,6 +3160,9 @@ mod test {
 			xmrig = "v6.18.0"
 		"#;
         let state = crate::State::from_str(state).unwrap();
+        assert_eq!(state.xmrig.instances[0].threads, 16);
+        assert_eq!(state.gupax.gfx_renderer, crate::gfx::Renderer::Glow);
+        assert_eq!(state.proxy.mode, crate::disk::ProxyMode::Simple);
         crate::State::to_string(&state).unwrap();
     }
 
@@ -1595,6 +3314,8 @@ mod test {
         api.payout_u64 = 1;
         api.xmr = AtomicUnit::from_u64(2);
         let (date, atomic_unit, block) = PayoutOrd::parse_raw_payout_line(&api.log);
+        let date = PayoutOrd::date_to_utc(&date);
+        assert!(date.ends_with('Z'));
         let formatted_log_line = GupaxP2poolApi::format_payout(&date, &atomic_unit, &block);
         GupaxP2poolApi::write_to_all_files(&api, &formatted_log_line).unwrap();
         println!("AFTER WRITE: {:#?}", api);
@@ -1607,9 +3328,8 @@ mod test {
         assert_eq!(api.payout_u64, 1);
         assert_eq!(api.xmr.to_u64(), 2);
         assert!(!api.payout_ord.is_empty());
-        assert!(api
-            .log
-            .contains("2022-01-27 01:30:23.1377 | 0.000000000001 XMR | Block 2,642,816"));
+        assert!(api.log.contains(&formatted_log_line));
+        assert!(!PayoutOrd::log_has_local_dates(&api.log));
     }
 
     #[test]
@@ -1636,4 +3356,495 @@ mod test {
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Mega), 1_000.0);
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Giga), 1.0);
     }
+
+    #[test]
+    fn import_node_and_pool_csv() {
+        let csv = "name,ip,rpc,zmq\n# comment\nNode One,192.168.1.2,18081,18083\n\nbad node!,localhost,1,2\nnode.two,node.com,18089,18084\n";
+        let nodes = crate::Node::from_csv(csv).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].0, "Node One");
+        assert_eq!(nodes[1].1.rpc, "18089");
+
+        let csv = "Local,localhost,3333\nRemote,pool.com,3334,my_rig\nBad,pool.com,99999\n";
+        let pools = crate::Pool::from_csv(csv).unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].1.rig, "");
+        assert_eq!(pools[1].1.rig, "my_rig");
+
+        assert!(crate::Pool::from_csv("name,ip,port\n").is_err());
+    }
+
+    #[test]
+    fn import_xmrig_config() {
+        let config = r#"{
+			"api": { "id": null },
+			"pools": [
+				{ "url": "stratum+tcp://pool.example.com:3333", "user": "4...", "rig-id": "rig1" },
+				{ "url": "192.168.1.2:3334", "user": "4...", "rig-id": null },
+				{ "url": "no-port.com", "user": "4..." },
+				{ "url": "stratum+tcp://aaaaaaaaaaaaaaaaaaaaaaaaaaaaaé.example.com:3333", "user": "4..." },
+				{ "url": "stratum+tcp://プール.example.com:3333", "user": "4..." }
+			]
+		}"#;
+        // Non-ASCII hosts are skipped, not cut in the middle of a character.
+        let pools = crate::Pool::from_xmrig_config(config).unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].0, "pool.example.com");
+        assert_eq!(pools[0].1.port, "3333");
+        assert_eq!(pools[0].1.rig, "rig1");
+        assert_eq!(pools[1].1.ip, "192.168.1.2");
+        assert!(crate::Pool::from_xmrig_config("{}").is_err());
+        assert!(crate::Pool::from_xmrig_config("not json").is_err());
+    }
+
+    #[test]
+    fn merge_imported_conflicts() {
+        use crate::disk::merge_imported;
+        let mut vec = crate::Pool::new_vec();
+        let imported = vec![
+            crate::Pool::new_tuple(),
+            (
+                "Local P2Pool".to_string(),
+                crate::Pool {
+                    rig: String::new(),
+                    ip: "pool.com".to_string(),
+                    port: "3333".to_string(),
+                    tags: vec![],
+                },
+            ),
+        ];
+        let (added, skipped) = merge_imported(&mut vec, imported);
+        assert_eq!((added, skipped), (1, 1));
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec[1].0, "Local P2Pool_2");
+    }
+
+    #[test]
+    fn unique_name_templates() {
+        use crate::disk::unique_name;
+        let mut vec = crate::Node::new_vec();
+        assert_eq!(unique_name(&vec, "New"), "New");
+        assert_eq!(
+            unique_name(&vec, "Local Monero Node"),
+            "Local Monero Node_2"
+        );
+        vec.push(("Local Monero Node_2".to_string(), crate::Node::localhost()));
+        assert_eq!(
+            unique_name(&vec, "Local Monero Node"),
+            "Local Monero Node_3"
+        );
+        let long = "a".repeat(30);
+        vec.push((long.clone(), crate::Node::localhost()));
+        assert_eq!(unique_name(&vec, &long), format!("{}_2", "a".repeat(28)));
+        // Template names are valid names.
+        for (name, _) in crate::Node::templates() {
+            assert!(crate::regex::REGEXES.name.is_match(name));
+        }
+        for (name, _) in crate::Pool::templates() {
+            assert!(crate::regex::REGEXES.name.is_match(name));
+        }
+    }
+
+    #[test]
+    fn status_layout() {
+        use crate::disk::StatusGroup::{self, *};
+        // Unknown, duplicate and missing groups.
+        let saved = [Xmrig, Payouts, Xmrig, Gupax];
+        assert_eq!(
+            StatusGroup::layout(&saved, &StatusGroup::PROCESSES),
+            [Xmrig, Gupax, P2pool]
+        );
+        assert_eq!(
+            StatusGroup::layout(&[], &StatusGroup::P2POOL),
+            [Payouts, Calculator]
+        );
+        assert_eq!(
+            StatusGroup::shown(&saved, &[Gupax], &StatusGroup::PROCESSES),
+            [Xmrig, P2pool]
+        );
+        // Drag & drop.
+        let mut layout = StatusGroup::PROCESSES.to_vec();
+        StatusGroup::move_to(&mut layout, Xmrig, Gupax);
+        assert_eq!(layout, [Xmrig, Gupax, P2pool]);
+        StatusGroup::move_to(&mut layout, Xmrig, P2pool);
+        assert_eq!(layout, [Gupax, P2pool, Xmrig]);
+        StatusGroup::move_to(&mut layout, Payouts, Gupax);
+        assert_eq!(layout, [Gupax, P2pool, Xmrig]);
+    }
+
+    #[test]
+    fn node_pool_tags() {
+        use crate::disk::{parse_tags, ListFilter, ListSort};
+        assert_eq!(parse_tags(" home, fast,,home, bad!tag "), ["home", "fast"]);
+
+        // Old files without [tags] still parse.
+        let old = "['Old']\nip = \"localhost\"\nrpc = \"18081\"\nzmq = \"18083\"\n\n";
+        let nodes = crate::Node::from_str_to_vec(old).unwrap();
+        assert!(nodes[0].1.tags.is_empty());
+        assert_eq!(crate::Node::to_string(&nodes).unwrap(), old);
+
+        // Tags survive a round-trip.
+        let mut pools = crate::Pool::new_vec();
+        pools.push((
+            "Remote".to_string(),
+            crate::Pool {
+                rig: String::new(),
+                ip: "pool.com".to_string(),
+                port: "3333".to_string(),
+                tags: vec!["away".to_string(), "tls".to_string()],
+            },
+        ));
+        let string = crate::Pool::to_string(&pools).unwrap();
+        assert_eq!(crate::Pool::from_str_to_vec(&string).unwrap(), pools);
+        let csv = "Tagged,pool.com,3333,,away;tls\n";
+        assert_eq!(
+            crate::Pool::from_csv(csv).unwrap()[0].1.tags,
+            ["away", "tls"]
+        );
+
+        // Filter by name/IP/tag, sort by name/latency.
+        let mut filter = ListFilter::default();
+        let latency = |ip: &str| (ip == "pool.com").then_some(50);
+        assert_eq!(filter.indices(&pools, latency), [0, 1]);
+        filter.text = "#AWAY".to_string();
+        assert_eq!(filter.indices(&pools, latency), [1]);
+        filter.text = "local".to_string();
+        assert_eq!(filter.indices(&pools, latency), [0]);
+        filter.text = "#tl".to_string();
+        assert!(filter.indices(&pools, latency).is_empty());
+        filter.text.clear();
+        filter.sort = ListSort::Name;
+        assert_eq!(filter.indices(&pools, latency), [0, 1]);
+        filter.sort = ListSort::Latency;
+        assert_eq!(filter.indices(&pools, latency), [1, 0]);
+    }
+
+    #[test]
+    fn p2pool_extra_args() {
+        let mut p2pool = crate::disk::P2pool::default();
+        assert!(p2pool.extra_args().is_empty());
+        p2pool.no_cache = true;
+        p2pool.no_dns = true;
+        p2pool.p2p_port = " 37890 ".to_string();
+        assert_eq!(
+            p2pool.extra_args(),
+            ["--no-cache", "--no-dns", "--p2p", "0.0.0.0:37890"]
+        );
+        p2pool.p2p_port = "99999".to_string();
+        assert_eq!(p2pool.extra_args(), ["--no-cache", "--no-dns"]);
+    }
+
+    #[test]
+    fn data_path_writable() {
+        let dir = std::env::temp_dir();
+        assert!(crate::disk::is_writable(&dir));
+        assert!(!dir.join(".gupax_write_test").exists());
+        assert!(!crate::disk::is_writable(
+            &dir.join("gupax_does_not_exist").join("x")
+        ));
+
+        // Only write errors are expected when read-only, broken files aren't.
+        use crate::disk::TomlError;
+        use std::io::{Error, ErrorKind};
+        let io = |kind| TomlError::Io(Error::from(kind));
+        assert!(io(ErrorKind::PermissionDenied).is_write());
+        assert!(io(ErrorKind::ReadOnlyFilesystem).is_write());
+        assert!(!io(ErrorKind::NotFound).is_write());
+        assert!(!TomlError::Parse("payout").is_write());
+    }
+
+    #[test]
+    fn gupax_p2pool_api_utc_log() {
+        use crate::disk::*;
+        use crate::xmr::PayoutOrd;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        GupaxP2poolApi::create_all_files(&dir).unwrap();
+        let local = "2022-04-11 00:20:17.2571 | 0.600000000000 XMR | Block 2,600,000\n";
+        let log = dir.join(GUPAX_P2POOL_API_LOG);
+        let bak = dir.join("log.bak");
+        let mut api = GupaxP2poolApi::new();
+        api.fill_paths(&dir);
+
+        // The original is kept, the file is replaced with UTC dates.
+        std::fs::write(&log, local).unwrap();
+        api.read_all_files_and_update().unwrap();
+        assert!(!PayoutOrd::log_has_local_dates(&api.log));
+        assert_eq!(std::fs::read_to_string(&bak).unwrap(), local);
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), api.log);
+
+        // A failed write doesn't fail the read, it's converted in memory.
+        std::fs::create_dir(dir.join("log.tmp")).unwrap();
+        std::fs::write(&log, local).unwrap();
+        api.read_all_files_and_update().unwrap();
+        assert!(!PayoutOrd::log_has_local_dates(&api.log));
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), local);
+    }
+
+    #[test]
+    fn gupax_p2pool_api_backup() {
+        use crate::disk::{GupaxP2poolApi, GUPAX_P2POOL_API_PAYOUT};
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        GupaxP2poolApi::create_all_files(&dir).unwrap();
+        let payout = dir.join(GUPAX_P2POOL_API_PAYOUT);
+        std::fs::write(&payout, "1").unwrap();
+
+        // First backup, then an identical one is skipped.
+        let first = GupaxP2poolApi::backup(&dir, 2).unwrap().unwrap();
+        assert!(GupaxP2poolApi::backup(&dir, 2).unwrap().is_none());
+
+        // Only the newest [keep] are kept.
+        for n in 2..=4 {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            std::fs::write(&payout, n.to_string()).unwrap();
+            GupaxP2poolApi::backup(&dir, 2).unwrap().unwrap();
+        }
+        let backups = GupaxP2poolApi::backups(&dir);
+        assert_eq!(backups.len(), 2);
+        assert!(!first.exists());
+        assert_eq!(
+            std::fs::read_to_string(backups[0].join(GUPAX_P2POOL_API_PAYOUT)).unwrap(),
+            "4"
+        );
+
+        // Restoring backs up the current files, and a reset keeps the backups.
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        std::fs::write(&payout, "5").unwrap();
+        GupaxP2poolApi::restore(&dir, &backups[1]).unwrap();
+        assert_eq!(std::fs::read_to_string(&payout).unwrap(), "3");
+        assert_eq!(GupaxP2poolApi::backups(&dir).len(), 3);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        GupaxP2poolApi::create_new(&dir).unwrap();
+        assert_eq!(std::fs::read_to_string(&payout).unwrap(), "0\n");
+        assert_eq!(GupaxP2poolApi::backups(&dir).len(), 4);
+    }
+
+    #[test]
+    fn restart_needed() {
+        use crate::disk::{restart_needed, P2pool, Xmrig};
+        let mut p2pool = P2pool::default();
+        let started = p2pool.start_settings();
+        // Not started by Gupax.
+        assert!(restart_needed(&[], &started).is_empty());
+        assert!(restart_needed(&started, &p2pool.start_settings()).is_empty());
+        // Settings read at runtime don't count.
+        p2pool.auto_ping = !p2pool.auto_ping;
+        p2pool.tls = !p2pool.tls;
+        assert!(restart_needed(&started, &p2pool.start_settings()).is_empty());
+        p2pool.address = "4".repeat(95);
+        p2pool.miner = !p2pool.miner;
+        assert_eq!(
+            restart_needed(&started, &p2pool.start_settings()),
+            ["Wallet address", "Built-in miner"]
+        );
+        p2pool.simple = !p2pool.simple;
+        assert_eq!(restart_needed(&started, &p2pool.start_settings()), ["Mode"]);
+
+        let mut xmrig = Xmrig {
+            simple: false,
+            ..Default::default()
+        };
+        let started = xmrig.start_settings();
+        xmrig.arguments = "--threads 1".to_string();
+        assert_eq!(
+            restart_needed(&started, &xmrig.start_settings()),
+            [
+                "Command arguments",
+                "Wallet address",
+                "Pool",
+                "HTTP API",
+                "TLS",
+                "Keepalive",
+                "Huge pages",
+                "MSR mod",
+                "Donate level",
+                "CPU affinity",
+                "Instances"
+            ]
+        );
+    }
+
+    #[test]
+    fn p2pool_node_changed() {
+        let mut p2pool = crate::disk::P2pool {
+            node: crate::REMOTE_NODES[0].0.to_string(),
+            ..Default::default()
+        };
+        let mut img = crate::helper::ImgP2pool::new();
+        // Not started yet.
+        assert!(!p2pool.node_changed(&img));
+        let (host, rpc, zmq) = p2pool.img_node().unwrap();
+        (img.host, img.rpc, img.zmq) = (host, rpc, zmq);
+        assert!(!p2pool.node_changed(&img));
+        p2pool.node = crate::REMOTE_NODES[1].0.to_string();
+        assert!(p2pool.node_changed(&img));
+        // Custom arguments pick their own node.
+        p2pool.simple = false;
+        p2pool.arguments = "--host 127.0.0.1".to_string();
+        assert!(!p2pool.node_changed(&img));
+    }
+
+    #[test]
+    fn p2pool_own_node() {
+        let mut p2pool = crate::disk::P2pool {
+            node: crate::REMOTE_NODES[0].0.to_string(),
+            ..Default::default()
+        };
+        let (ip, rpc, zmq) = crate::RemoteNode::get_ip_rpc_zmq(&p2pool.node);
+        let remote = (ip.to_string(), rpc.to_string(), zmq.to_string());
+        // Off by default.
+        assert_eq!(p2pool.simple_node(), remote);
+        p2pool.own_node = true;
+        assert_eq!(p2pool.own_node_valid(), [true; 3]);
+        assert_eq!(
+            p2pool.simple_node(),
+            ("127.0.0.1".into(), "18081".into(), "18083".into())
+        );
+        p2pool.own_ip = "localhost".to_string();
+        assert_eq!(p2pool.rpc_target(), ("127.0.0.1".into(), "18081".into()));
+        // An invalid field falls back to the remote node.
+        p2pool.own_zmq = "abc".to_string();
+        assert_eq!(p2pool.own_node_valid(), [true, true, false]);
+        assert_eq!(p2pool.img_node(), Some(remote));
+    }
+
+    #[test]
+    fn p2pool_ssh_config() {
+        let mut p2pool = crate::disk::P2pool::default();
+        assert!(p2pool.ssh_config().is_none());
+        p2pool.ssh_host = " node.example.com ".to_string();
+        p2pool.ssh_user = "monero".to_string();
+        p2pool.ssh_key = "/keys/node".to_string();
+        let config = p2pool.ssh_config().unwrap();
+        assert_eq!(config.host, "node.example.com");
+        assert_eq!((config.port, config.rpc, config.zmq), (22, 18081, 18083));
+        assert_eq!(config.key, std::path::PathBuf::from("/keys/node"));
+        p2pool.ssh_zmq = "0".to_string();
+        assert!(p2pool.ssh_config().is_none());
+    }
+
+    #[test]
+    fn p2pool_mode() {
+        use crate::disk::P2poolMode;
+        let gb = 1_000_000_000;
+        assert!(P2poolMode::Auto.is_light(8 * gb));
+        assert!(!P2poolMode::Auto.is_light(16 * gb));
+        assert!(P2poolMode::Light.is_light(64 * gb));
+        assert!(!P2poolMode::Full.is_light(4 * gb));
+        assert_eq!(P2poolMode::recommended(15_500_000_000), P2poolMode::Full);
+        let mut p2pool = crate::disk::P2pool::default();
+        assert!(p2pool.light_mode(8 * gb));
+        p2pool.miner = true;
+        assert!(!p2pool.light_mode(8 * gb));
+        p2pool.mode = P2poolMode::Light;
+        assert!(p2pool.light_mode(64 * gb));
+    }
+
+    #[test]
+    fn xmrig_api_port_conflict() {
+        let mut p2pool = crate::disk::P2pool::default();
+        let mut xmrig = crate::disk::Xmrig {
+            simple: false,
+            ..Default::default()
+        };
+        assert_eq!(xmrig.api_port_conflict(&p2pool), None);
+        xmrig.api_port = "3333".to_string();
+        assert!(xmrig.api_port_conflict(&p2pool).is_some());
+        p2pool.mini = true;
+        xmrig.api_port = "37888".to_string();
+        assert!(xmrig.api_port_conflict(&p2pool).is_some());
+        p2pool.p2p_port = "37890".to_string();
+        assert_eq!(xmrig.api_port_conflict(&p2pool), None);
+        xmrig.api_port = "37890".to_string();
+        assert!(xmrig.api_port_conflict(&p2pool).is_some());
+        // Only the local pool counts.
+        xmrig.api_port = "4444".to_string();
+        xmrig.port = "4444".to_string();
+        assert!(xmrig.api_port_conflict(&p2pool).is_some());
+        xmrig.ip = "pool.example.com".to_string();
+        assert_eq!(xmrig.api_port_conflict(&p2pool), None);
+        // No API, no conflict.
+        xmrig.ip = "localhost".to_string();
+        xmrig.api_enabled = false;
+        assert_eq!(xmrig.api_port_conflict(&p2pool), None);
+    }
+
+    #[test]
+    fn xmrig_cpu_usage() {
+        use crate::disk::Xmrig;
+        assert_eq!(Xmrig::threads_for_usage(50, 16), 8);
+        assert_eq!(Xmrig::threads_for_usage(1, 16), 1);
+        assert_eq!(Xmrig::threads_for_usage(100, 16), 16);
+        assert_eq!(Xmrig::threads_for_usage(33, 12), 4);
+        assert_eq!(Xmrig::threads_for_usage(50, 0), 1);
+        assert_eq!(Xmrig::usage_for_threads(8, 16), 50);
+        assert_eq!(Xmrig::usage_for_threads(1, 3), 33);
+        assert_eq!(Xmrig::usage_for_threads(16, 16), 100);
+        // Round trips land on the same thread count.
+        for threads in 1..=24 {
+            let usage = Xmrig::usage_for_threads(threads, 24);
+            assert_eq!(Xmrig::threads_for_usage(usage, 24), threads);
+        }
+    }
+
+    #[test]
+    fn profiles() {
+        use crate::disk::{list_profiles, profile_path, PROFILE_DIRECTORY};
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        assert!(list_profiles(&dir).is_empty());
+        let path = profile_path(&dir, "rig-01_a").unwrap();
+        assert_eq!(path, dir.join(PROFILE_DIRECTORY).join("rig-01_a.toml"));
+        assert_eq!(profile_path(&dir, ""), None);
+        assert_eq!(profile_path(&dir, "../state"), None);
+        assert_eq!(profile_path(&dir, "rig 1"), None);
+        std::fs::create_dir_all(dir.join(PROFILE_DIRECTORY)).unwrap();
+        for file in ["rig-b.toml", "rig-a.toml", "notes.txt", "bad name.toml"] {
+            std::fs::write(dir.join(PROFILE_DIRECTORY).join(file), "").unwrap();
+        }
+        assert_eq!(list_profiles(&dir), ["rig-a", "rig-b"]);
+    }
+
+    #[test]
+    fn save_skips_unchanged() {
+        use crate::disk::{save_all, write_toml, File, Node, Pool, State};
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let path = dir.join("state.toml");
+        assert!(write_toml(File::State, &path, "a = 1\n").unwrap());
+        assert!(!write_toml(File::State, &path, "a = 1\n").unwrap());
+        assert!(write_toml(File::State, &path, "a = 2\n").unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a = 2\n");
+        assert!(!dir.join("state.toml.tmp").exists());
+
+        // Everything is written together, a failed file replaces none of them.
+        let (node, pool) = (dir.join("node.toml"), dir.join("pool.toml"));
+        let mut state = State::new();
+        save_all(
+            &mut state,
+            &path,
+            &Node::new_vec(),
+            &node,
+            &Pool::new_vec(),
+            &pool,
+        )
+        .unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("[gupax]"));
+        assert!(node.is_file() && pool.is_file());
+        let missing = dir.join("missing").join("pool.toml");
+        state.gupax.simple = !state.gupax.simple;
+        let before = std::fs::read_to_string(&path).unwrap();
+        assert!(save_all(
+            &mut state,
+            &path,
+            &Node::new_vec(),
+            &node,
+            &Pool::new_vec(),
+            &missing
+        )
+        .is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+        assert!(!dir.join("state.toml.tmp").exists());
+    }
 }
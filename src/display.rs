@@ -0,0 +1,108 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Per-display window scaling.
+//
+// egui doesn't name monitors, it only tells us the size of the one the
+// window is on and its OS scale factor, so a display is keyed by its
+// physical resolution and scale factor, e.g: [3840x2160@2].
+// Each display can have its own [selected_scale], displays without
+// one fall back to the old global [selected_scale].
+
+use crate::disk::{DisplayScale, Gupax};
+
+//---------------------------------------------------------------------------------------------------- Display
+// The display the window is currently on, [None] if the platform doesn't say.
+pub fn current(ctx: &egui::Context) -> Option<String> {
+    ctx.input(|i| {
+        let viewport = i.viewport();
+        Some(key(
+            viewport.monitor_size?,
+            viewport.native_pixels_per_point?,
+        ))
+    })
+}
+
+// [size] is in points, like egui reports it.
+pub fn key(size: egui::Vec2, native_pixels_per_point: f32) -> String {
+    format!(
+        "{}x{}@{}",
+        (size.x * native_pixels_per_point).round() as u32,
+        (size.y * native_pixels_per_point).round() as u32,
+        (native_pixels_per_point * 100.0).round() / 100.0,
+    )
+}
+
+//---------------------------------------------------------------------------------------------------- Scale
+impl Gupax {
+    // The scale to use on [display].
+    pub fn scale(&self, display: Option<&str>) -> f32 {
+        display
+            .and_then(|d| self.display_scales.iter().find(|s| s.display == d))
+            .map_or(self.selected_scale, |s| s.scale)
+    }
+
+    // Set the scale for [display], or the global one if the display isn't known.
+    pub fn set_scale(&mut self, display: Option<&str>, scale: f32) {
+        let Some(display) = display else {
+            self.selected_scale = scale;
+            return;
+        };
+        match self
+            .display_scales
+            .iter_mut()
+            .find(|s| s.display == display)
+        {
+            Some(s) => s.scale = scale,
+            None => self.display_scales.push(DisplayScale {
+                display: display.to_string(),
+                scale,
+            }),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn display_scale() {
+        use crate::disk::Gupax;
+        assert_eq!(super::key(egui::vec2(1920.0, 1080.0), 2.0), "3840x2160@2");
+        assert_eq!(
+            super::key(egui::vec2(1706.6666, 960.0), 1.5),
+            "2560x1440@1.5"
+        );
+
+        let mut gupax = Gupax {
+            selected_scale: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(gupax.scale(Some("3840x2160@2")), 1.0);
+        gupax.set_scale(Some("3840x2160@2"), 1.5);
+        gupax.set_scale(Some("1920x1080@1"), 0.8);
+        gupax.set_scale(Some("3840x2160@2"), 1.7);
+        assert_eq!(gupax.display_scales.len(), 2);
+        assert_eq!(gupax.scale(Some("3840x2160@2")), 1.7);
+        assert_eq!(gupax.scale(Some("1920x1080@1")), 0.8);
+        assert_eq!(gupax.scale(Some("1280x720@1")), 1.0);
+        assert_eq!(gupax.scale(None), 1.0);
+        gupax.set_scale(None, 1.2);
+        assert_eq!(gupax.selected_scale, 1.2);
+        assert_eq!(gupax.scale(Some("1280x720@1")), 1.2);
+    }
+}
@@ -0,0 +1,193 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Extra environment variables for P2Pool/XMRig, and the
+// [Working directory] editor next to them.
+//
+// Some setups need them, e.g: [LD_PRELOAD] for a different allocator,
+// or [LC_ALL] when the locale breaks a process's output.
+// They're set on the PTY's [CommandBuilder], and on Unix XMRig is started
+// through [sudo/doas/pkexec] which drop the environment, so they're
+// also passed through [env] as arguments to the launcher.
+
+use crate::{constants::*, disk::EnvVar};
+use egui::{Label, RichText, TextEdit};
+use std::path::Path;
+
+//---------------------------------------------------------------------------------------------------- Env
+// [A-Za-z_][A-Za-z0-9_]*
+pub fn valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The variables that will actually be set, blank/invalid keys are skipped.
+pub fn vars(env: &[EnvVar]) -> impl Iterator<Item = (&str, &str)> {
+    env.iter()
+        .map(|var| (var.key.trim(), var.value.as_str()))
+        .filter(|(key, _)| valid_key(key))
+}
+
+// [KEY=VALUE KEY2=VALUE2], for showing what a process was started with.
+pub fn summary(env: &[EnvVar]) -> String {
+    vars(env)
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn apply(cmd: &mut portable_pty::CommandBuilder, env: &[EnvVar]) {
+    for (key, value) in vars(env) {
+        cmd.env(key, value);
+    }
+}
+
+// Wrap the program (the last of the launcher's arguments) with [env KEY=VALUE...].
+pub fn launcher_args(mut args: Vec<String>, env: &[EnvVar]) -> Vec<String> {
+    let vars: Vec<String> = vars(env).map(|(k, v)| format!("{k}={v}")).collect();
+    if vars.is_empty() || args.is_empty() {
+        return args;
+    }
+    let program = args.len() - 1;
+    args.splice(
+        program..program,
+        std::iter::once("env".to_string()).chain(vars),
+    );
+    args
+}
+
+//---------------------------------------------------------------------------------------------------- GUI
+// The key/value rows + [Add variable] button, shared by the P2Pool/XMRig tabs.
+pub fn editor(env: &mut Vec<EnvVar>, width: f32, height: f32, ui: &mut egui::Ui) {
+    let mut remove = None;
+    for (i, var) in env.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let color = if valid_key(var.key.trim()) {
+                LIGHT_GRAY
+            } else {
+                RED
+            };
+            ui.add_sized(
+                [width * 2.0, height],
+                TextEdit::hint_text(TextEdit::singleline(&mut var.key), "KEY").text_color(color),
+            )
+            .on_hover_text(GUPAX_ENV_KEY);
+            ui.add_sized([width * 0.25, height], Label::new(RichText::new("=")));
+            ui.add_sized(
+                [width * 6.0, height],
+                TextEdit::hint_text(TextEdit::singleline(&mut var.value), "value"),
+            );
+            var.key.truncate(128);
+            var.value.truncate(4096);
+            if ui.button("Remove").clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        env.remove(i);
+    }
+    if ui
+        .button("Add variable")
+        .on_hover_text(GUPAX_ENV_ADD)
+        .clicked()
+    {
+        env.push(EnvVar::default());
+    }
+}
+
+// The [Working directory] row, red if it doesn't exist. [path] is the binary's.
+pub fn workdir_editor(
+    workdir: &mut String,
+    path: &Path,
+    width: f32,
+    height: f32,
+    ui: &mut egui::Ui,
+) {
+    ui.horizontal(|ui| {
+        let dir = crate::helper::Helper::workdir(workdir, path);
+        let color = if workdir.trim().is_empty() {
+            LIGHT_GRAY
+        } else if dir.is_dir() {
+            GREEN
+        } else {
+            RED
+        };
+        ui.add_sized(
+            [width * 2.0, height],
+            Label::new(RichText::new("Working directory:").color(color)),
+        );
+        ui.add_sized(
+            [ui.available_width(), height],
+            TextEdit::hint_text(
+                TextEdit::singleline(workdir),
+                "Default: the directory the binary is in",
+            ),
+        )
+        .on_hover_text(format!("{}\n\n{}", GUPAX_WORKDIR, dir.display()));
+        workdir.truncate(1024);
+    });
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn env_vars() {
+        use crate::disk::EnvVar;
+        let var = |key: &str, value: &str| EnvVar {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        assert!(super::valid_key("LD_PRELOAD"));
+        assert!(super::valid_key("_x1"));
+        assert!(!super::valid_key(""));
+        assert!(!super::valid_key("1X"));
+        assert!(!super::valid_key("A=B"));
+        assert!(!super::valid_key("A B"));
+
+        let env = [
+            var(" LD_PRELOAD ", "/usr/lib/libjemalloc.so"),
+            var("", "ignored"),
+            var("BAD KEY", "ignored"),
+            var("LC_ALL", "C"),
+        ];
+        assert_eq!(
+            super::vars(&env).collect::<Vec<_>>(),
+            [("LD_PRELOAD", "/usr/lib/libjemalloc.so"), ("LC_ALL", "C")]
+        );
+
+        let args = vec![
+            "--prompt=".to_string(),
+            "--".to_string(),
+            "/tmp/xmrig".to_string(),
+        ];
+        assert_eq!(
+            super::launcher_args(args.clone(), &env),
+            [
+                "--prompt=",
+                "--",
+                "env",
+                "LD_PRELOAD=/usr/lib/libjemalloc.so",
+                "LC_ALL=C",
+                "/tmp/xmrig"
+            ]
+        );
+        assert_eq!(super::launcher_args(args.clone(), &[]), args);
+    }
+}
@@ -0,0 +1,264 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Event timeline.
+//
+// A global ring buffer of things that happened (process start/stop/crash,
+// payouts, shares, node switches, updates, warnings) for [Status/Events].
+// Events can come from any thread, so this is a global like [human.rs]
+// instead of yet another [Arc<Mutex>] passed around.
+//
+// If enabled, events are also appended to [events.log] in the Gupax
+// data folder, 1 line per event, and read back on startup:
+//
+//     "2024-05-01T03:12:45Z | PAYOUT | 0.000411111111 XMR in block 3100000"
+
+use crate::{constants::*, macros::*};
+use chrono::{DateTime, Utc};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Max events kept in memory, and read back from disk.
+pub const MAX_EVENTS: usize = 1000;
+// [events.log] gets trimmed back to [MAX_EVENTS] lines past this size.
+const MAX_LOG_BYTES: u64 = 1_000_000;
+
+static EVENTS: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+static LOG: Mutex<Option<PathBuf>> = Mutex::new(None);
+static LOADED: AtomicBool = AtomicBool::new(false);
+
+//---------------------------------------------------------------------------------------------------- [Kind]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub enum Kind {
+    Start,
+    Stop,
+    Crash,
+    Payout,
+    Share,
+    Node,
+    Update,
+    Notice,
+}
+
+impl Kind {
+    pub const ALL: [Self; 8] = [
+        Self::Start,
+        Self::Stop,
+        Self::Crash,
+        Self::Payout,
+        Self::Share,
+        Self::Node,
+        Self::Update,
+        Self::Notice,
+    ];
+
+    pub const fn color(self) -> egui::Color32 {
+        match self {
+            Self::Start => GREEN,
+            Self::Stop => GRAY,
+            Self::Crash => RED,
+            Self::Payout => BRIGHT_YELLOW,
+            Self::Share => BLUE,
+            Self::Node => LIGHT_GRAY,
+            Self::Update => BONE,
+            Self::Notice => ORANGE,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Start => "START",
+            Self::Stop => "STOP",
+            Self::Crash => "CRASH",
+            Self::Payout => "PAYOUT",
+            Self::Share => "SHARE",
+            Self::Node => "NODE",
+            Self::Update => "UPDATE",
+            Self::Notice => "NOTICE",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == s)
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Event]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub time: DateTime<Utc>,
+    pub kind: Kind,
+    pub msg: String,
+}
+
+impl Event {
+    fn to_line(&self) -> String {
+        format!(
+            "{} | {} | {}",
+            self.time.format("%Y-%m-%dT%H:%M:%SZ"),
+            self.kind,
+            self.msg.replace('\n', " ")
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut split = line.splitn(3, " | ");
+        let time = DateTime::parse_from_rfc3339(split.next()?).ok()?.to_utc();
+        let kind = Kind::from_str(split.next()?)?;
+        let msg = split.next()?.to_string();
+        Some(Self { time, kind, msg })
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Global
+// Record an event.
+pub fn push(kind: Kind, msg: impl Into<String>) {
+    let event = Event {
+        time: Utc::now(),
+        kind,
+        msg: msg.into(),
+    };
+    info!("Event | [{}] {}", event.kind, event.msg);
+    // Cloned so other threads' events don't wait on this write.
+    let path = lock!(LOG).clone();
+    if let Some(path) = path {
+        if let Err(e) = append(&path, &event) {
+            warn!("Event | Could not write [{}]: {}", path.display(), e);
+        }
+    }
+    let mut events = lock!(EVENTS);
+    if events.len() >= MAX_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(event);
+}
+
+// Events (oldest first) that aren't [hidden].
+pub fn recent(hidden: &[Kind]) -> Vec<Event> {
+    lock!(EVENTS)
+        .iter()
+        .filter(|e| !hidden.contains(&e.kind))
+        .cloned()
+        .collect()
+}
+
+pub fn clear() {
+    lock!(EVENTS).clear();
+    let path = lock!(LOG).clone();
+    if let Some(path) = path {
+        if let Err(e) = std::fs::write(&path, "") {
+            warn!("Event | Could not clear [{}]: {}", path.display(), e);
+        }
+    }
+}
+
+// Turn the disk log on/off. The first time it's turned on,
+// the events already on disk are read back into the timeline.
+pub fn set_log(path: Option<&Path>) {
+    {
+        let mut log = lock!(LOG);
+        if log.as_deref() == path {
+            return;
+        }
+        *log = path.map(Path::to_path_buf);
+    }
+    let Some(path) = path else {
+        return;
+    };
+    if LOADED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let old = match std::fs::read_to_string(path) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            warn!("Event | Could not read [{}]: {}", path.display(), e);
+            return;
+        }
+    };
+    info!(
+        "Event | Read [{}] events from [{}]",
+        old.len(),
+        path.display()
+    );
+    // Trim the file back down if it grew too big.
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+        let text: String = old.iter().map(|e| e.to_line() + "\n").collect();
+        if let Err(e) = std::fs::write(path, text) {
+            warn!("Event | Could not trim [{}]: {}", path.display(), e);
+        }
+    }
+    let mut events = lock!(EVENTS);
+    let new = std::mem::take(&mut *events);
+    *events = old.into_iter().chain(new).collect();
+    while events.len() > MAX_EVENTS {
+        events.pop_front();
+    }
+}
+
+// The last [MAX_EVENTS] valid lines of a log.
+fn parse(log: &str) -> Vec<Event> {
+    let mut events: Vec<Event> = log.lines().filter_map(Event::from_line).collect();
+    let skip = events.len().saturating_sub(MAX_EVENTS);
+    events.drain(..skip);
+    events
+}
+
+// The whole line in 1 buffer, so lines from different threads don't interleave.
+fn append(path: &Path, event: &Event) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(format!("{}\n", event.to_line()).as_bytes())
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn event_line() {
+        use super::{parse, Event, Kind};
+        let line = "2024-05-01T03:12:45Z | PAYOUT | 0.000411111111 XMR in block 3100000";
+        let event = Event::from_line(line).unwrap();
+        assert_eq!(event.kind, Kind::Payout);
+        assert_eq!(event.msg, "0.000411111111 XMR in block 3100000");
+        assert_eq!(event.to_line(), line);
+        // Messages with the separator in them still round-trip.
+        let line = "2024-05-01T03:12:45Z | NOTICE | a | b";
+        assert_eq!(Event::from_line(line).unwrap().msg, "a | b");
+        // Junk is skipped.
+        assert_eq!(Event::from_line("2024-05-01 | PAYOUT | x"), None);
+        assert_eq!(Event::from_line("2024-05-01T03:12:45Z | ??? | x"), None);
+        let log = format!("{line}\ngarbage\n\n{line}\n");
+        assert_eq!(parse(&log).len(), 2);
+        let log = format!("{line}\n").repeat(super::MAX_EVENTS + 5);
+        assert_eq!(parse(&log).len(), super::MAX_EVENTS);
+    }
+}
@@ -0,0 +1,272 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Graphics backend diagnostics and workarounds.
+//
+// Some Linux setups show a blank/black window under Wayland, and some GPUs
+// only work with one of eframe's renderers. Both can be worked around:
+// X11 is forced by relaunching Gupax without [WAYLAND_DISPLAY] so winit
+// falls back to X11/XWayland, the renderer is picked in [NativeOptions].
+// The settings are saved in [state.toml], the same can be done for a single
+// run with the [GUPAX_X11] and [GUPAX_RENDERER] environment variables.
+
+use log::*;
+use serde::{Deserialize, Serialize};
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const X11_ENV: &str = "GUPAX_X11";
+pub const RENDERER_ENV: &str = "GUPAX_RENDERER";
+// Set on the relaunched process so it never relaunches itself again.
+const RELAUNCHED_ENV: &str = "GUPAX_GFX_RELAUNCHED";
+
+//---------------------------------------------------------------------------------------------------- Renderer
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Renderer {
+    #[default]
+    Auto,
+    Glow,
+    Wgpu,
+}
+
+impl Renderer {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Glow, Self::Wgpu];
+
+    pub fn from_env_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "glow" | "opengl" | "gl" => Some(Self::Glow),
+            "wgpu" => Some(Self::Wgpu),
+            _ => None,
+        }
+    }
+
+    // [None] leaves eframe's default.
+    pub fn to_eframe(self) -> Option<eframe::Renderer> {
+        match self {
+            Self::Auto => None,
+            Self::Glow => Some(eframe::Renderer::Glow),
+            Self::Wgpu => Some(eframe::Renderer::Wgpu),
+        }
+    }
+}
+
+impl std::fmt::Display for Renderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "Auto"),
+            Self::Glow => write!(f, "OpenGL (glow)"),
+            Self::Wgpu => write!(f, "wgpu"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Settings
+fn env_bool(value: Option<&str>) -> Option<bool> {
+    match value?.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+// The environment variables take priority over the saved settings.
+pub fn force_x11(setting: bool) -> bool {
+    env_bool(std::env::var(X11_ENV).ok().as_deref()).unwrap_or(setting)
+}
+
+pub fn renderer(setting: Renderer) -> Renderer {
+    match std::env::var(RENDERER_ENV) {
+        Ok(s) => Renderer::from_env_str(&s).unwrap_or_else(|| {
+            warn!(
+                "Gfx | Unknown [{}={}], using [{}]",
+                RENDERER_ENV, s, setting
+            );
+            setting
+        }),
+        Err(_) => setting,
+    }
+}
+
+// Re-executes Gupax with the Wayland variables removed, so winit uses X11
+// (or XWayland). Only returns if nothing needs to be done or [exec()] failed.
+#[cold]
+#[inline(never)]
+pub fn relaunch_x11(setting: bool) {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::process::CommandExt;
+        if !force_x11(setting)
+            || std::env::var_os("WAYLAND_DISPLAY").is_none()
+            || std::env::var_os(RELAUNCHED_ENV).is_some()
+        {
+            return;
+        }
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => {
+                warn!(
+                    "Gfx | Could not get Gupax's path, staying on Wayland: {}",
+                    e
+                );
+                return;
+            }
+        };
+        info!(
+            "Gfx | Forcing X11, relaunching [{}] without Wayland",
+            exe.display()
+        );
+        let e = std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .env_remove("WAYLAND_DISPLAY")
+            .env_remove("WAYLAND_SOCKET")
+            .env(RELAUNCHED_ENV, "1")
+            .exec();
+        warn!("Gfx | Relaunch ... FAIL, staying on Wayland: {}", e);
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = setting;
+}
+
+//---------------------------------------------------------------------------------------------------- Diagnostics
+// What Gupax actually ended up running with, shown in the [Gupax] tab.
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    pub os: &'static str,
+    pub session: String,
+    pub display: &'static str,
+    pub renderer: String,
+    pub gpu: String,
+    pub driver: String,
+    // The saved settings this instance started with.
+    pub started_x11: bool,
+    pub started_renderer: Renderer,
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            session: "???".to_string(),
+            display: "???",
+            renderer: "???".to_string(),
+            gpu: "???".to_string(),
+            driver: "???".to_string(),
+            started_x11: false,
+            started_renderer: Renderer::Auto,
+        }
+    }
+}
+
+impl Diagnostics {
+    #[cold]
+    #[inline(never)]
+    pub fn new(cc: &eframe::CreationContext<'_>, x11: bool, renderer: Renderer) -> Self {
+        let mut diag = Self {
+            session: std::env::var("XDG_SESSION_TYPE").unwrap_or_else(|_| "???".to_string()),
+            display: display_server(
+                std::env::var_os("WAYLAND_DISPLAY").is_some(),
+                std::env::var_os("DISPLAY").is_some(),
+                std::env::var_os(RELAUNCHED_ENV).is_some(),
+            ),
+            started_x11: x11,
+            started_renderer: renderer,
+            ..Default::default()
+        };
+        if let Some(wgpu) = &cc.wgpu_render_state {
+            let info = wgpu.adapter.get_info();
+            diag.renderer = format!("wgpu ({:?})", info.backend);
+            diag.gpu = format!("{} ({:?})", info.name, info.device_type);
+            diag.driver = format!("{} {}", info.driver, info.driver_info)
+                .trim()
+                .to_string();
+        } else if let Some(gl) = &cc.gl {
+            use eframe::glow::HasContext;
+            diag.renderer = "OpenGL (glow)".to_string();
+            // SAFETY: plain string queries on the context eframe just made current.
+            unsafe {
+                diag.gpu = gl.get_parameter_string(eframe::glow::RENDERER);
+                diag.driver = gl.get_parameter_string(eframe::glow::VERSION);
+            }
+        }
+        info!("Gfx | {}", diag.to_string().replace('\n', " | "));
+        diag
+    }
+}
+
+impl Diagnostics {
+    pub fn restart_needed(&self, x11: bool, renderer: Renderer) -> bool {
+        self.started_x11 != x11 || self.started_renderer != renderer
+    }
+}
+
+impl std::fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "OS: {}\nSession: {}\nDisplay: {}\nRenderer: {}\nGPU: {}\nDriver: {}",
+            self.os, self.session, self.display, self.renderer, self.gpu, self.driver
+        )
+    }
+}
+
+// Which display server winit will pick, it prefers Wayland if both are there.
+fn display_server(wayland: bool, x11: bool, relaunched: bool) -> &'static str {
+    if cfg!(not(target_os = "linux")) {
+        return "Native";
+    }
+    match (wayland, x11) {
+        (true, _) => "Wayland",
+        (false, true) if relaunched => "X11 (forced)",
+        (false, true) => "X11",
+        (false, false) => "???",
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn renderer_from_env() {
+        use super::Renderer;
+        assert_eq!(Renderer::from_env_str("glow"), Some(Renderer::Glow));
+        assert_eq!(Renderer::from_env_str(" OpenGL "), Some(Renderer::Glow));
+        assert_eq!(Renderer::from_env_str("WGPU"), Some(Renderer::Wgpu));
+        assert_eq!(Renderer::from_env_str("auto"), Some(Renderer::Auto));
+        assert_eq!(Renderer::from_env_str("vulkan"), None);
+        assert!(Renderer::Auto.to_eframe().is_none());
+    }
+
+    #[test]
+    fn env_bool() {
+        use super::env_bool;
+        assert_eq!(env_bool(Some("1")), Some(true));
+        assert_eq!(env_bool(Some("Yes")), Some(true));
+        assert_eq!(env_bool(Some("0")), Some(false));
+        assert_eq!(env_bool(Some("maybe")), None);
+        assert_eq!(env_bool(None), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn display_server() {
+        use super::display_server;
+        assert_eq!(display_server(true, true, false), "Wayland");
+        assert_eq!(display_server(false, true, false), "X11");
+        assert_eq!(display_server(false, true, true), "X11 (forced)");
+        assert_eq!(display_server(false, false, false), "???");
+    }
+}
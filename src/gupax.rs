@@ -16,10 +16,16 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::State;
-use crate::{constants::*, macros::*, update::*, ErrorState, Restart, Tab};
+use crate::{
+    constants::*,
+    human::{HashrateUnit, NumberFormat},
+    macros::*,
+    update::*,
+    ErrorState, Restart, Tab,
+};
 use egui::{
-    Button, Checkbox, Label, ProgressBar, RichText, SelectableLabel, Slider, Spinner, TextEdit,
-    Vec2,
+    Button, Checkbox, ComboBox, Label, ProgressBar, RichText, SelectableLabel, Slider, Spinner,
+    TextEdit, Vec2,
 };
 use log::*;
 use serde::{Deserialize, Serialize};
@@ -34,11 +40,19 @@ use std::{
 // The opened file picker is started in a new
 // thread so main() needs to be in sync.
 pub struct FileWindow {
-    thread: bool,        // Is there already a FileWindow thread?
-    picked_p2pool: bool, // Did the user pick a path for p2pool?
-    picked_xmrig: bool,  // Did the user pick a path for xmrig?
-    p2pool_path: String, // The picked p2pool path
-    xmrig_path: String,  // The picked p2pool path
+    pub thread: bool,             // Is there already a FileWindow thread?
+    picked_p2pool: bool,          // Did the user pick a path for p2pool?
+    picked_xmrig: bool,           // Did the user pick a path for xmrig?
+    p2pool_path: String,          // The picked p2pool path
+    xmrig_path: String,           // The picked p2pool path
+    pub picked_proxy: bool,       // Did the user pick a path for xmrig-proxy?
+    pub proxy_path: String,       // The picked xmrig-proxy path
+    pub picked_node_import: bool, // Did the user pick a file to import nodes from?
+    pub picked_pool_import: bool, // Did the user pick a file to import pools from?
+    pub import_path: String,      // The picked node/pool import file
+    pub import_msg: String,       // Result of the last import, shown to the user
+    pub picked_data_dir: bool,    // Did the user pick a new (writable) data directory?
+    pub data_dir: String,         // The picked data directory
 }
 
 impl FileWindow {
@@ -49,6 +63,14 @@ impl FileWindow {
             picked_xmrig: false,
             p2pool_path: String::new(),
             xmrig_path: String::new(),
+            picked_proxy: false,
+            proxy_path: String::new(),
+            picked_node_import: false,
+            picked_pool_import: false,
+            import_path: String::new(),
+            import_msg: String::new(),
+            picked_data_dir: false,
+            data_dir: String::new(),
         })
     }
 }
@@ -57,6 +79,10 @@ impl FileWindow {
 pub enum FileType {
     P2pool,
     Xmrig,
+    Proxy,
+    NodeImport,
+    PoolImport,
+    DataDir,
 }
 
 //---------------------------------------------------------------------------------------------------- Ratio Lock
@@ -77,8 +103,24 @@ impl crate::disk::Gupax {
         state_path: &Path,
         update: &Arc<Mutex<Update>>,
         file_window: &Arc<Mutex<FileWindow>>,
+        hotkeys: &Option<crate::hotkey::Hotkeys>,
+        autostart: &crate::autostart::Autostart,
+        p2pool_probe: &Arc<Mutex<crate::probe::BinaryProbe>>,
+        xmrig_probe: &Arc<Mutex<crate::probe::BinaryProbe>>,
+        discovery: &Arc<Mutex<crate::discover::Discovery>>,
         error_state: &mut ErrorState,
         restart: &Arc<Mutex<Restart>>,
+        gupax_p2pool_api: &Arc<Mutex<crate::disk::GupaxP2poolApi>>,
+        gupax_p2pool_api_path: &Path,
+        payout_backups: &mut crate::PayoutBackups,
+        migrate: &Arc<Mutex<crate::snapshot::Migrate>>,
+        os_data_path: &Path,
+        nodes: &[(String, crate::disk::Node)],
+        pools: &[(String, crate::disk::Pool)],
+        gfx: &crate::gfx::Diagnostics,
+        usage_stats: &Arc<Mutex<crate::telemetry::UsageStats>>,
+        toml_editor: &mut crate::tomledit::TomlEditor,
+        display: Option<&str>,
         width: f32,
         height: f32,
         _frame: &mut eframe::Frame,
@@ -118,6 +160,26 @@ impl crate::disk::Gupax {
                 {
                     Update::spawn_thread(og, self, state_path, update, error_state, restart);
                 }
+                #[cfg(not(feature = "distro"))]
+                ui.horizontal(|ui| {
+                    let width = (width / 3.0) - SPACE;
+                    let height = button / 2.0;
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.update_gupax, "Gupax"),
+                    )
+                    .on_hover_text(GUPAX_UPDATE_GUPAX);
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.update_p2pool, "P2Pool"),
+                    )
+                    .on_hover_text(GUPAX_UPDATE_P2POOL);
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.update_xmrig, "XMRig"),
+                    )
+                    .on_hover_text(GUPAX_UPDATE_XMRIG);
+                });
             });
             ui.vertical(|ui| {
                 ui.set_enabled(updating);
@@ -140,7 +202,7 @@ impl crate::disk::Gupax {
         debug!("Gupax Tab | Rendering bool buttons");
         ui.horizontal(|ui| {
             ui.group(|ui| {
-                let width = (width - SPACE * 12.0) / 6.0;
+                let width = (width - SPACE * 22.0) / 11.0;
                 let height = if self.simple {
                     height / 10.0
                 } else {
@@ -182,6 +244,80 @@ impl crate::disk::Gupax {
                     Checkbox::new(&mut self.save_before_quit, "Save before quit"),
                 )
                 .on_hover_text(GUPAX_SAVE_BEFORE_QUIT);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.window_stats, "Window stats"),
+                )
+                .on_hover_text(GUPAX_WINDOW_STATS);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.colorblind, "Color-blind"),
+                )
+                .on_hover_text(GUPAX_COLORBLIND);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.time_check, "Clock check"),
+                )
+                .on_hover_text(GUPAX_TIME_CHECK);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.console_colors, "Console colors"),
+                )
+                .on_hover_text(GUPAX_CONSOLE_COLORS);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.restart_on_resume, "Restart on resume"),
+                )
+                .on_hover_text(GUPAX_RESTART_ON_RESUME);
+            });
+        });
+
+        debug!("Gupax Tab | Rendering start-on-login");
+        ui.group(|ui| {
+            let height = if self.simple {
+                height / 10.0
+            } else {
+                height / 15.0
+            };
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 5.0) - SPACE;
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.start_on_login, "Start on login"),
+                )
+                .on_hover_text(GUPAX_START_ON_LOGIN);
+                ui.separator();
+                ui.add_enabled_ui(self.start_on_login, |ui| {
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.start_on_login_quiet, "Quiet (--no-startup)"),
+                    )
+                    .on_hover_text(GUPAX_START_ON_LOGIN_QUIET);
+                });
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.event_log, "Event log"),
+                )
+                .on_hover_text(GUPAX_EVENT_LOG);
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.console_history, "Console history"),
+                )
+                .on_hover_text(GUPAX_CONSOLE_HISTORY);
+                if !autostart.error.is_empty() {
+                    ui.separator();
+                    ui.add_sized(
+                        [ui.available_width(), height],
+                        Label::new(RichText::new(format!("Error: {}", autostart.error)).color(RED)),
+                    );
+                }
             });
         });
 
@@ -228,7 +364,11 @@ impl crate::disk::Gupax {
                         [text_edit, height],
                         Label::new(RichText::new("P2Pool Binary Path ✔").color(GREEN)),
                     )
-                    .on_hover_text(P2POOL_PATH_OK);
+                    .on_hover_text(format!(
+                        "{}\nDetected: P2Pool {}",
+                        P2POOL_PATH_OK,
+                        lock!(p2pool_probe).version_text()
+                    ));
                 }
                 ui.spacing_mut().text_edit_width = ui.available_width() - SPACE;
                 ui.set_enabled(!lock!(file_window).thread);
@@ -265,7 +405,11 @@ impl crate::disk::Gupax {
                         [text_edit, height],
                         Label::new(RichText::new(" XMRig Binary Path ✔").color(GREEN)),
                     )
-                    .on_hover_text(XMRIG_PATH_OK);
+                    .on_hover_text(format!(
+                        "{}\nDetected: XMRig {}",
+                        XMRIG_PATH_OK,
+                        lock!(xmrig_probe).version_text()
+                    ));
                 }
                 ui.spacing_mut().text_edit_width = ui.available_width() - SPACE;
                 ui.set_enabled(!lock!(file_window).thread);
@@ -278,6 +422,57 @@ impl crate::disk::Gupax {
                 )
                 .on_hover_text(GUPAX_PATH_XMRIG);
             });
+            ui.separator();
+            let found = lock!(discovery);
+            let mut detect = false;
+            ui.horizontal(|ui| {
+                ui.set_enabled(!found.thread);
+                if ui
+                    .add_sized([text_edit, height], Button::new("Detect"))
+                    .on_hover_text(GUPAX_DETECT)
+                    .clicked()
+                {
+                    detect = true;
+                }
+                let msg = if found.thread {
+                    "Searching...".to_string()
+                } else if !found.done {
+                    String::new()
+                } else {
+                    format!(
+                        "Found [{}] P2Pool and [{}] XMRig binaries, click one to use it",
+                        found.p2pool.len(),
+                        found.xmrig.len()
+                    )
+                };
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(msg).color(LIGHT_GRAY)),
+                );
+            });
+            if found.done && !found.thread {
+                for (name, candidates, path) in [
+                    ("P2Pool", &found.p2pool, &mut self.p2pool_path),
+                    (" XMRig", &found.xmrig, &mut self.xmrig_path),
+                ] {
+                    for candidate in candidates.iter() {
+                        let text = format!("{} {}", name, crate::discover::display(candidate));
+                        if ui
+                            .add_sized(
+                                [ui.available_width(), height],
+                                SelectableLabel::new(*path == candidate.path, text),
+                            )
+                            .clicked()
+                        {
+                            path.clone_from(&candidate.path);
+                        }
+                    }
+                }
+            }
+            drop(found);
+            if detect {
+                crate::discover::Discovery::spawn_thread(discovery);
+            }
         });
         let mut guard = lock!(file_window);
         if guard.picked_p2pool {
@@ -290,6 +485,582 @@ impl crate::disk::Gupax {
         }
         drop(guard);
 
+        debug!("Gupax Tab | Rendering global hotkeys");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(
+                    RichText::new("Global Hotkeys")
+                        .underline()
+                        .color(LIGHT_GRAY),
+                ),
+            )
+            .on_hover_text(GUPAX_HOTKEYS);
+            ui.separator();
+            // [None] means the OS hotkey manager failed to initialize.
+            let Some(hotkeys) = hotkeys else {
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(GUPAX_HOTKEYS_UNSUPPORTED).color(LIGHT_GRAY)),
+                );
+                return;
+            };
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 4.0) - SPACE;
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.global_hotkeys, "Enable"),
+                )
+                .on_hover_text(GUPAX_HOTKEYS);
+                ui.set_enabled(self.global_hotkeys);
+                for (name, hotkey, hover) in [
+                    (
+                        "Pause/Resume XMRig",
+                        &mut self.hotkey_xmrig,
+                        GUPAX_HOTKEY_XMRIG,
+                    ),
+                    (
+                        "Show/Hide Gupax",
+                        &mut self.hotkey_window,
+                        GUPAX_HOTKEY_WINDOW,
+                    ),
+                ] {
+                    let (text, color) = if crate::hotkey::parse(hotkey).is_ok() {
+                        (format!("{} ✔", name), GREEN)
+                    } else {
+                        (format!("{} ❌", name), RED)
+                    };
+                    ui.add_sized(
+                        [width / 1.5, height],
+                        Label::new(RichText::new(text).color(color)),
+                    )
+                    .on_hover_text(hover);
+                    ui.add_sized([width, height], TextEdit::singleline(hotkey))
+                        .on_hover_text(hover);
+                }
+            });
+            if self.global_hotkeys && !hotkeys.error.is_empty() {
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(format!("Error: {}", hotkeys.error)).color(RED)),
+                );
+            }
+        });
+
+        debug!("Gupax Tab | Rendering auto-start delay");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(
+                    RichText::new("Auto-Start Delay")
+                        .underline()
+                        .color(LIGHT_GRAY),
+                ),
+            )
+            .on_hover_text(GUPAX_START_DELAY);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                ui.spacing_mut().slider_width = width * 1.5;
+                let max = crate::delay::MAX_DELAY;
+                ui.add_enabled_ui(self.auto_p2pool, |ui| {
+                    ui.add_sized(
+                        [width * 2.5, height],
+                        Slider::new(&mut self.p2pool_start_delay, 0..=max).text("P2Pool (s)"),
+                    )
+                    .on_hover_text(GUPAX_START_DELAY);
+                });
+                ui.separator();
+                ui.add_enabled_ui(self.auto_xmrig, |ui| {
+                    ui.add_sized(
+                        [width * 2.5, height],
+                        Slider::new(&mut self.xmrig_start_delay, 0..=max).text("XMRig (s)"),
+                    )
+                    .on_hover_text(GUPAX_START_DELAY);
+                });
+                ui.separator();
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.wait_for_network, "Wait for network"),
+                )
+                .on_hover_text(GUPAX_WAIT_FOR_NETWORK);
+            });
+        });
+
+        // Only builds with the [sound] feature can play it.
+        if crate::sound::SUPPORTED {
+            debug!("Gupax Tab | Rendering payout sound");
+            ui.group(|ui| {
+                ui.add_sized(
+                    [ui.available_width(), height / 2.0],
+                    Label::new(RichText::new("Payout Sound").underline().color(LIGHT_GRAY)),
+                )
+                .on_hover_text(GUPAX_PAYOUT_SOUND);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 8.0) - SPACE;
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.payout_sound, "Enable"),
+                    )
+                    .on_hover_text(GUPAX_PAYOUT_SOUND);
+                    ui.separator();
+                    ui.add_enabled_ui(self.payout_sound, |ui| {
+                        ui.spacing_mut().slider_width = width * 1.5;
+                        ui.add_sized(
+                            [width * 2.0, height],
+                            Slider::new(&mut self.payout_volume, 0..=100).text("Volume"),
+                        )
+                        .on_hover_text(GUPAX_PAYOUT_VOLUME);
+                        if ui
+                            .add_sized([width / 1.5, height], Button::new("Test"))
+                            .on_hover_text(GUPAX_PAYOUT_TEST)
+                            .clicked()
+                        {
+                            crate::sound::play(self.payout_volume);
+                        }
+                        ui.separator();
+                        ui.add_sized(
+                            [width, height],
+                            Checkbox::new(&mut self.quiet_hours, "Quiet hours"),
+                        )
+                        .on_hover_text(GUPAX_QUIET_HOURS);
+                        ui.add_enabled_ui(self.quiet_hours, |ui| {
+                            ui.spacing_mut().slider_width = width;
+                            ui.add_sized(
+                                [width * 1.5, height],
+                                Slider::new(&mut self.quiet_start, 0..=23).text("From"),
+                            )
+                            .on_hover_text(GUPAX_QUIET_HOURS);
+                            ui.add_sized(
+                                [width * 1.5, height],
+                                Slider::new(&mut self.quiet_end, 0..=23).text("To"),
+                            )
+                            .on_hover_text(GUPAX_QUIET_HOURS);
+                        });
+                    });
+                });
+            });
+        }
+
+        debug!("Gupax Tab | Rendering payout backups");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(
+                    RichText::new("Payout Backups")
+                        .underline()
+                        .color(LIGHT_GRAY),
+                ),
+            )
+            .on_hover_text(GUPAX_PAYOUT_BACKUPS);
+            ui.separator();
+            if payout_backups.list.is_empty() {
+                payout_backups.refresh(gupax_p2pool_api_path);
+            }
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                ui.spacing_mut().slider_width = width * 1.5;
+                ui.add_sized(
+                    [width * 2.0, height],
+                    Slider::new(&mut self.payout_backups, 0..=100).text("Keep"),
+                )
+                .on_hover_text(GUPAX_PAYOUT_BACKUPS);
+                ui.separator();
+                let selected = payout_backups
+                    .list
+                    .get(payout_backups.selected)
+                    .and_then(|p| p.file_name())
+                    .map_or("No backups".to_string(), |n| {
+                        n.to_string_lossy().to_string()
+                    });
+                ComboBox::from_id_source("payout_backups")
+                    .selected_text(selected)
+                    .width(width * 2.5)
+                    .show_ui(ui, |ui| {
+                        for (i, path) in payout_backups.list.iter().enumerate() {
+                            let name = path.file_name().unwrap_or_default().to_string_lossy();
+                            ui.selectable_value(&mut payout_backups.selected, i, name);
+                        }
+                    });
+                let backup = payout_backups.list.get(payout_backups.selected).cloned();
+                if ui
+                    .add_enabled_ui(backup.is_some(), |ui| {
+                        ui.add_sized([width, height], Button::new("Restore"))
+                            .on_hover_text(GUPAX_PAYOUT_RESTORE)
+                    })
+                    .inner
+                    .clicked()
+                {
+                    if let Some(backup) = backup {
+                        let mut api = lock!(gupax_p2pool_api);
+                        let result =
+                            crate::disk::GupaxP2poolApi::restore(gupax_p2pool_api_path, &backup);
+                        let result = match result {
+                            Ok(()) => api.read_all_files_and_update(),
+                            e => e,
+                        };
+                        payout_backups.msg = match result {
+                            Ok(_) => format!("Restored [{}]", backup.display()),
+                            Err(e) => {
+                                error!("GupaxP2poolApi | Restore ... FAIL: {}", e);
+                                format!("Restore failed: {}", e)
+                            }
+                        };
+                        drop(api);
+                        payout_backups.refresh(gupax_p2pool_api_path);
+                    }
+                }
+                if ui
+                    .add_sized([width, height], Button::new("Backup now"))
+                    .on_hover_text(GUPAX_PAYOUT_BACKUP_NOW)
+                    .clicked()
+                {
+                    let _api = lock!(gupax_p2pool_api);
+                    payout_backups.backup(gupax_p2pool_api_path, self.payout_backups.max(1));
+                }
+            });
+            if !payout_backups.msg.is_empty() {
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(&payout_backups.msg).color(LIGHT_GRAY)),
+                );
+            }
+        });
+
+        debug!("Gupax Tab | Rendering migrate machine");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(
+                    RichText::new("Migrate Machine")
+                        .underline()
+                        .color(LIGHT_GRAY),
+                ),
+            )
+            .on_hover_text(GUPAX_MIGRATE);
+            ui.separator();
+            let (mut export, mut import) = (false, false);
+            let mut migrate_lock = lock!(migrate);
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                ui.add_enabled_ui(!migrate_lock.thread, |ui| {
+                    ui.add_sized(
+                        [width * 2.0, height],
+                        Checkbox::new(&mut migrate_lock.binaries, "Include binaries"),
+                    )
+                    .on_hover_text(GUPAX_MIGRATE_BINARIES);
+                    ui.separator();
+                    if ui
+                        .add_sized([width, height], Button::new("Export"))
+                        .on_hover_text(GUPAX_MIGRATE_EXPORT)
+                        .clicked()
+                    {
+                        export = true;
+                    }
+                    if ui
+                        .add_sized([width, height], Button::new("Import"))
+                        .on_hover_text(GUPAX_MIGRATE_IMPORT)
+                        .clicked()
+                    {
+                        import = true;
+                    }
+                });
+                if migrate_lock.thread {
+                    ui.add_sized([width, height], Spinner::new().size(height));
+                }
+            });
+            if !migrate_lock.msg.is_empty() {
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(&migrate_lock.msg).color(LIGHT_GRAY)),
+                );
+            }
+            let mut apply = None;
+            if let Some(import) = &migrate_lock.preview {
+                for line in &import.adapted {
+                    ui.label(RichText::new(line).color(LIGHT_GRAY));
+                }
+                for line in &import.warnings {
+                    ui.label(RichText::new(line).color(YELLOW));
+                }
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 8.0) - SPACE;
+                    if ui
+                        .add_sized([width, height], Button::new("Apply"))
+                        .on_hover_text(GUPAX_MIGRATE_APPLY)
+                        .clicked()
+                    {
+                        apply = Some(true);
+                    }
+                    if ui
+                        .add_sized([width, height], Button::new("Cancel"))
+                        .on_hover_text(GUPAX_MIGRATE_CANCEL)
+                        .clicked()
+                    {
+                        apply = Some(false);
+                    }
+                });
+            }
+            match apply {
+                Some(true) => migrate_lock.pending = migrate_lock.preview.take(),
+                Some(false) => {
+                    migrate_lock.preview = None;
+                    migrate_lock.msg.clear();
+                }
+                None => (),
+            }
+            drop(migrate_lock);
+            if export {
+                crate::snapshot::spawn_export(
+                    migrate,
+                    crate::snapshot::Machine::this(os_data_path),
+                    lock!(og).clone(),
+                    nodes.to_vec(),
+                    pools.to_vec(),
+                    gupax_p2pool_api_path.to_path_buf(),
+                );
+            }
+            if import {
+                crate::snapshot::spawn_import(
+                    migrate,
+                    crate::snapshot::Machine::this(os_data_path),
+                );
+            }
+        });
+
+        // Console buffer
+        debug!("Gupax Tab | Rendering console buffer cap");
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                ui.add_sized(
+                    [width * 2.0, height],
+                    Label::new(RichText::new("Console buffer").color(LIGHT_GRAY)),
+                )
+                .on_hover_text(GUPAX_CONSOLE_CAP);
+                ui.separator();
+                ui.spacing_mut().slider_width = width * 4.0;
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Slider::new(&mut self.console_cap, 100..=5000).text("KB"),
+                )
+                .on_hover_text(GUPAX_CONSOLE_CAP);
+            });
+        });
+
+        // IPv4/IPv6 + DNS-over-HTTPS
+        debug!("Gupax Tab | Rendering network settings");
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                ui.add_sized(
+                    [width * 2.0, height],
+                    Label::new(RichText::new("Network").color(LIGHT_GRAY)),
+                )
+                .on_hover_text(GUPAX_IP_PREFERENCE);
+                ui.separator();
+                ComboBox::from_id_source("ip_preference")
+                    .selected_text(self.ip_preference.to_string())
+                    .width(width * 2.0)
+                    .show_ui(ui, |ui| {
+                        for ip in crate::net::IpPreference::ALL {
+                            ui.selectable_value(&mut self.ip_preference, ip, ip.to_string());
+                        }
+                    })
+                    .response
+                    .on_hover_text(GUPAX_IP_PREFERENCE);
+                ui.separator();
+                let valid =
+                    self.dns_over_https.is_empty() || self.dns_over_https.starts_with("https://");
+                let color = if valid { LIGHT_GRAY } else { RED };
+                ui.add_sized(
+                    [width, height],
+                    Label::new(RichText::new("DoH").color(color)),
+                )
+                .on_hover_text(GUPAX_DOH);
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    TextEdit::hint_text(
+                        TextEdit::singleline(&mut self.dns_over_https),
+                        crate::net::DEFAULT_DOH,
+                    ),
+                )
+                .on_hover_text(GUPAX_DOH);
+            });
+        });
+
+        // Event hooks
+        debug!("Gupax Tab | Rendering event hooks");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(RichText::new("Event hooks").underline().color(LIGHT_GRAY)),
+            )
+            .on_hover_text(GUPAX_HOOKS);
+            ui.separator();
+            for kind in crate::hook::KINDS {
+                let Some(path) = self.hook_mut(kind) else {
+                    continue;
+                };
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 8.0) - SPACE;
+                    let cleaned = crate::disk::clean_path(path);
+                    let color = if cleaned.is_empty() {
+                        GRAY
+                    } else if Path::new(&cleaned).is_file() {
+                        GREEN
+                    } else {
+                        RED
+                    };
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new(kind.to_string()).color(color)),
+                    )
+                    .on_hover_text(GUPAX_HOOKS);
+                    ui.separator();
+                    ui.add_sized(
+                        [width * 6.0, height],
+                        TextEdit::hint_text(TextEdit::singleline(path), "/path/to/script"),
+                    )
+                    .on_hover_text(GUPAX_HOOKS);
+                    if ui
+                        .add_enabled_ui(!cleaned.is_empty(), |ui| {
+                            ui.add_sized([ui.available_width(), height], Button::new("Test"))
+                        })
+                        .inner
+                        .on_hover_text(GUPAX_HOOK_TEST)
+                        .clicked()
+                    {
+                        crate::hook::fire(kind, "Test from Gupax", &[("test", "true".to_string())]);
+                    }
+                });
+            }
+        });
+
+        // XMRig launcher
+        #[cfg(target_family = "unix")]
+        {
+            debug!("Gupax Tab | Rendering XMRig launcher");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 8.0) - SPACE;
+                    ui.add_sized(
+                        [width * 2.0, height],
+                        Label::new(RichText::new("XMRig launcher").color(LIGHT_GRAY)),
+                    )
+                    .on_hover_text(GUPAX_ESCALATION);
+                    ui.separator();
+                    ComboBox::from_id_source("escalation")
+                        .selected_text(self.escalation.to_string())
+                        .width(width * 2.0)
+                        .show_ui(ui, |ui| {
+                            for escalation in crate::sudo::Escalation::ALL {
+                                ui.selectable_value(
+                                    &mut self.escalation,
+                                    escalation,
+                                    escalation.to_string(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(GUPAX_ESCALATION);
+                });
+            });
+        }
+
+        // Graphics
+        debug!("Gupax Tab | Rendering graphics diagnostics");
+        ui.group(|ui| {
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(RichText::new("Graphics").underline().color(LIGHT_GRAY)),
+            )
+            .on_hover_text(GUPAX_GFX);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 8.0) - SPACE;
+                #[cfg(target_os = "linux")]
+                {
+                    ui.add_sized(
+                        [width * 1.5, height],
+                        Checkbox::new(&mut self.gfx_x11, "Force X11"),
+                    )
+                    .on_hover_text(GUPAX_GFX_X11);
+                    ui.separator();
+                }
+                ComboBox::from_id_source("gfx_renderer")
+                    .selected_text(self.gfx_renderer.to_string())
+                    .width(width * 2.0)
+                    .show_ui(ui, |ui| {
+                        for renderer in crate::gfx::Renderer::ALL {
+                            ui.selectable_value(
+                                &mut self.gfx_renderer,
+                                renderer,
+                                renderer.to_string(),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text(GUPAX_GFX_RENDERER);
+                ui.separator();
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(
+                        RichText::new(format!("{} | {} | {}", gfx.display, gfx.renderer, gfx.gpu))
+                            .color(LIGHT_GRAY),
+                    ),
+                )
+                .on_hover_text(gfx.to_string());
+            });
+            if gfx.restart_needed(self.gfx_x11, self.gfx_renderer) {
+                ui.add_sized(
+                    [ui.available_width(), height / 2.0],
+                    Label::new(RichText::new(GUPAX_GFX_RESTART).color(YELLOW)),
+                );
+            }
+        });
+
+        // Usage stats
+        debug!("Gupax Tab | Rendering usage stats");
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let width = (ui.available_width() / 4.0) - SPACE;
+                ui.add_sized(
+                    [width, height],
+                    Checkbox::new(&mut self.usage_stats, "Usage stats"),
+                )
+                .on_hover_text(GUPAX_USAGE_STATS);
+                ui.separator();
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Label::new(RichText::new(&lock!(usage_stats).msg).color(LIGHT_GRAY)),
+                );
+            });
+            egui::CollapsingHeader::new("Preview")
+                .id_source("gupax_usage_stats_preview")
+                .show(ui, |ui| {
+                    let cpu = lock!(usage_stats).cpu;
+                    let payload = crate::telemetry::Payload::new(&lock!(og), cpu).to_json();
+                    ui.label(RichText::new(GUPAX_USAGE_STATS_PREVIEW).color(LIGHT_GRAY));
+                    ui.add_sized(
+                        [ui.available_width(), height],
+                        TextEdit::multiline(&mut payload.as_str()).font(egui::TextStyle::Monospace),
+                    );
+                });
+        });
+
+        // Raw [state.toml]
+        debug!("Gupax Tab | Rendering state.toml editor");
+        ui.group(|ui| {
+            egui::CollapsingHeader::new("Edit state.toml")
+                .id_source("gupax_toml_editor_header")
+                .show(ui, |ui| {
+                    ui.label(RichText::new(GUPAX_TOML).color(LIGHT_GRAY));
+                    toml_editor.show(&lock!(og), height, ui);
+                });
+        });
+
         let height = ui.available_height() / 6.0;
 
         // Saved [Tab]
@@ -360,6 +1131,60 @@ impl crate::disk::Gupax {
             })
         });
 
+        // Number/Time format
+        debug!("Gupax Tab | Rendering [Number/Time Format] selector");
+        ui.group(|ui| {
+            let width = (width / 5.0) - (SPACE * 1.93);
+            ui.add_sized(
+                [ui.available_width(), height / 2.0],
+                Label::new(
+                    RichText::new("Number/Time Format")
+                        .underline()
+                        .color(LIGHT_GRAY),
+                ),
+            )
+            .on_hover_text(GUPAX_NUMBER_FORMAT);
+            ui.separator();
+            ui.horizontal(|ui| {
+                for format in NumberFormat::ALL {
+                    if ui
+                        .add_sized(
+                            [width, height],
+                            SelectableLabel::new(self.number_format == format, format.example()),
+                        )
+                        .on_hover_text(GUPAX_NUMBER_FORMAT)
+                        .clicked()
+                    {
+                        self.number_format = format;
+                    }
+                    ui.separator();
+                }
+                ui.add_sized(
+                    [ui.available_width(), height],
+                    Checkbox::new(&mut self.short_time, "Short time"),
+                )
+                .on_hover_text(GUPAX_SHORT_TIME);
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                for unit in HashrateUnit::ALL {
+                    if ui
+                        .add_sized(
+                            [width, height],
+                            SelectableLabel::new(self.hashrate_unit == unit, unit.name()),
+                        )
+                        .on_hover_text(GUPAX_HASHRATE_UNIT)
+                        .clicked()
+                    {
+                        self.hashrate_unit = unit;
+                    }
+                    if unit != HashrateUnit::Raw {
+                        ui.separator();
+                    }
+                }
+            });
+        });
+
         // Gupax App resolution sliders
         debug!("Gupax Tab | Rendering resolution sliders");
         ui.group(|ui| {
@@ -432,12 +1257,22 @@ impl crate::disk::Gupax {
                         [width, height],
                         Label::new(format!("Scaling [{APP_MIN_SCALE}..{APP_MAX_SCALE}]:")),
                     );
-                    ui.add_sized(
-                        [width, height],
-                        Slider::new(&mut self.selected_scale, APP_MIN_SCALE..=APP_MAX_SCALE)
-                            .step_by(0.1),
-                    )
-                    .on_hover_text(GUPAX_SCALE);
+                    // Each display keeps its own scale.
+                    let mut scale = self.scale(display);
+                    let hover = match display {
+                        Some(display) => format!("{GUPAX_SCALE}\n\nCurrent display: {display}"),
+                        None => GUPAX_SCALE.to_string(),
+                    };
+                    if ui
+                        .add_sized(
+                            [width, height],
+                            Slider::new(&mut scale, APP_MIN_SCALE..=APP_MAX_SCALE).step_by(0.1),
+                        )
+                        .on_hover_text(hover)
+                        .changed()
+                    {
+                        self.set_scale(display, scale);
+                    }
                 });
             });
             ui.style_mut().override_text_style = Some(egui::TextStyle::Button);
@@ -502,19 +1337,31 @@ impl crate::disk::Gupax {
 
     #[cold]
     #[inline(never)]
-    fn spawn_file_window_thread(file_window: &Arc<Mutex<FileWindow>>, file_type: FileType) {
+    pub fn spawn_file_window_thread(file_window: &Arc<Mutex<FileWindow>>, file_type: FileType) {
         use FileType::*;
-        let name = match file_type {
-            P2pool => "P2Pool",
-            Xmrig => "XMRig",
+        let (name, title) = match file_type {
+            P2pool => ("P2Pool", "Select P2Pool Binary for Gupax"),
+            Xmrig => ("XMRig", "Select XMRig Binary for Gupax"),
+            Proxy => ("XMRig-Proxy", "Select XMRig-Proxy Binary for Gupax"),
+            NodeImport => ("Node Import", "Select a CSV file to import nodes from"),
+            PoolImport => (
+                "Pool Import",
+                "Select a CSV file or XMRig config to import pools from",
+            ),
+            DataDir => (
+                "Data Directory",
+                "Select a writable folder for Gupax's data",
+            ),
         };
         let file_window = file_window.clone();
         lock!(file_window).thread = true;
         thread::spawn(move || {
-            match rfd::FileDialog::new()
-                .set_title(format!("Select {} Binary for Gupax", name))
-                .pick_file()
-            {
+            let dialog = rfd::FileDialog::new().set_title(title);
+            let picked = match file_type {
+                DataDir => dialog.pick_folder(),
+                _ => dialog.pick_file(),
+            };
+            match picked {
                 Some(path) => {
                     info!("Gupax | Path selected for {} ... {}", name, path.display());
                     match file_type {
@@ -526,6 +1373,22 @@ impl crate::disk::Gupax {
                             lock!(file_window).xmrig_path = path.display().to_string();
                             lock!(file_window).picked_xmrig = true;
                         }
+                        Proxy => {
+                            lock!(file_window).proxy_path = path.display().to_string();
+                            lock!(file_window).picked_proxy = true;
+                        }
+                        NodeImport => {
+                            lock!(file_window).import_path = path.display().to_string();
+                            lock!(file_window).picked_node_import = true;
+                        }
+                        PoolImport => {
+                            lock!(file_window).import_path = path.display().to_string();
+                            lock!(file_window).picked_pool_import = true;
+                        }
+                        DataDir => {
+                            lock!(file_window).data_dir = path.display().to_string();
+                            lock!(file_window).picked_data_dir = true;
+                        }
                     };
                 }
                 None => info!("Gupax | No path selected for {}", name),
@@ -0,0 +1,243 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The in-app help overlay.
+//
+// Each tab has a page of Markdown embedded at compile time from [src/help/],
+// plus a troubleshooting page for common errors and a list of the [ErrorState]
+// codes (see [code.rs]). Only the small subset of Markdown those pages use is
+// rendered:
+//
+//   # Heading
+//   ## Subheading
+//   - Bullet
+//   ```
+//   Code block
+//   ```
+//
+// Everything else is a plain wrapped paragraph.
+
+use crate::{constants::*, Tab};
+use egui::{Label, RichText, ScrollArea, SelectableLabel, TextStyle};
+use log::*;
+
+//---------------------------------------------------------------------------------------------------- Page
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Page {
+    Status,
+    Gupax,
+    P2pool,
+    Xmrig,
+    Proxy,
+    #[default]
+    Troubleshooting,
+    Codes,
+}
+
+impl Page {
+    pub const ALL: [Self; 7] = [
+        Self::Status,
+        Self::Gupax,
+        Self::P2pool,
+        Self::Xmrig,
+        Self::Proxy,
+        Self::Troubleshooting,
+        Self::Codes,
+    ];
+
+    // The page the [?] button on [tab] opens.
+    pub const fn from_tab(tab: Tab) -> Self {
+        match tab {
+            Tab::About => Self::Troubleshooting,
+            Tab::Status => Self::Status,
+            Tab::Gupax => Self::Gupax,
+            Tab::P2pool => Self::P2pool,
+            Tab::Xmrig => Self::Xmrig,
+            Tab::Proxy => Self::Proxy,
+        }
+    }
+
+    pub const fn markdown(&self) -> &'static str {
+        match self {
+            Self::Status => include_str!("help/status.md"),
+            Self::Gupax => include_str!("help/gupax.md"),
+            Self::P2pool => include_str!("help/p2pool.md"),
+            Self::Xmrig => include_str!("help/xmrig.md"),
+            Self::Proxy => include_str!("help/proxy.md"),
+            Self::Troubleshooting => include_str!("help/troubleshooting.md"),
+            Self::Codes => include_str!("help/codes.md"),
+        }
+    }
+}
+
+impl std::fmt::Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Status => write!(f, "Status"),
+            Self::Gupax => write!(f, "Gupax"),
+            Self::P2pool => write!(f, "P2Pool"),
+            Self::Xmrig => write!(f, "XMRig"),
+            Self::Proxy => write!(f, "XMRig-Proxy"),
+            Self::Troubleshooting => write!(f, "Troubleshooting"),
+            Self::Codes => write!(f, "Error codes"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Block
+#[derive(Debug, Eq, PartialEq)]
+enum Block<'a> {
+    Heading(&'a str),
+    Subheading(&'a str),
+    Bullet(&'a str),
+    Code(&'a str), // One line of a code block
+    Text(&'a str),
+    Space,
+}
+
+// Split a page into the blocks it's rendered as.
+fn blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            code = !code;
+            continue;
+        }
+        let block = if code {
+            Block::Code(line)
+        } else if let Some(s) = line.strip_prefix("## ") {
+            Block::Subheading(s.trim())
+        } else if let Some(s) = line.strip_prefix("# ") {
+            Block::Heading(s.trim())
+        } else if let Some(s) = line.trim_start().strip_prefix("- ") {
+            Block::Bullet(s.trim())
+        } else if line.trim().is_empty() {
+            Block::Space
+        } else {
+            Block::Text(line.trim())
+        };
+        blocks.push(block);
+    }
+    blocks
+}
+
+//---------------------------------------------------------------------------------------------------- Help
+#[derive(Clone, Debug, Default)]
+pub struct Help {
+    pub open: bool,
+    pub page: Page,
+}
+
+impl Help {
+    // Open the help on [tab]'s page, or close it if it's already showing.
+    pub fn toggle(&mut self, tab: Tab) {
+        let page = Page::from_tab(tab);
+        if self.open && self.page == page {
+            self.open = false;
+        } else {
+            self.open = true;
+            self.page = page;
+        }
+    }
+
+    // Open the help on [page], used by in-app hints that point to a guide.
+    pub fn open_at(&mut self, page: Page) {
+        self.open = true;
+        self.page = page;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, width: f32, height: f32) {
+        if !self.open {
+            return;
+        }
+        debug!("Help | Rendering [{}] page", self.page);
+        let page = &mut self.page;
+        egui::Window::new("Help")
+            .open(&mut self.open)
+            .collapsible(false)
+            .default_size([width * 0.8, height * 0.8])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for p in Page::ALL {
+                        if ui
+                            .add(SelectableLabel::new(*page == p, p.to_string()))
+                            .clicked()
+                        {
+                            *page = p;
+                        }
+                    }
+                });
+                ui.separator();
+                ScrollArea::vertical().show(ui, |ui| {
+                    for block in blocks(page.markdown()) {
+                        match block {
+                            Block::Heading(s) => {
+                                ui.heading(s);
+                            }
+                            Block::Subheading(s) => {
+                                ui.label(RichText::new(s).underline().color(LIGHT_GRAY));
+                            }
+                            Block::Bullet(s) => {
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.label("•");
+                                    ui.add(Label::new(s).wrap(true));
+                                });
+                            }
+                            Block::Code(s) => {
+                                ui.label(RichText::new(s).text_style(TextStyle::Monospace));
+                            }
+                            Block::Text(s) => {
+                                ui.add(Label::new(s).wrap(true));
+                            }
+                            Block::Space => ui.add_space(SPACE),
+                        }
+                    }
+                });
+            });
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse_blocks() {
+        use super::{blocks, Block};
+        let md = "# Title\ntext\n\n## Sub\n- bullet\n```\n- not a bullet\n```\n";
+        assert_eq!(
+            blocks(md),
+            [
+                Block::Heading("Title"),
+                Block::Text("text"),
+                Block::Space,
+                Block::Subheading("Sub"),
+                Block::Bullet("bullet"),
+                Block::Code("- not a bullet"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_have_titles() {
+        use super::{blocks, Block, Page};
+        for page in Page::ALL {
+            let blocks = blocks(page.markdown());
+            assert!(matches!(blocks[0], Block::Heading(_)), "{page}");
+        }
+    }
+}
@@ -34,10 +34,19 @@
 // piping their stdout/stderr/stdin, accessing their APIs (HTTP + disk files), etc.
 
 //---------------------------------------------------------------------------------------------------- Import
-use crate::regex::{P2POOL_REGEX, XMRIG_REGEX};
-use crate::{constants::*, human::*, macros::*, xmr::*, GupaxP2poolApi, RemoteNode, SudoState};
+mod proxy;
+
+use crate::regex::{ANSI_ESCAPE, P2POOL_REGEX, XMRIG_REGEX};
+use crate::{constants::*, human::*, macros::*, xmr::*, GupaxP2poolApi, SudoState};
+use gupax_process::{
+    HttpJson, Hugepages, JsonFile, ManagedProcess, PrivP2poolLocalApi, PrivP2poolNetworkApi,
+    PrivP2poolPoolApi, PrivXmrigApi, Pty, StatsSource, Watchdog,
+};
+// The generic process state lives in [gupax-process], the rest of Gupax uses it from here.
+pub use gupax_process::{Process, ProcessName, ProcessSignal, ProcessState};
 use log::*;
-use serde::{Deserialize, Serialize};
+use proxy::ProxyProcess;
+pub use proxy::PubProxyApi;
 use std::{
     fmt::Write,
     path::PathBuf,
@@ -50,12 +59,6 @@ use sysinfo::SystemExt;
 use sysinfo::{CpuExt, ProcessExt};
 
 //---------------------------------------------------------------------------------------------------- Constants
-// The max amount of bytes of process output we are willing to
-// hold in memory before it's too much and we need to reset.
-const MAX_GUI_OUTPUT_BYTES: usize = 500_000;
-// Just a little leeway so a reset will go off before the [String] allocates more memory.
-const GUI_OUTPUT_LEEWAY: usize = MAX_GUI_OUTPUT_BYTES - 1000;
-
 // Some constants for generating hashrate/difficulty.
 const MONERO_BLOCK_TIME_IN_SECONDS: u64 = 120;
 const P2POOL_BLOCK_TIME_IN_SECONDS: u64 = 10;
@@ -68,13 +71,20 @@ pub struct Helper {
     pub pub_sys: Arc<Mutex<Sys>>, // The public API for [sysinfo] that the [Status] tab reads from
     pub p2pool: Arc<Mutex<Process>>, // P2Pool process state
     pub xmrig: Arc<Mutex<Process>>, // XMRig process state
+    pub proxy: Arc<Mutex<Process>>, // XMRig-Proxy process state
     pub gui_api_p2pool: Arc<Mutex<PubP2poolApi>>, // P2Pool API state (for GUI thread)
     pub gui_api_xmrig: Arc<Mutex<PubXmrigApi>>, // XMRig API state (for GUI thread)
+    pub gui_api_proxy: Arc<Mutex<PubProxyApi>>, // XMRig-Proxy API state (for GUI thread)
     pub img_p2pool: Arc<Mutex<ImgP2pool>>, // A static "image" of the data P2Pool started with
     pub img_xmrig: Arc<Mutex<ImgXmrig>>, // A static "image" of the data XMRig started with
-    pub_api_p2pool: Arc<Mutex<PubP2poolApi>>, // P2Pool API state (for Helper/P2Pool thread)
-    pub_api_xmrig: Arc<Mutex<PubXmrigApi>>, // XMRig API state (for Helper/XMRig thread)
+    pub xmrig_instances: Arc<Mutex<Vec<ExtraXmrig>>>, // The extra XMRig's started next to the main one
+    pub_api_p2pool: Arc<Mutex<PubP2poolApi>>,         // P2Pool API state (for Helper/P2Pool thread)
+    pub_api_xmrig: Arc<Mutex<PubXmrigApi>>,           // XMRig API state (for Helper/XMRig thread)
+    pub_api_proxy: Arc<Mutex<PubProxyApi>>, // XMRig-Proxy API state (for Helper/XMRig-Proxy thread)
     pub gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>, //
+    pub resumed: Option<Duration>, // Set after a system sleep/resume with how long it slept, taken by the GUI thread
+    pub network_changed: bool, // Set when the default route's local address changes, taken by the GUI thread
+    load_guard: LoadGuard,     // Pauses XMRig on high non-XMRig CPU load, set on XMRig start
 }
 
 // The communication between the data here and the GUI thread goes as follows:
@@ -94,14 +104,23 @@ pub struct Sys {
     pub system_cpu_model: String,
     pub system_memory: String,
     pub system_cpu_usage: String,
+    pub buffers: String, // Memory held by the P2Pool/XMRig output/API buffers
 }
 
+// Total system RAM in bytes, it doesn't change so it's only read once.
+pub static TOTAL_MEMORY: once_cell::sync::Lazy<u64> = once_cell::sync::Lazy::new(|| {
+    let mut sysinfo = sysinfo::System::new();
+    sysinfo.refresh_memory();
+    sysinfo.total_memory()
+});
+
 impl Sys {
     pub fn new() -> Self {
         Self {
             gupax_uptime: "0 seconds".to_string(),
             gupax_cpu_usage: "???%".to_string(),
             gupax_memory_used_mb: "??? megabytes".to_string(),
+            buffers: "???".to_string(),
             system_cpu_usage: "???%".to_string(),
             system_memory: "???GB / ???GB".to_string(),
             system_cpu_model: "???".to_string(),
@@ -114,150 +133,201 @@ impl Default for Sys {
     }
 }
 
-//---------------------------------------------------------------------------------------------------- [Process] Struct
-// This holds all the state of a (child) process.
-// The main GUI thread will use this to display console text, online state, etc.
-#[derive(Debug)]
-pub struct Process {
-    pub name: ProcessName,     // P2Pool or XMRig?
-    pub state: ProcessState,   // The state of the process (alive, dead, etc)
-    pub signal: ProcessSignal, // Did the user click [Start/Stop/Restart]?
-    // STDIN Problem:
-    //     - User can input many many commands in 1 second
-    //     - The process loop only processes every 1 second
-    //     - If there is only 1 [String] holding the user input,
-    //       the user could overwrite their last input before
-    //       the loop even has a chance to process their last command
-    // STDIN Solution:
-    //     - When the user inputs something, push it to a [Vec]
-    //     - In the process loop, loop over every [Vec] element and
-    //       send each one individually to the process stdin
-    //
-    pub input: Vec<String>,
-
-    // The below are the handles to the actual child process.
-    // [Simple] has no STDIN, but [Advanced] does. A PTY (pseudo-terminal) is
-    // required for P2Pool/XMRig to open their STDIN pipe.
-    //	child: Option<Arc<Mutex<Box<dyn portable_pty::Child + Send + std::marker::Sync>>>>, // STDOUT/STDERR is combined automatically thanks to this PTY, nice
-    //	stdin: Option<Box<dyn portable_pty::MasterPty + Send>>, // A handle to the process's MasterPTY/STDIN
-
-    // This is the process's private output [String], used by both [Simple] and [Advanced].
-    // "parse" contains the output that will be parsed, then tossed out. "pub" will be written to
-    // the same as parse, but it will be [swap()]'d by the "helper" thread into the GUIs [String].
-    // The "helper" thread synchronizes this swap so that the data in here is moved there
-    // roughly once a second. GUI thread never touches this.
-    output_parse: Arc<Mutex<String>>,
-    output_pub: Arc<Mutex<String>>,
+//---------------------------------------------------------------------------------------------------- [ManagedProcess]
+// Marker types telling [gupax-process] how P2Pool/XMRig's output changes their state.
+pub struct P2poolProcess;
+pub struct XmrigProcess;
 
-    // Start time of process.
-    start: std::time::Instant,
-}
+impl ManagedProcess for P2poolProcess {
+    const NAME: ProcessName = ProcessName::P2pool;
+    const START_STATE: ProcessState = ProcessState::Syncing;
 
-//---------------------------------------------------------------------------------------------------- [Process] Impl
-impl Process {
-    pub fn new(name: ProcessName, _args: String, _path: PathBuf) -> Self {
-        Self {
-            name,
-            state: ProcessState::Dead,
-            signal: ProcessSignal::None,
-            start: Instant::now(),
-            //			stdin: Option::None,
-            //			child: Option::None,
-            output_parse: arc_mut!(String::with_capacity(500)),
-            output_pub: arc_mut!(String::with_capacity(500)),
-            input: vec![String::new()],
+    // Check for "SYNCHRONIZED" only if we aren't already.
+    fn state_from_output(output: &str, state: ProcessState) -> Option<ProcessState> {
+        if state != ProcessState::Syncing {
+            return None;
         }
-    }
+        // How many times the word was captured.
+        let synchronized_captures = P2POOL_REGEX.synchronized.find_iter(output).count();
 
-    // Borrow a [&str], return an owned split collection
-    #[inline]
-    pub fn parse_args(args: &str) -> Vec<String> {
-        args.split_whitespace().map(|s| s.to_owned()).collect()
-    }
-
-    #[inline]
-    // Convenience functions
-    pub fn is_alive(&self) -> bool {
-        self.state == ProcessState::Alive
-            || self.state == ProcessState::Middle
-            || self.state == ProcessState::Syncing
-            || self.state == ProcessState::NotMining
+        // If P2Pool receives shares before syncing, it will start mining on its own sidechain.
+        // In this instance, we technically are "synced" on block 1 and P2Pool will print "SYNCHRONIZED"
+        // although, that doesn't necessarily mean we're synced on main/mini-chain.
+        //
+        // So, if we find a `next block = 1`, that means we
+        // must look for at least 2 instances of "SYNCHRONIZED",
+        // one for the sidechain, one for main/mini.
+        let synced = if P2POOL_REGEX.next_height_1.is_match(output) {
+            synchronized_captures > 1
+        } else {
+            // if there is no `next block = 1`, fallback to
+            // just finding 1 instance of "SYNCHRONIZED".
+            synchronized_captures > 0
+        };
+        synced.then_some(ProcessState::Alive)
     }
+}
 
-    #[inline]
-    pub fn is_waiting(&self) -> bool {
-        self.state == ProcessState::Middle || self.state == ProcessState::Waiting
-    }
+impl ManagedProcess for XmrigProcess {
+    const NAME: ProcessName = ProcessName::Xmrig;
+    const START_STATE: ProcessState = ProcessState::NotMining;
 
-    #[inline]
-    pub fn is_syncing(&self) -> bool {
-        self.state == ProcessState::Syncing
+    // Check for "new job"/"no active...".
+    fn state_from_output(output: &str, _: ProcessState) -> Option<ProcessState> {
+        if XMRIG_REGEX.new_job.is_match(output) {
+            Some(ProcessState::Alive)
+        } else if XMRIG_REGEX.not_mining.is_match(output) {
+            Some(ProcessState::NotMining)
+        } else {
+            None
+        }
     }
+}
 
-    #[inline]
-    pub fn is_not_mining(&self) -> bool {
-        self.state == ProcessState::NotMining
-    }
+//---------------------------------------------------------------------------------------------------- [Watchdog]
+// What P2Pool does every loop of [gupax_process::watch()].
+struct P2poolWatchdog {
+    gui_api: Arc<Mutex<PubP2poolApi>>,
+    pub_api: Arc<Mutex<PubP2poolApi>>,
+    output_parse: Arc<Mutex<String>>,
+    output_pub: Arc<Mutex<String>>,
+    api_local: JsonFile<PrivP2poolLocalApi>,
+    api_network: JsonFile<PrivP2poolNetworkApi>,
+    api_pool: JsonFile<PrivP2poolPoolApi>,
+    // The built-in miner's threads, 0 if it's off.
+    miner_threads: usize,
+    miner_status: Instant,
 }
 
-//---------------------------------------------------------------------------------------------------- [Process*] Enum
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum ProcessState {
-    Alive,   // Process is online, GREEN!
-    Dead,    // Process is dead, BLACK!
-    Failed,  // Process is dead AND exited with a bad code, RED!
-    Middle,  // Process is in the middle of something ([re]starting/stopping), YELLOW!
-    Waiting, // Process was successfully killed by a restart, and is ready to be started again, YELLOW!
+impl Watchdog for P2poolWatchdog {
+    type Managed = P2poolProcess;
 
-    // Only for P2Pool, ORANGE.
-    Syncing,
+    fn tick(&mut self, process: &Arc<Mutex<Process>>, elapsed: Duration) {
+        lock!(self.gui_api).tick += 1;
 
-    // Only for XMRig, ORANGE.
-    NotMining,
-}
+        // The built-in miner's hashrate only shows up in [status], ask every minute.
+        if self.miner_threads != 0 && self.miner_status.elapsed() >= Duration::from_secs(60) {
+            lock!(process).input.push("status".to_string());
+            self.miner_status = Instant::now();
+        }
 
-impl Default for ProcessState {
-    fn default() -> Self {
-        Self::Dead
+        // Check if logs need resetting
+        debug!("P2Pool Watchdog | Attempting GUI log reset check");
+        Helper::check_reset_gui_output(&mut lock!(self.gui_api).output, ProcessName::P2pool);
+
+        // Always update from output
+        debug!("P2Pool Watchdog | Starting [update_from_output()]");
+        PubP2poolApi::update_from_output(
+            &self.pub_api,
+            &self.output_parse,
+            &self.output_pub,
+            elapsed,
+            process,
+        );
+
+        // Read [local] API
+        debug!("P2Pool Watchdog | Attempting [local] API file read");
+        if let Ok(local_api) = self.api_local.fetch() {
+            // Update the structs.
+            PubP2poolApi::update_from_local(&self.pub_api, local_api);
+        }
+        // If more than 1 minute has passed, read the other API files.
+        if lock!(self.gui_api).tick >= 60 {
+            debug!("P2Pool Watchdog | Attempting [network] & [pool] API file read");
+            if let (Ok(network_api), Ok(pool_api)) =
+                (self.api_network.fetch(), self.api_pool.fetch())
+            {
+                PubP2poolApi::update_from_network_pool(&self.pub_api, network_api, pool_api);
+                lock!(self.gui_api).tick = 0;
+            }
+        }
     }
-}
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum ProcessSignal {
-    None,
-    Start,
-    Stop,
-    Restart,
-}
+    fn input(&mut self, line: &str) {
+        if let Some(threads) = PubP2poolApi::miner_command(line) {
+            self.miner_threads = threads;
+            let mut pub_api = lock!(self.pub_api);
+            pub_api.miner_threads = threads;
+            if threads == 0 {
+                pub_api.miner_hashrate = HumanNumber::unknown();
+            }
+        }
+    }
 
-impl Default for ProcessSignal {
-    fn default() -> Self {
-        Self::None
+    fn stopped(&mut self, uptime: Duration, exit_status: &str, hint: Option<&str>) {
+        let output = &mut lock!(self.gui_api).output;
+        Helper::write_stopped(output, ProcessName::P2pool, uptime, exit_status, hint);
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum ProcessName {
-    P2pool,
-    Xmrig,
+// What XMRig does every loop of [gupax_process::watch()].
+struct XmrigWatchdog {
+    gui_api: Arc<Mutex<PubXmrigApi>>,
+    pub_api: Arc<Mutex<PubXmrigApi>>,
+    output_parse: Arc<Mutex<String>>,
+    output_pub: Arc<Mutex<String>>,
+    // [None] if the HTTP API is off.
+    api: Option<HttpJson<PrivXmrigApi>>,
+    sudo: Arc<Mutex<SudoState>>,
 }
 
-impl std::fmt::Display for ProcessState {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:#?}", self)
-    }
-}
-impl std::fmt::Display for ProcessSignal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:#?}", self)
+impl Watchdog for XmrigWatchdog {
+    type Managed = XmrigProcess;
+
+    fn tick(&mut self, process: &Arc<Mutex<Process>>, elapsed: Duration) {
+        // Check if logs need resetting
+        debug!("XMRig Watchdog | Attempting GUI log reset check");
+        Helper::check_reset_gui_output(&mut lock!(self.gui_api).output, ProcessName::Xmrig);
+
+        // Always update from output
+        debug!("XMRig Watchdog | Starting [update_from_output()]");
+        PubXmrigApi::update_from_output(
+            &self.pub_api,
+            &self.output_pub,
+            &self.output_parse,
+            elapsed,
+            process,
+        );
+
+        // Send an HTTP API request
+        let Some(api) = &mut self.api else {
+            return;
+        };
+        debug!("XMRig Watchdog | Attempting HTTP API request...");
+        match api.fetch() {
+            Ok(priv_api) => {
+                debug!("XMRig Watchdog | HTTP API request OK, attempting [update_from_priv()]");
+                PubXmrigApi::update_from_priv(&self.pub_api, priv_api);
+            }
+            Err(_) => warn!(
+                "XMRig Watchdog | Could not send HTTP API request to: {}",
+                api.uri()
+            ),
+        }
     }
-}
-impl std::fmt::Display for ProcessName {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match *self {
-            ProcessName::P2pool => write!(f, "P2Pool"),
-            ProcessName::Xmrig => write!(f, "XMRig"),
+
+    fn kill(&mut self, pty: &mut Pty, signal: ProcessSignal) -> std::io::Result<()> {
+        // macOS requires [sudo] again to kill [XMRig]
+        if !cfg!(target_os = "macos") {
+            return pty.kill();
         }
+        // If we're at this point, that means the user has
+        // entered their [sudo] pass again, after we wiped it.
+        // So, we should be able to find it in our [Arc<Mutex<SudoState>>].
+        if let Some(pid) = pty.pid() {
+            Helper::sudo_kill(pid, &self.sudo);
+        }
+        // And... wipe it again (only if we're stopping full).
+        // If we're restarting, the next start will wipe it for us.
+        if signal != ProcessSignal::Restart {
+            SudoState::wipe(&self.sudo);
+        }
+        Ok(())
+    }
+
+    fn stopped(&mut self, uptime: Duration, exit_status: &str, hint: Option<&str>) {
+        let output = &mut lock!(self.gui_api).output;
+        Helper::write_stopped(output, ProcessName::Xmrig, uptime, exit_status, hint);
     }
 }
 
@@ -270,10 +340,13 @@ impl Helper {
         pub_sys: Arc<Mutex<Sys>>,
         p2pool: Arc<Mutex<Process>>,
         xmrig: Arc<Mutex<Process>>,
+        proxy: Arc<Mutex<Process>>,
         gui_api_p2pool: Arc<Mutex<PubP2poolApi>>,
         gui_api_xmrig: Arc<Mutex<PubXmrigApi>>,
+        gui_api_proxy: Arc<Mutex<PubProxyApi>>,
         img_p2pool: Arc<Mutex<ImgP2pool>>,
         img_xmrig: Arc<Mutex<ImgXmrig>>,
+        xmrig_instances: Arc<Mutex<Vec<ExtraXmrig>>>,
         gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
     ) -> Self {
         Self {
@@ -282,144 +355,121 @@ impl Helper {
             uptime: HumanTime::into_human(instant.elapsed()),
             pub_api_p2pool: arc_mut!(PubP2poolApi::new()),
             pub_api_xmrig: arc_mut!(PubXmrigApi::new()),
+            pub_api_proxy: arc_mut!(PubProxyApi::new()),
             // These are created when initializing [App], since it needs a handle to it as well
             p2pool,
             xmrig,
+            proxy,
             gui_api_p2pool,
             gui_api_xmrig,
+            gui_api_proxy,
             img_p2pool,
             img_xmrig,
+            xmrig_instances,
             gupax_p2pool_api,
+            resumed: None,
+            network_changed: false,
+            load_guard: LoadGuard::new(0, 0),
         }
     }
 
-    #[cold]
-    #[inline(never)]
-    fn read_pty_xmrig(
-        output_parse: Arc<Mutex<String>>,
-        output_pub: Arc<Mutex<String>>,
-        reader: Box<dyn std::io::Read + Send>,
-    ) {
-        use std::io::BufRead;
-        let mut stdout = std::io::BufReader::new(reader).lines();
-
-        // Run a ANSI escape sequence filter for the first few lines.
-        let mut i = 0;
-        while let Some(Ok(line)) = stdout.next() {
-            let line = strip_ansi_escapes::strip_str(line);
-            if let Err(e) = writeln!(lock!(output_parse), "{}", line) {
-                error!("XMRig PTY Parse | Output error: {}", e);
-            }
-            if let Err(e) = writeln!(lock!(output_pub), "{}", line) {
-                error!("XMRig PTY Pub | Output error: {}", e);
-            }
-            if i > 20 {
-                break;
-            } else {
-                i += 1;
-            }
-        }
-
-        while let Some(Ok(line)) = stdout.next() {
-            //			println!("{}", line); // For debugging.
-            if let Err(e) = writeln!(lock!(output_parse), "{}", line) {
-                error!("XMRig PTY Parse | Output error: {}", e);
-            }
-            if let Err(e) = writeln!(lock!(output_pub), "{}", line) {
-                error!("XMRig PTY Pub | Output error: {}", e);
+    // Every (ANSI stripped) line of P2Pool's output goes through here
+    // in the PTY read thread, the GUI console still gets the colors.
+    fn p2pool_line(gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>, plain: &str) {
+        if P2POOL_REGEX.payout.is_match(plain) {
+            debug!("P2Pool PTY | Found payout, attempting write: {}", plain);
+            let (date, atomic_unit, block) = PayoutOrd::parse_raw_payout_line(plain);
+            // English, hooks parse this (see [hook.rs]).
+            let xmr = atomic_unit.to_human_number_12_point().as_str().to_string();
+            let msg = format!("{} XMR in block {}", xmr, block);
+            crate::hook::fire(
+                crate::event::Kind::Payout,
+                &msg,
+                &[
+                    ("process", "P2Pool".to_string()),
+                    ("xmr", xmr),
+                    ("block", block.to_string()),
+                ],
+            );
+            crate::event::push(crate::event::Kind::Payout, msg);
+            crate::sound::payout();
+            let date = PayoutOrd::live_date_to_utc(&date);
+            let formatted_log_line = GupaxP2poolApi::format_payout(&date, &atomic_unit, &block);
+            GupaxP2poolApi::add_payout(
+                &mut lock!(gupax_p2pool_api),
+                &formatted_log_line,
+                date,
+                atomic_unit,
+                block,
+            );
+            if let Err(e) =
+                GupaxP2poolApi::write_to_all_files(&lock!(gupax_p2pool_api), &formatted_log_line)
+            {
+                error!("P2Pool PTY GupaxP2poolApi | Write error: {}", e);
             }
         }
     }
 
-    #[cold]
-    #[inline(never)]
-    fn read_pty_p2pool(
-        output_parse: Arc<Mutex<String>>,
-        output_pub: Arc<Mutex<String>>,
-        reader: Box<dyn std::io::Read + Send>,
-        gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
+    // Spawning the binary failed (or it vanished right before): mark the process
+    // failed instead of panicking the watchdog, and report it if it looks like
+    // an antivirus quarantined it.
+    fn spawn_failed(
+        name: ProcessName,
+        process: &Arc<Mutex<Process>>,
+        output: &mut String,
+        path: &std::path::Path,
+        error: &anyhow::Error,
     ) {
-        use std::io::BufRead;
-        let mut stdout = std::io::BufReader::new(reader).lines();
-
-        // Run a ANSI escape sequence filter for the first few lines.
-        let mut i = 0;
-        while let Some(Ok(line)) = stdout.next() {
-            let line = strip_ansi_escapes::strip_str(line);
-            if let Err(e) = writeln!(lock!(output_parse), "{}", line) {
-                error!("P2Pool PTY Parse | Output error: {}", e);
-            }
-            if let Err(e) = writeln!(lock!(output_pub), "{}", line) {
-                error!("P2Pool PTY Pub | Output error: {}", e);
-            }
-            if i > 20 {
-                break;
-            } else {
-                i += 1;
-            }
+        error!("{} | Could not start [{}]: {}", name, path.display(), error);
+        let mut lock = lock!(process);
+        lock.state = ProcessState::Failed;
+        lock.exit = format!("Could not start: {}", error);
+        lock.signal = ProcessSignal::None;
+        drop(lock);
+        if let Err(e) = writeln!(
+            output,
+            "{}\n{} could not be started: {}\n{}\n\n\n\n",
+            HORI_CONSOLE, name, error, HORI_CONSOLE
+        ) {
+            error!("{} | GUI spawn error write failed: {}", name, e);
         }
-
-        while let Some(Ok(line)) = stdout.next() {
-            //			println!("{}", line); // For debugging.
-            if P2POOL_REGEX.payout.is_match(&line) {
-                debug!("P2Pool PTY | Found payout, attempting write: {}", line);
-                let (date, atomic_unit, block) = PayoutOrd::parse_raw_payout_line(&line);
-                let formatted_log_line = GupaxP2poolApi::format_payout(&date, &atomic_unit, &block);
-                GupaxP2poolApi::add_payout(
-                    &mut lock!(gupax_p2pool_api),
-                    &formatted_log_line,
-                    date,
-                    atomic_unit,
-                    block,
-                );
-                if let Err(e) = GupaxP2poolApi::write_to_all_files(
-                    &lock!(gupax_p2pool_api),
-                    &formatted_log_line,
-                ) {
-                    error!("P2Pool PTY GupaxP2poolApi | Write error: {}", e);
-                }
-            }
-            if let Err(e) = writeln!(lock!(output_parse), "{}", line) {
-                error!("P2Pool PTY Parse | Output error: {}", e);
-            }
-            if let Err(e) = writeln!(lock!(output_pub), "{}", line) {
-                error!("P2Pool PTY Pub | Output error: {}", e);
-            }
+        if let Some(cause) = crate::quarantine::classify_spawn(path, error) {
+            crate::quarantine::report(crate::quarantine::Suspect::new(name, path, cause));
         }
     }
 
     // Reset output if larger than max bytes.
-    // This will also append a message showing it was reset.
     fn check_reset_gui_output(output: &mut String, name: ProcessName) {
-        let len = output.len();
-        if len > GUI_OUTPUT_LEEWAY {
-            info!(
-                "{} Watchdog | Output is nearing {} bytes, resetting!",
-                name, MAX_GUI_OUTPUT_BYTES
-            );
-            let text = format!("{}\n{} GUI log is exceeding the maximum: {} bytes!\nResetting the logs...\n{}\n\n\n\n", HORI_CONSOLE, name, MAX_GUI_OUTPUT_BYTES, HORI_CONSOLE);
-            output.clear();
-            output.push_str(&text);
-            debug!("{} Watchdog | Resetting GUI output ... OK", name);
-        } else {
-            debug!(
-                "{} Watchdog | GUI output reset not needed! Current byte length ... {}",
-                name, len
-            );
-        }
+        gupax_process::check_reset_gui_output(output, name, HORI_CONSOLE);
     }
 
-    // Read P2Pool/XMRig's API file to a [String].
-    fn path_to_string(
-        path: &std::path::PathBuf,
+    // For [Watchdog::stopped()]: log the uptime & exit status, and write them
+    // directly into the GUI, because sometimes the 900ms event loop can't catch it.
+    fn write_stopped(
+        output: &mut String,
         name: ProcessName,
-    ) -> std::result::Result<String, std::io::Error> {
-        match std::fs::read_to_string(path) {
-            Ok(s) => Ok(s),
-            Err(e) => {
-                warn!("{} API | [{}] read error: {}", name, path.display(), e);
-                Err(e)
-            }
+        uptime: Duration,
+        exit_status: &str,
+        hint: Option<&str>,
+    ) {
+        let uptime = HumanTime::into_human(uptime);
+        info!(
+            "{} Watchdog | Stopped ... Uptime was: [{}], Exit status: [{}]",
+            name, uptime, exit_status
+        );
+        let hint = match hint {
+            Some(hint) => format!("\n{}", hint),
+            None => String::new(),
+        };
+        if let Err(e) = writeln!(
+            output,
+            "{}\n{} stopped | Uptime: [{}] | Exit status: [{}]{}\n{}\n\n\n\n",
+            HORI_CONSOLE, name, uptime, exit_status, hint, HORI_CONSOLE
+        ) {
+            error!(
+                "{} Watchdog | GUI Uptime/Exit status write failed: {}",
+                name, e
+            );
         }
     }
 
@@ -443,24 +493,13 @@ impl Helper {
         path: &std::path::PathBuf,
         backup_hosts: Option<Vec<crate::Node>>,
     ) {
-        info!("P2Pool | Attempting to restart...");
-        lock2!(helper, p2pool).signal = ProcessSignal::Restart;
-        lock2!(helper, p2pool).state = ProcessState::Middle;
-
+        let process = Arc::clone(&lock!(helper).p2pool);
         let helper = Arc::clone(helper);
         let state = state.clone();
         let path = path.clone();
-        // This thread lives to wait, start p2pool then die.
-        thread::spawn(move || {
-            while lock2!(helper, p2pool).is_alive() {
-                warn!("P2Pool | Want to restart but process is still alive, waiting...");
-                sleep!(1000);
-            }
-            // Ok, process is not alive, start the new one!
-            info!("P2Pool | Old process seems dead, starting new one!");
+        gupax_process::restart(&process, move || {
             Self::start_p2pool(&helper, &state, &path, backup_hosts);
         });
-        info!("P2Pool | Restart ... OK");
     }
 
     #[cold]
@@ -476,6 +515,37 @@ impl Helper {
 
         let (args, api_path_local, api_path_network, api_path_pool) =
             Self::build_p2pool_args_and_mutate_img(helper, state, path, backup_hosts);
+        let cwd = Self::workdir_or_default(ProcessName::P2pool, &state.workdir, path);
+        lock2!(helper, img_p2pool).workdir = Self::img_workdir(&state.workdir, &cwd);
+
+        if crate::demo::enabled() {
+            Self::start_demo(helper, ProcessName::P2pool);
+            return;
+        }
+
+        // P2Pool doesn't create the [--data-api] directory itself.
+        if let Some(api_dir) = api_path_local.parent().and_then(|p| p.parent()) {
+            if !api_dir.exists() {
+                match std::fs::create_dir_all(api_dir) {
+                    Ok(()) => info!("P2Pool | Create API dir [{}] ... OK", api_dir.display()),
+                    Err(e) => warn!(
+                        "P2Pool | Create API dir [{}] ... FAIL ... {}",
+                        api_dir.display(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        // P2Pool never rotates its own log, so do it before it opens it.
+        if !state.simple && state.log_max_mb != 0 {
+            let log = cwd.join("p2pool.log");
+            match Self::rotate_log(&log, state.log_max_mb as u64 * 1_000_000) {
+                Ok(true) => info!("P2Pool | Rotate [{}] ... OK", log.display()),
+                Ok(false) => (),
+                Err(e) => warn!("P2Pool | Rotate [{}] ... FAIL ... {}", log.display(), e),
+            }
+        }
 
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!(
@@ -492,13 +562,16 @@ impl Helper {
         let pub_api = Arc::clone(&lock!(helper).pub_api_p2pool);
         let gupax_p2pool_api = Arc::clone(&lock!(helper).gupax_p2pool_api);
         let path = path.clone();
+        let env = state.env.clone();
         thread::spawn(move || {
             Self::spawn_p2pool_watchdog(
                 process,
                 gui_api,
                 pub_api,
                 args,
+                env,
                 path,
+                cwd,
                 api_path_local,
                 api_path_network,
                 api_path_pool,
@@ -507,6 +580,68 @@ impl Helper {
         });
     }
 
+    // Move [log] to [log.old] (replacing the last one) if it's bigger than [max_bytes].
+    // Returns if it was rotated.
+    pub fn rotate_log(log: &std::path::Path, max_bytes: u64) -> std::io::Result<bool> {
+        match std::fs::metadata(log) {
+            Ok(meta) if meta.len() > max_bytes => {
+                let mut old = log.as_os_str().to_owned();
+                old.push(".old");
+                std::fs::rename(log, old)?;
+                Ok(true)
+            }
+            Ok(_) => Ok(false),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // The [--data-api] directory, next to the P2Pool binary by default.
+    // Relative overrides are relative to the binary, same as P2Pool sees them.
+    pub fn p2pool_api_dir(state: &crate::disk::P2pool, path: &std::path::Path) -> PathBuf {
+        let dir = path.parent().unwrap_or(path).to_path_buf();
+        if state.api_dir.trim().is_empty() {
+            dir
+        } else {
+            dir.join(state.api_dir.trim())
+        }
+    }
+
+    // The directory P2Pool/XMRig run in (their cache, logs, etc), the binary's by default.
+    // Relative overrides are relative to the binary, same as [p2pool_api_dir()].
+    pub fn workdir(workdir: &str, path: &std::path::Path) -> PathBuf {
+        let dir = path.parent().unwrap_or(path).to_path_buf();
+        if workdir.trim().is_empty() {
+            dir
+        } else {
+            dir.join(workdir.trim())
+        }
+    }
+
+    // [workdir()], or the binary's directory if it doesn't exist
+    // (spawning in a missing directory fails).
+    fn workdir_or_default(name: ProcessName, workdir: &str, path: &std::path::Path) -> PathBuf {
+        let dir = Self::workdir(workdir, path);
+        if dir.is_dir() {
+            return dir;
+        }
+        warn!(
+            "{} | Working directory [{}] doesn't exist, using the binary's",
+            name,
+            dir.display()
+        );
+        path.parent().unwrap_or(path).to_path_buf()
+    }
+
+    // What the [Status] tab shows, empty if it's the default.
+    fn img_workdir(workdir: &str, cwd: &std::path::Path) -> String {
+        if workdir.trim().is_empty() {
+            String::new()
+        } else {
+            cwd.display().to_string()
+        }
+    }
+
     // Takes in a 95-char Monero address, returns the first and last
     // 6 characters separated with dots like so: [4abcde...abcdef]
     fn head_tail_of_monero_address(address: &str) -> String {
@@ -530,14 +665,13 @@ impl Helper {
         backup_hosts: Option<Vec<crate::Node>>,
     ) -> (Vec<String>, PathBuf, PathBuf, PathBuf) {
         let mut args = Vec::with_capacity(500);
-        let path = path.clone();
-        let mut api_path = path;
-        api_path.pop();
+        let mut api_path = Self::p2pool_api_dir(state, path);
 
         // [Simple]
         if state.simple {
             // Build the p2pool argument
-            let (ip, rpc, zmq) = RemoteNode::get_ip_rpc_zmq(&state.node); // Get: (IP, RPC, ZMQ)
+            let (ip, rpc, zmq) = state.simple_node(); // Get: (IP, RPC, ZMQ)
+            let (ip, rpc, zmq) = (ip.as_str(), rpc.as_str(), zmq.as_str());
             args.push("--wallet".to_string());
             args.push(state.address.clone()); // Wallet address
             args.push("--host".to_string());
@@ -549,9 +683,19 @@ impl Helper {
             args.push("--data-api".to_string());
             args.push(api_path.display().to_string()); // API Path
             args.push("--local-api".to_string()); // Enable API
-            args.push("--no-color".to_string()); // Remove color escape sequences, Gupax terminal can't parse it :(
             args.push("--mini".to_string()); // P2Pool Mini
-            args.push("--light-mode".to_string()); // Assume user is not using P2Pool to mine.
+            if state.light_mode(*TOTAL_MEMORY) {
+                args.push("--light-mode".to_string());
+            } // Light mode, unless there's RAM to spare
+            if state.miner {
+                args.push("--start-mining".to_string());
+                args.push(state.miner_threads.to_string());
+            } // Built-in miner
+            let peers = state.simple_peers.to_string();
+            args.push("--out-peers".to_string());
+            args.push(peers.clone()); // Out Peers
+            args.push("--in-peers".to_string());
+            args.push(peers.clone()); // In Peers
 
             // Push other nodes if `backup_host`.
             if let Some(nodes) = backup_hosts {
@@ -573,8 +717,10 @@ impl Helper {
                 host: ip.to_string(),
                 rpc: rpc.to_string(),
                 zmq: zmq.to_string(),
-                out_peers: "10".to_string(),
-                in_peers: "10".to_string(),
+                out_peers: peers.clone(),
+                in_peers: peers,
+                settings: state.start_settings(),
+                workdir: String::new(),
             };
 
         // [Advanced]
@@ -587,6 +733,7 @@ impl Helper {
                 let lock = lock!(helper);
                 let mut p2pool_image = lock!(lock.img_p2pool);
                 let mut mini = false;
+                p2pool_image.settings = state.start_settings();
                 for arg in state.arguments.split_whitespace() {
                     match last {
                         "--mini" => {
@@ -599,7 +746,7 @@ impl Helper {
                         "--zmq-port" => p2pool_image.zmq = arg.to_string(),
                         "--out-peers" => p2pool_image.out_peers = arg.to_string(),
                         "--in-peers" => p2pool_image.in_peers = arg.to_string(),
-                        "--data-api" => api_path = PathBuf::from(arg),
+                        "--data-api" => api_path = Self::workdir(&state.workdir, path).join(arg),
                         _ => (),
                     }
                     if !mini {
@@ -633,11 +780,17 @@ impl Helper {
                 args.push("--data-api".to_string());
                 args.push(api_path.display().to_string()); // API Path
                 args.push("--local-api".to_string()); // Enable API
-                args.push("--no-color".to_string()); // Remove color escape sequences
-                args.push("--light-mode".to_string()); // Assume user is not using P2Pool to mine.
+                if state.light_mode(*TOTAL_MEMORY) {
+                    args.push("--light-mode".to_string());
+                } // Light mode, unless there's RAM to spare
+                if state.miner {
+                    args.push("--start-mining".to_string());
+                    args.push(state.miner_threads.to_string());
+                } // Built-in miner
                 if state.mini {
                     args.push("--mini".to_string());
                 }; // Mini
+                args.append(&mut state.extra_args()); // [--no-cache], [--no-dns], [--p2p]
 
                 // Push other nodes if `backup_host`.
                 if let Some(nodes) = backup_hosts {
@@ -660,11 +813,16 @@ impl Helper {
                     }
                 }
 
+                let mini = if state.mini {
+                    "P2Pool Mini"
+                } else {
+                    "P2Pool Main"
+                };
                 *lock2!(helper, img_p2pool) = ImgP2pool {
-                    mini: if state.mini {
-                        "P2Pool Mini".to_string()
+                    mini: if state.network.is_mainnet() {
+                        mini.to_string()
                     } else {
-                        "P2Pool Main".to_string()
+                        format!("{} ({})", mini, state.network)
                     },
                     address: Self::head_tail_of_monero_address(&state.address),
                     host: state.selected_ip.to_string(),
@@ -672,6 +830,8 @@ impl Helper {
                     zmq: state.selected_zmq.to_string(),
                     out_peers: state.out_peers.to_string(),
                     in_peers: state.in_peers.to_string(),
+                    settings: state.start_settings(),
+                    workdir: String::new(),
                 };
             }
         }
@@ -687,60 +847,41 @@ impl Helper {
     #[cold]
     #[inline(never)]
     #[expect(clippy::too_many_arguments)]
-    // The P2Pool watchdog. Spawns 1 OS thread for reading a PTY (STDOUT+STDERR), and combines the [Child] with a PTY so STDIN actually works.
+    // The P2Pool watchdog. Spawns P2Pool in a PTY so STDIN actually works, see [gupax_process::watch()].
     fn spawn_p2pool_watchdog(
         process: Arc<Mutex<Process>>,
         gui_api: Arc<Mutex<PubP2poolApi>>,
         pub_api: Arc<Mutex<PubP2poolApi>>,
         args: Vec<String>,
+        env: Vec<crate::disk::EnvVar>,
         path: std::path::PathBuf,
+        cwd: std::path::PathBuf,
         api_path_local: std::path::PathBuf,
         api_path_network: std::path::PathBuf,
         api_path_pool: std::path::PathBuf,
         gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
     ) {
-        // 1a. Create PTY
-        debug!("P2Pool | Creating PTY...");
-        let pty = portable_pty::native_pty_system();
-        let pair = pty
-            .openpty(portable_pty::PtySize {
-                rows: 100,
-                cols: 1000,
-                pixel_width: 0,
-                pixel_height: 0,
-            })
-            .unwrap();
-        // 1b. Create command
+        // 1. Create command
         debug!("P2Pool | Creating command...");
         let mut cmd = portable_pty::CommandBuilder::new(path.as_path());
-        cmd.args(args);
-        cmd.env("NO_COLOR", "true");
-        cmd.cwd(path.as_path().parent().unwrap());
-        // 1c. Create child
+        cmd.args(&args);
+        cmd.cwd(cwd);
+        crate::envvar::apply(&mut cmd, &env);
+        // 2. Create child
         debug!("P2Pool | Creating child...");
-        let child_pty = arc_mut!(pair.slave.spawn_command(cmd).unwrap());
-        drop(pair.slave);
-
-        // 2. Set process state
-        debug!("P2Pool | Setting process state...");
-        let mut lock = lock!(process);
-        lock.state = ProcessState::Syncing;
-        lock.signal = ProcessSignal::None;
-        lock.start = Instant::now();
-        let reader = pair.master.try_clone_reader().unwrap(); // Get STDOUT/STDERR before moving the PTY
-        let mut stdin = pair.master.take_writer().unwrap();
-        drop(lock);
-
-        // 3. Spawn PTY read thread
-        debug!("P2Pool | Spawning PTY read thread...");
-        let output_parse = Arc::clone(&lock!(process).output_parse);
-        let output_pub = Arc::clone(&lock!(process).output_pub);
-        let gupax_p2pool_api = Arc::clone(&gupax_p2pool_api);
-        thread::spawn(move || {
-            Self::read_pty_p2pool(output_parse, output_pub, reader, gupax_p2pool_api);
-        });
-        let output_parse = Arc::clone(&lock!(process).output_parse);
-        let output_pub = Arc::clone(&lock!(process).output_pub);
+        let pty = match Pty::spawn(cmd, &path) {
+            Ok(pty) => pty,
+            Err(e) => {
+                Self::spawn_failed(
+                    ProcessName::P2pool,
+                    &process,
+                    &mut lock!(gui_api).output,
+                    &path,
+                    &e,
+                );
+                return;
+            }
+        };
 
         debug!("P2Pool | Cleaning old [local] API files...");
         // Attempt to remove stale API file
@@ -752,7 +893,6 @@ impl Helper {
             ),
         }
         // Attempt to create a default empty one.
-        use std::io::Write;
         if std::fs::File::create(&api_path_local).is_ok() {
             let text = r#"{"hashrate_15m":0,"hashrate_1h":0,"hashrate_24h":0,"shares_found":0,"average_effort":0.0,"current_effort":0.0,"connections":0}"#;
             match std::fs::write(&api_path_local, text) {
@@ -763,243 +903,41 @@ impl Helper {
                 ),
             }
         }
-        let start = lock!(process).start;
+        // The built-in miner is started with [--start-mining N] and
+        // controlled with [start_mining N]/[stop_mining] afterwards.
+        let miner_threads = args
+            .iter()
+            .position(|arg| arg == "--start-mining")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|threads| threads.parse::<usize>().ok())
+            .unwrap_or(0);
 
         // Reset stats before loop
         *lock!(pub_api) = PubP2poolApi::new();
         *lock!(gui_api) = PubP2poolApi::new();
+        lock!(pub_api).miner_threads = miner_threads;
+        let output_parse = Arc::clone(&lock!(process).output_parse);
+        let output_pub = Arc::clone(&lock!(process).output_pub);
+        let watchdog = P2poolWatchdog {
+            output_parse,
+            output_pub,
+            gui_api,
+            pub_api,
+            api_local: JsonFile::new(ProcessName::P2pool, api_path_local),
+            api_network: JsonFile::new(ProcessName::P2pool, api_path_network),
+            api_pool: JsonFile::new(ProcessName::P2pool, api_path_pool),
+            miner_threads,
+            miner_status: Instant::now(),
+        };
 
-        // 4. Loop as watchdog
-        info!("P2Pool | Entering watchdog mode... woof!");
-        loop {
-            // Set timer
-            let now = Instant::now();
-            debug!("P2Pool Watchdog | ----------- Start of loop -----------");
-            lock!(gui_api).tick += 1;
-
-            // Check if the process is secretly died without us knowing :)
-            if let Ok(Some(code)) = lock!(child_pty).try_wait() {
-                debug!("P2Pool Watchdog | Process secretly died! Getting exit status");
-                let exit_status = match code.success() {
-                    true => {
-                        lock!(process).state = ProcessState::Dead;
-                        "Successful"
-                    }
-                    false => {
-                        lock!(process).state = ProcessState::Failed;
-                        "Failed"
-                    }
-                };
-                let uptime = HumanTime::into_human(start.elapsed());
-                info!(
-                    "P2Pool Watchdog | Stopped ... Uptime was: [{}], Exit status: [{}]",
-                    uptime, exit_status
-                );
-                // This is written directly into the GUI, because sometimes the 900ms event loop can't catch it.
-                if let Err(e) = writeln!(
-                    lock!(gui_api).output,
-                    "{}\nP2Pool stopped | Uptime: [{}] | Exit status: [{}]\n{}\n\n\n\n",
-                    HORI_CONSOLE,
-                    uptime,
-                    exit_status,
-                    HORI_CONSOLE
-                ) {
-                    error!(
-                        "P2Pool Watchdog | GUI Uptime/Exit status write failed: {}",
-                        e
-                    );
-                }
-                lock!(process).signal = ProcessSignal::None;
-                debug!("P2Pool Watchdog | Secret dead process reap OK, breaking");
-                break;
-            }
-
-            // Check SIGNAL
-            if lock!(process).signal == ProcessSignal::Stop {
-                debug!("P2Pool Watchdog | Stop SIGNAL caught");
-                // This actually sends a SIGHUP to p2pool (closes the PTY, hangs up on p2pool)
-                if let Err(e) = lock!(child_pty).kill() {
-                    error!("P2Pool Watchdog | Kill error: {}", e);
-                }
-                // Wait to get the exit status
-                let exit_status = match lock!(child_pty).wait() {
-                    Ok(e) => {
-                        if e.success() {
-                            lock!(process).state = ProcessState::Dead;
-                            "Successful"
-                        } else {
-                            lock!(process).state = ProcessState::Failed;
-                            "Failed"
-                        }
-                    }
-                    _ => {
-                        lock!(process).state = ProcessState::Failed;
-                        "Unknown Error"
-                    }
-                };
-                let uptime = HumanTime::into_human(start.elapsed());
-                info!(
-                    "P2Pool Watchdog | Stopped ... Uptime was: [{}], Exit status: [{}]",
-                    uptime, exit_status
-                );
-                // This is written directly into the GUI API, because sometimes the 900ms event loop can't catch it.
-                if let Err(e) = writeln!(
-                    lock!(gui_api).output,
-                    "{}\nP2Pool stopped | Uptime: [{}] | Exit status: [{}]\n{}\n\n\n\n",
-                    HORI_CONSOLE,
-                    uptime,
-                    exit_status,
-                    HORI_CONSOLE
-                ) {
-                    error!(
-                        "P2Pool Watchdog | GUI Uptime/Exit status write failed: {}",
-                        e
-                    );
-                }
-                lock!(process).signal = ProcessSignal::None;
-                debug!("P2Pool Watchdog | Stop SIGNAL done, breaking");
-                break;
-            // Check RESTART
-            } else if lock!(process).signal == ProcessSignal::Restart {
-                debug!("P2Pool Watchdog | Restart SIGNAL caught");
-                // This actually sends a SIGHUP to p2pool (closes the PTY, hangs up on p2pool)
-                if let Err(e) = lock!(child_pty).kill() {
-                    error!("P2Pool Watchdog | Kill error: {}", e);
-                }
-                // Wait to get the exit status
-                let exit_status = match lock!(child_pty).wait() {
-                    Ok(e) => {
-                        if e.success() {
-                            "Successful"
-                        } else {
-                            "Failed"
-                        }
-                    }
-                    _ => "Unknown Error",
-                };
-                let uptime = HumanTime::into_human(start.elapsed());
-                info!(
-                    "P2Pool Watchdog | Stopped ... Uptime was: [{}], Exit status: [{}]",
-                    uptime, exit_status
-                );
-                // This is written directly into the GUI API, because sometimes the 900ms event loop can't catch it.
-                if let Err(e) = writeln!(
-                    lock!(gui_api).output,
-                    "{}\nP2Pool stopped | Uptime: [{}] | Exit status: [{}]\n{}\n\n\n\n",
-                    HORI_CONSOLE,
-                    uptime,
-                    exit_status,
-                    HORI_CONSOLE
-                ) {
-                    error!(
-                        "P2Pool Watchdog | GUI Uptime/Exit status write failed: {}",
-                        e
-                    );
-                }
-                lock!(process).state = ProcessState::Waiting;
-                debug!("P2Pool Watchdog | Restart SIGNAL done, breaking");
-                break;
-            }
-
-            // Check vector of user input
-            let mut lock = lock!(process);
-            if !lock.input.is_empty() {
-                let input = std::mem::take(&mut lock.input);
-                for line in input {
-                    if line.is_empty() {
-                        continue;
-                    }
-                    debug!(
-                        "P2Pool Watchdog | User input not empty, writing to STDIN: [{}]",
-                        line
-                    );
-                    // Windows terminals (or at least the PTY abstraction I'm using, portable_pty)
-                    // requires a [\r\n] to end a line, whereas Unix is okay with just a [\n].
-                    //
-                    // I have literally read all of [portable_pty]'s source code, dug into Win32 APIs,
-                    // even rewrote some of the actual PTY code in order to understand why STDIN doesn't work
-                    // on Windows. It's because of a fucking missing [\r]. Another reason to hate Windows :D
-                    //
-                    // XMRig did actually work before though, since it reads STDIN directly without needing a newline.
-                    #[cfg(target_os = "windows")]
-                    if let Err(e) = write!(stdin, "{}\r\n", line) {
-                        error!("P2Pool Watchdog | STDIN error: {}", e);
-                    }
-                    #[cfg(target_family = "unix")]
-                    if let Err(e) = writeln!(stdin, "{}", line) {
-                        error!("P2Pool Watchdog | STDIN error: {}", e);
-                    }
-                    // Flush.
-                    if let Err(e) = stdin.flush() {
-                        error!("P2Pool Watchdog | STDIN flush error: {}", e);
-                    }
-                }
-            }
-            drop(lock);
-
-            // Check if logs need resetting
-            debug!("P2Pool Watchdog | Attempting GUI log reset check");
-            let mut lock = lock!(gui_api);
-            Self::check_reset_gui_output(&mut lock.output, ProcessName::P2pool);
-            drop(lock);
-
-            // Always update from output
-            debug!("P2Pool Watchdog | Starting [update_from_output()]");
-            PubP2poolApi::update_from_output(
-                &pub_api,
-                &output_parse,
-                &output_pub,
-                start.elapsed(),
-                &process,
-            );
-
-            // Read [local] API
-            debug!("P2Pool Watchdog | Attempting [local] API file read");
-            if let Ok(string) = Self::path_to_string(&api_path_local, ProcessName::P2pool) {
-                // Deserialize
-                if let Ok(local_api) = PrivP2poolLocalApi::from_str(&string) {
-                    // Update the structs.
-                    PubP2poolApi::update_from_local(&pub_api, local_api);
-                }
-            }
-            // If more than 1 minute has passed, read the other API files.
-            if lock!(gui_api).tick >= 60 {
-                debug!("P2Pool Watchdog | Attempting [network] & [pool] API file read");
-                if let (Ok(network_api), Ok(pool_api)) = (
-                    Self::path_to_string(&api_path_network, ProcessName::P2pool),
-                    Self::path_to_string(&api_path_pool, ProcessName::P2pool),
-                ) {
-                    if let (Ok(network_api), Ok(pool_api)) = (
-                        PrivP2poolNetworkApi::from_str(&network_api),
-                        PrivP2poolPoolApi::from_str(&pool_api),
-                    ) {
-                        PubP2poolApi::update_from_network_pool(&pub_api, network_api, pool_api);
-                        lock!(gui_api).tick = 0;
-                    }
-                }
-            }
-
-            // Sleep (only if 900ms hasn't passed)
-            let elapsed = now.elapsed().as_millis();
-            // Since logic goes off if less than 1000, casting should be safe
-            if elapsed < 900 {
-                let sleep = (900 - elapsed) as u64;
-                debug!(
-                    "P2Pool Watchdog | END OF LOOP -  Tick: [{}/60] - Sleeping for [{}]ms...",
-                    lock!(gui_api).tick,
-                    sleep
-                );
-                sleep!(sleep);
-            } else {
-                debug!(
-                    "P2Pool Watchdog | END OF LOOP - Tick: [{}/60] Not sleeping!",
-                    lock!(gui_api).tick
-                );
-            }
-        }
-
-        // 5. If loop broke, we must be done here.
-        info!("P2Pool Watchdog | Watchdog thread exiting... Goodbye!");
+        // 3. Loop as watchdog
+        gupax_process::watch(
+            &process,
+            pty,
+            args,
+            move |plain| Self::p2pool_line(&gupax_p2pool_api, plain),
+            watchdog,
+        );
     }
 
     //---------------------------------------------------------------------------------------------------- XMRig specific, most functions are very similar to P2Pool's
@@ -1034,11 +972,36 @@ impl Helper {
         info!("XMRig | Attempting to stop...");
         lock2!(helper, xmrig).signal = ProcessSignal::Stop;
         lock2!(helper, xmrig).state = ProcessState::Middle;
+        Self::stop_xmrig_instances(helper);
     }
 
-    #[cold]
-    #[inline(never)]
-    // The "restart frontend" to a "frontend" function.
+    // Send the same STDIN line to every running extra XMRig.
+    pub fn xmrig_instances_input(instances: &Arc<Mutex<Vec<ExtraXmrig>>>, input: &str) {
+        for instance in lock!(instances).iter() {
+            let mut process = lock!(instance.process);
+            if process.is_alive() {
+                process.input.push(input.to_string());
+            }
+        }
+    }
+
+    // The extra XMRig's go down with the main one. On restart they're
+    // stopped too, and the new main XMRig starts a fresh set.
+    fn stop_xmrig_instances(helper: &Arc<Mutex<Self>>) {
+        let instances = Arc::clone(&lock!(helper).xmrig_instances);
+        for (i, instance) in lock!(instances).iter().enumerate() {
+            let mut process = lock!(instance.process);
+            if process.is_alive() {
+                info!("XMRig | Stopping instance [{}]...", i + 1);
+                process.signal = ProcessSignal::Stop;
+                process.state = ProcessState::Middle;
+            }
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // The "restart frontend" to a "frontend" function.
     // Basically calls to kill the current xmrig, waits a little, then starts the below function in a a new thread, then exit.
     pub fn restart_xmrig(
         helper: &Arc<Mutex<Self>>,
@@ -1046,24 +1009,14 @@ impl Helper {
         path: &std::path::PathBuf,
         sudo: Arc<Mutex<SudoState>>,
     ) {
-        info!("XMRig | Attempting to restart...");
-        lock2!(helper, xmrig).signal = ProcessSignal::Restart;
-        lock2!(helper, xmrig).state = ProcessState::Middle;
-
+        Self::stop_xmrig_instances(helper);
+        let process = Arc::clone(&lock!(helper).xmrig);
         let helper = Arc::clone(helper);
         let state = state.clone();
         let path = path.clone();
-        // This thread lives to wait, start xmrig then die.
-        thread::spawn(move || {
-            while lock2!(helper, xmrig).state != ProcessState::Waiting {
-                warn!("XMRig | Want to restart but process is still alive, waiting...");
-                sleep!(1000);
-            }
-            // Ok, process is not alive, start the new one!
-            info!("XMRig | Old process seems dead, starting new one!");
+        gupax_process::restart(&process, move || {
             Self::start_xmrig(&helper, &state, &path, sudo);
         });
-        info!("XMRig | Restart ... OK");
     }
 
     #[cold]
@@ -1075,23 +1028,427 @@ impl Helper {
         sudo: Arc<Mutex<SudoState>>,
     ) {
         lock2!(helper, xmrig).state = ProcessState::Middle;
+        lock2!(helper, img_xmrig).settings = state.start_settings();
+
+        if state.is_watch_only() {
+            Self::start_xmrig_watch(helper, state);
+            return;
+        }
 
-        let (args, api_ip_port) = Self::build_xmrig_args_and_mutate_img(helper, state, path);
+        let launcher = lock!(sudo).launcher;
+        let (args, api_ip_port) =
+            Self::build_xmrig_args_and_mutate_img(helper, state, path, launcher);
+        let cwd = Self::workdir_or_default(ProcessName::Xmrig, &state.workdir, path);
+        lock2!(helper, img_xmrig).workdir = Self::img_workdir(&state.workdir, &cwd);
+        lock!(helper).load_guard = LoadGuard::new(state.load_pause, state.load_pause_secs);
+
+        if crate::demo::enabled() {
+            Self::start_demo(helper, ProcessName::Xmrig);
+            return;
+        }
 
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("XMRig | Launch arguments: {:#?}", args));
         info!("XMRig | Using path: [{}]", path.display());
 
+        // The extra instances copy the main XMRig's arguments, so only
+        // for [Advanced] without overriding arguments.
+        if !state.simple && state.arguments.is_empty() {
+            Self::start_xmrig_instances(helper, state, &args, path, &cwd, &sudo);
+        }
+
         // Spawn watchdog thread
         let process = Arc::clone(&lock!(helper).xmrig);
         let gui_api = Arc::clone(&lock!(helper).gui_api_xmrig);
         let pub_api = Arc::clone(&lock!(helper).pub_api_xmrig);
         let path = path.clone();
+        let env = state.env.clone();
         thread::spawn(move || {
-            Self::spawn_xmrig_watchdog(process, gui_api, pub_api, args, path, sudo, api_ip_port);
+            Self::spawn_xmrig_watchdog(
+                process,
+                gui_api,
+                pub_api,
+                args,
+                env,
+                path,
+                cwd,
+                sudo,
+                api_ip_port,
+            );
         });
     }
 
+    #[cold]
+    #[inline(never)]
+    // Each extra instance gets its own process, API and watchdog, same as the main XMRig.
+    fn start_xmrig_instances(
+        helper: &Arc<Mutex<Self>>,
+        state: &crate::disk::Xmrig,
+        args: &[String],
+        path: &std::path::Path,
+        cwd: &std::path::Path,
+        sudo: &Arc<Mutex<SudoState>>,
+    ) {
+        let api_ip = if state.api_ip == "localhost" || state.api_ip.is_empty() {
+            "127.0.0.1"
+        } else {
+            state.api_ip.as_str()
+        };
+        let mut instances = Vec::with_capacity(state.instances.len());
+        for (i, instance) in state.instances.iter().enumerate() {
+            let n = i + 1;
+            let (args, api_ip_port) = Self::xmrig_instance_args(args, n, instance, api_ip);
+            crate::disk::print_dash(&format!(
+                "XMRig | Instance [{}] launch arguments: {:#?}",
+                n, args
+            ));
+            let extra = ExtraXmrig::new(instance);
+            lock!(extra.process).state = ProcessState::Middle;
+            let process = Arc::clone(&extra.process);
+            let gui_api = Arc::clone(&extra.gui_api);
+            let pub_api = Arc::clone(&extra.pub_api);
+            // Every watchdog wipes the [sudo] pass it was given after
+            // using it, so each gets a copy instead of sharing one.
+            let sudo = {
+                let lock = lock!(sudo);
+                arc_mut!(SudoState {
+                    pass: lock.pass.clone(),
+                    escalation: lock.escalation,
+                    launcher: lock.launcher,
+                    ..SudoState::new()
+                })
+            };
+            let path = path.to_path_buf();
+            let env = state.env.clone();
+            let cwd = cwd.to_path_buf();
+            thread::spawn(move || {
+                Self::spawn_xmrig_watchdog(
+                    process,
+                    gui_api,
+                    pub_api,
+                    args,
+                    env,
+                    path,
+                    cwd,
+                    sudo,
+                    api_ip_port,
+                );
+            });
+            instances.push(extra);
+        }
+        *lock2!(helper, xmrig_instances) = instances;
+    }
+
+    // The main XMRig's arguments with this instance's threads, affinity and API
+    // port swapped in, and a [_N] suffix on the rig ID so pools can tell them apart.
+    pub fn xmrig_instance_args(
+        main: &[String],
+        n: usize,
+        instance: &crate::disk::XmrigInstance,
+        api_ip: &str,
+    ) -> (Vec<String>, String) {
+        let mut args = Vec::with_capacity(main.len() + 4);
+        let mut iter = main.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--threads" => {
+                    iter.next();
+                    args.push(arg.clone());
+                    args.push(instance.threads.to_string());
+                }
+                "--rig-id" => {
+                    let rig = iter.next().cloned().unwrap_or_default();
+                    args.push(arg.clone());
+                    args.push(format!("{}_{}", rig, n));
+                }
+                // The API is (re)added below, the affinity too.
+                "--http-host" | "--http-port" | "--cpu-affinity" => {
+                    iter.next();
+                }
+                "--http-no-restricted" if instance.api_port.trim().is_empty() => (),
+                _ => args.push(arg.clone()),
+            }
+        }
+        if let Some(mask) = cpu_affinity_mask(&instance.affinity) {
+            args.push("--cpu-affinity".to_string());
+            args.push(mask);
+        }
+        let port = instance.api_port.trim();
+        if port.parse::<u16>().is_ok() {
+            args.push("--http-host".to_string());
+            args.push(api_ip.to_string());
+            args.push("--http-port".to_string());
+            args.push(port.to_string());
+            (args, format!("{}:{}", api_ip, port))
+        } else {
+            (args, String::new())
+        }
+    }
+
+    // The full API URI for watch-only mode, e.g:
+    // [192.168.1.2:18088] -> [http://192.168.1.2:18088/1/summary]
+    pub fn xmrig_watch_uri(url: &str) -> String {
+        let url = url.trim().trim_end_matches('/');
+        let url = if url == "localhost" || url.starts_with("localhost:") {
+            url.replacen("localhost", "127.0.0.1", 1)
+        } else {
+            url.to_string()
+        };
+        if url.starts_with("http://") || url.starts_with("https://") {
+            format!("{}/{}", url, XMRIG_API_URI)
+        } else {
+            format!("http://{}/{}", url, XMRIG_API_URI)
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Watch-only mode: an XMRig that Gupax doesn't own (e.g: a systemd service),
+    // only its HTTP API is read, nothing gets spawned or killed.
+    fn start_xmrig_watch(helper: &Arc<Mutex<Self>>, state: &crate::disk::Xmrig) {
+        let api_uri = Self::xmrig_watch_uri(&state.watch_url);
+        info!("XMRig | Watch-only mode, API URI: [{}]", api_uri);
+        // There's no STDIN to pause a watched XMRig with.
+        lock!(helper).load_guard = LoadGuard::new(0, 0);
+        {
+            let lock = lock!(helper);
+            let mut xmrig_image = lock!(lock.img_xmrig);
+            xmrig_image.threads = "???".to_string();
+            xmrig_image.url = state.watch_url.trim().to_string();
+        }
+
+        let process = Arc::clone(&lock!(helper).xmrig);
+        let gui_api = Arc::clone(&lock!(helper).gui_api_xmrig);
+        let pub_api = Arc::clone(&lock!(helper).pub_api_xmrig);
+        let token = state.watch_token.trim().to_string();
+        thread::spawn(move || {
+            Self::spawn_xmrig_watch_watchdog(process, gui_api, pub_api, api_uri, token);
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    // The watch-only "watchdog", it just polls the API until told to stop.
+    fn spawn_xmrig_watch_watchdog(
+        process: Arc<Mutex<Process>>,
+        gui_api: Arc<Mutex<PubXmrigApi>>,
+        pub_api: Arc<Mutex<PubXmrigApi>>,
+        api_uri: String,
+        token: String,
+    ) {
+        let mut api = HttpJson::<PrivXmrigApi>::new(api_uri.clone()).with_token(&token);
+
+        *lock!(pub_api) = PubXmrigApi::new();
+        *lock!(gui_api) = PubXmrigApi::new();
+        {
+            lock!(process).spawned::<XmrigProcess>();
+        }
+        if let Err(e) = writeln!(
+            lock!(gui_api).output,
+            "{}\nWatching XMRig at [{}]\nGupax does not own this XMRig, it can't be stopped or configured from here\n{}",
+            HORI_CONSOLE,
+            api_uri,
+            HORI_CONSOLE
+        ) {
+            error!("XMRig Watch | GUI write failed: {}", e);
+        }
+
+        // [None] until the first request, only state changes get printed.
+        let mut reachable = None;
+        info!("XMRig | Entering watch-only mode...");
+        loop {
+            let now = Instant::now();
+
+            // Stop on [Stop/Restart] SIGNAL, there's no process to kill.
+            let signal = lock!(process).signal;
+            if signal == ProcessSignal::Stop || signal == ProcessSignal::Restart {
+                {
+                    let mut process = lock!(process);
+                    match signal {
+                        ProcessSignal::Stop => {
+                            process.state = ProcessState::Dead;
+                            process.signal = ProcessSignal::None;
+                        }
+                        _ => process.state = ProcessState::Waiting,
+                    }
+                }
+                if let Err(e) = writeln!(
+                    lock!(gui_api).output,
+                    "{}\nStopped watching XMRig\n{}\n\n\n\n",
+                    HORI_CONSOLE,
+                    HORI_CONSOLE
+                ) {
+                    error!("XMRig Watch | GUI write failed: {}", e);
+                }
+                break;
+            }
+
+            match api.fetch() {
+                Ok(priv_api) => {
+                    lock!(pub_api).uptime = HumanTime::from_u64(priv_api.uptime);
+                    PubXmrigApi::update_from_priv(&pub_api, priv_api);
+                    lock!(process).state = ProcessState::Alive;
+                    if reachable != Some(true) {
+                        info!("XMRig Watch | [{}] ... OK", api_uri);
+                        let _ = writeln!(lock!(gui_api).output, "XMRig API is reachable");
+                    }
+                    reachable = Some(true);
+                }
+                Err(e) => {
+                    lock!(process).state = ProcessState::NotMining;
+                    if reachable != Some(false) {
+                        warn!("XMRig Watch | [{}] ... FAIL: {}", api_uri, e);
+                        let _ = writeln!(
+                            lock!(gui_api).output,
+                            "Could not reach the XMRig API: {}",
+                            e
+                        );
+                    }
+                    reachable = Some(false);
+                }
+            }
+
+            let elapsed = now.elapsed().as_millis();
+            if elapsed < 900 {
+                sleep!((900 - elapsed) as u64);
+            }
+        }
+        info!("XMRig Watch | Watch-only thread exiting... Goodbye!");
+    }
+
+    //---------------------------------------------------------------------------------------------------- Demo
+    #[cold]
+    #[inline(never)]
+    // [--demo] "frontend", nothing gets spawned, see [demo.rs].
+    fn start_demo(helper: &Arc<Mutex<Self>>, name: ProcessName) {
+        info!("{} | Demo mode, not spawning a process", name);
+        let lock = lock!(helper);
+        let process = match name {
+            ProcessName::P2pool => Arc::clone(&lock.p2pool),
+            ProcessName::Xmrig => Arc::clone(&lock.xmrig),
+            ProcessName::Proxy => Arc::clone(&lock.proxy),
+        };
+        let p2pool = (
+            Arc::clone(&lock.gui_api_p2pool),
+            Arc::clone(&lock.pub_api_p2pool),
+        );
+        let xmrig = (
+            Arc::clone(&lock.gui_api_xmrig),
+            Arc::clone(&lock.pub_api_xmrig),
+        );
+        drop(lock);
+        thread::spawn(move || match name {
+            ProcessName::P2pool => Self::spawn_demo_watchdog(process, Some(p2pool), None),
+            ProcessName::Xmrig => Self::spawn_demo_watchdog(process, None, Some(xmrig)),
+            ProcessName::Proxy => Self::spawn_demo_watchdog(process, None, None),
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[expect(clippy::type_complexity)]
+    // The demo watchdog, feeds fake data through the same parsing the real watchdogs use.
+    fn spawn_demo_watchdog(
+        process: Arc<Mutex<Process>>,
+        p2pool: Option<(Arc<Mutex<PubP2poolApi>>, Arc<Mutex<PubP2poolApi>>)>,
+        xmrig: Option<(Arc<Mutex<PubXmrigApi>>, Arc<Mutex<PubXmrigApi>>)>,
+    ) {
+        let (name, output_parse, output_pub) = {
+            let mut lock = lock!(process);
+            match lock.name {
+                ProcessName::P2pool => lock.spawned::<P2poolProcess>(),
+                ProcessName::Xmrig => lock.spawned::<XmrigProcess>(),
+                ProcessName::Proxy => lock.spawned::<ProxyProcess>(),
+            }
+            (
+                lock.name,
+                Arc::clone(&lock.output_parse),
+                Arc::clone(&lock.output_pub),
+            )
+        };
+        if let Some((gui_api, pub_api)) = &p2pool {
+            *lock!(pub_api) = PubP2poolApi::new();
+            *lock!(gui_api) = PubP2poolApi::new();
+        }
+        if let Some((gui_api, pub_api)) = &xmrig {
+            *lock!(pub_api) = PubXmrigApi::new();
+            *lock!(gui_api) = PubXmrigApi::new();
+        }
+
+        info!("{} | Entering demo mode...", name);
+        let mut secs = 0;
+        loop {
+            let now = Instant::now();
+
+            // There's no process to kill or send STDIN to.
+            let signal = {
+                let mut lock = lock!(process);
+                lock.input.clear();
+                lock.signal
+            };
+            if signal == ProcessSignal::Stop || signal == ProcessSignal::Restart {
+                let mut lock = lock!(process);
+                match signal {
+                    ProcessSignal::Stop => {
+                        lock.state = ProcessState::Dead;
+                        lock.signal = ProcessSignal::None;
+                    }
+                    _ => lock.state = ProcessState::Waiting,
+                }
+                break;
+            }
+
+            let output = match name {
+                ProcessName::P2pool => crate::demo::p2pool_output(secs),
+                ProcessName::Xmrig => crate::demo::xmrig_output(secs),
+                // There's no fake XMRig-Proxy, it just sits there.
+                ProcessName::Proxy => String::new(),
+            };
+            lock!(output_parse).push_str(&output);
+            lock!(output_pub).push_str(&output);
+            let elapsed = lock!(process).start.elapsed();
+
+            if let Some((_, pub_api)) = &p2pool {
+                PubP2poolApi::update_from_output(
+                    pub_api,
+                    &output_parse,
+                    &output_pub,
+                    elapsed,
+                    &process,
+                );
+                if let Ok(local) = crate::demo::p2pool_local(secs).parse::<PrivP2poolLocalApi>() {
+                    PubP2poolApi::update_from_local(pub_api, local);
+                }
+                if let (Ok(net), Ok(pool)) = (
+                    crate::demo::p2pool_network(secs).parse::<PrivP2poolNetworkApi>(),
+                    crate::demo::p2pool_pool().parse::<PrivP2poolPoolApi>(),
+                ) {
+                    PubP2poolApi::update_from_network_pool(pub_api, net, pool);
+                }
+            }
+            if let Some((_, pub_api)) = &xmrig {
+                PubXmrigApi::update_from_output(
+                    pub_api,
+                    &output_parse,
+                    &output_pub,
+                    elapsed,
+                    &process,
+                );
+                match serde_json::from_str::<PrivXmrigApi>(&crate::demo::xmrig_summary(secs)) {
+                    Ok(private) => PubXmrigApi::update_from_priv(pub_api, private),
+                    Err(e) => warn!("XMRig Demo | Could not deserialize API data: {}", e),
+                }
+            }
+
+            secs += 1;
+            let elapsed = now.elapsed().as_millis();
+            if elapsed < 1000 {
+                sleep!((1000 - elapsed) as u64);
+            }
+        }
+        info!("{} | Demo thread exiting... Goodbye!", name);
+    }
+
     #[cold]
     #[inline(never)]
     // Takes in some [State/Xmrig] and parses it to build the actual command arguments.
@@ -1101,19 +1458,20 @@ impl Helper {
         helper: &Arc<Mutex<Self>>,
         state: &crate::disk::Xmrig,
         path: &std::path::PathBuf,
+        launcher: crate::sudo::Escalation,
     ) -> (Vec<String>, String) {
         let mut args = Vec::with_capacity(500);
         let mut api_ip = String::with_capacity(15);
         let mut api_port = String::with_capacity(5);
         let path = path.clone();
-        // The actual binary we're executing is [sudo], technically
-        // the XMRig path is just an argument to sudo, so add it.
-        // Before that though, add the ["--prompt"] flag and set it
-        // to emptiness so that it doesn't show up in the output.
+        // The actual binary we're executing is [sudo] (or doas/pkexec),
+        // technically the XMRig path is just an argument to it, so add it.
+        // Extra environment variables go through [env] since the launcher drops them.
         if cfg!(unix) {
-            args.push(r#"--prompt="#.to_string());
-            args.push("--".to_string());
-            args.push(path.display().to_string());
+            args.extend(crate::envvar::launcher_args(
+                launcher.launcher().args(&path),
+                &state.env,
+            ));
         }
 
         // [Simple]
@@ -1122,7 +1480,7 @@ impl Helper {
             let rig = if state.simple_rig.is_empty() {
                 GUPAX_VERSION_UNDERSCORE.to_string()
             } else {
-                state.simple_rig.clone()
+                crate::rig::expand(&state.simple_rig, state.current_threads)
             }; // Rig name
             args.push("--url".to_string());
             args.push("127.0.0.1:3333".to_string()); // Local P2Pool (the default)
@@ -1130,7 +1488,6 @@ impl Helper {
             args.push(state.current_threads.to_string()); // Threads
             args.push("--user".to_string());
             args.push(rig); // Rig name
-            args.push("--no-color".to_string()); // No color
             args.push("--http-host".to_string());
             args.push("127.0.0.1".to_string()); // HTTP API IP
             args.push("--http-port".to_string());
@@ -1139,10 +1496,16 @@ impl Helper {
                 args.push("--pause-on-active".to_string());
                 args.push(state.pause.to_string());
             } // Pause on active
-            *lock2!(helper, img_xmrig) = ImgXmrig {
-                threads: state.current_threads.to_string(),
-                url: "127.0.0.1:3333 (Local P2Pool)".to_string(),
-            };
+            if let Some(priority) = state.priority {
+                args.push("--cpu-priority".to_string());
+                args.push(priority.to_string());
+            } // CPU priority
+            let lock = lock!(helper);
+            let mut xmrig_image = lock!(lock.img_xmrig);
+            xmrig_image.threads = state.current_threads.to_string();
+            xmrig_image.url = "127.0.0.1:3333 (Local P2Pool)".to_string();
+            drop(xmrig_image);
+            drop(lock);
             api_ip = "127.0.0.1".to_string();
             api_port = "18088".to_string();
 
@@ -1194,21 +1557,32 @@ impl Helper {
                 } else {
                     state.api_port.to_string()
                 };
+                if !state.api_enabled {
+                    api_ip.clear();
+                    api_port.clear();
+                }
                 let url = format!("{}:{}", ip, state.port); // Combine IP:Port into one string
                 args.push("--user".to_string());
                 args.push(state.address.clone()); // Wallet
                 args.push("--threads".to_string());
                 args.push(state.current_threads.to_string()); // Threads
                 args.push("--rig-id".to_string());
-                args.push(state.rig.to_string()); // Rig ID
+                args.push(crate::rig::expand(&state.rig, state.current_threads)); // Rig ID
                 args.push("--url".to_string());
                 args.push(url.clone()); // IP/Port
-                args.push("--http-host".to_string());
-                args.push(api_ip.to_string()); // HTTP API IP
-                args.push("--http-port".to_string());
-                args.push(api_port.to_string()); // HTTP API Port
-                args.push("--no-color".to_string()); // No color escape codes
-                if state.tls {
+                if state.api_enabled {
+                    args.push("--http-host".to_string());
+                    args.push(api_ip.to_string()); // HTTP API IP
+                    args.push("--http-port".to_string());
+                    args.push(api_port.to_string()); // HTTP API Port
+                    if !state.api_restricted {
+                        args.push("--http-no-restricted".to_string());
+                    } // Restricted (on by default)
+                }
+                // Only if the binary was built with TLS, XMRig exits on an unknown [--tls] otherwise.
+                let tls_ok = lock!(lock2!(helper, img_xmrig).probe)
+                    .has_feature(crate::probe::XMRIG_FEATURE_TLS);
+                if state.tls && tls_ok {
                     args.push("--tls".to_string());
                 } // TLS
                 if state.keepalive {
@@ -1218,78 +1592,120 @@ impl Helper {
                     args.push("--pause-on-active".to_string());
                     args.push(state.pause.to_string());
                 } // Pause on active
-                *lock2!(helper, img_xmrig) = ImgXmrig {
-                    url,
-                    threads: state.current_threads.to_string(),
-                };
+                if let Some(priority) = state.priority {
+                    args.push("--cpu-priority".to_string());
+                    args.push(priority.to_string());
+                } // CPU priority
+                if state.pages_1gb {
+                    args.push("--randomx-1gb-pages".to_string());
+                } // 1GB pages
+                if state.huge_pages_jit {
+                    args.push("--huge-pages-jit".to_string());
+                } // Huge pages for JIT code
+                if !state.msr_mod {
+                    args.push("--randomx-no-msr".to_string());
+                } // MSR mod (on by default)
+                args.push("--donate-level".to_string());
+                args.push(state.donate_level.to_string()); // Donate level
+                if let Some(mask) = cpu_affinity_mask(&state.affinity) {
+                    args.push("--cpu-affinity".to_string());
+                    args.push(mask);
+                } // CPU affinity
+                let lock = lock!(helper);
+                let mut xmrig_image = lock!(lock.img_xmrig);
+                xmrig_image.url = url;
+                xmrig_image.threads = state.current_threads.to_string();
             }
         }
+        // No [--http-port] (API turned off, or left out of the
+        // overriding arguments) means no API, only STDOUT.
+        if api_port.is_empty() {
+            return (args, String::new());
+        }
         (args, format!("{}:{}", api_ip, api_port))
     }
 
-    // We actually spawn [sudo] on Unix, with XMRig being the argument.
+    // We actually spawn [sudo] (or doas/pkexec) on Unix, with XMRig being the argument.
     #[cfg(target_family = "unix")]
-    fn create_xmrig_cmd_unix(args: Vec<String>, path: PathBuf) -> portable_pty::CommandBuilder {
-        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new("sudo");
+    fn create_xmrig_cmd_unix(
+        args: Vec<String>,
+        env: &[crate::disk::EnvVar],
+        cwd: PathBuf,
+        program: &str,
+    ) -> portable_pty::CommandBuilder {
+        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(program);
         cmd.args(args);
-        cmd.cwd(path.as_path().parent().unwrap());
+        cmd.cwd(cwd);
+        crate::envvar::apply(&mut cmd, env);
         cmd
     }
 
     // Gupax should be admin on Windows, so just spawn XMRig normally.
     #[cfg(target_os = "windows")]
-    fn create_xmrig_cmd_windows(args: Vec<String>, path: PathBuf) -> portable_pty::CommandBuilder {
-        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(path.clone());
+    fn create_xmrig_cmd_windows(
+        args: Vec<String>,
+        env: &[crate::disk::EnvVar],
+        path: PathBuf,
+        cwd: PathBuf,
+    ) -> portable_pty::CommandBuilder {
+        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(path);
         cmd.args(args);
-        cmd.cwd(path.as_path().parent().unwrap());
+        cmd.cwd(cwd);
+        crate::envvar::apply(&mut cmd, env);
         cmd
     }
 
     #[cold]
     #[inline(never)]
-    // The XMRig watchdog. Spawns 1 OS thread for reading a PTY (STDOUT+STDERR), and combines the [Child] with a PTY so STDIN actually works.
-    // This isn't actually async, a tokio runtime is unfortunately needed because [Hyper] is an async library (HTTP API calls)
-    #[tokio::main]
-    async fn spawn_xmrig_watchdog(
+    #[expect(clippy::too_many_arguments)]
+    // The XMRig watchdog. Spawns XMRig in a PTY so STDIN actually works, see [gupax_process::watch()].
+    fn spawn_xmrig_watchdog(
         process: Arc<Mutex<Process>>,
         gui_api: Arc<Mutex<PubXmrigApi>>,
         pub_api: Arc<Mutex<PubXmrigApi>>,
         args: Vec<String>,
+        env: Vec<crate::disk::EnvVar>,
+        // On Unix it's already in [args], after [sudo/doas/pkexec].
         path: std::path::PathBuf,
+        cwd: std::path::PathBuf,
         sudo: Arc<Mutex<SudoState>>,
         mut api_ip_port: String,
     ) {
-        // 1a. Create PTY
-        debug!("XMRig | Creating PTY...");
-        let pty = portable_pty::native_pty_system();
-        let pair = pty
-            .openpty(portable_pty::PtySize {
-                rows: 100,
-                cols: 1000,
-                pixel_width: 0,
-                pixel_height: 0,
-            })
-            .unwrap();
-        // 1b. Create command
+        // 0. Check the API port before XMRig grabs it.
+        let api_port_in_use = !api_ip_port.is_empty()
+            && std::net::TcpListener::bind(&api_ip_port)
+                .is_err_and(|e| e.kind() == std::io::ErrorKind::AddrInUse);
+        // 1. Create command
         debug!("XMRig | Creating command...");
+        let launcher = lock!(sudo).launcher.launcher();
         #[cfg(target_os = "windows")]
-        let cmd = Self::create_xmrig_cmd_windows(args, path);
+        let cmd = Self::create_xmrig_cmd_windows(args.clone(), &env, path.clone(), cwd);
         #[cfg(target_family = "unix")]
-        let cmd = Self::create_xmrig_cmd_unix(args, path);
-        // 1c. Create child
+        let cmd = Self::create_xmrig_cmd_unix(args.clone(), &env, cwd, launcher.program());
+        // 2. Create child
         debug!("XMRig | Creating child...");
-        let child_pty = arc_mut!(pair.slave.spawn_command(cmd).unwrap());
-        drop(pair.slave);
-
-        let mut stdin = pair.master.take_writer().unwrap();
+        let mut pty = match Pty::spawn(cmd, &path) {
+            Ok(pty) => pty,
+            Err(e) => {
+                Self::spawn_failed(
+                    ProcessName::Xmrig,
+                    &process,
+                    &mut lock!(gui_api).output,
+                    &path,
+                    &e,
+                );
+                return;
+            }
+        };
 
-        // 2. Input [sudo] pass, wipe, then drop.
-        if cfg!(unix) {
+        // 3. Input [sudo] pass, wipe, then drop.
+        // [pkexec] asks for it itself through the polkit agent.
+        if cfg!(unix) && launcher.needs_password() {
             debug!("XMRig | Inputting [sudo] and wiping...");
             // a) Sleep to wait for [sudo]'s non-echo prompt (on Unix).
             // this prevents users pass from showing up in the STDOUT.
             sleep!(3000);
-            if let Err(e) = writeln!(stdin, "{}", lock!(sudo).pass) {
+            if let Err(e) = pty.write_line(&lock!(sudo).pass) {
                 error!("XMRig | Sudo STDIN error: {}", e);
             };
             SudoState::wipe(&sudo);
@@ -1299,225 +1715,49 @@ impl Helper {
             lock!(gui_api).output.clear();
         }
 
-        // 3. Set process state
-        debug!("XMRig | Setting process state...");
-        let mut lock = lock!(process);
-        lock.state = ProcessState::NotMining;
-        lock.signal = ProcessSignal::None;
-        lock.start = Instant::now();
-        let reader = pair.master.try_clone_reader().unwrap(); // Get STDOUT/STDERR before moving the PTY
-        drop(lock);
-
-        // 4. Spawn PTY read thread
-        debug!("XMRig | Spawning PTY read thread...");
-        let output_parse = Arc::clone(&lock!(process).output_parse);
-        let output_pub = Arc::clone(&lock!(process).output_pub);
-        thread::spawn(move || {
-            Self::read_pty_xmrig(output_parse, output_pub, reader);
-        });
-        let output_parse = Arc::clone(&lock!(process).output_parse);
-        let output_pub = Arc::clone(&lock!(process).output_pub);
-
-        let client: hyper::Client<hyper::client::HttpConnector> =
-            hyper::Client::builder().build(hyper::client::HttpConnector::new());
-        let start = lock!(process).start;
-        let api_uri = {
+        let api = if api_ip_port.is_empty() {
+            info!("XMRig | HTTP API is off, only reading STDOUT");
+            None
+        } else {
             if !api_ip_port.ends_with('/') {
                 api_ip_port.push('/');
             }
-            "http://".to_owned() + &api_ip_port + XMRIG_API_URI
+            let api_uri = "http://".to_owned() + &api_ip_port + XMRIG_API_URI;
+            info!("XMRig | Final API URI: {}", api_uri);
+            Some(HttpJson::new(api_uri))
         };
-        info!("XMRig | Final API URI: {}", api_uri);
 
         // Reset stats before loop
         *lock!(pub_api) = PubXmrigApi::new();
         *lock!(gui_api) = PubXmrigApi::new();
-
-        // 5. Loop as watchdog
-        info!("XMRig | Entering watchdog mode... woof!");
-        loop {
-            // Set timer
-            let now = Instant::now();
-            debug!("XMRig Watchdog | ----------- Start of loop -----------");
-
-            // Check if the process secretly died without us knowing :)
-            if let Ok(Some(code)) = lock!(child_pty).try_wait() {
-                debug!("XMRig Watchdog | Process secretly died on us! Getting exit status...");
-                let exit_status = match code.success() {
-                    true => {
-                        lock!(process).state = ProcessState::Dead;
-                        "Successful"
-                    }
-                    false => {
-                        lock!(process).state = ProcessState::Failed;
-                        "Failed"
-                    }
-                };
-                let uptime = HumanTime::into_human(start.elapsed());
-                info!(
-                    "XMRig | Stopped ... Uptime was: [{}], Exit status: [{}]",
-                    uptime, exit_status
-                );
-                if let Err(e) = writeln!(
-                    lock!(gui_api).output,
-                    "{}\nXMRig stopped | Uptime: [{}] | Exit status: [{}]\n{}\n\n\n\n",
-                    HORI_CONSOLE,
-                    uptime,
-                    exit_status,
-                    HORI_CONSOLE
-                ) {
-                    error!(
-                        "XMRig Watchdog | GUI Uptime/Exit status write failed: {}",
-                        e
-                    );
-                }
-                lock!(process).signal = ProcessSignal::None;
-                debug!("XMRig Watchdog | Secret dead process reap OK, breaking");
-                break;
-            }
-
-            // Stop on [Stop/Restart] SIGNAL
-            let signal = lock!(process).signal;
-            if signal == ProcessSignal::Stop || signal == ProcessSignal::Restart {
-                debug!("XMRig Watchdog | Stop/Restart SIGNAL caught");
-                // macOS requires [sudo] again to kill [XMRig]
-                if cfg!(target_os = "macos") {
-                    // If we're at this point, that means the user has
-                    // entered their [sudo] pass again, after we wiped it.
-                    // So, we should be able to find it in our [Arc<Mutex<SudoState>>].
-                    Self::sudo_kill(lock!(child_pty).process_id().unwrap(), &sudo);
-                    // And... wipe it again (only if we're stopping full).
-                    // If we're restarting, the next start will wipe it for us.
-                    if signal != ProcessSignal::Restart {
-                        SudoState::wipe(&sudo);
-                    }
-                } else if let Err(e) = lock!(child_pty).kill() {
-                    error!("XMRig Watchdog | Kill error: {}", e);
-                }
-                let exit_status = match lock!(child_pty).wait() {
-                    Ok(e) => {
-                        let mut process = lock!(process);
-                        if e.success() {
-                            if process.signal == ProcessSignal::Stop {
-                                process.state = ProcessState::Dead;
-                            }
-                            "Successful"
-                        } else {
-                            if process.signal == ProcessSignal::Stop {
-                                process.state = ProcessState::Failed;
-                            }
-                            "Failed"
-                        }
-                    }
-                    _ => {
-                        let mut process = lock!(process);
-                        if process.signal == ProcessSignal::Stop {
-                            process.state = ProcessState::Failed;
-                        }
-                        "Unknown Error"
-                    }
-                };
-                let uptime = HumanTime::into_human(start.elapsed());
-                info!(
-                    "XMRig | Stopped ... Uptime was: [{}], Exit status: [{}]",
-                    uptime, exit_status
-                );
-                if let Err(e) = writeln!(
-                    lock!(gui_api).output,
-                    "{}\nXMRig stopped | Uptime: [{}] | Exit status: [{}]\n{}\n\n\n\n",
-                    HORI_CONSOLE,
-                    uptime,
-                    exit_status,
-                    HORI_CONSOLE
-                ) {
-                    error!(
-                        "XMRig Watchdog | GUI Uptime/Exit status write failed: {}",
-                        e
-                    );
-                }
-                let mut process = lock!(process);
-                match process.signal {
-                    ProcessSignal::Stop => process.signal = ProcessSignal::None,
-                    ProcessSignal::Restart => process.state = ProcessState::Waiting,
-                    _ => (),
-                }
-                debug!("XMRig Watchdog | Stop/Restart SIGNAL done, breaking");
-                break;
-            }
-
-            // Check vector of user input
-            let mut lock = lock!(process);
-            if !lock.input.is_empty() {
-                let input = std::mem::take(&mut lock.input);
-                for line in input {
-                    if line.is_empty() {
-                        continue;
-                    }
-                    debug!(
-                        "XMRig Watchdog | User input not empty, writing to STDIN: [{}]",
-                        line
-                    );
-                    #[cfg(target_os = "windows")]
-                    if let Err(e) = write!(stdin, "{}\r\n", line) {
-                        error!("XMRig Watchdog | STDIN error: {}", e);
-                    }
-                    #[cfg(target_family = "unix")]
-                    if let Err(e) = writeln!(stdin, "{}", line) {
-                        error!("XMRig Watchdog | STDIN error: {}", e);
-                    }
-                    // Flush.
-                    if let Err(e) = stdin.flush() {
-                        error!("XMRig Watchdog | STDIN flush error: {}", e);
-                    }
-                }
-            }
-            drop(lock);
-
-            // Check if logs need resetting
-            debug!("XMRig Watchdog | Attempting GUI log reset check");
-            let mut lock = lock!(gui_api);
-            Self::check_reset_gui_output(&mut lock.output, ProcessName::Xmrig);
-            drop(lock);
-
-            // Always update from output
-            debug!("XMRig Watchdog | Starting [update_from_output()]");
-            PubXmrigApi::update_from_output(
-                &pub_api,
-                &output_pub,
-                &output_parse,
-                start.elapsed(),
-                &process,
+        if api_port_in_use {
+            warn!(
+                "XMRig | HTTP API [{}] is already in use, falling back to STDOUT",
+                api_ip_port
             );
-
-            // Send an HTTP API request
-            debug!("XMRig Watchdog | Attempting HTTP API request...");
-            if let Ok(priv_api) = PrivXmrigApi::request_xmrig_api(client.clone(), &api_uri).await {
-                debug!("XMRig Watchdog | HTTP API request OK, attempting [update_from_priv()]");
-                PubXmrigApi::update_from_priv(&pub_api, priv_api);
-            } else {
-                warn!(
-                    "XMRig Watchdog | Could not send HTTP API request to: {}",
-                    api_uri
-                );
-            }
-
-            // Sleep (only if 900ms hasn't passed)
-            let elapsed = now.elapsed().as_millis();
-            // Since logic goes off if less than 1000, casting should be safe
-            if elapsed < 900 {
-                let sleep = (900 - elapsed) as u64;
-                debug!(
-                    "XMRig Watchdog | END OF LOOP - Sleeping for [{}]ms...",
-                    sleep
-                );
-                sleep!(sleep);
-            } else {
-                debug!("XMRig Watchdog | END OF LOOP - Not sleeping!");
+            if let Err(e) = writeln!(
+                lock!(gui_api).output,
+                "{}\nGupax | XMRig's HTTP API [{}] is already in use by another program, stats will only come from XMRig's output\n{}\n",
+                HORI_CONSOLE,
+                api_ip_port.trim_end_matches('/'),
+                HORI_CONSOLE
+            ) {
+                error!("XMRig Watchdog | GUI API port warning write failed: {}", e);
             }
         }
+        let output_parse = Arc::clone(&lock!(process).output_parse);
+        let output_pub = Arc::clone(&lock!(process).output_pub);
+        let watchdog = XmrigWatchdog {
+            output_parse,
+            output_pub,
+            gui_api,
+            pub_api,
+            api,
+            sudo,
+        };
 
-        // 5. If loop broke, we must be done here.
-        info!("XMRig Watchdog | Watchdog thread exiting... Goodbye!");
+        // 4. Loop as watchdog
+        gupax_process::watch(&process, pty, args, |_| {}, watchdog);
     }
 
     //---------------------------------------------------------------------------------------------------- The "helper"
@@ -1529,7 +1769,7 @@ impl Helper {
         helper: &Helper,
         max_threads: usize,
     ) {
-        let gupax_uptime = helper.uptime.to_string();
+        let gupax_uptime = helper.uptime.localized();
         let cpu = &sysinfo.cpus()[0];
         let gupax_cpu_usage = format!(
             "{:.2}%",
@@ -1537,7 +1777,7 @@ impl Helper {
         );
         let gupax_memory_used_mb =
             HumanNumber::from_u64(sysinfo.process(*pid).unwrap().memory() / 1_000_000);
-        let gupax_memory_used_mb = format!("{} megabytes", gupax_memory_used_mb);
+        let gupax_memory_used_mb = format!("{} megabytes", gupax_memory_used_mb.localized());
         let system_cpu_model = format!("{} ({}MHz)", cpu.brand(), cpu.frequency());
         let system_memory = {
             let used = (sysinfo.used_memory() as f64) / 1_000_000_000.0;
@@ -1558,9 +1798,57 @@ impl Helper {
             system_cpu_usage,
             system_memory,
             system_cpu_model,
+            buffers: std::mem::take(&mut pub_sys.buffers),
         };
     }
 
+    // The bytes held by the P2Pool/XMRig output and API buffers:
+    //   console | The GUI/helper copies of the console output
+    //   unread  | The [parse/pub] output the watchdogs haven't read yet
+    //   api     | The parsed [Pub*Api] stats
+    fn buffer_usage(
+        processes: &[&Process],
+        p2pool_apis: &[&PubP2poolApi],
+        xmrig_apis: &[&PubXmrigApi],
+    ) -> String {
+        // The watchdogs lock these before their [Process], which is already locked
+        // here, so they're only peeked at. A busy buffer is being emptied anyway.
+        let peek = |s: &Mutex<String>| s.try_lock().map_or(0, |s| s.capacity());
+        let unread: usize = processes
+            .iter()
+            .map(|p| peek(&p.output_parse) + peek(&p.output_pub))
+            .sum();
+        let console: usize = p2pool_apis
+            .iter()
+            .map(|a| a.output.capacity())
+            .sum::<usize>()
+            + xmrig_apis
+                .iter()
+                .map(|a| a.output.capacity())
+                .sum::<usize>();
+        let api: usize = p2pool_apis.iter().map(|a| a.api_bytes()).sum::<usize>()
+            + xmrig_apis.iter().map(|a| a.api_bytes()).sum::<usize>();
+        let mut text = format!(
+            "{} (console {}, unread {}, API {})",
+            HumanNumber::to_bytes_short(console + unread + api).localized(),
+            HumanNumber::to_bytes_short(console).localized(),
+            HumanNumber::to_bytes_short(unread).localized(),
+            HumanNumber::to_bytes_short(api).localized(),
+        );
+        let trimmed = gupax_process::trimmed_bytes();
+        if trimmed != 0 {
+            text.push_str(&format!(
+                ", dropped {}",
+                HumanNumber::to_bytes_short(trimmed as usize).localized()
+            ));
+        }
+        let skipped = gupax_process::skipped_lines();
+        if skipped != 0 {
+            text.push_str(&format!(", {} flood lines skipped", skipped));
+        }
+        text
+    }
+
     #[cold]
     #[inline(never)]
     // The "helper" thread. Syncs data between threads here and the GUI.
@@ -1595,6 +1883,10 @@ impl Helper {
         let gui_api_xmrig = Arc::clone(&lock.gui_api_xmrig);
         let pub_api_p2pool = Arc::clone(&lock.pub_api_p2pool);
         let pub_api_xmrig = Arc::clone(&lock.pub_api_xmrig);
+        let xmrig_instances = Arc::clone(&lock.xmrig_instances);
+        let proxy = Arc::clone(&lock.proxy);
+        let gui_api_proxy = Arc::clone(&lock.gui_api_proxy);
+        let pub_api_proxy = Arc::clone(&lock.pub_api_proxy);
         drop(lock);
 
         let sysinfo_cpu = sysinfo::CpuRefreshKind::everything();
@@ -1602,11 +1894,19 @@ impl Helper {
 
         thread::spawn(move || {
             info!("Helper | Hello from helper thread! Entering loop where I will spend the rest of my days...");
+            let mut resume = Resume::new();
+            let mut network = NetworkWatch::new();
+            let mut p2pool_restart = P2poolRestart::new();
+            let mut events = EventWatch::new();
+            let mut p2pool_args = ArgsWatch::new();
+            let mut xmrig_args = ArgsWatch::new();
             // Begin loop
             loop {
                 // 1. Loop init timestamp
                 let start = Instant::now();
                 debug!("Helper | ----------- Start of loop -----------");
+                let slept = resume.check();
+                let network_changed = network.check();
 
                 // Ignore the invasive [debug!()] messages on the right side of the code.
                 // The reason why they are there are so that it's extremely easy to track
@@ -1615,9 +1915,9 @@ impl Helper {
                 // 2. Lock... EVERYTHING!
                 let mut lock = lock!(helper);
                 debug!("Helper | Locking (1/8) ... [helper]");
-                let p2pool = lock!(p2pool);
+                let mut p2pool = lock!(p2pool);
                 debug!("Helper | Locking (2/8) ... [p2pool]");
-                let xmrig = lock!(xmrig);
+                let mut xmrig = lock!(xmrig);
                 debug!("Helper | Locking (3/8) ... [xmrig]");
                 let mut lock_pub_sys = lock!(pub_sys);
                 debug!("Helper | Locking (4/8) ... [pub_sys]");
@@ -1629,6 +1929,37 @@ impl Helper {
                 debug!("Helper | Locking (7/8) ... [pub_api_p2pool]");
                 let mut pub_api_xmrig = lock!(pub_api_xmrig);
                 debug!("Helper | Locking (8/8) ... [pub_api_xmrig]");
+                // After a system sleep, move the [Instant]'s forward so uptimes
+                // (and everything calculated from them) don't include the sleep.
+                // Only needed if the monotonic clock kept counting during it (Windows).
+                if let Some((slept, monotonic)) = slept {
+                    info!(
+                        "Helper | System resumed after sleeping for [{}]",
+                        HumanTime::into_human(slept)
+                    );
+                    for instant in [&mut lock.instant, &mut p2pool.start, &mut xmrig.start] {
+                        *instant = instant.checked_add(monotonic).unwrap_or(*instant);
+                    }
+                    lock.resumed = Some(slept);
+                }
+                if network_changed {
+                    lock.network_changed = true;
+                    crate::event::push(crate::event::Kind::Notice, "Network changed");
+                }
+                events.check(&p2pool, &xmrig, pub_api_p2pool.shares_found_u64);
+                p2pool_args.check("P2Pool", &p2pool, &mut gui_api_p2pool.output);
+                xmrig_args.check("XMRig", &xmrig, &mut gui_api_xmrig.output);
+                // XMRig can sit in its reconnect back-off for a while after P2Pool
+                // comes back, pausing/resuming makes it reconnect right away.
+                if p2pool_restart.check(p2pool.state, xmrig.state) {
+                    info!("Helper | P2Pool is back and XMRig isn't mining, sending XMRig [pause] + [resume]");
+                    crate::event::push(
+                        crate::event::Kind::Notice,
+                        "P2Pool is back, nudged XMRig to reconnect",
+                    );
+                    xmrig.input.push("p".to_string());
+                    xmrig.input.push("r".to_string());
+                }
                 // Calculate Gupax's uptime always.
                 lock.uptime = HumanTime::into_human(lock.instant.elapsed());
                 // If [P2Pool] is alive...
@@ -1645,6 +1976,29 @@ impl Helper {
                 } else {
                     debug!("Helper | XMRig is dead! Skipping...");
                 }
+                // The extra XMRig's, always locked after everything above.
+                for instance in lock!(xmrig_instances).iter() {
+                    if lock!(instance.process).is_alive() {
+                        PubXmrigApi::combine_gui_pub_api(
+                            &mut lock!(instance.gui_api),
+                            &mut lock!(instance.pub_api),
+                        );
+                    }
+                }
+                // XMRig-Proxy, also locked after everything above (and after the instances).
+                {
+                    let mut proxy = lock!(proxy);
+                    if let Some((_, monotonic)) = slept {
+                        proxy.start = proxy.start.checked_add(monotonic).unwrap_or(proxy.start);
+                    }
+                    events.check_proxy(&proxy);
+                    if proxy.is_alive() {
+                        PubProxyApi::combine_gui_pub_api(
+                            &mut lock!(gui_api_proxy),
+                            &mut lock!(pub_api_proxy),
+                        );
+                    }
+                }
 
                 // 2. Selectively refresh [sysinfo] for only what we need (better performance).
                 sysinfo.refresh_cpu_specifics(sysinfo_cpu);
@@ -1661,6 +2015,55 @@ impl Helper {
                     &lock,
                     max_threads,
                 );
+                {
+                    let instances = lock!(xmrig_instances);
+                    let instances: Vec<_> = instances
+                        .iter()
+                        .map(|i| (lock!(i.process), lock!(i.gui_api), lock!(i.pub_api)))
+                        .collect();
+                    let mut processes = vec![&*p2pool, &*xmrig];
+                    let p2pool_apis = [&*gui_api_p2pool, &*pub_api_p2pool];
+                    let mut xmrig_apis = vec![&*gui_api_xmrig, &*pub_api_xmrig];
+                    for (process, gui_api, pub_api) in &instances {
+                        processes.push(process);
+                        xmrig_apis.push(gui_api);
+                        xmrig_apis.push(pub_api);
+                    }
+                    lock_pub_sys.buffers =
+                        Self::buffer_usage(&processes, &p2pool_apis, &xmrig_apis);
+                }
+                if xmrig.is_alive() {
+                    let load = LoadGuard::other_load(&sysinfo, max_threads);
+                    match lock.load_guard.check(load) {
+                        Some(true) => {
+                            info!("Helper | Other programs are using [{:.0}%] of the CPU, pausing XMRig", load);
+                            crate::event::push(
+                                crate::event::Kind::Notice,
+                                format!(
+                                    "Other programs are using {:.0}% of the CPU, paused XMRig",
+                                    load
+                                ),
+                            );
+                            xmrig.input.push("p".to_string());
+                            Self::xmrig_instances_input(&xmrig_instances, "p");
+                        }
+                        Some(false) => {
+                            info!(
+                                "Helper | CPU load is back down to [{:.0}%], resuming XMRig",
+                                load
+                            );
+                            xmrig.input.push("r".to_string());
+                            Self::xmrig_instances_input(&xmrig_instances, "r");
+                            crate::event::push(
+                                crate::event::Kind::Notice,
+                                format!("CPU load is back down to {:.0}%, resumed XMRig", load),
+                            );
+                        }
+                        None => (),
+                    }
+                } else {
+                    lock.load_guard.reset();
+                }
 
                 // 3. Drop... (almost) EVERYTHING... IN REVERSE!
                 drop(lock_pub_sys);
@@ -1699,6 +2102,391 @@ impl Helper {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- [Resume]
+// Detects system sleep/resume by watching how long the system has been
+// suspended, i.e. the gap between a clock that counts suspend and one
+// that doesn't. The wall clock isn't used, NTP or the user can step it.
+// Linux/macOS' [Instant] stops while suspended, Windows' doesn't.
+const RESUME_THRESHOLD: Duration = Duration::from_secs(30);
+
+struct Resume {
+    instant: Instant,
+    suspended: Option<Duration>,
+}
+
+impl Resume {
+    fn new() -> Self {
+        Self {
+            instant: Instant::now(),
+            suspended: Self::suspended(),
+        }
+    }
+
+    // Returns [Some((slept, monotonic))] if the system slept since the last call,
+    // [monotonic] being how much of that the monotonic clock counted.
+    fn check(&mut self) -> Option<(Duration, Duration)> {
+        let now = Self::new();
+        let monotonic = now.instant.duration_since(self.instant);
+        let suspended = match (self.suspended, now.suspended) {
+            (Some(old), Some(new)) => new.saturating_sub(old),
+            _ => Duration::ZERO,
+        };
+        *self = now;
+        Self::slept(monotonic, suspended)
+    }
+
+    fn slept(monotonic: Duration, suspended: Duration) -> Option<(Duration, Duration)> {
+        if suspended < RESUME_THRESHOLD {
+            return None;
+        }
+        let monotonic = if monotonic >= suspended {
+            suspended
+        } else {
+            Duration::ZERO
+        };
+        Some((suspended, monotonic))
+    }
+
+    // Total time suspended since boot, [None] if this OS can't tell.
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+    fn suspended() -> Option<Duration> {
+        // macOS' [CLOCK_MONOTONIC] counts suspend, Linux's doesn't.
+        #[cfg(target_os = "macos")]
+        let (with, without) = (libc::CLOCK_MONOTONIC, libc::CLOCK_UPTIME_RAW);
+        #[cfg(not(target_os = "macos"))]
+        let (with, without) = (libc::CLOCK_BOOTTIME, libc::CLOCK_MONOTONIC);
+        let read = |clock| {
+            let mut ts: libc::timespec = unsafe { std::mem::zeroed() };
+            (unsafe { libc::clock_gettime(clock, &mut ts) } == 0)
+                .then(|| Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+        };
+        Some(read(with)?.saturating_sub(read(without)?))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn suspended() -> Option<Duration> {
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetTickCount64() -> u64;
+            fn QueryUnbiasedInterruptTime(unbiased: *mut u64) -> i32;
+        }
+        let mut unbiased: u64 = 0;
+        let (ticks, ok) = unsafe { (GetTickCount64(), QueryUnbiasedInterruptTime(&mut unbiased)) };
+        // Milliseconds with suspend, 100 nanoseconds without.
+        (ok != 0).then(|| {
+            Duration::from_millis(ticks).saturating_sub(Duration::from_nanos(unbiased * 100))
+        })
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "windows"
+    )))]
+    fn suspended() -> Option<Duration> {
+        None
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [NetworkWatch]
+// Detects network changes (Ethernet -> Wi-Fi, VPN up/down, etc) by watching
+// the local address of the default route. [UdpSocket::connect()] only asks the
+// OS for a route, nothing is actually sent to the address.
+const NETWORK_INTERVAL: Duration = Duration::from_secs(5);
+const NETWORK_ROUTE_ADDR: &str = "1.1.1.1:80";
+
+struct NetworkWatch {
+    addr: Option<std::net::IpAddr>,
+    last: Instant,
+}
+
+impl NetworkWatch {
+    fn new() -> Self {
+        Self {
+            addr: Self::default_route(),
+            last: Instant::now(),
+        }
+    }
+
+    fn default_route() -> Option<std::net::IpAddr> {
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+        socket.connect(NETWORK_ROUTE_ADDR).ok()?;
+        Some(socket.local_addr().ok()?.ip())
+    }
+
+    // Returns [true] if the network changed since the last check.
+    fn check(&mut self) -> bool {
+        if self.last.elapsed() < NETWORK_INTERVAL {
+            return false;
+        }
+        self.last = Instant::now();
+        let addr = Self::default_route();
+        let changed = Self::changed(self.addr, addr);
+        if changed {
+            info!(
+                "Helper | Network changed, default route [{:?}] -> [{:?}]",
+                self.addr, addr
+            );
+        }
+        self.addr = addr;
+        changed
+    }
+
+    // Losing the network isn't a change worth reacting to, there's
+    // nothing to re-ping. Coming back (or switching) is.
+    fn changed(old: Option<std::net::IpAddr>, new: Option<std::net::IpAddr>) -> bool {
+        new.is_some() && old != new
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [LoadGuard]
+// Pauses XMRig while the CPU is busy with other things (backups, compiles, Windows Update...)
+// so the machine stays responsive, and resumes it once that's over. Both directions need
+// the load to stay over/under the limit for [secs] in a row, so short spikes are ignored.
+struct LoadGuard {
+    limit: f32, // [0.0] is off
+    secs: u16,
+    over: u16,
+    under: u16,
+    paused: bool,
+}
+
+impl LoadGuard {
+    const fn new(limit: u8, secs: u16) -> Self {
+        Self {
+            limit: limit as f32,
+            secs,
+            over: 0,
+            under: 0,
+            paused: false,
+        }
+    }
+
+    // XMRig stopped, so it isn't paused anymore.
+    fn reset(&mut self) {
+        *self = Self::new(self.limit as u8, self.secs);
+    }
+
+    // The CPU usage % of everything but XMRig.
+    fn other_load(sysinfo: &sysinfo::System, max_threads: usize) -> f32 {
+        let threads = max_threads.max(1) as f32;
+        let total: f32 = sysinfo.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>() / threads;
+        let xmrig: f32 = sysinfo
+            .processes()
+            .values()
+            .filter(|p| p.name().to_lowercase().starts_with("xmrig"))
+            .map(|p| p.cpu_usage())
+            .sum::<f32>()
+            / threads;
+        (total - xmrig).max(0.0)
+    }
+
+    // Called every second with [other_load()], returns [Some(true)] to pause, [Some(false)] to resume.
+    fn check(&mut self, load: f32) -> Option<bool> {
+        if self.limit == 0.0 {
+            return None;
+        }
+        if load > self.limit {
+            self.over = self.over.saturating_add(1);
+            self.under = 0;
+        } else {
+            self.under = self.under.saturating_add(1);
+            self.over = 0;
+        }
+        if !self.paused && self.over >= self.secs {
+            self.paused = true;
+            Some(true)
+        } else if self.paused && self.under >= self.secs {
+            self.paused = false;
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [P2poolRestart]
+// Watches for P2Pool going down and coming back [Alive] (restart, crash, node switch),
+// so the helper can nudge an XMRig that lost its connection to it.
+struct P2poolRestart {
+    was_alive: bool,
+    went_down: bool,
+}
+
+impl P2poolRestart {
+    const fn new() -> Self {
+        Self {
+            was_alive: false,
+            went_down: false,
+        }
+    }
+
+    // Returns [true] once P2Pool is [Alive] again after going down, if XMRig isn't mining.
+    fn check(&mut self, p2pool: ProcessState, xmrig: ProcessState) -> bool {
+        let alive = p2pool == ProcessState::Alive;
+        if self.was_alive && !alive {
+            self.went_down = true;
+        }
+        self.was_alive = alive;
+        if alive && self.went_down {
+            self.went_down = false;
+            return xmrig == ProcessState::NotMining;
+        }
+        false
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [ArgsWatch]
+// Saves the arguments of runs that stayed up for [lastrun::GOOD_RUN], and prints
+// what changed since then into the console of a run that failed before that.
+struct ArgsWatch {
+    state: ProcessState,
+    start: Instant,
+    saved: bool,
+}
+
+impl ArgsWatch {
+    fn new() -> Self {
+        Self {
+            state: ProcessState::Dead,
+            start: Instant::now(),
+            saved: false,
+        }
+    }
+
+    fn check(&mut self, name: &str, process: &Process, output: &mut String) {
+        use crate::lastrun::*;
+        // A new run, or the same one moved forward after a system sleep.
+        if process.start != self.start {
+            self.start = process.start;
+            self.saved = false;
+        }
+        let failed = process.state == ProcessState::Failed && self.state != ProcessState::Failed;
+        self.state = process.state;
+        if process.args.is_empty() {
+            return;
+        }
+        let uptime = process.start.elapsed();
+        if !self.saved && process.is_alive() && uptime >= GOOD_RUN {
+            save(name, &process.args);
+            self.saved = true;
+        } else if failed && uptime < GOOD_RUN {
+            let diff = diff(&get(name), &process.args);
+            if diff.is_empty() {
+                return;
+            }
+            info!(
+                "Helper | {} failed right after starting, arguments changed: {:?}",
+                name, diff
+            );
+            if let Err(e) = writeln!(
+                output,
+                "Arguments changed since the last successful run:\n{}\n{}\n\n",
+                diff.join("\n"),
+                HORI_CONSOLE
+            ) {
+                error!("Helper | {} argument diff write failed: {}", name, e);
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [EventWatch]
+// Turns P2Pool/XMRig(-Proxy) state changes and new shares into [Status/Events].
+struct EventWatch {
+    p2pool: ProcessState,
+    xmrig: ProcessState,
+    proxy: ProcessState,
+    shares: u64,
+}
+
+impl EventWatch {
+    const fn new() -> Self {
+        Self {
+            p2pool: ProcessState::Dead,
+            xmrig: ProcessState::Dead,
+            proxy: ProcessState::Dead,
+            shares: 0,
+        }
+    }
+
+    fn check(&mut self, p2pool: &Process, xmrig: &Process, shares: u64) {
+        let events = [
+            (
+                "P2Pool",
+                p2pool,
+                Self::transition("P2Pool", self.p2pool, p2pool.state, &p2pool.exit),
+            ),
+            (
+                "XMRig",
+                xmrig,
+                Self::transition("XMRig", self.xmrig, xmrig.state, &xmrig.exit),
+            ),
+        ];
+        for (name, process, event) in events {
+            Self::fire(name, process, event);
+        }
+        // [shares_found] restarts at 0 with P2Pool.
+        if shares > self.shares {
+            crate::event::push(
+                crate::event::Kind::Share,
+                format!("P2Pool found a share ({} since start)", shares),
+            );
+        }
+        self.p2pool = p2pool.state;
+        self.xmrig = xmrig.state;
+        self.shares = shares;
+    }
+
+    // XMRig-Proxy is locked after everything else, so it's checked on its own.
+    fn check_proxy(&mut self, proxy: &Process) {
+        let event = Self::transition("XMRig-Proxy", self.proxy, proxy.state, &proxy.exit);
+        Self::fire("XMRig-Proxy", proxy, event);
+        self.proxy = proxy.state;
+    }
+
+    fn fire(name: &str, process: &Process, event: Option<(crate::event::Kind, String)>) {
+        if let Some((kind, msg)) = event {
+            let data = [
+                ("process", name.to_string()),
+                ("exit", process.exit.clone()),
+            ];
+            crate::hook::fire(kind, &msg, &data);
+            crate::event::push(kind, msg);
+        }
+    }
+
+    fn transition(
+        name: &str,
+        old: ProcessState,
+        new: ProcessState,
+        exit: &str, // [Process::exit], why it died on its own
+    ) -> Option<(crate::event::Kind, String)> {
+        use crate::event::Kind;
+        use ProcessState::*;
+        if old == new {
+            return None;
+        }
+        match (old, new) {
+            (Dead | Failed | Middle | Waiting, Alive | Syncing | NotMining) => {
+                Some((Kind::Start, format!("{} started", name)))
+            }
+            (_, Dead) => Some((Kind::Stop, format!("{} stopped", name))),
+            (_, Failed) if exit.is_empty() => {
+                Some((Kind::Crash, format!("{} exited with an error", name)))
+            }
+            (_, Failed) => Some((Kind::Crash, format!("{} died: {}", name, exit))),
+            (Alive, NotMining) => {
+                Some((Kind::Notice, format!("{} lost its pool connection", name)))
+            }
+            (NotMining, Alive) => Some((Kind::Notice, format!("{} is mining again", name))),
+            (Syncing, Alive) => Some((Kind::Notice, format!("{} is synced", name))),
+            _ => None,
+        }
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- [ImgP2pool]
 // A static "image" of data that P2Pool started with.
 // This is just a snapshot of the user data when they initially started P2Pool.
@@ -1706,13 +2494,15 @@ impl Helper {
 // No need for an [Arc<Mutex>] since the Helper thread doesn't need this information.
 #[derive(Debug, Clone)]
 pub struct ImgP2pool {
-    pub mini: String,      // Did the user start on the mini-chain?
+    pub mini: String,                          // Did the user start on the mini-chain?
     pub address: String, // What address is the current p2pool paying out to? (This gets shortened to [4xxxxx...xxxxxx])
     pub host: String,    // What monerod are we using?
     pub rpc: String,     // What is the RPC port?
     pub zmq: String,     // What is the ZMQ port?
     pub out_peers: String, // How many out-peers?
     pub in_peers: String, // How many in-peers?
+    pub settings: Vec<(&'static str, String)>, // [P2pool::start_settings()] it started with
+    pub workdir: String, // The directory it runs in if overridden, see [Helper::workdir()]
 }
 
 impl Default for ImgP2pool {
@@ -1731,10 +2521,63 @@ impl ImgP2pool {
             zmq: String::from("???"),
             out_peers: String::from("???"),
             in_peers: String::from("???"),
+            settings: Vec::new(),
+            workdir: String::new(),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- ZMQ health
+// P2Pool gets new blocks from [monerod] over ZMQ, so a node can answer RPC
+// while P2Pool is stuck mining on an old block. This is kept apart from the
+// process state so that case is visible before P2Pool starts complaining.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ZmqHealth {
+    Unknown, // Nothing ZMQ related seen yet
+    Ok,
+    Stale, // Was [Ok], but nothing from ZMQ in [STALE_SECS]
+    Failing,
+}
+
+impl ZmqHealth {
+    // A new block (and [new miner data]) is expected every ~2 minutes.
+    pub const STALE_SECS: u64 = 600;
+
+    // The health from the last ZMQ related line in [output], if any.
+    pub fn from_output(output: &str) -> Option<(Self, String)> {
+        output.lines().rev().find_map(|line| {
+            if P2POOL_REGEX.zmq_fail.is_match(line) {
+                Some((Self::Failing, line.trim().to_string()))
+            } else if P2POOL_REGEX.zmq_ok.is_match(line) {
+                Some((Self::Ok, line.trim().to_string()))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub const fn color(self) -> egui::Color32 {
+        match self {
+            Self::Unknown => LIGHT_GRAY,
+            Self::Ok => GREEN,
+            Self::Stale => YELLOW,
+            Self::Failing => RED,
         }
     }
 }
 
+impl std::fmt::Display for ZmqHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Self::Unknown => "Unknown",
+            Self::Ok => "OK",
+            Self::Stale => "Stale",
+            Self::Failing => "Failing",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- Public P2Pool API
 // Helper/GUI threads both have a copy of this, Helper updates
 // the GUI's version on a 1-second interval from the private data.
@@ -1757,6 +2600,7 @@ pub struct PubP2poolApi {
     pub hashrate_15m: HumanNumber,
     pub hashrate_1h: HumanNumber,
     pub hashrate_24h: HumanNumber,
+    pub hashrate: HumanNumber, // [15m, 1h, 24h] together, in the user's [HashrateUnit]
     pub shares_found: HumanNumber,
     pub average_effort: HumanNumber,
     pub current_effort: HumanNumber,
@@ -1790,6 +2634,18 @@ pub struct PubP2poolApi {
     pub p2pool_percent: HumanNumber, // Percentage of P2Pool hashrate capture of overall Monero hashrate.
     pub user_p2pool_percent: HumanNumber, // How much percent the user's hashrate accounts for in P2Pool.
     pub user_monero_percent: HumanNumber, // How much percent the user's hashrate accounts for in all of Monero hashrate.
+    // Share efficiency (found vs. statistically expected shares)
+    pub shares_found_u64: u64,
+    pub expected_shares: f64,      // Accumulated every [network/pool] update
+    pub expected_shares_secs: u64, // P2Pool uptime at the last accumulation
+    pub share_efficiency: HumanNumber,
+    // Built-in miner, [0] threads is off. The hashrate is parsed from [status].
+    pub miner_threads: usize,
+    pub miner_hashrate: HumanNumber,
+    // ZMQ health, parsed from the [ZMQReader]/[new miner data] lines.
+    pub zmq: ZmqHealth,
+    pub zmq_line: String, // The last ZMQ related line
+    pub zmq_secs: u64,    // P2Pool uptime when [zmq_line] was seen
 }
 
 impl Default for PubP2poolApi {
@@ -1814,6 +2670,7 @@ impl PubP2poolApi {
             hashrate_15m: HumanNumber::unknown(),
             hashrate_1h: HumanNumber::unknown(),
             hashrate_24h: HumanNumber::unknown(),
+            hashrate: HumanNumber::from_hashrate([None; 3]),
             shares_found: HumanNumber::unknown(),
             average_effort: HumanNumber::unknown(),
             current_effort: HumanNumber::unknown(),
@@ -1838,9 +2695,23 @@ impl PubP2poolApi {
             p2pool_percent: HumanNumber::unknown(),
             user_p2pool_percent: HumanNumber::unknown(),
             user_monero_percent: HumanNumber::unknown(),
+            shares_found_u64: 0,
+            expected_shares: 0.0,
+            expected_shares_secs: 0,
+            share_efficiency: HumanNumber::unknown(),
+            miner_threads: 0,
+            miner_hashrate: HumanNumber::unknown(),
+            zmq: ZmqHealth::Unknown,
+            zmq_line: String::new(),
+            zmq_secs: 0,
         }
     }
 
+    // Heap + inline bytes of the parsed stats, the console [output] is counted on its own.
+    pub fn api_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.hash.capacity()
+    }
+
     #[inline]
     // The issue with just doing [gui_api = pub_api] is that values get overwritten.
     // This doesn't matter for any of the values EXCEPT for the output, so we must
@@ -1880,6 +2751,35 @@ impl PubP2poolApi {
         (count, sum)
     }
 
+    // The built-in miner's hashrate (in H/s) from the last [status] in the output, e.g:
+    // [Hashrate        = 4.123 KH/s]. The sidechain/stratum ones have a [(...)] after [Hashrate].
+    fn calc_miner_hashrate(output: &str) -> Option<f64> {
+        if !output.contains("Hashrate") {
+            return None;
+        }
+        let output = ANSI_ESCAPE.replace_all(output, "");
+        let captures = P2POOL_REGEX.miner_hashrate.captures_iter(&output).last()?;
+        let hashrate = captures[1].parse::<f64>().ok()?;
+        let multiplier = match &captures[2] {
+            "K" => 1_000.0,
+            "M" => 1_000_000.0,
+            "G" => 1_000_000_000.0,
+            "T" => 1_000_000_000_000.0,
+            _ => 1.0,
+        };
+        Some(hashrate * multiplier)
+    }
+
+    // The new thread count if [line] is a built-in miner command.
+    fn miner_command(line: &str) -> Option<usize> {
+        let mut split = line.split_whitespace();
+        match split.next()? {
+            "start_mining" => split.next().and_then(|t| t.parse().ok()).or(Some(1)),
+            "stop_mining" => Some(0),
+            _ => None,
+        }
+    }
+
     // Mutate "watchdog"'s [PubP2poolApi] with data the process output.
     fn update_from_output(
         public: &Arc<Mutex<Self>>,
@@ -1899,28 +2799,9 @@ impl PubP2poolApi {
         // 2. Parse the full STDOUT
         let mut output_parse = lock!(output_parse);
         let (payouts_new, xmr_new) = Self::calc_payouts_and_xmr(&output_parse);
-        // Check for "SYNCHRONIZED" only if we aren't already.
-        if lock!(process).state == ProcessState::Syncing {
-            // How many times the word was captured.
-            let synchronized_captures = P2POOL_REGEX.synchronized.find_iter(&output_parse).count();
-
-            // If P2Pool receives shares before syncing, it will start mining on its own sidechain.
-            // In this instance, we technically are "synced" on block 1 and P2Pool will print "SYNCHRONIZED"
-            // although, that doesn't necessarily mean we're synced on main/mini-chain.
-            //
-            // So, if we find a `next block = 1`, that means we
-            // must look for at least 2 instances of "SYNCHRONIZED",
-            // one for the sidechain, one for main/mini.
-            if P2POOL_REGEX.next_height_1.is_match(&output_parse) {
-                if synchronized_captures > 1 {
-                    lock!(process).state = ProcessState::Alive;
-                }
-            } else if synchronized_captures > 0 {
-                // if there is no `next block = 1`, fallback to
-                // just finding 1 instance of "SYNCHRONIZED".
-                lock!(process).state = ProcessState::Alive;
-            }
-        }
+        let miner_hashrate = Self::calc_miner_hashrate(&output_parse);
+        let zmq = ZmqHealth::from_output(&output_parse);
+        lock!(process).update_state::<P2poolProcess>(&output_parse);
         // 3. Throw away [output_parse]
         output_parse.clear();
         drop(output_parse);
@@ -1974,6 +2855,30 @@ impl PubP2poolApi {
             xmr_month,
             ..std::mem::take(&mut *public)
         };
+        if let Some(hashrate) = miner_hashrate {
+            public.miner_hashrate = HumanNumber::to_hashrate_short(hashrate as f32);
+        }
+        public.update_zmq(zmq, elapsed.as_secs());
+    }
+
+    fn update_zmq(&mut self, zmq: Option<(ZmqHealth, String)>, secs: u64) {
+        match zmq {
+            Some((health, line)) => {
+                if health != self.zmq {
+                    info!("P2Pool Watchdog | ZMQ is now [{}]: {}", health, line);
+                }
+                self.zmq = health;
+                self.zmq_line = line;
+                self.zmq_secs = secs;
+            }
+            None if self.zmq == ZmqHealth::Ok
+                && secs.saturating_sub(self.zmq_secs) > ZmqHealth::STALE_SECS =>
+            {
+                info!("P2Pool Watchdog | ZMQ is now [{}]", ZmqHealth::Stale);
+                self.zmq = ZmqHealth::Stale;
+            }
+            None => (),
+        }
     }
 
     // Mutate [PubP2poolApi] with data from a [PrivP2poolLocalApi] and the process output.
@@ -1983,11 +2888,21 @@ impl PubP2poolApi {
             hashrate_15m: HumanNumber::from_u64(local.hashrate_15m),
             hashrate_1h: HumanNumber::from_u64(local.hashrate_1h),
             hashrate_24h: HumanNumber::from_u64(local.hashrate_24h),
+            hashrate: HumanNumber::from_hashrate([
+                Some(local.hashrate_15m as f32),
+                Some(local.hashrate_1h as f32),
+                Some(local.hashrate_24h as f32),
+            ]),
             shares_found: HumanNumber::from_u64(local.shares_found),
             average_effort: HumanNumber::to_percent(local.average_effort),
             current_effort: HumanNumber::to_percent(local.current_effort),
             connections: HumanNumber::from_u32(local.connections),
             user_p2pool_hashrate_u64: local.hashrate_1h,
+            shares_found_u64: local.shares_found,
+            share_efficiency: Self::calculate_share_efficiency(
+                local.shares_found,
+                public.expected_shares,
+            ),
             ..std::mem::take(&mut *public)
         };
     }
@@ -2041,7 +2956,22 @@ impl PubP2poolApi {
             ));
         }
         let mut public = lock!(public);
+        // Accumulate the shares we should have found since the last
+        // update: [hashrate * seconds / difficulty]. The user hashrate
+        // & P2Pool difficulty both change over time, so this is summed
+        // piece by piece instead of using the current values for all of uptime.
+        let uptime = public.uptime.as_secs();
+        let mut expected_shares = public.expected_shares;
+        if p2pool_difficulty != 0 {
+            let secs = uptime.saturating_sub(public.expected_shares_secs);
+            expected_shares += (user_hashrate as f64 * secs as f64) / p2pool_difficulty as f64;
+        }
+        let share_efficiency =
+            Self::calculate_share_efficiency(public.shares_found_u64, expected_shares);
         *public = Self {
+            expected_shares,
+            expected_shares_secs: uptime,
+            share_efficiency,
             p2pool_difficulty_u64: p2pool_difficulty,
             monero_difficulty_u64: monero_difficulty,
             p2pool_hashrate_u64: p2pool_hashrate,
@@ -2073,6 +3003,39 @@ impl PubP2poolApi {
         }
     }
 
+    // What a single share is worth on average: P2Pool pays out by work, so
+    // a share earns its slice of the sidechain's share of Monero's hashes.
+    pub fn calculate_reward_per_share(
+        reward: AtomicUnit,
+        p2pool_difficulty: u64,
+        monero_difficulty: u64,
+    ) -> AtomicUnit {
+        if monero_difficulty == 0 {
+            AtomicUnit::new()
+        } else {
+            AtomicUnit::from_u64(
+                (reward.to_u64() as u128 * p2pool_difficulty as u128 / monero_difficulty as u128)
+                    as u64,
+            )
+        }
+    }
+
+    // Shares found compared to shares expected, in percent.
+    // Unknown until at least 1 share is expected, anything less is just noise.
+    pub fn calculate_share_efficiency(found: u64, expected: f64) -> HumanNumber {
+        if expected < 1.0 {
+            HumanNumber::unknown()
+        } else {
+            HumanNumber::to_percent(((found as f64 / expected) * 100.0) as f32)
+        }
+    }
+
+    // Shares were found for a whole day but not a single payout line was matched,
+    // most likely P2Pool reworded its log, see [regex::P2POOL_LOG_FORMATS].
+    pub const fn payouts_missing(&self) -> bool {
+        self.payouts == 0 && self.shares_found_u64 >= 3 && self.expected_shares_secs >= 86_400
+    }
+
     #[inline]
     pub fn calculate_dominance(my_hashrate: u64, global_hashrate: u64) -> HumanNumber {
         if global_hashrate == 0 {
@@ -2083,6 +3046,82 @@ impl PubP2poolApi {
         }
     }
 
+    // The estimates in [Status/P2Pool] spelled out with the numbers that went in,
+    // [hashrate] is the user's (real or manually inputted).
+    pub fn formulas(&self, hashrate: u64) -> Formulas {
+        let time = |difficulty_name: &str, difficulty: u64, hashrate_name: &str, hashrate: u64| {
+            format!(
+                "[{}] / [{}]\n= {} / {} H/s\n= {}",
+                difficulty_name,
+                hashrate_name,
+                HumanNumber::from_u64(difficulty).localized(),
+                HumanNumber::from_u64(hashrate).localized(),
+                Self::calculate_share_or_block_time(hashrate, difficulty).localized(),
+            )
+        };
+        let dominance = |name: &str, hashrate: u64, global_name: &str, global_hashrate: u64| {
+            format!(
+                "[{}] / [{}] x 100\n= {} H/s / {} H/s x 100\n= {}",
+                name,
+                global_name,
+                HumanNumber::from_u64(hashrate).localized(),
+                HumanNumber::from_u64(global_hashrate).localized(),
+                Self::calculate_dominance(hashrate, global_hashrate).localized(),
+            )
+        };
+        Formulas {
+            p2pool_block_mean: time(
+                "Monero Difficulty",
+                self.monero_difficulty_u64,
+                "P2Pool Hashrate",
+                self.p2pool_hashrate_u64,
+            ),
+            p2pool_share_mean: time(
+                "P2Pool Difficulty",
+                self.p2pool_difficulty_u64,
+                "Your Hashrate",
+                hashrate,
+            ),
+            solo_block_mean: time(
+                "Monero Difficulty",
+                self.monero_difficulty_u64,
+                "Your Hashrate",
+                hashrate,
+            ),
+            p2pool_dominance: dominance(
+                "P2Pool Hashrate",
+                self.p2pool_hashrate_u64,
+                "Monero Hashrate",
+                self.monero_hashrate_u64,
+            ),
+            user_p2pool_dominance: dominance(
+                "Your Hashrate",
+                hashrate,
+                "P2Pool Hashrate",
+                self.p2pool_hashrate_u64,
+            ),
+            user_monero_dominance: dominance(
+                "Your Hashrate",
+                hashrate,
+                "Monero Hashrate",
+                self.monero_hashrate_u64,
+            ),
+            reward_per_share: format!(
+                "[Block Reward] x [P2Pool Difficulty] / [Monero Difficulty]\n= {} XMR x {} / {}\n= {} XMR",
+                self.reward.to_human_number_12_point().localized(),
+                HumanNumber::from_u64(self.p2pool_difficulty_u64).localized(),
+                HumanNumber::from_u64(self.monero_difficulty_u64).localized(),
+                Self::calculate_reward_per_share(
+                    self.reward,
+                    self.p2pool_difficulty_u64,
+                    self.monero_difficulty_u64
+                )
+                .to_human_number_12_point()
+                .localized(),
+            ),
+        }
+    }
+
     pub const fn calculate_tick_bar(&self) -> &'static str {
         // The stars are reduced by one because it takes a frame to render the stats.
         // We want 0 stars at the same time stats are rendered, so it looks a little off here.
@@ -2152,139 +3191,16 @@ impl PubP2poolApi {
     }
 }
 
-//---------------------------------------------------------------------------------------------------- Private P2Pool "Local" Api
-// This matches directly to P2Pool's [local/stratum] JSON API file (excluding a few stats).
-// P2Pool seems to initialize all stats at 0 (or 0.0), so no [Option] wrapper seems needed.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-struct PrivP2poolLocalApi {
-    hashrate_15m: u64,
-    hashrate_1h: u64,
-    hashrate_24h: u64,
-    shares_found: u64,
-    average_effort: f32,
-    current_effort: f32,
-    connections: u32, // This is a `uint32_t` in `p2pool`
-}
-
-impl Default for PrivP2poolLocalApi {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl PrivP2poolLocalApi {
-    fn new() -> Self {
-        Self {
-            hashrate_15m: 0,
-            hashrate_1h: 0,
-            hashrate_24h: 0,
-            shares_found: 0,
-            average_effort: 0.0,
-            current_effort: 0.0,
-            connections: 0,
-        }
-    }
-
-    // Deserialize the above [String] into a [PrivP2poolApi]
-    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
-        match serde_json::from_str::<Self>(string) {
-            Ok(a) => Ok(a),
-            Err(e) => {
-                warn!("P2Pool Local API | Could not deserialize API data: {}", e);
-                Err(e)
-            }
-        }
-    }
-}
-
-//---------------------------------------------------------------------------------------------------- Private P2Pool "Network" API
-// This matches P2Pool's [network/stats] JSON API file.
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct PrivP2poolNetworkApi {
-    difficulty: u64,
-    hash: String,
-    height: u32,
-    reward: u64,
-    timestamp: u32,
-}
-
-impl Default for PrivP2poolNetworkApi {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl PrivP2poolNetworkApi {
-    fn new() -> Self {
-        Self {
-            difficulty: 0,
-            hash: String::from("???"),
-            height: 0,
-            reward: 0,
-            timestamp: 0,
-        }
-    }
-
-    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
-        match serde_json::from_str::<Self>(string) {
-            Ok(a) => Ok(a),
-            Err(e) => {
-                warn!("P2Pool Network API | Could not deserialize API data: {}", e);
-                Err(e)
-            }
-        }
-    }
-}
-
-//---------------------------------------------------------------------------------------------------- Private P2Pool "Pool" API
-// This matches P2Pool's [pool/stats] JSON API file.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-struct PrivP2poolPoolApi {
-    pool_statistics: PoolStatistics,
-}
-
-impl Default for PrivP2poolPoolApi {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl PrivP2poolPoolApi {
-    fn new() -> Self {
-        Self {
-            pool_statistics: PoolStatistics::new(),
-        }
-    }
-
-    fn from_str(string: &str) -> std::result::Result<Self, serde_json::Error> {
-        match serde_json::from_str::<Self>(string) {
-            Ok(a) => Ok(a),
-            Err(e) => {
-                warn!("P2Pool Pool API | Could not deserialize API data: {}", e);
-                Err(e)
-            }
-        }
-    }
-}
-
-#[allow(non_snake_case)]
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-struct PoolStatistics {
-    hashRate: u64,
-    miners: u32,
-}
-impl Default for PoolStatistics {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-impl PoolStatistics {
-    fn new() -> Self {
-        Self {
-            hashRate: 0,
-            miners: 0,
-        }
-    }
+// See [PubP2poolApi::formulas()].
+#[derive(Debug, Clone)]
+pub struct Formulas {
+    pub p2pool_block_mean: String,
+    pub p2pool_share_mean: String,
+    pub solo_block_mean: String,
+    pub p2pool_dominance: String,
+    pub user_p2pool_dominance: String,
+    pub user_monero_dominance: String,
+    pub reward_per_share: String,
 }
 
 //---------------------------------------------------------------------------------------------------- [ImgXmrig]
@@ -2292,6 +3208,11 @@ impl PoolStatistics {
 pub struct ImgXmrig {
     pub threads: String,
     pub url: String,
+    pub settings: Vec<(&'static str, String)>, // [Xmrig::start_settings()] it started with
+    pub workdir: String, // The directory it runs in if overridden, see [Helper::workdir()]
+    // The detected XMRig binary version/features, this one
+    // is kept across restarts and only re-probed on path changes.
+    pub probe: Arc<Mutex<crate::probe::BinaryProbe>>,
 }
 
 impl Default for ImgXmrig {
@@ -2305,8 +3226,57 @@ impl ImgXmrig {
         Self {
             threads: "???".to_string(),
             url: "???".to_string(),
+            settings: Vec::new(),
+            workdir: String::new(),
+            probe: arc_mut!(crate::probe::BinaryProbe::new()),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [ExtraXmrig]
+// A running extra XMRig, see [disk::XmrigInstance]. The helper thread
+// moves its [pub_api] into [gui_api] every second, same as the main XMRig's.
+pub struct ExtraXmrig {
+    pub process: Arc<Mutex<Process>>,
+    pub gui_api: Arc<Mutex<PubXmrigApi>>,
+    pub_api: Arc<Mutex<PubXmrigApi>>,
+    pub threads: usize,
+}
+
+impl ExtraXmrig {
+    fn new(instance: &crate::disk::XmrigInstance) -> Self {
+        Self {
+            process: arc_mut!(Process::new(ProcessName::Xmrig)),
+            gui_api: arc_mut!(PubXmrigApi::new()),
+            pub_api: arc_mut!(PubXmrigApi::new()),
+            threads: instance.threads,
+        }
+    }
+}
+
+// A CPU list like [0-15,32-47] as the hex mask XMRig's [--cpu-affinity] takes.
+// [None] if it's empty or invalid, XMRig's mask only goes up to CPU 63.
+pub fn cpu_affinity_mask(list: &str) -> Option<String> {
+    let mut mask = 0_u64;
+    for item in list.split(',').map(str::trim) {
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (
+                start.trim().parse::<u32>().ok()?,
+                end.trim().parse::<u32>().ok()?,
+            ),
+            None => {
+                let cpu = item.parse::<u32>().ok()?;
+                (cpu, cpu)
+            }
+        };
+        if start > end || end > 63 {
+            return None;
+        }
+        for cpu in start..=end {
+            mask |= 1 << cpu;
         }
     }
+    Some(format!("0x{:X}", mask))
 }
 
 //---------------------------------------------------------------------------------------------------- Public XMRig API
@@ -2322,6 +3292,23 @@ pub struct PubXmrigApi {
     pub rejected: HumanNumber,
 
     pub hashrate_raw: f32,
+
+    // [XMRig details]
+    pub cpu: String,               // e.g: [AMD Ryzen 9 5950X (16C/32T, AES, AVX2)]
+    pub cache: String,             // e.g: [L2: 8 MB, L3: 64 MB]
+    pub msr: String,               // e.g: [ryzen_19h]
+    pub hugepages: String,         // e.g: [On (1168/1168)]
+    pub hugepages_ok: bool,        // Are [all] huge pages allocated?
+    pub memory: String,            // e.g: [XMRig: 2,400 MB | System: 10,000/32,000 MB]
+    pub threads_raw: Vec<f32>,     // Per-thread 10s hashrate
+    pub pages_1gb: String,         // [1GB PAGES] from XMRig's startup output, e.g: [Supported]
+    pub msr_mod: String,           // Did XMRig apply the MSR mod? e.g: [OK]
+    pub donate_level: Option<u32>, // XMRig's dev fee in %, [None] if it didn't report one
+    pub paused: bool,              // Paused with [p] (or the API), resumed with [r]
+
+    // Share acceptance latency, [share_ms] are the new ones since the last GUI update.
+    pub share_ms: Vec<u32>,
+    pub latency: crate::latency::ShareLatency,
 }
 
 impl Default for PubXmrigApi {
@@ -2342,6 +3329,95 @@ impl PubXmrigApi {
             accepted: HumanNumber::unknown(),
             rejected: HumanNumber::unknown(),
             hashrate_raw: 0.0,
+            cpu: "???".to_string(),
+            cache: "???".to_string(),
+            msr: "???".to_string(),
+            hugepages: "???".to_string(),
+            hugepages_ok: false,
+            memory: "???".to_string(),
+            threads_raw: vec![],
+            pages_1gb: "???".to_string(),
+            msr_mod: "???".to_string(),
+            donate_level: None,
+            paused: false,
+            share_ms: vec![],
+            latency: crate::latency::ShareLatency::new(),
+        }
+    }
+
+    // Heap + inline bytes of the parsed stats, the console [output] is counted on its own.
+    pub fn api_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + [
+                &self.worker_id,
+                &self.cpu,
+                &self.cache,
+                &self.msr,
+                &self.hugepages,
+                &self.memory,
+                &self.pages_1gb,
+                &self.msr_mod,
+            ]
+            .iter()
+            .map(|s| s.capacity())
+            .sum::<usize>()
+            + self.threads_raw.capacity() * std::mem::size_of::<f32>()
+            + self.share_ms.capacity() * std::mem::size_of::<u32>()
+            + self.latency.bytes()
+    }
+
+    // The 1GB pages/MSR mod results are only printed by XMRig on startup, not in the HTTP API.
+    fn update_from_startup_output(&mut self, output: &str) {
+        for line in output.lines() {
+            if let Some(i) = line.find("1GB PAGES") {
+                let mut result = line[i + 9..].trim().to_string();
+                if let Some(first) = result.get_mut(0..1) {
+                    first.make_ascii_uppercase();
+                }
+                self.pages_1gb = result;
+            } else if line.contains("failed to allocate RandomX dataset using 1GB pages") {
+                self.pages_1gb = "Failed, using normal pages".to_string();
+            } else if line.contains("msr") {
+                if line.contains("have been set successfully") {
+                    self.msr_mod = "OK".to_string();
+                } else if line.contains("FAILED TO APPLY MSR MOD") {
+                    self.msr_mod = "Failed".to_string();
+                }
+            }
+        }
+    }
+
+    // Hashrate/shares from the lines XMRig periodically prints, so there's
+    // still something to show with the HTTP API off (or not answering).
+    // If the API works, [update_from_priv()] overwrites these right after.
+    fn update_from_stdout_stats(&mut self, output: &str) {
+        if !output.contains("speed") && !output.contains("accepted") && !output.contains("rejected")
+        {
+            return;
+        }
+        let output = ANSI_ESCAPE.replace_all(output, "");
+        for line in output.lines() {
+            if let Some(c) = XMRIG_REGEX.speed.captures(line) {
+                let hashrate = [&c[1], &c[2], &c[3]].map(|h| h.parse::<f32>().ok());
+                self.hashrate_raw = hashrate[0].unwrap_or(0.0);
+                self.hashrate = HumanNumber::from_hashrate(hashrate);
+            } else if let Some(c) = XMRIG_REGEX.share.captures(line) {
+                let (Ok(accepted), Ok(rejected), Ok(diff)) =
+                    (c[1].parse(), c[2].parse(), c[3].parse())
+                else {
+                    continue;
+                };
+                self.accepted = HumanNumber::from_u128(accepted);
+                self.rejected = HumanNumber::from_u128(rejected);
+                self.diff = HumanNumber::from_u128(diff);
+                if let Some(ms) = XMRIG_REGEX
+                    .share_latency
+                    .captures(line)
+                    .and_then(|c| c[1].parse().ok())
+                {
+                    self.share_ms.push(ms);
+                }
+            }
         }
     }
 
@@ -2349,8 +3425,13 @@ impl PubXmrigApi {
     fn combine_gui_pub_api(gui_api: &mut Self, pub_api: &mut Self) {
         let output = std::mem::take(&mut gui_api.output);
         let buf = std::mem::take(&mut pub_api.output);
+        let mut latency = std::mem::take(&mut gui_api.latency);
+        for ms in pub_api.share_ms.drain(..) {
+            latency.push(ms);
+        }
         *gui_api = Self {
             output,
+            latency,
             ..std::mem::take(pub_api)
         };
         if !buf.is_empty() {
@@ -2379,13 +3460,13 @@ impl PubXmrigApi {
             public.uptime = HumanTime::into_human(elapsed);
         }
 
-        // 2. Check for "new job"/"no active...".
+        // 2. Check for "new job"/"no active...", 1GB pages and the MSR mod.
         let mut output_parse = lock!(output_parse);
-        if XMRIG_REGEX.new_job.is_match(&output_parse) {
-            lock!(process).state = ProcessState::Alive;
-        } else if XMRIG_REGEX.not_mining.is_match(&output_parse) {
-            lock!(process).state = ProcessState::NotMining;
-        }
+        lock!(process).update_state::<XmrigProcess>(&output_parse);
+        let mut public = lock!(public);
+        public.update_from_startup_output(&output_parse);
+        public.update_from_stdout_stats(&output_parse);
+        drop(public);
 
         // 3. Throw away [output_parse]
         output_parse.clear();
@@ -2400,7 +3481,52 @@ impl PubXmrigApi {
             _ => 0.0,
         };
 
+        let cpu = &private.cpu;
+        let mut features = vec![format!("{}C/{}T", cpu.cores, cpu.threads)];
+        if cpu.aes {
+            features.push("AES".to_string());
+        }
+        if cpu.avx2 {
+            features.push("AVX2".to_string());
+        }
+        let (hugepages, hugepages_ok) = match private.hugepages {
+            Hugepages::Bool(true) => ("On".to_string(), true),
+            Hugepages::Bool(false) => ("Off".to_string(), false),
+            Hugepages::Pages([0, _]) => ("Off".to_string(), false),
+            Hugepages::Pages([allocated, total]) => {
+                (format!("On ({}/{})", allocated, total), allocated >= total)
+            }
+        };
+        let memory = private.resources.memory;
+        let mb = |bytes: u64| HumanNumber::from_u64(bytes / 1_000_000).localized();
+        let threads_raw = private
+            .hashrate
+            .threads
+            .iter()
+            .map(|t| t[0].unwrap_or(0.0))
+            .collect();
+
         *public = Self {
+            cpu: format!("{} ({})", cpu.brand, features.join(", ")),
+            cache: format!(
+                "L2: {} MB, L3: {} MB",
+                cpu.l2 / 1_048_576,
+                cpu.l3 / 1_048_576
+            ),
+            msr: if cpu.msr.is_empty() {
+                "none".to_string()
+            } else {
+                cpu.msr.clone()
+            },
+            hugepages,
+            hugepages_ok,
+            memory: format!(
+                "XMRig: {} MB | System: {}/{} MB",
+                mb(memory.resident_set_memory),
+                mb(memory.total.saturating_sub(memory.free)),
+                mb(memory.total),
+            ),
+            threads_raw,
             worker_id: private.worker_id,
             resources: HumanNumber::from_load(private.resources.load_average),
             hashrate: HumanNumber::from_hashrate(private.hashrate.total),
@@ -2408,111 +3534,18 @@ impl PubXmrigApi {
             accepted: HumanNumber::from_u128(private.connection.accepted),
             rejected: HumanNumber::from_u128(private.connection.rejected),
             hashrate_raw,
+            donate_level: private.donate_level,
+            paused: private.paused,
             ..std::mem::take(&mut *public)
         }
     }
 }
 
-//---------------------------------------------------------------------------------------------------- Private XMRig API
-// This matches to some JSON stats in the HTTP call [summary],
-// e.g: [wget -qO- localhost:18085/1/summary].
-// XMRig doesn't initialize stats at 0 (or 0.0) and instead opts for [null]
-// which means some elements need to be wrapped in an [Option] or else serde will [panic!].
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct PrivXmrigApi {
-    worker_id: String,
-    resources: Resources,
-    connection: Connection,
-    hashrate: Hashrate,
-}
-
-impl PrivXmrigApi {
-    fn new() -> Self {
-        Self {
-            worker_id: String::new(),
-            resources: Resources::new(),
-            connection: Connection::new(),
-            hashrate: Hashrate::new(),
-        }
-    }
-
-    #[inline]
-    // Send an HTTP request to XMRig's API, serialize it into [Self] and return it
-    async fn request_xmrig_api(
-        client: hyper::Client<hyper::client::HttpConnector>,
-        api_uri: &str,
-    ) -> std::result::Result<Self, anyhow::Error> {
-        let request = hyper::Request::builder()
-            .method("GET")
-            .uri(api_uri)
-            .body(hyper::Body::empty())?;
-        let response = tokio::time::timeout(
-            std::time::Duration::from_millis(500),
-            client.request(request),
-        )
-        .await?;
-        let body = hyper::body::to_bytes(response?.body_mut()).await?;
-        Ok(serde_json::from_slice::<Self>(&body)?)
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-struct Resources {
-    load_average: [Option<f32>; 3],
-}
-impl Resources {
-    fn new() -> Self {
-        Self {
-            load_average: [Some(0.0), Some(0.0), Some(0.0)],
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct Connection {
-    diff: u128,
-    accepted: u128,
-    rejected: u128,
-}
-impl Connection {
-    fn new() -> Self {
-        Self {
-            diff: 0,
-            accepted: 0,
-            rejected: 0,
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-struct Hashrate {
-    total: [Option<f32>; 3],
-}
-impl Hashrate {
-    fn new() -> Self {
-        Self {
-            total: [Some(0.0), Some(0.0), Some(0.0)],
-        }
-    }
-}
-
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod test {
     use super::*;
 
-    #[test]
-    fn reset_gui_output() {
-        let max = crate::helper::GUI_OUTPUT_LEEWAY;
-        let mut string = String::with_capacity(max);
-        for _ in 0..=max {
-            string.push('0');
-        }
-        crate::Helper::check_reset_gui_output(&mut string, crate::ProcessName::P2pool);
-        // Some text gets added, so just check for less than 500 bytes.
-        assert!(string.len() < 500);
-    }
-
     #[test]
     fn combine_gui_pub_p2pool_api() {
         use crate::helper::PubP2poolApi;
@@ -2550,11 +3583,7 @@ mod test {
         )));
         let output_pub = Arc::new(Mutex::new(String::new()));
         let elapsed = std::time::Duration::from_secs(60);
-        let process = Arc::new(Mutex::new(Process::new(
-            ProcessName::P2pool,
-            "".to_string(),
-            PathBuf::new(),
-        )));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::P2pool)));
         PubP2poolApi::update_from_output(&public, &output_parse, &output_pub, elapsed, &process);
         let public = public.lock().unwrap();
         println!("{:#?}", public);
@@ -2580,11 +3609,7 @@ mod test {
         )));
         let output_pub = Arc::new(Mutex::new(String::new()));
         let elapsed = std::time::Duration::from_secs(60);
-        let process = Arc::new(Mutex::new(Process::new(
-            ProcessName::P2pool,
-            "".to_string(),
-            PathBuf::new(),
-        )));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::P2pool)));
 
         // It only gets checked if we're `Syncing`.
         process.lock().unwrap().state = ProcessState::Syncing;
@@ -2610,11 +3635,7 @@ mod test {
         )));
         let output_pub = Arc::new(Mutex::new(String::new()));
         let elapsed = std::time::Duration::from_secs(60);
-        let process = Arc::new(Mutex::new(Process::new(
-            ProcessName::P2pool,
-            "".to_string(),
-            PathBuf::new(),
-        )));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::P2pool)));
 
         // It only gets checked if we're `Syncing`.
         process.lock().unwrap().state = ProcessState::Syncing;
@@ -2642,11 +3663,7 @@ mod test {
         )));
         let output_pub = Arc::new(Mutex::new(String::new()));
         let elapsed = std::time::Duration::from_secs(60);
-        let process = Arc::new(Mutex::new(Process::new(
-            ProcessName::P2pool,
-            "".to_string(),
-            PathBuf::new(),
-        )));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::P2pool)));
 
         // It only gets checked if we're `Syncing`.
         process.lock().unwrap().state = ProcessState::Syncing;
@@ -2657,11 +3674,10 @@ mod test {
 
     #[test]
     fn update_pub_p2pool_from_local_network_pool() {
-        use crate::helper::PoolStatistics;
-        use crate::helper::PrivP2poolLocalApi;
-        use crate::helper::PrivP2poolNetworkApi;
-        use crate::helper::PrivP2poolPoolApi;
         use crate::helper::PubP2poolApi;
+        use gupax_process::{
+            PoolStatistics, PrivP2poolLocalApi, PrivP2poolNetworkApi, PrivP2poolPoolApi,
+        };
         use std::sync::{Arc, Mutex};
         let public = Arc::new(Mutex::new(PubP2poolApi::new()));
         let local = PrivP2poolLocalApi {
@@ -2693,6 +3709,10 @@ mod test {
         assert_eq!(p.hashrate_15m.to_string(), "10,000");
         assert_eq!(p.hashrate_1h.to_string(), "20,000");
         assert_eq!(p.hashrate_24h.to_string(), "30,000");
+        assert_eq!(
+            p.hashrate.to_string(),
+            "[10,000 H/s, 20,000 H/s, 30,000 H/s]"
+        );
         assert_eq!(p.shares_found.to_string(), "1,000");
         assert_eq!(p.average_effort.to_string(), "100.00%");
         assert_eq!(p.current_effort.to_string(), "200.00%");
@@ -2726,6 +3746,380 @@ mod test {
         drop(p);
     }
 
+    #[test]
+    fn demo_data_parses() {
+        use crate::helper::*;
+        for secs in [0, 1, 3, 600, 2400, 86400] {
+            let local = crate::demo::p2pool_local(secs)
+                .parse::<PrivP2poolLocalApi>()
+                .unwrap();
+            assert_eq!(local.shares_found, secs / 600);
+            crate::demo::p2pool_network(secs)
+                .parse::<PrivP2poolNetworkApi>()
+                .unwrap();
+            let xmrig =
+                serde_json::from_str::<PrivXmrigApi>(&crate::demo::xmrig_summary(secs)).unwrap();
+            assert_eq!(xmrig.hashrate.threads.len(), 8);
+        }
+        crate::demo::p2pool_pool()
+            .parse::<PrivP2poolPoolApi>()
+            .unwrap();
+        // The payout line has to match the real P2Pool format.
+        let payout = crate::demo::p2pool_output(2400);
+        assert_eq!(PubP2poolApi::calc_payouts_and_xmr(&payout).0, 1);
+        let synced = crate::demo::p2pool_output(3);
+        assert!(crate::regex::P2POOL_REGEX.synchronized.is_match(&synced));
+        assert!(crate::regex::XMRIG_REGEX
+            .new_job
+            .is_match(&crate::demo::xmrig_output(1)));
+    }
+
+    #[test]
+    fn detect_resume() {
+        use crate::helper::Resume;
+        use std::time::Duration;
+        let secs = Duration::from_secs;
+        // Normal loop.
+        assert_eq!(Resume::slept(secs(1), Duration::ZERO), None);
+        // A wall clock step isn't suspend time, so it can't get here.
+        // Linux/macOS, [Instant] stopped while suspended.
+        assert_eq!(
+            Resume::slept(secs(1), secs(3600)),
+            Some((secs(3600), Duration::ZERO))
+        );
+        // Windows, [Instant] kept counting.
+        assert_eq!(
+            Resume::slept(secs(3601), secs(3600)),
+            Some((secs(3600), secs(3600)))
+        );
+        // Whatever this machine says, it isn't asleep right now.
+        let mut resume = Resume::new();
+        assert_eq!(resume.check(), None);
+        // The OS clocks are readable where there's an implementation (CI runs all 3).
+        if cfg!(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "windows"
+        )) {
+            assert!(Resume::suspended().is_some());
+        }
+    }
+
+    #[test]
+    fn detect_network_change() {
+        use crate::helper::NetworkWatch;
+        let a = Some(std::net::IpAddr::from([192, 168, 1, 2]));
+        let b = Some(std::net::IpAddr::from([10, 8, 0, 2]));
+        assert!(!NetworkWatch::changed(a, a));
+        assert!(NetworkWatch::changed(a, b));
+        assert!(NetworkWatch::changed(None, a));
+        assert!(!NetworkWatch::changed(a, None));
+    }
+
+    #[test]
+    fn load_guard() {
+        use crate::helper::LoadGuard;
+        let mut guard = LoadGuard::new(0, 2);
+        assert_eq!(guard.check(100.0), None);
+
+        let mut guard = LoadGuard::new(80, 2);
+        assert_eq!(guard.check(90.0), None);
+        assert_eq!(guard.check(10.0), None); // Spike, ignored
+        assert_eq!(guard.check(90.0), None);
+        assert_eq!(guard.check(90.0), Some(true));
+        assert_eq!(guard.check(90.0), None);
+        assert_eq!(guard.check(50.0), None);
+        assert_eq!(guard.check(50.0), Some(false));
+        assert_eq!(guard.check(50.0), None);
+
+        guard.check(90.0);
+        guard.check(90.0);
+        guard.reset();
+        assert!(!guard.paused);
+        assert_eq!(guard.check(50.0), None);
+    }
+
+    #[test]
+    fn event_watch_transitions() {
+        use crate::event::Kind;
+        use crate::helper::{EventWatch, ProcessState::*};
+        let kind = |old, new| EventWatch::transition("P2Pool", old, new, "").map(|(k, _)| k);
+        assert_eq!(kind(Dead, Middle), None);
+        assert_eq!(kind(Middle, Syncing), Some(Kind::Start));
+        assert_eq!(kind(Syncing, Alive), Some(Kind::Notice));
+        assert_eq!(kind(Alive, Alive), None);
+        // Restart.
+        assert_eq!(kind(Alive, Middle), None);
+        assert_eq!(kind(Middle, Waiting), None);
+        assert_eq!(kind(Waiting, Alive), Some(Kind::Start));
+        assert_eq!(kind(Alive, Failed), Some(Kind::Crash));
+        assert_eq!(kind(Middle, Dead), Some(Kind::Stop));
+        assert_eq!(
+            EventWatch::transition("XMRig", Alive, NotMining, ""),
+            Some((Kind::Notice, "XMRig lost its pool connection".to_string()))
+        );
+        assert_eq!(
+            EventWatch::transition(
+                "XMRig",
+                Alive,
+                Failed,
+                "Killed by the OOM killer (out of memory)"
+            ),
+            Some((
+                Kind::Crash,
+                "XMRig died: Killed by the OOM killer (out of memory)".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn p2pool_restart_nudges_xmrig() {
+        use crate::helper::{P2poolRestart, ProcessState::*};
+        let mut watch = P2poolRestart::new();
+        // First start isn't a restart.
+        assert!(!watch.check(Syncing, NotMining));
+        assert!(!watch.check(Alive, NotMining));
+        // Restart with XMRig stuck.
+        assert!(!watch.check(Middle, Alive));
+        assert!(!watch.check(Syncing, NotMining));
+        assert!(watch.check(Alive, NotMining));
+        assert!(!watch.check(Alive, NotMining));
+        // Restart with XMRig that reconnected on its own.
+        assert!(!watch.check(Dead, NotMining));
+        assert!(!watch.check(Alive, Alive));
+        assert!(!watch.check(Alive, NotMining));
+    }
+
+    #[test]
+    fn xmrig_watch_uri() {
+        use crate::helper::Helper;
+        assert_eq!(
+            Helper::xmrig_watch_uri("127.0.0.1:18088"),
+            "http://127.0.0.1:18088/1/summary"
+        );
+        assert_eq!(
+            Helper::xmrig_watch_uri(" localhost:18088/ "),
+            "http://127.0.0.1:18088/1/summary"
+        );
+        assert_eq!(
+            Helper::xmrig_watch_uri("http://rig.lan:8080"),
+            "http://rig.lan:8080/1/summary"
+        );
+        assert_eq!(
+            Helper::xmrig_watch_uri("https://rigs.example.com/rig1/"),
+            "https://rigs.example.com/rig1/1/summary"
+        );
+    }
+
+    #[test]
+    fn cpu_affinity_mask() {
+        use crate::helper::cpu_affinity_mask;
+        assert_eq!(cpu_affinity_mask("0,1").as_deref(), Some("0x3"));
+        assert_eq!(cpu_affinity_mask("0-3, 8").as_deref(), Some("0x10F"));
+        assert_eq!(
+            cpu_affinity_mask("32-63").as_deref(),
+            Some("0xFFFFFFFF00000000")
+        );
+        assert_eq!(cpu_affinity_mask(""), None);
+        assert_eq!(cpu_affinity_mask("3-1"), None);
+        assert_eq!(cpu_affinity_mask("0-64"), None);
+        assert_eq!(cpu_affinity_mask("zero"), None);
+    }
+
+    #[test]
+    fn xmrig_instance_args() {
+        use crate::disk::XmrigInstance;
+        use crate::helper::Helper;
+        let main: Vec<String> = [
+            "--user",
+            "4abc",
+            "--threads",
+            "16",
+            "--rig-id",
+            "rig",
+            "--url",
+            "pool:3333",
+            "--http-host",
+            "127.0.0.1",
+            "--http-port",
+            "18088",
+            "--http-no-restricted",
+            "--cpu-affinity",
+            "0xFFFF",
+        ]
+        .map(String::from)
+        .to_vec();
+        let instance = XmrigInstance {
+            threads: 8,
+            affinity: "16-23".to_string(),
+            api_port: "18089".to_string(),
+        };
+        let (args, api) = Helper::xmrig_instance_args(&main, 1, &instance, "127.0.0.1");
+        assert_eq!(
+            args.join(" "),
+            "--user 4abc --threads 8 --rig-id rig_1 --url pool:3333 --http-no-restricted \
+             --cpu-affinity 0xFF0000 --http-host 127.0.0.1 --http-port 18089"
+        );
+        assert_eq!(api, "127.0.0.1:18089");
+
+        // No API port, no API.
+        let instance = XmrigInstance {
+            threads: 8,
+            affinity: String::new(),
+            api_port: String::new(),
+        };
+        let (args, api) = Helper::xmrig_instance_args(&main, 2, &instance, "127.0.0.1");
+        assert_eq!(
+            args.join(" "),
+            "--user 4abc --threads 8 --rig-id rig_2 --url pool:3333"
+        );
+        assert!(api.is_empty());
+    }
+
+    #[test]
+    fn workdir() {
+        use crate::helper::Helper;
+        use std::path::{Path, PathBuf};
+        let path = Path::new("/opt/p2pool/p2pool");
+        assert_eq!(Helper::workdir("", path), PathBuf::from("/opt/p2pool"));
+        assert_eq!(Helper::workdir("  ", path), PathBuf::from("/opt/p2pool"));
+        assert_eq!(
+            Helper::workdir("data", path),
+            PathBuf::from("/opt/p2pool/data")
+        );
+        assert_eq!(
+            Helper::workdir(" /mnt/ssd ", path),
+            PathBuf::from("/mnt/ssd")
+        );
+        let dir = std::env::temp_dir();
+        assert_eq!(
+            Helper::workdir_or_default(ProcessName::P2pool, "gupax_workdir_missing", path),
+            PathBuf::from("/opt/p2pool")
+        );
+        assert_eq!(
+            Helper::workdir_or_default(ProcessName::P2pool, &dir.display().to_string(), path),
+            dir
+        );
+        assert_eq!(Helper::img_workdir("", &dir), "");
+        assert_eq!(Helper::img_workdir("x", &dir), dir.display().to_string());
+    }
+
+    #[test]
+    fn p2pool_api_dir() {
+        use crate::helper::Helper;
+        use std::path::{Path, PathBuf};
+        let mut state = crate::disk::P2pool::default();
+        let path = Path::new("/opt/p2pool/p2pool");
+        assert_eq!(
+            Helper::p2pool_api_dir(&state, path),
+            PathBuf::from("/opt/p2pool")
+        );
+        state.api_dir = "instance_2".to_string();
+        assert_eq!(
+            Helper::p2pool_api_dir(&state, path),
+            PathBuf::from("/opt/p2pool/instance_2")
+        );
+        state.api_dir = "/tmp/p2pool_api ".to_string();
+        assert_eq!(
+            Helper::p2pool_api_dir(&state, path),
+            PathBuf::from("/tmp/p2pool_api")
+        );
+    }
+
+    #[test]
+    fn rotate_log() {
+        use crate::helper::Helper;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let log = dir.join("p2pool.log");
+        assert!(!Helper::rotate_log(&log, 10).unwrap());
+        std::fs::write(&log, "0123456789").unwrap();
+        assert!(!Helper::rotate_log(&log, 10).unwrap());
+        std::fs::write(&log, "0123456789ABC").unwrap();
+        assert!(Helper::rotate_log(&log, 10).unwrap());
+        assert!(!log.exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("p2pool.log.old")).unwrap(),
+            "0123456789ABC"
+        );
+    }
+
+    #[test]
+    fn reward_per_share() {
+        use crate::helper::PubP2poolApi;
+        use crate::xmr::AtomicUnit;
+        let r = PubP2poolApi::calculate_reward_per_share;
+        let reward = AtomicUnit::from_u64(600_000_000_000);
+        // 1/1000th of the work of a block, 1/1000th of the reward.
+        assert_eq!(
+            r(reward, 300_000_000, 300_000_000_000).to_u64(),
+            600_000_000
+        );
+        assert_eq!(r(reward, 300_000_000, 0).to_u64(), 0);
+        // Real world sized numbers don't overflow.
+        assert_eq!(
+            r(reward, 3_000_000_000, 300_000_000_000).to_u64(),
+            6_000_000_000
+        );
+    }
+
+    #[test]
+    fn formulas() {
+        use crate::helper::PubP2poolApi;
+        let api = PubP2poolApi {
+            monero_difficulty_u64: 300_000_000_000,
+            monero_hashrate_u64: 2_500_000_000,
+            p2pool_difficulty_u64: 100_000_000,
+            p2pool_hashrate_u64: 10_000_000,
+            ..PubP2poolApi::new()
+        };
+        let f = api.formulas(10_000);
+        assert_eq!(
+            f.p2pool_share_mean,
+            "[P2Pool Difficulty] / [Your Hashrate]\n= 100,000,000 / 10,000 H/s\n= 2 hours, 46 minutes, 40 seconds"
+        );
+        assert_eq!(
+            f.p2pool_block_mean,
+            "[Monero Difficulty] / [P2Pool Hashrate]\n= 300,000,000,000 / 10,000,000 H/s\n= 8 hours, 20 minutes"
+        );
+        assert!(f.user_p2pool_dominance.starts_with(
+            "[Your Hashrate] / [P2Pool Hashrate] x 100\n= 10,000 H/s / 10,000,000 H/s x 100\n= 0.1"
+        ));
+        // No hashrate yet, no division by 0.
+        assert!(api.formulas(0).solo_block_mean.contains("/ 0 H/s"));
+    }
+
+    #[test]
+    fn share_efficiency() {
+        use crate::helper::PubP2poolApi;
+        use crate::human::HumanTime;
+        let e = PubP2poolApi::calculate_share_efficiency;
+        assert_eq!(e(5, 0.5).to_string(), "???");
+        assert_eq!(e(3, 4.0).to_string(), "75.00%");
+        assert_eq!(e(10, 8.0).to_string(), "125.00%");
+
+        // 1 hour at 1 KH/s with a 360,000 difficulty = 10 expected shares.
+        let mut p = PubP2poolApi::new();
+        p.uptime = HumanTime::from_u64(3600);
+        p.user_p2pool_hashrate_u64 = 1_000;
+        p.shares_found_u64 = 9;
+        let public = std::sync::Arc::new(std::sync::Mutex::new(p));
+        let network = gupax_process::PrivP2poolNetworkApi::new();
+        let pool = gupax_process::PrivP2poolPoolApi {
+            pool_statistics: gupax_process::PoolStatistics {
+                hashRate: 36_000, // 360,000 difficulty
+                miners: 1,
+            },
+        };
+        PubP2poolApi::update_from_network_pool(&public, network, pool);
+        let p = public.lock().unwrap();
+        assert_eq!(p.expected_shares, 10.0);
+        assert!(!p.payouts_missing());
+        assert_eq!(p.expected_shares_secs, 3600);
+        assert_eq!(p.share_efficiency.to_string(), "90.00%");
+    }
+
     #[test]
     fn set_xmrig_mining() {
         use crate::helper::PubXmrigApi;
@@ -2736,11 +4130,7 @@ mod test {
         )));
         let output_pub = Arc::new(Mutex::new(String::new()));
         let elapsed = std::time::Duration::from_secs(60);
-        let process = Arc::new(Mutex::new(Process::new(
-            ProcessName::Xmrig,
-            "".to_string(),
-            PathBuf::new(),
-        )));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::Xmrig)));
 
         process.lock().unwrap().state = ProcessState::Alive;
         PubXmrigApi::update_from_output(&public, &output_parse, &output_pub, elapsed, &process);
@@ -2753,189 +4143,308 @@ mod test {
     }
 
     #[test]
-    fn serde_priv_p2pool_local_api() {
-        let data = r#"{
-				"hashrate_15m": 12,
-				"hashrate_1h": 11111,
-				"hashrate_24h": 468967,
-				"total_hashes": 2019283840922394082390,
-				"shares_found": 289037,
-				"average_effort": 915.563,
-				"current_effort": 129.297,
-				"connections": 123,
-				"incoming_connections": 96
-			}"#;
-        let priv_api = crate::helper::PrivP2poolLocalApi::from_str(data).unwrap();
-        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
-        println!("{}", json);
-        let data_after_ser = r#"{
-  "hashrate_15m": 12,
-  "hashrate_1h": 11111,
-  "hashrate_24h": 468967,
-  "shares_found": 289037,
-  "average_effort": 915.563,
-  "current_effort": 129.297,
-  "connections": 123
-}"#;
-        assert_eq!(data_after_ser, json)
+    fn xmrig_startup_pages_msr() {
+        use crate::helper::PubXmrigApi;
+        let mut public = PubXmrigApi::new();
+        public.update_from_startup_output(
+            " * HUGE PAGES   supported\n * 1GB PAGES    unavailable\n * CPU          AMD Ryzen 9 5950X\n",
+        );
+        assert_eq!(public.pages_1gb, "Unavailable");
+        assert_eq!(public.msr_mod, "???");
+        public.update_from_startup_output(
+            "[2024-01-01 00:00:00.000]  msr      register values for \"ryzen_19h\" preset have been set successfully (1 ms)\n",
+        );
+        assert_eq!(public.msr_mod, "OK");
+        public.update_from_startup_output(
+            "[2024-01-01 00:00:00.000]  msr      FAILED TO APPLY MSR MOD, HASHRATE WILL BE LOW\n[2024-01-01 00:00:00.000]  randomx  failed to allocate RandomX dataset using 1GB pages\n",
+        );
+        assert_eq!(public.msr_mod, "Failed");
+        assert_eq!(public.pages_1gb, "Failed, using normal pages");
     }
 
     #[test]
-    fn serde_priv_p2pool_network_api() {
-        let data = r#"{
-				"difficulty": 319028180924,
-				"hash": "22ae1b83d727bb2ff4efc17b485bc47bc8bf5e29a7b3af65baf42213ac70a39b",
-				"height": 2776576,
-				"reward": 600499860000,
-				"timestamp": 1670953659
-			}"#;
-        let priv_api = crate::helper::PrivP2poolNetworkApi::from_str(data).unwrap();
-        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
-        println!("{}", json);
-        let data_after_ser = r#"{
-  "difficulty": 319028180924,
-  "hash": "22ae1b83d727bb2ff4efc17b485bc47bc8bf5e29a7b3af65baf42213ac70a39b",
-  "height": 2776576,
-  "reward": 600499860000,
-  "timestamp": 1670953659
-}"#;
-        assert_eq!(data_after_ser, json)
+    fn p2pool_zmq_health() {
+        use crate::helper::{PubP2poolApi, ZmqHealth};
+        let ready = "NOTICE  2024-01-01 00:00:00.0000 ZMQReader worker thread ready\n";
+        let miner = "NOTICE  2024-01-01 00:00:00.0000 P2Pool new miner data\n";
+        let fail = "WARNING 2024-01-01 00:00:00.0000 P2Pool No ZMQ messages received from monerod in the last 30 seconds, check your monerod/p2pool/network/firewall setup!!!\n";
+        assert_eq!(ZmqHealth::from_output("Hashrate = 512.0 H/s\n"), None);
+        assert_eq!(ZmqHealth::from_output(ready).unwrap().0, ZmqHealth::Ok);
+        // The last ZMQ line wins.
+        let (health, line) = ZmqHealth::from_output(&format!("{ready}{fail}")).unwrap();
+        assert_eq!(health, ZmqHealth::Failing);
+        assert!(line.starts_with("WARNING"));
+        assert_eq!(
+            ZmqHealth::from_output(&format!("{fail}{miner}")).unwrap().0,
+            ZmqHealth::Ok
+        );
+        assert_eq!(
+            ZmqHealth::from_output("ZMQReader exception: connection refused\n")
+                .unwrap()
+                .0,
+            ZmqHealth::Failing
+        );
+
+        let mut p = PubP2poolApi::new();
+        p.update_zmq(ZmqHealth::from_output(miner), 10);
+        assert_eq!((p.zmq, p.zmq_secs), (ZmqHealth::Ok, 10));
+        p.update_zmq(None, 10 + ZmqHealth::STALE_SECS);
+        assert_eq!(p.zmq, ZmqHealth::Ok);
+        p.update_zmq(None, 11 + ZmqHealth::STALE_SECS);
+        assert_eq!(p.zmq, ZmqHealth::Stale);
+        p.update_zmq(ZmqHealth::from_output(miner), 700);
+        assert_eq!(p.zmq, ZmqHealth::Ok);
     }
 
     #[test]
-    fn serde_priv_p2pool_pool_api() {
-        let data = r#"{
-				"pool_list": ["pplns"],
-				"pool_statistics": {
-					"hashRate": 10225772,
-					"miners": 713,
-					"totalHashes": 487463929193948,
-					"lastBlockFoundTime": 1670453228,
-					"lastBlockFound": 2756570,
-					"totalBlocksFound": 4
-				}
-			}"#;
-        let priv_api = crate::helper::PrivP2poolPoolApi::from_str(data).unwrap();
-        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
-        println!("{}", json);
-        let data_after_ser = r#"{
-  "pool_statistics": {
-    "hashRate": 10225772,
-    "miners": 713
-  }
-}"#;
-        assert_eq!(data_after_ser, json)
+    fn p2pool_builtin_miner() {
+        use crate::helper::PubP2poolApi;
+        let status = "NOTICE  2024-01-01 00:00:00.0000 StratumServer status\n\
+            Hashrate (15m est) = 9.999 KH/s\n\
+            NOTICE  2024-01-01 00:00:00.0000 Miner status\n\
+            Threads         = 4\n\
+            Hashrate        = \x1b[0;36m4.123 KH/s\x1b[0m\n\
+            Total hashes    = 123456\n";
+        assert_eq!(PubP2poolApi::calc_miner_hashrate(status), Some(4123.0));
+        assert_eq!(
+            PubP2poolApi::calc_miner_hashrate("Hashrate (1h  est) = 1.000 MH/s\n"),
+            None
+        );
+        assert_eq!(
+            PubP2poolApi::calc_miner_hashrate("Hashrate = 512.0 H/s"),
+            Some(512.0)
+        );
+        assert_eq!(PubP2poolApi::miner_command("start_mining 8"), Some(8));
+        assert_eq!(PubP2poolApi::miner_command("start_mining"), Some(1));
+        assert_eq!(PubP2poolApi::miner_command("stop_mining"), Some(0));
+        assert_eq!(PubP2poolApi::miner_command("status"), None);
+    }
+
+    #[test]
+    fn xmrig_stdout_stats() {
+        use crate::helper::PubXmrigApi;
+        let mut public = PubXmrigApi::new();
+        public.update_from_stdout_stats(
+            "[2024-01-01 00:00:00.000]  \x1b[1;37mminer\x1b[0m    speed 10s/60s/15m \x1b[1;36m4102.6\x1b[0m \x1b[0;36mn/a\x1b[0m n/a H/s max 4120.1 H/s\n\
+             [2024-01-01 00:00:01.000]  cpu      accepted (12/1) diff 120001 (45 ms)\n",
+        );
+        assert_eq!(public.hashrate_raw, 4102.6);
+        assert_eq!(public.hashrate.to_string(), "[4,102 H/s, ??? H/s, ??? H/s]");
+        assert_eq!(public.accepted.to_string(), "12");
+        assert_eq!(public.rejected.to_string(), "1");
+        assert_eq!(public.diff.to_string(), "120,001");
+        assert_eq!(public.share_ms, [45]);
+        // Anything else leaves the stats alone.
+        public.update_from_stdout_stats("net      new job from 127.0.0.1:3333 diff 120001\n");
+        assert_eq!(public.accepted.to_string(), "12");
+        // The GUI keeps the latency history, [share_ms] only carries the new ones.
+        let mut gui = PubXmrigApi::new();
+        PubXmrigApi::combine_gui_pub_api(&mut gui, &mut public);
+        assert_eq!(gui.latency.current(), Some([45, 45]));
+        assert!(gui.share_ms.is_empty() && public.share_ms.is_empty());
     }
 
     #[test]
-    fn serde_priv_xmrig_api() {
+    fn update_pub_xmrig_details() {
+        // Older XMRig, no [cpu], [hugepages], [memory] or per-thread hashrate.
+        let data = r#"{
+		    "worker_id": "hinto",
+		    "resources": { "load_average": [1.0, 1.0, 1.0] },
+		    "connection": { "diff": 1, "accepted": 1, "rejected": 0 },
+		    "hashrate": { "total": [1000.0, null, null] }
+		}"#;
+        let priv_api = serde_json::from_str::<PrivXmrigApi>(data).unwrap();
+        let public = arc_mut!(PubXmrigApi::new());
+        PubXmrigApi::update_from_priv(&public, priv_api);
+        let p = lock!(public);
+        assert_eq!(p.hugepages, "Off");
+        assert!(!p.hugepages_ok);
+        assert_eq!(p.msr, "none");
+        assert!(p.threads_raw.is_empty());
+        assert_eq!(p.donate_level, None);
+        assert!(!p.paused);
+        drop(p);
+
+        // Newer XMRig, [hugepages] is [allocated, total].
         let data = r#"{
-		    "id": "6226e3sd0cd1a6es",
 		    "worker_id": "hinto",
-		    "uptime": 123,
-		    "restricted": true,
 		    "resources": {
-		        "memory": {
-		            "free": 123,
-		            "total": 123123,
-		            "resident_set_memory": 123123123
-		        },
-		        "load_average": [10.97, 10.58, 10.47],
-		        "hardware_concurrency": 12
-		    },
-		    "features": ["api", "asm", "http", "hwloc", "tls", "opencl", "cuda"],
-		    "results": {
-		        "diff_current": 123,
-		        "shares_good": 123,
-		        "shares_total": 123,
-		        "avg_time": 123,
-		        "avg_time_ms": 123,
-		        "hashes_total": 123,
-		        "best": [123, 123, 123, 13, 123, 123, 123, 123, 123, 123],
-		        "error_log": []
+		        "load_average": [1.0, 1.0, 1.0],
+		        "memory": { "free": 2000000000, "total": 8000000000, "resident_set_memory": 2400000000 }
 		    },
-		    "algo": "rx/0",
-		    "connection": {
-		        "pool": "localhost:3333",
-		        "ip": "127.0.0.1",
-		        "uptime": 123,
-		        "uptime_ms": 123,
-		        "ping": 0,
-		        "failures": 0,
-		        "tls": null,
-		        "tls-fingerprint": null,
-		        "algo": "rx/0",
-		        "diff": 123,
-		        "accepted": 123,
-		        "rejected": 123,
-		        "avg_time": 123,
-		        "avg_time_ms": 123,
-		        "hashes_total": 123,
-		        "error_log": []
-		    },
-		    "version": "6.18.0",
-		    "kind": "miner",
-		    "ua": "XMRig/6.18.0 (Linux x86_64) libuv/2.0.0-dev gcc/10.2.1",
-		    "cpu": {
-		        "brand": "blah blah blah",
-		        "family": 1,
-		        "model": 2,
-		        "stepping": 0,
-		        "proc_info": 123,
-		        "aes": true,
-		        "avx2": true,
-		        "x64": true,
-		        "64_bit": true,
-		        "l2": 123123,
-		        "l3": 123123,
-		        "cores": 12,
-		        "threads": 24,
-		        "packages": 1,
-		        "nodes": 1,
-		        "backend": "hwloc/2.8.0a1-git",
-		        "msr": "ryzen_19h",
-		        "assembly": "ryzen",
-		        "arch": "x86_64",
-		        "flags": ["aes", "vaes", "avx", "avx2", "bmi2", "osxsave", "pdpe1gb", "sse2", "ssse3", "sse4.1", "popcnt", "cat_l3"]
-		    },
-		    "donate_level": 0,
-		    "paused": false,
-		    "algorithms": ["cn/1", "cn/2", "cn/r", "cn/fast", "cn/half", "cn/xao", "cn/rto", "cn/rwz", "cn/zls", "cn/double", "cn/ccx", "cn-lite/1", "cn-heavy/0", "cn-heavy/tube", "cn-heavy/xhv", "cn-pico", "cn-pico/tlo", "cn/upx2", "rx/0", "rx/wow", "rx/arq", "rx/graft", "rx/sfx", "rx/keva", "argon2/chukwa", "argon2/chukwav2", "argon2/ninja", "astrobwt", "astrobwt/v2", "ghostrider"],
-		    "hashrate": {
-		        "total": [111.11, 111.11, 111.11],
-		        "highest": 111.11,
-		        "threads": [
-		            [111.11, 111.11, 111.11]
-		        ]
-		    },
-		    "hugepages": true
+		    "connection": { "diff": 1, "accepted": 1, "rejected": 0 },
+		    "hashrate": { "total": [2000.0, null, null], "threads": [[1000.0, null, null], [null, null, null]] },
+		    "cpu": { "brand": "CPU", "aes": true, "avx2": false, "l2": 8388608, "l3": 67108864, "cores": 16, "threads": 32, "msr": "ryzen_19h" },
+		    "hugepages": [1168, 1168],
+		    "donate_level": 1,
+		    "paused": true
 		}"#;
-        use crate::helper::PrivXmrigApi;
         let priv_api = serde_json::from_str::<PrivXmrigApi>(data).unwrap();
-        let json = serde_json::ser::to_string_pretty(&priv_api).unwrap();
-        println!("{}", json);
-        let data_after_ser = r#"{
-  "worker_id": "hinto",
-  "resources": {
-    "load_average": [
-      10.97,
-      10.58,
-      10.47
-    ]
-  },
-  "connection": {
-    "diff": 123,
-    "accepted": 123,
-    "rejected": 123
-  },
-  "hashrate": {
-    "total": [
-      111.11,
-      111.11,
-      111.11
-    ]
-  }
-}"#;
-        assert_eq!(data_after_ser, json)
+        PubXmrigApi::update_from_priv(&public, priv_api);
+        let p = lock!(public);
+        assert_eq!(p.cpu, "CPU (16C/32T, AES)");
+        assert_eq!(p.cache, "L2: 8 MB, L3: 64 MB");
+        assert_eq!(p.msr, "ryzen_19h");
+        assert_eq!(p.hugepages, "On (1168/1168)");
+        assert!(p.hugepages_ok);
+        assert_eq!(p.memory, "XMRig: 2,400 MB | System: 6,000/8,000 MB");
+        assert_eq!(p.threads_raw, vec![1000.0, 0.0]);
+        assert_eq!(p.donate_level, Some(1));
+        assert!(p.paused);
+    }
+
+    //---------------------------------------------------------------------------------------------------- Watchdog tests
+    // These drive the real watchdogs end to end, P2Pool with the
+    // stub binary in [tests/stub], XMRig (watch-only) with a fake HTTP API.
+    // XMRig itself isn't spawned since it goes through [sudo] on Unix.
+
+    fn test_helper(dir: &std::path::Path) -> Arc<Mutex<Helper>> {
+        let mut gupax_p2pool_api = GupaxP2poolApi::new();
+        let api_dir = dir.join("gupax_p2pool_api");
+        std::fs::create_dir_all(&api_dir).unwrap();
+        GupaxP2poolApi::fill_paths(&mut gupax_p2pool_api, &api_dir);
+        GupaxP2poolApi::create_all_files(&api_dir).unwrap();
+        arc_mut!(Helper::new(
+            Instant::now(),
+            arc_mut!(Sys::new()),
+            arc_mut!(Process::new(ProcessName::P2pool)),
+            arc_mut!(Process::new(ProcessName::Xmrig)),
+            arc_mut!(Process::new(ProcessName::Proxy)),
+            arc_mut!(PubP2poolApi::new()),
+            arc_mut!(PubXmrigApi::new()),
+            arc_mut!(PubProxyApi::new()),
+            arc_mut!(ImgP2pool::new()),
+            arc_mut!(ImgXmrig::new()),
+            arc_mut!(Vec::new()),
+            arc_mut!(gupax_p2pool_api),
+        ))
+    }
+
+    // Polls [f] for up to 20 seconds.
+    fn wait_for(what: &str, f: impl Fn() -> bool) {
+        let now = Instant::now();
+        while !f() {
+            assert!(
+                now.elapsed() < Duration::from_secs(20),
+                "timed out waiting for: {}",
+                what
+            );
+            sleep!(100);
+        }
+    }
+
+    #[test]
+    fn stub_p2pool_lifecycle() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let stub = if cfg!(windows) {
+            "p2pool.bat"
+        } else {
+            "p2pool"
+        };
+        let path = dir.join(stub);
+        std::fs::copy(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("stub")
+                .join(stub),
+            &path,
+        )
+        .unwrap();
+        let helper = test_helper(&dir);
+        let process = Arc::clone(&lock!(helper).p2pool);
+        let state = crate::disk::P2pool {
+            simple: false,
+            ..Default::default()
+        };
+
+        // Start -> Syncing -> Alive.
+        Helper::start_p2pool(&helper, &state, &path, None);
+        wait_for("P2Pool to sync", || {
+            lock!(process).state == ProcessState::Alive
+        });
+        assert!(lock!(process).is_alive());
+
+        // Local API + output, then merged into the GUI side like the helper thread does.
+        let pub_api = Arc::clone(&lock!(helper).pub_api_p2pool);
+        wait_for("P2Pool local API", || lock!(pub_api).shares_found_u64 == 3);
+        wait_for("P2Pool payout in output", || lock!(pub_api).payouts == 1);
+        let gui_api = Arc::clone(&lock!(helper).gui_api_p2pool);
+        PubP2poolApi::combine_gui_pub_api(&mut lock!(gui_api), &mut lock!(pub_api));
+        let gui = lock!(gui_api).clone();
+        assert_eq!(gui.user_p2pool_hashrate_u64, 11000);
+        assert_eq!(gui.payouts, 1);
+        assert!(gui.output.contains("SideChain SYNCHRONIZED"));
+
+        // The payout made it to disk.
+        let gupax_p2pool_api = Arc::clone(&lock!(helper).gupax_p2pool_api);
+        wait_for("P2Pool payout on disk", || {
+            lock!(gupax_p2pool_api).payout_u64 == 1
+        });
+        assert_eq!(lock!(gupax_p2pool_api).xmr.to_u64(), 411111111);
+        let log = std::fs::read_to_string(&lock!(gupax_p2pool_api).path_log).unwrap();
+        assert!(log.contains("0.000411111111 XMR"));
+
+        // Stop -> Dead.
+        Helper::stop_p2pool(&helper);
+        wait_for("P2Pool to stop", || {
+            matches!(
+                lock!(process).state,
+                ProcessState::Dead | ProcessState::Failed
+            )
+        });
+        // The stub is killed, so [Failed] is fine, it just can't be alive anymore.
+        assert!(!lock!(process).is_alive());
+    }
+
+    #[test]
+    fn stub_xmrig_watch_lifecycle() {
+        use std::io::{Read, Write};
+        // Fake XMRig HTTP API, answers every request with the same summary.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buf = [0; 1024];
+                let _ = stream.read(&mut buf);
+                let body = crate::demo::xmrig_summary(60);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let helper = test_helper(&dir);
+        let process = Arc::clone(&lock!(helper).xmrig);
+        let state = crate::disk::Xmrig {
+            simple: false,
+            watch_only: true,
+            watch_url: addr.to_string(),
+            ..Default::default()
+        };
+
+        // Start -> Alive.
+        Helper::start_xmrig(&helper, &state, &PathBuf::new(), arc_mut!(SudoState::new()));
+        wait_for("XMRig API", || lock!(process).state == ProcessState::Alive);
+        let pub_api = Arc::clone(&lock!(helper).pub_api_xmrig);
+        wait_for("XMRig hashrate", || lock!(pub_api).hashrate_raw > 0.0);
+        let gui_api = Arc::clone(&lock!(helper).gui_api_xmrig);
+        PubXmrigApi::combine_gui_pub_api(&mut lock!(gui_api), &mut lock!(pub_api));
+        let gui = lock!(gui_api).clone();
+        assert_eq!(gui.worker_id, "gupax-demo");
+        assert_eq!(gui.threads_raw.len(), 8);
+        assert!(gui.hugepages_ok);
+
+        // Stop -> Dead.
+        Helper::stop_xmrig(&helper);
+        wait_for("XMRig to stop", || {
+            lock!(process).state == ProcessState::Dead
+        });
     }
 }
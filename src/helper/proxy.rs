@@ -0,0 +1,656 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// XMRig-Proxy, it's run like P2Pool (no [sudo]) but polled like XMRig.
+//
+// The rest of [helper.rs] only sees [PubProxyApi] and the [Helper::*_proxy()]
+// functions, the watchdog itself is [gupax_process::watch()] with [ProxyWatchdog].
+
+//---------------------------------------------------------------------------------------------------- Use
+use super::{Helper, NetworkWatch};
+use crate::regex::XMRIG_REGEX;
+use crate::{constants::*, human::*, macros::*};
+use gupax_process::{
+    HttpJson, ManagedProcess, Process, ProcessName, ProcessSignal, ProcessState, Pty, StatsSource,
+    Watchdog,
+};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+//---------------------------------------------------------------------------------------------------- [ManagedProcess]
+pub struct ProxyProcess;
+
+// XMRig-Proxy logs its upstream's jobs like XMRig does, it's
+// also set back to [NotMining] if its API says no upstream is up.
+impl ManagedProcess for ProxyProcess {
+    const NAME: ProcessName = ProcessName::Proxy;
+    const START_STATE: ProcessState = ProcessState::NotMining;
+
+    fn state_from_output(output: &str, _: ProcessState) -> Option<ProcessState> {
+        XMRIG_REGEX
+            .new_job
+            .is_match(output)
+            .then_some(ProcessState::Alive)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Watchdog]
+// What XMRig-Proxy does every loop of [gupax_process::watch()].
+struct ProxyWatchdog {
+    gui_api: Arc<Mutex<PubProxyApi>>,
+    pub_api: Arc<Mutex<PubProxyApi>>,
+    output_parse: Arc<Mutex<String>>,
+    output_pub: Arc<Mutex<String>>,
+    // [summary] & [workers], [None] if the HTTP API is off.
+    api: Option<(HttpJson<PrivProxyApi>, HttpJson<PrivProxyWorkers>)>,
+}
+
+impl Watchdog for ProxyWatchdog {
+    type Managed = ProxyProcess;
+
+    fn tick(&mut self, process: &Arc<Mutex<Process>>, elapsed: Duration) {
+        // Check if logs need resetting
+        Helper::check_reset_gui_output(&mut lock!(self.gui_api).output, ProcessName::Proxy);
+
+        // Always update from output
+        PubProxyApi::update_from_output(
+            &self.pub_api,
+            &self.output_parse,
+            &self.output_pub,
+            elapsed,
+            process,
+        );
+
+        // Send the HTTP API requests
+        let Some((summary, workers)) = &mut self.api else {
+            return;
+        };
+        match summary.fetch() {
+            Ok(priv_api) => PubProxyApi::update_from_priv(&self.pub_api, process, priv_api),
+            Err(e) => warn!("XMRig-Proxy Watchdog | [{}] ... FAIL: {}", summary.uri(), e),
+        }
+        match workers.fetch() {
+            Ok(priv_workers) => PubProxyApi::update_from_workers(&self.pub_api, priv_workers),
+            Err(e) => warn!("XMRig-Proxy Watchdog | [{}] ... FAIL: {}", workers.uri(), e),
+        }
+    }
+
+    fn stopped(&mut self, uptime: Duration, exit_status: &str, hint: Option<&str>) {
+        let output = &mut lock!(self.gui_api).output;
+        Helper::write_stopped(output, ProcessName::Proxy, uptime, exit_status, hint);
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Helper]
+impl Helper {
+    #[cold]
+    #[inline(never)]
+    // Just sets some signals for the watchdog thread to pick up on.
+    pub fn stop_proxy(helper: &Arc<Mutex<Self>>) {
+        info!("XMRig-Proxy | Attempting to stop...");
+        lock2!(helper, proxy).signal = ProcessSignal::Stop;
+        lock2!(helper, proxy).state = ProcessState::Middle;
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Kill the current XMRig-Proxy, wait for it in a new thread, then start a new one.
+    pub fn restart_proxy(helper: &Arc<Mutex<Self>>, state: &crate::disk::Proxy) {
+        let process = Arc::clone(&lock!(helper).proxy);
+        let helper = Arc::clone(helper);
+        let state = state.clone();
+        gupax_process::restart(&process, move || Self::start_proxy(&helper, &state));
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn start_proxy(helper: &Arc<Mutex<Self>>, state: &crate::disk::Proxy) {
+        lock2!(helper, proxy).state = ProcessState::Middle;
+
+        if crate::demo::enabled() {
+            Self::start_demo(helper, ProcessName::Proxy);
+            return;
+        }
+
+        let path = state.absolute_path();
+        let (args, api_ip_port) = Self::build_proxy_args(state);
+        let cwd = Self::workdir_or_default(ProcessName::Proxy, &state.workdir, &path);
+        let connect = Self::proxy_connect_to(state);
+        crate::disk::print_dash(&format!("XMRig-Proxy | Launch arguments: {:#?}", args));
+        info!("XMRig-Proxy | Using path: [{}]", path.display());
+
+        // Spawn watchdog thread
+        let process = Arc::clone(&lock!(helper).proxy);
+        let gui_api = Arc::clone(&lock!(helper).gui_api_proxy);
+        let pub_api = Arc::clone(&lock!(helper).pub_api_proxy);
+        let env = state.env.clone();
+        thread::spawn(move || {
+            Self::spawn_proxy_watchdog(
+                process,
+                gui_api,
+                pub_api,
+                args,
+                env,
+                path,
+                cwd,
+                api_ip_port,
+                connect,
+            );
+        });
+    }
+
+    // Takes in some [State/Proxy] and builds the actual command arguments.
+    // Returns the arguments and the HTTP API [IP:Port], empty if there's none.
+    pub fn build_proxy_args(state: &crate::disk::Proxy) -> (Vec<String>, String) {
+        let mut args = Vec::with_capacity(32);
+        let localhost = |ip: &str| {
+            if ip == "localhost" || ip.is_empty() {
+                "127.0.0.1".to_string()
+            } else {
+                ip.to_string()
+            }
+        };
+        // Overriding command arguments
+        if !state.arguments.is_empty() {
+            let mut last = "";
+            let mut api_ip = "127.0.0.1".to_string();
+            let mut api_port = String::new();
+            for arg in state.arguments.split_whitespace() {
+                match last {
+                    "--http-host" => api_ip = localhost(arg),
+                    "--http-port" => api_port = arg.to_string(),
+                    _ => (),
+                }
+                args.push(if arg == "localhost" {
+                    "127.0.0.1".to_string()
+                } else {
+                    arg.to_string()
+                });
+                last = arg;
+            }
+            if api_port.is_empty() {
+                return (args, String::new());
+            }
+            return (args, format!("{}:{}", api_ip, api_port));
+        }
+
+        let bind_ip = match state.bind_ip.trim() {
+            "" => "0.0.0.0".to_string(),
+            ip => localhost(ip),
+        };
+        let api_ip = localhost(state.api_ip.trim());
+        let api_port = match state.api_port.trim() {
+            "" => "18087",
+            port => port,
+        };
+        args.push("--url".to_string());
+        args.push(format!(
+            "{}:{}",
+            localhost(state.ip.trim()),
+            state.port.trim()
+        )); // Upstream
+        if !state.address.trim().is_empty() {
+            args.push("--user".to_string());
+            args.push(state.address.trim().to_string()); // Wallet
+        }
+        args.push("--bind".to_string());
+        args.push(format!("{}:{}", bind_ip, state.bind_port.trim())); // Rigs connect here
+        args.push("--mode".to_string());
+        args.push(state.mode.arg().to_string()); // NiceHash/Simple
+        args.push("--http-host".to_string());
+        args.push(api_ip.clone()); // HTTP API IP
+        args.push("--http-port".to_string());
+        args.push(api_port.to_string()); // HTTP API Port
+        if state.tls {
+            args.push("--tls".to_string());
+        } // TLS (upstream)
+        if state.keepalive {
+            args.push("--keepalive".to_string());
+        } // Keepalive
+        (args, format!("{}:{}", api_ip, api_port))
+    }
+
+    // The [IP:Port] rigs should point at, this machine's LAN address if bound to all of them.
+    fn proxy_connect_to(state: &crate::disk::Proxy) -> String {
+        let port = state.bind_port.trim();
+        match state.bind_ip.trim() {
+            "0.0.0.0" | "" => match NetworkWatch::default_route() {
+                Some(ip) => format!("{}:{}", ip, port),
+                None => format!("0.0.0.0:{}", port),
+            },
+            "localhost" => format!("127.0.0.1:{}", port),
+            ip => format!("{}:{}", ip, port),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[expect(clippy::too_many_arguments)]
+    // The XMRig-Proxy watchdog. Same as XMRig's, minus [sudo]: the console from
+    // the PTY, and the HTTP API ([summary] + [workers]) polled every loop.
+    fn spawn_proxy_watchdog(
+        process: Arc<Mutex<Process>>,
+        gui_api: Arc<Mutex<PubProxyApi>>,
+        pub_api: Arc<Mutex<PubProxyApi>>,
+        args: Vec<String>,
+        env: Vec<crate::disk::EnvVar>,
+        path: std::path::PathBuf,
+        cwd: std::path::PathBuf,
+        api_ip_port: String,
+        connect: String,
+    ) {
+        // 1. Create command
+        debug!("XMRig-Proxy | Creating command...");
+        let mut cmd = portable_pty::CommandBuilder::new(path.as_path());
+        cmd.args(&args);
+        cmd.cwd(cwd);
+        crate::envvar::apply(&mut cmd, &env);
+        // 2. Create child
+        debug!("XMRig-Proxy | Creating child...");
+        let pty = match Pty::spawn(cmd, &path) {
+            Ok(pty) => pty,
+            Err(e) => {
+                Self::spawn_failed(
+                    ProcessName::Proxy,
+                    &process,
+                    &mut lock!(gui_api).output,
+                    &path,
+                    &e,
+                );
+                return;
+            }
+        };
+
+        let api = if api_ip_port.is_empty() {
+            info!("XMRig-Proxy | HTTP API is off, only reading STDOUT");
+            None
+        } else {
+            let api_uri = format!("http://{}/", api_ip_port.trim_end_matches('/'));
+            info!("XMRig-Proxy | Final API URI: {}", api_uri);
+            Some((
+                HttpJson::new(format!("{}{}", api_uri, XMRIG_API_URI)),
+                HttpJson::new(format!("{}{}", api_uri, PROXY_API_WORKERS_URI)),
+            ))
+        };
+
+        // Reset stats before loop
+        *lock!(pub_api) = PubProxyApi::new();
+        *lock!(gui_api) = PubProxyApi::new();
+        lock!(pub_api).connect = connect;
+        let output_parse = Arc::clone(&lock!(process).output_parse);
+        let output_pub = Arc::clone(&lock!(process).output_pub);
+        let watchdog = ProxyWatchdog {
+            gui_api,
+            pub_api,
+            output_parse,
+            output_pub,
+            api,
+        };
+
+        // 3. Loop as watchdog
+        gupax_process::watch(&process, pty, args, |_| {}, watchdog);
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Public XMRig-Proxy API
+#[derive(Debug, Clone)]
+pub struct PubProxyApi {
+    pub output: String,
+    pub uptime: HumanTime,
+    pub connect: String, // The [IP:Port] rigs should point at, e.g: [192.168.1.2:3355]
+    pub mode: String,
+    pub hashrate: HumanNumber,
+    pub miners: String,    // e.g: [3 (max 4)]
+    pub upstreams: String, // e.g: [1/1 active]
+    pub accepted: HumanNumber,
+    pub rejected: HumanNumber,
+    pub invalid: HumanNumber,
+    pub expired: HumanNumber,
+    pub latency: String, // e.g: [52ms (avg 2s per share)]
+    pub workers: Vec<ProxyWorker>,
+}
+
+// One rig connected to the proxy, from the [workers] HTTP call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyWorker {
+    pub name: String,
+    pub ip: String,
+    pub connections: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub invalid: u64,
+    pub hashrate: HumanNumber, // [1m, 10m, 1h]
+    pub idle: bool,            // No connections left, but XMRig-Proxy still remembers it
+}
+
+impl Default for PubProxyApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubProxyApi {
+    pub fn new() -> Self {
+        Self {
+            output: String::new(),
+            uptime: HumanTime::new(),
+            connect: "???".to_string(),
+            mode: "???".to_string(),
+            hashrate: HumanNumber::unknown(),
+            miners: "???".to_string(),
+            upstreams: "???".to_string(),
+            accepted: HumanNumber::unknown(),
+            rejected: HumanNumber::unknown(),
+            invalid: HumanNumber::unknown(),
+            expired: HumanNumber::unknown(),
+            latency: "???".to_string(),
+            workers: vec![],
+        }
+    }
+
+    #[inline]
+    pub(super) fn combine_gui_pub_api(gui_api: &mut Self, pub_api: &mut Self) {
+        let output = std::mem::take(&mut gui_api.output);
+        let buf = std::mem::take(&mut pub_api.output);
+        *gui_api = Self {
+            output,
+            ..pub_api.clone()
+        };
+        if !buf.is_empty() {
+            gui_api.output.push_str(&buf);
+        }
+    }
+
+    // Same as [PubXmrigApi::update_from_output()], minus the XMRig-only startup lines.
+    fn update_from_output(
+        public: &Arc<Mutex<Self>>,
+        output_parse: &Arc<Mutex<String>>,
+        output_pub: &Arc<Mutex<String>>,
+        elapsed: std::time::Duration,
+        process: &Arc<Mutex<Process>>,
+    ) {
+        let mut output_pub = lock!(output_pub);
+        {
+            let mut public = lock!(public);
+            if !output_pub.is_empty() {
+                public.output.push_str(&std::mem::take(&mut *output_pub));
+            }
+            public.uptime = HumanTime::into_human(elapsed);
+        }
+        let mut output_parse = lock!(output_parse);
+        lock!(process).update_state::<ProxyProcess>(&output_parse);
+        output_parse.clear();
+    }
+
+    // Formats raw private data into ready-to-print human readable version,
+    // and flips the process between [Alive/NotMining] depending on the upstreams.
+    fn update_from_priv(
+        public: &Arc<Mutex<Self>>,
+        process: &Arc<Mutex<Process>>,
+        private: PrivProxyApi,
+    ) {
+        let mut lock = lock!(process);
+        match lock.state {
+            ProcessState::Alive if private.upstreams.active == 0 => {
+                lock.state = ProcessState::NotMining
+            }
+            ProcessState::NotMining if private.upstreams.active > 0 => {
+                lock.state = ProcessState::Alive
+            }
+            _ => (),
+        }
+        drop(lock);
+
+        let results = &private.results;
+        let mut public = lock!(public);
+        *public = Self {
+            mode: private.mode,
+            hashrate: HumanNumber::from_hashrate(PrivProxyApi::khs_to_hs(&private.hashrate.total)),
+            miners: format!("{} (max {})", private.miners.now, private.miners.max),
+            upstreams: format!(
+                "{}/{} active, {} sleeping, {} error",
+                private.upstreams.active,
+                private.upstreams.total,
+                private.upstreams.sleep,
+                private.upstreams.error,
+            ),
+            accepted: HumanNumber::from_u64(results.accepted),
+            rejected: HumanNumber::from_u64(results.rejected),
+            invalid: HumanNumber::from_u64(results.invalid),
+            expired: HumanNumber::from_u64(results.expired),
+            latency: format!(
+                "{}ms (avg {}s per share)",
+                results.latency, results.avg_time
+            ),
+            ..std::mem::take(&mut *public)
+        };
+    }
+
+    fn update_from_workers(public: &Arc<Mutex<Self>>, private: PrivProxyWorkers) {
+        lock!(public).workers = private
+            .workers
+            .into_iter()
+            .map(|w| ProxyWorker {
+                hashrate: HumanNumber::from_hashrate(PrivProxyApi::khs_to_hs(&[
+                    w.hashrate_1m,
+                    w.hashrate_10m,
+                    w.hashrate_1h,
+                ])),
+                idle: w.connections == 0,
+                name: w.name,
+                ip: w.ip,
+                connections: w.connections,
+                accepted: w.accepted,
+                rejected: w.rejected,
+                invalid: w.invalid,
+            })
+            .collect();
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Private XMRig-Proxy API
+// The [summary] HTTP call, e.g: [wget -qO- localhost:18087/1/summary].
+// Unlike XMRig, hashrates are in kH/s and there's no [null]s, so
+// everything just defaults in case a field is missing in some version.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct PrivProxyApi {
+    mode: String,
+    hashrate: ProxyHashrate,
+    miners: ProxyMiners,
+    upstreams: ProxyUpstreams,
+    results: ProxyResults,
+}
+
+impl PrivProxyApi {
+    // [1m, 10m, 1h] in H/s, from XMRig-Proxy's kH/s.
+    fn khs_to_hs(total: &[f64]) -> [Option<f32>; 3] {
+        let mut hashrate = [None; 3];
+        for (h, khs) in hashrate.iter_mut().zip(total) {
+            *h = Some((khs * 1000.0) as f32);
+        }
+        hashrate
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct ProxyHashrate {
+    total: Vec<f64>, // [1m, 10m, 1h, 12h, 24h, all time]
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+struct ProxyMiners {
+    now: u64,
+    max: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+struct ProxyUpstreams {
+    active: u64,
+    sleep: u64,
+    error: u64,
+    total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+struct ProxyResults {
+    accepted: u64,
+    rejected: u64,
+    invalid: u64,
+    expired: u64,
+    avg_time: u64,
+    latency: u64,
+}
+
+// The [workers] HTTP call, each worker is an array of:
+// [name, ip, connections, accepted, rejected, invalid, hashes, last_hash_ms, 1m, 10m, 1h, 12h, 24h]
+// which serde reads into [ProxyWorkerRow] in the same order.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct PrivProxyWorkers {
+    workers: Vec<ProxyWorkerRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct ProxyWorkerRow {
+    name: String,
+    ip: String,
+    connections: u64,
+    accepted: u64,
+    rejected: u64,
+    invalid: u64,
+    hashes: u64,
+    last_hash_ms: u64,
+    // kH/s
+    hashrate_1m: f64,
+    hashrate_10m: f64,
+    hashrate_1h: f64,
+    hashrate_12h: f64,
+    hashrate_24h: f64,
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serde_priv_proxy_api() {
+        let summary = r#"{
+		    "id": "a1b2c3d4e5f6a7b8",
+		    "worker_id": "farm",
+		    "uptime": 300,
+		    "kind": "proxy",
+		    "mode": "nicehash",
+		    "hashrate": {"total": [12.5, 12.0, 11.5, 0.0, 0.0, 10.0]},
+		    "miners": {"now": 3, "max": 4},
+		    "workers": 3,
+		    "upstreams": {"active": 0, "sleep": 0, "error": 1, "total": 1, "ratio": 0.0},
+		    "results": {
+		        "accepted": 10, "rejected": 1, "invalid": 0, "expired": 2,
+		        "avg_time": 30, "avg_time_ms": 30000, "latency": 52,
+		        "hashes_total": 123, "best": [1, 2]
+		    }
+		}"#;
+        let workers = r#"{
+		    "mode": "rig_id",
+		    "workers": [
+		        ["rig1", "192.168.1.10", 1, 5, 0, 0, 1000, 1700000000000, 4.5, 4.2, 4.0, 0.0, 0.0],
+		        ["rig2", "192.168.1.11", 0, 2, 1, 0, 500, 1700000000000, 0.0, 1.0, 2.0, 0.0, 0.0]
+		    ]
+		}"#;
+        let private: PrivProxyApi = serde_json::from_str(summary).unwrap();
+        assert_eq!(private.hashrate.total.len(), 6);
+        assert_eq!(
+            PrivProxyApi::khs_to_hs(&private.hashrate.total),
+            [Some(12500.0), Some(12000.0), Some(11500.0)]
+        );
+
+        // No upstream is up: [Alive] -> [NotMining], and back.
+        let public = Arc::new(Mutex::new(PubProxyApi::new()));
+        let process = Arc::new(Mutex::new(Process::new(ProcessName::Proxy)));
+        process.lock().unwrap().state = ProcessState::Alive;
+        PubProxyApi::update_from_priv(&public, &process, private.clone());
+        assert_eq!(process.lock().unwrap().state, ProcessState::NotMining);
+        let lock = public.lock().unwrap();
+        assert_eq!(lock.mode, "nicehash");
+        assert_eq!(lock.miners, "3 (max 4)");
+        assert_eq!(lock.upstreams, "0/1 active, 0 sleeping, 1 error");
+        assert_eq!(lock.latency, "52ms (avg 30s per share)");
+        drop(lock);
+        let mut private = private;
+        private.upstreams.active = 1;
+        PubProxyApi::update_from_priv(&public, &process, private);
+        assert_eq!(process.lock().unwrap().state, ProcessState::Alive);
+
+        let private: PrivProxyWorkers = serde_json::from_str(workers).unwrap();
+        PubProxyApi::update_from_workers(&public, private);
+        let lock = public.lock().unwrap();
+        assert_eq!(lock.workers.len(), 2);
+        assert_eq!(lock.workers[0].name, "rig1");
+        assert_eq!(lock.workers[0].ip, "192.168.1.10");
+        assert_eq!(lock.workers[0].accepted, 5);
+        assert!(!lock.workers[0].idle);
+        assert!(lock.workers[1].idle);
+    }
+
+    #[test]
+    fn build_proxy_args() {
+        let mut state = crate::disk::Proxy {
+            address: "4abc".to_string(),
+            keepalive: true,
+            ..Default::default()
+        };
+        let (args, api) = Helper::build_proxy_args(&state);
+        assert_eq!(
+            args,
+            [
+                "--url",
+                "127.0.0.1:3333",
+                "--user",
+                "4abc",
+                "--bind",
+                "0.0.0.0:3355",
+                "--mode",
+                "nicehash",
+                "--http-host",
+                "127.0.0.1",
+                "--http-port",
+                "18087",
+                "--keepalive",
+            ]
+        );
+        assert_eq!(api, "127.0.0.1:18087");
+
+        // Overriding arguments, the API is only polled if they set a port.
+        state.arguments = "-o pool:3333 --http-host localhost --http-port 9999".to_string();
+        let (args, api) = Helper::build_proxy_args(&state);
+        assert_eq!(args[3], "127.0.0.1");
+        assert_eq!(api, "127.0.0.1:9999");
+        state.arguments = "-o pool:3333".to_string();
+        assert_eq!(Helper::build_proxy_args(&state).1, "");
+    }
+}
@@ -0,0 +1,205 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Scriptable hooks on process events.
+//
+// Instead of building every integration (smart plugs, chat notifications,
+// custom logging) into Gupax, a script/executable can be set per event in
+// [Gupax Advanced]. When the event happens, the helper runs it with the event
+// in [GUPAX_*] environment variables and as 1 line of JSON on STDIN:
+//
+//     GUPAX_EVENT=payout GUPAX_PROCESS=P2Pool GUPAX_XMR=0.000411111111 GUPAX_BLOCK=3100000
+//     {"event":"payout","time":"2024-05-01T03:12:45Z","message":"...","process":"P2Pool","xmr":"0.000411111111","block":"3100000"}
+//
+// Like [event.rs] this is a global, set by the GUI thread when the settings are saved.
+
+use crate::event::Kind;
+use crate::macros::*;
+use chrono::{DateTime, Utc};
+use log::*;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// The events that can have a hook, in the order of [set()].
+pub const KINDS: [Kind; 4] = [Kind::Start, Kind::Stop, Kind::Crash, Kind::Payout];
+// A hook still running after this is killed.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+static PATHS: Mutex<[String; 4]> =
+    Mutex::new([String::new(), String::new(), String::new(), String::new()]);
+
+//---------------------------------------------------------------------------------------------------- Config
+// Set the hook paths for [KINDS], empty means no hook.
+pub fn set(paths: [&str; 4]) {
+    let mut lock = lock!(PATHS);
+    for (old, new) in lock.iter_mut().zip(paths) {
+        let new = crate::disk::clean_path(new);
+        if *old != new {
+            *old = new;
+        }
+    }
+}
+
+fn path(kind: Kind) -> Option<String> {
+    let i = KINDS.iter().position(|k| *k == kind)?;
+    let path = lock!(PATHS)[i].clone();
+    (!path.is_empty()).then_some(path)
+}
+
+//---------------------------------------------------------------------------------------------------- Payload
+// The environment variables and JSON line a hook gets.
+fn payload(
+    kind: Kind,
+    time: DateTime<Utc>,
+    msg: &str,
+    data: &[(&str, String)],
+) -> (Vec<(String, String)>, String) {
+    let event = kind.to_string().to_lowercase();
+    let time = time.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let mut env = vec![
+        ("GUPAX_EVENT".to_string(), event.clone()),
+        ("GUPAX_EVENT_TIME".to_string(), time.clone()),
+        ("GUPAX_EVENT_MESSAGE".to_string(), msg.to_string()),
+    ];
+    let mut json = serde_json::Map::new();
+    json.insert("event".into(), event.into());
+    json.insert("time".into(), time.into());
+    json.insert("message".into(), msg.into());
+    for (key, value) in data {
+        env.push((format!("GUPAX_{}", key.to_uppercase()), value.clone()));
+        json.insert(key.to_string(), value.clone().into());
+    }
+    (env, serde_json::Value::Object(json).to_string())
+}
+
+//---------------------------------------------------------------------------------------------------- Run
+// Run [kind]'s hook in its own thread, if there is one.
+pub fn fire(kind: Kind, msg: &str, data: &[(&str, String)]) {
+    let Some(path) = path(kind) else {
+        return;
+    };
+    let (env, json) = payload(kind, Utc::now(), msg, data);
+    std::thread::spawn(move || {
+        if let Err(e) = run(&path, &env, &json) {
+            warn!("Hook | [{}] failed: {}", path, e);
+            crate::event::push(Kind::Notice, format!("Hook [{}] failed: {}", path, e));
+        }
+    });
+}
+
+fn run(path: &str, env: &[(String, String)], json: &str) -> std::io::Result<()> {
+    info!("Hook | Running [{}]", path);
+    let mut command = Command::new(path);
+    command
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // [CREATE_NO_WINDOW], don't flash a console window.
+        command.creation_flags(0x08000000);
+    }
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that only reads the environment may exit before reading this.
+        let _ = writeln!(stdin, "{}", json);
+    }
+
+    let now = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                info!("Hook | [{}] ... OK", path);
+                return Ok(());
+            }
+            return Err(std::io::Error::other(status.to_string()));
+        }
+        if now.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::other(format!(
+                "still running after {}s, killed",
+                TIMEOUT.as_secs()
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn hook_payload() {
+        use crate::event::Kind;
+        use crate::hook::payload;
+        use chrono::TimeZone;
+        let time = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 3, 12, 45).unwrap();
+        let (env, json) = payload(
+            Kind::Payout,
+            time,
+            "0.000411111111 XMR in block 3100000",
+            &[
+                ("process", "P2Pool".to_string()),
+                ("xmr", "0.000411111111".to_string()),
+                ("block", "3100000".to_string()),
+            ],
+        );
+        let get = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+        assert_eq!(get("GUPAX_EVENT"), Some("payout"));
+        assert_eq!(get("GUPAX_EVENT_TIME"), Some("2024-05-01T03:12:45Z"));
+        assert_eq!(get("GUPAX_PROCESS"), Some("P2Pool"));
+        assert_eq!(get("GUPAX_BLOCK"), Some("3100000"));
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["event"], "payout");
+        assert_eq!(json["message"], "0.000411111111 XMR in block 3100000");
+        assert_eq!(json["xmr"], "0.000411111111");
+        assert!(!json.to_string().contains('\n'));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn hook_run() {
+        use crate::hook::run;
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let out = dir.join("out");
+        let script = dir.join("hook.sh");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nread line\necho \"$GUPAX_EVENT $line\" > '{}'\n",
+                out.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let env = [("GUPAX_EVENT".to_string(), "start".to_string())];
+        run(script.to_str().unwrap(), &env, "{\"event\":\"start\"}").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "start {\"event\":\"start\"}\n"
+        );
+        assert!(run(dir.join("missing").to_str().unwrap(), &env, "{}").is_err());
+    }
+}
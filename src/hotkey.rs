@@ -0,0 +1,170 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// OS-global hotkeys, these work even when Gupax is unfocused/minimized.
+//
+// The [GlobalHotKeyManager] must be created on the main thread
+// (the one running the event loop) on Windows/macOS, so this
+// gets created once in [App::cc()] and lives inside [App].
+//
+// Hotkey events come from a background thread, so the event handler
+// just forwards them into a channel and asks egui to repaint, the
+// actual [HotkeyAction]'s are handled in the next [update()].
+
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use log::*;
+use std::sync::mpsc::{channel, Receiver};
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const DEFAULT_HOTKEY_XMRIG: &str = "Ctrl+Shift+F9";
+pub const DEFAULT_HOTKEY_WINDOW: &str = "Ctrl+Shift+F10";
+
+//---------------------------------------------------------------------------------------------------- [HotkeyAction]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HotkeyAction {
+    Xmrig,  // Pause/resume XMRig
+    Window, // Show/hide the Gupax window
+}
+
+// Returns [Ok] if [s] is a valid hotkey, e.g: [Ctrl+Shift+F9]
+pub fn parse(s: &str) -> Result<HotKey, String> {
+    s.parse::<HotKey>().map_err(|e| e.to_string())
+}
+
+//---------------------------------------------------------------------------------------------------- [Hotkeys]
+pub struct Hotkeys {
+    manager: GlobalHotKeyManager,
+    recv: Receiver<GlobalHotKeyEvent>,
+    // The currently registered hotkeys.
+    xmrig: Option<HotKey>,
+    window: Option<HotKey>,
+    // What the user settings looked like on the last [sync()],
+    // so we only (un)register when something actually changes.
+    last: Option<(bool, String, String)>,
+    // The last registration error, shown in the [Gupax] tab.
+    pub error: String,
+}
+
+impl Hotkeys {
+    #[cold]
+    #[inline(never)]
+    pub fn new(ctx: &egui::Context) -> Option<Self> {
+        let manager = match GlobalHotKeyManager::new() {
+            Ok(m) => m,
+            Err(e) => {
+                warn!("Hotkeys | Could not initialize global hotkeys: {}", e);
+                return None;
+            }
+        };
+        let (send, recv) = channel();
+        let ctx = ctx.clone();
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            if send.send(event).is_ok() {
+                ctx.request_repaint();
+            }
+        }));
+        info!("Hotkeys | Init ... OK");
+        Some(Self {
+            manager,
+            recv,
+            xmrig: None,
+            window: None,
+            last: None,
+            error: String::new(),
+        })
+    }
+
+    // (Re)register the hotkeys if the user settings changed.
+    pub fn sync(&mut self, enabled: bool, xmrig: &str, window: &str) {
+        let settings = (enabled, xmrig.to_string(), window.to_string());
+        if self.last.as_ref() == Some(&settings) {
+            return;
+        }
+        self.last = Some(settings);
+        self.error.clear();
+
+        for hotkey in [self.xmrig.take(), self.window.take()]
+            .into_iter()
+            .flatten()
+        {
+            if let Err(e) = self.manager.unregister(hotkey) {
+                warn!(
+                    "Hotkeys | Unregister [{}] ... FAIL: {}",
+                    hotkey.into_string(),
+                    e
+                );
+            }
+        }
+        if !enabled {
+            return;
+        }
+
+        self.xmrig = self.register(xmrig);
+        self.window = self.register(window);
+    }
+
+    fn register(&mut self, s: &str) -> Option<HotKey> {
+        let result = parse(s).and_then(|hotkey| {
+            self.manager
+                .register(hotkey)
+                .map(|()| hotkey)
+                .map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(hotkey) => {
+                info!("Hotkeys | Register [{}] ... OK", s);
+                Some(hotkey)
+            }
+            Err(e) => {
+                warn!("Hotkeys | Register [{}] ... FAIL: {}", s, e);
+                self.error = format!("[{}]: {}", s, e);
+                None
+            }
+        }
+    }
+
+    // Drain all pending hotkey presses since the last frame.
+    pub fn poll(&self) -> Vec<HotkeyAction> {
+        let mut actions = vec![];
+        while let Ok(event) = self.recv.try_recv() {
+            if event.state() != HotKeyState::Pressed {
+                continue;
+            }
+            if self.xmrig.is_some_and(|h| h.id() == event.id()) {
+                actions.push(HotkeyAction::Xmrig);
+            } else if self.window.is_some_and(|h| h.id() == event.id()) {
+                actions.push(HotkeyAction::Window);
+            }
+        }
+        actions
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse_hotkeys() {
+        use super::*;
+        assert!(parse(DEFAULT_HOTKEY_XMRIG).is_ok());
+        assert!(parse(DEFAULT_HOTKEY_WINDOW).is_ok());
+        assert!(parse("alt+m").is_ok());
+        assert!(parse("").is_err());
+        assert!(parse("Ctrl+").is_err());
+        assert!(parse("Ctrl+Shift+NotAKey").is_err());
+    }
+}
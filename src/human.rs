@@ -20,6 +20,165 @@
 pub const LOCALE: num_format::Locale = num_format::Locale::en;
 pub const ZERO_SECONDS: std::time::Duration = std::time::Duration::from_secs(0);
 
+//---------------------------------------------------------------------------------------------------- [NumberFormat]
+// Everything is formatted in English internally (that's what gets
+// written to disk, logged and parsed back), the user's preferred number
+// format is only applied to what the GUI shows, see [localize()].
+//
+// The current settings are global since [HumanNumber]/[HumanTime] get
+// displayed from everywhere, [App] syncs them with [State] every frame.
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static NUMBER_FORMAT: AtomicU8 = AtomicU8::new(NumberFormat::Comma as u8);
+static SHORT_TIME: AtomicBool = AtomicBool::new(false);
+static HASHRATE_UNIT: AtomicU8 = AtomicU8::new(HashrateUnit::Auto as u8);
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum NumberFormat {
+    #[default]
+    Comma, // [1,234.5]
+    Period,     // [1.234,5]
+    Space,      // [1 234,5]
+    Apostrophe, // [1'234.5]
+}
+
+impl NumberFormat {
+    pub const ALL: [Self; 4] = [Self::Comma, Self::Period, Self::Space, Self::Apostrophe];
+
+    // (thousands separator, decimal point)
+    #[inline]
+    pub const fn separators(self) -> (char, char) {
+        match self {
+            Self::Comma => (',', '.'),
+            Self::Period => ('.', ','),
+            Self::Space => (' ', ','),
+            Self::Apostrophe => ('\'', '.'),
+        }
+    }
+
+    // Example for the UI, e.g: [1,234.5]
+    pub fn example(self) -> String {
+        let (thousands, decimal) = self.separators();
+        format!("1{}234{}5", thousands, decimal)
+    }
+
+    #[inline]
+    pub fn current() -> Self {
+        match NUMBER_FORMAT.load(Ordering::Relaxed) {
+            1 => Self::Period,
+            2 => Self::Space,
+            3 => Self::Apostrophe,
+            _ => Self::Comma,
+        }
+    }
+
+    // Converts English formatted numbers within [s] into [self]'s format.
+    // Only separators in between digits are touched, so things
+    // like [[1, 2, 3]] and [1,234 H/s, ??? H/s] stay intact.
+    pub fn apply(self, s: &str) -> Cow<'_, str> {
+        if self == Self::Comma {
+            return Cow::Borrowed(s);
+        }
+        let (thousands, decimal) = self.separators();
+        let mut string = String::with_capacity(s.len());
+        let mut chars = s.chars().peekable();
+        let mut last = ' ';
+        while let Some(c) = chars.next() {
+            let between_digits =
+                last.is_ascii_digit() && chars.peek().is_some_and(|n| n.is_ascii_digit());
+            match c {
+                ',' if between_digits => string.push(thousands),
+                '.' if between_digits => string.push(decimal),
+                _ => string.push(c),
+            }
+            last = c;
+        }
+        Cow::Owned(string)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [HashrateUnit]
+// Every hashrate goes through [HumanNumber]'s hashrate functions, which ask
+// this first. [Auto] keeps each stat's own unit (e.g: [12,345 H/s] next to
+// [1.000 MH/s]), the rest put every hashrate on screen in the same unit.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum HashrateUnit {
+    #[default]
+    Auto,
+    Hash, // [12,345 H/s]
+    Kilo, // [12.345 kH/s]
+    Mega, // [0.012 MH/s]
+    Raw,  // [12345 H/s], no scaling or separators
+}
+
+impl HashrateUnit {
+    pub const ALL: [Self; 5] = [Self::Auto, Self::Hash, Self::Kilo, Self::Mega, Self::Raw];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Hash => "H/s",
+            Self::Kilo => "kH/s",
+            Self::Mega => "MH/s",
+            Self::Raw => "Raw",
+        }
+    }
+
+    #[inline]
+    pub fn current() -> Self {
+        match HASHRATE_UNIT.load(Ordering::Relaxed) {
+            1 => Self::Hash,
+            2 => Self::Kilo,
+            3 => Self::Mega,
+            4 => Self::Raw,
+            _ => Self::Auto,
+        }
+    }
+
+    // [f] (H/s) in this unit, [None] for [Auto] (the caller picks).
+    pub fn format(self, f: f64) -> Option<String> {
+        let f = f.max(0.0);
+        match self {
+            Self::Auto => None,
+            Self::Hash => Some(format!("{} H/s", separated(f, 0))),
+            Self::Kilo => Some(format!("{} kH/s", separated(f / 1_000.0, 3))),
+            Self::Mega => Some(format!("{} MH/s", separated(f / 1_000_000.0, 3))),
+            Self::Raw => Some(format!("{:.0} H/s", f)),
+        }
+    }
+}
+
+// [f] with [places] decimals and English thousands separators, e.g: [1,234.567]
+fn separated(f: f64, places: usize) -> String {
+    let s = format!("{:.*}", places, f);
+    let (int, frac) = match s.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (s.as_str(), None),
+    };
+    let mut buf = num_format::Buffer::new();
+    buf.write_formatted(&int.parse::<u128>().unwrap_or(0), &LOCALE);
+    match frac {
+        Some(frac) => format!("{}.{}", buf.as_str(), frac),
+        None => buf.as_str().to_string(),
+    }
+}
+
+// Set the global display formats.
+#[inline]
+pub fn set_format(number: NumberFormat, short_time: bool, hashrate: HashrateUnit) {
+    NUMBER_FORMAT.store(number as u8, Ordering::Relaxed);
+    SHORT_TIME.store(short_time, Ordering::Relaxed);
+    HASHRATE_UNIT.store(hashrate as u8, Ordering::Relaxed);
+}
+
+// Apply the current [NumberFormat] to an English formatted [str].
+#[inline]
+pub fn localize(s: &str) -> Cow<'_, str> {
+    NumberFormat::current().apply(s)
+}
+
 //---------------------------------------------------------------------------------------------------- [HumanTime]
 // This converts a [std::time::Duration] into something more readable.
 // Used for uptime display purposes: [7 years, 8 months, 15 days, 23 hours, 35 minutes, 1 second]
@@ -51,12 +210,41 @@ impl HumanTime {
         HumanTime(Duration::from_secs(u))
     }
 
+    #[inline]
+    pub const fn as_secs(&self) -> u64 {
+        self.0.as_secs()
+    }
+
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        self.0.as_secs() == 0
+    }
+
+    // Only the largest unit, e.g: [2 hours, 3 minutes] -> [2 hours]
+    // Used where space is tight (the window title).
+    pub fn rough(&self) -> String {
+        let s = self.localized();
+        let split = if SHORT_TIME.load(Ordering::Relaxed) {
+            s.split_once(' ')
+        } else {
+            s.split_once(", ")
+        };
+        match split {
+            Some((largest, _)) => largest.to_string(),
+            None => s,
+        }
+    }
+
     fn plural(
-        f: &mut std::fmt::Formatter,
+        f: &mut impl std::fmt::Write,
+        short: bool,
         started: &mut bool,
         name: &str,
         value: u64,
     ) -> std::fmt::Result {
+        if short {
+            return Self::short(f, started, name, value);
+        }
         if value > 0 {
             if *started {
                 f.write_str(", ")?;
@@ -69,13 +257,41 @@ impl HumanTime {
         }
         Ok(())
     }
-}
 
-impl std::fmt::Display for HumanTime {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    // Compact and (mostly) language neutral: [1y 2mo 3d 4h 5m 6s]
+    fn short(
+        f: &mut impl std::fmt::Write,
+        started: &mut bool,
+        name: &str,
+        value: u64,
+    ) -> std::fmt::Result {
+        if value > 0 {
+            if *started {
+                f.write_str(" ")?;
+            }
+            let unit = match name {
+                "year" => "y",
+                "month" => "mo",
+                "day" => "d",
+                "hour" => "h",
+                "minute" => "m",
+                _ => "s",
+            };
+            write!(f, "{}{}", value, unit)?;
+            *started = true;
+        }
+        Ok(())
+    }
+
+    // [short] writes [1h 2m] instead of [1 hour, 2 minutes].
+    pub fn write(&self, f: &mut impl std::fmt::Write, short: bool) -> std::fmt::Result {
         let secs = self.0.as_secs();
         if secs == 0 {
-            f.write_str("0 seconds")?;
+            if short {
+                f.write_str("0s")?;
+            } else {
+                f.write_str("0 seconds")?;
+            }
             return Ok(());
         }
 
@@ -90,14 +306,28 @@ impl std::fmt::Display for HumanTime {
         let seconds = day_secs % 60;
 
         let started = &mut false;
-        Self::plural(f, started, "year", years)?;
-        Self::plural(f, started, "month", months)?;
-        Self::plural(f, started, "day", days)?;
-        Self::plural(f, started, "hour", hours)?;
-        Self::plural(f, started, "minute", minutes)?;
-        Self::plural(f, started, "second", seconds)?;
+        Self::plural(f, short, started, "year", years)?;
+        Self::plural(f, short, started, "month", months)?;
+        Self::plural(f, short, started, "day", days)?;
+        Self::plural(f, short, started, "hour", hours)?;
+        Self::plural(f, short, started, "minute", minutes)?;
+        Self::plural(f, short, started, "second", seconds)?;
         Ok(())
     }
+
+    // For the GUI, short if the user picked [Short time].
+    pub fn localized(&self) -> String {
+        let mut s = String::new();
+        let _ = self.write(&mut s, SHORT_TIME.load(Ordering::Relaxed));
+        s
+    }
+}
+
+// Always the long form, [localized()] is for the GUI.
+impl std::fmt::Display for HumanTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.write(f, false)
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- [HumanNumber]
@@ -108,6 +338,9 @@ impl std::fmt::Display for HumanTime {
 // Percent  | [0.001]  -> [0%]                        | Rounds down, removes redundant zeros
 // Hashrate | [123.0, 311.2, null] -> [123, 311, ???] | Casts, replaces null with [???]
 // CPU Load | [12.0, 11.4, null] -> [12.0, 11.4, ???] | No change, just into [String] form
+//
+// [Display] and [as_str()] are always English, [localized()]
+// applies the user's [NumberFormat] for the GUI.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct HumanNumber(String);
 
@@ -118,6 +351,10 @@ impl std::fmt::Display for HumanNumber {
 }
 
 impl HumanNumber {
+    #[inline]
+    pub fn localized(&self) -> String {
+        localize(&self.0).into_owned()
+    }
     #[inline]
     pub fn unknown() -> Self {
         Self("???".to_string())
@@ -126,9 +363,45 @@ impl HumanNumber {
     pub fn from_str(s: &str) -> Self {
         Self(s.to_string())
     }
+    // [f] in the user's [HashrateUnit], if it isn't [Auto].
+    #[inline]
+    fn in_hashrate_unit(f: f64) -> Option<Self> {
+        HashrateUnit::current().format(f).map(Self)
+    }
     #[inline]
     pub fn to_hashrate(f: f32) -> Self {
-        Self(format!("{} H/s", Self::from_f32(f)))
+        Self::in_hashrate_unit(f as f64)
+            .unwrap_or_else(|| Self(format!("{} H/s", Self::from_f32(f))))
+    }
+    // Scaled hashrate with 1 decimal, e.g: [12345.0] -> [12.3 kH/s]
+    pub fn to_hashrate_short(f: f32) -> Self {
+        if let Some(s) = Self::in_hashrate_unit(f as f64) {
+            return s;
+        }
+        let (f, unit) = if f >= 1_000_000_000.0 {
+            (f / 1_000_000_000.0, "GH/s")
+        } else if f >= 1_000_000.0 {
+            (f / 1_000_000.0, "MH/s")
+        } else if f >= 1_000.0 {
+            (f / 1_000.0, "kH/s")
+        } else {
+            return Self(format!("{:.0} H/s", f));
+        };
+        Self(format!("{:.1} {}", f, unit))
+    }
+    // Scaled bytes with 1 decimal, e.g: [1234567] -> [1.2 MB]
+    pub fn to_bytes_short(u: usize) -> Self {
+        let f = u as f64;
+        let (f, unit) = if f >= 1_000_000_000.0 {
+            (f / 1_000_000_000.0, "GB")
+        } else if f >= 1_000_000.0 {
+            (f / 1_000_000.0, "MB")
+        } else if f >= 1_000.0 {
+            (f / 1_000.0, "KB")
+        } else {
+            return Self(format!("{} B", u));
+        };
+        Self(format!("{:.1} {}", f, unit))
     }
     #[inline]
     pub fn to_percent(f: f32) -> Self {
@@ -202,10 +475,14 @@ impl HumanNumber {
     pub fn from_hashrate(array: [Option<f32>; 3]) -> Self {
         let mut string = "[".to_string();
         let mut buf = num_format::Buffer::new();
+        let unit = HashrateUnit::current();
 
         let mut n = 0;
         for i in array {
             match i {
+                Some(f) if unit != HashrateUnit::Auto => {
+                    string.push_str(&unit.format(f as f64).unwrap_or_default());
+                }
                 Some(f) => {
                     let f = f as u128;
                     buf.write_formatted(&f, &LOCALE);
@@ -247,6 +524,9 @@ impl HumanNumber {
     // [1_000_000] -> [1.000 MH/s]
     #[inline]
     pub fn from_u64_to_megahash_3_point(hash: u64) -> Self {
+        if let Some(s) = Self::in_hashrate_unit(hash as f64) {
+            return s;
+        }
         let hash = (hash as f64) / 1_000_000.0;
         let hash = format!("{:.3} MH/s", hash);
         Self(hash)
@@ -254,6 +534,9 @@ impl HumanNumber {
     // [1_000_000_000] -> [1.000 GH/s]
     #[inline]
     pub fn from_u64_to_gigahash_3_point(hash: u64) -> Self {
+        if let Some(s) = Self::in_hashrate_unit(hash as f64) {
+            return s;
+        }
         let hash = (hash as f64) / 1_000_000_000.0;
         let hash = format!("{:.3} GH/s", hash);
         Self(hash)
@@ -268,6 +551,7 @@ impl HumanNumber {
         let f = format!("{}", f);
         Self(f)
     }
+    // The English form, use this for anything written to disk.
     #[inline]
     pub fn as_str(&self) -> &str {
         self.0.as_str()
@@ -299,6 +583,11 @@ mod test {
             HumanNumber::from_load([None, Some(4321.43), Some(1234.1)]).to_string()
                 == "[???, 4321.43, 1234.10]"
         );
+        assert!(HumanNumber::to_hashrate_short(999.4).to_string() == "999 H/s");
+        assert!(HumanNumber::to_hashrate_short(12_345.0).to_string() == "12.3 kH/s");
+        assert!(HumanNumber::to_hashrate_short(2_500_000.0).to_string() == "2.5 MH/s");
+        assert!(HumanNumber::to_bytes_short(999).to_string() == "999 B");
+        assert!(HumanNumber::to_bytes_short(1_234_567).to_string() == "1.2 MB");
         assert!(HumanNumber::from_f32(123_123.125).to_string() == "123,123");
         assert!(HumanNumber::from_f64(123_123_123.123_123_12).to_string() == "123,123,123");
         assert!(HumanNumber::from_u16(1_000).to_string() == "1,000");
@@ -348,6 +637,9 @@ mod test {
         use crate::human::HumanTime;
         use std::time::Duration;
         assert!(HumanTime::into_human(Duration::from_secs(0)).to_string() == "0 seconds");
+        assert!(HumanTime::into_human(Duration::from_secs(0)).rough() == "0 seconds");
+        assert!(HumanTime::into_human(Duration::from_secs(7380)).rough() == "2 hours");
+        assert!(HumanTime::into_human(Duration::from_secs(59)).rough() == "59 seconds");
         assert!(HumanTime::into_human(Duration::from_secs(1)).to_string() == "1 second");
         assert!(HumanTime::into_human(Duration::from_secs(2)).to_string() == "2 seconds");
         assert!(HumanTime::into_human(Duration::from_secs(59)).to_string() == "59 seconds");
@@ -412,4 +704,58 @@ mod test {
             "584542046090 years, 7 months, 15 days, 17 hours, 5 minutes, 3 seconds",
         );
     }
+
+    #[test]
+    fn human_time_short() {
+        use crate::human::HumanTime;
+        let short = |secs| {
+            let mut s = String::new();
+            HumanTime::from_u64(secs).write(&mut s, true).unwrap();
+            s
+        };
+        assert_eq!(short(0), "0s");
+        assert_eq!(short(62), "1m 2s");
+        assert_eq!(short(93600), "1d 2h");
+        assert_eq!(short(3234815), "1mo 6d 23h 59m 59s");
+        assert_eq!(short(63115200), "2y");
+    }
+
+    #[test]
+    fn number_format() {
+        use crate::human::NumberFormat;
+        let s = "[1,234 H/s, 12,345,678 H/s, ??? H/s] | 0.002382256231 XMR | 99.12%";
+        assert_eq!(NumberFormat::Comma.apply(s), s);
+        assert_eq!(
+            NumberFormat::Period.apply(s),
+            "[1.234 H/s, 12.345.678 H/s, ??? H/s] | 0,002382256231 XMR | 99,12%"
+        );
+        assert_eq!(
+            NumberFormat::Space.apply(s),
+            "[1 234 H/s, 12 345 678 H/s, ??? H/s] | 0,002382256231 XMR | 99,12%"
+        );
+        assert_eq!(
+            NumberFormat::Apostrophe.apply(s),
+            "[1'234 H/s, 12'345'678 H/s, ??? H/s] | 0.002382256231 XMR | 99.12%"
+        );
+        assert_eq!(NumberFormat::Period.apply("[1, 2, 3]"), "[1, 2, 3]");
+        assert_eq!(NumberFormat::Space.example(), "1 234,5");
+    }
+
+    #[test]
+    fn hashrate_unit() {
+        use crate::human::{HashrateUnit, NumberFormat};
+        let f = 12_345_678.9;
+        assert_eq!(HashrateUnit::Auto.format(f), None);
+        assert_eq!(HashrateUnit::Hash.format(f).unwrap(), "12,345,679 H/s");
+        assert_eq!(HashrateUnit::Kilo.format(f).unwrap(), "12,345.679 kH/s");
+        assert_eq!(HashrateUnit::Mega.format(f).unwrap(), "12.346 MH/s");
+        assert_eq!(HashrateUnit::Raw.format(f).unwrap(), "12345679 H/s");
+        assert_eq!(HashrateUnit::Kilo.format(512.0).unwrap(), "0.512 kH/s");
+        assert_eq!(HashrateUnit::Hash.format(-1.0).unwrap(), "0 H/s");
+        // The number format still applies on top.
+        assert_eq!(
+            NumberFormat::Period.apply(&HashrateUnit::Kilo.format(f).unwrap()),
+            "12.345,679 kH/s"
+        );
+    }
 }
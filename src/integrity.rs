@@ -0,0 +1,243 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// P2Pool/XMRig binary integrity checks.
+//
+// Trojanized miners are a common way malware gets onto machines, so the
+// SHA-256 of each binary is remembered in [binaries.toml] the first time
+// Gupax starts it (or when Gupax's updater installs it) and re-checked every
+// time it's started after that. A binary that changed outside of the updater
+// has to be explicitly trusted again before it's started.
+
+use crate::macros::*;
+use anyhow::Context;
+use gupax_process::{ProcessName, ProcessSignal};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// The GUI and the updater thread both write the file.
+static LOCK: Mutex<()> = Mutex::new(());
+
+//---------------------------------------------------------------------------------------------------- Hashes
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Hashes {
+    #[serde(default)]
+    sha256: BTreeMap<String, String>, // Absolute binary path -> hex SHA-256
+}
+
+impl Hashes {
+    fn read(file: &Path) -> Self {
+        match std::fs::read_to_string(file) {
+            Ok(s) => toml::from_str(&s).unwrap_or_else(|e| {
+                warn!(
+                    "Integrity | [{}] is corrupt, starting over: {}",
+                    file.display(),
+                    e
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self, file: &Path) -> anyhow::Result<()> {
+        std::fs::write(file, toml::to_string(self)?)
+            .with_context(|| format!("could not write [{}]", file.display()))
+    }
+}
+
+fn key(binary: &Path) -> String {
+    binary.display().to_string()
+}
+
+//---------------------------------------------------------------------------------------------------- Check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    New(String), // Never seen, this is its hash
+    Unchanged,
+    Changed { old: String, new: String },
+}
+
+// Hex SHA-256 of a file.
+pub fn sha256(path: &Path) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("could not open [{}]", path.display()))?;
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.update(&buf[..n]);
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+// Compare [binary] against the hash remembered in [file].
+pub fn check(file: &Path, binary: &Path) -> anyhow::Result<Check> {
+    let new = sha256(binary)?;
+    let _lock = LOCK.lock().unwrap();
+    Ok(match Hashes::read(file).sha256.remove(&key(binary)) {
+        None => Check::New(new),
+        Some(old) if old == new => Check::Unchanged,
+        Some(old) => Check::Changed { old, new },
+    })
+}
+
+// Remember [hash] as the trusted hash of [binary].
+pub fn trust(file: &Path, binary: &Path, hash: &str) -> anyhow::Result<()> {
+    let _lock = LOCK.lock().unwrap();
+    let mut hashes = Hashes::read(file);
+    hashes.sha256.insert(key(binary), hash.to_string());
+    hashes.save(file)?;
+    info!("Integrity | Trusting [{}] ... {}", binary.display(), hash);
+    Ok(())
+}
+
+// Hash [binary] and trust it, used after Gupax itself installed it.
+pub fn trust_file(file: &Path, binary: &Path) -> anyhow::Result<()> {
+    trust(file, binary, &sha256(binary)?)
+}
+
+//---------------------------------------------------------------------------------------------------- Background checks
+// Hashing a big binary takes long enough to freeze the GUI, so [start()]
+// checks in a thread and the GUI picks the finished ones up with [take_done()].
+#[derive(Debug)]
+pub struct Checking {
+    pub name: ProcessName,
+    pub signal: ProcessSignal, // What to do once it's trusted
+    pub binary: PathBuf,
+    pub result: Option<anyhow::Result<Check>>, // [None] while hashing
+}
+
+pub type Checks = Arc<Mutex<Vec<Checking>>>;
+
+// Check [binary] in a thread. Nothing happens if [name] is already being checked,
+// so clicking [Start] twice doesn't start it twice.
+pub fn start(
+    checks: &Checks,
+    file: &Path,
+    name: ProcessName,
+    signal: ProcessSignal,
+    binary: PathBuf,
+) {
+    let mut list = lock!(checks);
+    if list.iter().any(|c| c.name == name) {
+        info!("Integrity | [{}] is already being checked", name);
+        return;
+    }
+    list.push(Checking {
+        name,
+        signal,
+        binary: binary.clone(),
+        result: None,
+    });
+    drop(list);
+    let checks = Arc::clone(checks);
+    let file = file.to_path_buf();
+    std::thread::spawn(move || {
+        let result = check(&file, &binary);
+        if let Some(c) = lock!(checks).iter_mut().find(|c| c.name == name) {
+            c.result = Some(result);
+        }
+    });
+}
+
+// The finished checks, removed from [checks].
+pub fn take_done(checks: &Checks) -> Vec<Checking> {
+    let mut list = lock!(checks);
+    let (done, running) = std::mem::take(&mut *list)
+        .into_iter()
+        .partition(|c| c.result.is_some());
+    *list = running;
+    done
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn check_and_trust() {
+        use super::{check, sha256, trust, trust_file, Check};
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let file = dir.join("binaries.toml");
+        let binary = dir.join("xmrig");
+
+        std::fs::write(&binary, "abc").unwrap();
+        let hash = sha256(&binary).unwrap();
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(check(&file, &binary).unwrap(), Check::New(hash.clone()));
+        trust(&file, &binary, &hash).unwrap();
+        assert_eq!(check(&file, &binary).unwrap(), Check::Unchanged);
+
+        // Replaced behind Gupax's back.
+        std::fs::write(&binary, "abcd").unwrap();
+        let Check::Changed { old, new } = check(&file, &binary).unwrap() else {
+            panic!("change not detected");
+        };
+        assert_eq!(old, hash);
+        assert_ne!(new, hash);
+
+        // Installed by the updater.
+        trust_file(&file, &binary).unwrap();
+        assert_eq!(check(&file, &binary).unwrap(), Check::Unchanged);
+        assert!(check(&file, &dir.join("missing")).is_err());
+
+        // In a thread, a second check of the same process is ignored until it's done.
+        use super::{start, take_done, Checks};
+        use gupax_process::{ProcessName, ProcessSignal};
+        let checks = Checks::default();
+        start(
+            &checks,
+            &file,
+            ProcessName::Xmrig,
+            ProcessSignal::Start,
+            binary.clone(),
+        );
+        start(
+            &checks,
+            &file,
+            ProcessName::Xmrig,
+            ProcessSignal::Restart,
+            binary.clone(),
+        );
+        let mut done = vec![];
+        while done.is_empty() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            done = take_done(&checks);
+        }
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].signal, ProcessSignal::Start);
+        assert!(matches!(done[0].result, Some(Ok(Check::Unchanged))));
+        assert!(crate::macros::lock!(checks).is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Start arguments of the last successful run.
+//
+// Once P2Pool/XMRig has stayed up for [GOOD_RUN], the arguments it was
+// started with are saved to [last_run.toml]. If a later start fails right
+// away, the arguments that changed since then are printed in its console,
+// which usually points straight at the setting that broke it.
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How long a process has to stay up to count as a successful run.
+pub const GOOD_RUN: Duration = Duration::from_secs(60);
+// Set by the GUI thread to [OS data folder]/[last_run.toml].
+static FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+//---------------------------------------------------------------------------------------------------- [LastRun]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+struct LastRun {
+    #[serde(default)]
+    p2pool: Vec<String>,
+    #[serde(default)]
+    xmrig: Vec<String>,
+}
+
+impl LastRun {
+    fn read(file: &Path) -> Self {
+        match std::fs::read_to_string(file) {
+            Ok(s) => toml::from_str(&s).unwrap_or_else(|e| {
+                warn!("LastRun | [{}] is corrupt, ignoring: {}", file.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn args(&mut self, name: &str) -> &mut Vec<String> {
+        match name {
+            "P2Pool" => &mut self.p2pool,
+            _ => &mut self.xmrig,
+        }
+    }
+}
+
+pub fn set_path(path: PathBuf) {
+    *FILE.lock().unwrap() = Some(path);
+}
+
+// The arguments [name] ("P2Pool"/"XMRig") last ran successfully with.
+pub fn get(name: &str) -> Vec<String> {
+    match &*FILE.lock().unwrap() {
+        Some(file) => LastRun::read(file).args(name).clone(),
+        None => vec![],
+    }
+}
+
+// Remember [args] as [name]'s last successful run.
+pub fn save(name: &str, args: &[String]) {
+    let Some(file) = FILE.lock().unwrap().clone() else {
+        return;
+    };
+    let mut last = LastRun::read(&file);
+    if last.args(name) == args {
+        return;
+    }
+    *last.args(name) = args.to_vec();
+    let result = toml::to_string(&last)
+        .map_err(|e| e.to_string())
+        .and_then(|s| std::fs::write(&file, s).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => info!("LastRun | Saved {} arguments ... OK", name),
+        Err(e) => warn!("LastRun | Saved {} arguments ... FAIL ... {}", name, e),
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Diff
+// Group flags with their values: ["--host", "a", "--mini"] -> ["--host a", "--mini"]
+fn options(args: &[String]) -> Vec<String> {
+    let mut vec: Vec<String> = vec![];
+    for arg in args {
+        match vec.last_mut() {
+            Some(last) if !arg.starts_with('-') => {
+                last.push(' ');
+                last.push_str(arg);
+            }
+            _ => vec.push(arg.clone()),
+        }
+    }
+    vec
+}
+
+// Options only in [good] as [- ...], options only in [failed] as [+ ...].
+pub fn diff(good: &[String], failed: &[String]) -> Vec<String> {
+    let good = options(good);
+    let failed = options(failed);
+    let removed = good
+        .iter()
+        .filter(|o| !failed.contains(o))
+        .map(|o| format!("- {}", o));
+    let added = failed
+        .iter()
+        .filter(|o| !good.contains(o))
+        .map(|o| format!("+ {}", o));
+    removed.chain(added).collect()
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn last_run_diff() {
+        use crate::lastrun::*;
+        let args = |s: &str| -> Vec<String> { s.split_whitespace().map(String::from).collect() };
+        let good = args("--wallet 4abc --host 127.0.0.1 --rpc-port 18081 --mini");
+        let failed = args("--wallet 4abc --host 127.0.0.1 --rpc-port 18089 --light-mode");
+        assert_eq!(
+            diff(&good, &failed),
+            [
+                "- --rpc-port 18081",
+                "- --mini",
+                "+ --rpc-port 18089",
+                "+ --light-mode"
+            ]
+        );
+        assert!(diff(&good, &good).is_empty());
+
+        // Round trip through [last_run.toml].
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        set_path(dir.join(crate::disk::LAST_RUN_TOML));
+        assert!(get("P2Pool").is_empty());
+        save("P2Pool", &good);
+        save("XMRig", &failed);
+        assert_eq!(get("P2Pool"), good);
+        assert_eq!(get("XMRig"), failed);
+    }
+}
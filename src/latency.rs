@@ -0,0 +1,133 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Share acceptance latency.
+//
+// XMRig prints how long the pool took to answer each accepted share,
+// e.g: [accepted (12/1) diff 120001 (45 ms)]. A node/network going bad
+// shows up here as a slow climb well before shares start getting rejected,
+// so the p50/p95 of the last [WINDOW] shares are kept as a chart.
+
+use std::collections::VecDeque;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Shares the percentiles are taken over.
+pub const WINDOW: usize = 50;
+// Chart points kept, one per share.
+pub const HISTORY: usize = 500;
+// Latest p50 vs. the lowest p50 seen that gets a warning...
+const RISE_RATIO: u32 = 2;
+// ...as long as it also went up by at least this much.
+const RISE_MS: u32 = 100;
+
+//---------------------------------------------------------------------------------------------------- ShareLatency
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShareLatency {
+    window: VecDeque<u32>, // The last [WINDOW] share latencies in milliseconds
+    pub history: VecDeque<[u32; 2]>, // [p50, p95] after each share, oldest first
+}
+
+impl ShareLatency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, ms: u32) {
+        if self.window.len() == WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(ms);
+        let mut sorted: Vec<u32> = self.window.iter().copied().collect();
+        sorted.sort_unstable();
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history
+            .push_back([percentile(&sorted, 50), percentile(&sorted, 95)]);
+    }
+
+    // The current [p50, p95].
+    pub fn current(&self) -> Option<[u32; 2]> {
+        self.history.back().copied()
+    }
+
+    // The lowest and current p50, if it has climbed enough to point at a
+    // network/node problem. Needs a full [WINDOW] so a few slow shares
+    // right after starting don't count.
+    pub fn rising(&self) -> Option<(u32, u32)> {
+        if self.history.len() < WINDOW {
+            return None;
+        }
+        let low = self.history.iter().map(|[p50, _]| *p50).min()?;
+        let [now, _] = self.current()?;
+        (now >= low * RISE_RATIO && now >= low + RISE_MS).then_some((low, now))
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.window.capacity() * std::mem::size_of::<u32>()
+            + self.history.capacity() * std::mem::size_of::<[u32; 2]>()
+    }
+}
+
+// Nearest-rank percentile of an already sorted slice.
+fn percentile(sorted: &[u32], p: usize) -> u32 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn share_latency() {
+        use crate::latency::*;
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[7], 95), 7);
+        let sorted: Vec<u32> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50), 50);
+        assert_eq!(percentile(&sorted, 95), 95);
+
+        let mut latency = ShareLatency::new();
+        assert_eq!(latency.current(), None);
+        for _ in 0..WINDOW {
+            latency.push(40);
+        }
+        assert_eq!(latency.current(), Some([40, 40]));
+        assert_eq!(latency.rising(), None);
+        // A few slow shares only move the p95.
+        for _ in 0..5 {
+            latency.push(400);
+        }
+        assert_eq!(latency.current(), Some([40, 400]));
+        assert_eq!(latency.rising(), None);
+        // Sustained.
+        for _ in 0..WINDOW {
+            latency.push(300);
+        }
+        assert_eq!(latency.current(), Some([300, 300]));
+        assert_eq!(latency.rising(), Some((40, 300)));
+
+        for _ in 0..HISTORY {
+            latency.push(1);
+        }
+        assert_eq!(latency.history.len(), HISTORY);
+        assert_eq!(latency.window.len(), WINDOW);
+    }
+}
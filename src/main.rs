@@ -44,29 +44,68 @@ use serde::{Deserialize, Serialize};
 use std::{
     env,
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::exit,
     sync::{Arc, Mutex},
-    time::Instant,
+    time::{Duration, Instant},
 };
 // Sysinfo
 use sysinfo::CpuExt;
 use sysinfo::SystemExt;
 // Modules
 //mod benchmark;
+mod advisor;
+mod ansi;
+mod autostart;
+mod availability;
+mod clock;
+mod code;
 mod constants;
+mod contribute;
+mod delay;
+mod demo;
+mod diagnostic;
+mod discover;
 mod disk;
+mod display;
+mod envvar;
+mod event;
 mod free;
+mod gfx;
 mod gupax;
+mod help;
 mod helper;
+mod hook;
+mod hotkey;
 mod human;
+mod integrity;
+mod lastrun;
+mod latency;
 mod macros;
+mod migrate;
+mod mode;
+mod net;
 mod node;
 mod p2pool;
 mod panic;
+mod poolstats;
+mod probe;
+mod proxy;
+mod quarantine;
 mod regex;
+mod report;
+mod rig;
+mod snapshot;
+mod sound;
+mod ssh;
 mod status;
+mod stdin;
+mod telemetry;
+mod timer;
+mod tls;
+mod tomledit;
 mod update;
+mod virt;
 mod xmr;
 mod xmrig;
 use {crate::regex::*, constants::*, disk::*, gupax::*, helper::*, macros::*, node::*, update::*};
@@ -107,7 +146,16 @@ pub struct App {
     node_vec: Vec<(String, Node)>,       // Manual Node database
     og_pool_vec: Vec<(String, Pool)>,    // Manual Pool database
     pool_vec: Vec<(String, Pool)>,       // Manual Pool database
+    node_filter: ListFilter,             // Filter/sort of the [Node] list
+    pool_filter: ListFilter,             // Filter/sort of the [Pool] list
+    pool_ping: Arc<Mutex<PoolPing>>,     // Latency of the [Pool] list, found in [node.rs]
+    clock: Arc<Mutex<clock::Clock>>,     // System clock skew, found in [clock.rs]
     diff: bool,                          // This bool indicates state changes
+    tab_diff: bool,                      // [diff], but only for the current [Tab]
+    // The [Start/Restart] waiting on the unsaved changes prompt.
+    unsaved_start: Option<(ProcessName, ProcessSignal)>,
+    binary_changed: Option<(ProcessName, ProcessSignal, String)>, // Waiting to trust this new hash
+    quarantined: Option<quarantine::Suspect>, // Binary an antivirus probably removed
     // Restart state:
     // If Gupax updated itself, this represents that the
     // user should (but isn't required to) restart Gupax.
@@ -128,13 +176,20 @@ pub struct App {
     xmrig_api: Arc<Mutex<PubXmrigApi>>, // Public ready-to-print XMRig API made by the "helper" thread
     p2pool_img: Arc<Mutex<ImgP2pool>>,  // A one-time snapshot of what data P2Pool started with
     xmrig_img: Arc<Mutex<ImgXmrig>>,    // A one-time snapshot of what data XMRig started with
+    proxy: Arc<Mutex<Process>>,         // [XMRig-Proxy] process state
+    proxy_api: Arc<Mutex<PubProxyApi>>, // Public ready-to-print XMRig-Proxy API made by the "helper" thread
     // STDIN Buffer
-    p2pool_stdin: String, // The buffer between the p2pool console and the [Helper]
-    xmrig_stdin: String,  // The buffer between the xmrig console and the [Helper]
+    p2pool_stdin: stdin::Stdin, // The p2pool console input box, with its command history
+    xmrig_stdin: stdin::Stdin,  // The xmrig console input box, with its command history
+    proxy_stdin: stdin::Stdin,  // The xmrig-proxy console input box, with its command history
     // Sudo State
     sudo: Arc<Mutex<SudoState>>, // This is just a dummy struct on [Windows].
     // State from [--flags]
     no_startup: bool,
+    profile: Option<String>, // [--profile <name>], settings come from [profiles/<name>.toml]
+    // The data path isn't writable, settings only live in memory
+    read_only: bool,
+    data_dir_msg: String, // Result of picking a new data path, shown in the banner
     // Gupax-P2Pool API
     // Gupax's P2Pool API (e.g: ~/.local/share/gupax/p2pool/)
     // This is a file-based API that contains data for permanent stats.
@@ -142,21 +197,57 @@ pub struct App {
     // actual stats, and all the functions needed to mutate them.
     gupax_p2pool_api: Arc<Mutex<GupaxP2poolApi>>,
     // Static stuff
-    benchmarks: Vec<Benchmark>,     // XMRig CPU benchmarks
-    pid: sysinfo::Pid,              // Gupax's PID
-    max_threads: usize,             // Max amount of detected system threads
-    now: Instant,                   // Internal timer
-    exe: String,                    // Path for [Gupax] binary
-    dir: String,                    // Directory [Gupax] binary is in
-    os: &'static str,               // OS
-    admin: bool,                    // Are we admin? (for Windows)
-    os_data_path: PathBuf,          // OS data path (e.g: ~/.local/share/gupax/)
+    benchmarks: Vec<Benchmark>,                     // XMRig CPU benchmarks
+    benchmark_view: BenchmarkView,                  // Search/sort/pins of the above
+    payout_backups: PayoutBackups,                  // [Gupax-P2Pool API] backup picker/timer
+    pid: sysinfo::Pid,                              // Gupax's PID
+    max_threads: usize,                             // Max amount of detected system threads
+    now: Instant,                                   // Internal timer
+    exe: String,                                    // Path for [Gupax] binary
+    dir: String,                                    // Directory [Gupax] binary is in
+    os: &'static str,                               // OS
+    admin: bool,                                    // Are we admin? (for Windows)
+    os_data_path: PathBuf,                          // OS data path (e.g: ~/.local/share/gupax/)
+    events_path: PathBuf,                           // Path to [events.log]
+    integrity_path: PathBuf,                        // Path to [binaries.toml]
+    integrity_checks: integrity::Checks,            // Binaries being hashed before they start
     gupax_p2pool_api_path: PathBuf, // Gupax-P2Pool API path (e.g: ~/.local/share/gupax/p2pool/)
     state_path: PathBuf,            // State file path
     node_path: PathBuf,             // Node file path
     pool_path: PathBuf,             // Pool file path
     name_version: String,           // [Gupax vX.X.X]
-    img: Images,                    // Custom Struct holding pre-compiled bytes of [Images]
+    title: String,                  // Current window title, [name_version] + live stats
+    last_state: [ProcessState; 2],  // P2Pool/XMRig state on the last frame, used to detect failures
+    hotkeys: Option<hotkey::Hotkeys>, // OS-global hotkeys, [None] if they couldn't be initialized
+    timer: timer::Timer,            // One-shot [Stop after N hours] timer in the bottom bar
+    availability: availability::Availability, // P2Pool/XMRig up/failed/dead time, last 24h/7d
+    delayed_start: delay::DelayedStart, // [Auto-P2Pool/XMRig] waiting for their start delay
+    gfx: gfx::Diagnostics,          // Graphics backend actually in use
+    autostart: autostart::Autostart, // Start-on-login registration
+    mining_mode: Option<mode::MiningMode>, // The [Status] mining mode waiting for confirmation
+    p2pool_probe: Arc<Mutex<probe::BinaryProbe>>, // Detected P2Pool binary version
+    tls: Arc<Mutex<tls::TlsProxy>>, // Stratum TLS terminator in front of P2Pool
+    ssh: Arc<Mutex<ssh::Tunnel>>,   // SSH tunnel to the manual node
+    node_rpc: Arc<Mutex<node::NodeRpc>>, // Read-only RPC calls to the selected node
+    node_check: Arc<Mutex<contribute::NodeCheck>>, // Checks a node before it's sent in to [REMOTE_NODES]
+    block_hash: Arc<Mutex<node::BlockHash>>,       // Payout block hash lookups
+    usage_stats: Arc<Mutex<telemetry::UsageStats>>, // Opt-in anonymous usage stats
+    help: help::Help,                              // The [?] help overlay
+    discovery: Arc<Mutex<discover::Discovery>>,    // P2Pool/XMRig binary auto-discovery
+    pool_hashrate: Arc<Mutex<poolstats::PoolHashrate>>, // What the XMRig pool's API reports for us
+    advisor: Arc<Mutex<advisor::Advisor>>,         // P2Pool Main vs Mini advice
+    xmrig_instances: Arc<Mutex<Vec<ExtraXmrig>>>,  // The extra XMRig's, see [helper::ExtraXmrig]
+    p2pool_console: ansi::Console,                 // Parsed (colored) P2Pool console output
+    xmrig_console: ansi::Console,                  // Parsed (colored) XMRig console output
+    proxy_console: ansi::Console,                  // Parsed (colored) XMRig-Proxy console output
+    history_now: Instant, // Last time mining uptime/hashrate was added to [history]
+    last_save: Option<Instant>, // Last time [Save] wrote to disk
+    save_pending: bool,   // A [Save] waiting for [SAVE_INTERVAL] to pass
+    network_changed: Option<u64>, // After a network change, the ping generation to wait past
+    display: Option<String>, // The display the window is on, see [display.rs]
+    toml_editor: tomledit::TomlEditor, // The raw [state.toml] editor on the [Gupax] tab
+    migrate: Arc<Mutex<snapshot::Migrate>>, // [Migrate machine] on the [Gupax] tab
+    img: Images,          // Custom Struct holding pre-compiled bytes of [Images]
 }
 
 impl App {
@@ -169,20 +260,943 @@ impl App {
             crate::free::clamp_scale(app.state.gupax.selected_scale),
         );
         cc.egui_ctx.set_visuals(VISUALS.clone());
-        Self { ..app }
+        // Global hotkeys must be created on the main thread.
+        let hotkeys = hotkey::Hotkeys::new(&cc.egui_ctx);
+        let gfx = gfx::Diagnostics::new(cc, app.state.gupax.gfx_x11, app.state.gupax.gfx_renderer);
+        Self {
+            hotkeys,
+            gfx,
+            ..app
+        }
+    }
+
+    // Set the [*.toml] and [Gupax-P2Pool API] paths from the OS data path.
+    fn set_paths(&mut self, os_data_path: PathBuf) {
+        self.os_data_path = os_data_path;
+        self.state_path = self.os_data_path.join(STATE_TOML);
+        self.node_path = self.os_data_path.join(NODE_TOML);
+        self.pool_path = self.os_data_path.join(POOL_TOML);
+        self.events_path = self.os_data_path.join(EVENTS_LOG);
+        self.integrity_path = self.os_data_path.join(BINARIES_TOML);
+        lastrun::set_path(self.os_data_path.join(LAST_RUN_TOML));
+        self.availability =
+            availability::Availability::load(self.os_data_path.join(AVAILABILITY_TOML));
+        self.gupax_p2pool_api_path = crate::disk::get_gupax_p2pool_path(&self.os_data_path);
+        lock!(self.gupax_p2pool_api).fill_paths(&self.gupax_p2pool_api_path);
+    }
+
+    // Move to a new data path after the old one turned out to be read-only.
+    // The last saved settings are written there, along with the
+    // old payout stats if they could be read.
+    #[cold]
+    #[inline(never)]
+    fn switch_data_dir(&mut self, dir: PathBuf) {
+        info!("App | Switching data path to [{}]", dir.display());
+        if create_gupax_dir(&dir).is_err() || !crate::disk::is_writable(&dir) {
+            self.data_dir_msg = format!("[{}] is not writable either", dir.display());
+            return;
+        }
+        let old_api_path = self.gupax_p2pool_api_path.clone();
+        self.set_paths(dir);
+        let result = (|| -> Result<(), TomlError> {
+            create_gupax_p2pool_dir(&self.gupax_p2pool_api_path)?;
+            for file in GUPAX_P2POOL_API_FILE_ARRAY {
+                let old = old_api_path.join(file);
+                let new = self.gupax_p2pool_api_path.join(file);
+                if old.is_file() && !new.exists() {
+                    std::fs::copy(old, new)?;
+                }
+            }
+            GupaxP2poolApi::create_all_files(&self.gupax_p2pool_api_path)?;
+            lock!(self.gupax_p2pool_api).read_all_files_and_update()?;
+            let mut og = lock!(self.og).clone();
+            crate::disk::save_all(
+                &mut og,
+                &self.state_path,
+                &self.og_node_vec,
+                &self.node_path,
+                &self.og_pool_vec,
+                &self.pool_path,
+            )
+        })();
+        self.data_dir_msg = match result {
+            Ok(_) => {
+                self.read_only = false;
+                format!(
+                    "Gupax data is now saved in [{}], to keep using it start Gupax with [{}={}]",
+                    self.os_data_path.display(),
+                    crate::disk::DATA_DIR_ENV,
+                    self.os_data_path.display(),
+                )
+            }
+            Err(e) => {
+                error!("App | Switching data path ... FAIL: {}", e);
+                format!("Could not use [{}]: {}", self.os_data_path.display(), e)
+            }
+        };
+    }
+
+    // Start/stop/restart the SSH tunnel to match the saved settings,
+    // not every keystroke. A restart is a stop, then (once the old
+    // session is fully [Off]) a start on a later frame.
+    fn ssh_tunnel(&mut self) {
+        let want = {
+            let og = lock!(self.og);
+            if og.p2pool.ssh && !demo::enabled() {
+                og.p2pool.ssh_config()
+            } else {
+                None
+            }
+        };
+        let tunnel = lock!(self.ssh);
+        let changed = tunnel.config != want;
+        let running = tunnel.running();
+        drop(tunnel);
+        if running && changed {
+            ssh::Tunnel::stop(&self.ssh);
+        } else if !running && changed {
+            if let Some(config) = want {
+                ssh::Tunnel::start(&self.ssh, config);
+            }
+        }
+    }
+
+    // Check the pool-side hashrate while XMRig is mining to a pool with a known API.
+    fn pool_hashrate(&mut self, xmrig_is_alive: bool) {
+        let xmrig = &self.state.xmrig;
+        let target = if xmrig_is_alive
+            && !xmrig.simple
+            && xmrig.arguments.is_empty()
+            && !xmrig.is_watch_only()
+            && !demo::enabled()
+        {
+            poolstats::PoolApi::from_host(&xmrig.ip).map(|api| (api, xmrig.address.clone()))
+        } else {
+            None
+        };
+        let mut pool = lock!(self.pool_hashrate);
+        pool.set_target(target);
+        let due = pool.due();
+        drop(pool);
+        if due {
+            poolstats::PoolHashrate::spawn_thread(&self.pool_hashrate);
+        }
+    }
+
+    // Keep the Main/Mini advice fed with the user's hashrate, P2Pool's (every
+    // miner on it) if it has one yet, XMRig's otherwise. Re-fetched hourly.
+    fn chain_advisor(&mut self, p2pool_is_alive: bool, xmrig_is_alive: bool) {
+        if self.state.p2pool.simple || demo::enabled() {
+            return;
+        }
+        let p2pool = if p2pool_is_alive {
+            lock!(self.p2pool_api).user_p2pool_hashrate_u64 as f64
+        } else {
+            0.0
+        };
+        let hashrate = if p2pool > 0.0 {
+            p2pool
+        } else if xmrig_is_alive {
+            lock!(self.xmrig_api).hashrate_raw as f64
+        } else {
+            0.0
+        };
+        let mut advisor = lock!(self.advisor);
+        advisor.hashrate = hashrate;
+        let due = hashrate > 0.0 && advisor.due();
+        drop(advisor);
+        if due {
+            advisor::Advisor::spawn_thread(&self.advisor);
+        }
+    }
+
+    // The P2Pool settings to start with, pointed at the SSH tunnel if it's up.
+    fn p2pool_state(&self) -> crate::disk::P2pool {
+        let mut state = self.state.p2pool.clone();
+        let tunnel = lock!(self.ssh);
+        if state.ssh && !state.simple && state.arguments.is_empty() && tunnel.running() {
+            info!(
+                "App | Using the SSH tunnel [127.0.0.1:{}/{}] for P2Pool",
+                tunnel.local_rpc, tunnel.local_zmq
+            );
+            state.ip = "127.0.0.1".to_string();
+            state.rpc = tunnel.local_rpc.to_string();
+            state.zmq = tunnel.local_zmq.to_string();
+        }
+        state
+    }
+
+    // Start/stop the stratum TLS terminator to match P2Pool and the settings.
+    fn tls_proxy(&mut self, p2pool_is_alive: bool) {
+        let port = self
+            .state
+            .p2pool
+            .tls_port
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p != 0 && *p != tls::STRATUM_PORT);
+        let want = self.state.p2pool.tls && p2pool_is_alive && !demo::enabled();
+        let mut proxy = lock!(self.tls);
+        if !p2pool_is_alive {
+            proxy.error.clear();
+        }
+        let same_port = port == Some(proxy.port);
+        if proxy.running {
+            if !want || !same_port {
+                drop(proxy);
+                tls::TlsProxy::stop(&self.tls);
+            }
+        } else if let (true, Some(port)) = (want, port) {
+            // Don't retry a failed port every frame.
+            if proxy.error.is_empty() || !same_port {
+                drop(proxy);
+                let dir = tls::cert_dir(&self.os_data_path);
+                tls::TlsProxy::spawn_thread(&self.tls, dir, port, tls::STRATUM_PORT);
+            }
+        }
     }
 
     #[cold]
     #[inline(never)]
     fn save_before_quit(&mut self) {
-        if let Err(e) = State::save(&mut self.state, &self.state_path) {
-            error!("State file: {}", e);
+        if self.read_only {
+            return;
+        }
+        if let Err(e) = crate::disk::save_all(
+            &mut self.state,
+            &self.state_path,
+            &self.node_vec,
+            &self.node_path,
+            &self.pool_vec,
+            &self.pool_path,
+        ) {
+            error!("Saving before quit: {}", e);
+        }
+    }
+
+    // [Save], rate-limited to one write per [SAVE_INTERVAL]:
+    // saves within that are merged into one once it passes.
+    fn request_save(&mut self, ctx: &egui::Context) {
+        match self.last_save.map(|last| last.elapsed()) {
+            Some(elapsed) if elapsed < SAVE_INTERVAL => {
+                debug!(
+                    "App | Save requested, waiting [{:?}]",
+                    SAVE_INTERVAL - elapsed
+                );
+                self.save_pending = true;
+                ctx.request_repaint_after(SAVE_INTERVAL - elapsed);
+            }
+            _ => self.save(),
+        }
+    }
+
+    // Write [state/node/pool] and mark them as saved.
+    fn save(&mut self) {
+        self.save_pending = false;
+        self.last_save = Some(Instant::now());
+        match crate::disk::save_all(
+            &mut self.state,
+            &self.state_path,
+            &self.node_vec,
+            &self.node_path,
+            &self.pool_vec,
+            &self.pool_path,
+        ) {
+            Ok(_) => {
+                let mut og = lock!(self.og);
+                og.status = self.state.status.clone();
+                og.gupax = self.state.gupax.clone();
+                og.p2pool = self.state.p2pool.clone();
+                og.xmrig = self.state.xmrig.clone();
+                drop(og);
+                self.og_node_vec = self.node_vec.clone();
+                self.og_pool_vec = self.pool_vec.clone();
+                self.sync_saved();
+            }
+            Err(e) => self.error_state.set_code(
+                code::ErrorCode::StateSave,
+                format!("Saving: {}", e),
+                ErrorFerris::Error,
+                ErrorButtons::Okay,
+            ),
+        }
+    }
+
+    // The saved settings changed, pass the ones
+    // other threads read (not whatever is being typed).
+    fn sync_saved(&self) {
+        let og = lock!(self.og);
+        // IPv4/IPv6 preference and DoH for Gupax's own connections.
+        net::set(og.gupax.ip_preference, &og.gupax.dns_over_https);
+        // Demo events don't run hooks.
+        if demo::enabled() {
+            hook::set(["", "", "", ""]);
+        } else {
+            hook::set(og.gupax.hooks());
+        }
+    }
+
+    // Sets the window title to [Gupax vX.X.X — 12.3 kH/s — share in ~2 hours]
+    // and requests user attention (taskbar flash/bounce) if a process
+    // failed while the window is in the background. Title changes are
+    // only sent to the OS when the text actually changes.
+    fn update_title(
+        &mut self,
+        ctx: &egui::Context,
+        p2pool_state: ProcessState,
+        xmrig_state: ProcessState,
+    ) {
+        let title = if self.state.gupax.window_stats {
+            let hashrate = lock!(self.xmrig_api).hashrate_raw;
+            let share = lock!(self.p2pool_api).p2pool_share_mean.clone();
+            window_title(
+                &self.name_version,
+                p2pool_state,
+                xmrig_state,
+                hashrate,
+                &share,
+            )
+        } else {
+            self.name_version.clone()
+        };
+        // Make it obvious this isn't real XMR.
+        let network = self.state.p2pool.network();
+        let title = if network.is_mainnet() {
+            title
+        } else {
+            format!("[{}] {}", network, title)
+        };
+        let title = if demo::enabled() {
+            format!("[Demo] {}", title)
+        } else {
+            title
+        };
+        let title = match &self.profile {
+            Some(profile) => format!("[{}] {}", profile, title),
+            None => title,
+        };
+        if title != self.title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.title = title;
+        }
+
+        let failed = (p2pool_state == ProcessState::Failed
+            && self.last_state[0] != ProcessState::Failed)
+            || (xmrig_state == ProcessState::Failed && self.last_state[1] != ProcessState::Failed);
+        // [eframe] has no taskbar progress/badge, flashing (or bouncing) the
+        // taskbar entry is the closest thing that works on every platform.
+        if failed && self.state.gupax.window_stats && !ctx.input(|i| i.focused) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                egui::UserAttentionType::Critical,
+            ));
+        }
+        self.last_state = [p2pool_state, xmrig_state];
+    }
+
+    // Handle a global hotkey press. While XMRig is running this pauses/resumes
+    // it ([p]/[r]) without stealing focus, otherwise it's the [Start] button.
+    fn hotkey(
+        &mut self,
+        ctx: &egui::Context,
+        action: hotkey::HotkeyAction,
+        xmrig_is_alive: bool,
+        xmrig_is_waiting: bool,
+    ) {
+        use egui::ViewportCommand;
+        info!("Hotkeys | {:?} was pressed", action);
+        // Un-minimize and focus Gupax, used when
+        // we need the user to type the [sudo] password.
+        let show = |ctx: &egui::Context| {
+            ctx.send_viewport_cmd(ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(ViewportCommand::Focus);
+        };
+        match action {
+            hotkey::HotkeyAction::Window => {
+                let hidden = ctx.input(|i| i.viewport().minimized == Some(true) || !i.focused);
+                if hidden {
+                    show(ctx);
+                } else {
+                    ctx.send_viewport_cmd(ViewportCommand::Minimized(true));
+                }
+            }
+            hotkey::HotkeyAction::Xmrig => {
+                // XMRig is in the middle of (re)starting/stopping.
+                if xmrig_is_waiting {
+                    return;
+                }
+                if xmrig_is_alive {
+                    let input = if lock!(self.xmrig_api).paused {
+                        "r"
+                    } else {
+                        "p"
+                    };
+                    lock!(self.xmrig).input.push(input.to_string());
+                    Helper::xmrig_instances_input(&self.xmrig_instances, input);
+                    return;
+                }
+                let no_binary = !self.state.xmrig.needs_binary();
+                if !no_binary
+                    && (!Gupax::path_is_file(&self.state.gupax.xmrig_path)
+                        || !crate::update::check_xmrig_path(&self.state.gupax.xmrig_path))
+                {
+                    warn!("Hotkeys | XMRig path is not valid, not starting");
+                    return;
+                }
+                self.start(ProcessName::Xmrig, ProcessSignal::Start);
+                // It may ask for a password (or about a changed binary).
+                if !cfg!(windows) && !no_binary {
+                    show(ctx);
+                }
+            }
+        }
+    }
+
+    // The bottom bar [⏱] timer ran out, stopping XMRig
+    // goes through [sudo] on macOS just like the [Stop] button.
+    #[cold]
+    #[inline(never)]
+    fn timer_ran_out(
+        &mut self,
+        ctx: &egui::Context,
+        action: timer::TimerAction,
+        p2pool_is_alive: bool,
+        xmrig_is_alive: bool,
+    ) {
+        info!("App | Timer ran out, running [{}]", action);
+        event::push(event::Kind::Notice, format!("Timer ran out: {}", action));
+        match action {
+            timer::TimerAction::PauseXmrig => {
+                if xmrig_is_alive {
+                    lock!(self.xmrig).input.push("p".to_string());
+                    Helper::xmrig_instances_input(&self.xmrig_instances, "p");
+                }
+                return;
+            }
+            timer::TimerAction::StopBoth if p2pool_is_alive => Helper::stop_p2pool(&self.helper),
+            _ => (),
+        }
+        if !xmrig_is_alive {
+            return;
+        }
+        if cfg!(target_os = "macos") && self.state.xmrig.needs_binary() {
+            lock!(self.sudo).signal = ProcessSignal::Stop;
+            self.error_state.ask_sudo(&self.sudo);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        } else {
+            Helper::stop_xmrig(&self.helper);
+        }
+    }
+
+    // After a system sleep: the helper has already re-baselined the uptimes,
+    // this re-pings the nodes and optionally restarts the processes since
+    // their connections are most likely dead.
+    #[cold]
+    #[inline(never)]
+    fn resumed(&mut self, slept: Duration, p2pool_is_alive: bool, xmrig_is_alive: bool) {
+        info!(
+            "App | System resumed after [{}], re-baselining",
+            crate::human::HumanTime::into_human(slept)
+        );
+        // Don't count the sleep as mining time in the [history].
+        self.history_now = Instant::now();
+        if self.state.p2pool.simple && self.state.p2pool.auto_ping && !lock!(self.ping).pinging {
+            Ping::spawn_thread(&self.ping);
+        }
+        if !self.state.gupax.restart_on_resume {
+            return;
+        }
+        // [start()] re-checks the binaries, they could have been swapped while asleep.
+        if p2pool_is_alive {
+            info!("App | Restarting P2Pool after resume");
+            self.start(ProcessName::P2pool, ProcessSignal::Restart);
+        }
+        if xmrig_is_alive {
+            info!("App | Restarting XMRig after resume");
+            self.start(ProcessName::Xmrig, ProcessSignal::Restart);
+        }
+    }
+
+    // After the re-ping from a network change finishes: switch to the
+    // fastest node if [Auto-select] is on, else suggest it in the ping message.
+    fn reselect_node(&mut self) {
+        self.network_changed = None;
+        let mut ping = lock!(self.ping);
+        if !ping.pinged || ping.auto_selected {
+            return;
+        }
+        if ping.fastest == self.state.p2pool.node {
+            return;
+        }
+        if self.state.p2pool.auto_select {
+            info!(
+                "App | Network changed, switching remote node [{}] -> [{}]",
+                self.state.p2pool.node, ping.fastest
+            );
+            event::push(
+                event::Kind::Node,
+                format!(
+                    "Network changed, switched remote node [{}] -> [{}]",
+                    self.state.p2pool.node, ping.fastest
+                ),
+            );
+            self.state.p2pool.node = ping.fastest.to_string();
+            ping.auto_selected = true;
+        } else {
+            ping.msg = format!(
+                "Network changed, [{}] is now faster than [{}]",
+                ping.fastest, self.state.p2pool.node
+            );
+        }
+    }
+
+    // The unsaved sections that starting [name] would use.
+    // [Gupax] only counts if the binary path itself changed.
+    fn unsaved_sections(&self, name: ProcessName) -> Vec<Section> {
+        let og = lock!(self.og);
+        let (tab, path_changed) = match name {
+            ProcessName::P2pool => (
+                Tab::P2pool,
+                og.gupax.p2pool_path != self.state.gupax.p2pool_path,
+            ),
+            ProcessName::Xmrig => (
+                Tab::Xmrig,
+                og.gupax.xmrig_path != self.state.gupax.xmrig_path,
+            ),
+            // Its path is in its own section.
+            ProcessName::Proxy => (Tab::Proxy, false),
+        };
+        let mut unsaved: Vec<Section> = tab
+            .sections()
+            .iter()
+            .copied()
+            .filter(|section| self.section_diff(&og, *section))
+            .collect();
+        if path_changed {
+            unsaved.push(Section::Gupax);
+        }
+        unsaved
+    }
+
+    // [Start/Restart] was clicked. The in-memory (maybe unsaved) settings are
+    // what gets used, so if they differ from what's saved, ask first.
+    fn start_or_ask(&mut self, name: ProcessName, signal: ProcessSignal) {
+        // Started by hand, its auto-start delay doesn't matter anymore.
+        self.delayed_start.cancel(name);
+        let unsaved = self.unsaved_sections(name);
+        if unsaved.is_empty() {
+            self.start(name, signal);
+            return;
+        }
+        let list = unsaved
+            .iter()
+            .map(|section| section.name())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        info!(
+            "App | [{}] {} with unsaved [{}], asking...",
+            name, signal, list
+        );
+        self.unsaved_start = Some((name, signal));
+        self.error_state.set(
+            format!(
+                "{} will {} with the current settings, but these aren't saved: [{}]",
+                name,
+                signal.to_string().to_lowercase(),
+                list
+            ),
+            ErrorFerris::Oops,
+            ErrorButtons::UnsavedStart,
+        );
+    }
+
+    // Why P2Pool can't be started right now, [None] if it can.
+    fn p2pool_start_error(&self) -> Option<String> {
+        let error = if demo::enabled() {
+            // No binary or address needed.
+            return None;
+        } else if !Regexes::addr_ok(&self.state.p2pool.address, self.state.p2pool.network()) {
+            P2POOL_ADDRESS
+        } else if !Gupax::path_is_file(&self.state.gupax.p2pool_path) {
+            P2POOL_PATH_NOT_FILE
+        } else if !crate::update::check_p2pool_path(&self.state.gupax.p2pool_path) {
+            P2POOL_PATH_NOT_VALID
+        } else if !Helper::workdir(
+            &self.state.p2pool.workdir,
+            &self.state.gupax.absolute_p2pool_path,
+        )
+        .is_dir()
+        {
+            GUPAX_WORKDIR_MISSING
+        } else {
+            return None;
+        };
+        Some(format!("Error: {}", error))
+    }
+
+    // Why XMRig can't be started right now, [None] if it can.
+    fn xmrig_start_error(&self) -> Option<String> {
+        if !self.state.xmrig.needs_binary() {
+            // No binary needed (watch-only/demo).
+            None
+        } else if !Gupax::path_is_file(&self.state.gupax.xmrig_path) {
+            Some(format!("Error: {}", XMRIG_PATH_NOT_FILE))
+        } else if !crate::update::check_xmrig_path(&self.state.gupax.xmrig_path) {
+            Some(format!("Error: {}", XMRIG_PATH_NOT_VALID))
+        } else if !Helper::workdir(
+            &self.state.xmrig.workdir,
+            &self.state.gupax.absolute_xmrig_path,
+        )
+        .is_dir()
+        {
+            Some(format!("Error: {}", GUPAX_WORKDIR_MISSING))
+        } else {
+            self.state
+                .xmrig
+                .api_port_conflict(&self.state.p2pool)
+                .map(|used_by| format!("Error: {} {}", XMRIG_API_CONFLICT, used_by))
+        }
+    }
+
+    // Why XMRig-Proxy can't be started right now, [None] if it can.
+    fn proxy_start_error(&self) -> Option<String> {
+        let proxy = &self.state.proxy;
+        let error = if demo::enabled() {
+            return None;
+        } else if !Gupax::path_is_file(&proxy.path) {
+            PROXY_PATH_NOT_FILE
+        } else if !crate::update::check_proxy_path(&proxy.path) {
+            PROXY_PATH_NOT_VALID
+        } else if !Helper::workdir(&proxy.workdir, &proxy.absolute_path()).is_dir() {
+            GUPAX_WORKDIR_MISSING
+        } else if proxy.arguments.is_empty()
+            && !(REGEXES.port.is_match(&proxy.bind_port) && REGEXES.port.is_match(&proxy.port))
+        {
+            PROXY_PORT
+        } else {
+            return None;
+        };
+        Some(format!("Error: {}", error))
+    }
+
+    // Stopping XMRig goes through [sudo] on macOS.
+    fn stop_xmrig(&mut self) {
+        if cfg!(target_os = "macos") && self.state.xmrig.needs_binary() {
+            lock!(self.sudo).signal = ProcessSignal::Stop;
+            self.error_state.ask_sudo(&self.sudo);
+        } else {
+            Helper::stop_xmrig(&self.helper);
+        }
+    }
+
+    // A [Start/Stop/Restart] from outside the bottom bar, e.g: the [Status] process cards.
+    fn process_signal(&mut self, name: ProcessName, signal: ProcessSignal) {
+        info!("App | [{}] {} from the Status tab", name, signal);
+        match (name, signal) {
+            (ProcessName::P2pool, ProcessSignal::Stop) => Helper::stop_p2pool(&self.helper),
+            (ProcessName::Xmrig, ProcessSignal::Stop) => self.stop_xmrig(),
+            (ProcessName::Proxy, ProcessSignal::Stop) => Helper::stop_proxy(&self.helper),
+            (_, ProcessSignal::Start | ProcessSignal::Restart) => self.start_or_ask(name, signal),
+            _ => (),
+        }
+    }
+
+    // The saved settings of [name] that changed since it was started.
+    fn restart_needed(&self, name: ProcessName, alive: bool) -> Vec<&'static str> {
+        if !alive {
+            return Vec::new();
+        }
+        let og = lock!(self.og);
+        match name {
+            ProcessName::P2pool => crate::disk::restart_needed(
+                &lock!(self.p2pool_img).settings,
+                &og.p2pool.start_settings(),
+            ),
+            ProcessName::Xmrig => crate::disk::restart_needed(
+                &lock!(self.xmrig_img).settings,
+                &og.xmrig.start_settings(),
+            ),
+            // It's started straight from [state.proxy], there's nothing to compare.
+            ProcessName::Proxy => Vec::new(),
+        }
+    }
+
+    // Start/Restart [name] with the current settings, once its binary
+    // is checked (in a thread), see [binary_trusted()] and [launch()].
+    fn start(&mut self, name: ProcessName, signal: ProcessSignal) {
+        let _ = lock!(self.og).update_absolute_path();
+        let _ = self.state.update_absolute_path();
+        if demo::enabled() || (name == ProcessName::Xmrig && !self.state.xmrig.needs_binary()) {
+            self.launch(name, signal);
+            return;
+        }
+        let binary = self.binary_path(name);
+        integrity::start(
+            &self.integrity_checks,
+            &self.integrity_path,
+            name,
+            signal,
+            binary,
+        );
+    }
+
+    fn binary_path(&self, name: ProcessName) -> PathBuf {
+        match name {
+            ProcessName::P2pool => self.state.gupax.absolute_p2pool_path.clone(),
+            ProcessName::Xmrig => self.state.gupax.absolute_xmrig_path.clone(),
+            ProcessName::Proxy => self.state.proxy.absolute_path(),
+        }
+    }
+
+    // Start/Restart [name] right away, its binary is already checked.
+    fn launch(&mut self, name: ProcessName, signal: ProcessSignal) {
+        let restart = signal == ProcessSignal::Restart;
+        match name {
+            ProcessName::P2pool if restart => Helper::restart_p2pool(
+                &self.helper,
+                &self.p2pool_state(),
+                &self.state.gupax.absolute_p2pool_path,
+                self.gather_backup_hosts(),
+            ),
+            ProcessName::P2pool => Helper::start_p2pool(
+                &self.helper,
+                &self.p2pool_state(),
+                &self.state.gupax.absolute_p2pool_path,
+                self.gather_backup_hosts(),
+            ),
+            ProcessName::Xmrig if cfg!(windows) || !self.state.xmrig.needs_binary() => {
+                if restart {
+                    Helper::restart_xmrig(
+                        &self.helper,
+                        &self.state.xmrig,
+                        &self.state.gupax.absolute_xmrig_path,
+                        Arc::clone(&self.sudo),
+                    );
+                } else {
+                    Helper::start_xmrig(
+                        &self.helper,
+                        &self.state.xmrig,
+                        &self.state.gupax.absolute_xmrig_path,
+                        Arc::clone(&self.sudo),
+                    );
+                }
+            }
+            ProcessName::Xmrig => {
+                lock!(self.sudo).signal = signal;
+                self.error_state.ask_sudo(&self.sudo);
+            }
+            ProcessName::Proxy if restart => Helper::restart_proxy(&self.helper, &self.state.proxy),
+            ProcessName::Proxy => Helper::start_proxy(&self.helper, &self.state.proxy),
+        }
+    }
+
+    // Resolve the XMRig launcher setting when it changes, [Auto] searches the [PATH].
+    fn sync_launcher(&self) {
+        let escalation = self.state.gupax.escalation;
+        let mut sudo = lock!(self.sudo);
+        if sudo.escalation != escalation {
+            sudo.escalation = escalation;
+            sudo.launcher = escalation.resolve();
+            info!(
+                "App | XMRig launcher [{}] ... {}",
+                escalation, sudo.launcher
+            );
+        }
+    }
+
+    // Is [checked]'s binary the same one that was started (or installed) last time?
+    // A changed binary asks first, a new one is trusted on first use.
+    fn binary_trusted(&mut self, checked: integrity::Checking) -> bool {
+        let integrity::Checking {
+            name,
+            signal,
+            binary,
+            result,
+        } = checked;
+        match result {
+            Some(Ok(integrity::Check::Unchanged)) => true,
+            Some(Ok(integrity::Check::New(hash))) => {
+                if let Err(e) = integrity::trust(&self.integrity_path, &binary, &hash) {
+                    warn!("App | Could not record [{}]'s hash: {}", name, e);
+                }
+                true
+            }
+            Some(Ok(integrity::Check::Changed { old, new })) => {
+                warn!(
+                    "App | [{}] changed outside of Gupax: [{}] -> [{}]",
+                    binary.display(),
+                    old,
+                    new
+                );
+                self.error_state.set_code(
+                    code::ErrorCode::BinaryChanged,
+                    format!(
+                        "{}\n\n[{}]\nWas: {}\nNow: {}",
+                        BINARY_CHANGED,
+                        binary.display(),
+                        old,
+                        new
+                    ),
+                    ErrorFerris::Error,
+                    ErrorButtons::BinaryChanged,
+                );
+                self.binary_changed = Some((name, signal, new));
+                false
+            }
+            // Starting will fail and say why.
+            Some(Err(e)) => {
+                warn!("App | Could not check [{}]: {}", binary.display(), e);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Does [section] have unsaved changes?
+    fn section_diff(&self, og: &State, section: Section) -> bool {
+        match section {
+            Section::Status => og.status != self.state.status,
+            Section::Gupax => og.gupax != self.state.gupax,
+            Section::P2pool => og.p2pool != self.state.p2pool,
+            Section::Xmrig => og.xmrig != self.state.xmrig,
+            Section::Proxy => og.proxy != self.state.proxy,
+            Section::Node => self.og_node_vec != self.node_vec,
+            Section::Pool => self.og_pool_vec != self.pool_vec,
+        }
+    }
+
+    // Save only [section], the other unsaved changes are left alone.
+    // The state file is written as [og] + this section.
+    // Replace every setting with an edited [state.toml], see [tomledit.rs].
+    fn apply_toml(&mut self, mut new: State) {
+        info!("App | Applying edited [state.toml]");
+        if !self.read_only {
+            if let Err(e) = State::save(&mut new, &self.state_path) {
+                self.error_state.set_code(
+                    code::ErrorCode::StateSave,
+                    format!("State file: {}", e),
+                    ErrorFerris::Error,
+                    ErrorButtons::Okay,
+                );
+                return;
+            }
+        }
+        self.state = new.clone();
+        *lock!(self.og) = new;
+        self.sync_saved();
+        self.toml_editor.msg = "Applied".to_string();
+    }
+
+    // Replace the whole setup with an imported snapshot, see [snapshot.rs].
+    // The current payout files are backed up before they're overwritten.
+    fn apply_snapshot(&mut self, import: snapshot::Import) {
+        info!("App | Applying snapshot from [{}]", import.from);
+        if self.read_only {
+            lock!(self.migrate).msg =
+                "The data folder is read-only, select a writable one first".to_string();
+            return;
+        }
+        let mut state = import.state;
+        let result = (|| -> Result<(), TomlError> {
+            if !import.p2pool.is_empty() {
+                let mut api = lock!(self.gupax_p2pool_api);
+                GupaxP2poolApi::backup(&self.gupax_p2pool_api_path, usize::MAX)?;
+                for (file, contents) in &import.p2pool {
+                    std::fs::write(self.gupax_p2pool_api_path.join(file), contents)?;
+                }
+                api.read_all_files_and_update()?;
+            }
+            crate::disk::save_all(
+                &mut state,
+                &self.state_path,
+                &import.nodes,
+                &self.node_path,
+                &import.pools,
+                &self.pool_path,
+            )
+        })();
+        if let Err(e) = result {
+            error!("App | Applying snapshot ... FAIL: {}", e);
+            lock!(self.migrate).msg = format!("Import failed: {}", e);
+            return;
+        }
+        for (binary, hash) in &import.trusted {
+            if let Err(e) = integrity::trust(&self.integrity_path, binary, hash) {
+                warn!("App | Couldn't trust [{}]: {}", binary.display(), e);
+            }
         }
-        if let Err(e) = Node::save(&self.node_vec, &self.node_path) {
-            error!("Node list: {}", e);
+        self.node_vec = import.nodes.clone();
+        self.og_node_vec = import.nodes;
+        self.pool_vec = import.pools.clone();
+        self.og_pool_vec = import.pools;
+        self.toml_editor.load(&state);
+        self.state = state.clone();
+        *lock!(self.og) = state;
+        self.sync_saved();
+        self.payout_backups.refresh(&self.gupax_p2pool_api_path);
+        lock!(self.migrate).msg = format!("Imported the snapshot from {}", import.from);
+    }
+
+    fn apply(&mut self, section: Section) {
+        info!("App | Applying [{:?}]", section);
+        if self.read_only {
+            // Nowhere to write, only keep them for this session.
+            match section {
+                Section::Node => self.og_node_vec = self.node_vec.clone(),
+                Section::Pool => self.og_pool_vec = self.pool_vec.clone(),
+                _ => section.copy(&self.state, &mut lock!(self.og)),
+            }
+            self.sync_saved();
+            return;
         }
-        if let Err(e) = Pool::save(&self.pool_vec, &self.pool_path) {
-            error!("Pool list: {}", e);
+        match section {
+            Section::Node => match Node::save(&self.node_vec, &self.node_path) {
+                Ok(_) => self.og_node_vec = self.node_vec.clone(),
+                Err(e) => self.error_state.set_code(
+                    code::ErrorCode::NodeSave,
+                    format!("Node list: {}", e),
+                    ErrorFerris::Error,
+                    ErrorButtons::Okay,
+                ),
+            },
+            Section::Pool => match Pool::save(&self.pool_vec, &self.pool_path) {
+                Ok(_) => self.og_pool_vec = self.pool_vec.clone(),
+                Err(e) => self.error_state.set_code(
+                    code::ErrorCode::PoolSave,
+                    format!("Pool list: {}", e),
+                    ErrorFerris::Error,
+                    ErrorButtons::Okay,
+                ),
+            },
+            _ => {
+                let mut new = lock!(self.og).clone();
+                section.copy(&self.state, &mut new);
+                match State::save(&mut new, &self.state_path) {
+                    Ok(_) => {
+                        // [State::save()] fills in the absolute paths.
+                        section.copy(&new, &mut self.state);
+                        section.copy(&new, &mut lock!(self.og));
+                        self.sync_saved();
+                    }
+                    Err(e) => self.error_state.set_code(
+                        code::ErrorCode::StateSave,
+                        format!("State file: {}", e),
+                        ErrorFerris::Error,
+                        ErrorButtons::Okay,
+                    ),
+                }
+            }
+        }
+    }
+
+    // Throw away the unsaved changes of only [section].
+    fn revert(&mut self, section: Section) {
+        info!("App | Reverting [{:?}]", section);
+        match section {
+            Section::Node => self.node_vec = self.og_node_vec.clone(),
+            Section::Pool => self.pool_vec = self.og_pool_vec.clone(),
+            _ => section.copy(&lock!(self.og), &mut self.state),
         }
     }
 
@@ -190,21 +1204,16 @@ impl App {
     #[inline(never)]
     fn new(now: Instant) -> Self {
         info!("Initializing App Struct...");
-        info!("App Init | P2Pool & XMRig processes...");
-        let p2pool = arc_mut!(Process::new(
-            ProcessName::P2pool,
-            String::new(),
-            PathBuf::new()
-        ));
-        let xmrig = arc_mut!(Process::new(
-            ProcessName::Xmrig,
-            String::new(),
-            PathBuf::new()
-        ));
+        info!("App Init | P2Pool, XMRig & XMRig-Proxy processes...");
+        let p2pool = arc_mut!(Process::new(ProcessName::P2pool));
+        let xmrig = arc_mut!(Process::new(ProcessName::Xmrig));
+        let proxy = arc_mut!(Process::new(ProcessName::Proxy));
         let p2pool_api = arc_mut!(PubP2poolApi::new());
         let xmrig_api = arc_mut!(PubXmrigApi::new());
+        let proxy_api = arc_mut!(PubProxyApi::new());
         let p2pool_img = arc_mut!(ImgP2pool::new());
         let xmrig_img = arc_mut!(ImgXmrig::new());
+        let xmrig_instances = arc_mut!(Vec::new());
 
         info!("App Init | Sysinfo...");
         // We give this to the [Helper] thread.
@@ -234,6 +1243,7 @@ impl App {
             json
         };
         info!("App Init | Assuming user's CPU is: {}", benchmarks[0].cpu);
+        let cpu_family = telemetry::cpu_family(sysinfo.cpus()[0].brand());
 
         info!("App Init | The rest of the [App]...");
         let mut app = Self {
@@ -255,18 +1265,29 @@ impl App {
             node_vec: Node::new_vec(),
             og_pool_vec: Pool::new_vec(),
             pool_vec: Pool::new_vec(),
+            node_filter: ListFilter::default(),
+            pool_filter: ListFilter::default(),
+            pool_ping: arc_mut!(PoolPing::new()),
+            clock: arc_mut!(clock::Clock::new()),
             restart: arc_mut!(Restart::No),
             diff: false,
+            tab_diff: false,
+            unsaved_start: None,
+            binary_changed: None,
+            quarantined: None,
             error_state: ErrorState::new(),
             helper: arc_mut!(Helper::new(
                 now,
                 pub_sys.clone(),
                 p2pool.clone(),
                 xmrig.clone(),
+                proxy.clone(),
                 p2pool_api.clone(),
                 xmrig_api.clone(),
+                proxy_api.clone(),
                 p2pool_img.clone(),
                 xmrig_img.clone(),
+                xmrig_instances.clone(),
                 arc_mut!(GupaxP2poolApi::new())
             )),
             p2pool,
@@ -275,15 +1296,24 @@ impl App {
             xmrig_api,
             p2pool_img,
             xmrig_img,
-            p2pool_stdin: String::with_capacity(10),
-            xmrig_stdin: String::with_capacity(10),
+            proxy,
+            proxy_api,
+            xmrig_instances,
+            p2pool_stdin: stdin::Stdin::new(),
+            xmrig_stdin: stdin::Stdin::new(),
+            proxy_stdin: stdin::Stdin::new(),
             sudo: arc_mut!(SudoState::new()),
             resizing: false,
             alpha: 0,
             no_startup: false,
+            profile: None,
+            read_only: false,
+            data_dir_msg: String::new(),
             gupax_p2pool_api: arc_mut!(GupaxP2poolApi::new()),
             pub_sys,
             benchmarks,
+            benchmark_view: BenchmarkView::default(),
+            payout_backups: PayoutBackups::default(),
             pid,
             max_threads: benri::threads!(),
             now,
@@ -292,11 +1322,44 @@ impl App {
             dir: String::new(),
             os: OS,
             os_data_path: PathBuf::new(),
+            events_path: PathBuf::new(),
+            integrity_path: PathBuf::new(),
+            integrity_checks: integrity::Checks::default(),
             gupax_p2pool_api_path: PathBuf::new(),
             state_path: PathBuf::new(),
             node_path: PathBuf::new(),
             pool_path: PathBuf::new(),
             name_version: format!("Gupax {}", GUPAX_VERSION),
+            title: format!("Gupax {}", GUPAX_VERSION),
+            last_state: [ProcessState::Dead; 2],
+            hotkeys: None,
+            timer: timer::Timer::new(),
+            availability: availability::Availability::new(),
+            delayed_start: delay::DelayedStart::new(),
+            gfx: gfx::Diagnostics::default(),
+            autostart: autostart::Autostart::default(),
+            mining_mode: None,
+            p2pool_probe: arc_mut!(probe::BinaryProbe::new()),
+            tls: arc_mut!(tls::TlsProxy::new()),
+            ssh: arc_mut!(ssh::Tunnel::new()),
+            node_rpc: arc_mut!(node::NodeRpc::new()),
+            node_check: arc_mut!(contribute::NodeCheck::new()),
+            block_hash: arc_mut!(node::BlockHash::default()),
+            usage_stats: arc_mut!(telemetry::UsageStats::new(cpu_family)),
+            help: help::Help::default(),
+            discovery: arc_mut!(discover::Discovery::default()),
+            pool_hashrate: arc_mut!(poolstats::PoolHashrate::new()),
+            advisor: arc_mut!(advisor::Advisor::new()),
+            p2pool_console: ansi::Console::new(),
+            xmrig_console: ansi::Console::new(),
+            proxy_console: ansi::Console::new(),
+            history_now: Instant::now(),
+            last_save: None,
+            save_pending: false,
+            network_changed: None,
+            display: None,
+            toml_editor: tomledit::TomlEditor::default(),
+            migrate: snapshot::Migrate::new(),
             img: Images::new(),
         };
         //---------------------------------------------------------------------------------------------------- App init data that *could* panic
@@ -323,27 +1386,31 @@ impl App {
             }
         };
         // Get OS data path
-        app.os_data_path = match get_gupax_data_path() {
+        let os_data_path = match get_gupax_data_path() {
             Ok(dir) => dir,
-            Err(e) => {
-                panic = format!("get_os_data_path(): {}", e);
-                app.error_state
-                    .set(panic.clone(), ErrorFerris::Panic, ErrorButtons::Quit);
-                PathBuf::new()
-            }
+            // The path exists (or should), it just can't be created/written to.
+            // Keep going with in-memory state instead of panicking.
+            Err(e) => match crate::disk::gupax_data_path() {
+                Some(dir) => {
+                    warn!(
+                        "App Init | Data path is unusable, continuing read-only: {}",
+                        e
+                    );
+                    dir
+                }
+                None => {
+                    panic = format!("get_os_data_path(): {}", e);
+                    app.error_state
+                        .set(panic.clone(), ErrorFerris::Panic, ErrorButtons::Quit);
+                    PathBuf::new()
+                }
+            },
         };
+        app.read_only =
+            !os_data_path.as_os_str().is_empty() && !crate::disk::is_writable(&os_data_path);
 
         info!("App Init | Setting TOML path...");
-        // Set [*.toml] path
-        app.state_path = app.os_data_path.clone();
-        app.state_path.push(STATE_TOML);
-        app.node_path = app.os_data_path.clone();
-        app.node_path.push(NODE_TOML);
-        app.pool_path = app.os_data_path.clone();
-        app.pool_path.push(POOL_TOML);
-        // Set GupaxP2poolApi path
-        app.gupax_p2pool_api_path = crate::disk::get_gupax_p2pool_path(&app.os_data_path);
-        lock!(app.gupax_p2pool_api).fill_paths(&app.gupax_p2pool_api_path);
+        app.set_paths(os_data_path);
 
         // Apply arg state
         // It's not safe to [--reset] if any of the previous variables
@@ -354,8 +1421,12 @@ impl App {
         // Read disk state
         info!("App Init | Reading disk state...");
         use TomlError::*;
-        app.state = match State::get(&app.state_path) {
+        app.state = match State::get(&app.state_path, app.read_only) {
             Ok(toml) => toml,
+            Err(err) if app.read_only && err.is_write() => {
+                warn!("State ... read-only, using defaults: {}", err);
+                State::new()
+            }
             Err(err) => {
                 error!("State ... {}", err);
                 let set = match err {
@@ -368,7 +1439,7 @@ impl App {
                     _ => None,
                 };
                 if let Some((e, ferris, button)) = set {
-                    app.error_state.set(format!("State file: {}\n\nTry deleting: {}\n\n(Warning: this will delete your Gupax settings)\n\n", e, app.state_path.display()), ferris, button);
+                    app.error_state.set_code(code::ErrorCode::StateRead, format!("State file: {}\n\nTry deleting: {}\n\n(Warning: this will delete your Gupax settings)\n\n", e, app.state_path.display()), ferris, button);
                 }
 
                 State::new()
@@ -376,12 +1447,22 @@ impl App {
         };
         // Clamp window resolution scaling values.
         app.state.gupax.selected_scale = crate::free::clamp_scale(app.state.gupax.selected_scale);
+        for display in app.state.gupax.display_scales.iter_mut() {
+            display.scale = crate::free::clamp_scale(display.scale);
+        }
+        // Fill in missing P2Pool/XMRig paths from the usual install locations.
+        discover::fill_missing(&mut app.state.gupax);
+        let _ = app.state.update_absolute_path();
 
         app.og = arc_mut!(app.state.clone());
         // Read node list
         info!("App Init | Reading node list...");
         app.node_vec = match Node::get(&app.node_path) {
             Ok(toml) => toml,
+            Err(err) if app.read_only && err.is_write() => {
+                warn!("Node ... read-only, using defaults: {}", err);
+                Node::new_vec()
+            }
             Err(err) => {
                 error!("Node ... {}", err);
                 let (e, ferris, button) = match err {
@@ -393,7 +1474,7 @@ impl App {
                     Merge(e) => (e.to_string(), ErrorFerris::Error, ErrorButtons::ResetState),
                     Parse(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
                 };
-                app.error_state.set(format!("Node list: {}\n\nTry deleting: {}\n\n(Warning: this will delete your custom node list)\n\n", e, app.node_path.display()), ferris, button);
+                app.error_state.set_code(code::ErrorCode::NodeRead, format!("Node list: {}\n\nTry deleting: {}\n\n(Warning: this will delete your custom node list)\n\n", e, app.node_path.display()), ferris, button);
                 Node::new_vec()
             }
         };
@@ -404,6 +1485,10 @@ impl App {
         info!("App Init | Reading pool list...");
         app.pool_vec = match Pool::get(&app.pool_path) {
             Ok(toml) => toml,
+            Err(err) if app.read_only && err.is_write() => {
+                warn!("Pool ... read-only, using defaults: {}", err);
+                Pool::new_vec()
+            }
             Err(err) => {
                 error!("Pool ... {}", err);
                 let (e, ferris, button) = match err {
@@ -415,7 +1500,7 @@ impl App {
                     Merge(e) => (e.to_string(), ErrorFerris::Error, ErrorButtons::ResetState),
                     Parse(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
                 };
-                app.error_state.set(format!("Pool list: {}\n\nTry deleting: {}\n\n(Warning: this will delete your custom pool list)\n\n", e, app.pool_path.display()), ferris, button);
+                app.error_state.set_code(code::ErrorCode::PoolRead, format!("Pool list: {}\n\nTry deleting: {}\n\n(Warning: this will delete your custom pool list)\n\n", e, app.pool_path.display()), ferris, button);
                 Pool::new_vec()
             }
         };
@@ -426,8 +1511,14 @@ impl App {
         //----------------------------------------------------------------------------------------------------
         // Read [GupaxP2poolApi] disk files
         let mut gupax_p2pool_api = lock!(app.gupax_p2pool_api);
+        // Read-only and the files couldn't be created: there's nothing to read either.
+        let mut read_api = true;
         match GupaxP2poolApi::create_all_files(&app.gupax_p2pool_api_path) {
             Ok(_) => info!("App Init | Creating Gupax-P2Pool API files ... OK"),
+            Err(err) if app.read_only && err.is_write() => {
+                warn!("GupaxP2poolApi ... read-only, not creating files: {}", err);
+                read_api = false;
+            }
             Err(err) => {
                 error!("GupaxP2poolApi ... {}", err);
                 let (e, ferris, button) = match err {
@@ -439,31 +1530,33 @@ impl App {
                     Merge(e) => (e.to_string(), ErrorFerris::Error, ErrorButtons::ResetState),
                     Parse(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
                 };
-                app.error_state.set(format!("Gupax P2Pool Stats: {}\n\nTry deleting: {}\n\n(Warning: this will delete your P2Pool payout history...!)\n\n", e, app.gupax_p2pool_api_path.display()), ferris, button);
+                app.error_state.set_code(code::ErrorCode::StatsRead, format!("Gupax P2Pool Stats: {}\n\nTry deleting: {}\n\n(Warning: this will delete your P2Pool payout history...!)\n\n", e, app.gupax_p2pool_api_path.display()), ferris, button);
             }
         }
         info!("App Init | Reading Gupax-P2Pool API files...");
-        match gupax_p2pool_api.read_all_files_and_update() {
-            Ok(_) => {
-                info!(
-                    "GupaxP2poolApi ... Payouts: {} | XMR (atomic-units): {}",
-                    gupax_p2pool_api.payout, gupax_p2pool_api.xmr,
-                );
-            }
-            Err(err) => {
-                error!("GupaxP2poolApi ... {}", err);
-                let (e, ferris, button) = match err {
-                    Io(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                    Path(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                    Serialize(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                    Deserialize(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                    Format(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                    Merge(e) => (e.to_string(), ErrorFerris::Error, ErrorButtons::ResetState),
-                    Parse(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
-                };
-                app.error_state.set(format!("Gupax P2Pool Stats: {}\n\nTry deleting: {}\n\n(Warning: this will delete your P2Pool payout history...!)\n\n", e, app.gupax_p2pool_api_path.display()), ferris, button);
+        if read_api {
+            match gupax_p2pool_api.read_all_files_and_update() {
+                Ok(_) => {
+                    info!(
+                        "GupaxP2poolApi ... Payouts: {} | XMR (atomic-units): {}",
+                        gupax_p2pool_api.payout, gupax_p2pool_api.xmr,
+                    );
+                }
+                Err(err) => {
+                    error!("GupaxP2poolApi ... {}", err);
+                    let (e, ferris, button) = match err {
+                        Io(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                        Path(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                        Serialize(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                        Deserialize(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                        Format(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                        Merge(e) => (e.to_string(), ErrorFerris::Error, ErrorButtons::ResetState),
+                        Parse(e) => (e.to_string(), ErrorFerris::Panic, ErrorButtons::Quit),
+                    };
+                    app.error_state.set_code(code::ErrorCode::StatsRead, format!("Gupax P2Pool Stats: {}\n\nTry deleting: {}\n\n(Warning: this will delete your P2Pool payout history...!)\n\n", e, app.gupax_p2pool_api_path.display()), ferris, button);
+                }
             }
-        };
+        }
         drop(gupax_p2pool_api);
         lock!(app.helper).gupax_p2pool_api = Arc::clone(&app.gupax_p2pool_api);
 
@@ -477,6 +1570,9 @@ impl App {
         if current > max {
             og.xmrig.current_threads = max;
         }
+        // Older [state.toml]'s don't have the CPU usage %, the thread count wins.
+        og.xmrig.cpu_usage = crate::disk::Xmrig::usage_for_threads(og.xmrig.current_threads, max);
+        app.state.xmrig.cpu_usage = og.xmrig.cpu_usage;
         // Handle [node_vec] overflow
         info!("App Init | Handling [node_vec] overflow");
         if og.p2pool.selected_index > app.og_node_vec.len() {
@@ -538,17 +1634,31 @@ impl App {
         info!("App Init | Setting saved [Tab]...");
         app.tab = app.state.gupax.tab;
 
+        // Make sure payouts/sync can still be detected.
+        info!("App Init | Self-testing P2Pool log regexes...");
+        for failed in crate::regex::p2pool_self_test() {
+            error!("App Init | Regex self-test failed: {}", failed);
+            event::push(
+                event::Kind::Notice,
+                format!("Regex self-test failed: {}", failed),
+            );
+        }
+
         // Check if [P2pool.node] exists
         info!("App Init | Checking if saved remote node still exists...");
         app.state.p2pool.node = RemoteNode::check_exists(&app.state.p2pool.node);
 
         drop(og); // Unlock [og]
+        app.sync_saved();
 
         // Spawn the "Helper" thread.
         info!("Helper | Spawning helper thread...");
         Helper::spawn_helper(&app.helper, sysinfo, app.pid, app.max_threads);
         info!("Helper ... OK");
 
+        // VM/container detection, cached for the [XMRig] tab.
+        info!("App Init | Environment ... {}", virt::VIRT.describe());
+
         // Check for privilege. Should be Admin on [Windows] and NOT root on Unix.
         info!("App Init | Checking for privilege level...");
         #[cfg(target_os = "windows")]
@@ -556,13 +1666,13 @@ impl App {
             app.admin = true;
         } else {
             error!("Windows | Admin user not detected!");
-            app.error_state.set(format!("Gupax was not launched as Administrator!\nBe warned, XMRig might have less hashrate!"), ErrorFerris::Sudo, ErrorButtons::WindowsAdmin);
+            app.error_state.set_code(code::ErrorCode::NotAdmin, format!("Gupax was not launched as Administrator!\nBe warned, XMRig might have less hashrate!"), ErrorFerris::Sudo, ErrorButtons::WindowsAdmin);
         }
         #[cfg(target_family = "unix")]
         if sudo_check::check() != sudo_check::RunningAs::User {
             let id = sudo_check::check();
             error!("Unix | Regular user not detected: [{:?}]", id);
-            app.error_state.set(format!("Gupax was launched as: [{:?}]\nPlease launch Gupax with regular user permissions.", id), ErrorFerris::Panic, ErrorButtons::Quit);
+            app.error_state.set_code(code::ErrorCode::Root, format!("Gupax was launched as: [{:?}]\nPlease launch Gupax with regular user permissions.", id), ErrorFerris::Panic, ErrorButtons::Quit);
         }
 
         // macOS re-locates "dangerous" applications into some read-only "/private" directory.
@@ -570,7 +1680,7 @@ impl App {
         // So, detect if we are in in "/private" and warn the user.
         #[cfg(target_os = "macos")]
         if app.exe.starts_with("/private") {
-            app.error_state.set(format!("macOS thinks Gupax is a virus!\n(macOS has relocated Gupax for security reasons)\n\nThe directory: [{}]\nSince this is a private read-only directory, it causes issues with updates and correctly locating P2Pool/XMRig. Please move Gupax into the [Applications] directory, this lets macOS relax a little.\n", app.exe), ErrorFerris::Panic, ErrorButtons::Quit);
+            app.error_state.set_code(code::ErrorCode::Translocated, format!("macOS thinks Gupax is a virus!\n(macOS has relocated Gupax for security reasons)\n\nThe directory: [{}]\nSince this is a private read-only directory, it causes issues with updates and correctly locating P2Pool/XMRig. Please move Gupax into the [Applications] directory, this lets macOS relax a little.\n", app.exe), ErrorFerris::Panic, ErrorButtons::Quit);
         }
 
         info!("App ... OK");
@@ -584,6 +1694,12 @@ impl App {
             return None;
         }
 
+        // Older P2Pool's only accept a single [--host].
+        if !lock!(self.p2pool_probe).supports(probe::P2POOL_MIN_BACKUP_HOSTS) {
+            warn!("Backup hosts ... P2Pool version is too old, returning None");
+            return None;
+        }
+
         // INVARIANT:
         // We must ensure all nodes are capable of
         // sending/receiving valid JSON-RPC requests.
@@ -624,6 +1740,7 @@ impl App {
                     ip: ip.into(),
                     rpc: rpc.into(),
                     zmq: zmq.into(),
+                    tags: vec![],
                 };
 
                 vec.push(node);
@@ -651,6 +1768,7 @@ pub enum Tab {
     Gupax,
     P2pool,
     Xmrig,
+    Proxy,
 }
 
 impl Default for Tab {
@@ -659,6 +1777,70 @@ impl Default for Tab {
     }
 }
 
+impl Tab {
+    // The sections a tab's [Apply/Revert] buttons cover.
+    const fn sections(&self) -> &'static [Section] {
+        match self {
+            Self::About => &[],
+            Self::Status => &[Section::Status],
+            Self::Gupax => &[Section::Gupax],
+            Self::P2pool => &[Section::P2pool, Section::Node],
+            Self::Xmrig => &[Section::Xmrig, Section::Pool],
+            Self::Proxy => &[Section::Proxy],
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Section] Enum + Impl
+// The parts of the settings that can be saved/reverted on their own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Section {
+    Status,
+    Gupax,
+    P2pool,
+    Xmrig,
+    Proxy,
+    Node, // [node.toml]
+    Pool, // [pool.toml]
+}
+
+impl Section {
+    const ALL: [Self; 7] = [
+        Self::Status,
+        Self::Gupax,
+        Self::P2pool,
+        Self::Xmrig,
+        Self::Proxy,
+        Self::Node,
+        Self::Pool,
+    ];
+
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Status => "Status",
+            Self::Gupax => "Gupax",
+            Self::P2pool => "P2Pool",
+            Self::Xmrig => "XMRig",
+            Self::Proxy => "XMRig-Proxy",
+            Self::Node => "Node list",
+            Self::Pool => "Pool list",
+        }
+    }
+
+    // Copy this section of [from] into [to].
+    // [Node/Pool] aren't part of [State], they're handled by [App].
+    fn copy(&self, from: &State, to: &mut State) {
+        match self {
+            Self::Status => to.status = from.status.clone(),
+            Self::Gupax => to.gupax = from.gupax.clone(),
+            Self::P2pool => to.p2pool = from.p2pool.clone(),
+            Self::Xmrig => to.xmrig = from.xmrig.clone(),
+            Self::Proxy => to.proxy = from.proxy.clone(),
+            Self::Node | Self::Pool => (),
+        }
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- CPU Benchmarks.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Benchmark {
@@ -671,6 +1853,130 @@ pub struct Benchmark {
     pub low: f32,
 }
 
+impl Benchmark {
+    // Only some CPU names include their core count, e.g: [AMD EPYC 7763 64-Core Processor].
+    pub fn cores(&self) -> Option<u16> {
+        let end = self.cpu.find("-Core")?;
+        let start = self.cpu[..end]
+            .rfind(|c: char| !c.is_ascii_digit())
+            .map_or(0, |i| i + 1);
+        self.cpu[start..end].parse().ok().filter(|c| *c != 0)
+    }
+
+    pub fn per_core(&self) -> Option<f32> {
+        self.cores().map(|c| self.average / c as f32)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BenchmarkSort {
+    #[default]
+    Similarity, // Most similar to the user's CPU first
+    Rank,
+    Average,
+    PerCore,
+}
+
+// The [Gupax] tab's payout backup picker, and the last automatic backup.
+#[derive(Clone, Debug, Default)]
+pub struct PayoutBackups {
+    pub list: Vec<PathBuf>, // Newest first
+    pub selected: usize,
+    pub msg: String,
+    last: Option<Instant>, // [None] means one is due at startup
+}
+
+impl PayoutBackups {
+    pub const INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+    pub fn due(&self) -> bool {
+        self.last
+            .is_none_or(|last| last.elapsed() >= Self::INTERVAL)
+    }
+
+    pub fn refresh(&mut self, gupax_p2pool_dir: &Path) {
+        self.list = GupaxP2poolApi::backups(gupax_p2pool_dir);
+        self.selected = self.selected.min(self.list.len().saturating_sub(1));
+    }
+
+    // The [GupaxP2poolApi] lock should be held while this runs.
+    pub fn backup(&mut self, gupax_p2pool_dir: &Path, keep: u8) {
+        self.last = Some(Instant::now());
+        self.msg = match GupaxP2poolApi::backup(gupax_p2pool_dir, keep as usize) {
+            Ok(Some(dir)) => format!("Backed up to [{}]", dir.display()),
+            Ok(None) => "Nothing changed since the last backup".to_string(),
+            Err(e) => {
+                error!("GupaxP2poolApi | Backup ... FAIL: {}", e);
+                format!("Backup failed: {}", e)
+            }
+        };
+        self.refresh(gupax_p2pool_dir);
+    }
+}
+
+// The [Status/Benchmarks] search box, sort column and pinned CPUs.
+#[derive(Clone, Debug, Default)]
+pub struct BenchmarkView {
+    pub search: String,
+    pub sort: BenchmarkSort,
+    pub reverse: bool,
+    pub pinned: Vec<usize>, // Indices into the benchmark [Vec], max [Self::MAX_PINNED]
+}
+
+impl BenchmarkView {
+    pub const MAX_PINNED: usize = 3;
+
+    // Clicking the current sort column flips it, another column sorts by it.
+    pub fn sort_by(&mut self, sort: BenchmarkSort) {
+        if self.sort == sort {
+            self.reverse = !self.reverse;
+        } else {
+            self.sort = sort;
+            self.reverse = false;
+        }
+    }
+
+    pub fn pin(&mut self, index: usize) {
+        if let Some(i) = self.pinned.iter().position(|p| *p == index) {
+            self.pinned.remove(i);
+        } else if self.pinned.len() < Self::MAX_PINNED {
+            self.pinned.push(index);
+        }
+    }
+
+    // The benchmarks to list (skipping [0], the user's CPU), in order.
+    // Every word of the search has to be in the CPU name.
+    pub fn indices(&self, benchmarks: &[Benchmark]) -> Vec<usize> {
+        let words: Vec<String> = self
+            .search
+            .split_whitespace()
+            .map(|w| w.to_lowercase())
+            .collect();
+        let mut indices: Vec<usize> = (1..benchmarks.len())
+            .filter(|i| {
+                let cpu = benchmarks[*i].cpu.to_lowercase();
+                words.iter().all(|w| cpu.contains(w))
+            })
+            .collect();
+        // Best first, unknown per-core last.
+        match self.sort {
+            BenchmarkSort::Similarity => (),
+            BenchmarkSort::Rank => indices.sort_by_key(|i| benchmarks[*i].rank),
+            BenchmarkSort::Average => indices.sort_by(|a, b| {
+                cmp_f64(benchmarks[*b].average as f64, benchmarks[*a].average as f64)
+            }),
+            BenchmarkSort::PerCore => indices.sort_by(|a, b| {
+                let (a, b) = (benchmarks[*a].per_core(), benchmarks[*b].per_core());
+                cmp_f64(b.unwrap_or(-1.0) as f64, a.unwrap_or(-1.0) as f64)
+            }),
+        }
+        if self.reverse {
+            indices.reverse();
+        }
+        indices
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- [Restart] Enum
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Restart {
@@ -690,6 +1996,9 @@ pub enum ErrorButtons {
     Sudo,
     WindowsAdmin,
     Debug,
+    UnsavedStart,
+    BinaryChanged,
+    Quarantined,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -703,11 +2012,12 @@ pub enum ErrorFerris {
 }
 
 pub struct ErrorState {
-    error: bool,           // Is there an error?
-    msg: String,           // What message to display?
-    ferris: ErrorFerris,   // Which ferris to display?
-    buttons: ErrorButtons, // Which buttons to display?
-    quit_twice: bool,      // This indicates the user tried to quit on the [ask_before_quit] screen
+    error: bool,                   // Is there an error?
+    msg: String,                   // What message to display?
+    ferris: ErrorFerris,           // Which ferris to display?
+    buttons: ErrorButtons,         // Which buttons to display?
+    quit_twice: bool, // This indicates the user tried to quit on the [ask_before_quit] screen
+    code: Option<code::ErrorCode>, // The troubleshooting code, if this is an error
 }
 
 impl Default for ErrorState {
@@ -724,11 +2034,35 @@ impl ErrorState {
             ferris: ErrorFerris::Oops,
             buttons: ErrorButtons::Okay,
             quit_twice: false,
+            code: None,
         }
     }
 
     // Convenience function to enable the [App] error state
     pub fn set(&mut self, msg: impl Into<String>, ferris: ErrorFerris, buttons: ErrorButtons) {
+        self.replace(None, msg.into(), ferris, buttons);
+    }
+
+    // Same as [set()], for actual errors: the [code] is shown with the message.
+    pub fn set_code(
+        &mut self,
+        code: code::ErrorCode,
+        msg: impl Into<String>,
+        ferris: ErrorFerris,
+        buttons: ErrorButtons,
+    ) {
+        let msg = msg.into();
+        error!("ErrorState | [{}] {}", code, msg.replace('\n', " "));
+        self.replace(Some(code), msg, ferris, buttons);
+    }
+
+    fn replace(
+        &mut self,
+        code: Option<code::ErrorCode>,
+        msg: String,
+        ferris: ErrorFerris,
+        buttons: ErrorButtons,
+    ) {
         if self.error {
             // If a panic error is already set and there isn't an [Okay] confirm or another [Panic], return
             if self.ferris == ErrorFerris::Panic
@@ -739,13 +2073,22 @@ impl ErrorState {
         }
         *self = Self {
             error: true,
-            msg: msg.into(),
+            msg,
             ferris,
             buttons,
             quit_twice: false,
+            code,
         };
     }
 
+    // The [code] line under the screen's title, empty if there's no code.
+    fn code_text(&self) -> String {
+        match self.code {
+            Some(code) => format!("\n[{}] {} (see Help -> Error codes)", code, code.title()),
+            None => String::new(),
+        }
+    }
+
     // Just sets the current state to new, resetting it.
     pub fn reset(&mut self) {
         *self = Self::new();
@@ -762,6 +2105,7 @@ impl ErrorState {
             ferris: ErrorFerris::Sudo,
             buttons: ErrorButtons::Sudo,
             quit_twice: false,
+            code: None,
         };
         SudoState::reset(state)
     }
@@ -792,6 +2136,7 @@ impl Images {
 // That's ugly, so these are used instead so a simple compare can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum KeyPressed {
+    F1,
     F11,
     Up,
     Down,
@@ -807,6 +2152,10 @@ enum KeyPressed {
 }
 
 impl KeyPressed {
+    #[inline]
+    fn is_f1(&self) -> bool {
+        *self == Self::F1
+    }
     #[inline]
     fn is_f11(&self) -> bool {
         *self == Self::F11
@@ -972,8 +2321,11 @@ fn init_logger(now: Instant) {
 
 #[cold]
 #[inline(never)]
-fn init_options(initial_window_size: Option<Vec2>) -> NativeOptions {
+fn init_options(initial_window_size: Option<Vec2>, renderer: gfx::Renderer) -> NativeOptions {
     let mut options = eframe::NativeOptions::default();
+    if let Some(renderer) = gfx::renderer(renderer).to_eframe() {
+        options.renderer = renderer;
+    }
     options.viewport.min_inner_size = Some(Vec2::new(APP_MIN_WIDTH, APP_MIN_HEIGHT));
     options.viewport.max_inner_size = Some(Vec2::new(APP_MAX_WIDTH, APP_MAX_HEIGHT));
     options.viewport.inner_size = initial_window_size;
@@ -1006,9 +2358,17 @@ fn init_auto(app: &mut App) {
         info!("Starting init_auto()...");
     }
 
+    // [Usage stats], opt-in only.
+    if app.state.gupax.usage_stats && !demo::enabled() {
+        telemetry::UsageStats::spawn_thread(&app.usage_stats, &app.state);
+    }
+
     // [Auto-Update]
     #[cfg(not(feature = "distro"))]
-    if app.state.gupax.auto_update {
+    if app.state.gupax.auto_update
+        && app.state.gupax.update_scope().contains(&true)
+        && !demo::enabled()
+    {
         Update::spawn_thread(
             &app.og,
             &app.state.gupax,
@@ -1028,22 +2388,19 @@ fn init_auto(app: &mut App) {
         info!("Skipping auto-ping...");
     }
 
+    // [SSH tunnel], the local ports have to exist before P2Pool starts.
+    app.ssh_tunnel();
+
     // [Auto-P2Pool]
+    let now = std::time::Instant::now();
+    let wait_network = app.state.gupax.wait_for_network;
     if app.state.gupax.auto_p2pool {
-        if !Regexes::addr_ok(&app.state.p2pool.address) {
-            warn!("Gupax | P2Pool address is not valid! Skipping auto-p2pool...");
-        } else if !Gupax::path_is_file(&app.state.gupax.p2pool_path) {
-            warn!("Gupax | P2Pool path is not a file! Skipping auto-p2pool...");
-        } else if !crate::update::check_p2pool_path(&app.state.gupax.p2pool_path) {
-            warn!("Gupax | P2Pool path is not valid! Skipping auto-p2pool...");
+        let delay = app.state.gupax.p2pool_start_delay;
+        if (delay > 0 || wait_network) && !demo::enabled() {
+            app.delayed_start
+                .queue(ProcessName::P2pool, delay, wait_network, now);
         } else {
-            let backup_hosts = app.gather_backup_hosts();
-            Helper::start_p2pool(
-                &app.helper,
-                &app.state.p2pool,
-                &app.state.gupax.absolute_p2pool_path,
-                backup_hosts,
-            );
+            app.auto_start_p2pool();
         }
     } else {
         info!("Skipping auto-p2pool...");
@@ -1051,26 +2408,60 @@ fn init_auto(app: &mut App) {
 
     // [Auto-XMRig]
     if app.state.gupax.auto_xmrig {
-        if !Gupax::path_is_file(&app.state.gupax.xmrig_path) {
-            warn!("Gupax | XMRig path is not an executable! Skipping auto-xmrig...");
-        } else if !crate::update::check_xmrig_path(&app.state.gupax.xmrig_path) {
-            warn!("Gupax | XMRig path is not valid! Skipping auto-xmrig...");
-        } else if cfg!(windows) {
-            Helper::start_xmrig(
-                &app.helper,
-                &app.state.xmrig,
-                &app.state.gupax.absolute_xmrig_path,
-                Arc::clone(&app.sudo),
-            );
+        let delay = app.state.gupax.xmrig_start_delay;
+        if (delay > 0 || wait_network) && !demo::enabled() {
+            app.delayed_start
+                .queue(ProcessName::Xmrig, delay, wait_network, now);
         } else {
-            lock!(app.sudo).signal = ProcessSignal::Start;
-            app.error_state.ask_sudo(&app.sudo);
+            app.auto_start_xmrig();
         }
     } else {
         info!("Skipping auto-xmrig...");
     }
 }
 
+impl App {
+    // The [Auto-P2Pool] checks + start, right away
+    // from [init_auto()] or once its start delay passed.
+    fn auto_start_p2pool(&mut self) {
+        if demo::enabled() {
+            Helper::start_p2pool(
+                &self.helper,
+                &self.state.p2pool,
+                &self.state.gupax.absolute_p2pool_path,
+                None,
+            );
+        } else if !Regexes::addr_ok(&self.state.p2pool.address, self.state.p2pool.network()) {
+            warn!("Gupax | P2Pool address is not valid! Skipping auto-p2pool...");
+        } else if !Gupax::path_is_file(&self.state.gupax.p2pool_path) {
+            warn!("Gupax | P2Pool path is not a file! Skipping auto-p2pool...");
+        } else if !crate::update::check_p2pool_path(&self.state.gupax.p2pool_path) {
+            warn!("Gupax | P2Pool path is not valid! Skipping auto-p2pool...");
+        } else {
+            self.start(ProcessName::P2pool, ProcessSignal::Start);
+        }
+    }
+
+    // The [Auto-XMRig] checks + start.
+    fn auto_start_xmrig(&mut self) {
+        self.sync_launcher();
+        if !self.state.xmrig.needs_binary() {
+            Helper::start_xmrig(
+                &self.helper,
+                &self.state.xmrig,
+                &self.state.gupax.absolute_xmrig_path,
+                Arc::clone(&self.sudo),
+            );
+        } else if !Gupax::path_is_file(&self.state.gupax.xmrig_path) {
+            warn!("Gupax | XMRig path is not an executable! Skipping auto-xmrig...");
+        } else if !crate::update::check_xmrig_path(&self.state.gupax.xmrig_path) {
+            warn!("Gupax | XMRig path is not valid! Skipping auto-xmrig...");
+        } else {
+            self.start(ProcessName::Xmrig, ProcessSignal::Start);
+        }
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- Reset functions
 #[cold]
 #[inline(never)]
@@ -1213,8 +2604,54 @@ fn parse_args<S: Into<String>>(mut app: App, panic: S) -> App {
     }
 
     // Everything else
-    for arg in args {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
         match arg.as_str() {
+            "--profile" => {
+                let name = profile_arg(&app, args.next());
+                let path = crate::disk::profile_path(&app.os_data_path, &name).unwrap();
+                if !path.is_file() {
+                    eprintln!(
+                        "\n[Gupax error] Profile [{}] not found at [{}]\nSaved profiles: {:?}\nTo save the current settings as one, use: [--save-profile {}]",
+                        name,
+                        path.display(),
+                        crate::disk::list_profiles(&app.os_data_path),
+                        name
+                    );
+                    exit(1);
+                }
+                info!("Using profile [{}] ... {}", name, path.display());
+                app.state_path = path;
+                rig::set_profile(Some(&name));
+                app.profile = Some(name);
+            }
+            "--save-profile" => {
+                let name = profile_arg(&app, args.next());
+                let path = crate::disk::profile_path(&app.os_data_path, &name).unwrap();
+                // Read (or create) the state first, it may not exist yet on a fresh install.
+                let result = (|| -> Result<(), TomlError> {
+                    if let Some(dir) = path.parent() {
+                        std::fs::create_dir_all(dir)?;
+                    }
+                    State::get(&app.state_path, app.read_only)?.save(&path)
+                })();
+                match result {
+                    Ok(_) => {
+                        println!("\nProfile [{}] saved ... {}", name, path.display());
+                        exit(0);
+                    }
+                    Err(e) => {
+                        eprintln!("\nProfile [{}] save ... FAIL: {}", name, e);
+                        exit(1);
+                    }
+                }
+            }
+            "--profiles" => {
+                for name in crate::disk::list_profiles(&app.os_data_path) {
+                    println!("{}", name);
+                }
+                exit(0);
+            }
             "--state" => {
                 info!("Printing state...");
                 print_disk_file(&app.state_path);
@@ -1271,6 +2708,10 @@ fn parse_args<S: Into<String>>(mut app: App, panic: S) -> App {
                 &app.gupax_p2pool_api_path,
             ),
             "--no-startup" => app.no_startup = true,
+            "--demo" => {
+                info!("Demo mode, P2Pool/XMRig will be simulated");
+                demo::enable();
+            }
             _ => {
                 eprintln!(
                     "\n[Gupax error] Invalid option: [{}]\nFor help, use: [--help]",
@@ -1283,6 +2724,23 @@ fn parse_args<S: Into<String>>(mut app: App, panic: S) -> App {
     app
 }
 
+// The [<name>] after [--profile/--save-profile], exits if it's missing or not a valid name.
+#[cold]
+#[inline(never)]
+fn profile_arg(app: &App, name: Option<String>) -> String {
+    match name {
+        Some(name) if crate::disk::profile_path(&app.os_data_path, &name).is_some() => name,
+        Some(name) => {
+            eprintln!("\n[Gupax error] Invalid profile name: [{}]\nOnly letters, numbers, [-] and [_] are allowed", name);
+            exit(1);
+        }
+        None => {
+            eprintln!("\n[Gupax error] Missing profile name\nFor help, use: [--help]");
+            exit(1);
+        }
+    }
+}
+
 // Get absolute [Gupax] binary path
 #[cold]
 #[inline(never)]
@@ -1356,6 +2814,31 @@ fn print_disk_file(path: &PathBuf) {
     }
 }
 
+// Builds the window title out of the current P2Pool/XMRig state.
+// Failures come first since they're the most important thing to see.
+fn window_title(
+    name_version: &str,
+    p2pool_state: ProcessState,
+    xmrig_state: ProcessState,
+    hashrate: f32,
+    share: &crate::human::HumanTime,
+) -> String {
+    let mut title = vec![name_version.to_string()];
+    if p2pool_state == ProcessState::Failed {
+        title.push("P2Pool failed".to_string());
+    }
+    if xmrig_state == ProcessState::Failed {
+        title.push("XMRig failed".to_string());
+    }
+    if xmrig_state == ProcessState::Alive && hashrate > 0.0 {
+        title.push(crate::human::HumanNumber::to_hashrate_short(hashrate).localized());
+    }
+    if p2pool_state == ProcessState::Alive && !share.is_zero() {
+        title.push(format!("share in ~{}", share.rough()));
+    }
+    title.join(" — ")
+}
+
 // Prints the GupaxP2PoolApi files.
 #[cold]
 #[inline(never)]
@@ -1400,6 +2883,38 @@ fn print_gupax_p2pool_api(gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>) {
 }
 
 #[inline]
+// The color and letter of a process state in the bottom bar/tabs.
+// [busy] is the process's own "online but not there yet" state
+// (P2Pool [Syncing], XMRig [NotMining]), which is orange.
+fn state_indicator(
+    state: ProcessState,
+    busy: ProcessState,
+    colorblind: bool,
+) -> (egui::Color32, char) {
+    use ProcessState::*;
+    match (state, colorblind) {
+        (Alive, false) => (GREEN, 'A'),
+        (Alive, true) => (CB_BLUE, 'A'),
+        (Dead, _) => (GRAY, 'D'),
+        (Failed, false) => (RED, 'F'),
+        (Failed, true) => (CB_VERMILLION, 'F'),
+        (s, false) if s == busy => (ORANGE, 'S'),
+        (s, true) if s == busy => (CB_ORANGE, 'S'),
+        (_, false) => (YELLOW, 'W'),
+        (_, true) => (CB_YELLOW, 'W'),
+    }
+}
+
+// Mark a status dot whose process has saved settings it hasn't picked up yet,
+// returns the hover text listing them.
+fn restart_badge(text: &mut String, hover: &str, changed: &[&str]) -> String {
+    if changed.is_empty() {
+        return hover.to_string();
+    }
+    text.push_str(" *");
+    format!("{}\n\n{} [{}]", hover, RESTART_NEEDED, changed.join(", "))
+}
+
 fn cmp_f64(a: f64, b: f64) -> std::cmp::Ordering {
     match (a <= b, a >= b) {
         (false, true) => std::cmp::Ordering::Greater,
@@ -1419,6 +2934,8 @@ fn main() {
     // Init logger.
     init_logger(now);
     let mut app = App::new(now);
+    // Must happen before anything is started, the process gets replaced.
+    gfx::relaunch_x11(app.state.gupax.gfx_x11);
     init_auto(&mut app);
 
     // Init GUI stuff.
@@ -1434,7 +2951,7 @@ fn main() {
             app.state.gupax.selected_height as f32,
         ))
     };
-    let options = init_options(initial_window_size);
+    let options = init_options(initial_window_size, app.state.gupax.gfx_renderer);
 
     // Gupax folder cleanup.
     match clean_dir() {
@@ -1500,7 +3017,9 @@ impl eframe::App for App {
 
         // If [F11] was pressed, reverse [fullscreen] bool
         let key: KeyPressed = ctx.input_mut(|input| {
-            if input.consume_key(Modifiers::NONE, Key::F11) {
+            if input.consume_key(Modifiers::NONE, Key::F1) {
+                KeyPressed::F1
+            } else if input.consume_key(Modifiers::NONE, Key::F11) {
                 KeyPressed::F11
             } else if input.consume_key(Modifiers::NONE, Key::Z) {
                 KeyPressed::Z
@@ -1536,14 +3055,20 @@ impl eframe::App for App {
             if ctx.input(|i| i.viewport().maximized == Some(true)) {
                 ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
             }
+        // Open/close [Help]
+        } else if key.is_f1() {
+            self.help.toggle(self.tab);
+        } else if key.is_esc() && self.help.open && !self.error_state.error {
+            self.help.open = false;
         // Change Tabs LEFT
         } else if key.is_z() && !wants_input {
             match self.tab {
-                Tab::About => self.tab = Tab::Xmrig,
+                Tab::About => self.tab = Tab::Proxy,
                 Tab::Status => self.tab = Tab::About,
                 Tab::Gupax => self.tab = Tab::Status,
                 Tab::P2pool => self.tab = Tab::Gupax,
                 Tab::Xmrig => self.tab = Tab::P2pool,
+                Tab::Proxy => self.tab = Tab::Xmrig,
             };
         // Change Tabs RIGHT
         } else if key.is_x() && !wants_input {
@@ -1552,15 +3077,18 @@ impl eframe::App for App {
                 Tab::Status => self.tab = Tab::Gupax,
                 Tab::Gupax => self.tab = Tab::P2pool,
                 Tab::P2pool => self.tab = Tab::Xmrig,
-                Tab::Xmrig => self.tab = Tab::About,
+                Tab::Xmrig => self.tab = Tab::Proxy,
+                Tab::Proxy => self.tab = Tab::About,
             };
         // Change Submenu LEFT
         } else if key.is_c() && !wants_input {
             match self.tab {
                 Tab::Status => match self.state.status.submenu {
-                    Submenu::Processes => self.state.status.submenu = Submenu::Benchmarks,
+                    Submenu::Processes => self.state.status.submenu = Submenu::Events,
                     Submenu::P2pool => self.state.status.submenu = Submenu::Processes,
                     Submenu::Benchmarks => self.state.status.submenu = Submenu::P2pool,
+                    Submenu::Consoles => self.state.status.submenu = Submenu::Benchmarks,
+                    Submenu::Events => self.state.status.submenu = Submenu::Consoles,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
                 Tab::P2pool => flip!(self.state.p2pool.simple),
@@ -1573,7 +3101,9 @@ impl eframe::App for App {
                 Tab::Status => match self.state.status.submenu {
                     Submenu::Processes => self.state.status.submenu = Submenu::P2pool,
                     Submenu::P2pool => self.state.status.submenu = Submenu::Benchmarks,
-                    Submenu::Benchmarks => self.state.status.submenu = Submenu::Processes,
+                    Submenu::Benchmarks => self.state.status.submenu = Submenu::Consoles,
+                    Submenu::Consoles => self.state.status.submenu = Submenu::Events,
+                    Submenu::Events => self.state.status.submenu = Submenu::Processes,
                 },
                 Tab::Gupax => flip!(self.state.gupax.simple),
                 Tab::P2pool => flip!(self.state.p2pool.simple),
@@ -1582,26 +3112,228 @@ impl eframe::App for App {
             };
         }
 
-        // Refresh AT LEAST once a second
-        debug!("App | Refreshing frame once per second");
-        ctx.request_repaint_after(SECOND);
+        // Refresh AT LEAST once a second
+        debug!("App | Refreshing frame once per second");
+        ctx.request_repaint_after(SECOND);
+
+        // Get P2Pool/XMRig process state.
+        // These values are checked multiple times so
+        // might as well check only once here to save
+        // on a bunch of [.lock().unwrap()]s.
+        debug!("App | Locking and collecting P2Pool state...");
+        let p2pool = lock!(self.p2pool);
+        let p2pool_is_alive = p2pool.is_alive();
+        let p2pool_is_waiting = p2pool.is_waiting();
+        let p2pool_state = p2pool.state;
+        drop(p2pool);
+        debug!("App | Locking and collecting XMRig state...");
+        let xmrig = lock!(self.xmrig);
+        let xmrig_is_alive = xmrig.is_alive();
+        let xmrig_is_waiting = xmrig.is_waiting();
+        let xmrig_state = xmrig.state;
+        drop(xmrig);
+        debug!("App | Locking and collecting XMRig-Proxy state...");
+        let proxy = lock!(self.proxy);
+        let proxy_is_alive = proxy.is_alive();
+        let proxy_is_waiting = proxy.is_waiting();
+        let proxy_state = proxy.state;
+        drop(proxy);
+        self.availability.tick(
+            Instant::now(),
+            p2pool_state,
+            xmrig_state,
+            !self.read_only && !demo::enabled(),
+        );
+
+        // Stratum TLS terminator, runs alongside P2Pool.
+        self.tls_proxy(p2pool_is_alive);
+        // SSH tunnel, runs whenever it's enabled.
+        self.ssh_tunnel();
+        // Pool-side hashrate, while XMRig mines to a pool with a known API.
+        self.pool_hashrate(xmrig_is_alive);
+        // Main vs Mini advice, while mining.
+        self.chain_advisor(p2pool_is_alive, xmrig_is_alive);
+
+        // Display formats, these are global (see [human.rs]).
+        human::set_format(
+            self.state.gupax.number_format,
+            self.state.gupax.short_time,
+            self.state.gupax.hashrate_unit,
+        );
+
+        // Window title stats.
+        self.update_title(ctx, p2pool_state, xmrig_state);
+
+        // Mining history for the payout reports, once a minute.
+        if self.history_now.elapsed() >= report::HISTORY_INTERVAL {
+            let secs = self.history_now.elapsed().as_secs();
+            self.history_now = Instant::now();
+            if xmrig_is_alive && !demo::enabled() && !self.read_only {
+                let hashrate = lock!(self.xmrig_api).hashrate_raw;
+                if let Err(e) = lock!(self.gupax_p2pool_api).add_history(secs, hashrate as f64) {
+                    error!("GupaxP2poolApi | Could not write [history]: {}", e);
+                }
+            }
+        }
+
+        // [Gupax-P2Pool API] backups, every few hours.
+        if self.state.gupax.payout_backups != 0
+            && !demo::enabled()
+            && !self.read_only
+            && self.payout_backups.due()
+        {
+            let _api = lock!(self.gupax_p2pool_api);
+            self.payout_backups
+                .backup(&self.gupax_p2pool_api_path, self.state.gupax.payout_backups);
+        }
+
+        // System clock skew check, every few hours.
+        if self.state.gupax.time_check && !demo::enabled() && lock!(self.clock).due() {
+            clock::Clock::spawn_thread(&self.clock);
+        }
+
+        // [sudo/doas/pkexec] for XMRig.
+        self.sync_launcher();
+        gupax_process::set_console_cap(self.state.gupax.console_cap as usize * 1000);
+
+        // A [Save] that was held back by [SAVE_INTERVAL].
+        if self.save_pending
+            && self
+                .last_save
+                .is_none_or(|last| last.elapsed() >= SAVE_INTERVAL)
+        {
+            self.save();
+        }
+
+        // [events.log], demo events stay in memory.
+        let log = self.state.gupax.event_log && !demo::enabled() && !self.read_only;
+        event::set_log(log.then_some(self.events_path.as_path()));
+
+        // Payout sound, played from the P2Pool PTY thread (see [helper.rs]).
+        sound::set(&self.state.gupax);
+
+        // Console command history files.
+        let history = self.state.gupax.console_history && !demo::enabled() && !self.read_only;
+        let p2pool_history = self.os_data_path.join(P2POOL_HISTORY);
+        let xmrig_history = self.os_data_path.join(XMRIG_HISTORY);
+        self.p2pool_stdin
+            .set_path(history.then_some(p2pool_history.as_path()));
+        self.xmrig_stdin
+            .set_path(history.then_some(xmrig_history.as_path()));
+
+        // Binaries that finished their integrity check, start the trusted ones.
+        for checked in integrity::take_done(&self.integrity_checks) {
+            let (name, signal) = (checked.name, checked.signal);
+            if self.binary_trusted(checked) {
+                self.launch(name, signal);
+            }
+        }
+        if !lock!(self.integrity_checks).is_empty() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // The helper noticed a system sleep/resume.
+        let resumed = lock!(self.helper).resumed.take();
+        if let Some(slept) = resumed {
+            self.resumed(slept, p2pool_is_alive, xmrig_is_alive);
+        }
+
+        // The network changed, re-ping the remote nodes
+        // and switch to (or suggest) the new fastest one.
+        if std::mem::take(&mut lock!(self.helper).network_changed) && self.state.p2pool.simple {
+            info!("App | Network changed, re-pinging remote nodes");
+            // A ping already running started before the change, so wait for the one after it.
+            let ping = lock!(self.ping);
+            self.network_changed = Some(ping.generation + u64::from(ping.pinging));
+        }
+        if let Some(after) = self.network_changed {
+            let (pinging, generation) = {
+                let ping = lock!(self.ping);
+                (ping.pinging, ping.generation)
+            };
+            if !pinging && generation > after {
+                self.reselect_node();
+            } else if !pinging {
+                Ping::spawn_thread(&self.ping);
+            }
+        }
+
+        // Probe the P2Pool binary version if the path changed.
+        if Gupax::path_is_file(&self.state.gupax.p2pool_path)
+            && crate::update::check_p2pool_path(&self.state.gupax.p2pool_path)
+        {
+            probe::BinaryProbe::spawn_if_changed(
+                &self.p2pool_probe,
+                ProcessName::P2pool,
+                &self.state.gupax.p2pool_path,
+            );
+        }
+        // Same for XMRig, this one is cached in [ImgXmrig].
+        let xmrig_probe = Arc::clone(&lock!(self.xmrig_img).probe);
+        if Gupax::path_is_file(&self.state.gupax.xmrig_path)
+            && crate::update::check_xmrig_path(&self.state.gupax.xmrig_path)
+        {
+            probe::BinaryProbe::spawn_if_changed(
+                &xmrig_probe,
+                ProcessName::Xmrig,
+                &self.state.gupax.xmrig_path,
+            );
+        }
 
-        // Get P2Pool/XMRig process state.
-        // These values are checked multiple times so
-        // might as well check only once here to save
-        // on a bunch of [.lock().unwrap()]s.
-        debug!("App | Locking and collecting P2Pool state...");
-        let p2pool = lock!(self.p2pool);
-        let p2pool_is_alive = p2pool.is_alive();
-        let p2pool_is_waiting = p2pool.is_waiting();
-        let p2pool_state = p2pool.state;
-        drop(p2pool);
-        debug!("App | Locking and collecting XMRig state...");
-        let xmrig = lock!(self.xmrig);
-        let xmrig_is_alive = xmrig.is_alive();
-        let xmrig_is_waiting = xmrig.is_waiting();
-        let xmrig_state = xmrig.state;
-        drop(xmrig);
+        // Start-on-login.
+        self.autostart.sync(
+            self.state.gupax.start_on_login,
+            self.state.gupax.start_on_login_quiet,
+            self.no_startup,
+            self.profile.as_deref(),
+        );
+
+        // Global hotkeys.
+        let actions = match &mut self.hotkeys {
+            Some(hotkeys) => {
+                // Saved settings, not a half-typed combo.
+                let og = lock!(self.og);
+                hotkeys.sync(
+                    og.gupax.global_hotkeys,
+                    &og.gupax.hotkey_xmrig,
+                    &og.gupax.hotkey_window,
+                );
+                drop(og);
+                hotkeys.poll()
+            }
+            None => vec![],
+        };
+        for action in actions {
+            self.hotkey(ctx, action, xmrig_is_alive, xmrig_is_waiting);
+        }
+
+        // [Stop after N hours] timer.
+        if let Some(action) = self.timer.due() {
+            self.timer_ran_out(ctx, action, p2pool_is_alive, xmrig_is_alive);
+        }
+
+        // [Auto-P2Pool/XMRig] start delay, a process
+        // started by hand in the meantime is left alone.
+        if !self.delayed_start.is_empty() {
+            match self.delayed_start.due(Instant::now()) {
+                Some(ProcessName::P2pool) if !p2pool_is_alive => self.auto_start_p2pool(),
+                Some(ProcessName::Xmrig) if !xmrig_is_alive => self.auto_start_xmrig(),
+                Some(name) => info!("App | {} already started, skipping its delayed start", name),
+                None => (),
+            }
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
+        // A watchdog couldn't spawn its binary, probably an antivirus.
+        if let Some(suspect) = quarantine::take() {
+            self.error_state.set_code(
+                code::ErrorCode::Quarantined,
+                suspect.text(),
+                ErrorFerris::Error,
+                ErrorButtons::Quarantined,
+            );
+            self.quarantined = Some(suspect);
+        }
 
         // This sets the top level Ui dimensions.
         // Used as a reference for other uis.
@@ -1627,6 +3359,21 @@ impl eframe::App for App {
             self.resizing = true;
             self.must_resize = false;
         }
+        // The window moved to another display (or this is the first frame that knows which one),
+        // egui keeps the old zoom factor so re-apply this display's own scale.
+        let display = crate::display::current(ctx);
+        if display.is_some() && display != self.display {
+            let scale = self.state.gupax.scale(display.as_deref());
+            info!(
+                "App | Window is on display [{}], scale: [{}]",
+                display.as_deref().unwrap_or_default(),
+                scale
+            );
+            if self.display.is_some() || scale != self.state.gupax.selected_scale {
+                self.resizing = true;
+            }
+            self.display = display;
+        }
         // This (ab)uses [Area] and [TextEdit] to overlay a full black layer over whatever UI we had before.
         // It incrementally becomes more opaque until [self.alpha] >= 250, when we just switch to pure black (no alpha).
         // When black, we're safe to [init_text_styles()], and then incrementally go transparent, until we remove the layer.
@@ -1660,7 +3407,11 @@ impl eframe::App for App {
                             "App | Resizing frame to match new internal resolution: [{}x{}]",
                             self.width, self.height
                         );
-                        init_text_styles(ctx, self.width, self.state.gupax.selected_scale);
+                        init_text_styles(
+                            ctx,
+                            self.width,
+                            self.state.gupax.scale(self.display.as_deref()),
+                        );
                         self.resizing = false;
                     }
                 });
@@ -1710,17 +3461,30 @@ impl eframe::App for App {
 						ui.add_sized([width, height], Label::new(text))
 					},
 					ResetState => {
-						ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an error! ---\n{}", &self.error_state.msg)));
+						ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an error! ---{}\n{}", self.error_state.code_text(), &self.error_state.msg)));
 						ui.add_sized([width, height], Label::new("Reset Gupax state? (Your settings)"))
 					},
+					UnsavedStart => {
+						ui.add_sized([width, height], Label::new("--- Unsaved changes! ---"));
+						ui.add_sized([width, height], Label::new(&self.error_state.msg))
+					},
+					BinaryChanged => {
+						ui.add_sized([width, height], Label::new(RichText::new(format!("--- WARNING: Binary changed! ---{}", self.error_state.code_text())).color(RED)));
+						ui.add_sized([width, height], Label::new(&self.error_state.msg))
+					},
+					Quarantined => {
+						ui.add_sized([width, height/2.0], Label::new(RichText::new(format!("--- Binary removed or blocked by antivirus! ---{}", self.error_state.code_text())).color(RED)));
+						ui.add_sized([width, height*1.5], Label::new(&self.error_state.msg))
+					},
 					ResetNode  => {
-						ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an error! ---\n{}", &self.error_state.msg)));
+						ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an error! ---{}\n{}", self.error_state.code_text(), &self.error_state.msg)));
 						ui.add_sized([width, height], Label::new("Reset the manual node list?"))
 					},
 					ErrorButtons::Sudo => {
 						let text = format!("Why does XMRig need admin privilege?\n{}", XMRIG_ADMIN_REASON);
 						let height = height/4.0;
-						ui.add_sized([width, height], Label::new(format!("--- Gupax needs sudo/admin privilege for XMRig! ---\n{}", &self.error_state.msg)));
+						let program = lock!(self.sudo).launcher.launcher().program();
+						ui.add_sized([width, height], Label::new(format!("--- Gupax needs {}/admin privilege for XMRig! ---\n{}", program, &self.error_state.msg)));
 						ui.style_mut().override_text_style = Some(Name("MonospaceSmall".into()));
 						ui.add_sized([width/2.0, height], Label::new(text));
 						ui.add_sized([width, height], Hyperlink::from_label_and_url("Click here for more info.", "https://xmrig.com/docs/miner/randomx-optimization-guide"))
@@ -1736,10 +3500,11 @@ impl eframe::App for App {
 						ui.label("")
 					},
 					_ => {
+						let code = self.error_state.code_text();
 						match self.error_state.ferris {
-							Panic => ui.add_sized([width, height], Label::new("--- Gupax has encountered an unrecoverable error! ---")),
+							Panic => ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an unrecoverable error! ---{}", code))),
 							Happy => ui.add_sized([width, height], Label::new("--- Success! ---")),
-							_ => ui.add_sized([width, height], Label::new("--- Gupax has encountered an error! ---")),
+							_ => ui.add_sized([width, height], Label::new(format!("--- Gupax has encountered an error! ---{}", code))),
 						};
 						let height = height/2.0;
 						// Show GitHub rant link for Windows admin problems.
@@ -1754,6 +3519,31 @@ impl eframe::App for App {
 						}
 					},
 				};
+				// Actual errors can be copied as a pre-filled bug report.
+				let copyable = self.error_state.code.is_some() || matches!(self.error_state.ferris, Error|Panic);
+				if copyable && ui.add_sized([width/2.0, height/4.0], Button::new("Copy diagnostic for this error")).on_hover_text(ERROR_COPY_DIAGNOSTIC).clicked() {
+					let version = lock!(self.state.version).clone();
+					let p2pool_output = lock!(self.p2pool_api).output.clone();
+					let xmrig_output = lock!(self.xmrig_api).output.clone();
+					let processes = [
+						diagnostic::Process {
+							name: "P2Pool",
+							state: lock!(self.p2pool).state.to_string(),
+							path: &self.state.gupax.absolute_p2pool_path,
+							version: &version.p2pool,
+							output: &p2pool_output,
+						},
+						diagnostic::Process {
+							name: "XMRig",
+							state: lock!(self.xmrig).state.to_string(),
+							path: &self.state.gupax.absolute_xmrig_path,
+							version: &version.xmrig,
+							output: &xmrig_output,
+						},
+					];
+					let report = diagnostic::report(self.error_state.code, &self.error_state.msg, &processes, &self.os_data_path);
+					ui.output_mut(|o| o.copied_text = report);
+				}
 				let height = ui.available_height();
 
 				match self.error_state.buttons {
@@ -1762,6 +3552,67 @@ impl eframe::App for App {
 						// If [Esc] was pressed, assume [No]
 				        if key.is_esc() || ui.add_sized([width, height/2.0], Button::new("No")).clicked() { exit(0); }
 					},
+					UnsavedStart => {
+						let height = height/3.0;
+						if let Some((name, signal)) = self.unsaved_start {
+							let (verb, button) = if signal == ProcessSignal::Restart { ("restart", "Restart") } else { ("start", "Start") };
+							if ui.add_sized([width, height], Button::new(format!("{} with unsaved changes", button))).on_hover_text(UNSAVED_START).clicked() {
+								self.error_state.reset();
+								self.unsaved_start = None;
+								self.start(name, signal);
+							}
+							if ui.add_sized([width, height], Button::new(format!("Save & {}", verb))).on_hover_text(UNSAVED_SAVE_START).clicked() {
+								self.error_state.reset();
+								self.unsaved_start = None;
+								for section in self.unsaved_sections(name) { self.apply(section); }
+								// Don't start if saving failed, the error is showing instead.
+								if !self.error_state.error { self.start(name, signal); }
+							}
+						}
+						// If [Esc] was pressed, assume [Cancel]
+						if key.is_esc() || ui.add_sized([width, height], Button::new("Cancel")).clicked() {
+							self.error_state.reset();
+							self.unsaved_start = None;
+						}
+					},
+					BinaryChanged => {
+						let height = height/2.0;
+						if let Some((name, signal, hash)) = self.binary_changed.clone() {
+							if ui.add_sized([width, height], Button::new(format!("Trust the new binary & {}", signal.to_string().to_lowercase()))).on_hover_text(BINARY_CHANGED_TRUST).clicked() {
+								self.error_state.reset();
+								self.binary_changed = None;
+								let binary = self.binary_path(name);
+								match integrity::trust(&self.integrity_path, &binary, &hash) {
+									Ok(()) => self.launch(name, signal),
+									Err(e) => self.error_state.set_code(code::ErrorCode::BinaryTrust, format!("Could not record the new hash: {}", e), ErrorFerris::Error, ErrorButtons::Okay),
+								}
+							}
+						}
+						// If [Esc] was pressed, assume [Cancel]
+						if key.is_esc() || ui.add_sized([width, height], Button::new("Cancel")).clicked() {
+							self.error_state.reset();
+							self.binary_changed = None;
+						}
+					},
+					Quarantined => {
+						let height = height/2.0;
+						if let Some(suspect) = self.quarantined.clone() {
+							if ui.add_sized([width, height], Button::new("Re-check path")).on_hover_text(QUARANTINE_RECHECK).clicked() {
+								if suspect.restored() {
+									info!("App | [{}] is back, restarting it", suspect.path.display());
+									self.error_state.reset();
+									self.quarantined = None;
+									self.start(suspect.name, ProcessSignal::Start);
+								} else {
+									self.error_state.msg = format!("Still not there or not readable!\n\n{}", suspect.text());
+								}
+							}
+						}
+						if key.is_esc() || ui.add_sized([width, height], Button::new("Okay")).clicked() {
+							self.error_state.reset();
+							self.quarantined = None;
+						}
+					},
 					StayQuit => {
 						// If [Esc] was pressed, assume [Stay]
 				        if key.is_esc() || ui.add_sized([width, height/2.0], Button::new("Stay")).clicked() {
@@ -1779,16 +3630,16 @@ impl eframe::App for App {
 						if ui.add_sized([width, height/2.0], Button::new("Yes")).clicked() {
 							match reset_state(&self.state_path) {
 								Ok(_)  => {
-									match State::get(&self.state_path) {
+									match State::get(&self.state_path, self.read_only) {
 										Ok(s) => {
 											self.state = s;
 											self.og = arc_mut!(self.state.clone());
 											self.error_state.set("State read OK", ErrorFerris::Happy, ErrorButtons::Okay);
 										},
-										Err(e) => self.error_state.set(format!("State read fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
+										Err(e) => self.error_state.set_code(code::ErrorCode::StateRead, format!("State read fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
 									}
 								},
-								Err(e) => self.error_state.set(format!("State reset fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
+								Err(e) => self.error_state.set_code(code::ErrorCode::StateReset, format!("State reset fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
 							};
 						}
 				        if key.is_esc() || ui.add_sized([width, height/2.0], Button::new("No")).clicked() { self.error_state.reset() }
@@ -1803,10 +3654,10 @@ impl eframe::App for App {
 											self.og_node_vec = self.node_vec.clone();
 											self.error_state.set("Node read OK", ErrorFerris::Happy, ErrorButtons::Okay);
 										},
-										Err(e) => self.error_state.set(format!("Node read fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
+										Err(e) => self.error_state.set_code(code::ErrorCode::NodeRead, format!("Node read fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
 									}
 								},
-								Err(e) => self.error_state.set(format!("Node reset fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
+								Err(e) => self.error_state.set_code(code::ErrorCode::NodeReset, format!("Node reset fail: {}", e), ErrorFerris::Panic, ErrorButtons::Quit),
 							};
 						}
 				        if key.is_esc() || ui.add_sized([width, height/2.0], Button::new("No")).clicked() { self.error_state.reset() }
@@ -1816,6 +3667,7 @@ impl eframe::App for App {
 						let height = ui.available_height()/4.0;
 						let mut sudo = lock!(self.sudo);
 						let hide = sudo.hide;
+						let launcher = sudo.launcher.launcher();
 						if sudo.testing {
 							ui.add_sized([width, height], Spinner::new().size(height));
 							ui.set_enabled(false);
@@ -1826,7 +3678,12 @@ impl eframe::App for App {
 						let height = ui.available_height()/5.0;
 						// Password input box with a hider.
 						ui.horizontal(|ui| {
-							let response = ui.add_sized([sudo_width*8.0, height], TextEdit::hint_text(TextEdit::singleline(&mut sudo.pass).password(hide), PASSWORD_TEXT));
+							// [pkexec] shows its own password dialog.
+							let response = if launcher.needs_password() {
+								ui.add_sized([sudo_width*8.0, height], TextEdit::hint_text(TextEdit::singleline(&mut sudo.pass).password(hide), launcher.prompt()))
+							} else {
+								ui.add_sized([sudo_width*8.0, height], Label::new(launcher.prompt()))
+							};
 							let box_width = (ui.available_width()/2.0)-5.0;
 							if (response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter))) ||
 							ui.add_sized([box_width, height], Button::new("Enter")).on_hover_text(PASSWORD_ENTER).clicked() {
@@ -1862,15 +3719,22 @@ impl eframe::App for App {
             || og.gupax != self.state.gupax
             || og.p2pool != self.state.p2pool
             || og.xmrig != self.state.xmrig
+            || og.proxy != self.state.proxy
             || self.og_node_vec != self.node_vec
             || self.og_pool_vec != self.pool_vec;
+        self.tab_diff = self
+            .tab
+            .sections()
+            .iter()
+            .any(|section| self.section_diff(&og, *section));
         drop(og);
 
         // Top: Tabs
         debug!("App | Rendering TOP tabs");
         TopBottomPanel::top("top").show(ctx, |ui| {
-            let width = (self.width - (SPACE * 10.0)) / 5.0;
             let height = self.height / 15.0;
+            let help_width = height;
+            let width = (self.width - help_width - (SPACE * 14.0)) / 6.0;
             ui.add_space(4.0);
             ui.horizontal(|ui| {
                 ui.style_mut().override_text_style = Some(Name("Tab".into()));
@@ -1904,10 +3768,22 @@ impl eframe::App for App {
                     self.tab = Tab::Gupax;
                 }
                 ui.separator();
+                // The color-blind palette also shows the process state in the tab.
+                let tab_text = |name: &str, state, busy| {
+                    if self.state.gupax.colorblind {
+                        let (color, letter) = state_indicator(state, busy, true);
+                        RichText::new(format!("{} [{}]", name, letter)).color(color)
+                    } else {
+                        RichText::new(name)
+                    }
+                };
+                let p2pool_text = tab_text("P2Pool", p2pool_state, ProcessState::Syncing);
+                let xmrig_text = tab_text("XMRig", xmrig_state, ProcessState::NotMining);
+                let proxy_text = tab_text("Proxy", proxy_state, ProcessState::NotMining);
                 if ui
                     .add_sized(
                         [width, height],
-                        SelectableLabel::new(self.tab == Tab::P2pool, "P2Pool"),
+                        SelectableLabel::new(self.tab == Tab::P2pool, p2pool_text),
                     )
                     .clicked()
                 {
@@ -1917,16 +3793,100 @@ impl eframe::App for App {
                 if ui
                     .add_sized(
                         [width, height],
-                        SelectableLabel::new(self.tab == Tab::Xmrig, "XMRig"),
+                        SelectableLabel::new(self.tab == Tab::Xmrig, xmrig_text),
                     )
                     .clicked()
                 {
                     self.tab = Tab::Xmrig;
                 }
+                ui.separator();
+                if ui
+                    .add_sized(
+                        [width, height],
+                        SelectableLabel::new(self.tab == Tab::Proxy, proxy_text),
+                    )
+                    .on_hover_text(match proxy_state {
+                        ProcessState::Alive => PROXY_ALIVE,
+                        ProcessState::Dead => PROXY_DEAD,
+                        ProcessState::Failed => PROXY_FAILED,
+                        ProcessState::NotMining => PROXY_NOT_MINING,
+                        _ => PROXY_MIDDLE,
+                    })
+                    .clicked()
+                {
+                    self.tab = Tab::Proxy;
+                }
+                ui.separator();
+                if ui
+                    .add_sized(
+                        [help_width, height],
+                        SelectableLabel::new(self.help.open, "?"),
+                    )
+                    .on_hover_text(GUPAX_HELP)
+                    .clicked()
+                {
+                    self.help.toggle(self.tab);
+                }
             });
             ui.add_space(4.0);
         });
 
+        // Top: Read-only data path
+        let picked = std::mem::take(&mut lock!(self.file_window).picked_data_dir);
+        if picked {
+            let dir = PathBuf::from(lock!(self.file_window).data_dir.clone());
+            self.switch_data_dir(dir);
+        }
+        if self.read_only || !self.data_dir_msg.is_empty() {
+            TopBottomPanel::top("read_only").show(ctx, |ui| {
+                ui.horizontal_centered(|ui| {
+                    if self.read_only {
+                        ui.label(
+                            RichText::new(format!(
+                                "⚠ [{}] is read-only, settings won't be saved",
+                                self.os_data_path.display()
+                            ))
+                            .color(RED),
+                        )
+                        .on_hover_text(GUPAX_READ_ONLY);
+                        let thread = lock!(self.file_window).thread;
+                        if ui
+                            .add_enabled(!thread, Button::new("Pick a writable folder"))
+                            .on_hover_text(GUPAX_READ_ONLY_PICK)
+                            .clicked()
+                        {
+                            Gupax::spawn_file_window_thread(&self.file_window, FileType::DataDir);
+                        }
+                    }
+                    if !self.data_dir_msg.is_empty() {
+                        ui.label(RichText::new(&self.data_dir_msg).color(LIGHT_GRAY));
+                        if ui.button("Dismiss").clicked() {
+                            self.data_dir_msg.clear();
+                        }
+                    }
+                });
+            });
+        }
+
+        // Top: Clock skew warning
+        let skew = lock!(self.clock).warning();
+        if let (true, Some(skew)) = (self.state.gupax.time_check, skew) {
+            TopBottomPanel::top("clock").show(ctx, |ui| {
+                let server = lock!(self.clock).server.clone();
+                let direction = if skew > 0 { "ahead" } else { "behind" };
+                let text = format!(
+                    "⚠ Your system clock is {} {} [{}], P2Pool may reject shares. Please sync your clock",
+                    human::HumanTime::into_human(Duration::from_millis(skew.unsigned_abs())).localized(),
+                    direction,
+                    server,
+                );
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new(text).color(RED))
+                        .on_hover_text(GUPAX_TIME_CHECK);
+                });
+            });
+        }
+
         // Bottom: app info + state/process buttons
         debug!("App | Rendering BOTTOM bar");
         TopBottomPanel::bottom("bottom").show(ctx, |ui| {
@@ -1934,7 +3894,7 @@ impl eframe::App for App {
             ui.style_mut().override_text_style = Some(Name("Bottom".into()));
             ui.horizontal(|ui| {
                 ui.group(|ui| {
-                    let width = ((self.width / 2.0) / 4.0) - (SPACE * 2.0);
+                    let width = ((self.width / 2.0) / 5.0) - (SPACE * 2.0);
                     // [Gupax Version]
                     // Is yellow if the user updated and should (but isn't required to) restart.
                     match *lock!(self.restart) {
@@ -1966,71 +3926,70 @@ impl eframe::App for App {
                     ui.separator();
                     // [P2Pool/XMRig] Status
                     use ProcessState::*;
-                    match p2pool_state {
-                        Alive => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("P2Pool  ⏺").color(GREEN)),
-                            )
-                            .on_hover_text(P2POOL_ALIVE),
-                        Dead => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("P2Pool  ⏺").color(GRAY)),
-                            )
-                            .on_hover_text(P2POOL_DEAD),
-                        Failed => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("P2Pool  ⏺").color(RED)),
-                            )
-                            .on_hover_text(P2POOL_FAILED),
-                        Syncing => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("P2Pool  ⏺").color(ORANGE)),
-                            )
-                            .on_hover_text(P2POOL_SYNCING),
-                        Middle | Waiting | NotMining => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("P2Pool  ⏺").color(YELLOW)),
-                            )
-                            .on_hover_text(P2POOL_MIDDLE),
+                    let colorblind = self.state.gupax.colorblind;
+                    let hover = match p2pool_state {
+                        Alive => P2POOL_ALIVE,
+                        Dead => P2POOL_DEAD,
+                        Failed => P2POOL_FAILED,
+                        Syncing => P2POOL_SYNCING,
+                        Middle | Waiting | NotMining => P2POOL_MIDDLE,
+                    };
+                    let (color, letter) = state_indicator(p2pool_state, Syncing, colorblind);
+                    let mut text = if colorblind {
+                        format!("P2Pool  [{}]", letter)
+                    } else {
+                        "P2Pool  ⏺".to_string()
                     };
+                    let changed = self.restart_needed(ProcessName::P2pool, p2pool_is_alive);
+                    let hover = restart_badge(&mut text, hover, &changed);
+                    if let Some(secs) = self
+                        .delayed_start
+                        .remaining(ProcessName::P2pool, Instant::now())
+                    {
+                        text.push_str(&format!(" ({}s)", secs));
+                    }
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new(text).color(color)),
+                    )
+                    .on_hover_text(hover);
                     ui.separator();
-                    match xmrig_state {
-                        Alive => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("XMRig  ⏺").color(GREEN)),
-                            )
-                            .on_hover_text(XMRIG_ALIVE),
-                        Dead => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("XMRig  ⏺").color(GRAY)),
-                            )
-                            .on_hover_text(XMRIG_DEAD),
-                        Failed => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("XMRig  ⏺").color(RED)),
-                            )
-                            .on_hover_text(XMRIG_FAILED),
-                        NotMining => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("XMRig  ⏺").color(ORANGE)),
-                            )
-                            .on_hover_text(XMRIG_NOT_MINING),
-                        Middle | Waiting | Syncing => ui
-                            .add_sized(
-                                [width, height],
-                                Label::new(RichText::new("XMRig  ⏺").color(YELLOW)),
-                            )
-                            .on_hover_text(XMRIG_MIDDLE),
+                    let hover = match xmrig_state {
+                        Alive => XMRIG_ALIVE,
+                        Dead => XMRIG_DEAD,
+                        Failed => XMRIG_FAILED,
+                        NotMining => XMRIG_NOT_MINING,
+                        Middle | Waiting | Syncing => XMRIG_MIDDLE,
+                    };
+                    let (color, letter) = state_indicator(xmrig_state, NotMining, colorblind);
+                    let mut text = if colorblind {
+                        format!("XMRig  [{}]", letter)
+                    } else {
+                        "XMRig  ⏺".to_string()
+                    };
+                    let changed = self.restart_needed(ProcessName::Xmrig, xmrig_is_alive);
+                    let hover = restart_badge(&mut text, hover, &changed);
+                    if let Some(secs) = self
+                        .delayed_start
+                        .remaining(ProcessName::Xmrig, Instant::now())
+                    {
+                        text.push_str(&format!(" ({}s)", secs));
+                    }
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new(text).color(color)),
+                    )
+                    .on_hover_text(hover);
+                    ui.separator();
+                    // [⏱ Timer]
+                    let text = self.timer.text();
+                    let text = match self.timer.remaining() {
+                        Some(_) => RichText::new(text).color(YELLOW),
+                        None => RichText::new(text),
                     };
+                    ui.menu_button(text, |ui| self.timer.show(ui))
+                        .response
+                        .on_hover_text(GUPAX_TIMER);
                 });
 
                 // [Save/Reset]
@@ -2038,7 +3997,7 @@ impl eframe::App for App {
                     let width = (ui.available_width() / 3.0) - (SPACE * 3.0);
                     ui.group(|ui| {
                         ui.set_enabled(self.diff);
-                        let width = width / 2.0;
+                        let width = (width / 4.0) - SPACE;
                         if key.is_r() && !wants_input && self.diff
                             || ui
                                 .add_sized([width, height], Button::new("Reset"))
@@ -2050,6 +4009,7 @@ impl eframe::App for App {
                             self.state.gupax = og.gupax;
                             self.state.p2pool = og.p2pool;
                             self.state.xmrig = og.xmrig;
+                            self.state.proxy = og.proxy;
                             self.node_vec = self.og_node_vec.clone();
                             self.pool_vec = self.og_pool_vec.clone();
                         }
@@ -2059,38 +4019,42 @@ impl eframe::App for App {
                                 .on_hover_text("Save changes")
                                 .clicked()
                         {
-                            match State::save(&mut self.state, &self.state_path) {
-                                Ok(_) => {
-                                    let mut og = lock!(self.og);
-                                    og.status = self.state.status.clone();
-                                    og.gupax = self.state.gupax.clone();
-                                    og.p2pool = self.state.p2pool.clone();
-                                    og.xmrig = self.state.xmrig.clone();
+                            if self.read_only {
+                                // Nowhere to write, only keep them for this session.
+                                for section in Section::ALL {
+                                    self.apply(section);
                                 }
-                                Err(e) => {
-                                    self.error_state.set(
-                                        format!("State file: {}", e),
-                                        ErrorFerris::Error,
-                                        ErrorButtons::Okay,
-                                    );
-                                }
-                            };
-                            match Node::save(&self.node_vec, &self.node_path) {
-                                Ok(_) => self.og_node_vec = self.node_vec.clone(),
-                                Err(e) => self.error_state.set(
-                                    format!("Node list: {}", e),
-                                    ErrorFerris::Error,
-                                    ErrorButtons::Okay,
-                                ),
-                            };
-                            match Pool::save(&self.pool_vec, &self.pool_path) {
-                                Ok(_) => self.og_pool_vec = self.pool_vec.clone(),
-                                Err(e) => self.error_state.set(
-                                    format!("Pool list: {}", e),
-                                    ErrorFerris::Error,
-                                    ErrorButtons::Okay,
-                                ),
-                            };
+                            } else {
+                                self.request_save(ctx);
+                            }
+                        }
+
+                        // Only the current tab.
+                        ui.separator();
+                        ui.set_enabled(self.tab_diff);
+                        if ui
+                            .add_sized([width, height], Button::new("Revert"))
+                            .on_hover_text(format!(
+                                "Reset only the changes in the [{:?}] tab",
+                                self.tab
+                            ))
+                            .clicked()
+                        {
+                            for section in self.tab.sections() {
+                                self.revert(*section);
+                            }
+                        }
+                        if ui
+                            .add_sized([width, height], Button::new("Apply"))
+                            .on_hover_text(format!(
+                                "Save only the changes in the [{:?}] tab",
+                                self.tab
+                            ))
+                            .clicked()
+                        {
+                            for section in self.tab.sections() {
+                                self.apply(*section);
+                            }
                         }
                     });
 
@@ -2098,7 +4062,35 @@ impl eframe::App for App {
                     match self.tab {
                         Tab::Status => {
                             ui.group(|ui| {
-                                let width = (ui.available_width() / 3.0) - 14.0;
+                                let width = (ui.available_width() / 5.0) - 14.0;
+                                if ui
+                                    .add_sized(
+                                        [width, height],
+                                        SelectableLabel::new(
+                                            self.state.status.submenu == Submenu::Events,
+                                            "Events",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_EVENTS)
+                                    .clicked()
+                                {
+                                    self.state.status.submenu = Submenu::Events;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, height],
+                                        SelectableLabel::new(
+                                            self.state.status.submenu == Submenu::Consoles,
+                                            "Consoles",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_CONSOLES)
+                                    .clicked()
+                                {
+                                    self.state.status.submenu = Submenu::Consoles;
+                                }
+                                ui.separator();
                                 if ui
                                     .add_sized(
                                         [width, height],
@@ -2211,13 +4203,9 @@ impl eframe::App for App {
                                             .on_hover_text("Restart P2Pool")
                                             .clicked()
                                     {
-                                        let _ = lock!(self.og).update_absolute_path();
-                                        let _ = self.state.update_absolute_path();
-                                        Helper::restart_p2pool(
-                                            &self.helper,
-                                            &self.state.p2pool,
-                                            &self.state.gupax.absolute_p2pool_path,
-                                            self.gather_backup_hosts(),
+                                        self.start_or_ask(
+                                            ProcessName::P2pool,
+                                            ProcessSignal::Restart,
                                         );
                                     }
                                     if key.is_down() && !wants_input
@@ -2240,20 +4228,8 @@ impl eframe::App for App {
                                             .on_disabled_hover_text("Stop P2Pool");
                                     });
                                     // Check if address is okay before allowing to start.
-                                    let mut text = String::new();
-                                    let mut ui_enabled = true;
-                                    if !Regexes::addr_ok(&self.state.p2pool.address) {
-                                        ui_enabled = false;
-                                        text = format!("Error: {}", P2POOL_ADDRESS);
-                                    } else if !Gupax::path_is_file(&self.state.gupax.p2pool_path) {
-                                        ui_enabled = false;
-                                        text = format!("Error: {}", P2POOL_PATH_NOT_FILE);
-                                    } else if !crate::update::check_p2pool_path(
-                                        &self.state.gupax.p2pool_path,
-                                    ) {
-                                        ui_enabled = false;
-                                        text = format!("Error: {}", P2POOL_PATH_NOT_VALID);
-                                    }
+                                    let text = self.p2pool_start_error().unwrap_or_default();
+                                    let ui_enabled = text.is_empty();
                                     ui.set_enabled(ui_enabled);
                                     let color = if ui_enabled { GREEN } else { RED };
                                     if (ui_enabled && key.is_up() && !wants_input)
@@ -2266,13 +4242,9 @@ impl eframe::App for App {
                                             .on_disabled_hover_text(text)
                                             .clicked()
                                     {
-                                        let _ = lock!(self.og).update_absolute_path();
-                                        let _ = self.state.update_absolute_path();
-                                        Helper::start_p2pool(
-                                            &self.helper,
-                                            &self.state.p2pool,
-                                            &self.state.gupax.absolute_p2pool_path,
-                                            self.gather_backup_hosts(),
+                                        self.start_or_ask(
+                                            ProcessName::P2pool,
+                                            ProcessSignal::Start,
                                         );
                                     }
                                 }
@@ -2321,19 +4293,10 @@ impl eframe::App for App {
                                             .on_hover_text("Restart XMRig")
                                             .clicked()
                                     {
-                                        let _ = lock!(self.og).update_absolute_path();
-                                        let _ = self.state.update_absolute_path();
-                                        if cfg!(windows) {
-                                            Helper::restart_xmrig(
-                                                &self.helper,
-                                                &self.state.xmrig,
-                                                &self.state.gupax.absolute_xmrig_path,
-                                                Arc::clone(&self.sudo),
-                                            );
-                                        } else {
-                                            lock!(self.sudo).signal = ProcessSignal::Restart;
-                                            self.error_state.ask_sudo(&self.sudo);
-                                        }
+                                        self.start_or_ask(
+                                            ProcessName::Xmrig,
+                                            ProcessSignal::Restart,
+                                        );
                                     }
                                     if key.is_down() && !wants_input
                                         || ui
@@ -2341,12 +4304,7 @@ impl eframe::App for App {
                                             .on_hover_text("Stop XMRig")
                                             .clicked()
                                     {
-                                        if cfg!(target_os = "macos") {
-                                            lock!(self.sudo).signal = ProcessSignal::Stop;
-                                            self.error_state.ask_sudo(&self.sudo);
-                                        } else {
-                                            Helper::stop_xmrig(&self.helper);
-                                        }
+                                        self.stop_xmrig();
                                     }
                                     ui.add_enabled_ui(false, |ui| {
                                         ui.add_sized([width, height], Button::new("▶"))
@@ -2359,17 +4317,8 @@ impl eframe::App for App {
                                         ui.add_sized([width, height], Button::new("⏹"))
                                             .on_disabled_hover_text("Stop XMRig");
                                     });
-                                    let mut text = String::new();
-                                    let mut ui_enabled = true;
-                                    if !Gupax::path_is_file(&self.state.gupax.xmrig_path) {
-                                        ui_enabled = false;
-                                        text = format!("Error: {}", XMRIG_PATH_NOT_FILE);
-                                    } else if !crate::update::check_xmrig_path(
-                                        &self.state.gupax.xmrig_path,
-                                    ) {
-                                        ui_enabled = false;
-                                        text = format!("Error: {}", XMRIG_PATH_NOT_VALID);
-                                    }
+                                    let text = self.xmrig_start_error().unwrap_or_default();
+                                    let ui_enabled = text.is_empty();
                                     ui.set_enabled(ui_enabled);
                                     let color = if ui_enabled { GREEN } else { RED };
                                     if (ui_enabled && key.is_up() && !wants_input)
@@ -2382,19 +4331,69 @@ impl eframe::App for App {
                                             .on_disabled_hover_text(text)
                                             .clicked()
                                     {
-                                        let _ = lock!(self.og).update_absolute_path();
-                                        let _ = self.state.update_absolute_path();
-                                        if cfg!(windows) {
-                                            Helper::start_xmrig(
-                                                &self.helper,
-                                                &self.state.xmrig,
-                                                &self.state.gupax.absolute_xmrig_path,
-                                                Arc::clone(&self.sudo),
-                                            );
-                                        } else if cfg!(unix) {
-                                            lock!(self.sudo).signal = ProcessSignal::Start;
-                                            self.error_state.ask_sudo(&self.sudo);
-                                        }
+                                        self.start_or_ask(ProcessName::Xmrig, ProcessSignal::Start);
+                                    }
+                                }
+                            });
+                        }
+                        Tab::Proxy => {
+                            ui.group(|ui| {
+                                let width = (ui.available_width() / 3.0) - 5.0;
+                                if proxy_is_waiting {
+                                    ui.add_enabled_ui(false, |ui| {
+                                        ui.add_sized([width, height], Button::new("⟲"))
+                                            .on_disabled_hover_text(PROXY_MIDDLE);
+                                        ui.add_sized([width, height], Button::new("⏹"))
+                                            .on_disabled_hover_text(PROXY_MIDDLE);
+                                        ui.add_sized([width, height], Button::new("▶"))
+                                            .on_disabled_hover_text(PROXY_MIDDLE);
+                                    });
+                                } else if proxy_is_alive {
+                                    if key.is_up() && !wants_input
+                                        || ui
+                                            .add_sized([width, height], Button::new("⟲"))
+                                            .on_hover_text("Restart XMRig-Proxy")
+                                            .clicked()
+                                    {
+                                        self.start_or_ask(
+                                            ProcessName::Proxy,
+                                            ProcessSignal::Restart,
+                                        );
+                                    }
+                                    if key.is_down() && !wants_input
+                                        || ui
+                                            .add_sized([width, height], Button::new("⏹"))
+                                            .on_hover_text("Stop XMRig-Proxy")
+                                            .clicked()
+                                    {
+                                        Helper::stop_proxy(&self.helper);
+                                    }
+                                    ui.add_enabled_ui(false, |ui| {
+                                        ui.add_sized([width, height], Button::new("▶"))
+                                            .on_disabled_hover_text("Start XMRig-Proxy");
+                                    });
+                                } else {
+                                    ui.add_enabled_ui(false, |ui| {
+                                        ui.add_sized([width, height], Button::new("⟲"))
+                                            .on_disabled_hover_text("Restart XMRig-Proxy");
+                                        ui.add_sized([width, height], Button::new("⏹"))
+                                            .on_disabled_hover_text("Stop XMRig-Proxy");
+                                    });
+                                    let text = self.proxy_start_error().unwrap_or_default();
+                                    let ui_enabled = text.is_empty();
+                                    ui.set_enabled(ui_enabled);
+                                    let color = if ui_enabled { GREEN } else { RED };
+                                    if (ui_enabled && key.is_up() && !wants_input)
+                                        || ui
+                                            .add_sized(
+                                                [width, height],
+                                                Button::new(RichText::new("▶").color(color)),
+                                            )
+                                            .on_hover_text("Start XMRig-Proxy")
+                                            .on_disabled_hover_text(text)
+                                            .clicked()
+                                    {
+                                        self.start_or_ask(ProcessName::Proxy, ProcessSignal::Start);
                                     }
                                 }
                             });
@@ -2426,10 +4425,14 @@ impl eframe::App for App {
 						let p2pool_gui_len = lock!(self.p2pool_api).output.len();
 						let xmrig_gui_len = lock!(self.xmrig_api).output.len();
 						let gupax_p2pool_api = lock!(self.gupax_p2pool_api);
+						let p2pool_configured = lock!(self.p2pool_probe).compare_text(P2POOL_VERSION);
+						let xmrig_configured = lock!(lock!(self.xmrig_img).probe).compare_text(XMRIG_VERSION);
 						let debug_info = format!(
 "Gupax version: {}\n
 Bundled P2Pool version: {}\n
 Bundled XMRig version: {}\n
+Configured P2Pool version: {}\n
+Configured XMRig version: {}\n
 Gupax uptime: {} seconds\n
 Selected resolution: {}x{}\n
 Internal resolution: {}x{}\n
@@ -2460,7 +4463,8 @@ payout_u64: {:#?}
 xmr: {:#?}
 path_log: {:#?}
 path_payout: {:#?}
-path_xmr: {:#?}\n
+path_xmr: {:#?}
+path_history: {:#?}\n
 ------------------------------------------ WORKING STATE ------------------------------------------
 {:#?}\n
 ------------------------------------------ ORIGINAL STATE ------------------------------------------
@@ -2468,6 +4472,8 @@ path_xmr: {:#?}\n
 							GUPAX_VERSION,
 							P2POOL_VERSION,
 							XMRIG_VERSION,
+							p2pool_configured,
+							xmrig_configured,
 							self.now.elapsed().as_secs_f32(),
 							self.state.gupax.selected_width,
 							self.state.gupax.selected_height,
@@ -2498,6 +4504,7 @@ path_xmr: {:#?}\n
 							gupax_p2pool_api.path_log,
 							gupax_p2pool_api.path_payout,
 							gupax_p2pool_api.path_xmr,
+							gupax_p2pool_api.path_history,
 							self.state,
 							lock!(self.og),
 						);
@@ -2523,35 +4530,94 @@ path_xmr: {:#?}\n
 						ui.add_sized([width, height], Label::new(KEYBOARD_SHORTCUTS));
 						ui.add_space(SPACE*2.0);
 
+						// Configured binaries, not just what this Gupax was bundled with
+						let p2pool = lock!(self.p2pool_probe).compare_text(P2POOL_VERSION);
+						let xmrig = lock!(lock!(self.xmrig_img).probe).compare_text(XMRIG_VERSION);
+						ui.label(format!("P2Pool {}", p2pool)).on_hover_text(ABOUT_CONFIGURED_VERSION);
+						ui.label(format!("XMRig {}", xmrig)).on_hover_text(ABOUT_CONFIGURED_VERSION);
+						ui.add_space(SPACE*2.0);
+
 						if cfg!(debug_assertions) { ui.label(format!("Gupax is running in debug mode - {}", self.now.elapsed().as_secs_f64())); }
 						ui.label(format!("Gupax has been running for {}", lock!(self.pub_sys).gupax_uptime));
 					});
 				}
 				Tab::Status => {
 					debug!("App | Entering [Status] Tab");
-					crate::disk::Status::show(&mut self.state.status, &self.pub_sys, &self.p2pool_api, &self.xmrig_api, &self.p2pool_img, &self.xmrig_img, p2pool_is_alive, xmrig_is_alive, self.max_threads, &self.gupax_p2pool_api, &self.benchmarks, self.width, self.height, ctx, ui);
+					let cards = [
+						status::ProcessCard { process: &self.p2pool, start_error: self.p2pool_start_error(), availability: self.availability.text(ProcessName::P2pool) },
+						status::ProcessCard { process: &self.xmrig, start_error: self.xmrig_start_error(), availability: self.availability.text(ProcessName::Xmrig) },
+					];
+					let mut signal = None;
+					crate::disk::Status::show(&mut self.state.status, &self.pub_sys, &self.p2pool_api, &self.xmrig_api, &self.p2pool_img, &self.xmrig_img, &self.xmrig_instances, &self.pool_hashrate, &self.block_hash, p2pool_is_alive, xmrig_is_alive, cards, &mut signal, self.max_threads, &self.gupax_p2pool_api, &mut self.state.p2pool, &mut self.state.xmrig, &mut self.mining_mode, &self.benchmarks, &mut self.benchmark_view, &mut self.p2pool_console, &mut self.xmrig_console, self.state.gupax.console_colors, self.width, self.height, ctx, ui);
+					if let Some((name, signal)) = signal { self.process_signal(name, signal); }
 				}
 				Tab::Gupax => {
 					debug!("App | Entering [Gupax] Tab");
-					crate::disk::Gupax::show(&mut self.state.gupax, &self.og, &self.state_path, &self.update, &self.file_window, &mut self.error_state, &self.restart, self.width, self.height, frame, ctx, ui);
+					crate::disk::Gupax::show(&mut self.state.gupax, &self.og, &self.state_path, &self.update, &self.file_window, &self.hotkeys, &self.autostart, &self.p2pool_probe, &xmrig_probe, &self.discovery, &mut self.error_state, &self.restart, &self.gupax_p2pool_api, &self.gupax_p2pool_api_path, &mut self.payout_backups, &self.migrate, &self.os_data_path, &self.og_node_vec, &self.og_pool_vec, &self.gfx, &self.usage_stats, &mut self.toml_editor, self.display.as_deref(), self.width, self.height, frame, ctx, ui);
+					if let Some(state) = self.toml_editor.pending.take() { self.apply_toml(state); }
+					let import = lock!(self.migrate).pending.take();
+					if let Some(import) = import { self.apply_snapshot(import); }
 				}
 				Tab::P2pool => {
 					debug!("App | Entering [P2Pool] Tab");
-					crate::disk::P2pool::show(&mut self.state.p2pool, &mut self.node_vec, &self.og, &self.ping, &self.p2pool, &self.p2pool_api, &mut self.p2pool_stdin, self.width, self.height, ctx, ui);
+					let img = lock!(self.p2pool_img).clone();
+					if p2pool_is_alive && self.state.p2pool.node_changed(&img) {
+						ui.group(|ui| {
+							ui.horizontal(|ui| {
+								let text = format!("P2Pool is still using [{}:{}], restart it to switch to the selected node", img.host, img.rpc);
+								ui.colored_label(YELLOW, text).on_hover_text(P2POOL_NODE_CHANGED);
+								if ui.button("Apply & restart P2Pool").on_hover_text(P2POOL_NODE_CHANGED_RESTART).clicked() {
+									info!("App | Node changed from [{}:{}], applying and restarting P2Pool", img.host, img.rpc);
+									self.apply(Section::P2pool);
+									// Don't restart if saving failed, the error is showing instead.
+									if !self.error_state.error { self.start(ProcessName::P2pool, ProcessSignal::Restart); }
+								}
+							});
+						});
+					}
+					crate::disk::P2pool::show(&mut self.state.p2pool, &mut self.node_vec, &mut self.node_filter, &self.file_window, &self.p2pool_probe, &self.og, &self.ping, &self.p2pool, &self.p2pool_api, &self.advisor, &mut self.help, &self.state.gupax.absolute_p2pool_path, &self.tls, &tls::cert_dir(&self.os_data_path), &self.ssh, &self.node_rpc, &self.node_check, &mut self.p2pool_stdin, &mut self.p2pool_console, self.state.gupax.console_colors, self.width, self.height, ctx, ui);
 				}
 				Tab::Xmrig => {
 					debug!("App | Entering [XMRig] Tab");
-					crate::disk::Xmrig::show(&mut self.state.xmrig, &mut self.pool_vec, &self.xmrig, &self.xmrig_api, &mut self.xmrig_stdin, self.width, self.height, ctx, ui);
+					let api_conflict = self.state.xmrig.api_port_conflict(&self.state.p2pool);
+					crate::disk::Xmrig::show(&mut self.state.xmrig, &mut self.pool_vec, &mut self.pool_filter, &self.pool_ping, &self.file_window, &xmrig_probe, &self.xmrig, &self.xmrig_api, api_conflict, &self.benchmarks[0], &self.state.gupax.absolute_xmrig_path, &mut self.xmrig_stdin, &mut self.xmrig_console, self.state.gupax.console_colors, self.width, self.height, ctx, ui);
+				}
+				Tab::Proxy => {
+					debug!("App | Entering [XMRig-Proxy] Tab");
+					crate::disk::Proxy::show(&mut self.state.proxy, &self.file_window, &self.proxy, &self.proxy_api, &mut self.proxy_stdin, &mut self.proxy_console, self.state.gupax.console_colors, self.width, self.height, ctx, ui);
 				}
 			}
         });
 		});
+
+        // [?] Help overlay
+        self.help.show(ctx, self.width, self.height);
     }
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod test {
+    #[test]
+    fn state_indicator() {
+        use super::{state_indicator, ProcessState::*};
+        let states = [Alive, Syncing, Middle, Dead, Failed];
+        for colorblind in [false, true] {
+            let mut seen = Vec::new();
+            for state in states {
+                let indicator = state_indicator(state, Syncing, colorblind);
+                assert!(!seen
+                    .iter()
+                    .any(|(c, l)| *c == indicator.0 || *l == indicator.1));
+                seen.push(indicator);
+            }
+            assert_eq!(seen.iter().map(|(_, l)| *l).collect::<String>(), "ASWDF");
+        }
+        // [NotMining] is XMRig's orange, P2Pool's yellow.
+        assert_eq!(state_indicator(NotMining, NotMining, true).1, 'S');
+        assert_eq!(state_indicator(NotMining, Syncing, true).1, 'W');
+    }
+
     #[test]
     fn detect_benchmark_cpu() {
         use super::{cmp_f64, Benchmark};
@@ -2567,4 +4633,116 @@ mod test {
 
         assert!(benchmarks[0].cpu == "AMD Ryzen 9 5950X 16-Core Processor");
     }
+
+    #[test]
+    fn benchmark_view() {
+        use super::{Benchmark, BenchmarkSort, BenchmarkView};
+
+        let bench = |cpu: &str, rank, average| Benchmark {
+            cpu: cpu.to_string(),
+            rank,
+            percent: 0.0,
+            benchmarks: 1,
+            average,
+            high: average,
+            low: average,
+        };
+        let benchmarks = [
+            bench("AMD Ryzen 9 5950X 16-Core Processor", 3, 16_000.0),
+            bench("AMD EPYC 7763 64-Core Processor", 1, 64_000.0),
+            bench("Intel(R) Core(TM) i7-8700 CPU @ 3.20GHz", 4, 3_000.0),
+            bench("AMD Ryzen 7 5800X 8-Core Processor", 2, 12_000.0),
+        ];
+        assert_eq!(benchmarks[0].cores(), Some(16));
+        assert_eq!(benchmarks[2].cores(), None);
+        assert_eq!(benchmarks[3].per_core(), Some(1_500.0));
+
+        let mut view = BenchmarkView::default();
+        assert_eq!(view.indices(&benchmarks), [1, 2, 3]);
+        view.search = "amd core".to_string();
+        assert_eq!(view.indices(&benchmarks), [1, 3]);
+        view.search.clear();
+        view.sort_by(BenchmarkSort::Rank);
+        assert_eq!(view.indices(&benchmarks), [1, 3, 2]);
+        view.sort_by(BenchmarkSort::Rank);
+        assert_eq!(view.indices(&benchmarks), [2, 3, 1]);
+        view.sort_by(BenchmarkSort::PerCore);
+        assert_eq!(view.indices(&benchmarks), [3, 1, 2]);
+
+        for i in 0..5 {
+            view.pin(i);
+        }
+        assert_eq!(view.pinned, [0, 1, 2]);
+        view.pin(1);
+        assert_eq!(view.pinned, [0, 2]);
+    }
+
+    #[test]
+    fn copy_section() {
+        use super::{State, Tab};
+
+        let og = State::new();
+        let mut state = State::new();
+        state.p2pool.out_peers = 50;
+        state.xmrig.current_threads = 3;
+
+        // Applying [P2Pool] must leave the unsaved [XMRig] change out.
+        let mut new = og.clone();
+        for section in Tab::P2pool.sections() {
+            section.copy(&state, &mut new);
+        }
+        assert_eq!(new.p2pool.out_peers, 50);
+        assert_eq!(new.xmrig, og.xmrig);
+
+        // Reverting [XMRig] must leave the [P2Pool] change in.
+        for section in Tab::Xmrig.sections() {
+            section.copy(&og, &mut state);
+        }
+        assert_eq!(state.xmrig, og.xmrig);
+        assert_eq!(state.p2pool.out_peers, 50);
+        assert!(Tab::About.sections().is_empty());
+
+        // [XMRig-Proxy] is its own section.
+        state.proxy.bind_port = "4444".to_string();
+        let mut new = og.clone();
+        for section in Tab::Proxy.sections() {
+            section.copy(&state, &mut new);
+        }
+        assert_eq!(new.proxy.bind_port, "4444");
+        assert_eq!(new.p2pool, og.p2pool);
+    }
+
+    #[test]
+    fn build_window_title() {
+        use super::window_title;
+        use crate::helper::ProcessState;
+        use crate::human::HumanTime;
+
+        let zero = HumanTime::new();
+        let share = HumanTime::from_u64(7380);
+        assert_eq!(
+            window_title("Gupax", ProcessState::Dead, ProcessState::Dead, 0.0, &zero),
+            "Gupax"
+        );
+        assert_eq!(
+            window_title(
+                "Gupax",
+                ProcessState::Alive,
+                ProcessState::Alive,
+                12_345.0,
+                &share
+            ),
+            "Gupax — 12.3 kH/s — share in ~2 hours"
+        );
+        assert_eq!(
+            window_title(
+                "Gupax",
+                ProcessState::Syncing,
+                ProcessState::Failed,
+                12_345.0,
+                &share
+            ),
+            "Gupax — XMRig failed"
+        );
+    }
 }
@@ -0,0 +1,180 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// [state.toml] schema migrations.
+//
+// [State::merge()] fills in missing keys with defaults and drops unknown
+// ones, which is enough for additions but loses data on renames and
+// moves. Each [state.toml] carries a top-level [schema] number; files
+// older than [SCHEMA] are walked through every step in [STEPS] (1 -> 2,
+// 2 -> 3, ...) on the parsed TOML tree before deserialization, so values
+// are carried over as-is without going through any string formatting.
+// The pre-migration file is kept next to it as [state.toml.v{N}.bak].
+
+use log::*;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// The current [state.toml] schema, bump this when adding a step.
+pub const SCHEMA: u32 = 2;
+// Files written before the [schema] key existed.
+const UNVERSIONED: u32 = 1;
+
+// [STEPS[0]] migrates 1 -> 2, [STEPS[1]] migrates 2 -> 3, etc.
+type Step = fn(&mut Table);
+const STEPS: [Step; (SCHEMA - UNVERSIONED) as usize] = [v1_to_v2];
+
+//---------------------------------------------------------------------------------------------------- Steps
+// v2 introduced the [schema] key itself, nothing was renamed or moved.
+fn v1_to_v2(_: &mut Table) {}
+
+//---------------------------------------------------------------------------------------------------- Migrate
+// The schema a parsed [state.toml] was written with.
+fn schema(table: &Table) -> u32 {
+    match table.get("schema").and_then(Value::as_integer) {
+        Some(n) if n > 0 => n as u32,
+        _ => UNVERSIONED,
+    }
+}
+
+// Apply [steps] to [table] starting at schema [from], then stamp it with [to].
+fn run(table: &mut Table, from: u32, to: u32, steps: &[Step]) {
+    for (i, step) in steps.iter().enumerate() {
+        let version = UNVERSIONED + i as u32;
+        if version < from {
+            continue;
+        }
+        info!("Migrate | state.toml v{} -> v{}", version, version + 1);
+        step(table);
+    }
+    table.insert("schema".to_string(), Value::Integer(to as i64));
+}
+
+// If [string] is an older schema, return the version it was and the migrated TOML.
+// Files that don't parse are left alone for [State::merge()], and files
+// from a newer Gupax are left alone as well (unknown keys are ignored).
+pub fn migrate(string: &str) -> Option<(u32, String)> {
+    let mut table: Table = match toml::from_str(string) {
+        Ok(t) => t,
+        Err(_) => return None,
+    };
+    let from = schema(&table);
+    if from >= SCHEMA {
+        if from > SCHEMA {
+            warn!(
+                "Migrate | state.toml is schema v{}, newer than this Gupax (v{}), leaving it as-is",
+                from, SCHEMA
+            );
+        }
+        return None;
+    }
+    run(&mut table, from, SCHEMA, &STEPS);
+    match toml::to_string(&table) {
+        Ok(s) => Some((from, s)),
+        Err(e) => {
+            error!("Migrate | Couldn't serialize migrated state.toml: {}", e);
+            None
+        }
+    }
+}
+
+// [state.toml] -> [state.toml.v1.bak]
+pub fn backup_path(path: &Path, schema: u32) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".v{}.bak", schema));
+    PathBuf::from(backup)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn migrate() {
+        use crate::migrate::*;
+        let old = "[gupax]\nsimple = false\n\n[p2pool]\nin_peers = 450\n";
+        let (from, new) = migrate(old).unwrap();
+        assert_eq!(from, 1);
+        assert!(new.contains(&format!("schema = {}", SCHEMA)));
+        assert!(new.contains("simple = false"));
+        assert!(new.contains("in_peers = 450"));
+        // Already current, newer, and unparsable files are untouched.
+        assert!(migrate(&new).is_none());
+        assert!(migrate(&format!("schema = {}\n", SCHEMA + 1)).is_none());
+        assert!(migrate("[gupax\n").is_none());
+        assert_eq!(
+            backup_path(Path::new("/tmp/state.toml"), 1),
+            PathBuf::from("/tmp/state.toml.v1.bak")
+        );
+    }
+
+    #[test]
+    fn migrate_steps() {
+        use crate::migrate::*;
+        fn rename(table: &mut Table) {
+            let gupax = table.get_mut("gupax").unwrap().as_table_mut().unwrap();
+            let value = gupax.remove("old").unwrap();
+            gupax.insert("new".to_string(), value);
+        }
+        fn double(table: &mut Table) {
+            let gupax = table.get_mut("gupax").unwrap().as_table_mut().unwrap();
+            let n = gupax["new"].as_float().unwrap();
+            gupax.insert("new".to_string(), Value::Float(n * 2.0));
+        }
+        let steps: [Step; 2] = [rename, double];
+        // v1 gets both steps.
+        let mut table: Table = toml::from_str("[gupax]\nold = 1.5\n").unwrap();
+        let from = schema(&table);
+        run(&mut table, from, 3, &steps);
+        assert_eq!(table["schema"].as_integer(), Some(3));
+        assert_eq!(table["gupax"]["new"].as_float(), Some(3.0));
+        // v2 only gets the second.
+        let mut table: Table = toml::from_str("schema = 2\n[gupax]\nnew = 1.5\n").unwrap();
+        let from = schema(&table);
+        run(&mut table, from, 3, &steps);
+        assert_eq!(table["gupax"]["new"].as_float(), Some(3.0));
+    }
+
+    #[test]
+    fn migrate_on_read() {
+        use crate::disk::State;
+        use crate::migrate::*;
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("state.toml");
+        let old = "[gupax]\nsimple = false\n\n[p2pool]\nin_peers = 45\n";
+
+        // Read-only: migrated in memory, nothing is written.
+        std::fs::write(&path, old).unwrap();
+        let state = State::get(&path, true).unwrap();
+        assert!(!state.gupax.simple);
+        assert_eq!(state.p2pool.in_peers, 45);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+        assert!(!backup_path(&path, 1).exists());
+
+        // A failed backup is only a warning, and the old file is left alone.
+        std::fs::create_dir(backup_path(&path, 1)).unwrap();
+        let state = State::get(&path, false).unwrap();
+        assert_eq!(state.p2pool.in_peers, 45);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+
+        // Otherwise the old file is backed up and replaced.
+        std::fs::remove_dir(backup_path(&path, 1)).unwrap();
+        State::get(&path, false).unwrap();
+        assert_eq!(std::fs::read_to_string(backup_path(&path, 1)).unwrap(), old);
+        assert!(migrate(&std::fs::read_to_string(&path).unwrap()).is_none());
+    }
+}
@@ -0,0 +1,312 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// One-click "Mining modes" on the [Status] tab.
+//
+// Each mode is a bundle of P2Pool + XMRig settings. Picking one shows a
+// diff of what would change, [Apply] sets them all at once (both states
+// in the same frame), they take effect the next time P2Pool/XMRig start.
+//
+// [ThreadPreset] is the smaller [Simple] XMRig version: Eco/Standard/Max
+// only set the CPU usage %, with a rough hashrate/power guess next to them.
+
+use crate::disk::{P2pool, Xmrig};
+
+//---------------------------------------------------------------------------------------------------- [MiningMode]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MiningMode {
+    MaxProfit,
+    Balanced,
+    Background,
+}
+
+// The settings a mode changes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct Settings {
+    threads: usize,
+    pause: u8,
+    out_peers: u16,
+    in_peers: u16,
+    priority: Option<u8>,
+}
+
+// A single [old -> new] setting change.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Change {
+    pub setting: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl MiningMode {
+    pub const ALL: [Self; 3] = [Self::MaxProfit, Self::Balanced, Self::Background];
+
+    pub const fn description(&self) -> &'static str {
+        use crate::constants::*;
+        match self {
+            Self::MaxProfit => STATUS_MODE_MAX_PROFIT,
+            Self::Balanced => STATUS_MODE_BALANCED,
+            Self::Background => STATUS_MODE_BACKGROUND,
+        }
+    }
+
+    fn settings(&self, xmrig: &Xmrig) -> Settings {
+        let max = xmrig.max_threads.max(1);
+        // Pause on active isn't supported on Linux, leave it alone there.
+        let pause = |secs| {
+            if cfg!(target_os = "linux") {
+                xmrig.pause
+            } else {
+                secs
+            }
+        };
+        match self {
+            Self::MaxProfit => Settings {
+                threads: max,
+                pause: pause(0),
+                out_peers: 50,
+                in_peers: 50,
+                priority: Some(3),
+            },
+            Self::Balanced => Settings {
+                threads: (max / 2).max(1),
+                pause: pause(0),
+                out_peers: 10,
+                in_peers: 10,
+                priority: None,
+            },
+            Self::Background => Settings {
+                threads: (max / 4).max(1),
+                pause: pause(60),
+                out_peers: 10,
+                in_peers: 10,
+                priority: Some(0),
+            },
+        }
+    }
+
+    // What [apply()] would change, empty if the settings already match.
+    pub fn diff(&self, p2pool: &P2pool, xmrig: &Xmrig) -> Vec<Change> {
+        let new = self.settings(xmrig);
+        let priority = |p: Option<u8>| match p {
+            Some(p) => p.to_string(),
+            None => "XMRig default".to_string(),
+        };
+        [
+            (
+                "XMRig threads",
+                xmrig.current_threads.to_string(),
+                new.threads.to_string(),
+            ),
+            (
+                "XMRig pause on active",
+                format!("{}s", xmrig.pause),
+                format!("{}s", new.pause),
+            ),
+            (
+                "XMRig CPU priority",
+                priority(xmrig.priority),
+                priority(new.priority),
+            ),
+        ]
+        .into_iter()
+        .chain(Self::peers_diff(p2pool, &new))
+        .filter(|(_, old, new)| old != new)
+        .map(|(setting, old, new)| Change { setting, old, new })
+        .collect()
+    }
+
+    // [Simple] has a single peers slider with a smaller range.
+    fn peers_diff(p2pool: &P2pool, new: &Settings) -> Vec<(&'static str, String, String)> {
+        if p2pool.simple {
+            vec![(
+                "P2Pool peers",
+                p2pool.simple_peers.to_string(),
+                Self::simple_peers(new).to_string(),
+            )]
+        } else {
+            vec![
+                (
+                    "P2Pool out peers",
+                    p2pool.out_peers.to_string(),
+                    new.out_peers.to_string(),
+                ),
+                (
+                    "P2Pool in peers",
+                    p2pool.in_peers.to_string(),
+                    new.in_peers.to_string(),
+                ),
+            ]
+        }
+    }
+
+    fn simple_peers(new: &Settings) -> u16 {
+        let range = crate::constants::P2POOL_SIMPLE_PEERS_RANGE;
+        new.out_peers.clamp(*range.start(), *range.end())
+    }
+
+    pub fn apply(&self, p2pool: &mut P2pool, xmrig: &mut Xmrig) {
+        let new = self.settings(xmrig);
+        xmrig.current_threads = new.threads;
+        xmrig.cpu_usage = Xmrig::usage_for_threads(new.threads, xmrig.max_threads);
+        xmrig.pause = new.pause;
+        xmrig.priority = new.priority;
+        p2pool.out_peers = new.out_peers;
+        p2pool.in_peers = new.in_peers;
+        p2pool.simple_peers = Self::simple_peers(&new);
+    }
+}
+
+impl std::fmt::Display for MiningMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MaxProfit => write!(f, "Max profit"),
+            Self::Balanced => write!(f, "Balanced"),
+            Self::Background => write!(f, "Background"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [ThreadPreset]
+// Very rough: desktop/laptop CPUs draw somewhere around 4-8W
+// per busy thread under RandomX, there's no per-CPU power data.
+const WATTS_PER_THREAD: f32 = 5.0;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ThreadPreset {
+    Eco,
+    Standard,
+    Max,
+}
+
+impl ThreadPreset {
+    pub const ALL: [Self; 3] = [Self::Eco, Self::Standard, Self::Max];
+
+    pub const fn usage(&self) -> u8 {
+        match self {
+            Self::Eco => 25,
+            Self::Standard => 50,
+            Self::Max => 100,
+        }
+    }
+
+    pub fn threads(&self, max_threads: usize) -> usize {
+        Xmrig::threads_for_usage(self.usage(), max_threads)
+    }
+
+    // Is [xmrig] currently set to this preset?
+    pub fn selected(&self, xmrig: &Xmrig) -> bool {
+        xmrig.current_threads == self.threads(xmrig.max_threads)
+    }
+
+    // (Hashrate, watts) guess for this preset. [average] is the benchmark
+    // hashrate of the user's CPU with every thread, RandomX scales about
+    // linearly with threads until the L3 cache runs out.
+    pub fn estimate(&self, max_threads: usize, average: f32) -> (f32, u32) {
+        let threads = self.threads(max_threads);
+        let hashrate = average / max_threads.max(1) as f32 * threads as f32;
+        let watts = (WATTS_PER_THREAD * threads as f32).round() as u32;
+        (hashrate, watts)
+    }
+
+    pub fn apply(&self, xmrig: &mut Xmrig) {
+        xmrig.cpu_usage = self.usage();
+        xmrig.current_threads = self.threads(xmrig.max_threads);
+    }
+}
+
+impl std::fmt::Display for ThreadPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Eco => write!(f, "Eco"),
+            Self::Standard => write!(f, "Standard"),
+            Self::Max => write!(f, "Max"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mining_mode_diff_apply() {
+        let mut p2pool = P2pool {
+            simple: false,
+            ..Default::default()
+        };
+        let mut xmrig = Xmrig {
+            max_threads: 8,
+            current_threads: 4,
+            ..Default::default()
+        };
+
+        let diff = MiningMode::Balanced.diff(&p2pool, &xmrig);
+        assert!(diff.is_empty(), "{diff:?}");
+
+        let diff = MiningMode::MaxProfit.diff(&p2pool, &xmrig);
+        assert!(diff.contains(&Change {
+            setting: "XMRig threads",
+            old: "4".to_string(),
+            new: "8".to_string(),
+        }));
+        assert!(diff.iter().any(|c| c.setting == "P2Pool out peers"));
+
+        MiningMode::Background.apply(&mut p2pool, &mut xmrig);
+        assert_eq!(xmrig.current_threads, 2);
+        assert_eq!(xmrig.priority, Some(0));
+        assert_eq!((p2pool.out_peers, p2pool.in_peers), (10, 10));
+        assert!(MiningMode::Background.diff(&p2pool, &xmrig).is_empty());
+
+        // [Simple] only shows its single peers slider.
+        p2pool.simple = true;
+        let diff = MiningMode::MaxProfit.diff(&p2pool, &xmrig);
+        assert!(diff
+            .iter()
+            .any(|c| c.setting == "P2Pool peers" && c.new == "50"));
+        assert!(!diff.iter().any(|c| c.setting == "P2Pool out peers"));
+        MiningMode::MaxProfit.apply(&mut p2pool, &mut xmrig);
+        assert_eq!(p2pool.simple_peers, 50);
+
+        // Always at least 1 thread.
+        xmrig.max_threads = 1;
+        MiningMode::Background.apply(&mut p2pool, &mut xmrig);
+        assert_eq!(xmrig.current_threads, 1);
+    }
+
+    #[test]
+    fn thread_presets() {
+        let mut xmrig = Xmrig {
+            max_threads: 16,
+            current_threads: 3,
+            ..Default::default()
+        };
+        assert!(!ThreadPreset::ALL.iter().any(|p| p.selected(&xmrig)));
+        ThreadPreset::Eco.apply(&mut xmrig);
+        assert_eq!((xmrig.current_threads, xmrig.cpu_usage), (4, 25));
+        assert!(ThreadPreset::Eco.selected(&xmrig));
+        ThreadPreset::Max.apply(&mut xmrig);
+        assert_eq!((xmrig.current_threads, xmrig.cpu_usage), (16, 100));
+
+        // 16 threads at 16000 H/s -> 1000 H/s per thread.
+        assert_eq!(ThreadPreset::Standard.estimate(16, 16000.0), (8000.0, 40));
+        assert_eq!(ThreadPreset::Eco.estimate(16, 16000.0), (4000.0, 20));
+        // Always at least 1 thread.
+        assert_eq!(ThreadPreset::Eco.estimate(2, 2000.0), (1000.0, 5));
+    }
+}
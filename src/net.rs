@@ -0,0 +1,219 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// IPv4/IPv6 preference and DNS-over-HTTPS for Gupax's own HTTP(S) calls.
+//
+// Some networks have broken IPv6 or hijack DNS. The HTTP clients for
+// updates, the node pings, the P2Pool advisor/stats and usage stats all
+// resolve hosts through [Resolver], which can query a DNS-over-HTTPS server
+// (the JSON API Cloudflare/Google/Quad9 serve) and puts the preferred
+// address family first. P2Pool and XMRig do their own DNS and aren't affected.
+
+use crate::macros::*;
+use hyper::client::{connect::dns::Name, HttpConnector};
+use hyper::{Body, Client, Request};
+use hyper_tls::HttpsConnector;
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const DEFAULT_DOH: &str = "https://cloudflare-dns.com/dns-query";
+const DOH_TIMEOUT: Duration = Duration::from_secs(5);
+// Set by the GUI thread from the saved [Gupax] settings, read on every lookup.
+static CONFIG: Mutex<(IpPreference, String)> = Mutex::new((IpPreference::Auto, String::new()));
+
+//---------------------------------------------------------------------------------------------------- IpPreference
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum IpPreference {
+    #[default]
+    Auto,
+    Ipv4,
+    Ipv6,
+}
+
+impl IpPreference {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Ipv4, Self::Ipv6];
+
+    // The preferred addresses first, the other family is still tried after them.
+    fn sort(self, addrs: &mut [SocketAddr]) {
+        match self {
+            Self::Auto => (),
+            Self::Ipv4 => addrs.sort_by_key(|a| !a.is_ipv4()),
+            Self::Ipv6 => addrs.sort_by_key(|a| !a.is_ipv6()),
+        }
+    }
+}
+
+impl std::fmt::Display for IpPreference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "Auto"),
+            Self::Ipv4 => write!(f, "Prefer IPv4"),
+            Self::Ipv6 => write!(f, "Prefer IPv6"),
+        }
+    }
+}
+
+pub fn set(ip: IpPreference, doh: &str) {
+    let mut config = lock!(CONFIG);
+    if config.0 != ip || config.1 != doh.trim() {
+        *config = (ip, doh.trim().to_string());
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Clients
+pub type HttpsClient = Client<HttpsConnector<HttpConnector<Resolver>>>;
+pub type HttpClient = Client<HttpConnector<Resolver>>;
+
+// HTTPS only, for everything that talks to the internet.
+pub fn https_client() -> HttpsClient {
+    let mut http = HttpConnector::new_with_resolver(Resolver);
+    http.enforce_http(false);
+    let mut https = HttpsConnector::new_with_connector(http);
+    https.https_only(true);
+    Client::builder().build(https)
+}
+
+// Plain HTTP, for the Monero node RPC.
+pub fn http_client() -> HttpClient {
+    Client::builder().build(HttpConnector::new_with_resolver(Resolver))
+}
+
+//---------------------------------------------------------------------------------------------------- Resolver
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Resolver;
+
+impl hyper::service::Service<Name> for Resolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = std::io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let (ip, doh) = lock!(CONFIG).clone();
+        Box::pin(async move {
+            let mut addrs = resolve(name.as_str(), &doh).await?;
+            ip.sort(&mut addrs);
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+// [doh] if set, the system resolver if not (or if [doh] fails).
+async fn resolve(host: &str, doh: &str) -> std::io::Result<Vec<SocketAddr>> {
+    if !doh.is_empty() {
+        match tokio::time::timeout(DOH_TIMEOUT, doh_lookup(doh, host)).await {
+            Ok(Ok(ips)) if !ips.is_empty() => {
+                return Ok(ips.into_iter().map(|ip| SocketAddr::new(ip, 0)).collect());
+            }
+            Ok(Ok(_)) => warn!("Net | DoH [{}] has no address for [{}]", doh, host),
+            Ok(Err(e)) => warn!("Net | DoH [{}] lookup of [{}] failed: {}", doh, host, e),
+            Err(_) => warn!("Net | DoH [{}] lookup of [{}] timed out", doh, host),
+        }
+        warn!("Net | Falling back to the system resolver for [{}]", host);
+    }
+    Ok(tokio::net::lookup_host((host, 0)).await?.collect())
+}
+
+// The [A] and [AAAA] records of [host] from the DoH server at [url].
+// The server itself is looked up with the system resolver.
+async fn doh_lookup(url: &str, host: &str) -> Result<Vec<IpAddr>, anyhow::Error> {
+    let mut https = HttpsConnector::new();
+    https.https_only(true);
+    let client: Client<HttpsConnector<HttpConnector>> = Client::builder().build(https);
+    let mut ips = vec![];
+    for kind in ["A", "AAAA"] {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("{}{}name={}&type={}", url, separator, host, kind))
+            .header("Accept", "application/dns-json")
+            .body(Body::empty())?;
+        let response = client.request(request).await?;
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        ips.extend(parse_doh(&body)?);
+    }
+    Ok(ips)
+}
+
+// The addresses in a DoH JSON answer, CNAMEs and the like are skipped.
+fn parse_doh(body: &[u8]) -> Result<Vec<IpAddr>, serde_json::Error> {
+    #[derive(Deserialize)]
+    struct Answer {
+        data: String,
+    }
+    #[derive(Deserialize)]
+    struct Response {
+        #[serde(rename = "Answer", default)]
+        answer: Vec<Answer>,
+    }
+    let response: Response = serde_json::from_slice(body)?;
+    Ok(response
+        .answer
+        .iter()
+        .filter_map(|a| a.data.parse().ok())
+        .collect())
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn ip_preference() {
+        use crate::net::*;
+        let v4: SocketAddr = "1.1.1.1:0".parse().unwrap();
+        let v6: SocketAddr = "[2606:4700::1111]:0".parse().unwrap();
+        let v4b: SocketAddr = "1.0.0.1:0".parse().unwrap();
+        let mut addrs = [v6, v4, v4b];
+        IpPreference::Auto.sort(&mut addrs);
+        assert_eq!(addrs, [v6, v4, v4b]);
+        IpPreference::Ipv4.sort(&mut addrs);
+        assert_eq!(addrs, [v4, v4b, v6]);
+        IpPreference::Ipv6.sort(&mut addrs);
+        assert_eq!(addrs, [v6, v4, v4b]);
+    }
+
+    #[test]
+    fn parse_doh_answer() {
+        use crate::net::*;
+        let body = br#"{"Status":0,"Answer":[
+            {"name":"github.com","type":5,"TTL":60,"data":"alias.github.com."},
+            {"name":"alias.github.com","type":1,"TTL":60,"data":"140.82.121.4"},
+            {"name":"alias.github.com","type":28,"TTL":60,"data":"2606:50c0:8000::154"}
+        ]}"#;
+        let ips = parse_doh(body).unwrap();
+        assert_eq!(
+            ips,
+            [
+                "140.82.121.4".parse::<IpAddr>().unwrap(),
+                "2606:50c0:8000::154".parse().unwrap()
+            ]
+        );
+        // NXDOMAIN has no [Answer].
+        assert!(parse_doh(br#"{"Status":3}"#).unwrap().is_empty());
+        assert!(parse_doh(b"not json").is_err());
+    }
+}
@@ -17,7 +17,7 @@
 
 use crate::{constants::*, macros::*};
 use egui::Color32;
-use hyper::{client::HttpConnector, Body, Client, Request};
+use hyper::{Body, Request};
 use log::*;
 use rand::{thread_rng, Rng};
 use std::sync::{Arc, Mutex};
@@ -265,6 +265,39 @@ pub const GREEN_NODE_PING: u128 = 300;
 // yellow is anything in-between green/red
 pub const RED_NODE_PING: u128 = 500;
 pub const TIMEOUT_NODE_PING: u128 = 5000;
+// Past this, shares reach the node late enough to be noticeably more likely to become uncles.
+pub const SLOW_NODE_PING: u128 = GREEN_NODE_PING;
+
+// How the selected node's latency looks compared to the fastest one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Latency {
+    // The selected node is fast enough.
+    Fine,
+    // The selected node is slow, but a faster one exists.
+    Slow,
+    // Even the fastest node is slow, the remote nodes are all far away.
+    Far,
+}
+
+impl Latency {
+    pub const fn new(selected_ms: u128, fastest_ms: u128) -> Self {
+        if selected_ms <= SLOW_NODE_PING {
+            Self::Fine
+        } else if fastest_ms <= SLOW_NODE_PING {
+            Self::Slow
+        } else {
+            Self::Far
+        }
+    }
+
+    pub const fn hint(&self) -> &'static str {
+        match self {
+            Self::Fine => "",
+            Self::Slow => "This node is slow, your shares may arrive late and become uncles. A faster node is available: [Select fastest node]",
+            Self::Far => "Every remote node is slow from your location, your shares may arrive late and become uncles. Consider running your own Monero node",
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct NodeData {
@@ -316,6 +349,7 @@ pub struct Ping {
     pub prog: f32,
     pub pinged: bool,
     pub auto_selected: bool,
+    pub generation: u64, // Bumped every time a ping finishes (or fails)
 }
 
 impl Default for Ping {
@@ -334,6 +368,7 @@ impl Ping {
             prog: 0.0,
             pinged: false,
             auto_selected: true,
+            generation: 0,
         }
     }
 
@@ -343,6 +378,9 @@ impl Ping {
     // Intermediate function for spawning thread
     pub fn spawn_thread(ping: &Arc<Mutex<Self>>) {
         info!("Spawning ping thread...");
+        // Set here and not only in the thread, so the
+        // caller's next look at [pinging] already sees it.
+        lock!(ping).pinging = true;
         let ping = Arc::clone(ping);
         std::thread::spawn(move || {
             let now = Instant::now();
@@ -361,7 +399,9 @@ impl Ping {
                 }
             }
             info!("Ping ... Took [{}] seconds...", now.elapsed().as_secs_f32());
-            lock!(ping).pinging = false;
+            let mut lock = lock!(ping);
+            lock.generation += 1;
+            lock.pinging = false;
         });
     }
 
@@ -395,7 +435,7 @@ impl Ping {
         // Create HTTP client
         let info = "Creating HTTP Client".to_string();
         lock!(ping).msg = info;
-        let client: Client<HttpConnector> = Client::builder().build(HttpConnector::new());
+        let client = crate::net::http_client();
 
         // Random User Agent
         let rand_user_agent = crate::Pkg::get_user_agent();
@@ -442,7 +482,7 @@ impl Ping {
     #[cold]
     #[inline(never)]
     async fn response(
-        client: Client<HttpConnector>,
+        client: crate::net::HttpClient,
         request: Request<Body>,
         ip: &'static str,
         ping: Arc<Mutex<Self>>,
@@ -497,9 +537,559 @@ impl Ping {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Pool ping
+// Same idea as [Ping], but for the user's own [pool.toml] entries. Pools aren't
+// HTTP, so this measures a plain TCP connect (port open), and optionally
+// waits for the answer to a stratum [login] to make sure a pool is behind it.
+const POOL_PING_THREADS: usize = 16;
+const POOL_STRATUM_LOGIN: &str = r#"{"id":1,"jsonrpc":"2.0","method":"login","params":{"login":"x","pass":"x","agent":"Gupax"}}"#;
+
+#[derive(Debug, Clone)]
+pub struct PoolData {
+    pub ip: String,
+    pub port: String,
+    pub ms: u128, // [TIMEOUT_NODE_PING] if it failed
+    pub color: Color32,
+}
+
+#[derive(Debug)]
+pub struct PoolPing {
+    pub pools: Vec<PoolData>,
+    pub stratum: bool, // Also wait for a stratum response?
+    pub pinging: bool,
+    pub msg: String,
+    pub prog: f32,
+    pub pinged: bool,
+}
+
+impl Default for PoolPing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolPing {
+    pub fn new() -> Self {
+        Self {
+            pools: Vec::new(),
+            stratum: false,
+            pinging: false,
+            msg: "No ping in progress".to_string(),
+            prog: 0.0,
+            pinged: false,
+        }
+    }
+
+    // The last result for this pool, if it was pinged.
+    pub fn get(&self, ip: &str, port: &str) -> Option<&PoolData> {
+        self.pools.iter().find(|p| p.ip == ip && p.port == port)
+    }
+
+    // The lowest successful latency of any pool on this IP, for sorting.
+    pub fn ms(&self, ip: &str) -> Option<u128> {
+        self.pools
+            .iter()
+            .filter(|p| p.ip == ip && p.ms < TIMEOUT_NODE_PING)
+            .map(|p| p.ms)
+            .min()
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(ping: &Arc<Mutex<Self>>, pools: Vec<(String, String)>) {
+        info!("Spawning pool ping thread...");
+        let ping = Arc::clone(ping);
+        std::thread::spawn(move || {
+            let now = Instant::now();
+            let stratum = {
+                let mut lock = lock!(ping);
+                lock.pinging = true;
+                lock.prog = 0.0;
+                lock.stratum
+            };
+            let percent = 100.0 / (pools.len().max(1) as f32);
+            let mut results = Vec::with_capacity(pools.len());
+            for chunk in pools.chunks(POOL_PING_THREADS) {
+                std::thread::scope(|s| {
+                    let handles: Vec<_> = chunk
+                        .iter()
+                        .map(|(ip, port)| {
+                            let ping = Arc::clone(&ping);
+                            s.spawn(move || {
+                                let ms = Self::ping_pool(ip, port, stratum);
+                                info!("Pool Ping | {ms}ms ... {ip}:{port}");
+                                let mut lock = lock!(ping);
+                                lock.msg = format!("{ms}ms ... {ip}:{port}");
+                                lock.prog += percent;
+                                PoolData {
+                                    ip: ip.clone(),
+                                    port: port.clone(),
+                                    ms,
+                                    color: Self::color(ms),
+                                }
+                            })
+                        })
+                        .collect();
+                    for handle in handles {
+                        if let Ok(data) = handle.join() {
+                            results.push(data);
+                        }
+                    }
+                });
+            }
+            let reachable = results.iter().filter(|p| p.ms < TIMEOUT_NODE_PING).count();
+            let mut lock = lock!(ping);
+            lock.msg = format!("Pinged {} pools, {} reachable", results.len(), reachable);
+            lock.pools = results;
+            lock.prog = 100.0;
+            lock.pinged = true;
+            lock.pinging = false;
+            info!(
+                "Pool Ping ... Took [{}] seconds...",
+                now.elapsed().as_secs_f32()
+            );
+        });
+    }
+
+    // Milliseconds until the port accepted (and answered the stratum login), or [TIMEOUT_NODE_PING].
+    fn ping_pool(ip: &str, port: &str, stratum: bool) -> u128 {
+        use std::io::{BufRead, Write};
+        use std::net::ToSocketAddrs;
+        let timeout = Duration::from_millis(TIMEOUT_NODE_PING as u64);
+        let ip = if ip == "localhost" { "127.0.0.1" } else { ip };
+        let addr = match (ip, port.parse::<u16>().unwrap_or(0)).to_socket_addrs() {
+            Ok(mut addrs) => match addrs.next() {
+                Some(addr) => addr,
+                None => return TIMEOUT_NODE_PING,
+            },
+            Err(_) => return TIMEOUT_NODE_PING,
+        };
+        let now = Instant::now();
+        let Ok(mut stream) = std::net::TcpStream::connect_timeout(&addr, timeout) else {
+            return TIMEOUT_NODE_PING;
+        };
+        if stratum {
+            let left = timeout
+                .saturating_sub(now.elapsed())
+                .max(Duration::from_millis(1));
+            let _ = stream.set_read_timeout(Some(left));
+            let _ = stream.set_write_timeout(Some(left));
+            if writeln!(stream, "{}", POOL_STRATUM_LOGIN).is_err() {
+                return TIMEOUT_NODE_PING;
+            }
+            let mut line = String::new();
+            let mut reader = std::io::BufReader::new(stream);
+            match reader.read_line(&mut line) {
+                Ok(_) if serde_json::from_str::<serde_json::Value>(&line).is_ok() => (),
+                _ => return TIMEOUT_NODE_PING,
+            }
+        }
+        now.elapsed().as_millis().min(TIMEOUT_NODE_PING)
+    }
+
+    fn color(ms: u128) -> Color32 {
+        if ms < GREEN_NODE_PING {
+            GREEN
+        } else if ms < RED_NODE_PING {
+            YELLOW
+        } else if ms < TIMEOUT_NODE_PING {
+            RED
+        } else {
+            BLACK
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Node RPC
+// A small, curated set of read-only RPC calls that can be sent to the selected
+// node from the P2Pool tab. Most "P2Pool is broken" reports turn out to be a node
+// that is behind, has no peers or runs restricted RPC, so the formatted result
+// points those out first and the raw JSON is appended below it.
+const NODE_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+// A block older than this (seconds) means the node is probably stuck.
+const NODE_RPC_STALE_BLOCK: u64 = 20 * 60;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RpcCall {
+    GetInfo,
+    GetLastBlockHeader,
+    SyncInfo,
+}
+
+impl RpcCall {
+    pub const ALL: [Self; 3] = [Self::GetInfo, Self::GetLastBlockHeader, Self::SyncInfo];
+
+    pub const fn method(&self) -> &'static str {
+        match self {
+            Self::GetInfo => "get_info",
+            Self::GetLastBlockHeader => "get_last_block_header",
+            Self::SyncInfo => "sync_info",
+        }
+    }
+}
+
+impl std::fmt::Display for RpcCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.method())
+    }
+}
+
+#[derive(Debug)]
+pub struct NodeRpc {
+    pub call: RpcCall,
+    pub running: bool,
+    pub node: String,   // [ip:port] the last call was sent to
+    pub output: String, // Formatted result of the last call
+}
+
+impl Default for NodeRpc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeRpc {
+    pub fn new() -> Self {
+        Self {
+            call: RpcCall::GetInfo,
+            running: false,
+            node: String::new(),
+            output: String::new(),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(rpc: &Arc<Mutex<Self>>, ip: &str, port: &str) {
+        let rpc = Arc::clone(rpc);
+        let node = format!("{}:{}", ip, port);
+        let call = {
+            let mut lock = lock!(rpc);
+            lock.running = true;
+            lock.node = node.clone();
+            lock.output = format!("Sending [{}] to [{}]...", lock.call, node);
+            lock.call
+        };
+        info!("Node RPC | Sending [{}] to [{}]...", call, node);
+        std::thread::spawn(move || {
+            let output = match Self::request(&node, call.method(), "{}") {
+                Ok(json) => Self::format(call, &json),
+                Err(e) => {
+                    warn!("Node RPC | [{}] to [{}] failed: {}", call, node, e);
+                    format!("[{}] to [{}] failed: {}\n\nIs the node online and is its RPC port reachable from this machine?", call, node, e)
+                }
+            };
+            let mut lock = lock!(rpc);
+            lock.output = output;
+            lock.running = false;
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[tokio::main(flavor = "current_thread")]
+    pub async fn request(
+        node: &str,
+        method: &str,
+        params: &str,
+    ) -> Result<serde_json::Value, anyhow::Error> {
+        let client = crate::net::http_client();
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("http://{}/json_rpc", node))
+            .header("User-Agent", crate::Pkg::get_user_agent())
+            .body(Body::from(format!(
+                r#"{{"jsonrpc":"2.0","id":"0","method":"{}","params":{}}}"#,
+                method, params
+            )))?;
+        let response = tokio::time::timeout(NODE_RPC_TIMEOUT, client.request(request))
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!("timed out after {} seconds", NODE_RPC_TIMEOUT.as_secs())
+            })??;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        match serde_json::from_slice(&bytes) {
+            Ok(json) => Ok(json),
+            Err(_) => Err(anyhow::anyhow!("HTTP {}, response was not JSON", status)),
+        }
+    }
+
+    // Pick out the fields that matter for diagnosing the node, flag
+    // anything that looks wrong, then append the whole response.
+    pub fn format(call: RpcCall, json: &serde_json::Value) -> String {
+        let mut out = String::new();
+        let mut warnings = Vec::new();
+
+        if let Some(error) = json.get("error").filter(|e| !e.is_null()) {
+            let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+            let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("");
+            out.push_str(&format!("Error {}: {}\n", code, message));
+            if code == -32601 {
+                warnings.push(format!(
+                    "[{}] is not available, the node is probably running restricted RPC",
+                    call
+                ));
+            }
+        }
+
+        if let Some(result) = json.get("result") {
+            let str = |key: &str| result.get(key).and_then(|v| v.as_str());
+            let u64 = |key: &str| result.get(key).and_then(|v| v.as_u64());
+            let bool = |key: &str| result.get(key).and_then(|v| v.as_bool());
+            let mut field = |name: &str, value: Option<String>| {
+                if let Some(value) = value {
+                    out.push_str(&format!("{:<20}{}\n", name, value));
+                }
+            };
+
+            if let Some(status) = str("status") {
+                field("Status", Some(status.to_string()));
+                if status != "OK" {
+                    warnings.push(format!("Node reported status [{}]", status));
+                }
+            }
+
+            match call {
+                RpcCall::GetInfo => {
+                    field("Version", str("version").map(String::from));
+                    field("Network", str("nettype").map(String::from));
+                    field("Height", u64("height").map(|h| h.to_string()));
+                    field("Target height", u64("target_height").map(|h| h.to_string()));
+                    field("Synchronized", bool("synchronized").map(|b| b.to_string()));
+                    field("Busy syncing", bool("busy_syncing").map(|b| b.to_string()));
+                    field(
+                        "Outgoing peers",
+                        u64("outgoing_connections_count").map(|c| c.to_string()),
+                    );
+                    field(
+                        "Incoming peers",
+                        u64("incoming_connections_count").map(|c| c.to_string()),
+                    );
+                    field("Restricted RPC", bool("restricted").map(|b| b.to_string()));
+                    field(
+                        "Database size",
+                        u64("database_size").map(|s| format!("{} GB", s / 1_000_000_000)),
+                    );
+                    if bool("synchronized") == Some(false) {
+                        warnings.push(
+                            "Node is not synchronized, P2Pool cannot mine until it is".to_string(),
+                        );
+                    }
+                    if bool("busy_syncing") == Some(true) {
+                        warnings.push("Node is busy syncing".to_string());
+                    }
+                    if u64("outgoing_connections_count") == Some(0) {
+                        warnings.push(
+                            "Node has 0 outgoing peers, check its internet connection".to_string(),
+                        );
+                    }
+                    if str("nettype").is_some_and(|n| n != "mainnet") {
+                        warnings.push("Node is not on mainnet".to_string());
+                    }
+                }
+                RpcCall::GetLastBlockHeader => {
+                    let header = result.get("block_header");
+                    let h_u64 =
+                        |key: &str| header.and_then(|h| h.get(key)).and_then(|v| v.as_u64());
+                    field("Height", h_u64("height").map(|h| h.to_string()));
+                    field(
+                        "Hash",
+                        header
+                            .and_then(|h| h.get("hash"))
+                            .and_then(|v| v.as_str())
+                            .map(String::from),
+                    );
+                    field(
+                        "Reward",
+                        h_u64("reward")
+                            .map(|r| format!("{} XMR", crate::xmr::AtomicUnit::from_u64(r))),
+                    );
+                    field("Difficulty", h_u64("difficulty").map(|d| d.to_string()));
+                    if let Some(timestamp) = h_u64("timestamp") {
+                        let now = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        let age = now.saturating_sub(timestamp);
+                        field("Block age", Some(format!("{} seconds", age)));
+                        if age > NODE_RPC_STALE_BLOCK {
+                            warnings.push(format!(
+                                "Last block is {} minutes old, the node may be stuck or behind",
+                                age / 60
+                            ));
+                        }
+                    }
+                }
+                RpcCall::SyncInfo => {
+                    field("Height", u64("height").map(|h| h.to_string()));
+                    field("Target height", u64("target_height").map(|h| h.to_string()));
+                    let peers = result
+                        .get("peers")
+                        .and_then(|p| p.as_array())
+                        .map(|p| p.len());
+                    field("Peers", peers.map(|p| p.to_string()));
+                    let spans = result
+                        .get("spans")
+                        .and_then(|s| s.as_array())
+                        .map(|s| s.len());
+                    field("Spans", spans.map(|s| s.to_string()));
+                    if let (Some(height), Some(target)) = (u64("height"), u64("target_height")) {
+                        if target > height {
+                            warnings.push(format!("Node is {} blocks behind", target - height));
+                        }
+                    }
+                    if peers == Some(0) {
+                        warnings
+                            .push("Node has 0 peers, check its internet connection".to_string());
+                    }
+                }
+            }
+        }
+
+        if warnings.is_empty() && json.get("result").is_some() {
+            out.insert_str(0, "Node looks healthy\n\n");
+        } else {
+            let mut top = String::new();
+            for w in &warnings {
+                top.push_str(&format!("WARNING: {}\n", w));
+            }
+            top.push('\n');
+            out.insert_str(0, &top);
+        }
+
+        out.push_str("\n--- Raw response ---\n");
+        out.push_str(&serde_json::to_string_pretty(json).unwrap_or_default());
+        out
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Block hash
+// P2Pool's payout log only has the block height, this asks
+// the selected node for the hash so the GUI can copy it.
+#[derive(Debug, Default)]
+pub struct BlockHash {
+    pub running: bool,
+    pub height: u64,
+    pub hash: Option<String>, // Taken by the GUI once it's copied
+    pub msg: String,          // Result of the last lookup, shown to the user
+}
+
+impl BlockHash {
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(lookup: &Arc<Mutex<Self>>, ip: &str, port: &str, height: u64) {
+        let lookup = Arc::clone(lookup);
+        let node = format!("{}:{}", ip, port);
+        {
+            let mut lock = lock!(lookup);
+            lock.running = true;
+            lock.height = height;
+            lock.hash = None;
+            lock.msg = format!("Asking [{}] for the hash of block {}...", node, height);
+        }
+        std::thread::spawn(move || {
+            let params = format!(r#"{{"height":{}}}"#, height);
+            let result = NodeRpc::request(&node, "get_block_header_by_height", &params);
+            let hash = result.and_then(|json| {
+                json.pointer("/result/block_header/hash")
+                    .and_then(|h| h.as_str())
+                    .map(String::from)
+                    .ok_or_else(|| anyhow::anyhow!("the node did not return a block hash"))
+            });
+            let mut lock = lock!(lookup);
+            lock.running = false;
+            match hash {
+                Ok(hash) => {
+                    info!("Block Hash | Block [{}] is [{}]", height, hash);
+                    lock.hash = Some(hash);
+                }
+                Err(e) => {
+                    warn!(
+                        "Block Hash | Block [{}] lookup on [{}] failed: {}",
+                        height, node, e
+                    );
+                    lock.msg = format!(
+                        "Could not get the hash of block {} from [{}]: {}",
+                        height, node, e
+                    );
+                }
+            }
+        });
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod test {
+    #[test]
+    fn latency() {
+        use super::Latency;
+        assert_eq!(Latency::new(80, 40), Latency::Fine);
+        assert_eq!(Latency::new(300, 40), Latency::Fine);
+        assert_eq!(Latency::new(301, 40), Latency::Slow);
+        assert_eq!(Latency::new(5000, 250), Latency::Slow);
+        assert_eq!(Latency::new(450, 301), Latency::Far);
+        assert_eq!(Latency::new(5000, 5000), Latency::Far);
+    }
+
+    #[test]
+    fn node_rpc_format() {
+        use super::{NodeRpc, RpcCall};
+        let healthy = serde_json::json!({"result":{"status":"OK","nettype":"mainnet","height":3000000,"synchronized":true,"busy_syncing":false,"outgoing_connections_count":12}});
+        let out = NodeRpc::format(RpcCall::GetInfo, &healthy);
+        assert!(out.starts_with("Node looks healthy"));
+        assert!(out.contains("3000000"));
+        assert!(out.contains("--- Raw response ---"));
+
+        let stuck = serde_json::json!({"result":{"status":"OK","nettype":"mainnet","synchronized":false,"outgoing_connections_count":0}});
+        let out = NodeRpc::format(RpcCall::GetInfo, &stuck);
+        assert!(out.contains("WARNING: Node is not synchronized"));
+        assert!(out.contains("WARNING: Node has 0 outgoing peers"));
+
+        let old =
+            serde_json::json!({"result":{"status":"OK","block_header":{"height":1,"timestamp":0}}});
+        assert!(NodeRpc::format(RpcCall::GetLastBlockHeader, &old).contains("may be stuck"));
+
+        let behind =
+            serde_json::json!({"result":{"status":"OK","height":10,"target_height":15,"peers":[]}});
+        let out = NodeRpc::format(RpcCall::SyncInfo, &behind);
+        assert!(out.contains("5 blocks behind"));
+        assert!(out.contains("0 peers"));
+
+        let restricted = serde_json::json!({"error":{"code":-32601,"message":"Method not found"}});
+        assert!(NodeRpc::format(RpcCall::SyncInfo, &restricted).contains("restricted RPC"));
+    }
+
+    #[test]
+    fn ping_pool() {
+        use super::{PoolPing, TIMEOUT_NODE_PING};
+        use std::io::{BufRead, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        let server = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) != 0 {
+                    writeln!(
+                        &stream,
+                        r#"{{"id":1,"jsonrpc":"2.0","error":null,"result":{{}}}}"#
+                    )
+                    .unwrap();
+                }
+            }
+        });
+        assert!(PoolPing::ping_pool("localhost", &port, false) < TIMEOUT_NODE_PING);
+        assert!(PoolPing::ping_pool("127.0.0.1", &port, true) < TIMEOUT_NODE_PING);
+        server.join().unwrap();
+        assert_eq!(
+            PoolPing::ping_pool("127.0.0.1", "not a port", false),
+            TIMEOUT_NODE_PING
+        );
+    }
+
     #[test]
     fn validate_node_ips() {
         for (ip, location, rpc, zmq) in crate::REMOTE_NODES {
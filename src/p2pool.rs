@@ -16,30 +16,65 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::regex::REGEXES;
-use crate::{constants::*, disk::*, helper::*, macros::*, node::*, Regexes};
+use crate::{
+    ansi::Console,
+    constants::*,
+    disk::*,
+    gupax::{FileType, FileWindow},
+    helper::*,
+    macros::*,
+    node::*,
+    probe::{BinaryProbe, P2POOL_MIN_BACKUP_HOSTS},
+    Regexes,
+};
 use egui::{
     Button, Checkbox, Color32, ComboBox, Hyperlink, Label, ProgressBar, RichText, SelectableLabel,
     Slider, Spinner, TextEdit, TextStyle::*,
 };
 use log::*;
-use std::sync::{Arc, Mutex};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 impl crate::disk::P2pool {
     #[expect(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         node_vec: &mut Vec<(String, Node)>,
+        node_filter: &mut ListFilter,
+        file_window: &Arc<Mutex<FileWindow>>,
+        probe: &Arc<Mutex<BinaryProbe>>,
         _og: &Arc<Mutex<State>>,
         ping: &Arc<Mutex<Ping>>,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubP2poolApi>>,
-        buffer: &mut String,
+        advisor: &Arc<Mutex<crate::advisor::Advisor>>,
+        help: &mut crate::help::Help,
+        path: &Path,
+        tls: &Arc<Mutex<crate::tls::TlsProxy>>,
+        tls_dir: &Path,
+        ssh: &Arc<Mutex<crate::ssh::Tunnel>>,
+        node_rpc: &Arc<Mutex<crate::node::NodeRpc>>,
+        node_check: &Arc<Mutex<crate::contribute::NodeCheck>>,
+        stdin: &mut crate::stdin::Stdin,
+        console: &mut Console,
+        colors: bool,
         width: f32,
         height: f32,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
     ) {
         let text_edit = height / 25.0;
+        // Older P2Pool's don't support multiple [--host]'s.
+        let probe = lock!(probe).clone();
+        let backup_host_ok = probe.supports(P2POOL_MIN_BACKUP_HOSTS);
+        let backup_host_disabled = format!(
+            "{} (detected P2Pool {}, requires {})",
+            P2POOL_BACKUP_HOST_UNSUPPORTED,
+            probe.version_text(),
+            P2POOL_MIN_BACKUP_HOSTS
+        );
         //---------------------------------------------------------------------------------------------------- [Simple] Console
         debug!("P2Pool Tab | Rendering [Console]");
         ui.group(|ui| {
@@ -54,10 +89,7 @@ impl crate::disk::P2pool {
                         .max_height(height)
                         .auto_shrink([false; 2])
                         .show_viewport(ui, |ui, _| {
-                            ui.add_sized(
-                                [width, height],
-                                TextEdit::multiline(&mut lock!(api).output.as_str()),
-                            );
+                            console.show(ui, &lock!(api).output, colors, [width, height]);
                         });
                 });
             //---------------------------------------------------------------------------------------------------- [Advanced] Console
@@ -72,34 +104,87 @@ impl crate::disk::P2pool {
                         .max_height(height)
                         .auto_shrink([false; 2])
                         .show_viewport(ui, |ui, _| {
-                            ui.add_sized(
-                                [width, height],
-                                TextEdit::multiline(&mut lock!(api).output.as_str()),
-                            );
+                            console.show(ui, &lock!(api).output, colors, [width, height]);
                         });
                 });
                 ui.separator();
-                let response = ui
-                    .add_sized(
-                        [width, text_edit],
-                        TextEdit::hint_text(
-                            TextEdit::singleline(buffer),
-                            r#"Type a command (e.g "help" or "status") and press Enter"#,
-                        ),
-                    )
-                    .on_hover_text(P2POOL_INPUT);
-                // If the user pressed enter, dump buffer contents into the process STDIN
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    response.request_focus(); // Get focus back
-                    let buffer = std::mem::take(buffer); // Take buffer
-                    let mut process = lock!(process); // Lock
-                    if process.is_alive() {
-                        process.input.push(buffer);
-                    } // Push only if alive
-                }
+                stdin.show(
+                    ui,
+                    process,
+                    r#"Type a command (e.g "help" or "status") and press Enter"#,
+                    P2POOL_INPUT,
+                    &P2POOL_COMMANDS,
+                    [width, text_edit],
+                );
             }
         });
 
+        //---------------------------------------------------------------------------------------------------- Node RPC
+        debug!("P2Pool Tab | Rendering [Node RPC]");
+        ui.group(|ui| {
+            egui::CollapsingHeader::new("Node RPC")
+                .id_source("p2pool_node_rpc")
+                .show(ui, |ui| {
+                    let (mut ip, mut port) = self.rpc_target();
+                    if !self.simple && self.ssh {
+                        let tunnel = lock!(ssh);
+                        if tunnel.running() {
+                            ip = "127.0.0.1".to_string();
+                            port = tunnel.local_rpc.to_string();
+                        }
+                    }
+                    let mut rpc = lock!(node_rpc);
+                    let mut run = false;
+                    ui.horizontal(|ui| {
+                        for call in crate::node::RpcCall::ALL {
+                            ui.selectable_value(&mut rpc.call, call, call.method())
+                                .on_hover_text(P2POOL_NODE_RPC_CALL);
+                        }
+                        ui.separator();
+                        let valid = !ip.is_empty() && port.parse::<u16>().is_ok();
+                        ui.add_enabled_ui(valid && !rpc.running, |ui| {
+                            if ui
+                                .button("Run")
+                                .on_hover_text(P2POOL_NODE_RPC_RUN)
+                                .on_disabled_hover_text(P2POOL_NODE_RPC_INVALID)
+                                .clicked()
+                            {
+                                run = true;
+                            }
+                        });
+                        if rpc.running {
+                            ui.spinner();
+                        }
+                        ui.label(format!("{}:{}", ip, port));
+                    });
+                    if run {
+                        drop(rpc);
+                        crate::node::NodeRpc::spawn_thread(node_rpc, &ip, &port);
+                        rpc = lock!(node_rpc);
+                    }
+                    if !rpc.output.is_empty() {
+                        let width = width - SPACE;
+                        egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
+                            ui.style_mut().override_text_style =
+                                Some(Name("MonospaceSmall".into()));
+                            egui::ScrollArea::vertical()
+                                .id_source("p2pool_node_rpc_output")
+                                .max_width(width)
+                                .max_height(height / 4.0)
+                                .auto_shrink([false, true])
+                                .show(ui, |ui| {
+                                    ui.label(rpc.output.as_str());
+                                });
+                        });
+                    }
+                });
+            egui::CollapsingHeader::new("Contribute a node")
+                .id_source("p2pool_contribute")
+                .show(ui, |ui| {
+                    crate::contribute::NodeCheck::show(node_check, width, ui);
+                });
+        });
+
         //---------------------------------------------------------------------------------------------------- Args
         if !self.simple {
             debug!("P2Pool Tab | Rendering [Arguments]");
@@ -129,14 +214,20 @@ impl crate::disk::P2pool {
             let text;
             let color;
             let len = format!("{:02}", self.address.len());
+            let network = self.network();
+            let name = if network.is_mainnet() {
+                "Monero".to_string()
+            } else {
+                format!("[{}] Monero", network)
+            };
             if self.address.is_empty() {
-                text = format!("Monero Address [{}/95] ➖", len);
+                text = format!("{} Address [{}/95] ➖", name, len);
                 color = Color32::LIGHT_GRAY;
-            } else if Regexes::addr_ok(&self.address) {
-                text = format!("Monero Address [{}/95] ✔", len);
+            } else if Regexes::addr_ok(&self.address, network) {
+                text = format!("{} Address [{}/95] ✔", name, len);
                 color = Color32::from_rgb(100, 230, 100);
             } else {
-                text = format!("Monero Address [{}/95] ❌", len);
+                text = format!("{} Address [{}/95] ❌", name, len);
                 color = Color32::from_rgb(230, 50, 50);
             }
             ui.add_sized(
@@ -151,6 +242,63 @@ impl crate::disk::P2pool {
             self.address.truncate(95);
         });
 
+        //---------------------------------------------------------------------------------------------------- Built-in miner
+        debug!("P2Pool Tab | Rendering [Built-in miner]");
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let width = (width / 10.0) - SPACE;
+                let alive = lock!(process).is_alive();
+                let (running, hashrate) = {
+                    let api = lock!(api);
+                    (api.miner_threads, api.miner_hashrate.localized())
+                };
+                let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+                let toggled = ui
+                    .add_sized(
+                        [width * 1.5, text_edit],
+                        Checkbox::new(&mut self.miner, "Built-in miner"),
+                    )
+                    .on_hover_text(P2POOL_MINER)
+                    .changed();
+                ui.separator();
+                ui.add_sized(
+                    [width, text_edit],
+                    Label::new(format!("Threads [1-{}]:", max_threads)),
+                );
+                ui.spacing_mut().slider_width = width * 3.0;
+                let slider = ui
+                    .add_enabled(
+                        self.miner,
+                        Slider::new(&mut self.miner_threads, 1..=max_threads),
+                    )
+                    .on_hover_text(P2POOL_MINER_THREADS);
+                // Only once the slider is let go, not for every value dragged over.
+                let resized = slider.drag_stopped() || (slider.changed() && !slider.dragged());
+                ui.separator();
+                ui.add_sized(
+                    [ui.available_width(), text_edit],
+                    Label::new(if running == 0 {
+                        "Hashrate: not mining".to_string()
+                    } else {
+                        format!("Hashrate: {} [{} threads]", hashrate, running)
+                    }),
+                )
+                .on_hover_text(P2POOL_MINER_HASHRATE);
+                // Apply it to a running P2Pool, [start_mining] won't change a running miner's threads.
+                if alive && (toggled || (resized && self.miner)) {
+                    let mut process = lock!(process);
+                    if running != 0 {
+                        process.input.push("stop_mining".to_string());
+                    }
+                    if self.miner {
+                        process
+                            .input
+                            .push(format!("start_mining {}", self.miner_threads));
+                    }
+                }
+            });
+        });
+
         //---------------------------------------------------------------------------------------------------- Simple
         let height = ui.available_height();
         if self.simple {
@@ -159,6 +307,45 @@ impl crate::disk::P2pool {
             ui.spacing_mut().slider_width = width - 8.0;
             ui.spacing_mut().icon_width = width / 25.0;
 
+            // [Use my own node]
+            debug!("P2Pool Tab | Rendering [Own node]");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = (width / 7.0) - SPACE;
+                    ui.add_sized(
+                        [width * 1.5, text_edit],
+                        Checkbox::new(&mut self.own_node, "Use my own node"),
+                    )
+                    .on_hover_text(P2POOL_OWN_NODE);
+                    let valid = self.own_node_valid();
+                    ui.add_enabled_ui(self.own_node, |ui| {
+                        for ((value, name, max, hover), ok) in [
+                            (&mut self.own_ip, "IP", 255, P2POOL_NODE_IP),
+                            (&mut self.own_rpc, "RPC", 5, P2POOL_RPC_PORT),
+                            (&mut self.own_zmq, "ZMQ", 5, P2POOL_ZMQ_PORT),
+                        ]
+                        .into_iter()
+                        .zip(valid)
+                        {
+                            let (text, color) = if ok {
+                                (format!("{} ✔", name), GREEN)
+                            } else {
+                                (format!("{} ❌", name), RED)
+                            };
+                            ui.add_sized(
+                                [width / 2.0, text_edit],
+                                Label::new(RichText::new(text).color(color)),
+                            );
+                            let width = if name == "IP" { width * 2.0 } else { width };
+                            ui.add_sized([width, text_edit], TextEdit::singleline(value))
+                                .on_hover_text(hover);
+                            value.truncate(max);
+                        }
+                    });
+                });
+            });
+            let own_node = self.own_node && self.own_node_valid().iter().all(|ok| *ok);
+
             // [Auto-select] if we haven't already.
             // Using [Arc<Mutex<Ping>>] as an intermediary here
             // saves me the hassle of wrapping [state: State] completely
@@ -176,6 +363,8 @@ impl crate::disk::P2pool {
             }
 
             ui.vertical(|ui| {
+                // The remote nodes aren't used with [own_node].
+                ui.set_enabled(!own_node);
                 ui.horizontal(|ui| {
                     debug!("P2Pool Tab | Rendering [Ping List]");
                     // [Ping List]
@@ -193,9 +382,10 @@ impl crate::disk::P2pool {
                     debug!("P2Pool Tab | Rendering [ComboBox] of Remote Nodes");
                     let ip_location = crate::node::format_ip_location(&self.node, false);
                     let text = RichText::new(format!(" ⏺ {}ms | {}", ms, ip_location)).color(color);
+                    let zmq = width / 12.0;
                     ComboBox::from_id_source("remote_nodes")
                         .selected_text(text)
-                        .width(width)
+                        .width(width - zmq - SPACE)
                         .show_ui(ui, |ui| {
                             for data in lock!(ping).nodes.iter() {
                                 let ms = crate::node::format_ms(data.ms);
@@ -205,8 +395,38 @@ impl crate::disk::P2pool {
                                 ui.selectable_value(&mut self.node, data.ip.to_string(), text);
                             }
                         });
+                    zmq_indicator(api, process, [zmq, height], ui);
                 });
 
+                // [Latency hint]
+                let latency = {
+                    let ping = lock!(ping);
+                    let selected = ping.nodes.iter().find(|data| data.ip == self.node);
+                    match (ping.pinged, selected, ping.nodes.first()) {
+                        (true, Some(selected), Some(fastest)) => {
+                            Latency::new(selected.ms, fastest.ms)
+                        }
+                        _ => Latency::Fine,
+                    }
+                };
+                if latency != Latency::Fine {
+                    ui.horizontal(|ui| {
+                        let button = width / 6.0;
+                        ui.add_sized(
+                            [width - button - SPACE * 2.0, height],
+                            Label::new(RichText::new(latency.hint()).color(YELLOW)),
+                        );
+                        if latency == Latency::Far
+                            && ui
+                                .add_sized([button, height], Button::new("Local node guide"))
+                                .on_hover_text(P2POOL_LOCAL_NODE_GUIDE)
+                                .clicked()
+                        {
+                            help.open_at(crate::help::Page::Troubleshooting);
+                        }
+                    });
+                }
+
                 ui.add_space(5.0);
 
                 debug!("P2Pool Tab | Rendering [Select fastest ... Ping] buttons");
@@ -310,11 +530,25 @@ impl crate::disk::P2pool {
                     .on_hover_text(P2POOL_AUTO_NODE);
                     ui.separator();
                     // [Backup host]
+                    ui.add_enabled_ui(backup_host_ok, |ui| {
+                        ui.add_sized(
+                            [width, height],
+                            Checkbox::new(&mut self.backup_host, "Backup host"),
+                        )
+                        .on_hover_text(P2POOL_BACKUP_HOST_SIMPLE)
+                        .on_disabled_hover_text(&backup_host_disabled);
+                    });
+                });
+                // [Peers]
+                ui.horizontal(|ui| {
+                    let text = width / 6.0;
+                    ui.add_sized([text, height], Label::new("Peers [10-50]:"));
+                    ui.spacing_mut().slider_width = ui.available_width() - (text / 2.0);
                     ui.add_sized(
-                        [width, height],
-                        Checkbox::new(&mut self.backup_host, "Backup host"),
+                        [ui.available_width(), height],
+                        Slider::new(&mut self.simple_peers, P2POOL_SIMPLE_PEERS_RANGE),
                     )
-                    .on_hover_text(P2POOL_BACKUP_HOST_SIMPLE);
+                    .on_hover_text(P2POOL_SIMPLE_PEERS);
                 })
             });
 
@@ -418,6 +652,26 @@ impl crate::disk::P2pool {
 				ui.text_edit_singleline(&mut self.zmq).on_hover_text(P2POOL_ZMQ_PORT);
 				self.zmq.truncate(5);
 			});
+			ui.horizontal(|ui| {
+				let text;
+				let color;
+				let tags = parse_tags(&self.tags);
+				let entered = self.tags.split(',').filter(|t| !t.trim().is_empty()).count();
+				if entered == 0 {
+					text = format!("Tags [ {:02}/{} ]➖", entered, MAX_TAGS);
+					color = Color32::LIGHT_GRAY;
+				} else if entered == tags.len() {
+					text = format!("Tags [ {:02}/{} ]✔", entered, MAX_TAGS);
+					color = Color32::from_rgb(100, 230, 100);
+				} else {
+					text = format!("Tags [ {:02}/{} ]❌", entered, MAX_TAGS);
+					color = Color32::from_rgb(230, 50, 50);
+					incorrect_input = true;
+				}
+				ui.add_sized([width, text_edit], Label::new(RichText::new(text).color(color)));
+				ui.text_edit_singleline(&mut self.tags).on_hover_text(LIST_TAGS);
+				self.tags.truncate(330);
+			});
 		});
 
 		ui.vertical(|ui| {
@@ -428,24 +682,45 @@ impl crate::disk::P2pool {
 			ui.spacing_mut().icon_width = width / 25.0;
 			// [Ping List]
 			debug!("P2Pool Tab | Rendering [Node List]");
-			let text = RichText::new(format!("{}. {}", self.selected_index+1, self.selected_name));
-			ComboBox::from_id_source("manual_nodes").selected_text(text).width(width).show_ui(ui, |ui| {
-				for (i, (name, node)) in node_vec.iter().enumerate() {
-					let text = RichText::new(format!("{}. {}\n     IP: {}\n    RPC: {}\n    ZMQ: {}", i+1, name, node.ip, node.rpc, node.zmq));
-					if ui.add(SelectableLabel::new(self.selected_name == *name, text)).clicked() {
-						self.selected_index = i;
-						let node = node.clone();
-						self.selected_name = name.clone();
-						self.selected_ip = node.ip.clone();
-						self.selected_rpc = node.rpc.clone();
-						self.selected_zmq = node.zmq.clone();
-						self.name = name.clone();
-						self.ip = node.ip;
-						self.rpc = node.rpc;
-						self.zmq = node.zmq;
+			// [Filter/Sort]
+			let pings: Vec<(&'static str, u128)> = lock!(ping).nodes.iter().map(|data| (data.ip, data.ms)).collect();
+			let indices = node_filter.indices(node_vec, |ip| pings.iter().find(|(i, _)| *i == ip).map(|(_, ms)| *ms));
+			ui.horizontal(|ui| {
+				let sort_width = width / 10.0;
+				ui.add_sized([width - (sort_width*3.0) - 24.0, text_edit], TextEdit::singleline(&mut node_filter.text).hint_text("Filter...")).on_hover_text(LIST_FILTER);
+				for (sort, text, hover) in [(ListSort::Index, "#", LIST_SORT_INDEX), (ListSort::Name, "Name", LIST_SORT_NAME), (ListSort::Latency, "Ping", LIST_SORT_LATENCY)] {
+					if ui.add_sized([sort_width, text_edit], SelectableLabel::new(node_filter.sort == sort, text)).on_hover_text(hover).clicked() {
+						node_filter.sort = sort;
 					}
 				}
 			});
+			let text = RichText::new(format!("{}. {}", self.selected_index+1, self.selected_name));
+			ui.horizontal(|ui| {
+				ComboBox::from_id_source("manual_nodes").selected_text(text).width(width - (width / 12.0) - SPACE).show_ui(ui, |ui| {
+					if indices.is_empty() {
+						ui.label(format!("No nodes match [{}]", node_filter.text));
+					}
+					for i in indices {
+						let (name, node) = &node_vec[i];
+						let tags = if node.tags.is_empty() { String::new() } else { format!("\n   Tags: {}", node.tags.join(", ")) };
+						let text = RichText::new(format!("{}. {}\n     IP: {}\n    RPC: {}\n    ZMQ: {}{}", i+1, name, node.ip, node.rpc, node.zmq, tags));
+						if ui.add(SelectableLabel::new(self.selected_name == *name, text)).clicked() {
+							self.selected_index = i;
+							let node = node.clone();
+							self.selected_name = name.clone();
+							self.selected_ip = node.ip.clone();
+							self.selected_rpc = node.rpc.clone();
+							self.selected_zmq = node.zmq.clone();
+							self.name = name.clone();
+							self.ip = node.ip;
+							self.rpc = node.rpc;
+							self.zmq = node.zmq;
+							self.tags = node.tags.join(", ");
+						}
+					}
+				});
+				zmq_indicator(api, process, [width / 12.0, text_edit], ui);
+			});
 			// [Add/Save]
 			let node_vec_len = node_vec.len();
 			let mut exists = false;
@@ -454,7 +729,7 @@ impl crate::disk::P2pool {
 			for (name, node) in node_vec.iter() {
 				if *name == self.name {
 					exists = true;
-					if self.ip == node.ip && self.rpc == node.rpc && self.zmq == node.zmq {
+					if self.ip == node.ip && self.rpc == node.rpc && self.zmq == node.zmq && parse_tags(&self.tags) == node.tags {
 						save_diff = false;
 					}
 					break
@@ -472,6 +747,7 @@ impl crate::disk::P2pool {
 							ip: self.ip.clone(),
 							rpc: self.rpc.clone(),
 							zmq: self.zmq.clone(),
+							tags: parse_tags(&self.tags),
 						};
 						node_vec[existing_index].1 = node;
 						self.selected_index = existing_index;
@@ -488,6 +764,7 @@ impl crate::disk::P2pool {
 							ip: self.ip.clone(),
 							rpc: self.rpc.clone(),
 							zmq: self.zmq.clone(),
+							tags: parse_tags(&self.tags),
 						};
 						node_vec.push((self.name.clone(), node));
 						self.selected_index = node_vec_len;
@@ -527,16 +804,69 @@ impl crate::disk::P2pool {
 					self.ip = new_node.ip;
 					self.rpc = new_node.rpc;
 					self.zmq = new_node.zmq;
+					self.tags = new_node.tags.join(", ");
 					info!("Node | D | [index: {}, name: \"{}\", ip: \"{}\", rpc: {}, zmq: {}]", self.selected_index, self.selected_name, self.selected_ip, self.selected_rpc, self.selected_zmq);
 				}
 			});
 			ui.horizontal(|ui| {
-				ui.set_enabled(!self.name.is_empty() || !self.ip.is_empty() || !self.rpc.is_empty() || !self.zmq.is_empty());
+				ui.set_enabled(!self.name.is_empty() || !self.ip.is_empty() || !self.rpc.is_empty() || !self.zmq.is_empty() || !self.tags.is_empty());
 				if ui.add_sized([width, text_edit], Button::new("Clear")).on_hover_text(LIST_CLEAR).clicked() {
 					self.name.clear();
 					self.ip.clear();
 					self.rpc.clear();
 					self.zmq.clear();
+					self.tags.clear();
+				}
+			});
+			// [Duplicate/Template]
+			ui.horizontal(|ui| {
+				let width = (width / 2.0) - 4.0;
+				ui.add_enabled_ui(node_vec_len < 1000, |ui| {
+					if ui.add_sized([width, text_edit], Button::new("Duplicate")).on_hover_text(LIST_DUPLICATE).clicked() {
+						if let Some((name, node)) = node_vec.get(self.selected_index).cloned() {
+							self.name = unique_name(node_vec, &name);
+							self.ip = node.ip;
+							self.rpc = node.rpc;
+							self.zmq = node.zmq;
+							self.tags = node.tags.join(", ");
+						}
+					}
+				});
+				ComboBox::from_id_source("node_template").selected_text("New from template").width(width).show_ui(ui, |ui| {
+					for (name, node) in Node::templates() {
+						let text = format!("{}\n     IP: {}\n    RPC: {}\n    ZMQ: {}", name, node.ip, node.rpc, node.zmq);
+						if ui.add(SelectableLabel::new(false, text)).clicked() {
+							self.name = unique_name(node_vec, name);
+							self.ip = node.ip;
+							self.rpc = node.rpc;
+							self.zmq = node.zmq;
+							self.tags.clear();
+						}
+					}
+				}).response.on_hover_text(LIST_TEMPLATE);
+			});
+			// [Import]
+			ui.horizontal(|ui| {
+				let mut guard = lock!(file_window);
+				if guard.picked_node_import {
+					guard.picked_node_import = false;
+					guard.import_msg = match std::fs::read_to_string(&guard.import_path) {
+						Ok(string) => match Node::from_csv(&string) {
+							Ok(imported) => {
+								let (added, skipped) = merge_imported(node_vec, imported);
+								info!("Node | Import | [added: {}, skipped: {}, path: \"{}\"]", added, skipped, guard.import_path);
+								format!("Last import: added {} nodes, skipped {}", added, skipped)
+							}
+							Err(e) => format!("Last import failed: {}", e),
+						},
+						Err(e) => format!("Last import failed: {}", e),
+					};
+				}
+				ui.set_enabled(!guard.thread && node_vec.len() < 1000);
+				let text = if guard.import_msg.is_empty() { LIST_IMPORT_NODE.to_string() } else { format!("{}\n\n{}", LIST_IMPORT_NODE, guard.import_msg) };
+				drop(guard);
+				if ui.add_sized([width, text_edit], Button::new("Import")).on_hover_text(text).clicked() {
+					Gupax::spawn_file_window_thread(file_window, FileType::NodeImport);
 				}
 			});
 		});
@@ -549,29 +879,84 @@ impl crate::disk::P2pool {
             ui.horizontal(|ui| {
                 let height = height / 4.0;
                 ui.group(|ui| {
-                    ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
                         let width = (width / 4.0) - SPACE;
-                        let height = height + 6.0;
-                        if ui
-                            .add_sized(
-                                [width, height],
-                                SelectableLabel::new(!self.mini, "P2Pool Main"),
-                            )
-                            .on_hover_text(P2POOL_MAIN)
-                            .clicked()
-                        {
-                            self.mini = false;
-                        }
-                        if ui
-                            .add_sized(
-                                [width, height],
-                                SelectableLabel::new(self.mini, "P2Pool Mini"),
-                            )
-                            .on_hover_text(P2POOL_MINI)
-                            .clicked()
-                        {
-                            self.mini = true;
-                        }
+                        ui.horizontal(|ui| {
+                            let height = height * 0.75;
+                            if ui
+                                .add_sized(
+                                    [width, height],
+                                    SelectableLabel::new(!self.mini, "P2Pool Main"),
+                                )
+                                .on_hover_text(P2POOL_MAIN)
+                                .clicked()
+                            {
+                                self.mini = false;
+                            }
+                            if ui
+                                .add_sized(
+                                    [width, height],
+                                    SelectableLabel::new(self.mini, "P2Pool Mini"),
+                                )
+                                .on_hover_text(P2POOL_MINI)
+                                .clicked()
+                            {
+                                self.mini = true;
+                            }
+                        });
+                        // [Advisor]
+                        ui.horizontal(|ui| {
+                            let height = height / 4.0;
+                            ui.style_mut().override_text_style =
+                                Some(Name("MonospaceSmall".into()));
+                            let current = crate::advisor::Sidechain::from_mini(self.mini);
+                            let advisor = lock!(advisor);
+                            let advice = advisor.advice(current);
+                            let (text, hover) = match &advice {
+                                Some(advice) => (
+                                    advice.summary(),
+                                    format!(
+                                        "{}
+
+{}",
+                                        P2POOL_ADVISOR,
+                                        advice.details()
+                                    ),
+                                ),
+                                None if !advisor.error.is_empty() => {
+                                    (advisor.error.clone(), P2POOL_ADVISOR.to_string())
+                                }
+                                None if advisor.fetching => (
+                                    "Advice: fetching sidechain stats...".to_string(),
+                                    P2POOL_ADVISOR.to_string(),
+                                ),
+                                None => (
+                                    "Advice: start mining to compare Main & Mini".to_string(),
+                                    P2POOL_ADVISOR.to_string(),
+                                ),
+                            };
+                            drop(advisor);
+                            let switch = advice.filter(|a| a.pick != current);
+                            let label = if switch.is_some() {
+                                width * 1.5
+                            } else {
+                                width * 2.0 + SPACE
+                            };
+                            ui.add_sized([label, height], Label::new(text))
+                                .on_hover_text(hover);
+                            if let Some(advice) = switch {
+                                if ui
+                                    .add_sized(
+                                        [width * 0.5, height],
+                                        Button::new(format!("Switch to {}", advice.pick)),
+                                    )
+                                    .on_hover_text(P2POOL_ADVISOR_SWITCH)
+                                    .clicked()
+                                {
+                                    self.mini = advice.pick.is_mini();
+                                }
+                            }
+                        });
                     })
                 });
                 // [Out/In Peers] + [Log Level]
@@ -609,17 +994,349 @@ impl crate::disk::P2pool {
                 });
             });
 
-            debug!("P2Pool Tab | Rendering Backup host button");
+            debug!("P2Pool Tab | Rendering Backup host button & API directory");
             ui.group(|ui| {
-                let width = width - SPACE;
                 let height = ui.available_height() / 3.0;
-                // [Backup host]
-                ui.add_sized(
-                    [width, height],
-                    Checkbox::new(&mut self.backup_host, "Backup host"),
-                )
-                .on_hover_text(P2POOL_BACKUP_HOST_ADVANCED);
+                ui.horizontal(|ui| {
+                    let width = (width / 4.0) - SPACE;
+                    // [Backup host]
+                    ui.add_enabled_ui(backup_host_ok, |ui| {
+                        ui.add_sized(
+                            [width, height],
+                            Checkbox::new(&mut self.backup_host, "Backup host"),
+                        )
+                        .on_hover_text(P2POOL_BACKUP_HOST_ADVANCED)
+                        .on_disabled_hover_text(&backup_host_disabled);
+                    });
+                    ui.separator();
+                    // [Network]
+                    for network in MoneroNetwork::ALL {
+                        if ui
+                            .add_sized(
+                                [width / 3.0, height],
+                                SelectableLabel::new(self.network == network, network.to_string()),
+                            )
+                            .on_hover_text(P2POOL_NETWORK)
+                            .clicked()
+                        {
+                            self.set_network(network);
+                        }
+                    }
+                    ui.separator();
+                    // [API directory]
+                    ui.add_sized([width / 2.0, height], Label::new("API directory:"));
+                    ui.add_sized(
+                        [ui.available_width(), height],
+                        TextEdit::hint_text(
+                            TextEdit::singleline(&mut self.api_dir),
+                            "Default: next to the P2Pool binary",
+                        ),
+                    )
+                    .on_hover_text(P2POOL_API_DIR);
+                    self.api_dir.truncate(1024);
+                });
+            });
+
+            debug!("P2Pool Tab | Rendering [Light/Full mode]");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = (width / 8.0) - SPACE;
+                    ui.add_sized([width, text_edit], Label::new("Mode:"))
+                        .on_hover_text(P2POOL_MODE);
+                    for mode in P2poolMode::ALL {
+                        if ui
+                            .add_sized(
+                                [width, text_edit],
+                                SelectableLabel::new(self.mode == mode, mode.to_string()),
+                            )
+                            .on_hover_text(P2POOL_MODE)
+                            .clicked()
+                        {
+                            self.mode = mode;
+                        }
+                    }
+                    ui.separator();
+                    let ram = *TOTAL_MEMORY;
+                    let light = self.light_mode(ram);
+                    ui.label(format!(
+                        "Using: {} | Recommended: {} ({:.1} GB RAM)",
+                        if light { "Light" } else { "Full" },
+                        P2poolMode::recommended(ram),
+                        ram as f64 / 1_000_000_000.0,
+                    ));
+                });
+            });
+
+            debug!("P2Pool Tab | Rendering [No cache/No DNS/P2P port/Log size]");
+            ui.group(|ui| {
+                let height = ui.available_height() / 3.0;
+                ui.horizontal(|ui| {
+                    let width = (width / 8.0) - SPACE;
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.no_cache, "No cache"),
+                    )
+                    .on_hover_text(P2POOL_NO_CACHE);
+                    ui.add_sized([width, height], Checkbox::new(&mut self.no_dns, "No DNS"))
+                        .on_hover_text(P2POOL_NO_DNS);
+                    ui.separator();
+                    // [P2P port]
+                    let port = self.p2p_port.trim();
+                    let color = if port.is_empty() {
+                        Color32::LIGHT_GRAY
+                    } else if REGEXES.port.is_match(port) {
+                        Color32::from_rgb(100, 230, 100)
+                    } else {
+                        Color32::from_rgb(230, 50, 50)
+                    };
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new("P2P port:").color(color)),
+                    );
+                    ui.add_sized(
+                        [width, height],
+                        TextEdit::hint_text(TextEdit::singleline(&mut self.p2p_port), "Default"),
+                    )
+                    .on_hover_text(P2POOL_P2P_PORT);
+                    self.p2p_port.truncate(5);
+                    ui.separator();
+                    // [Max log size]
+                    ui.add_sized([width * 1.5, height], Label::new("Max log size [MB]:"));
+                    ui.spacing_mut().slider_width = ui.available_width() - (width / 1.5);
+                    ui.add_sized(
+                        [ui.available_width(), height],
+                        Slider::new(&mut self.log_max_mb, 0..=1000),
+                    )
+                    .on_hover_text(P2POOL_LOG_MAX);
+                });
+            });
+
+            debug!("P2Pool Tab | Rendering [Stratum TLS]");
+            ui.group(|ui| {
+                let height = ui.available_height() / 3.0;
+                ui.horizontal(|ui| {
+                    let width = (width / 8.0) - SPACE;
+                    ui.add_sized(
+                        [width * 1.5, height],
+                        Checkbox::new(&mut self.tls, "Stratum TLS"),
+                    )
+                    .on_hover_text(P2POOL_TLS);
+                    ui.separator();
+                    let port = self.tls_port.trim();
+                    let color = if REGEXES.port.is_match(port)
+                        && port.parse() != Ok(crate::tls::STRATUM_PORT)
+                    {
+                        Color32::from_rgb(100, 230, 100)
+                    } else {
+                        Color32::from_rgb(230, 50, 50)
+                    };
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new("Port:").color(color)),
+                    );
+                    ui.add_sized([width, height], TextEdit::singleline(&mut self.tls_port))
+                        .on_hover_text(P2POOL_TLS_PORT);
+                    self.tls_port.truncate(5);
+                    ui.separator();
+                    let proxy = lock!(tls);
+                    let (text, color) = if proxy.running {
+                        (
+                            format!(
+                                "Listening on [{}] | Connections: {}",
+                                proxy.port, proxy.connections
+                            ),
+                            Color32::from_rgb(100, 230, 100),
+                        )
+                    } else if !proxy.error.is_empty() {
+                        (
+                            format!("Error: {}", proxy.error),
+                            Color32::from_rgb(230, 50, 50),
+                        )
+                    } else {
+                        ("Starts with P2Pool".to_string(), Color32::LIGHT_GRAY)
+                    };
+                    let mut fingerprint = proxy.fingerprint.clone();
+                    let running = proxy.running;
+                    drop(proxy);
+                    ui.add_sized(
+                        [width * 2.5, height],
+                        Label::new(RichText::new(text).color(color)),
+                    );
+                    ui.separator();
+                    ui.add_sized(
+                        [ui.available_width() - width * 1.5, height],
+                        TextEdit::hint_text(TextEdit::singleline(&mut fingerprint), "Fingerprint"),
+                    )
+                    .on_hover_text(P2POOL_TLS_FINGERPRINT);
+                    if ui
+                        .add_sized(
+                            [ui.available_width(), height],
+                            Button::new("New certificate"),
+                        )
+                        .on_hover_text(P2POOL_TLS_NEW_CERT)
+                        .clicked()
+                    {
+                        match crate::tls::Cert::generate(tls_dir) {
+                            Ok(cert) => {
+                                lock!(tls).fingerprint = cert.fingerprint();
+                                // The main loop starts it again with the new one.
+                                if running {
+                                    crate::tls::TlsProxy::stop(tls);
+                                }
+                            }
+                            Err(e) => lock!(tls).error = e.to_string(),
+                        }
+                    }
+                });
+            });
+
+            debug!("P2Pool Tab | Rendering [SSH tunnel]");
+            ui.group(|ui| {
+                let height = ui.available_height() / 3.0;
+                let width = (width / 8.0) - SPACE;
+                ui.horizontal(|ui| {
+                    ui.add_sized(
+                        [width * 1.5, height],
+                        Checkbox::new(&mut self.ssh, "SSH tunnel"),
+                    )
+                    .on_hover_text(P2POOL_SSH);
+                    ui.separator();
+                    ui.add_enabled_ui(self.ssh, |ui| {
+                        ui.add_sized(
+                            [width * 1.5, height],
+                            TextEdit::hint_text(TextEdit::singleline(&mut self.ssh_user), "User"),
+                        )
+                        .on_hover_text(P2POOL_SSH_USER);
+                        ui.label("@");
+                        ui.add_sized(
+                            [width * 2.0, height],
+                            TextEdit::hint_text(TextEdit::singleline(&mut self.ssh_host), "Host"),
+                        )
+                        .on_hover_text(P2POOL_SSH_HOST);
+                        ui.label(":");
+                        ui.add_sized(
+                            [width / 1.5, height],
+                            TextEdit::singleline(&mut self.ssh_port),
+                        )
+                        .on_hover_text(P2POOL_SSH_HOST);
+                        self.ssh_port.truncate(5);
+                        ui.separator();
+                        ui.label("RPC/ZMQ:");
+                        ui.add_sized(
+                            [width / 1.5, height],
+                            TextEdit::singleline(&mut self.ssh_rpc),
+                        )
+                        .on_hover_text(P2POOL_SSH_PORTS);
+                        ui.add_sized(
+                            [width / 1.5, height],
+                            TextEdit::singleline(&mut self.ssh_zmq),
+                        )
+                        .on_hover_text(P2POOL_SSH_PORTS);
+                        self.ssh_rpc.truncate(5);
+                        self.ssh_zmq.truncate(5);
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(self.ssh, |ui| {
+                        ui.add_sized(
+                            [width * 3.0, height],
+                            TextEdit::hint_text(
+                                TextEdit::singleline(&mut self.ssh_key),
+                                crate::ssh::DEFAULT_KEY,
+                            ),
+                        )
+                        .on_hover_text(P2POOL_SSH_KEY);
+                        ui.separator();
+                        let tunnel = lock!(ssh);
+                        let (text, color) = match tunnel.status {
+                            _ if self.ssh && self.ssh_config().is_none() => (
+                                "Invalid settings".to_string(),
+                                Color32::from_rgb(230, 50, 50),
+                            ),
+                            crate::ssh::TunnelStatus::Connected => (
+                                format!(
+                                    "Connected | 127.0.0.1:{}/{} | Connections: {}",
+                                    tunnel.local_rpc, tunnel.local_zmq, tunnel.connections
+                                ),
+                                Color32::from_rgb(100, 230, 100),
+                            ),
+                            _ if !tunnel.error.is_empty() => {
+                                (tunnel.error.clone(), Color32::from_rgb(230, 50, 50))
+                            }
+                            crate::ssh::TunnelStatus::Off => {
+                                ("Off".to_string(), Color32::LIGHT_GRAY)
+                            }
+                            status => (format!("{:?}...", status), Color32::YELLOW),
+                        };
+                        let trust = tunnel.unknown_host.then(|| tunnel.host_key.clone());
+                        drop(tunnel);
+                        ui.add_sized(
+                            [ui.available_width() - width * 1.5, height],
+                            Label::new(RichText::new(text).color(color)),
+                        );
+                        if let Some(host_key) = trust {
+                            if ui
+                                .add_sized(
+                                    [ui.available_width(), height],
+                                    Button::new("Trust host key"),
+                                )
+                                .on_hover_text(format!("{}\n\n{}", P2POOL_SSH_TRUST, host_key))
+                                .clicked()
+                            {
+                                self.ssh_host_key = host_key;
+                            }
+                        }
+                    });
+                });
+            });
+
+            debug!("P2Pool Tab | Rendering [Working directory/Environment]");
+            ui.group(|ui| {
+                crate::envvar::workdir_editor(
+                    &mut self.workdir,
+                    path,
+                    (width / 10.0) - SPACE,
+                    text_edit,
+                    ui,
+                );
+                egui::CollapsingHeader::new(format!("Environment ({})", self.env.len()))
+                    .id_source("p2pool_env")
+                    .show(ui, |ui| {
+                        crate::envvar::editor(&mut self.env, (width / 10.0) - SPACE, text_edit, ui);
+                    });
             });
         }
     }
 }
+
+// [ZMQ ⏺] next to the node selection, colored by the ZMQ health of the
+// running P2Pool rather than the process state.
+fn zmq_indicator(
+    api: &Arc<Mutex<PubP2poolApi>>,
+    process: &Arc<Mutex<Process>>,
+    size: [f32; 2],
+    ui: &mut egui::Ui,
+) {
+    let (health, hover) = if lock!(process).is_alive() {
+        let api = lock!(api);
+        let hover = if api.zmq_line.is_empty() {
+            format!("{}\n\nZMQ: {}", P2POOL_ZMQ_HEALTH, api.zmq)
+        } else {
+            format!(
+                "{}\n\nZMQ: {}\nLast ZMQ line ({}s into P2Pool's uptime):\n{}",
+                P2POOL_ZMQ_HEALTH, api.zmq, api.zmq_secs, api.zmq_line
+            )
+        };
+        (api.zmq, hover)
+    } else {
+        (
+            ZmqHealth::Unknown,
+            format!("{}\n\nP2Pool is not running", P2POOL_ZMQ_HEALTH),
+        )
+    };
+    ui.add_sized(
+        size,
+        Label::new(RichText::new("ZMQ ⏺").color(health.color())),
+    )
+    .on_hover_text(hover);
+}
@@ -0,0 +1,300 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Pool-side hashrate.
+//
+// Pools with a public API report the hashrate they credit an address
+// with, averaged from the shares that actually arrived. If that's far
+// below what XMRig says it's doing, shares are getting lost on the way
+// (NAT, TLS, a proxy) and XMRig alone can't tell. Only a few well known
+// pools are supported, the rest simply show nothing.
+
+use crate::macros::*;
+use hyper::{Body, Request};
+use log::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Pools cache these stats, no point in asking more often.
+pub const INTERVAL: Duration = Duration::from_secs(120);
+// Pool-side averages need a while of shares before they mean anything.
+pub const WARMUP: Duration = Duration::from_secs(20 * 60);
+// Pool-side below this much of XMRig's hashrate is a warning.
+pub const DIVERGENCE: f64 = 0.5;
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+//---------------------------------------------------------------------------------------------------- PoolApi
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoolApi {
+    SupportXmr,
+    MoneroOcean,
+    Nanopool,
+    TwoMiners,
+}
+
+impl PoolApi {
+    // Which API (if any) belongs to the pool at [host], e.g: [pool.supportxmr.com:443].
+    pub fn from_host(host: &str) -> Option<Self> {
+        let host = host.trim().to_lowercase();
+        let host = host
+            .trim_start_matches("stratum+tcp://")
+            .trim_start_matches("stratum+ssl://");
+        let host = host.split(':').next().unwrap_or_default();
+        let is = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+        if is("supportxmr.com") {
+            Some(Self::SupportXmr)
+        } else if is("moneroocean.stream") {
+            Some(Self::MoneroOcean)
+        } else if is("nanopool.org") {
+            Some(Self::Nanopool)
+        } else if is("2miners.com") {
+            Some(Self::TwoMiners)
+        } else {
+            None
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::SupportXmr => "SupportXMR",
+            Self::MoneroOcean => "MoneroOcean",
+            Self::Nanopool => "Nanopool",
+            Self::TwoMiners => "2Miners",
+        }
+    }
+
+    // The stats URL for [address], which is cut at the first [.] or [+]
+    // since pools accept [address.worker] and [address+difficulty] logins.
+    pub fn url(&self, address: &str) -> String {
+        let address = address.trim().split(['.', '+']).next().unwrap_or_default();
+        match self {
+            Self::SupportXmr => format!("https://supportxmr.com/api/miner/{}/stats", address),
+            Self::MoneroOcean => {
+                format!("https://api.moneroocean.stream/miner/{}/stats", address)
+            }
+            Self::Nanopool => format!("https://api.nanopool.org/v1/xmr/hashrate/{}", address),
+            Self::TwoMiners => format!("https://xmr.2miners.com/api/accounts/{}", address),
+        }
+    }
+
+    // The hashrate (H/s) out of the API's response.
+    pub fn parse(&self, json: &serde_json::Value) -> Option<f64> {
+        match self {
+            Self::SupportXmr | Self::MoneroOcean => json.get("hash")?.as_f64(),
+            Self::Nanopool => {
+                if json.get("status")?.as_bool()? {
+                    json.get("data")?.as_f64()
+                } else {
+                    None
+                }
+            }
+            Self::TwoMiners => json.get("currentHashrate")?.as_f64(),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- PoolHashrate
+#[derive(Debug)]
+pub struct PoolHashrate {
+    pub target: Option<(PoolApi, String)>, // The pool and address being checked
+    pub hashrate: Option<f64>,             // H/s, [None] until the first answer
+    pub error: String,
+    pub fetching: bool,
+    pub last: Option<Instant>,
+}
+
+impl Default for PoolHashrate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolHashrate {
+    pub const fn new() -> Self {
+        Self {
+            target: None,
+            hashrate: None,
+            error: String::new(),
+            fetching: false,
+            last: None,
+        }
+    }
+
+    // Point at a different pool/address, the old result no longer applies.
+    pub fn set_target(&mut self, target: Option<(PoolApi, String)>) {
+        if self.target != target {
+            *self = Self {
+                target,
+                ..Self::new()
+            };
+        }
+    }
+
+    pub fn due(&self) -> bool {
+        self.target.is_some()
+            && !self.fetching
+            && self.last.is_none_or(|last| last.elapsed() >= INTERVAL)
+    }
+
+    // The pool-side/XMRig ratio if it's low enough to warn about. Nothing
+    // during the [WARMUP], the pool's average is still catching up then.
+    pub fn divergence(pool: f64, local: f64, uptime: Duration) -> Option<f64> {
+        if uptime < WARMUP || local <= 0.0 {
+            return None;
+        }
+        let ratio = pool / local;
+        (ratio < DIVERGENCE).then_some(ratio)
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(pool: &Arc<Mutex<Self>>) {
+        let pool = Arc::clone(pool);
+        let (api, address) = {
+            let mut lock = lock!(pool);
+            let Some(target) = lock.target.clone() else {
+                return;
+            };
+            lock.fetching = true;
+            lock.last = Some(Instant::now());
+            target
+        };
+        std::thread::spawn(move || {
+            let url = api.url(&address);
+            let result = Self::fetch(&url).and_then(|json| {
+                api.parse(&json)
+                    .ok_or_else(|| anyhow::anyhow!("unexpected response from [{}]", api.name()))
+            });
+            let mut lock = lock!(pool);
+            lock.fetching = false;
+            // The target changed while this was in flight.
+            if lock.target.as_ref() != Some(&(api, address)) {
+                return;
+            }
+            match result {
+                Ok(hashrate) => {
+                    info!("Pool Hashrate | [{}] reports {} H/s", api.name(), hashrate);
+                    lock.hashrate = Some(hashrate);
+                    lock.error.clear();
+                }
+                Err(e) => {
+                    warn!("Pool Hashrate | [{}] ... FAIL ... {}", api.name(), e);
+                    lock.error = e.to_string();
+                }
+            }
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[tokio::main(flavor = "current_thread")]
+    async fn fetch(url: &str) -> Result<serde_json::Value, anyhow::Error> {
+        let client = crate::net::https_client();
+        let request = Request::builder()
+            .method("GET")
+            .uri(url)
+            .header("User-Agent", crate::Pkg::get_user_agent())
+            .body(Body::empty())?;
+        let response = tokio::time::timeout(TIMEOUT, client.request(request))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out"))??;
+        let bytes = hyper::body::to_bytes(response.into_body()).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn from_host() {
+        use super::PoolApi;
+        assert_eq!(
+            PoolApi::from_host("pool.supportxmr.com:443"),
+            Some(PoolApi::SupportXmr)
+        );
+        assert_eq!(
+            PoolApi::from_host("gulf.moneroocean.stream"),
+            Some(PoolApi::MoneroOcean)
+        );
+        assert_eq!(
+            PoolApi::from_host("stratum+ssl://xmr-eu1.nanopool.org:14433"),
+            Some(PoolApi::Nanopool)
+        );
+        assert_eq!(
+            PoolApi::from_host("xmr.2miners.com"),
+            Some(PoolApi::TwoMiners)
+        );
+        assert_eq!(PoolApi::from_host("127.0.0.1:3333"), None);
+        assert_eq!(PoolApi::from_host("notsupportxmr.com"), None);
+    }
+
+    #[test]
+    fn url_and_parse() {
+        use super::PoolApi;
+        assert_eq!(
+            PoolApi::SupportXmr.url("4abc.rig1"),
+            "https://supportxmr.com/api/miner/4abc/stats"
+        );
+        assert_eq!(
+            PoolApi::Nanopool.url("4abc+50000"),
+            "https://api.nanopool.org/v1/xmr/hashrate/4abc"
+        );
+        let json = serde_json::json!({"hash": 1234, "identifier": "global"});
+        assert_eq!(PoolApi::SupportXmr.parse(&json), Some(1234.0));
+        assert_eq!(PoolApi::MoneroOcean.parse(&json), Some(1234.0));
+        let json = serde_json::json!({"status": true, "data": 1234.5});
+        assert_eq!(PoolApi::Nanopool.parse(&json), Some(1234.5));
+        let json = serde_json::json!({"status": false, "error": "Account not found"});
+        assert_eq!(PoolApi::Nanopool.parse(&json), None);
+        let json = serde_json::json!({"currentHashrate": 1000, "hashrate": 900});
+        assert_eq!(PoolApi::TwoMiners.parse(&json), Some(1000.0));
+    }
+
+    #[test]
+    fn divergence() {
+        use super::{PoolHashrate, WARMUP};
+        use std::time::Duration;
+        let warm = WARMUP + Duration::from_secs(1);
+        assert_eq!(
+            PoolHashrate::divergence(100.0, 1000.0, Duration::ZERO),
+            None
+        );
+        assert_eq!(PoolHashrate::divergence(100.0, 1000.0, warm), Some(0.1));
+        assert_eq!(PoolHashrate::divergence(900.0, 1000.0, warm), None);
+        assert_eq!(PoolHashrate::divergence(100.0, 0.0, warm), None);
+    }
+
+    #[test]
+    fn set_target() {
+        use super::{PoolApi, PoolHashrate};
+        use std::time::Instant;
+        let mut pool = PoolHashrate::new();
+        assert!(!pool.due());
+        pool.set_target(Some((PoolApi::SupportXmr, "4abc".to_string())));
+        assert!(pool.due());
+        pool.hashrate = Some(1.0);
+        pool.last = Some(Instant::now());
+        assert!(!pool.due());
+        pool.set_target(Some((PoolApi::SupportXmr, "4abc".to_string())));
+        assert_eq!(pool.hashrate, Some(1.0));
+        pool.set_target(Some((PoolApi::MoneroOcean, "4abc".to_string())));
+        assert_eq!(pool.hashrate, None);
+        assert!(pool.due());
+    }
+}
@@ -0,0 +1,352 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Binary version probing.
+//
+// Different P2Pool/XMRig versions support different flags, so when the user
+// selects a binary path, we run it once with [--version] in a separate
+// thread and parse the version (and XMRig's build features) out of the
+// output. The result is cached in a [BinaryProbe] and used to gate
+// generated arguments & UI options.
+//
+// An unknown version (custom builds, probe failed, etc) is treated as
+// "supports everything" so that Gupax never breaks a working setup.
+
+use crate::{helper::ProcessName, macros::*};
+use log::*;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    io::Read,
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How long to wait for [--version] before killing the process.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Minimum P2Pool versions for generated arguments.
+pub const P2POOL_MIN_BACKUP_HOSTS: Version = Version::new(3, 5, 0); // Multiple [--host]
+
+// XMRig build features.
+pub const XMRIG_FEATURE_TLS: &str = "tls"; // Built with OpenSSL, required for [--tls]
+
+static P2POOL_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"P2Pool v(\d+)\.(\d+)(?:\.(\d+))?").unwrap());
+static XMRIG_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"XMRig(?:-\w+)? (\d+)\.(\d+)\.(\d+)").unwrap());
+
+//---------------------------------------------------------------------------------------------------- [Version]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Version {
+    major: u16,
+    minor: u16,
+    patch: u16,
+}
+
+impl Version {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    // Parse the version out of [--version] output.
+    pub fn from_output(name: ProcessName, output: &str) -> Option<Self> {
+        let regex = match name {
+            ProcessName::P2pool => &P2POOL_VERSION,
+            // Also matches [XMRig-Proxy 6.22.0].
+            ProcessName::Xmrig | ProcessName::Proxy => &XMRIG_VERSION,
+        };
+        let c = regex.captures(output)?;
+        let num = |i: usize| c.get(i).and_then(|m| m.as_str().parse::<u16>().ok());
+        Some(Self::new(num(1)?, num(2)?, num(3).unwrap_or(0)))
+    }
+}
+
+// Parse XMRig's build features out of [--version] output, e.g:
+//
+// XMRig 6.22.2
+//  built on Nov  4 2024 with GCC 13.2.0
+//  features: 64-bit AVX2 AES
+//
+// libuv/1.49.2
+// OpenSSL/3.0.15
+// hwloc/2.11.2
+pub fn xmrig_features(output: &str) -> Vec<String> {
+    let mut features = vec![];
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(f) = line.strip_prefix("features:") {
+            features.extend(f.split_whitespace().map(str::to_lowercase));
+        } else if line.starts_with("OpenSSL/") {
+            features.push(XMRIG_FEATURE_TLS.to_string());
+        } else if line.starts_with("hwloc/") {
+            features.push("hwloc".to_string());
+        }
+    }
+    features
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // P2Pool doesn't use patch versions, e.g: [v4.2]
+        if self.patch == 0 {
+            write!(f, "v{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [BinaryProbe]
+#[derive(Clone, Debug, Default)]
+pub struct BinaryProbe {
+    pub path: String,                  // The path that was (or is being) probed
+    pub version: Option<Version>,      // [None] if unknown
+    pub features: Option<Vec<String>>, // XMRig build features, [None] if unknown
+    pub probing: bool,                 // Is the probe thread running?
+}
+
+impl BinaryProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Is [min] supported by the probed binary?
+    // Unknown versions are assumed to support everything.
+    pub fn supports(&self, min: Version) -> bool {
+        self.version.is_none_or(|v| v >= min)
+    }
+
+    // Was the binary built with [feature]?
+    // Unknown features are assumed to be supported.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features
+            .as_ref()
+            .is_none_or(|f| f.iter().any(|f| f == feature))
+    }
+
+    // Version text for UI, e.g: [v4.2] or [unknown version]
+    pub fn version_text(&self) -> String {
+        match self.version {
+            Some(v) => v.to_string(),
+            None => "unknown version".to_string(),
+        }
+    }
+
+    // [Configured] vs [bundled] version text for the [About] tab
+    // and debug info, so reports don't quote the bundled version
+    // when a different binary is actually in use, e.g:
+    // [v4.1 (bundled: v4.2)] or [v4.2 (same as bundled)]
+    pub fn compare_text(&self, bundled: &str) -> String {
+        if self.probing {
+            return format!("probing... (bundled: {})", bundled);
+        }
+        match self.version {
+            Some(v) if v.to_string() == bundled => format!("{} (same as bundled)", v),
+            Some(v) => format!("{} (bundled: {})", v, bundled),
+            None if self.path.is_empty() => format!("not configured (bundled: {})", bundled),
+            None => format!("unknown version (bundled: {})", bundled),
+        }
+    }
+
+    // Spawn a thread that probes [path] if it differs from the last
+    // probed path. Cheap to call every frame; [path] must already be
+    // validated (is a file, correct name) by the caller.
+    pub fn spawn_if_changed(probe: &Arc<Mutex<Self>>, name: ProcessName, path: &str) {
+        let mut lock = lock!(probe);
+        if lock.probing || lock.path == path {
+            return;
+        }
+        *lock = Self {
+            path: path.to_string(),
+            version: None,
+            features: None,
+            probing: true,
+        };
+        drop(lock);
+
+        let probe = Arc::clone(probe);
+        let path = path.to_string();
+        std::thread::spawn(move || {
+            let output = Self::run(&path);
+            let version = output
+                .as_deref()
+                .and_then(|o| Version::from_output(name, o));
+            match version {
+                Some(v) => info!("{} | Probe [{}] ... {}", name, path, v),
+                None => warn!("{} | Probe [{}] ... unknown version", name, path),
+            }
+            // Only trust the features if the output was actually XMRig's.
+            let features = match (name, version, output) {
+                (ProcessName::Xmrig, Some(_), Some(o)) => Some(xmrig_features(&o)),
+                _ => None,
+            };
+            if name == ProcessName::P2pool {
+                crate::regex::select_p2pool_format(version);
+            }
+            let mut lock = lock!(probe);
+            lock.version = version;
+            lock.features = features;
+            lock.probing = false;
+        });
+    }
+
+    // Run [path --version] and return the combined STDOUT/STDERR.
+    fn run(path: &str) -> Option<String> {
+        let path = crate::disk::into_absolute_path(path.to_string()).ok()?;
+        let mut command = Command::new(&path);
+        command
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            // [CREATE_NO_WINDOW], don't flash a console window.
+            command.creation_flags(0x08000000);
+        }
+        let mut child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Probe | Could not spawn [{}]: {}", path.display(), e);
+                return None;
+            }
+        };
+
+        // Read both pipes while waiting, a binary that prints
+        // more than the pipe buffer would block on a full one.
+        fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+            std::thread::spawn(move || {
+                let mut bytes = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut bytes);
+                }
+                bytes
+            })
+        }
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        // Don't wait forever, an old binary might
+        // ignore [--version] and actually start.
+        let now = Instant::now();
+        loop {
+            match child.try_wait() {
+                Ok(Some(_)) => break,
+                Ok(None) if now.elapsed() < PROBE_TIMEOUT => sleep!(50),
+                _ => {
+                    warn!("Probe | [{}] timed out, killing", path.display());
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return None;
+                }
+            }
+        }
+
+        let mut string = String::from_utf8_lossy(&stdout.join().ok()?).to_string();
+        string.push_str(&String::from_utf8_lossy(&stderr.join().ok()?));
+        Some(string)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse_p2pool_version() {
+        use super::*;
+        let v = |s| Version::from_output(ProcessName::P2pool, s);
+        assert_eq!(
+            v("P2Pool v4.2 (built with GCC 13.2.0 on Nov  5 2024)"),
+            Some(Version::new(4, 2, 0))
+        );
+        assert_eq!(v("P2Pool v3.10.1"), Some(Version::new(3, 10, 1)));
+        assert_eq!(v("unknown option --version"), None);
+        assert_eq!(Version::new(4, 2, 0).to_string(), "v4.2");
+        assert_eq!(Version::new(3, 10, 1).to_string(), "v3.10.1");
+
+        // Gating
+        let mut probe = BinaryProbe::new();
+        assert!(probe.supports(P2POOL_MIN_BACKUP_HOSTS));
+        probe.version = Some(Version::new(3, 4, 0));
+        assert!(!probe.supports(P2POOL_MIN_BACKUP_HOSTS));
+        probe.version = Some(Version::new(3, 10, 0));
+        assert!(probe.supports(P2POOL_MIN_BACKUP_HOSTS));
+
+        // Configured vs bundled
+        assert_eq!(probe.compare_text("v4.2"), "v3.10 (bundled: v4.2)");
+        probe.version = Some(Version::new(4, 2, 0));
+        assert_eq!(probe.compare_text("v4.2"), "v4.2 (same as bundled)");
+        probe.version = None;
+        assert_eq!(probe.compare_text("v4.2"), "not configured (bundled: v4.2)");
+        probe.path = "p2pool/p2pool".to_string();
+        assert_eq!(
+            probe.compare_text("v4.2"),
+            "unknown version (bundled: v4.2)"
+        );
+        probe.probing = true;
+        assert_eq!(probe.compare_text("v4.2"), "probing... (bundled: v4.2)");
+    }
+
+    #[test]
+    fn parse_xmrig_version_and_features() {
+        use super::*;
+        let output = "XMRig 6.22.2\n built on Nov  4 2024 with GCC 13.2.0\n features: 64-bit AVX2 AES\n\nlibuv/1.49.2\nOpenSSL/3.0.15\nhwloc/2.11.2\n";
+        assert_eq!(
+            Version::from_output(ProcessName::Xmrig, output),
+            Some(Version::new(6, 22, 2))
+        );
+        assert_eq!(Version::new(6, 22, 2).to_string(), "v6.22.2");
+        let features = xmrig_features(output);
+        assert_eq!(features, ["64-bit", "avx2", "aes", "tls", "hwloc"]);
+
+        // Built without OpenSSL.
+        let output = "XMRig 6.22.2\n features: 64-bit AVX2 AES\n\nlibuv/1.49.2\n";
+        let mut probe = BinaryProbe::new();
+        assert!(probe.has_feature(XMRIG_FEATURE_TLS));
+        probe.features = Some(xmrig_features(output));
+        assert!(!probe.has_feature(XMRIG_FEATURE_TLS));
+        assert!(probe.has_feature("aes"));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn run_drains_big_output() {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let script = tmp.path().join("p2pool");
+        // Way more than a pipe buffer (64 KiB on Linux) on both pipes.
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nhead -c 1000000 /dev/zero | tr '\\0' a\nhead -c 1000000 /dev/zero | tr '\\0' b >&2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let output = BinaryProbe::run(script.to_str().unwrap()).unwrap();
+        assert_eq!(output.len(), 2_000_000);
+        assert!(output.starts_with('a') && output.ends_with('b'));
+    }
+}
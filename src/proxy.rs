@@ -0,0 +1,416 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The [XMRig-Proxy] tab: the proxy's console, what the rigs
+// should point at, its stats/rigs and the settings it starts with.
+
+use crate::regex::REGEXES;
+use crate::{
+    ansi::Console,
+    constants::*,
+    disk::*,
+    gupax::{FileType, FileWindow},
+    human::localize,
+    macros::*,
+    Process, PubProxyApi, Regexes,
+};
+use egui::{Button, Checkbox, ComboBox, Label, RichText, TextEdit, TextStyle::*};
+use log::*;
+use std::sync::{Arc, Mutex};
+
+impl crate::disk::Proxy {
+    #[expect(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        file_window: &Arc<Mutex<FileWindow>>,
+        process: &Arc<Mutex<Process>>,
+        api: &Arc<Mutex<PubProxyApi>>,
+        stdin: &mut crate::stdin::Stdin,
+        console: &mut Console,
+        colors: bool,
+        width: f32,
+        height: f32,
+        _ctx: &egui::Context,
+        ui: &mut egui::Ui,
+    ) {
+        let text_edit = height / 25.0;
+        let alive = lock!(process).is_alive();
+        //---------------------------------------------------------------------------------------------------- Console
+        debug!("XMRig-Proxy Tab | Rendering [Console]");
+        ui.group(|ui| {
+            let height = height / 3.2;
+            let width = width - SPACE;
+            egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
+                ui.style_mut().override_text_style = Some(Name("MonospaceSmall".into()));
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .max_width(width)
+                    .max_height(height)
+                    .auto_shrink([false; 2])
+                    .show_viewport(ui, |ui, _| {
+                        console.show(ui, &lock!(api).output, colors, [width, height]);
+                    });
+            });
+            ui.separator();
+            stdin.show(
+                ui,
+                process,
+                r#"Commands: [h]ashrate, [c]onnections, [v]erbose, [w]orkers"#,
+                PROXY_INPUT,
+                &[],
+                [width, text_edit],
+            );
+        });
+
+        //---------------------------------------------------------------------------------------------------- Stats
+        debug!("XMRig-Proxy Tab | Rendering [Stats]");
+        let api = lock!(api);
+        ui.group(|ui| {
+            let width = (width / 4.0) - SPACE;
+            ui.horizontal(|ui| {
+                let connect = if alive { api.connect.as_str() } else { "???" };
+                ui.add_sized(
+                    [width, text_edit],
+                    Label::new(RichText::new("Point rigs at").underline().color(BONE)),
+                )
+                .on_hover_text(PROXY_CONNECT);
+                ui.add_sized([width, text_edit], Label::new(connect))
+                    .on_hover_text(PROXY_CONNECT);
+                ui.add_enabled_ui(alive, |ui| {
+                    if ui
+                        .add_sized([width / 2.0, text_edit], Button::new("Copy"))
+                        .on_hover_text(PROXY_CONNECT)
+                        .clicked()
+                    {
+                        let connect = api.connect.clone();
+                        ui.output_mut(|o| o.copied_text = connect);
+                    }
+                });
+            });
+            ui.separator();
+            egui::Grid::new("proxy_stats")
+                .num_columns(4)
+                .min_col_width(width)
+                .show(ui, |ui| {
+                    let row = |ui: &mut egui::Ui, name: &str, value: String| {
+                        ui.label(RichText::new(name).underline().color(BONE));
+                        ui.label(value);
+                    };
+                    row(ui, "Uptime", api.uptime.localized());
+                    row(ui, "Mode", api.mode.clone());
+                    ui.end_row();
+                    row(ui, "Hashrate (1m/10m/1h)", api.hashrate.localized());
+                    row(ui, "Rigs", api.miners.clone());
+                    ui.end_row();
+                    row(ui, "Pool connections", api.upstreams.clone());
+                    row(ui, "Latency", api.latency.clone());
+                    ui.end_row();
+                    row(
+                        ui,
+                        "Shares (accepted/rejected)",
+                        localize(&format!("{} / {}", api.accepted, api.rejected)).into_owned(),
+                    );
+                    row(
+                        ui,
+                        "Shares (invalid/expired)",
+                        localize(&format!("{} / {}", api.invalid, api.expired)).into_owned(),
+                    );
+                    ui.end_row();
+                });
+        });
+
+        //---------------------------------------------------------------------------------------------------- Rigs
+        debug!("XMRig-Proxy Tab | Rendering [Rigs]");
+        ui.group(|ui| {
+            egui::CollapsingHeader::new(format!("Rigs ({})", api.workers.len()))
+                .id_source("proxy_workers")
+                .default_open(true)
+                .show(ui, |ui| {
+                    let width = (width / 6.0) - SPACE;
+                    egui::Grid::new("proxy_workers_grid")
+                        .num_columns(6)
+                        .min_col_width(width)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "Name",
+                                "IP",
+                                "Hashrate (1m/10m/1h)",
+                                "Accepted",
+                                "Rejected",
+                                "Invalid",
+                            ] {
+                                ui.label(RichText::new(header).underline().color(BONE))
+                                    .on_hover_text(PROXY_WORKERS);
+                            }
+                            ui.end_row();
+                            for worker in &api.workers {
+                                let color = if worker.idle { GRAY } else { LIGHT_GRAY };
+                                for text in [
+                                    worker.name.clone(),
+                                    worker.ip.clone(),
+                                    worker.hashrate.localized(),
+                                    worker.accepted.to_string(),
+                                    worker.rejected.to_string(),
+                                    worker.invalid.to_string(),
+                                ] {
+                                    ui.label(RichText::new(text).color(color));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+        drop(api);
+
+        //---------------------------------------------------------------------------------------------------- Arguments
+        debug!("XMRig-Proxy Tab | Rendering [Arguments]");
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let width = (width / 10.0) - SPACE;
+                ui.add_sized([width, text_edit], Label::new("Command arguments:"));
+                ui.add_sized(
+                    [ui.available_width(), text_edit],
+                    TextEdit::hint_text(
+                        TextEdit::singleline(&mut self.arguments),
+                        r#"--url <...> --bind <...> --http-port <...> --no-color"#,
+                    ),
+                )
+                .on_hover_text(PROXY_ARGUMENTS);
+                self.arguments.truncate(1024);
+            })
+        });
+
+        //---------------------------------------------------------------------------------------------------- Path
+        debug!("XMRig-Proxy Tab | Rendering [Path]");
+        {
+            let mut guard = lock!(file_window);
+            if guard.picked_proxy {
+                self.path = std::mem::take(&mut guard.proxy_path);
+                guard.picked_proxy = false;
+            }
+        }
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let (text, color, hover) = if self.path.is_empty() {
+                    ("XMRig-Proxy Binary Path ➖", LIGHT_GRAY, PROXY_PATH_EMPTY)
+                } else if !Gupax::path_is_file(&self.path) {
+                    ("XMRig-Proxy Binary Path ❌", RED, PROXY_PATH_NOT_FILE)
+                } else if !crate::update::check_proxy_path(&self.path) {
+                    ("XMRig-Proxy Binary Path ❌", RED, PROXY_PATH_NOT_VALID)
+                } else {
+                    ("XMRig-Proxy Binary Path ✔", GREEN, PROXY_PATH_OK)
+                };
+                ui.add_sized(
+                    [width / 4.0, text_edit],
+                    Label::new(RichText::new(text).color(color)),
+                )
+                .on_hover_text(hover);
+                ui.add_enabled_ui(!lock!(file_window).thread, |ui| {
+                    if ui.button("Open").on_hover_text(GUPAX_SELECT).clicked() {
+                        Gupax::spawn_file_window_thread(file_window, FileType::Proxy);
+                    }
+                });
+                ui.add_sized(
+                    [ui.available_width(), text_edit],
+                    TextEdit::singleline(&mut self.path),
+                )
+                .on_hover_text(PROXY_PATH);
+            });
+        });
+
+        ui.add_enabled_ui(self.arguments.is_empty(), |ui| {
+            //---------------------------------------------------------------------------------------------------- Address
+            debug!("XMRig-Proxy Tab | Rendering [Address]");
+            ui.group(|ui| {
+                let width = width - SPACE;
+                let len = format!("{:02}", self.address.len());
+                let (text, color) = if self.address.is_empty() {
+                    (format!("Monero Address [{}/95] ➖", len), LIGHT_GRAY)
+                } else if Regexes::addr_ok(&self.address, MoneroNetwork::Mainnet) {
+                    (format!("Monero Address [{}/95] ✔", len), GREEN)
+                } else {
+                    (format!("Monero Address [{}/95] ❌", len), RED)
+                };
+                ui.add_sized(
+                    [width, text_edit],
+                    Label::new(RichText::new(text).color(color)),
+                );
+                ui.add_sized(
+                    [width, text_edit],
+                    TextEdit::hint_text(TextEdit::singleline(&mut self.address), "4..."),
+                )
+                .on_hover_text(PROXY_ADDRESS);
+                self.address.truncate(95);
+            });
+
+            //---------------------------------------------------------------------------------------------------- IP/Port
+            debug!("XMRig-Proxy Tab | Rendering [IP/Port] TextEdits");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = width / 10.0;
+                    ui.spacing_mut().text_edit_width = width * 2.39;
+                    ui.vertical(|ui| {
+                        ip_edit(
+                            ui,
+                            "Pool IP      ",
+                            &mut self.ip,
+                            PROXY_UPSTREAM_IP,
+                            width,
+                            text_edit,
+                        );
+                        port_edit(
+                            ui,
+                            "Pool Port    ",
+                            &mut self.port,
+                            PROXY_UPSTREAM_PORT,
+                            width,
+                            text_edit,
+                        );
+                        ip_edit(
+                            ui,
+                            "Bind IP      ",
+                            &mut self.bind_ip,
+                            PROXY_BIND_IP,
+                            width,
+                            text_edit,
+                        );
+                        port_edit(
+                            ui,
+                            "Bind Port    ",
+                            &mut self.bind_port,
+                            PROXY_BIND_PORT,
+                            width,
+                            text_edit,
+                        );
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        ip_edit(
+                            ui,
+                            "HTTP API IP  ",
+                            &mut self.api_ip,
+                            PROXY_API_IP,
+                            width,
+                            text_edit,
+                        );
+                        port_edit(
+                            ui,
+                            "HTTP API Port",
+                            &mut self.api_port,
+                            PROXY_API_PORT,
+                            width,
+                            text_edit,
+                        );
+                        ui.horizontal(|ui| {
+                            ui.add_sized([width, text_edit], Label::new("Mode"))
+                                .on_hover_text(PROXY_MODE);
+                            ComboBox::from_id_source("proxy_mode")
+                                .selected_text(self.mode.to_string())
+                                .width(width * 2.39)
+                                .show_ui(ui, |ui| {
+                                    for mode in ProxyMode::ALL {
+                                        ui.selectable_value(&mut self.mode, mode, mode.to_string());
+                                    }
+                                })
+                                .response
+                                .on_hover_text(PROXY_MODE);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add_sized(
+                                [width * 1.5, text_edit],
+                                Checkbox::new(&mut self.tls, "TLS Connection"),
+                            )
+                            .on_hover_text(PROXY_TLS);
+                            ui.separator();
+                            ui.add_sized(
+                                [width * 1.5, text_edit],
+                                Checkbox::new(&mut self.keepalive, "Keepalive"),
+                            )
+                            .on_hover_text(PROXY_KEEPALIVE);
+                        });
+                    });
+                });
+            });
+        });
+
+        //---------------------------------------------------------------------------------------------------- Environment
+        debug!("XMRig-Proxy Tab | Rendering [Working directory/Environment]");
+        let path = self.absolute_path();
+        ui.group(|ui| {
+            crate::envvar::workdir_editor(
+                &mut self.workdir,
+                &path,
+                (width / 10.0) - SPACE,
+                text_edit,
+                ui,
+            );
+            egui::CollapsingHeader::new(format!("Environment ({})", self.env.len()))
+                .id_source("proxy_env")
+                .show(ui, |ui| {
+                    crate::envvar::editor(&mut self.env, (width / 10.0) - SPACE, text_edit, ui);
+                });
+        });
+    }
+}
+
+// An [IP] label + text box, with the same ✔/❌/➖ as the [XMRig] tab.
+fn ip_edit(ui: &mut egui::Ui, name: &str, ip: &mut String, hover: &str, width: f32, height: f32) {
+    ui.horizontal(|ui| {
+        let len = format!("{:03}", ip.len());
+        let (text, color) = if ip.is_empty() {
+            (format!("{} [{}/255]➖", name, len), LIGHT_GRAY)
+        } else if ip == "localhost" || REGEXES.ipv4.is_match(ip) || REGEXES.domain.is_match(ip) {
+            (format!("{} [{}/255]✔", name, len), GREEN)
+        } else {
+            (format!("{} [{}/255]❌", name, len), RED)
+        };
+        ui.add_sized(
+            [width, height],
+            Label::new(RichText::new(text).color(color)),
+        );
+        ui.text_edit_singleline(ip).on_hover_text(hover);
+        ip.truncate(255);
+    });
+}
+
+// A [Port] label + text box.
+fn port_edit(
+    ui: &mut egui::Ui,
+    name: &str,
+    port: &mut String,
+    hover: &str,
+    width: f32,
+    height: f32,
+) {
+    ui.horizontal(|ui| {
+        let len = port.len();
+        let (text, color) = if port.is_empty() {
+            (format!("{} [  {}/5  ]➖", name, len), LIGHT_GRAY)
+        } else if REGEXES.port.is_match(port) {
+            (format!("{} [  {}/5  ]✔", name, len), GREEN)
+        } else {
+            (format!("{} [  {}/5  ]❌", name, len), RED)
+        };
+        ui.add_sized(
+            [width, height],
+            Label::new(RichText::new(text).color(color)),
+        );
+        ui.text_edit_singleline(port).on_hover_text(hover);
+        port.truncate(5);
+    });
+}
@@ -0,0 +1,182 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Antivirus quarantine detection.
+//
+// Windows Defender (and most other AVs) flag XMRig as a "potentially
+// unwanted" miner and silently remove or lock [xmrig.exe]. From Gupax's side
+// this looks like a binary that passed the path check a moment ago but is
+// gone (or can't be opened) when it's spawned. The watchdogs report that here
+// and [App] turns it into an [ErrorState] with the exclusion steps.
+
+use crate::helper::ProcessName;
+use log::*;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Windows system error codes an AV block shows up as.
+const ERROR_ACCESS_DENIED: i32 = 5;
+const ERROR_VIRUS_INFECTED: i32 = 225;
+const ERROR_VIRUS_DELETED: i32 = 226;
+
+// Set by the watchdog threads, taken by [App] on the next frame.
+static SUSPECT: Mutex<Option<Suspect>> = Mutex::new(None);
+
+//---------------------------------------------------------------------------------------------------- Cause
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cause {
+    Missing, // The binary disappeared
+    Blocked, // It's there but opening/executing it was denied
+}
+
+impl std::fmt::Display for Cause {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "disappeared"),
+            Self::Blocked => write!(f, "was blocked"),
+        }
+    }
+}
+
+// Why [path] probably got quarantined, [None] if [error] looks unrelated.
+pub fn classify(path: &Path, error: Option<&std::io::Error>) -> Option<Cause> {
+    if !path.exists() {
+        return Some(Cause::Missing);
+    }
+    let error = error?;
+    match error.raw_os_error() {
+        Some(ERROR_VIRUS_DELETED) => Some(Cause::Missing),
+        Some(ERROR_ACCESS_DENIED | ERROR_VIRUS_INFECTED) => Some(Cause::Blocked),
+        _ if error.kind() == std::io::ErrorKind::PermissionDenied => Some(Cause::Blocked),
+        _ if error.kind() == std::io::ErrorKind::NotFound => Some(Cause::Missing),
+        _ => None,
+    }
+}
+
+// Same as [classify()] for a [portable_pty] spawn error.
+pub fn classify_spawn(path: &Path, error: &anyhow::Error) -> Option<Cause> {
+    classify(path, error.downcast_ref::<std::io::Error>())
+}
+
+//---------------------------------------------------------------------------------------------------- Suspect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suspect {
+    pub name: ProcessName,
+    pub path: PathBuf,
+    pub cause: Cause,
+}
+
+impl Suspect {
+    pub fn new(name: ProcessName, path: &Path, cause: Cause) -> Self {
+        Self {
+            name,
+            path: path.to_path_buf(),
+            cause,
+        }
+    }
+
+    // The message for the [ErrorState].
+    pub fn text(&self) -> String {
+        let steps = if cfg!(windows) {
+            crate::constants::QUARANTINE_STEPS_WINDOWS
+        } else {
+            crate::constants::QUARANTINE_STEPS
+        };
+        format!(
+			"[{}] {} right before it was started:\n{}\n\nThis is almost always an antivirus quarantining it, {} is often flagged as a \"potentially unwanted\" program.\n\n{}",
+			self.name,
+			self.cause,
+			self.path.display(),
+			self.name,
+			steps
+		)
+    }
+
+    // Is the binary back (restored from quarantine) and readable?
+    pub fn restored(&self) -> bool {
+        self.path.is_file() && std::fs::File::open(&self.path).is_ok()
+    }
+}
+
+// Called by the watchdogs when spawning failed.
+pub fn report(suspect: Suspect) {
+    warn!(
+        "Quarantine | [{}] {}: [{}]",
+        suspect.name,
+        suspect.cause,
+        suspect.path.display()
+    );
+    *SUSPECT.lock().unwrap() = Some(suspect);
+}
+
+// The last reported [Suspect], if [App] hasn't shown it yet.
+pub fn take() -> Option<Suspect> {
+    SUSPECT.lock().unwrap().take()
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn classify_spawn_errors() {
+        use crate::quarantine::*;
+        use std::io::{Error, ErrorKind};
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let binary = dir.join("xmrig");
+        std::fs::write(&binary, "").unwrap();
+
+        // Gone is always suspicious, with or without an error.
+        let gone = dir.join("gone");
+        assert_eq!(classify(&gone, None), Some(Cause::Missing));
+        assert_eq!(
+            classify(&gone, Some(&Error::from(ErrorKind::Other))),
+            Some(Cause::Missing)
+        );
+
+        // Present: depends on the error.
+        assert_eq!(classify(&binary, None), None);
+        assert_eq!(
+            classify(&binary, Some(&Error::from(ErrorKind::PermissionDenied))),
+            Some(Cause::Blocked)
+        );
+        assert_eq!(
+            classify(&binary, Some(&Error::from_raw_os_error(225))),
+            Some(Cause::Blocked)
+        );
+        assert_eq!(
+            classify(&binary, Some(&Error::from_raw_os_error(226))),
+            Some(Cause::Missing)
+        );
+        assert_eq!(
+            classify(&binary, Some(&Error::from(ErrorKind::Other))),
+            None
+        );
+        let spawn = anyhow::Error::from(Error::from(ErrorKind::PermissionDenied));
+        assert_eq!(classify_spawn(&binary, &spawn), Some(Cause::Blocked));
+        assert_eq!(classify_spawn(&binary, &anyhow::anyhow!("pty")), None);
+
+        // Reported once, then taken.
+        let suspect = Suspect::new(ProcessName::Xmrig, &gone, Cause::Missing);
+        assert!(!suspect.restored());
+        assert!(suspect.text().contains("disappeared"));
+        report(suspect.clone());
+        assert_eq!(take(), Some(suspect));
+        assert_eq!(take(), None);
+    }
+}
@@ -17,13 +17,22 @@
 
 // Some regexes used throughout Gupax.
 
+use crate::disk::MoneroNetwork;
+use crate::probe::Version;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 //---------------------------------------------------------------------------------------------------- Lazy
 pub static REGEXES: Lazy<Regexes> = Lazy::new(Regexes::new);
-pub static P2POOL_REGEX: Lazy<P2poolRegex> = Lazy::new(P2poolRegex::new);
+pub static P2POOL_REGEX: P2poolRegexRef = P2poolRegexRef;
+static P2POOL_REGEXES: Lazy<Vec<P2poolRegex>> =
+    Lazy::new(|| P2POOL_LOG_FORMATS.iter().map(P2poolRegex::new).collect());
+// Index into [P2POOL_LOG_FORMATS], the newest until P2Pool is probed.
+static P2POOL_FORMAT: AtomicUsize = AtomicUsize::new(P2POOL_LOG_FORMATS.len() - 1);
 pub static XMRIG_REGEX: Lazy<XmrigRegex> = Lazy::new(XmrigRegex::new);
+// Terminal escape codes (colors, cursor), P2Pool/XMRig's output is full of them.
+pub static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
 
 //---------------------------------------------------------------------------------------------------- [Regexes] struct
 // General purpose Regexes, mostly used in the GUI.
@@ -42,7 +51,7 @@ impl Regexes {
     fn new() -> Self {
         Self {
 			name: Regex::new("^[A-Za-z0-9-_.]+( [A-Za-z0-9-_.]+)*$").unwrap(),
-			address: Regex::new("^4[A-Za-z1-9]+$").unwrap(), // This still needs to check for (l, I, o, 0)
+			address: Regex::new("^[459A][A-Za-z1-9]+$").unwrap(), // This still needs to check for (l, I, o, 0) and the network prefix
 			ipv4: Regex::new(r#"^((25[0-5]|(2[0-4]|1\d|[1-9]|)\d)\.?\b){4}$"#).unwrap(),
 			domain: Regex::new(r#"^[A-Za-z0-9-.]+[A-Za-z0-9-]+$"#).unwrap(),
 			port: Regex::new(r#"^([1-9][0-9]{0,3}|[1-5][0-9]{4}|6[0-4][0-9]{3}|65[0-4][0-9]{2}|655[0-2][0-9]|6553[0-5])$"#).unwrap(),
@@ -53,8 +62,9 @@ impl Regexes {
     // Check if a Monero address is correct.
     // This actually only checks for length & Base58, and doesn't do any checksum validation
     // (the last few bytes of a Monero address are a Keccak hash checksum) so some invalid addresses can trick this function.
-    pub fn addr_ok(address: &str) -> bool {
+    pub fn addr_ok(address: &str, network: MoneroNetwork) -> bool {
         address.len() == 95
+            && address.starts_with(network.address_prefixes())
             && REGEXES.address.is_match(address)
             && !address.contains('0')
             && !address.contains('O')
@@ -89,30 +99,127 @@ pub struct P2poolRegex {
     pub block_comma: Regex,
     pub synchronized: Regex,
     pub next_height_1: Regex,
+    pub miner_hashrate: Regex,
+    pub zmq_ok: Regex,
+    pub zmq_fail: Regex,
 }
 
 impl P2poolRegex {
     #[cold]
     #[inline(never)]
-    fn new() -> Self {
+    fn new(format: &P2poolLogFormat) -> Self {
         Self {
-            date: Regex::new("[0-9]+-[0-9]+-[0-9]+ [0-9]+:[0-9]+:[0-9]+.[0-9]+").unwrap(),
-            payout: Regex::new("payout of [0-9].[0-9]+ XMR").unwrap(), // Assumes 12 digits after the dot.
+            date: Regex::new("[0-9]+-[0-9]+-[0-9]+ [0-9]+:[0-9]+:[0-9]+.[0-9]+Z?").unwrap(), // [Z] if UTC, see [xmr.rs]
+            payout: Regex::new(format.payout).unwrap(),
             payout_float: Regex::new("[0-9].[0-9]{12}").unwrap(), // Assumes 12 digits after the dot.
-            block: Regex::new("block [0-9]{7}").unwrap(), // Monero blocks will be 7 digits for... the next 10,379 years
-            block_int: Regex::new("[0-9]{7}").unwrap(),
+            block: Regex::new(format.block).unwrap(),
+            block_int: Regex::new("[0-9]{7}").unwrap(), // Monero blocks will be 7 digits for... the next 10,379 years
             block_comma: Regex::new("[0-9],[0-9]{3},[0-9]{3}").unwrap(),
-            synchronized: Regex::new("SYNCHRONIZED").unwrap(),
-            next_height_1: Regex::new("next height = 1").unwrap(),
+            synchronized: Regex::new(format.synchronized).unwrap(),
+            next_height_1: Regex::new(format.next_height_1).unwrap(),
+            miner_hashrate: Regex::new(r"(?m)^\s*Hashrate\s+= ([0-9.]+) ([KMGT]?)H/s").unwrap(),
+            // [monerod] pushes new blocks/miner data to P2Pool over ZMQ, RPC is only used on startup.
+            zmq_ok: Regex::new("ZMQReader worker thread ready|new miner data").unwrap(),
+            zmq_fail: Regex::new("(?i)no ZMQ messages received|ZMQReader.*(exception|fail|error)")
+                .unwrap(),
         }
     }
 }
 
+// [P2POOL_REGEX] derefs to the [P2poolRegex] of the selected log format.
+pub struct P2poolRegexRef;
+
+impl std::ops::Deref for P2poolRegexRef {
+    type Target = P2poolRegex;
+    fn deref(&self) -> &P2poolRegex {
+        &P2POOL_REGEXES[P2POOL_FORMAT.load(Ordering::Relaxed)]
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- P2Pool log formats
+// P2Pool occasionally rewords its log, which silently breaks payout/sync
+// detection. The wording-dependent regexes are kept per P2Pool version
+// (oldest first) along with real sample lines for [p2pool_self_test()].
+// A rewording gets a new entry here instead of changing an old one.
+pub struct P2poolLogFormat {
+    pub since: Version,
+    payout: &'static str,
+    block: &'static str,
+    synchronized: &'static str,
+    next_height_1: &'static str,
+    sample_payout: &'static str,
+    sample_synchronized: &'static str,
+}
+
+pub const P2POOL_LOG_FORMATS: [P2poolLogFormat; 1] = [P2poolLogFormat {
+    since: Version::new(0, 0, 0),
+    payout: "payout of [0-9].[0-9]+ XMR", // Assumes 12 digits after the dot.
+    block: "block [0-9]{7}",
+    synchronized: "SYNCHRONIZED",
+    next_height_1: "next height = 1",
+    sample_payout: "NOTICE  2022-11-11 11:11:11.1111 P2Pool You received a payout of 0.111111111111 XMR in block 1111111",
+    sample_synchronized: "NOTICE  2020-12-11 12:35:41.3150 SideChain SYNCHRONIZED",
+}];
+
+// The log format currently in use.
+pub fn p2pool_format() -> &'static P2poolLogFormat {
+    &P2POOL_LOG_FORMATS[P2POOL_FORMAT.load(Ordering::Relaxed)]
+}
+
+// Use the newest format the probed P2Pool version is covered by, [None] (unknown) uses the newest.
+pub fn select_p2pool_format(version: Option<Version>) {
+    let i = match version {
+        Some(v) => P2POOL_LOG_FORMATS
+            .iter()
+            .rposition(|f| f.since <= v)
+            .unwrap_or(0),
+        None => P2POOL_LOG_FORMATS.len() - 1,
+    };
+    if P2POOL_FORMAT.swap(i, Ordering::Relaxed) != i {
+        log::info!(
+            "Regex | Using the P2Pool {} log format",
+            P2POOL_LOG_FORMATS[i].since
+        );
+    }
+}
+
+// Run every format's regexes against its own sample lines,
+// returns what failed (empty if everything is fine).
+pub fn p2pool_self_test() -> Vec<String> {
+    let mut failed = vec![];
+    for (format, regex) in P2POOL_LOG_FORMATS.iter().zip(P2POOL_REGEXES.iter()) {
+        let mut check = |ok: bool, what: &str| {
+            if !ok {
+                failed.push(format!("P2Pool {} log format: {}", format.since, what));
+            }
+        };
+        let payout = regex.payout.find(format.sample_payout);
+        check(payout.is_some(), "payout not matched");
+        let amount = payout
+            .and_then(|p| regex.payout_float.find(p.as_str()))
+            .and_then(|f| f.as_str().parse::<f64>().ok());
+        check(amount.is_some_and(|a| a > 0.0), "payout amount not parsed");
+        let block = regex
+            .block
+            .find(format.sample_payout)
+            .and_then(|b| regex.block_int.find(b.as_str()));
+        check(block.is_some(), "payout block not parsed");
+        check(
+            regex.synchronized.is_match(format.sample_synchronized),
+            "sync not matched",
+        );
+    }
+    failed
+}
+
 //---------------------------------------------------------------------------------------------------- XMRig regex.
 #[derive(Debug)]
 pub struct XmrigRegex {
     pub not_mining: Regex,
     pub new_job: Regex,
+    pub speed: Regex,
+    pub share: Regex,
+    pub share_latency: Regex,
 }
 
 impl XmrigRegex {
@@ -120,6 +227,11 @@ impl XmrigRegex {
         Self {
             not_mining: Regex::new("no active pools, stop mining").unwrap(),
             new_job: Regex::new("new job").unwrap(),
+            speed: Regex::new(r"speed 10s/60s/15m (\S+) (\S+) (\S+) H/s").unwrap(),
+            share: Regex::new(r"(?:accepted|rejected) \(([0-9]+)/([0-9]+)\) diff ([0-9]+)")
+                .unwrap(),
+            share_latency: Regex::new(r"accepted \([0-9]+/[0-9]+\) diff [0-9]+ \(([0-9]+) ms\)")
+                .unwrap(),
         }
     }
 }
@@ -156,7 +268,7 @@ mod test {
 
     #[test]
     fn build_p2pool_regex() {
-        let r = P2poolRegex::new();
+        let r = P2poolRegex::new(&P2POOL_LOG_FORMATS[0]);
         let text = "NOTICE  2022-11-11 11:11:11.1111 P2Pool You received a payout of 0.111111111111 XMR in block 1111111";
         let text2 = "2022-11-11 11:11:11.1111 | 0.111111111111 XMR | Block 1,111,111";
         let text3 = "NOTICE  2020-12-11 12:35:41.3150 SideChain SYNCHRONIZED";
@@ -178,6 +290,45 @@ mod test {
         assert_eq!(r.synchronized.find(text3).unwrap().as_str(), "SYNCHRONIZED");
     }
 
+    #[test]
+    fn p2pool_log_formats() {
+        assert!(p2pool_self_test().is_empty());
+        // Formats are oldest first.
+        assert!(P2POOL_LOG_FORMATS
+            .windows(2)
+            .all(|f| f[0].since < f[1].since));
+        select_p2pool_format(Some(Version::new(4, 0, 0)));
+        assert!(p2pool_format().since <= Version::new(4, 0, 0));
+        select_p2pool_format(None);
+        assert_eq!(
+            p2pool_format().since,
+            P2POOL_LOG_FORMATS.last().unwrap().since
+        );
+    }
+
+    #[test]
+    fn address_network() {
+        use crate::disk::MoneroNetwork;
+        use crate::regex::Regexes;
+        let tail = "A".repeat(94);
+        let mainnet = format!("4{tail}");
+        let testnet = format!("9{tail}");
+        let stagenet = format!("5{tail}");
+        assert!(Regexes::addr_ok(&mainnet, MoneroNetwork::Mainnet));
+        assert!(Regexes::addr_ok(&testnet, MoneroNetwork::Testnet));
+        assert!(Regexes::addr_ok(
+            &format!("A{tail}"),
+            MoneroNetwork::Testnet
+        ));
+        assert!(Regexes::addr_ok(&stagenet, MoneroNetwork::Stagenet));
+        assert!(!Regexes::addr_ok(&testnet, MoneroNetwork::Mainnet));
+        assert!(!Regexes::addr_ok(&mainnet, MoneroNetwork::Stagenet));
+        assert!(!Regexes::addr_ok(
+            &format!("40{}", &tail[1..]),
+            MoneroNetwork::Mainnet
+        ));
+    }
+
     #[test]
     fn build_xmrig_regex() {
         let r = XmrigRegex::new();
@@ -188,5 +339,11 @@ mod test {
             "no active pools, stop mining"
         );
         assert_eq!(r.new_job.find(text2).unwrap().as_str(), "new job");
+        let text3 = "[2022-02-12 12:49:30.311]  cpu      accepted (12/1) diff 120001 (45 ms)";
+        assert_eq!(&r.share_latency.captures(text3).unwrap()[1], "45");
+        assert!(r
+            .share_latency
+            .captures("cpu      rejected (12/1) diff 120001 (45 ms)")
+            .is_none());
     }
 }
@@ -0,0 +1,427 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Weekly/monthly payout reports.
+//
+// Payouts come from the Gupax-P2Pool API [log], uptime & hashrate come
+// from the [history] file in the same folder, which holds 1 line per
+// day that XMRig was mining:
+//
+//     "2024-05-01 | 3600 | 12345.6"
+//     (DATE | SECONDS MINED | AVERAGE HASHRATE)
+
+use crate::{disk::PayoutTimezone, human::*, xmr::*};
+use chrono::{Datelike, NaiveDate};
+use log::*;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How often the current XMRig hashrate gets added to [history].
+pub const HISTORY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+//---------------------------------------------------------------------------------------------------- [HistoryDay]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HistoryDay {
+    pub date: NaiveDate,
+    pub uptime: u64,   // Seconds XMRig was mining
+    pub hashrate: f64, // Average hashrate while mining
+}
+
+impl HistoryDay {
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut split = line.split('|').map(str::trim);
+        let date = NaiveDate::parse_from_str(split.next()?, "%Y-%m-%d").ok()?;
+        let uptime = split.next()?.parse::<u64>().ok()?;
+        let hashrate = split.next()?.parse::<f64>().ok()?;
+        Some(Self {
+            date,
+            uptime,
+            hashrate,
+        })
+    }
+
+    pub fn parse(history: &str) -> Vec<Self> {
+        let mut vec = Vec::with_capacity(history.lines().count());
+        for line in history.lines().filter(|l| !l.trim().is_empty()) {
+            match Self::from_line(line) {
+                Some(day) => vec.push(day),
+                None => warn!("Report | Skipping invalid history line: [{}]", line),
+            }
+        }
+        vec
+    }
+
+    pub fn to_lines(history: &[Self]) -> String {
+        let mut string = String::with_capacity(history.len() * 30);
+        for day in history {
+            let _ = writeln!(
+                string,
+                "{} | {} | {:.1}",
+                day.date, day.uptime, day.hashrate
+            );
+        }
+        string
+    }
+
+    // Add [secs] of mining at [hashrate] to [date]'s entry.
+    pub fn add_sample(history: &mut Vec<Self>, date: NaiveDate, secs: u64, hashrate: f64) {
+        match history.last_mut() {
+            Some(day) if day.date == date => {
+                let total = day.uptime + secs;
+                day.hashrate =
+                    (day.hashrate * day.uptime as f64 + hashrate * secs as f64) / total as f64;
+                day.uptime = total;
+            }
+            _ => history.push(Self {
+                date,
+                uptime: secs,
+                hashrate,
+            }),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Period]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Period {
+    Week,
+    Month,
+}
+
+impl Period {
+    // Sortable label for the period [date] is in, e.g: [2024-W05] or [2024-05]
+    fn label(self, date: NaiveDate) -> String {
+        match self {
+            Self::Week => {
+                let week = date.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            Self::Month => format!("{}-{:02}", date.year(), date.month()),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Week => "Weekly",
+            Self::Month => "Monthly",
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- [Row]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub period: String,
+    pub payouts: u64,
+    pub xmr: AtomicUnit,
+    pub uptime: u64,
+    pub hashrate: f64, // Average, weighted by uptime
+}
+
+impl Row {
+    fn new(period: String) -> Self {
+        Self {
+            period,
+            payouts: 0,
+            xmr: AtomicUnit::new(),
+            uptime: 0,
+            hashrate: 0.0,
+        }
+    }
+
+    // [localized] for the [Status] tab, exports are always English.
+    fn cells(&self, localized: bool) -> [String; 5] {
+        let local = |s: String| {
+            if localized {
+                localize(&s).into_owned()
+            } else {
+                s
+            }
+        };
+        let uptime = HumanTime::from_u64(self.uptime);
+        [
+            self.period.clone(),
+            local(HumanNumber::from_u64(self.payouts).to_string()),
+            local(format!("{} XMR", self.xmr)),
+            local(HumanNumber::to_hashrate(self.hashrate as f32).to_string()),
+            if localized {
+                uptime.localized()
+            } else {
+                uptime.to_string()
+            },
+        ]
+    }
+}
+
+const HEADER: [&str; 5] = ["Period", "Payouts", "XMR", "Avg Hashrate", "Uptime"];
+
+//---------------------------------------------------------------------------------------------------- Report
+// Summarize payouts & history per [period], newest first.
+// Payouts are grouped by their date in [timezone].
+pub fn build(
+    period: Period,
+    timezone: PayoutTimezone,
+    payouts: &PayoutOrd,
+    history: &[HistoryDay],
+) -> Vec<Row> {
+    let mut map: BTreeMap<String, Row> = BTreeMap::new();
+    for (date, atomic_unit, _) in payouts.iter() {
+        // "2022-08-17 12:16:11.8662Z" -> "2022-08-17"
+        let date = PayoutOrd::display_date(date, timezone);
+        let Some(date) = date
+            .get(..10)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        else {
+            continue;
+        };
+        let label = period.label(date);
+        let row = map.entry(label.clone()).or_insert_with(|| Row::new(label));
+        row.payouts += 1;
+        row.xmr = row.xmr.add_self(*atomic_unit);
+    }
+    for day in history {
+        let label = period.label(day.date);
+        let row = map.entry(label.clone()).or_insert_with(|| Row::new(label));
+        let total = row.uptime + day.uptime;
+        if total != 0 {
+            row.hashrate = (row.hashrate * row.uptime as f64 + day.hashrate * day.uptime as f64)
+                / total as f64;
+        }
+        row.uptime = total;
+    }
+    map.into_values().rev().collect()
+}
+
+//---------------------------------------------------------------------------------------------------- Recent payouts
+// How many payouts the [Recent] view lists.
+pub const RECENT_PAYOUTS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Payout {
+    pub time: String, // Time of day in [timezone], e.g: "12:16:11.8662 UTC"
+    pub xmr: AtomicUnit,
+    pub height: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Day {
+    pub date: String,      // "2022-08-17"
+    pub total: AtomicUnit, // Sum of [payouts], not of all payouts that day
+    pub payouts: Vec<Payout>,
+}
+
+// The last [n] payouts grouped by their date in [timezone], newest first.
+pub fn recent(payouts: &PayoutOrd, timezone: PayoutTimezone, n: usize) -> Vec<Day> {
+    let mut days: Vec<Day> = vec![];
+    for (date, atomic_unit, block) in payouts
+        .rev_iter()
+        .filter(|(date, _, _)| !date.starts_with('?'))
+        .take(n)
+    {
+        // "2022-08-17 12:16:11.8662 UTC" -> ("2022-08-17", "12:16:11.8662 UTC")
+        let date = PayoutOrd::display_date(date, timezone);
+        let Some((date, time)) = date.split_once(' ') else {
+            continue;
+        };
+        let payout = Payout {
+            time: time.to_string(),
+            xmr: *atomic_unit,
+            height: block.to_string().replace(',', "").parse().unwrap_or(0),
+        };
+        match days.last_mut() {
+            Some(day) if day.date == date => {
+                day.total = day.total.add_self(payout.xmr);
+                day.payouts.push(payout);
+            }
+            _ => days.push(Day {
+                date: date.to_string(),
+                total: payout.xmr,
+                payouts: vec![payout],
+            }),
+        }
+    }
+    days
+}
+
+// Fixed-width text table for the [Status] tab.
+pub fn to_text(rows: &[Row]) -> String {
+    if rows.is_empty() {
+        return "No payouts or mining history yet".to_string();
+    }
+    let cells: Vec<[String; 5]> = rows.iter().map(|row| row.cells(true)).collect();
+    let mut width = HEADER.map(str::len);
+    for row in &cells {
+        for (w, cell) in width.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let mut string = String::new();
+    let line = |string: &mut String, row: &[&str]| {
+        let row: Vec<String> = row
+            .iter()
+            .zip(width)
+            .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+            .collect();
+        let _ = writeln!(string, "{}", row.join(" | "));
+    };
+    line(&mut string, &HEADER);
+    for row in &cells {
+        line(&mut string, &row.each_ref().map(String::as_str));
+    }
+    string
+}
+
+pub fn to_markdown(period: Period, timezone: PayoutTimezone, rows: &[Row]) -> String {
+    let mut string = format!(
+        "# Gupax {} Report\n\nTimezone: {}\n\n",
+        period.name(),
+        timezone
+    );
+    let _ = writeln!(string, "| {} |", HEADER.join(" | "));
+    let _ = writeln!(string, "|{}", "---|".repeat(HEADER.len()));
+    for row in rows {
+        let _ = writeln!(string, "| {} |", row.cells(false).join(" | "));
+    }
+    string
+}
+
+pub fn to_html(period: Period, timezone: PayoutTimezone, rows: &[Row]) -> String {
+    let mut string = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Gupax {0} Report</title>\n<style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #888; padding: 4px 8px; }}</style>\n</head>\n<body>\n<h1>Gupax {0} Report</h1>\n<p>Timezone: {1}</p>\n<table>\n",
+        period.name(),
+        timezone
+    );
+    let _ = writeln!(
+        string,
+        "<tr>{}</tr>",
+        HEADER.map(|h| format!("<th>{}</th>", h)).concat()
+    );
+    for row in rows {
+        let _ = writeln!(
+            string,
+            "<tr>{}</tr>",
+            row.cells(false).map(|c| format!("<td>{}</td>", c)).concat()
+        );
+    }
+    string.push_str("</table>\n</body>\n</html>\n");
+    string
+}
+
+// Open a save dialog and write the report, this blocks so it runs in a thread.
+pub fn spawn_export(period: Period, timezone: PayoutTimezone, rows: Vec<Row>, html: bool) {
+    std::thread::spawn(move || {
+        let (ext, text) = if html {
+            ("html", to_html(period, timezone, &rows))
+        } else {
+            ("md", to_markdown(period, timezone, &rows))
+        };
+        let name = format!("gupax_{}_report.{}", period.name().to_lowercase(), ext);
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&name)
+            .add_filter(ext, &[ext])
+            .save_file()
+        else {
+            info!("Report | Export cancelled");
+            return;
+        };
+        match std::fs::write(&path, text) {
+            Ok(()) => info!("Report | Export to [{}] ... OK", path.display()),
+            Err(e) => error!("Report | Export to [{}] ... FAIL: {}", path.display(), e),
+        }
+    });
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn build_reports() {
+        use super::*;
+        let mut payouts = PayoutOrd::from_vec(vec![]);
+        payouts.push_raw("2024-04-30 12:00:00.0000Z", 1_000_000_000, 1);
+        payouts.push_raw("2024-05-01 12:00:00.0000Z", 2_000_000_000, 2);
+        payouts.push_raw("2024-05-02 12:00:00.0000Z", 3_000_000_000, 3);
+        let utc = PayoutTimezone::Utc;
+
+        let mut history = vec![];
+        let date = |d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        HistoryDay::add_sample(&mut history, date("2024-04-30"), 60, 1000.0);
+        HistoryDay::add_sample(&mut history, date("2024-04-30"), 60, 3000.0);
+        HistoryDay::add_sample(&mut history, date("2024-05-01"), 120, 4000.0);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].uptime, 120);
+        assert_eq!(history[0].hashrate, 2000.0);
+
+        // Round trip to disk.
+        let lines = HistoryDay::to_lines(&history);
+        assert_eq!(
+            lines,
+            "2024-04-30 | 120 | 2000.0\n2024-05-01 | 120 | 4000.0\n"
+        );
+        assert_eq!(HistoryDay::parse(&lines), history);
+
+        // Monthly, newest first.
+        let rows = build(Period::Month, utc, &payouts, &history);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].period, "2024-05");
+        assert_eq!(rows[0].payouts, 2);
+        assert_eq!(rows[0].xmr, AtomicUnit::from_u64(5_000_000_000));
+        assert_eq!(rows[0].uptime, 120);
+        assert_eq!(rows[1].period, "2024-04");
+        assert_eq!(rows[1].hashrate, 2000.0);
+
+        // 2024-04-30 to 2024-05-02 are all in ISO week 18.
+        let rows = build(Period::Week, utc, &payouts, &history);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].period, "2024-W18");
+        assert_eq!(rows[0].payouts, 3);
+        assert_eq!(rows[0].uptime, 240);
+        assert_eq!(rows[0].hashrate, 3000.0);
+
+        let markdown = to_markdown(Period::Week, utc, &rows);
+        assert!(markdown.contains("Timezone: UTC"));
+        assert!(markdown.contains("| 2024-W18 | 3 | 0.006000000000 XMR | 3,000 H/s | 4 minutes |"));
+        assert!(to_html(Period::Week, utc, &rows).contains("<td>2024-W18</td>"));
+        assert!(to_text(&rows).starts_with("Period   | Payouts |"));
+    }
+
+    #[test]
+    fn recent_payouts() {
+        use super::*;
+        let mut payouts = PayoutOrd::new();
+        payouts.push_raw("2024-04-30 12:00:00.0000Z", 1_000_000_000, 3_100_000);
+        payouts.push_raw("2024-05-01 11:00:00.0000Z", 2_000_000_000, 3_100_500);
+        payouts.push_raw("2024-05-01 12:00:00.0000Z", 3_000_000_000, 3_100_510);
+        let utc = PayoutTimezone::Utc;
+
+        // Newest first, the [????] placeholder is skipped.
+        let days = recent(&payouts, utc, RECENT_PAYOUTS);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-05-01");
+        assert_eq!(days[0].total, AtomicUnit::from_u64(5_000_000_000));
+        assert_eq!(days[0].payouts[0].time, "12:00:00.0000 UTC");
+        assert_eq!(days[0].payouts[0].height, 3_100_510);
+        assert_eq!(days[1].date, "2024-04-30");
+        assert_eq!(days[1].payouts.len(), 1);
+
+        // Only the last [n].
+        let days = recent(&payouts, utc, 1);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].total, AtomicUnit::from_u64(3_000_000_000));
+    }
+}
@@ -0,0 +1,113 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Template variables in the XMRig rig ID.
+//
+// The rig ID is the worker name shown on the pool (and P2Pool's side),
+// a machine image cloned to a bunch of PCs would otherwise show up as the
+// same worker everywhere. [{hostname}], [{profile}] and [{threads}] are
+// filled in right when XMRig starts, the saved setting keeps the template.
+
+use std::sync::Mutex;
+use sysinfo::{System, SystemExt};
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const HOSTNAME: &str = "{hostname}";
+pub const PROFILE: &str = "{profile}";
+pub const THREADS: &str = "{threads}";
+// The [{profile}] without [--profile].
+const NO_PROFILE: &str = "default";
+// Set by the GUI thread from [--profile].
+static CURRENT_PROFILE: Mutex<Option<String>> = Mutex::new(None);
+
+//---------------------------------------------------------------------------------------------------- Rig ID
+pub fn set_profile(profile: Option<&str>) {
+    *CURRENT_PROFILE.lock().unwrap() = profile.map(str::to_string);
+}
+
+// The rig ID XMRig is started with.
+pub fn expand(template: &str, threads: usize) -> String {
+    if !template.contains('{') {
+        return template.to_string();
+    }
+    let hostname = System::new().host_name().unwrap_or_default();
+    let profile = CURRENT_PROFILE.lock().unwrap().clone();
+    expand_with(template, &hostname, profile.as_deref(), threads)
+}
+
+fn expand_with(template: &str, hostname: &str, profile: Option<&str>, threads: usize) -> String {
+    template
+        .replace(HOSTNAME, &sanitize(hostname))
+        .replace(PROFILE, profile.unwrap_or(NO_PROFILE))
+        .replace(THREADS, &threads.to_string())
+}
+
+// Hostnames can have characters pools don't like in a worker name.
+fn sanitize(hostname: &str) -> String {
+    let hostname: String = hostname
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if hostname.is_empty() {
+        "unknown".to_string()
+    } else {
+        hostname
+    }
+}
+
+// Is [template] a valid rig ID once the variables are filled in?
+// Checked with placeholders, so the result doesn't depend on this PC's hostname.
+pub fn is_valid(template: &str) -> bool {
+    crate::regex::REGEXES
+        .name
+        .is_match(&expand_with(template, "host", Some("profile"), 1))
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn rig_template() {
+        use crate::rig::*;
+        assert_eq!(expand("my-rig", 8), "my-rig");
+        assert_eq!(
+            expand_with(
+                "{hostname}_{profile}_{threads}t",
+                "office pc",
+                Some("night"),
+                8
+            ),
+            "office-pc_night_8t"
+        );
+        assert_eq!(expand_with("{profile}", "pc", None, 1), "default");
+        assert_eq!(expand_with("{hostname}", "", None, 1), "unknown");
+        // Unknown variables are left alone and fail validation.
+        assert_eq!(expand_with("{user}", "pc", None, 1), "{user}");
+        assert!(is_valid("{hostname}-{threads}"));
+        assert!(is_valid("rig 1"));
+        assert!(!is_valid("{user}"));
+        assert!(!is_valid("{hostname}!"));
+        // Filled in with the real hostname.
+        assert!(!expand("{hostname}", 1).contains('{'));
+    }
+}
@@ -0,0 +1,544 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// [Migrate machine] on the [Gupax] tab.
+//
+// A snapshot is one TOML file holding [state.toml], [node.toml], [pool.toml],
+// the Gupax-P2Pool API files (payouts, XMR, history) and optionally the
+// path + SHA-256 of each binary. Importing it validates every part before
+// anything is written, then rewrites paths that pointed into the old
+// machine's data folder, Gupax folder or home folder to this machine's.
+// (Not to be confused with [migrate.rs], which upgrades old [state.toml]'s).
+
+use crate::disk::{
+    into_absolute_path, Node, Pool, State, GUPAX_P2POOL_API_FILE_ARRAY, GUPAX_P2POOL_API_PAYOUT,
+    GUPAX_P2POOL_API_XMR,
+};
+use crate::{constants::*, macros::*};
+use gupax_process::ProcessName;
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Bumped if the layout changes, older Gupax's refuse newer snapshots.
+pub const FORMAT: u32 = 1;
+pub const EXTENSION: &str = "toml";
+
+//---------------------------------------------------------------------------------------------------- Machine
+// The folders paths get adapted between, see [adapt()].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub os: String,
+    pub data_dir: String, // Gupax's OS data folder
+    pub exe_dir: String,  // The folder Gupax is in, relative binary paths start here
+    pub home: String,
+}
+
+impl Machine {
+    pub fn this(os_data_path: &Path) -> Self {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|dir| dir.display().to_string()))
+            .unwrap_or_default();
+        let home = dirs::home_dir()
+            .map(|home| home.display().to_string())
+            .unwrap_or_default();
+        Self {
+            os: std::env::consts::OS.to_string(),
+            data_dir: trim_dir(&os_data_path.display().to_string()),
+            exe_dir: trim_dir(&exe_dir),
+            home: trim_dir(&home),
+        }
+    }
+}
+
+// [/home/alice/.local/share/gupax/] -> [/home/alice/.local/share/gupax]
+fn trim_dir(dir: &str) -> String {
+    dir.trim_end_matches(['/', '\\']).to_string()
+}
+
+//---------------------------------------------------------------------------------------------------- Snapshot
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub format: u32,
+    pub version: String, // The Gupax that wrote it
+    pub created: String,
+    pub os: String,
+    pub data_dir: String, // The old [Machine]
+    pub exe_dir: String,
+    pub home: String,
+    pub state: String, // The files as they are on disk
+    pub node: String,
+    pub pool: String,
+    #[serde(default)]
+    pub p2pool: BTreeMap<String, String>, // Gupax-P2Pool API file name -> contents
+    #[serde(default)]
+    pub binaries: Vec<Binary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Binary {
+    pub name: String, // [ProcessName]'s [Display]
+    pub path: String, // Absolute, on the old machine
+    pub sha256: String,
+}
+
+// The binaries a snapshot can hold, and where [state] says they are.
+fn binary_paths(state: &State) -> [(ProcessName, PathBuf); 3] {
+    let absolute =
+        |path: &str| into_absolute_path(path.to_string()).unwrap_or_else(|_| PathBuf::from(path));
+    [
+        (ProcessName::P2pool, absolute(&state.gupax.p2pool_path)),
+        (ProcessName::Xmrig, absolute(&state.gupax.xmrig_path)),
+        (ProcessName::Proxy, state.proxy.absolute_path()),
+    ]
+}
+
+impl Snapshot {
+    // [state]/[nodes]/[pools] should be the saved ones, not unsaved edits.
+    pub fn create(
+        machine: &Machine,
+        state: &State,
+        nodes: &[(String, Node)],
+        pools: &[(String, Pool)],
+        gupax_p2pool_dir: &Path,
+        binaries: bool,
+    ) -> Result<Self, String> {
+        let mut p2pool = BTreeMap::new();
+        for file in GUPAX_P2POOL_API_FILE_ARRAY {
+            if let Ok(contents) = std::fs::read_to_string(gupax_p2pool_dir.join(file)) {
+                p2pool.insert(file.to_string(), contents);
+            }
+        }
+        let binaries = if binaries {
+            binary_paths(state)
+                .into_iter()
+                .filter(|(_, path)| path.is_file())
+                .map(|(name, path)| {
+                    Ok(Binary {
+                        name: name.to_string(),
+                        sha256: crate::integrity::sha256(&path).map_err(|e| e.to_string())?,
+                        path: path.display().to_string(),
+                    })
+                })
+                .collect::<Result<_, String>>()?
+        } else {
+            vec![]
+        };
+        Ok(Self {
+            format: FORMAT,
+            version: GUPAX_VERSION.to_string(),
+            created: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            os: machine.os.clone(),
+            data_dir: machine.data_dir.clone(),
+            exe_dir: machine.exe_dir.clone(),
+            home: machine.home.clone(),
+            state: state.to_string().map_err(|e| e.to_string())?,
+            node: Node::to_string(nodes).map_err(|e| e.to_string())?,
+            pool: Pool::to_string(pools).map_err(|e| e.to_string())?,
+            p2pool,
+            binaries,
+        })
+    }
+
+    pub fn from_str(string: &str) -> Result<Self, String> {
+        toml::from_str(string).map_err(|e| format!("Not a Gupax snapshot: {}", e))
+    }
+
+    pub fn to_string(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    // The old folder -> new folder pairs, longest first so the data folder
+    // wins over the home folder it's usually in.
+    fn prefixes(&self, machine: &Machine) -> Vec<(String, String)> {
+        let mut prefixes: Vec<(String, String)> = [
+            (&self.data_dir, &machine.data_dir),
+            (&self.exe_dir, &machine.exe_dir),
+            (&self.home, &machine.home),
+        ]
+        .into_iter()
+        .map(|(old, new)| (trim_dir(old), trim_dir(new)))
+        .filter(|(old, new)| !old.is_empty() && !new.is_empty() && old != new)
+        .collect();
+        prefixes.sort_by_key(|(old, _)| std::cmp::Reverse(old.len()));
+        prefixes
+    }
+
+    // Validate everything and adapt it to [machine], nothing is written yet.
+    pub fn import(self, machine: &Machine) -> Result<Import, String> {
+        if self.format == 0 || self.format > FORMAT {
+            return Err(format!(
+                "This snapshot is format [{}] from Gupax {}, this Gupax only reads up to [{}], update it first",
+                self.format, self.version, FORMAT
+            ));
+        }
+        let (mut state, _) =
+            crate::tomledit::validate(&self.state).map_err(|e| format!("[state.toml] {}", e))?;
+        let nodes = Node::from_str_to_vec(&self.node).map_err(|e| format!("[node.toml] {}", e))?;
+        let pools = Pool::from_str_to_vec(&self.pool).map_err(|e| format!("[pool.toml] {}", e))?;
+        for (file, contents) in &self.p2pool {
+            if !GUPAX_P2POOL_API_FILE_ARRAY.contains(&file.as_str()) {
+                return Err(format!("Unknown payout file [{}]", file));
+            }
+            let number = file == GUPAX_P2POOL_API_PAYOUT || file == GUPAX_P2POOL_API_XMR;
+            if number && contents.trim().parse::<u64>().is_err() {
+                return Err(format!("Payout file [{}] is not a number", file));
+            }
+        }
+
+        // Paths
+        let prefixes = self.prefixes(machine);
+        let separator = (self.os == "windows") != (machine.os == "windows");
+        let mut adapted = vec![];
+        for (name, path) in paths_mut(&mut state) {
+            if let Some(new) = adapt(path, &prefixes, separator) {
+                adapted.push(format!("{}: [{}] -> [{}]", name, path, new));
+                *path = new;
+            }
+        }
+        state.update_absolute_path().map_err(|e| e.to_string())?;
+
+        // Binaries
+        let mut warnings = vec![];
+        let mut trusted = vec![];
+        for (name, path) in binary_paths(&state) {
+            let Some(old) = self.binaries.iter().find(|b| b.name == name.to_string()) else {
+                continue;
+            };
+            match crate::integrity::sha256(&path) {
+                Ok(hash) if hash == old.sha256 => trusted.push((path, hash)),
+                Ok(_) => warnings.push(format!(
+                    "{} at [{}] is not the same binary as on the old machine",
+                    name,
+                    path.display()
+                )),
+                Err(_) => warnings.push(format!(
+                    "{} was not found at [{}], select it again",
+                    name,
+                    path.display()
+                )),
+            }
+        }
+
+        // Hooks run whatever they point to, the user should see them before applying.
+        for (kind, hook) in crate::hook::KINDS.iter().zip(state.gupax.hooks()) {
+            if !hook.is_empty() {
+                warnings.push(format!(
+                    "{} hook will run [{}], only apply this if you trust the snapshot",
+                    kind, hook
+                ));
+            }
+        }
+
+        Ok(Import {
+            from: format!("{} ({}, Gupax {})", self.created, self.os, self.version),
+            state,
+            nodes,
+            pools,
+            p2pool: self.p2pool,
+            adapted,
+            warnings,
+            trusted,
+        })
+    }
+}
+
+// Every setting that holds a path.
+fn paths_mut(state: &mut State) -> [(&'static str, &mut String); 12] {
+    [
+        ("P2Pool binary", &mut state.gupax.p2pool_path),
+        ("XMRig binary", &mut state.gupax.xmrig_path),
+        ("XMRig-Proxy binary", &mut state.proxy.path),
+        ("P2Pool folder", &mut state.p2pool.workdir),
+        ("XMRig folder", &mut state.xmrig.workdir),
+        ("XMRig-Proxy folder", &mut state.proxy.workdir),
+        ("P2Pool API folder", &mut state.p2pool.api_dir),
+        ("SSH key", &mut state.p2pool.ssh_key),
+        ("Start hook", &mut state.gupax.hook_start),
+        ("Stop hook", &mut state.gupax.hook_stop),
+        ("Crash hook", &mut state.gupax.hook_crash),
+        ("Payout hook", &mut state.gupax.hook_payout),
+    ]
+}
+
+// Swap the first matching old folder at the start of [path] for the new one.
+// [separator] also converts [/] and [\] for a Windows <-> Unix move.
+// [None] if [path] isn't in any of the old folders.
+pub fn adapt(path: &str, prefixes: &[(String, String)], separator: bool) -> Option<String> {
+    prefixes.iter().find_map(|(old, new)| {
+        let rest = path.strip_prefix(old.as_str())?;
+        if !rest.is_empty() && !rest.starts_with(['/', '\\']) {
+            return None;
+        }
+        let rest: String = if separator {
+            rest.chars()
+                .map(|c| match c {
+                    '/' | '\\' => std::path::MAIN_SEPARATOR,
+                    c => c,
+                })
+                .collect()
+        } else {
+            rest.to_string()
+        };
+        Some(format!("{}{}", new, rest))
+    })
+}
+
+//---------------------------------------------------------------------------------------------------- Import
+// A validated snapshot, ready to replace the current setup.
+#[derive(Clone, Debug)]
+pub struct Import {
+    pub from: String,
+    pub state: State,
+    pub nodes: Vec<(String, Node)>,
+    pub pools: Vec<(String, Pool)>,
+    pub p2pool: BTreeMap<String, String>,
+    pub adapted: Vec<String>, // Paths that were rewritten, shown before [Apply]
+    pub warnings: Vec<String>, // Binaries that are missing or different
+    pub trusted: Vec<(PathBuf, String)>, // Binaries with the old hash, trusted on [Apply]
+}
+
+//---------------------------------------------------------------------------------------------------- Migrate
+// The [Gupax] tab's [Migrate machine] section.
+#[derive(Debug, Default)]
+pub struct Migrate {
+    pub binaries: bool, // Include the binaries' paths/hashes in an export
+    pub thread: bool,   // Is a file dialog open?
+    pub msg: String,
+    pub preview: Option<Import>, // Validated, waiting for [Apply]
+    pub pending: Option<Import>, // [Apply] was clicked, taken by the GUI thread
+}
+
+impl Migrate {
+    pub fn new() -> Arc<Mutex<Self>> {
+        arc_mut!(Self::default())
+    }
+}
+
+// Pick where to save the snapshot and write it, this blocks so it runs in a thread.
+pub fn spawn_export(
+    migrate: &Arc<Mutex<Migrate>>,
+    machine: Machine,
+    state: State,
+    nodes: Vec<(String, Node)>,
+    pools: Vec<(String, Pool)>,
+    gupax_p2pool_dir: PathBuf,
+) {
+    let migrate = Arc::clone(migrate);
+    let binaries = {
+        let mut migrate = lock!(migrate);
+        migrate.thread = true;
+        migrate.binaries
+    };
+    std::thread::spawn(move || {
+        let name = format!(
+            "gupax_snapshot_{}.{}",
+            chrono::Local::now().format("%Y-%m-%d"),
+            EXTENSION
+        );
+        let msg = match rfd::FileDialog::new()
+            .set_title("Save Gupax snapshot")
+            .set_file_name(&name)
+            .add_filter(EXTENSION, &[EXTENSION])
+            .save_file()
+        {
+            None => {
+                info!("Snapshot | Export cancelled");
+                String::new()
+            }
+            Some(path) => {
+                let result = Snapshot::create(
+                    &machine,
+                    &state,
+                    &nodes,
+                    &pools,
+                    &gupax_p2pool_dir,
+                    binaries,
+                )
+                .and_then(|snapshot| snapshot.to_string().map_err(|e| e.to_string()))
+                .and_then(|string| std::fs::write(&path, string).map_err(|e| e.to_string()));
+                match result {
+                    Ok(()) => {
+                        info!("Snapshot | Export to [{}] ... OK", path.display());
+                        format!("Exported to [{}]", path.display())
+                    }
+                    Err(e) => {
+                        error!("Snapshot | Export to [{}] ... FAIL: {}", path.display(), e);
+                        format!("Export failed: {}", e)
+                    }
+                }
+            }
+        };
+        let mut migrate = lock!(migrate);
+        migrate.msg = msg;
+        migrate.thread = false;
+    });
+}
+
+// Pick a snapshot and validate it into [Migrate::preview], nothing is applied yet.
+pub fn spawn_import(migrate: &Arc<Mutex<Migrate>>, machine: Machine) {
+    let migrate = Arc::clone(migrate);
+    lock!(migrate).thread = true;
+    std::thread::spawn(move || {
+        let picked = rfd::FileDialog::new()
+            .set_title("Select a Gupax snapshot to import")
+            .add_filter(EXTENSION, &[EXTENSION])
+            .pick_file();
+        let result = match &picked {
+            None => {
+                info!("Snapshot | Import cancelled");
+                Err(String::new())
+            }
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|string| Snapshot::from_str(&string))
+                .and_then(|snapshot| snapshot.import(&machine)),
+        };
+        let mut migrate = lock!(migrate);
+        match result {
+            Ok(import) => {
+                info!("Snapshot | Import from [{}] ... OK", import.from);
+                migrate.msg = format!("Snapshot from {}, check it and click [Apply]", import.from);
+                migrate.preview = Some(import);
+            }
+            Err(e) => {
+                if !e.is_empty() {
+                    warn!("Snapshot | Import ... FAIL: {}", e);
+                }
+                migrate.msg = e;
+                migrate.preview = None;
+            }
+        }
+        migrate.thread = false;
+    });
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn adapt_paths() {
+        use super::*;
+        let prefixes = vec![
+            (
+                "/home/alice/.local/share/gupax".to_string(),
+                "/home/bob/.local/share/gupax".to_string(),
+            ),
+            ("/home/alice".to_string(), "/home/bob".to_string()),
+        ];
+        assert_eq!(
+            adapt("/home/alice/xmrig/xmrig", &prefixes, false).unwrap(),
+            "/home/bob/xmrig/xmrig"
+        );
+        assert_eq!(
+            adapt("/home/alice/.local/share/gupax/p2pool", &prefixes, false).unwrap(),
+            "/home/bob/.local/share/gupax/p2pool"
+        );
+        assert_eq!(adapt("/home/alice", &prefixes, false).unwrap(), "/home/bob");
+        // Only whole folder names match, relative paths stay as they are.
+        assert!(adapt("/home/alice2/xmrig", &prefixes, false).is_none());
+        assert!(adapt("xmrig/xmrig", &prefixes, false).is_none());
+        assert!(adapt("", &prefixes, false).is_none());
+
+        // Windows -> here.
+        let prefixes = vec![(r"C:\Users\Alice".to_string(), "/home/bob".to_string())];
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            adapt(r"C:\Users\Alice\XMRig\xmrig.exe", &prefixes, true).unwrap(),
+            format!("/home/bob{sep}XMRig{sep}xmrig.exe")
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        use super::*;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let p2pool = dir.join("p2pool");
+        std::fs::create_dir_all(&p2pool).unwrap();
+        std::fs::write(p2pool.join(GUPAX_P2POOL_API_PAYOUT), "3").unwrap();
+        std::fs::write(p2pool.join(GUPAX_P2POOL_API_XMR), "1000").unwrap();
+        let binary = dir.join("xmrig");
+        std::fs::write(&binary, b"xmrig").unwrap();
+
+        let old = Machine {
+            os: "linux".to_string(),
+            data_dir: dir.display().to_string(),
+            exe_dir: "/opt/gupax".to_string(),
+            home: "/home/alice".to_string(),
+        };
+        let mut state = State::new();
+        state.gupax.xmrig_path = binary.display().to_string();
+        state.p2pool.workdir = "/home/alice/p2pool".to_string();
+        state.gupax.hook_payout = "/opt/gupax/hooks/payout.sh".to_string();
+        let nodes = Node::new_vec();
+        let pools = Pool::new_vec();
+
+        let snapshot = Snapshot::create(&old, &state, &nodes, &pools, &p2pool, true).unwrap();
+        assert_eq!(snapshot.format, FORMAT);
+        assert_eq!(snapshot.p2pool.len(), 2);
+        assert_eq!(snapshot.binaries.len(), 1);
+        assert_eq!(snapshot.binaries[0].name, "XMRig");
+        let string = snapshot.to_string().unwrap();
+
+        // Same data folder, new home and Gupax folder.
+        let new = Machine {
+            exe_dir: "/usr/local/gupax".to_string(),
+            home: "/home/bob".to_string(),
+            ..old.clone()
+        };
+        let import = Snapshot::from_str(&string).unwrap().import(&new).unwrap();
+        assert_eq!(import.state.gupax.xmrig_path, binary.display().to_string());
+        assert_eq!(import.state.p2pool.workdir, "/home/bob/p2pool");
+        assert_eq!(
+            import.state.gupax.hook_payout,
+            "/usr/local/gupax/hooks/payout.sh"
+        );
+        assert_eq!(import.adapted.len(), 2);
+        assert_eq!(import.nodes.len(), nodes.len());
+        assert_eq!(import.pools.len(), pools.len());
+        assert_eq!(import.p2pool[GUPAX_P2POOL_API_XMR], "1000");
+        // Hooks run programs, they're always shown before [Apply].
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("/usr/local/gupax/hooks/payout.sh"));
+        assert_eq!(import.trusted.len(), 1);
+
+        // A changed binary is a warning, not an error.
+        std::fs::write(&binary, b"not xmrig").unwrap();
+        let import = Snapshot::from_str(&string).unwrap().import(&new).unwrap();
+        assert_eq!(import.warnings.len(), 2);
+        assert!(import.trusted.is_empty());
+
+        // Broken parts are refused before anything is written.
+        let mut broken = Snapshot::from_str(&string).unwrap();
+        broken
+            .p2pool
+            .insert(GUPAX_P2POOL_API_PAYOUT.to_string(), "three".to_string());
+        assert!(broken.import(&new).is_err());
+        let mut broken = Snapshot::from_str(&string).unwrap();
+        broken.format = FORMAT + 1;
+        assert!(broken.import(&new).is_err());
+        let mut broken = Snapshot::from_str(&string).unwrap();
+        broken.node = "[broken".to_string();
+        assert!(broken.import(&new).is_err());
+        assert!(Snapshot::from_str("[gupax]\nsimple = true\n").is_err());
+    }
+}
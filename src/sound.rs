@@ -0,0 +1,141 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Payout sound notification.
+//
+// The actual audio output is behind the [sound] feature since it
+// needs ALSA on Linux. The chime is generated (2 sine wave notes)
+// so there's no audio file to embed or decode.
+
+use crate::macros::*;
+use chrono::Timelike;
+use log::*;
+use std::sync::Mutex;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Was Gupax built with audio output?
+pub const SUPPORTED: bool = cfg!(feature = "sound");
+
+// The payout sound settings, payouts are found in the P2Pool PTY thread, not the GUI.
+struct Settings {
+    enabled: bool,
+    volume: u8,
+    quiet: Option<(u8, u8)>,
+}
+
+static SETTINGS: Mutex<Settings> = Mutex::new(Settings {
+    enabled: false,
+    volume: 50,
+    quiet: None,
+});
+
+//---------------------------------------------------------------------------------------------------- Config
+// Set the payout sound settings from [Gupax].
+pub fn set(state: &crate::disk::Gupax) {
+    let mut lock = lock!(SETTINGS);
+    lock.enabled = state.payout_sound;
+    lock.volume = state.payout_volume;
+    lock.quiet = state
+        .quiet_hours
+        .then_some((state.quiet_start, state.quiet_end));
+}
+
+//---------------------------------------------------------------------------------------------------- Sound
+// Is [hour] within the quiet hours [start..end]?
+// This wraps around midnight, e.g: [22 -> 8].
+pub const fn is_quiet_hour(hour: u8, start: u8, end: u8) -> bool {
+    if start <= end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+// Play the payout chime if enabled and not within quiet hours.
+pub fn payout() {
+    let (enabled, volume, quiet) = {
+        let lock = lock!(SETTINGS);
+        (lock.enabled, lock.volume, lock.quiet)
+    };
+    if !enabled {
+        return;
+    }
+    let hour = chrono::Local::now().hour() as u8;
+    if quiet.is_some_and(|(start, end)| is_quiet_hour(hour, start, end)) {
+        info!("Sound | Payout during quiet hours, skipping sound");
+        return;
+    }
+    play(volume);
+}
+
+// Play the chime in a separate thread, audio output blocks until done.
+// [volume] is [0..=100].
+pub fn play(volume: u8) {
+    #[cfg(feature = "sound")]
+    std::thread::spawn(move || {
+        if let Err(e) = chime(volume) {
+            warn!("Sound | Could not play payout sound: {}", e);
+        }
+    });
+    #[cfg(not(feature = "sound"))]
+    debug!(
+        "Sound | Built without [sound], skipping payout sound ({}%)",
+        volume
+    );
+}
+
+#[cfg(feature = "sound")]
+fn chime(volume: u8) -> anyhow::Result<()> {
+    use rodio::{source::SineWave, OutputStream, Sink, Source};
+    use std::time::Duration;
+
+    let (_stream, handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&handle)?;
+    sink.set_volume(f32::from(volume) / 100.0);
+    // [B5 -> E6], sounds like picking up a coin.
+    for freq in [987.77, 1318.51] {
+        sink.append(
+            SineWave::new(freq)
+                .take_duration(Duration::from_millis(120))
+                .fade_in(Duration::from_millis(5))
+                .amplify(0.3),
+        );
+    }
+    sink.sleep_until_end();
+    Ok(())
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn quiet_hours() {
+        use super::is_quiet_hour;
+        // Same day.
+        assert!(is_quiet_hour(13, 12, 14));
+        assert!(!is_quiet_hour(14, 12, 14));
+        assert!(!is_quiet_hour(11, 12, 14));
+        // Wraps around midnight.
+        assert!(is_quiet_hour(23, 22, 8));
+        assert!(is_quiet_hour(0, 22, 8));
+        assert!(is_quiet_hour(7, 22, 8));
+        assert!(!is_quiet_hour(8, 22, 8));
+        assert!(!is_quiet_hour(21, 22, 8));
+        // Empty range.
+        assert!(!is_quiet_hour(5, 5, 5));
+    }
+}
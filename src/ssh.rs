@@ -0,0 +1,403 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// SSH tunnel to a remote node.
+//
+// For a trusted node that only exposes SSH, this opens local forwards
+// ([127.0.0.1:<random>] -> [<node>:<RPC/ZMQ>]) over an SSH session
+// with key auth, and P2Pool is pointed at the local ends instead.
+// The local listeners stay up for as long as the tunnel is enabled,
+// only the SSH session is re-established if it drops, so P2Pool's
+// ports never change under it.
+
+use crate::macros::*;
+use log::*;
+use russh::client::{self, Handle};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+const TIMEOUT: Duration = Duration::from_secs(15);
+const KEEPALIVE: Duration = Duration::from_secs(30);
+// How often the listeners check if they should stop.
+const STOP_CHECK: Duration = Duration::from_secs(1);
+// Reconnect backoff.
+const RETRY_MIN: Duration = Duration::from_secs(5);
+const RETRY_MAX: Duration = Duration::from_secs(120);
+pub const DEFAULT_KEY: &str = "~/.ssh/id_ed25519";
+
+//---------------------------------------------------------------------------------------------------- TunnelConfig
+// What to connect to, built from [P2pool::ssh_config()].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TunnelConfig {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub key: PathBuf,
+    pub host_key: String, // A trusted SHA-256 host key fingerprint, on top of [~/.ssh/known_hosts]
+    pub rpc: u16,         // The node's ports on the remote host
+    pub zmq: u16,
+}
+
+// [~/...] -> [$HOME/...], empty is [DEFAULT_KEY].
+pub fn key_path(path: &str) -> PathBuf {
+    let path = match path.trim() {
+        "" => DEFAULT_KEY,
+        path => path,
+    };
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Tunnel
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TunnelStatus {
+    #[default]
+    Off,
+    Connecting,
+    Connected,
+    Retrying, // Lost or failed, waiting to reconnect
+}
+
+#[derive(Debug, Default)]
+pub struct Tunnel {
+    pub status: TunnelStatus,
+    pub config: Option<TunnelConfig>, // What it was last started with
+    pub local_rpc: u16,
+    pub local_zmq: u16,
+    pub connections: usize, // Currently forwarded connections
+    pub error: String,      // The last connection error, empty if none
+    pub host_key: String,   // The fingerprint the server last presented
+    pub unknown_host: bool, // ...and it wasn't trusted
+    stop: bool,
+}
+
+impl Tunnel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn running(&self) -> bool {
+        self.status != TunnelStatus::Off
+    }
+
+    // Ask the tunnel to stop, forwarded connections are dropped with the session.
+    pub fn stop(tunnel: &Arc<Mutex<Self>>) {
+        let mut tunnel = lock!(tunnel);
+        if tunnel.running() {
+            info!("SSH | Stopping tunnel");
+            tunnel.stop = true;
+        }
+    }
+
+    // The local listeners are bound here (not in the thread)
+    // so the ports are known before P2Pool gets started.
+    #[cold]
+    #[inline(never)]
+    pub fn start(tunnel: &Arc<Mutex<Self>>, config: TunnelConfig) {
+        let bind = || -> std::io::Result<(TcpListener, TcpListener)> {
+            let rpc = TcpListener::bind("127.0.0.1:0")?;
+            let zmq = TcpListener::bind("127.0.0.1:0")?;
+            rpc.set_nonblocking(true)?;
+            zmq.set_nonblocking(true)?;
+            Ok((rpc, zmq))
+        };
+        let mut guard = lock!(tunnel);
+        // Only ever 1 session, the old one has to finish stopping first.
+        if guard.running() {
+            warn!("SSH | Tunnel is still running, not starting another");
+            return;
+        }
+        guard.config = Some(config.clone());
+        guard.error.clear();
+        guard.unknown_host = false;
+        let (rpc, zmq) = match bind() {
+            Ok(listeners) => listeners,
+            Err(e) => {
+                error!("SSH | Binding local ports ... FAIL: {}", e);
+                guard.error = e.to_string();
+                return;
+            }
+        };
+        guard.local_rpc = rpc.local_addr().map_or(0, |a| a.port());
+        guard.local_zmq = zmq.local_addr().map_or(0, |a| a.port());
+        guard.status = TunnelStatus::Connecting;
+        guard.stop = false;
+        info!(
+            "SSH | Forwarding [127.0.0.1:{}, 127.0.0.1:{}] -> [{}:{}, {}:{}]",
+            guard.local_rpc, guard.local_zmq, config.host, config.rpc, config.host, config.zmq
+        );
+        drop(guard);
+        let tunnel = Arc::clone(tunnel);
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build();
+            match runtime {
+                Ok(runtime) => runtime.block_on(run(&tunnel, &config, rpc, zmq)),
+                Err(e) => lock!(tunnel).error = e.to_string(),
+            }
+            let mut tunnel = lock!(tunnel);
+            tunnel.status = TunnelStatus::Off;
+            tunnel.config = None;
+            tunnel.stop = false;
+            info!("SSH | Tunnel stopped");
+        });
+    }
+}
+
+fn stopping(tunnel: &Arc<Mutex<Tunnel>>) -> bool {
+    lock!(tunnel).stop
+}
+
+async fn run(
+    tunnel: &Arc<Mutex<Tunnel>>,
+    config: &TunnelConfig,
+    rpc: TcpListener,
+    zmq: TcpListener,
+) {
+    let listeners = (
+        tokio::net::TcpListener::from_std(rpc),
+        tokio::net::TcpListener::from_std(zmq),
+    );
+    let (rpc, zmq) = match listeners {
+        (Ok(rpc), Ok(zmq)) => (rpc, zmq),
+        (Err(e), _) | (_, Err(e)) => {
+            lock!(tunnel).error = e.to_string();
+            return;
+        }
+    };
+    let mut wait = RETRY_MIN;
+    while !stopping(tunnel) {
+        lock!(tunnel).status = TunnelStatus::Connecting;
+        match connect(tunnel, config).await {
+            Ok(session) => {
+                info!("SSH | Connected to [{}:{}]", config.host, config.port);
+                wait = RETRY_MIN;
+                {
+                    let mut guard = lock!(tunnel);
+                    guard.status = TunnelStatus::Connected;
+                    guard.error.clear();
+                }
+                let session = Arc::new(session);
+                serve(tunnel, config, &session, &rpc, &zmq).await;
+                let _ = session
+                    .disconnect(russh::Disconnect::ByApplication, "", "en")
+                    .await;
+                if !stopping(tunnel) {
+                    warn!("SSH | Lost connection to [{}:{}]", config.host, config.port);
+                    lock!(tunnel).error = "Connection lost".to_string();
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "SSH | Connecting to [{}:{}] ... FAIL: {}",
+                    config.host, config.port, e
+                );
+                let mut guard = lock!(tunnel);
+                // [check_server_key()] already explained this one.
+                if !guard.unknown_host {
+                    guard.error = e.to_string();
+                }
+            }
+        }
+        lock!(tunnel).status = TunnelStatus::Retrying;
+        let mut waited = Duration::ZERO;
+        while waited < wait && !stopping(tunnel) {
+            tokio::time::sleep(STOP_CHECK).await;
+            waited += STOP_CHECK;
+        }
+        wait = (wait * 2).min(RETRY_MAX);
+    }
+}
+
+async fn connect(
+    tunnel: &Arc<Mutex<Tunnel>>,
+    config: &TunnelConfig,
+) -> anyhow::Result<Handle<Client>> {
+    let key = russh_keys::load_secret_key(&config.key, None)
+        .map_err(|e| anyhow::anyhow!("SSH key [{}]: {}", config.key.display(), e))?;
+    let ssh = Arc::new(client::Config {
+        keepalive_interval: Some(KEEPALIVE),
+        ..Default::default()
+    });
+    let client = Client {
+        tunnel: Arc::clone(tunnel),
+        host: config.host.clone(),
+        port: config.port,
+        trusted: config.host_key.clone(),
+    };
+    let address = (config.host.as_str(), config.port);
+    let mut session = tokio::time::timeout(TIMEOUT, client::connect(ssh, address, client))
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out"))??;
+    if !session
+        .authenticate_publickey(&config.user, Arc::new(key))
+        .await?
+    {
+        anyhow::bail!("The server rejected the key for [{}]", config.user);
+    }
+    Ok(session)
+}
+
+// Accept on both local ports and forward each connection over its own SSH channel.
+async fn serve(
+    tunnel: &Arc<Mutex<Tunnel>>,
+    config: &TunnelConfig,
+    session: &Arc<Handle<Client>>,
+    rpc: &tokio::net::TcpListener,
+    zmq: &tokio::net::TcpListener,
+) {
+    while !stopping(tunnel) && !session.is_closed() {
+        let (accepted, remote) = tokio::select! {
+            accepted = rpc.accept() => (accepted, config.rpc),
+            accepted = zmq.accept() => (accepted, config.zmq),
+            _ = tokio::time::sleep(STOP_CHECK) => continue,
+        };
+        let Ok((stream, addr)) = accepted else {
+            continue;
+        };
+        let session = Arc::clone(session);
+        let tunnel = Arc::clone(tunnel);
+        tokio::spawn(async move {
+            lock!(tunnel).connections += 1;
+            if let Err(e) = forward(&session, stream, addr, remote).await {
+                debug!("SSH | Forward to [{}] closed: {}", remote, e);
+            }
+            lock!(tunnel).connections -= 1;
+        });
+    }
+}
+
+async fn forward(
+    session: &Handle<Client>,
+    mut stream: tokio::net::TcpStream,
+    addr: std::net::SocketAddr,
+    remote: u16,
+) -> anyhow::Result<()> {
+    let channel = session
+        .channel_open_direct_tcpip(
+            "127.0.0.1",
+            remote.into(),
+            addr.ip().to_string(),
+            addr.port().into(),
+        )
+        .await?;
+    let mut channel = channel.into_stream();
+    tokio::io::copy_bidirectional(&mut stream, &mut channel).await?;
+    Ok(())
+}
+
+//---------------------------------------------------------------------------------------------------- Client
+// Accepts the server if it's in [~/.ssh/known_hosts] or matches the trusted fingerprint.
+struct Client {
+    tunnel: Arc<Mutex<Tunnel>>,
+    host: String,
+    port: u16,
+    trusted: String,
+}
+
+#[async_trait::async_trait]
+impl client::Handler for Client {
+    type Error = russh::Error;
+
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &russh_keys::key::PublicKey,
+    ) -> Result<bool, Self::Error> {
+        let fingerprint = format!("SHA256:{}", server_public_key.fingerprint());
+        let known = russh_keys::check_known_hosts(&self.host, self.port, server_public_key);
+        let mut tunnel = lock!(self.tunnel);
+        tunnel.host_key.clone_from(&fingerprint);
+        let (ok, error) = match known {
+            Ok(true) => (true, String::new()),
+            // Changed keys aren't something to click through.
+            Err(russh_keys::Error::KeyChanged { line }) => (
+                false,
+                format!(
+                    "Host key changed! (~/.ssh/known_hosts line {}) [{}]",
+                    line, fingerprint
+                ),
+            ),
+            _ if fingerprint == self.trusted => (true, String::new()),
+            _ => (false, format!("Unknown host key [{}]", fingerprint)),
+        };
+        tunnel.unknown_host = !ok;
+        if !ok {
+            warn!("SSH | {}", error);
+            tunnel.error = error;
+        }
+        Ok(ok)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn key_path() {
+        use super::key_path;
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(key_path(""), home.join(".ssh/id_ed25519"));
+        assert_eq!(key_path(" ~/keys/node "), home.join("keys/node"));
+        assert_eq!(key_path("/etc/key"), std::path::PathBuf::from("/etc/key"));
+    }
+
+    #[test]
+    fn one_session() {
+        use super::*;
+        let tunnel = Arc::new(Mutex::new(Tunnel::new()));
+        // Nothing listens on port 1, so the session just keeps retrying.
+        let config = |host: &str| TunnelConfig {
+            host: host.to_string(),
+            port: 1,
+            user: "gupax".to_string(),
+            key: PathBuf::from("/nonexistent"),
+            host_key: String::new(),
+            rpc: 18081,
+            zmq: 18083,
+        };
+        let host = |tunnel: &Arc<Mutex<Tunnel>>| lock!(tunnel).config.clone().unwrap().host;
+        let stop = |tunnel: &Arc<Mutex<Tunnel>>| {
+            Tunnel::stop(tunnel);
+            for _ in 0..100 {
+                if !lock!(tunnel).running() {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            panic!("tunnel didn't stop");
+        };
+
+        Tunnel::start(&tunnel, config("127.0.0.1"));
+        assert!(lock!(tunnel).running());
+        // Ignored while the first one runs.
+        Tunnel::start(&tunnel, config("127.0.0.2"));
+        assert_eq!(host(&tunnel), "127.0.0.1");
+        stop(&tunnel);
+        assert_eq!(lock!(tunnel).config, None);
+
+        // Once it's [Off], a new one can start.
+        Tunnel::start(&tunnel, config("127.0.0.2"));
+        assert_eq!(host(&tunnel), "127.0.0.2");
+        stop(&tunnel);
+    }
+}
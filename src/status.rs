@@ -16,12 +16,21 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    constants::*, human::HumanNumber, macros::*, Benchmark, GupaxP2poolApi, Hash, ImgP2pool,
-    ImgXmrig, PayoutView, PubP2poolApi, PubXmrigApi, Submenu, Sys,
+    ansi::Console,
+    constants::*,
+    event::{self, Kind},
+    human::{localize, HumanNumber},
+    macros::*,
+    mode::MiningMode,
+    report,
+    xmr::PayoutOrd,
+    Benchmark, BenchmarkSort, BenchmarkView, ConsoleLayout, GupaxP2poolApi, Hash, ImgP2pool,
+    ImgXmrig, PayoutTimezone, PayoutView, Process, ProcessName, ProcessSignal, ProcessState,
+    PubP2poolApi, PubXmrigApi, StatusGroup, Submenu, Sys,
 };
 use egui::{
-    Hyperlink, Label, ProgressBar, RichText, SelectableLabel, Slider, Spinner, TextEdit, TextStyle,
-    TextStyle::Name,
+    Button, Checkbox, Hyperlink, Label, ProgressBar, RichText, SelectableLabel, Slider, Spinner,
+    TextEdit, TextStyle, TextStyle::Name,
 };
 use log::*;
 use std::sync::{Arc, Mutex};
@@ -35,11 +44,23 @@ impl crate::disk::Status {
         xmrig_api: &Arc<Mutex<PubXmrigApi>>,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         xmrig_img: &Arc<Mutex<ImgXmrig>>,
+        xmrig_instances: &Arc<Mutex<Vec<crate::helper::ExtraXmrig>>>,
+        pool_hashrate: &Arc<Mutex<crate::poolstats::PoolHashrate>>,
+        block_hash: &Arc<Mutex<crate::node::BlockHash>>,
         p2pool_alive: bool,
         xmrig_alive: bool,
+        cards: [ProcessCard; 2],
+        signal: &mut Option<(ProcessName, ProcessSignal)>,
         max_threads: usize,
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+        p2pool: &mut crate::disk::P2pool,
+        xmrig: &mut crate::disk::Xmrig,
+        mining_mode: &mut Option<MiningMode>,
         benchmarks: &[Benchmark],
+        benchmark_view: &mut BenchmarkView,
+        p2pool_console: &mut Console,
+        xmrig_console: &mut Console,
+        colors: bool,
         width: f32,
         height: f32,
         _ctx: &egui::Context,
@@ -47,718 +68,1509 @@ impl crate::disk::Status {
     ) {
         //---------------------------------------------------------------------------------------------------- [Processes]
         if self.submenu == Submenu::Processes {
-            let width = (width / 3.0) - (SPACE * 1.666);
-            let min_height = height - SPACE;
-            let height = height / 25.0;
-            ui.horizontal(|ui| {
-                // [Gupax]
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        debug!("Status Tab | Rendering [Gupax]");
-                        ui.set_min_height(min_height);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("[Gupax]")
-                                    .color(LIGHT_GRAY)
-                                    .text_style(TextStyle::Name("MonospaceLarge".into())),
-                            ),
-                        )
-                        .on_hover_text("Gupax is online");
-                        let sys = lock!(sys);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Uptime").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_UPTIME);
-                        ui.add_sized([width, height], Label::new(sys.gupax_uptime.to_string()));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Gupax CPU").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_CPU_USAGE);
-                        ui.add_sized([width, height], Label::new(sys.gupax_cpu_usage.to_string()));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Gupax Memory").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_MEMORY_USAGE);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(sys.gupax_memory_used_mb.to_string()),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("System CPU").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_USAGE);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(sys.system_cpu_usage.to_string()),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("System Memory").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_SYSTEM_MEMORY);
-                        ui.add_sized([width, height], Label::new(sys.system_memory.to_string()));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("System CPU Model").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_MODEL);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(sys.system_cpu_model.to_string()),
-                        );
-                        drop(sys);
-                    })
-                });
-                // [P2Pool]
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        debug!("Status Tab | Rendering [P2Pool]");
-                        ui.set_enabled(p2pool_alive);
-                        ui.set_min_height(min_height);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("[P2Pool]")
-                                    .color(LIGHT_GRAY)
-                                    .text_style(TextStyle::Name("MonospaceLarge".into())),
-                            ),
-                        )
-                        .on_hover_text("P2Pool is online")
-                        .on_disabled_hover_text("P2Pool is offline");
-                        ui.style_mut().override_text_style = Some(Name("MonospaceSmall".into()));
-                        let height = height / 1.4;
-                        let api = lock!(p2pool_api);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Uptime").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_UPTIME);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.uptime)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Shares Found").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_SHARES);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.shares_found)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Payouts").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_PAYOUTS);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!("Total: {}", api.payouts)),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[{:.7}/hour]\n[{:.7}/day]\n[{:.7}/month]",
-                                api.payouts_hour, api.payouts_day, api.payouts_month
-                            )),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("XMR Mined").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_XMR);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!("Total: {:.13} XMR", api.xmr)),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[{:.7}/hour]\n[{:.7}/day]\n[{:.7}/month]",
-                                api.xmr_hour, api.xmr_day, api.xmr_month
-                            )),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("Hashrate (15m/1h/24h)")
-                                    .underline()
-                                    .color(BONE),
-                            ),
-                        )
-                        .on_hover_text(STATUS_P2POOL_HASHRATE);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[{} H/s] [{} H/s] [{} H/s]",
-                                api.hashrate_15m, api.hashrate_1h, api.hashrate_24h
-                            )),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Miners Connected").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_CONNECTIONS);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.connections)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Effort").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_EFFORT);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[Average: {}] [Current: {}]",
-                                api.average_effort, api.current_effort
-                            )),
-                        );
-                        let img = lock!(p2pool_img);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Monero Node").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_MONERO_NODE);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[IP: {}]\n[RPC: {}] [ZMQ: {}]",
-                                &img.host, &img.rpc, &img.zmq
-                            )),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Sidechain").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_POOL);
-                        ui.add_sized([width, height], Label::new(&img.mini));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Address").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_P2POOL_ADDRESS);
-                        ui.add_sized([width, height], Label::new(&img.address));
-                        drop(img);
-                        drop(api);
-                    })
-                });
-                // [XMRig]
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        debug!("Status Tab | Rendering [XMRig]");
-                        ui.set_enabled(xmrig_alive);
-                        ui.set_min_height(min_height);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("[XMRig]")
-                                    .color(LIGHT_GRAY)
-                                    .text_style(TextStyle::Name("MonospaceLarge".into())),
-                            ),
-                        )
-                        .on_hover_text("XMRig is online")
-                        .on_disabled_hover_text("XMRig is offline");
-                        let api = lock!(xmrig_api);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Uptime").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_XMRIG_UPTIME);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.uptime)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("CPU Load (10s/60s/15m)")
-                                    .underline()
-                                    .color(BONE),
-                            ),
-                        )
-                        .on_hover_text(STATUS_XMRIG_CPU);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.resources)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(
-                                RichText::new("Hashrate (10s/60s/15m)")
-                                    .underline()
-                                    .color(BONE),
-                            ),
-                        )
-                        .on_hover_text(STATUS_XMRIG_HASHRATE);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.hashrate)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Difficulty").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_XMRIG_DIFFICULTY);
-                        ui.add_sized([width, height], Label::new(format!("{}", api.diff)));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Shares").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_XMRIG_SHARES);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!(
-                                "[Accepted: {}] [Rejected: {}]",
-                                api.accepted, api.rejected
-                            )),
-                        );
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Pool").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_XMRIG_POOL);
-                        ui.add_sized([width, height], Label::new(&lock!(xmrig_img).url));
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(RichText::new("Threads").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_XMRIG_THREADS);
-                        ui.add_sized(
-                            [width, height],
-                            Label::new(format!("{}/{}", &lock!(xmrig_img).threads, max_threads)),
-                        );
-                        drop(api);
-                    })
-                });
-            });
-        //---------------------------------------------------------------------------------------------------- [P2Pool]
-        } else if self.submenu == Submenu::P2pool {
-            let api = lock!(gupax_p2pool_api);
-            let text = height / 25.0;
-            let log = height / 2.8;
-            // Payout Text + PayoutView buttons
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    let width = (width / 3.0) - (SPACE * 4.0);
-                    ui.add_sized(
-                        [width, text],
-                        Label::new(
-                            RichText::new(format!("Total Payouts: {}", api.payout))
-                                .underline()
-                                .color(LIGHT_GRAY),
-                        ),
-                    )
-                    .on_hover_text(STATUS_SUBMENU_PAYOUT);
-                    ui.separator();
-                    ui.add_sized(
-                        [width, text],
-                        Label::new(
-                            RichText::new(format!("Total XMR: {}", api.xmr))
-                                .underline()
-                                .color(LIGHT_GRAY),
-                        ),
-                    )
-                    .on_hover_text(STATUS_SUBMENU_XMR);
-                    let width = width / 4.0;
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [width, text],
-                            SelectableLabel::new(self.payout_view == PayoutView::Latest, "Latest"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_LATEST)
-                        .clicked()
-                    {
-                        self.payout_view = PayoutView::Latest;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [width, text],
-                            SelectableLabel::new(self.payout_view == PayoutView::Oldest, "Oldest"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_OLDEST)
-                        .clicked()
-                    {
-                        self.payout_view = PayoutView::Oldest;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [width, text],
-                            SelectableLabel::new(
-                                self.payout_view == PayoutView::Biggest,
-                                "Biggest",
-                            ),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_BIGGEST)
-                        .clicked()
-                    {
-                        self.payout_view = PayoutView::Biggest;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [width, text],
-                            SelectableLabel::new(
-                                self.payout_view == PayoutView::Smallest,
-                                "Smallest",
-                            ),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_SMALLEST)
-                        .clicked()
-                    {
-                        self.payout_view = PayoutView::Smallest;
-                    }
-                });
-                ui.separator();
-                // Actual logs
-                egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
-                    egui::ScrollArea::vertical()
-                        .stick_to_bottom(self.payout_view == PayoutView::Oldest)
-                        .max_width(width)
-                        .max_height(log)
-                        .auto_shrink([false; 2])
-                        .show_viewport(ui, |ui, _| {
-                            ui.style_mut().override_text_style =
-                                Some(Name("MonospaceLarge".into()));
-                            match self.payout_view {
-                                PayoutView::Latest => ui.add_sized(
-                                    [width, log],
-                                    TextEdit::multiline(&mut api.log_rev.as_str()),
-                                ),
-                                PayoutView::Oldest => ui.add_sized(
-                                    [width, log],
-                                    TextEdit::multiline(&mut api.log.as_str()),
-                                ),
-                                PayoutView::Biggest => ui.add_sized(
-                                    [width, log],
-                                    TextEdit::multiline(&mut api.payout_high.as_str()),
-                                ),
-                                PayoutView::Smallest => ui.add_sized(
-                                    [width, log],
-                                    TextEdit::multiline(&mut api.payout_low.as_str()),
-                                ),
-                            };
-                        });
-                });
-            });
-            drop(api);
-            // Payout/Share Calculator
-            let button = (width / 20.0) - (SPACE * 1.666);
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    ui.set_min_width(width - SPACE);
-                    if ui
-                        .add_sized(
-                            [button * 2.0, text],
-                            SelectableLabel::new(!self.manual_hash, "Automatic"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_AUTOMATIC)
-                        .clicked()
-                    {
-                        self.manual_hash = false;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [button * 2.0, text],
-                            SelectableLabel::new(self.manual_hash, "Manual"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_MANUAL)
-                        .clicked()
-                    {
-                        self.manual_hash = true;
-                    }
-                    ui.separator();
-                    ui.set_enabled(self.manual_hash);
-                    if ui
-                        .add_sized(
-                            [button, text],
-                            SelectableLabel::new(self.hash_metric == Hash::Hash, "Hash"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_HASH)
-                        .clicked()
-                    {
-                        self.hash_metric = Hash::Hash;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [button, text],
-                            SelectableLabel::new(self.hash_metric == Hash::Kilo, "Kilo"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_KILO)
-                        .clicked()
-                    {
-                        self.hash_metric = Hash::Kilo;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [button, text],
-                            SelectableLabel::new(self.hash_metric == Hash::Mega, "Mega"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_MEGA)
-                        .clicked()
-                    {
-                        self.hash_metric = Hash::Mega;
-                    }
-                    ui.separator();
-                    if ui
-                        .add_sized(
-                            [button, text],
-                            SelectableLabel::new(self.hash_metric == Hash::Giga, "Giga"),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_GIGA)
-                        .clicked()
-                    {
-                        self.hash_metric = Hash::Giga;
-                    }
-                    ui.separator();
-                    ui.spacing_mut().slider_width = button * 11.5;
-                    ui.add_sized(
-                        [button * 14.0, text],
-                        Slider::new(&mut self.hashrate, 1.0..=1_000.0),
-                    );
-                })
-            });
-            // Actual stats
-            ui.set_enabled(p2pool_alive);
-            let text = height / 25.0;
-            let width = (width / 3.0) - (SPACE * 1.666);
-            let min_height = ui.available_height() / 1.3;
-            let api = lock!(p2pool_api);
-            ui.horizontal(|ui| {
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        ui.set_min_height(min_height);
-                        ui.add_sized(
-                            [width, text],
-                            Label::new(RichText::new("Monero Difficulty").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_MONERO_DIFFICULTY);
-                        ui.add_sized([width, text], Label::new(api.monero_difficulty.as_str()));
-                        ui.add_sized(
-                            [width, text],
-                            Label::new(RichText::new("Monero Hashrate").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_MONERO_HASHRATE);
-                        ui.add_sized([width, text], Label::new(api.monero_hashrate.as_str()));
-                        ui.add_sized(
-                            [width, text],
-                            Label::new(RichText::new("P2Pool Difficulty").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_P2POOL_DIFFICULTY);
-                        ui.add_sized([width, text], Label::new(api.p2pool_difficulty.as_str()));
-                        ui.add_sized(
-                            [width, text],
-                            Label::new(RichText::new("P2Pool Hashrate").underline().color(BONE)),
-                        )
-                        .on_hover_text(STATUS_SUBMENU_P2POOL_HASHRATE);
-                        ui.add_sized([width, text], Label::new(api.p2pool_hashrate.as_str()));
-                    })
-                });
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        ui.set_min_height(min_height);
-                        if self.manual_hash {
-                            let hashrate =
-                                Hash::convert_to_hash(self.hashrate, self.hash_metric) as u64;
-                            let p2pool_share_mean = PubP2poolApi::calculate_share_or_block_time(
-                                hashrate,
-                                api.p2pool_difficulty_u64,
-                            );
-                            let solo_block_mean = PubP2poolApi::calculate_share_or_block_time(
-                                hashrate,
-                                api.monero_difficulty_u64,
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Manually Inputted Hashrate")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(format!("{} H/s", HumanNumber::from_u64(hashrate))),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("P2Pool Block Mean").underline().color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_BLOCK_MEAN);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.p2pool_block_mean.to_string()),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your P2Pool Share Mean")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_SHARE_MEAN);
-                            ui.add_sized([width, text], Label::new(p2pool_share_mean.to_string()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your Solo Block Mean")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_SOLO_BLOCK_MEAN);
-                            ui.add_sized([width, text], Label::new(solo_block_mean.to_string()));
-                        } else {
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your P2Pool Hashrate")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_YOUR_P2POOL_HASHRATE);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(format!("{} H/s", api.hashrate_1h)),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("P2Pool Block Mean").underline().color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_BLOCK_MEAN);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.p2pool_block_mean.to_string()),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your P2Pool Share Mean")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_SHARE_MEAN);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.p2pool_share_mean.to_string()),
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your Solo Block Mean")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_SOLO_BLOCK_MEAN);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.solo_block_mean.to_string()),
-                            );
+            debug!("Status Tab | Rendering [Mining mode]");
+            let row = height / 25.0;
+            let mode_height = ui
+                .group(|ui| {
+                    ui.horizontal(|ui| {
+                        let width = (width - SPACE * 8.0) / 4.0;
+                        ui.add_sized(
+                            [width, row],
+                            Label::new(RichText::new("Mining mode").underline().color(LIGHT_GRAY)),
+                        )
+                        .on_hover_text(STATUS_MODE);
+                        for mode in MiningMode::ALL {
+                            ui.separator();
+                            let selected = *mining_mode == Some(mode);
+                            if ui
+                                .add_sized(
+                                    [width, row],
+                                    SelectableLabel::new(selected, mode.to_string()),
+                                )
+                                .on_hover_text(mode.description())
+                                .clicked()
+                            {
+                                *mining_mode = if selected { None } else { Some(mode) };
+                            }
                         }
-                    })
-                });
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        ui.set_min_height(min_height);
-                        if self.manual_hash {
-                            let hashrate =
-                                Hash::convert_to_hash(self.hashrate, self.hash_metric) as u64;
-                            let user_p2pool_percent = PubP2poolApi::calculate_dominance(
-                                hashrate,
-                                api.p2pool_hashrate_u64,
-                            );
-                            let user_monero_percent = PubP2poolApi::calculate_dominance(
-                                hashrate,
-                                api.monero_hashrate_u64,
-                            );
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(RichText::new("P2Pool Miners").underline().color(BONE)),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_MINERS);
-                            ui.add_sized([width, text], Label::new(api.miners.as_str()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("P2Pool Dominance").underline().color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_DOMINANCE);
-                            ui.add_sized([width, text], Label::new(api.p2pool_percent.as_str()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your P2Pool Dominance")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_YOUR_P2POOL_DOMINANCE);
-                            ui.add_sized([width, text], Label::new(user_p2pool_percent.as_str()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your Monero Dominance")
-                                        .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_YOUR_MONERO_DOMINANCE);
-                            ui.add_sized([width, text], Label::new(user_monero_percent.as_str()));
-                        } else {
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(RichText::new("P2Pool Miners").underline().color(BONE)),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_MINERS);
-                            ui.add_sized([width, text], Label::new(api.miners.as_str()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("P2Pool Dominance").underline().color(BONE),
-                                ),
+                    });
+                    let Some(mode) = *mining_mode else {
+                        return;
+                    };
+                    // Confirmation, show exactly what will change.
+                    ui.separator();
+                    let diff = mode.diff(p2pool, xmrig);
+                    if diff.is_empty() {
+                        ui.add_sized(
+                            [width, row],
+                            Label::new(format!("[{}] is already applied", mode)),
+                        );
+                    }
+                    for change in &diff {
+                        ui.add_sized(
+                            [width, row],
+                            Label::new(
+                                RichText::new(format!(
+                                    "{}: {} ➡ {}",
+                                    change.setting, change.old, change.new
+                                ))
+                                .color(YELLOW),
+                            ),
+                        );
+                    }
+                    ui.horizontal(|ui| {
+                        let width = (width - SPACE * 4.0) / 2.0;
+                        if ui
+                            .add_enabled(
+                                !diff.is_empty(),
+                                Button::new("Apply").min_size([width, row].into()),
                             )
-                            .on_hover_text(STATUS_SUBMENU_P2POOL_DOMINANCE);
-                            ui.add_sized([width, text], Label::new(api.p2pool_percent.as_str()));
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your P2Pool Dominance")
+                            .on_hover_text(STATUS_MODE)
+                            .clicked()
+                        {
+                            info!("Status | Applying mining mode [{}]", mode);
+                            mode.apply(p2pool, xmrig);
+                            *mining_mode = None;
+                        }
+                        if ui.add_sized([width, row], Button::new("Cancel")).clicked() {
+                            *mining_mode = None;
+                        }
+                    });
+                })
+                .response
+                .rect
+                .height();
+
+            let bar_height = layout_bar(
+                ui,
+                &mut self.processes_layout,
+                &mut self.collapsed,
+                &StatusGroup::PROCESSES,
+                width,
+                row,
+            );
+            let shown = StatusGroup::shown(
+                &self.processes_layout,
+                &self.collapsed,
+                &StatusGroup::PROCESSES,
+            );
+            let columns = shown.len().max(1) as f32;
+            let width = (width / columns) - (SPACE * 5.0 / columns);
+            let min_height = height - mode_height - bar_height - (SPACE * 5.0);
+            let height = height / 25.0;
+            ui.horizontal(|ui| {
+                for group in shown {
+                    match group {
+                        StatusGroup::Gupax => {
+                            // [Gupax]
+                            ui.group(|ui| {
+                                ui.vertical(|ui| {
+                                    debug!("Status Tab | Rendering [Gupax]");
+                                    ui.set_min_height(min_height);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("[Gupax]").color(LIGHT_GRAY).text_style(
+                                                TextStyle::Name("MonospaceLarge".into()),
+                                            ),
+                                        ),
+                                    )
+                                    .on_hover_text("Gupax is online");
+                                    let sys = lock!(sys);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Uptime").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_UPTIME);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.gupax_uptime.to_string()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Gupax CPU").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_CPU_USAGE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.gupax_cpu_usage.to_string()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Gupax Memory").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_MEMORY_USAGE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.gupax_memory_used_mb.to_string()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Console Buffers").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_BUFFERS);
+                                    ui.add_sized([width, height], Label::new(sys.buffers.as_str()));
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("System CPU").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_USAGE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.system_cpu_usage.to_string()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("System Memory").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_SYSTEM_MEMORY);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.system_memory.to_string()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("System CPU Model")
+                                                .underline()
+                                                .color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_GUPAX_SYSTEM_CPU_MODEL);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(sys.system_cpu_model.to_string()),
+                                    );
+                                    drop(sys);
+                                })
+                            });
+                        }
+                        StatusGroup::P2pool => {
+                            // [P2Pool]
+                            ui.group(|ui| {
+                                ui.vertical(|ui| {
+                                    debug!("Status Tab | Rendering [P2Pool]");
+                                    ui.set_min_height(min_height);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("[P2Pool]").color(LIGHT_GRAY).text_style(
+                                                TextStyle::Name("MonospaceLarge".into()),
+                                            ),
+                                        ),
+                                    )
+                                    .on_hover_text(if p2pool_alive {
+                                        "P2Pool is online"
+                                    } else {
+                                        "P2Pool is offline"
+                                    });
+                                    process_controls(
+                                        ui,
+                                        &cards[0],
+                                        &mut self.submenu,
+                                        signal,
+                                        width,
+                                        height,
+                                    );
+                                    ui.set_enabled(p2pool_alive);
+                                    ui.style_mut().override_text_style =
+                                        Some(Name("MonospaceSmall".into()));
+                                    let height = height / 1.4;
+                                    let api = lock!(p2pool_api);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Uptime").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_UPTIME);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.uptime.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Shares Found").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_SHARES);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(localize(&format!(
+                                            "{} [Efficiency: {}]",
+                                            api.shares_found, api.share_efficiency
+                                        )).into_owned()),
+                                    )
+                                    .on_hover_text(format!(
+                                        "{}\n\nExpected shares: {:.2}",
+                                        STATUS_P2POOL_SHARE_EFFICIENCY, api.expected_shares
+                                    ));
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Payouts").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_PAYOUTS);
+                                    if api.payouts_missing() {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new(format!("Total: {} ⚠", api.payouts))
+                                                    .color(RED),
+                                            ),
+                                        )
+                                        .on_hover_text(
+                                            format!(
+                                                "{}\n\nLog format: P2Pool {}+",
+                                                STATUS_P2POOL_PAYOUTS_MISSING,
+                                                crate::regex::p2pool_format().since
+                                            ),
+                                        );
+                                    } else {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(format!("Total: {}", api.payouts)),
+                                        );
+                                    }
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(format!(
+                                            "[{:.7}/hour]\n[{:.7}/day]\n[{:.7}/month]",
+                                            api.payouts_hour, api.payouts_day, api.payouts_month
+                                        )),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("XMR Mined").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_XMR);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(format!("Total: {:.13} XMR", api.xmr)),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(format!(
+                                            "[{:.7}/hour]\n[{:.7}/day]\n[{:.7}/month]",
+                                            api.xmr_hour, api.xmr_day, api.xmr_month
+                                        )),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Hashrate (15m/1h/24h)")
+                                                .underline()
+                                                .color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_HASHRATE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.hashrate.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Miners Connected")
+                                                .underline()
+                                                .color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_CONNECTIONS);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.connections.localized()),
+                                    );
+                                    if api.miner_threads != 0 {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new("Built-in Miner")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_P2POOL_MINER);
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(localize(&format!(
+                                                "[{}] [Threads: {}]",
+                                                api.miner_hashrate, api.miner_threads
+                                            )).into_owned()),
+                                        );
+                                    }
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Effort").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_EFFORT);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(localize(&format!(
+                                            "[Average: {}] [Current: {}]",
+                                            api.average_effort, api.current_effort
+                                        )).into_owned()),
+                                    );
+                                    let img = lock!(p2pool_img);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Monero Node").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_MONERO_NODE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(format!(
+                                            "[IP: {}]\n[RPC: {}] [ZMQ: {}]",
+                                            &img.host, &img.rpc, &img.zmq
+                                        )),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Sidechain").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_POOL);
+                                    ui.add_sized([width, height], Label::new(&img.mini));
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Address").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_P2POOL_ADDRESS);
+                                    ui.add_sized([width, height], Label::new(&img.address));
+                                    if !img.workdir.is_empty() {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new("Working Directory")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_WORKDIR);
+                                        ui.add_sized([width, height], Label::new(&img.workdir));
+                                    }
+                                    drop(img);
+                                    drop(api);
+                                })
+                            });
+                        }
+                        StatusGroup::Xmrig => {
+                            // [XMRig]
+                            ui.group(|ui| {
+                                ui.vertical(|ui| {
+                                    debug!("Status Tab | Rendering [XMRig]");
+                                    ui.set_min_height(min_height);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("[XMRig]").color(LIGHT_GRAY).text_style(
+                                                TextStyle::Name("MonospaceLarge".into()),
+                                            ),
+                                        ),
+                                    )
+                                    .on_hover_text(if xmrig_alive {
+                                        "XMRig is online"
+                                    } else {
+                                        "XMRig is offline"
+                                    });
+                                    process_controls(
+                                        ui,
+                                        &cards[1],
+                                        &mut self.submenu,
+                                        signal,
+                                        width,
+                                        height,
+                                    );
+                                    ui.set_enabled(xmrig_alive);
+                                    let api = lock!(xmrig_api);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Uptime").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_UPTIME);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.uptime.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("CPU Load (10s/60s/15m)")
+                                                .underline()
+                                                .color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_CPU);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.resources.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Hashrate (10s/60s/15m)")
+                                                .underline()
+                                                .color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_HASHRATE);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.hashrate.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Difficulty").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_DIFFICULTY);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(api.diff.localized()),
+                                    );
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Shares").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_SHARES);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(localize(&format!(
+                                            "[Accepted: {}] [Rejected: {}]",
+                                            api.accepted, api.rejected
+                                        )).into_owned()),
+                                    );
+                                    if let Some([p50, p95]) = api.latency.current() {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new("Share Latency")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_XMRIG_SHARE_LATENCY);
+                                        let (text, color) = match api.latency.rising() {
+                                            Some((low, _)) => (
+                                                format!(
+                                                    "[p50: {} ms] [p95: {} ms] up from {} ms, check the node/network",
+                                                    p50, p95, low
+                                                ),
+                                                RED,
+                                            ),
+                                            None => (
+                                                format!("[p50: {} ms] [p95: {} ms]", p50, p95),
+                                                WHITE,
+                                            ),
+                                        };
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(RichText::new(text).color(color)),
+                                        );
+                                        latency_chart(ui, &api.latency, [width, height * 2.0]);
+                                    }
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(RichText::new("Pool").underline().color(BONE)),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_POOL);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(&lock!(xmrig_img).url),
+                                    );
+                                    let pool = lock!(pool_hashrate);
+                                    if let Some((pool_api, _)) = &pool.target {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new(format!(
+                                                    "Pool-side hashrate ({})",
+                                                    pool_api.name()
+                                                ))
+                                                .underline()
+                                                .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_XMRIG_POOL_HASHRATE);
+                                        let local = api.hashrate_raw as f64;
+                                        let uptime =
+                                            std::time::Duration::from_secs(api.uptime.as_secs());
+                                        let (text, color) = match pool.hashrate {
+                                            Some(h) => match crate::poolstats::PoolHashrate::divergence(
+                                                h, local, uptime,
+                                            ) {
+                                                Some(ratio) => (
+                                                    localize(&format!(
+                                                        "{} ({:.0}% of XMRig's), shares may not be reaching the pool",
+                                                        HumanNumber::to_hashrate(h as f32),
+                                                        ratio * 100.0
+                                                    )).into_owned(),
+                                                    RED,
+                                                ),
+                                                None => (
+                                                    HumanNumber::to_hashrate(h as f32).localized(),
+                                                    WHITE,
+                                                ),
+                                            },
+                                            None if !pool.error.is_empty() => {
+                                                (format!("Unavailable: {}", pool.error), GRAY)
+                                            }
+                                            None => ("Checking...".to_string(), GRAY),
+                                        };
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(RichText::new(text).color(color)),
+                                        );
+                                    }
+                                    drop(pool);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(
+                                            RichText::new("Threads").underline().color(BONE),
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_XMRIG_THREADS);
+                                    ui.add_sized(
+                                        [width, height],
+                                        Label::new(format!(
+                                            "{}/{}",
+                                            &lock!(xmrig_img).threads,
+                                            max_threads
+                                        )),
+                                    );
+                                    let workdir = lock!(xmrig_img).workdir.clone();
+                                    if !workdir.is_empty() {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new("Working Directory")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_WORKDIR);
+                                        ui.add_sized([width, height], Label::new(workdir));
+                                    }
+                                    // [Instances]
+                                    let instances = lock!(xmrig_instances);
+                                    if !instances.is_empty() {
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(
+                                                RichText::new("Instances").underline().color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_XMRIG_INSTANCES);
+                                        let mut total = api.hashrate_raw;
+                                        let mut alive = 1;
+                                        for (i, instance) in instances.iter().enumerate() {
+                                            let (is_alive, state) = {
+                                                let process = lock!(instance.process);
+                                                (process.is_alive(), process.state)
+                                            };
+                                            let hashrate = lock!(instance.gui_api).hashrate_raw;
+                                            if is_alive {
+                                                alive += 1;
+                                                total += hashrate;
+                                            }
+                                            ui.add_sized(
+                                                [width, height],
+                                                Label::new(localize(&format!(
+                                                    "#{} [{:?}] {} threads | {}",
+                                                    i + 1,
+                                                    state,
+                                                    instance.threads,
+                                                    HumanNumber::to_hashrate(hashrate)
+                                                )).into_owned()),
+                                            );
+                                        }
+                                        ui.add_sized(
+                                            [width, height],
+                                            Label::new(localize(&format!(
+                                                "{}/{} running | Combined: {}",
+                                                alive,
+                                                instances.len() + 1,
+                                                HumanNumber::to_hashrate(total)
+                                            )).into_owned()),
+                                        );
+                                    }
+                                    drop(instances);
+                                    // [XMRig details]
+                                    egui::CollapsingHeader::new("XMRig details")
+                                        .id_source("xmrig_details")
+                                        .show(ui, |ui| {
+                                            ui.style_mut().override_text_style =
+                                                Some(Name("MonospaceSmall".into()));
+                                            let height = height / 1.4;
+                                            for (name, value, hover) in [
+                                                ("CPU", &api.cpu, STATUS_XMRIG_DETAILS_CPU),
+                                                ("Cache", &api.cache, STATUS_XMRIG_DETAILS_CACHE),
+                                                ("MSR", &api.msr, STATUS_XMRIG_DETAILS_MSR),
+                                                (
+                                                    "Memory",
+                                                    &api.memory,
+                                                    STATUS_XMRIG_DETAILS_MEMORY,
+                                                ),
+                                            ] {
+                                                ui.add_sized(
+                                                    [width, height],
+                                                    Label::new(format!("{}: {}", name, value)),
+                                                )
+                                                .on_hover_text(hover);
+                                            }
+                                            let color = if api.hugepages_ok { GREEN } else { RED };
+                                            ui.add_sized(
+                                                [width, height],
+                                                Label::new(
+                                                    RichText::new(format!(
+                                                        "Huge Pages: {}",
+                                                        api.hugepages
+                                                    ))
+                                                    .color(color),
+                                                ),
+                                            )
+                                            .on_hover_text(STATUS_XMRIG_DETAILS_HUGEPAGES);
+                                            // Per-thread hashrate, relative to the fastest thread.
+                                            let max =
+                                                api.threads_raw.iter().copied().fold(0.0, f32::max);
+                                            egui::ScrollArea::vertical()
+                                                .max_height(height * 8.0)
+                                                .show(ui, |ui| {
+                                                    for (i, h) in api.threads_raw.iter().enumerate()
+                                                    {
+                                                        let fill =
+                                                            if max > 0.0 { h / max } else { 0.0 };
+                                                        ui.add_sized(
+                                                            [width, height],
+                                                            ProgressBar::new(fill).text(localize(&format!(
+                                                                "Thread {}: {}",
+                                                                i,
+                                                                HumanNumber::to_hashrate(*h)
+                                                            )).into_owned()),
+                                                        )
+                                                        .on_hover_text(STATUS_XMRIG_DETAILS_THREAD);
+                                                    }
+                                                });
+                                        });
+                                    drop(api);
+                                })
+                            });
+                        }
+                        _ => (),
+                    }
+                }
+            });
+        //---------------------------------------------------------------------------------------------------- [P2Pool]
+        } else if self.submenu == Submenu::P2pool {
+            let text = height / 25.0;
+            let log = height / 2.8;
+            layout_bar(
+                ui,
+                &mut self.p2pool_layout,
+                &mut self.collapsed,
+                &StatusGroup::P2POOL,
+                width,
+                text,
+            );
+            let shown =
+                StatusGroup::shown(&self.p2pool_layout, &self.collapsed, &StatusGroup::P2POOL);
+            for (i, group) in shown.iter().enumerate() {
+                match group {
+                    StatusGroup::Payouts => {
+                        let api = lock!(gupax_p2pool_api);
+                        // Payout Text + PayoutView buttons
+                        ui.group(|ui| {
+                            ui.horizontal(|ui| {
+                                let width = (width / 3.0) - (SPACE * 4.0);
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(
+                                        RichText::new(
+                                            localize(&format!("Total Payouts: {}", api.payout))
+                                                .into_owned(),
+                                        )
                                         .underline()
-                                        .color(BONE),
-                                ),
-                            )
-                            .on_hover_text(STATUS_SUBMENU_YOUR_P2POOL_DOMINANCE);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.user_p2pool_percent.as_str()),
-                            );
+                                        .color(LIGHT_GRAY),
+                                    ),
+                                )
+                                .on_hover_text(STATUS_SUBMENU_PAYOUT);
+                                ui.separator();
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(
+                                        RichText::new(format!("Total XMR: {}", api.xmr))
+                                            .underline()
+                                            .color(LIGHT_GRAY),
+                                    ),
+                                )
+                                .on_hover_text(STATUS_SUBMENU_XMR);
+                                let width = width / 8.0;
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        Button::new(self.payout_timezone.to_string()),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_TIMEZONE)
+                                    .clicked()
+                                {
+                                    self.payout_timezone = match self.payout_timezone {
+                                        PayoutTimezone::Local => PayoutTimezone::Utc,
+                                        PayoutTimezone::Utc => PayoutTimezone::Local,
+                                    };
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Latest,
+                                            "Latest",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_LATEST)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Latest;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Oldest,
+                                            "Oldest",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_OLDEST)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Oldest;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Biggest,
+                                            "Biggest",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_BIGGEST)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Biggest;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Smallest,
+                                            "Smallest",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_SMALLEST)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Smallest;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Weekly,
+                                            "Weekly",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_WEEKLY)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Weekly;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Monthly,
+                                            "Monthly",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_MONTHLY)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Monthly;
+                                }
+                                ui.separator();
+                                if ui
+                                    .add_sized(
+                                        [width, text],
+                                        SelectableLabel::new(
+                                            self.payout_view == PayoutView::Recent,
+                                            "Recent",
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_RECENT)
+                                    .clicked()
+                                {
+                                    self.payout_view = PayoutView::Recent;
+                                }
+                            });
+                            ui.separator();
+                            // Report export buttons
+                            let report = self.payout_view.period().map(|period| {
+                                (
+                                    period,
+                                    report::build(
+                                        period,
+                                        self.payout_timezone,
+                                        &api.payout_ord,
+                                        &api.history,
+                                    ),
+                                )
+                            });
+                            let log = if let Some((period, rows)) = &report {
+                                ui.horizontal(|ui| {
+                                    let width = (width / 2.0) - (SPACE * 2.0);
+                                    if ui
+                                        .add_sized([width, text], Button::new("Export Markdown"))
+                                        .on_hover_text(STATUS_SUBMENU_EXPORT_MARKDOWN)
+                                        .clicked()
+                                    {
+                                        report::spawn_export(
+                                            *period,
+                                            self.payout_timezone,
+                                            rows.clone(),
+                                            false,
+                                        );
+                                    }
+                                    ui.separator();
+                                    if ui
+                                        .add_sized([width, text], Button::new("Export HTML"))
+                                        .on_hover_text(STATUS_SUBMENU_EXPORT_HTML)
+                                        .clicked()
+                                    {
+                                        report::spawn_export(
+                                            *period,
+                                            self.payout_timezone,
+                                            rows.clone(),
+                                            true,
+                                        );
+                                    }
+                                });
+                                ui.separator();
+                                log - text - SPACE * 2.0
+                            } else {
+                                // Block explorer + hash lookup result
+                                let mut lookup = lock!(block_hash);
+                                if let Some(hash) = lookup.hash.take() {
+                                    lookup.msg = format!(
+                                        "Copied the hash of block {}: {}",
+                                        lookup.height, hash
+                                    );
+                                    ui.output_mut(|o| o.copied_text = hash);
+                                }
+                                ui.horizontal(|ui| {
+                                    let width = (width / 3.0) - (SPACE * 2.0);
+                                    ui.add_sized(
+                                        [width, text],
+                                        TextEdit::hint_text(
+                                            TextEdit::singleline(&mut self.explorer),
+                                            crate::xmr::DEFAULT_EXPLORER,
+                                        ),
+                                    )
+                                    .on_hover_text(STATUS_SUBMENU_EXPLORER);
+                                    ui.separator();
+                                    ui.add_sized(
+                                        [ui.available_width(), text],
+                                        Label::new(RichText::new(&lookup.msg).color(LIGHT_GRAY)),
+                                    );
+                                });
+                                drop(lookup);
+                                ui.separator();
+                                log - text - SPACE * 2.0
+                            };
+                            // Actual logs
+                            let payouts = match self.payout_view {
+                                PayoutView::Latest => Some(&api.log_rev),
+                                PayoutView::Oldest => Some(&api.log),
+                                PayoutView::Biggest => Some(&api.payout_high),
+                                PayoutView::Smallest => Some(&api.payout_low),
+                                PayoutView::Weekly | PayoutView::Monthly | PayoutView::Recent => {
+                                    None
+                                }
+                            };
+                            egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
+                                ui.style_mut().override_text_style =
+                                    Some(Name("MonospaceLarge".into()));
+                                let scroll = egui::ScrollArea::vertical()
+                                    .stick_to_bottom(self.payout_view == PayoutView::Oldest)
+                                    .max_width(width)
+                                    .max_height(log)
+                                    .auto_shrink([false; 2]);
+                                if self.payout_view == PayoutView::Recent {
+                                    let days = report::recent(
+                                        &api.payout_ord,
+                                        self.payout_timezone,
+                                        report::RECENT_PAYOUTS,
+                                    );
+                                    scroll.show(ui, |ui| {
+                                        ui.set_min_width(width);
+                                        recent_payouts(ui, &days, &self.explorer, width);
+                                    });
+                                } else if let Some(payouts) = payouts {
+                                    let (ip, port) = p2pool.rpc_target();
+                                    let lines: Vec<&str> =
+                                        payouts.lines().filter(|l| !l.is_empty()).collect();
+                                    let row = ui.text_style_height(&Name("MonospaceLarge".into()));
+                                    scroll.show_rows(ui, row, lines.len(), |ui, range| {
+                                        ui.set_min_width(width);
+                                        for line in &lines[range] {
+                                            payout_row(
+                                                ui,
+                                                line,
+                                                self.payout_timezone,
+                                                &self.explorer,
+                                                block_hash,
+                                                &ip,
+                                                &port,
+                                            );
+                                        }
+                                    });
+                                } else {
+                                    scroll.show_viewport(ui, |ui, _| {
+                                        ui.add_sized(
+                                            [width, log],
+                                            TextEdit::multiline(
+                                                &mut report::to_text(
+                                                    report
+                                                        .as_ref()
+                                                        .map_or(&[][..], |(_, rows)| rows),
+                                                )
+                                                .as_str(),
+                                            ),
+                                        );
+                                    });
+                                }
+                            });
+                        });
+                        drop(api);
+                    }
+                    StatusGroup::Calculator => {
+                        ui.scope(|ui| {
+                            // Payout/Share Calculator
+                            let button = (width / 20.0) - (SPACE * 1.666);
+                            ui.group(|ui| {
+                                ui.horizontal(|ui| {
+                                    ui.set_min_width(width - SPACE);
+                                    if ui
+                                        .add_sized(
+                                            [button * 2.0, text],
+                                            SelectableLabel::new(!self.manual_hash, "Automatic"),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_AUTOMATIC)
+                                        .clicked()
+                                    {
+                                        self.manual_hash = false;
+                                    }
+                                    ui.separator();
+                                    if ui
+                                        .add_sized(
+                                            [button * 2.0, text],
+                                            SelectableLabel::new(self.manual_hash, "Manual"),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_MANUAL)
+                                        .clicked()
+                                    {
+                                        self.manual_hash = true;
+                                    }
+                                    ui.separator();
+                                    ui.set_enabled(self.manual_hash);
+                                    if ui
+                                        .add_sized(
+                                            [button, text],
+                                            SelectableLabel::new(
+                                                self.hash_metric == Hash::Hash,
+                                                "Hash",
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_HASH)
+                                        .clicked()
+                                    {
+                                        self.hash_metric = Hash::Hash;
+                                    }
+                                    ui.separator();
+                                    if ui
+                                        .add_sized(
+                                            [button, text],
+                                            SelectableLabel::new(
+                                                self.hash_metric == Hash::Kilo,
+                                                "Kilo",
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_KILO)
+                                        .clicked()
+                                    {
+                                        self.hash_metric = Hash::Kilo;
+                                    }
+                                    ui.separator();
+                                    if ui
+                                        .add_sized(
+                                            [button, text],
+                                            SelectableLabel::new(
+                                                self.hash_metric == Hash::Mega,
+                                                "Mega",
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_MEGA)
+                                        .clicked()
+                                    {
+                                        self.hash_metric = Hash::Mega;
+                                    }
+                                    ui.separator();
+                                    if ui
+                                        .add_sized(
+                                            [button, text],
+                                            SelectableLabel::new(
+                                                self.hash_metric == Hash::Giga,
+                                                "Giga",
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_GIGA)
+                                        .clicked()
+                                    {
+                                        self.hash_metric = Hash::Giga;
+                                    }
+                                    ui.separator();
+                                    ui.spacing_mut().slider_width = button * 11.5;
+                                    ui.add_sized(
+                                        [button * 14.0, text],
+                                        Slider::new(&mut self.hashrate, 1.0..=1_000.0),
+                                    );
+                                })
+                            });
+                            // Actual stats
+                            ui.set_enabled(p2pool_alive);
+                            let text = height / 25.0;
+                            let width = (width / 3.0) - (SPACE * 1.666);
+                            // Leave room for the payout log if it comes after.
+                            let reserve = if shown[i + 1..].contains(&StatusGroup::Payouts) {
+                                log + text * 4.0
+                            } else {
+                                0.0
+                            };
+                            let min_height = (ui.available_height() - reserve) / 1.3;
+                            let api = lock!(p2pool_api);
+                            let formulas = if self.manual_hash {
+                                api.formulas(
+                                    Hash::convert_to_hash(self.hashrate, self.hash_metric) as u64,
+                                )
+                            } else {
+                                api.formulas(api.user_p2pool_hashrate_u64)
+                            };
+                            ui.horizontal(|ui| {
+                                ui.group(|ui| {
+                                    ui.vertical(|ui| {
+                                        ui.set_min_height(min_height);
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("Monero Difficulty")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_MONERO_DIFFICULTY);
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(api.monero_difficulty.localized()),
+                                        );
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("Monero Hashrate")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_MONERO_HASHRATE);
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(api.monero_hashrate.localized()),
+                                        );
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("P2Pool Difficulty")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_P2POOL_DIFFICULTY);
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(api.p2pool_difficulty.localized()),
+                                        );
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("P2Pool Hashrate")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(STATUS_SUBMENU_P2POOL_HASHRATE);
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(api.p2pool_hashrate.localized()),
+                                        );
+                                        let (reward, per_share, split) =
+                                            match api.reward.tail_and_fees() {
+                                                Some((tail, fees)) => (
+                                                    localize(&format!(
+                                                        "{} XMR",
+                                                        api.reward.to_human_number_12_point()
+                                                    )).into_owned(),
+                                                    localize(&format!(
+                                                        "{} XMR",
+                                                        PubP2poolApi::calculate_reward_per_share(
+                                                            api.reward,
+                                                            api.p2pool_difficulty_u64,
+                                                            api.monero_difficulty_u64,
+                                                        )
+                                                        .to_human_number_12_point()
+                                                    )).into_owned(),
+                                                    localize(&format!(
+                                                        "\n\nTail emission: {} XMR\nTransaction fees: {} XMR",
+                                                        tail.to_human_number_12_point(),
+                                                        fees.to_human_number_12_point()
+                                                    )).into_owned(),
+                                                ),
+                                                None => (
+                                                    HumanNumber::unknown().localized(),
+                                                    HumanNumber::unknown().localized(),
+                                                    String::new(),
+                                                ),
+                                            };
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("Block Reward")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_text(format!(
+                                            "{}{}",
+                                            STATUS_SUBMENU_BLOCK_REWARD, split
+                                        ));
+                                        ui.add_sized([width, text], Label::new(reward));
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new(
+                                                RichText::new("Reward per Share")
+                                                    .underline()
+                                                    .color(BONE),
+                                            ),
+                                        )
+                                        .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_REWARD_PER_SHARE, &formulas.reward_per_share));
+                                        ui.add_sized([width, text], Label::new(per_share));
+                                    })
+                                });
+                                ui.group(|ui| {
+                                    ui.vertical(|ui| {
+                                        ui.set_min_height(min_height);
+                                        if self.manual_hash {
+                                            let hashrate = Hash::convert_to_hash(
+                                                self.hashrate,
+                                                self.hash_metric,
+                                            )
+                                                as u64;
+                                            let p2pool_share_mean =
+                                                PubP2poolApi::calculate_share_or_block_time(
+                                                    hashrate,
+                                                    api.p2pool_difficulty_u64,
+                                                );
+                                            let solo_block_mean =
+                                                PubP2poolApi::calculate_share_or_block_time(
+                                                    hashrate,
+                                                    api.monero_difficulty_u64,
+                                                );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Manually Inputted Hashrate")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    HumanNumber::to_hashrate(hashrate as f32)
+                                                        .localized(),
+                                                ),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Block Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_BLOCK_MEAN, &formulas.p2pool_block_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.p2pool_block_mean.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your P2Pool Share Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_SHARE_MEAN, &formulas.p2pool_share_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(p2pool_share_mean.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your Solo Block Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_SOLO_BLOCK_MEAN, &formulas.solo_block_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(solo_block_mean.localized()),
+                                            );
+                                        } else {
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your P2Pool Hashrate")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_text(STATUS_SUBMENU_YOUR_P2POOL_HASHRATE);
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    HumanNumber::to_hashrate(
+                                                        api.user_p2pool_hashrate_u64 as f32,
+                                                    )
+                                                    .localized(),
+                                                ),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Block Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_BLOCK_MEAN, &formulas.p2pool_block_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.p2pool_block_mean.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your P2Pool Share Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_SHARE_MEAN, &formulas.p2pool_share_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.p2pool_share_mean.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your Solo Block Mean")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_SOLO_BLOCK_MEAN, &formulas.solo_block_mean));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.solo_block_mean.localized()),
+                                            );
+                                        }
+                                    })
+                                });
+                                ui.group(|ui| {
+                                    ui.vertical(|ui| {
+                                        ui.set_min_height(min_height);
+                                        if self.manual_hash {
+                                            let hashrate = Hash::convert_to_hash(
+                                                self.hashrate,
+                                                self.hash_metric,
+                                            )
+                                                as u64;
+                                            let user_p2pool_percent =
+                                                PubP2poolApi::calculate_dominance(
+                                                    hashrate,
+                                                    api.p2pool_hashrate_u64,
+                                                );
+                                            let user_monero_percent =
+                                                PubP2poolApi::calculate_dominance(
+                                                    hashrate,
+                                                    api.monero_hashrate_u64,
+                                                );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Miners")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_text(STATUS_SUBMENU_P2POOL_MINERS);
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.miners.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_DOMINANCE, &formulas.p2pool_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.p2pool_percent.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your P2Pool Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_YOUR_P2POOL_DOMINANCE, &formulas.user_p2pool_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(user_p2pool_percent.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your Monero Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_YOUR_MONERO_DOMINANCE, &formulas.user_monero_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(user_monero_percent.localized()),
+                                            );
+                                        } else {
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Miners")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_text(STATUS_SUBMENU_P2POOL_MINERS);
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.miners.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("P2Pool Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_P2POOL_DOMINANCE, &formulas.p2pool_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.p2pool_percent.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your P2Pool Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_YOUR_P2POOL_DOMINANCE, &formulas.user_p2pool_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.user_p2pool_percent.localized()),
+                                            );
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(
+                                                    RichText::new("Your Monero Dominance")
+                                                        .underline()
+                                                        .color(BONE),
+                                                ),
+                                            )
+                                            .on_hover_ui(|ui| formula_hover(ui, STATUS_SUBMENU_YOUR_MONERO_DOMINANCE, &formulas.user_monero_dominance));
+                                            ui.add_sized(
+                                                [width, text],
+                                                Label::new(api.user_monero_percent.localized()),
+                                            );
+                                        }
+                                    })
+                                });
+                            });
+                            // Tick bar
                             ui.add_sized(
-                                [width, text],
-                                Label::new(
-                                    RichText::new("Your Monero Dominance")
-                                        .underline()
-                                        .color(BONE),
-                                ),
+                                [ui.available_width(), text],
+                                Label::new(api.calculate_tick_bar()),
                             )
-                            .on_hover_text(STATUS_SUBMENU_YOUR_MONERO_DOMINANCE);
-                            ui.add_sized(
-                                [width, text],
-                                Label::new(api.user_monero_percent.as_str()),
-                            );
-                        }
-                    })
-                });
-            });
-            // Tick bar
-            ui.add_sized(
-                [ui.available_width(), text],
-                Label::new(api.calculate_tick_bar()),
-            )
-            .on_hover_text(STATUS_SUBMENU_PROGRESS_BAR);
-            drop(api);
+                            .on_hover_text(STATUS_SUBMENU_PROGRESS_BAR);
+                            drop(api);
+                        });
+                    }
+                    _ => (),
+                }
+            }
         //---------------------------------------------------------------------------------------------------- [Benchmarks]
         } else if self.submenu == Submenu::Benchmarks {
             debug!("Status Tab | Rendering [Benchmarks]");
@@ -807,7 +1619,7 @@ impl crate::disk::Status {
                         .on_hover_text(STATUS_SUBMENU_YOUR_HIGH);
                         ui.add_sized(
                             [width, text],
-                            Label::new(format!("{} H/s", HumanNumber::from_f32(cpu.high))),
+                            Label::new(HumanNumber::to_hashrate(cpu.high).localized()),
                         );
                         ui.add_sized(
                             [width, text],
@@ -816,7 +1628,7 @@ impl crate::disk::Status {
                         .on_hover_text(STATUS_SUBMENU_YOUR_AVERAGE);
                         ui.add_sized(
                             [width, text],
-                            Label::new(format!("{} H/s", HumanNumber::from_f32(cpu.average))),
+                            Label::new(HumanNumber::to_hashrate(cpu.average).localized()),
                         );
                         ui.add_sized(
                             [width, text],
@@ -825,7 +1637,7 @@ impl crate::disk::Status {
                         .on_hover_text(STATUS_SUBMENU_YOUR_LOW);
                         ui.add_sized(
                             [width, text],
-                            Label::new(format!("{} H/s", HumanNumber::from_f32(cpu.low))),
+                            Label::new(HumanNumber::to_hashrate(cpu.low).localized()),
                         );
                     })
                 })
@@ -838,14 +1650,19 @@ impl crate::disk::Status {
 			let percent = (api.hashrate_raw / cpu.high) * 100.0;
 			let human = HumanNumber::to_percent(percent);
 			if percent > 100.0 {
-				ui.add_sized([width, double], Label::new(format!("Your CPU's is faster than the highest benchmark! It is [{}] faster @ {}!", human, api.hashrate)));
+				ui.add_sized([width, double], Label::new(localize(&format!("Your CPU's is faster than the highest benchmark! It is [{}] faster @ {}!", human, api.hashrate)).into_owned()));
 				ui.add_sized([width, text], ProgressBar::new(1.0));
 			} else if api.hashrate_raw == 0.0 {
 				ui.add_sized([width, text], Label::new("Measuring hashrate..."));
 				ui.add_sized([width, text], Spinner::new().size(text));
 				ui.add_sized([width, text], ProgressBar::new(0.0));
+			} else if let crate::virt::Environment::Vm(_) = crate::virt::VIRT.env {
+				let vm = HumanNumber::to_percent(percent / crate::virt::VM_EXPECTED);
+				ui.add_sized([width, double], Label::new(localize(&format!("Your CPU's hashrate is [{}] of the highest benchmark @ {} ([{}] of what a VM can expect)", human, api.hashrate, vm)).into_owned()))
+					.on_hover_text(STATUS_SUBMENU_VIRT);
+				ui.add_sized([width, text], ProgressBar::new((percent / 100.0 / crate::virt::VM_EXPECTED).min(1.0)));
 			} else {
-				ui.add_sized([width, double], Label::new(format!("Your CPU's hashrate is [{}] of the highest benchmark @ {}", human, api.hashrate)));
+				ui.add_sized([width, double], Label::new(localize(&format!("Your CPU's hashrate is [{}] of the highest benchmark @ {}", human, api.hashrate)).into_owned()));
 				ui.add_sized([width, text], ProgressBar::new(percent / 100.0));
 			}
 		} else {
@@ -856,14 +1673,103 @@ impl crate::disk::Status {
 	});
 
             // Comparison
+            let view = benchmark_view;
             ui.group(|ui| {
-                ui.add_sized(
-                    [width, text],
-                    Hyperlink::from_label_and_url("Other CPUs", "https://xmrig.com/benchmark"),
-                )
-                .on_hover_text(STATUS_SUBMENU_OTHER_CPUS);
+                ui.horizontal(|ui| {
+                    let width = (width / 6.0) - SPACE;
+                    ui.add_sized(
+                        [width, text],
+                        Hyperlink::from_label_and_url("Other CPUs", "https://xmrig.com/benchmark"),
+                    )
+                    .on_hover_text(STATUS_SUBMENU_OTHER_CPUS);
+                    ui.separator();
+                    ui.add_sized(
+                        [width * 4.0, text],
+                        TextEdit::singleline(&mut view.search).hint_text("Search CPUs..."),
+                    )
+                    .on_hover_text(STATUS_SUBMENU_OTHER_SEARCH);
+                    ui.separator();
+                    ui.add_enabled_ui(!view.pinned.is_empty(), |ui| {
+                        if ui
+                            .add_sized([width, text], Button::new("Clear pins"))
+                            .on_hover_text(STATUS_SUBMENU_OTHER_CLEAR_PINS)
+                            .clicked()
+                        {
+                            view.pinned.clear();
+                        }
+                    });
+                });
             });
 
+            // Pinned CPUs side by side, with the user's hashrate (if XMRig is alive) overlayed.
+            if !view.pinned.is_empty() {
+                let measured = if xmrig_alive {
+                    lock!(xmrig_api).hashrate_raw
+                } else {
+                    0.0
+                };
+                ui.horizontal(|ui| {
+                    let width = (width / view.pinned.len() as f32) - (SPACE * 1.666);
+                    for i in view.pinned.iter() {
+                        let benchmark = &benchmarks[*i];
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(
+                                        RichText::new(&benchmark.cpu).underline().color(BONE),
+                                    ),
+                                );
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(
+                                        localize(&format!(
+                                            "Rank {} | Average {} | High {}",
+                                            benchmark.rank,
+                                            HumanNumber::to_hashrate(benchmark.average),
+                                            HumanNumber::to_hashrate(benchmark.high),
+                                        ))
+                                        .into_owned(),
+                                    ),
+                                );
+                                let per_core = match benchmark.per_core() {
+                                    Some(h) => HumanNumber::to_hashrate(h).localized(),
+                                    None => "?".to_string(),
+                                };
+                                ui.add_sized(
+                                    [width, text],
+                                    Label::new(format!("Per core {}", per_core)),
+                                );
+                                if measured > 0.0 {
+                                    let percent = (measured / benchmark.average) * 100.0;
+                                    ui.add_sized(
+                                        [width, text],
+                                        Label::new(
+                                            localize(&format!(
+                                                "Yours: {} ({} of its average)",
+                                                HumanNumber::to_hashrate(measured),
+                                                HumanNumber::to_percent(percent),
+                                            ))
+                                            .into_owned(),
+                                        ),
+                                    );
+                                    ui.add_sized([width, text], ProgressBar::new(percent / 100.0));
+                                } else {
+                                    ui.add_enabled_ui(false, |ui| {
+                                        ui.add_sized(
+                                            [width, text],
+                                            Label::new("Yours: XMRig is offline"),
+                                        );
+                                        ui.add_sized([width, text], ProgressBar::new(0.0));
+                                    });
+                                }
+                            })
+                        });
+                    }
+                });
+            }
+
+            let indices = view.indices(benchmarks);
             egui::ScrollArea::both()
                 .scroll_bar_visibility(
                     egui::containers::scroll_area::ScrollBarVisibility::AlwaysVisible,
@@ -873,7 +1779,8 @@ impl crate::disk::Status {
                 .auto_shrink([false; 2])
                 .show_viewport(ui, |ui, _| {
                     let width = width / 20.0;
-                    let (cpu, bar, high, average, low, rank, bench) = (
+                    let (pin, cpu, bar, high, average, low, rank, per_core, bench) = (
+                        width,
                         width * 10.0,
                         width * 3.0,
                         width * 2.0,
@@ -881,11 +1788,40 @@ impl crate::disk::Status {
                         width * 2.0,
                         width,
                         width * 2.0,
+                        width * 2.0,
                     );
+                    // Sortable columns show an arrow, clicking again reverses them.
+                    let mut sort = None;
+                    let mut header = |ui: &mut egui::Ui,
+                                      size: f32,
+                                      name: &str,
+                                      by: BenchmarkSort,
+                                      hover: &str| {
+                        let text = match (view.sort == by, view.reverse) {
+                            (true, false) => format!("{} ⏷", name),
+                            (true, true) => format!("{} ⏶", name),
+                            _ => name.to_string(),
+                        };
+                        if ui
+                            .add_sized([size, double], SelectableLabel::new(view.sort == by, text))
+                            .on_hover_text(hover)
+                            .clicked()
+                        {
+                            sort = Some(by);
+                        }
+                    };
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
-                            ui.add_sized([cpu, double], Label::new("CPU"))
-                                .on_hover_text(STATUS_SUBMENU_OTHER_CPU);
+                            ui.add_sized([pin, double], Label::new("Pin"))
+                                .on_hover_text(STATUS_SUBMENU_OTHER_PIN);
+                            ui.separator();
+                            header(
+                                ui,
+                                cpu,
+                                "CPU",
+                                BenchmarkSort::Similarity,
+                                STATUS_SUBMENU_OTHER_CPU,
+                            );
                             ui.separator();
                             ui.add_sized([bar, double], Label::new("Relative"))
                                 .on_hover_text(STATUS_SUBMENU_OTHER_RELATIVE);
@@ -893,63 +1829,602 @@ impl crate::disk::Status {
                             ui.add_sized([high, double], Label::new("High"))
                                 .on_hover_text(STATUS_SUBMENU_OTHER_HIGH);
                             ui.separator();
-                            ui.add_sized([average, double], Label::new("Average"))
-                                .on_hover_text(STATUS_SUBMENU_OTHER_AVERAGE);
+                            header(
+                                ui,
+                                average,
+                                "Average",
+                                BenchmarkSort::Average,
+                                STATUS_SUBMENU_OTHER_AVERAGE,
+                            );
                             ui.separator();
                             ui.add_sized([low, double], Label::new("Low"))
                                 .on_hover_text(STATUS_SUBMENU_OTHER_LOW);
                             ui.separator();
-                            ui.add_sized([rank, double], Label::new("Rank"))
-                                .on_hover_text(STATUS_SUBMENU_OTHER_RANK);
+                            header(
+                                ui,
+                                rank,
+                                "Rank",
+                                BenchmarkSort::Rank,
+                                STATUS_SUBMENU_OTHER_RANK,
+                            );
+                            ui.separator();
+                            header(
+                                ui,
+                                per_core,
+                                "Per core",
+                                BenchmarkSort::PerCore,
+                                STATUS_SUBMENU_OTHER_PER_CORE,
+                            );
                             ui.separator();
                             ui.add_sized([bench, double], Label::new("Benchmarks"))
                                 .on_hover_text(STATUS_SUBMENU_OTHER_BENCHMARKS);
                         });
                     });
+                    if let Some(by) = sort {
+                        view.sort_by(by);
+                    }
 
-                    for benchmark in benchmarks[1..].iter() {
+                    for i in indices {
+                        let benchmark = &benchmarks[i];
                         ui.group(|ui| {
                             ui.horizontal(|ui| {
+                                let pinned = view.pinned.contains(&i);
+                                if ui
+                                    .add_enabled(
+                                        pinned || view.pinned.len() < BenchmarkView::MAX_PINNED,
+                                        |ui: &mut egui::Ui| {
+                                            ui.add_sized(
+                                                [pin, text],
+                                                SelectableLabel::new(pinned, "📌"),
+                                            )
+                                        },
+                                    )
+                                    .clicked()
+                                {
+                                    view.pin(i);
+                                }
+                                ui.separator();
                                 ui.add_sized([cpu, text], Label::new(benchmark.cpu.as_str()));
                                 ui.separator();
                                 ui.add_sized(
                                     [bar, text],
                                     ProgressBar::new(benchmark.percent / 100.0),
                                 )
-                                .on_hover_text(HumanNumber::to_percent(benchmark.percent).as_str());
+                                .on_hover_text(
+                                    HumanNumber::to_percent(benchmark.percent).localized(),
+                                );
                                 ui.separator();
                                 ui.add_sized(
                                     [high, text],
-                                    Label::new(HumanNumber::to_hashrate(benchmark.high).as_str()),
+                                    Label::new(
+                                        HumanNumber::to_hashrate(benchmark.high).localized(),
+                                    ),
                                 );
                                 ui.separator();
                                 ui.add_sized(
                                     [average, text],
                                     Label::new(
-                                        HumanNumber::to_hashrate(benchmark.average).as_str(),
+                                        HumanNumber::to_hashrate(benchmark.average).localized(),
                                     ),
                                 );
                                 ui.separator();
                                 ui.add_sized(
                                     [low, text],
-                                    Label::new(HumanNumber::to_hashrate(benchmark.low).as_str()),
+                                    Label::new(HumanNumber::to_hashrate(benchmark.low).localized()),
                                 );
                                 ui.separator();
                                 ui.add_sized(
                                     [rank, text],
-                                    Label::new(HumanNumber::from_u16(benchmark.rank).as_str()),
+                                    Label::new(HumanNumber::from_u16(benchmark.rank).localized()),
                                 );
                                 ui.separator();
+                                let text_per_core = match benchmark.per_core() {
+                                    Some(h) => HumanNumber::to_hashrate(h).localized(),
+                                    None => "?".to_string(),
+                                };
+                                ui.add_sized([per_core, text], Label::new(text_per_core));
+                                ui.separator();
                                 ui.add_sized(
                                     [bench, text],
                                     Label::new(
-                                        HumanNumber::from_u16(benchmark.benchmarks).as_str(),
+                                        HumanNumber::from_u16(benchmark.benchmarks).localized(),
                                     ),
                                 );
                             })
                         });
                     }
                 });
+        //---------------------------------------------------------------------------------------------------- [Consoles]
+        } else if self.submenu == Submenu::Consoles {
+            debug!("Status Tab | Rendering [Consoles]");
+            let text = height / 25.0;
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = (width / 5.0) - (SPACE * 2.0);
+                    if ui
+                        .add_sized(
+                            [width, text],
+                            SelectableLabel::new(
+                                self.console_layout == ConsoleLayout::SideBySide,
+                                "Side by side",
+                            ),
+                        )
+                        .on_hover_text(STATUS_CONSOLES_SIDE_BY_SIDE)
+                        .clicked()
+                    {
+                        self.console_layout = ConsoleLayout::SideBySide;
+                    }
+                    ui.separator();
+                    if ui
+                        .add_sized(
+                            [width, text],
+                            SelectableLabel::new(
+                                self.console_layout == ConsoleLayout::Stacked,
+                                "Stacked",
+                            ),
+                        )
+                        .on_hover_text(STATUS_CONSOLES_STACKED)
+                        .clicked()
+                    {
+                        self.console_layout = ConsoleLayout::Stacked;
+                    }
+                    ui.separator();
+                    ui.add_sized(
+                        [width, text],
+                        Checkbox::new(&mut self.console_sync, "Sync scroll"),
+                    )
+                    .on_hover_text(STATUS_CONSOLES_SYNC);
+                    ui.separator();
+                    ui.add_sized(
+                        [width, text],
+                        Checkbox::new(&mut p2pool_console.paused, "Pause P2Pool"),
+                    )
+                    .on_hover_text(STATUS_CONSOLES_PAUSE);
+                    ui.separator();
+                    ui.add_sized(
+                        [width, text],
+                        Checkbox::new(&mut xmrig_console.paused, "Pause XMRig"),
+                    )
+                    .on_hover_text(STATUS_CONSOLES_PAUSE);
+                });
+            });
+
+            const SPLITTER: f32 = 8.0;
+            let width = width - SPACE;
+            let height = ui.available_height() - SPACE;
+            let (p2pool_delta, xmrig_delta) = match self.console_layout {
+                ConsoleLayout::SideBySide => {
+                    let width = width - SPLITTER - SPACE;
+                    ui.horizontal(|ui| {
+                        let size = [width * self.console_split, height];
+                        let p2pool_delta = console(
+                            ui,
+                            "P2Pool",
+                            p2pool_console,
+                            &lock!(p2pool_api).output,
+                            colors,
+                            size,
+                        );
+                        let drag = splitter(ui, [SPLITTER, height], true);
+                        self.console_split = (self.console_split + drag / width).clamp(0.2, 0.8);
+                        let size = [width * (1.0 - self.console_split), height];
+                        let xmrig_delta = console(
+                            ui,
+                            "XMRig",
+                            xmrig_console,
+                            &lock!(xmrig_api).output,
+                            colors,
+                            size,
+                        );
+                        (p2pool_delta, xmrig_delta)
+                    })
+                    .inner
+                }
+                ConsoleLayout::Stacked => {
+                    let height = height - SPLITTER - (text * 2.0) - SPACE;
+                    ui.vertical(|ui| {
+                        let size = [width, height * self.console_split];
+                        let p2pool_delta = console(
+                            ui,
+                            "P2Pool",
+                            p2pool_console,
+                            &lock!(p2pool_api).output,
+                            colors,
+                            size,
+                        );
+                        let drag = splitter(ui, [width, SPLITTER], false);
+                        self.console_split = (self.console_split + drag / height).clamp(0.2, 0.8);
+                        let size = [width, height * (1.0 - self.console_split)];
+                        let xmrig_delta = console(
+                            ui,
+                            "XMRig",
+                            xmrig_console,
+                            &lock!(xmrig_api).output,
+                            colors,
+                            size,
+                        );
+                        (p2pool_delta, xmrig_delta)
+                    })
+                    .inner
+                }
+            };
+            if self.console_sync {
+                xmrig_console.scroll_by(p2pool_delta);
+                p2pool_console.scroll_by(xmrig_delta);
+            }
+        //---------------------------------------------------------------------------------------------------- [Events]
+        } else if self.submenu == Submenu::Events {
+            debug!("Status Tab | Rendering [Events]");
+            let text = height / 25.0;
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let cells = (Kind::ALL.len() + 2) as f32;
+                    let width = (width / cells) - (SPACE * 1.5);
+                    for kind in Kind::ALL {
+                        let shown = !self.events_hidden.contains(&kind);
+                        let label = RichText::new(kind.to_string()).color(kind.color());
+                        if ui
+                            .add_sized([width, text], SelectableLabel::new(shown, label))
+                            .on_hover_text(STATUS_EVENTS_FILTER)
+                            .clicked()
+                        {
+                            if shown {
+                                self.events_hidden.push(kind);
+                            } else {
+                                self.events_hidden.retain(|k| *k != kind);
+                            }
+                        }
+                        ui.separator();
+                    }
+                    if ui
+                        .add_sized([width, text], Button::new(self.payout_timezone.to_string()))
+                        .on_hover_text(STATUS_SUBMENU_TIMEZONE)
+                        .clicked()
+                    {
+                        self.payout_timezone = match self.payout_timezone {
+                            PayoutTimezone::Local => PayoutTimezone::Utc,
+                            PayoutTimezone::Utc => PayoutTimezone::Local,
+                        };
+                    }
+                    ui.separator();
+                    if ui
+                        .add_sized([width, text], Button::new("Clear"))
+                        .on_hover_text(STATUS_EVENTS_CLEAR)
+                        .clicked()
+                    {
+                        event::clear();
+                    }
+                });
+            });
+            // Newest first.
+            let events = event::recent(&self.events_hidden);
+            let row = ui.text_style_height(&Name("MonospaceSmall".into()));
+            egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
+                ui.style_mut().override_text_style = Some(Name("MonospaceSmall".into()));
+                if events.is_empty() {
+                    ui.add_sized([width, text], Label::new("No events yet"));
+                }
+                egui::ScrollArea::vertical()
+                    .max_width(width)
+                    .max_height(ui.available_height())
+                    .auto_shrink([false; 2])
+                    .show_rows(ui, row, events.len(), |ui, range| {
+                        for e in events.iter().rev().skip(range.start).take(range.len()) {
+                            let date = match self.payout_timezone {
+                                PayoutTimezone::Local => e
+                                    .time
+                                    .with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M:%S"),
+                                PayoutTimezone::Utc => e.time.format("%Y-%m-%d %H:%M:%S UTC"),
+                            };
+                            ui.label(
+                                RichText::new(format!("{} | {:<6} | {}", date, e.kind, e.msg))
+                                    .color(e.kind.color()),
+                            );
+                        }
+                    });
+            });
+        }
+    }
+}
+
+// Payout log with dates in [timezone] and numbers in the user's format.
+// What a [Processes] card needs from [App] for its buttons.
+pub struct ProcessCard<'a> {
+    pub process: &'a Arc<Mutex<Process>>,
+    pub start_error: Option<String>, // Why [Start] is disabled, if it is
+    pub availability: String,        // Up/failed time over the last 24h/7d
+}
+
+// [▶] [⟲] [⏹] [Console] under a process card's title, plus how it last exited.
+// The clicked signal goes to [signal] for [App] to run, same as the bottom bar buttons.
+fn process_controls(
+    ui: &mut egui::Ui,
+    card: &ProcessCard,
+    submenu: &mut Submenu,
+    signal: &mut Option<(ProcessName, ProcessSignal)>,
+    width: f32,
+    height: f32,
+) {
+    let (name, state, exit, alive, waiting) = {
+        let process = lock!(card.process);
+        (
+            process.name,
+            process.state,
+            process.exit.clone(),
+            process.is_alive(),
+            process.is_waiting(),
+        )
+    };
+    ui.horizontal(|ui| {
+        let width = (width - SPACE * 6.0) / 4.0;
+        for (text, button, enabled) in [
+            (
+                "▶",
+                ProcessSignal::Start,
+                !alive && !waiting && card.start_error.is_none(),
+            ),
+            ("⟲", ProcessSignal::Restart, alive && !waiting),
+            ("⏹", ProcessSignal::Stop, alive && !waiting),
+        ] {
+            let hover = format!("{} {}", button, name);
+            let disabled = match &card.start_error {
+                Some(error) if button == ProcessSignal::Start && !alive && !waiting => {
+                    error.clone()
+                }
+                _ => hover.clone(),
+            };
+            if ui
+                .add_enabled(enabled, Button::new(text).min_size([width, height].into()))
+                .on_hover_text(hover)
+                .on_disabled_hover_text(disabled)
+                .clicked()
+            {
+                *signal = Some((name, button));
+            }
+        }
+        if ui
+            .add_sized([width, height], Button::new("Console"))
+            .on_hover_text(STATUS_CARD_CONSOLE)
+            .clicked()
+        {
+            *submenu = Submenu::Consoles;
+        }
+    });
+    ui.add_sized(
+        [width, height],
+        Label::new(RichText::new(&card.availability).color(LIGHT_GRAY)),
+    )
+    .on_hover_text(STATUS_CARD_AVAILABILITY);
+    if alive || waiting {
+        return;
+    }
+    let (text, color) = match (state, exit.is_empty()) {
+        (_, false) => (format!("Last exit: {}", exit), RED),
+        (ProcessState::Failed, true) => ("Last exit: Failed".to_string(), RED),
+        _ => ("Offline".to_string(), LIGHT_GRAY),
+    };
+    ui.add_sized(
+        [width, height],
+        Label::new(RichText::new(text).color(color)),
+    )
+    .on_hover_text(STATUS_CARD_EXIT);
+}
+
+fn payout_text(log: &str, timezone: PayoutTimezone) -> String {
+    let log = PayoutOrd::map_log_dates(log, |date| PayoutOrd::display_date(date, timezone));
+    localize(&log).into_owned()
+}
+
+// The [Recent] payouts table, a total row above each day's payouts.
+fn recent_payouts(ui: &mut egui::Ui, days: &[report::Day], explorer: &str, width: f32) {
+    if days.is_empty() {
+        ui.label("No payouts yet");
+        return;
+    }
+    let column = width / 4.0 - SPACE * 2.0;
+    egui::Grid::new("recent_payouts")
+        .num_columns(4)
+        .min_col_width(column)
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Time", "XMR", "Atomic Units", "Block"] {
+                ui.label(RichText::new(header).underline().color(LIGHT_GRAY));
+            }
+            ui.end_row();
+            for day in days {
+                let payouts = match day.payouts.len() {
+                    1 => "1 payout".to_string(),
+                    n => format!("{} payouts", n),
+                };
+                ui.label(RichText::new(&day.date).color(BONE));
+                ui.label(RichText::new(localize(&format!("{} XMR", day.total))).color(BONE));
+                ui.label(
+                    RichText::new(HumanNumber::from_u64(day.total.to_u64()).localized())
+                        .color(BONE),
+                );
+                ui.label(RichText::new(payouts).color(BONE));
+                ui.end_row();
+                for payout in &day.payouts {
+                    ui.label(&payout.time);
+                    ui.label(localize(&format!("{} XMR", payout.xmr)));
+                    ui.label(HumanNumber::from_u64(payout.xmr.to_u64()).localized());
+                    let block = HumanNumber::from_u64(payout.height).localized();
+                    let url = crate::xmr::explorer_url(explorer, payout.height);
+                    ui.hyperlink_to(block, &url).on_hover_text(&url);
+                    ui.end_row();
+                }
+            }
+        });
+}
+
+// A single payout log line, the block links to the
+// [explorer] and its hash can be copied from the node.
+fn payout_row(
+    ui: &mut egui::Ui,
+    line: &str,
+    timezone: PayoutTimezone,
+    explorer: &str,
+    block_hash: &Arc<Mutex<crate::node::BlockHash>>,
+    ip: &str,
+    port: &str,
+) {
+    let text = payout_text(line, timezone);
+    let text = text.trim_end();
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        let (Some(height), Some((payout, block))) =
+            (PayoutOrd::line_block_height(line), text.rsplit_once(" | "))
+        else {
+            ui.label(text);
+            return;
+        };
+        ui.label(format!("{} | ", payout));
+        let url = crate::xmr::explorer_url(explorer, height);
+        ui.hyperlink_to(block, &url).on_hover_text(&url);
+        ui.spacing_mut().item_spacing.x = SPACE;
+        ui.add_space(SPACE);
+        let running = lock!(block_hash).running;
+        if ui
+            .add_enabled(!running, Button::new("Copy hash").small())
+            .on_hover_text(STATUS_SUBMENU_COPY_HASH)
+            .clicked()
+        {
+            crate::node::BlockHash::spawn_thread(block_hash, ip, port, height);
         }
+    });
+}
+
+// A titled console for the [Consoles] submenu, returns the user scroll delta.
+fn console(
+    ui: &mut egui::Ui,
+    name: &str,
+    console: &mut Console,
+    output: &str,
+    colors: bool,
+    size: [f32; 2],
+) -> f32 {
+    ui.vertical(|ui| {
+        ui.add_sized(
+            [size[0], size[1] / 20.0],
+            Label::new(RichText::new(name).underline().color(BONE)),
+        );
+        egui::Frame::none()
+            .fill(DARK_GRAY)
+            .show(ui, |ui| {
+                ui.style_mut().override_text_style = Some(TextStyle::Name("MonospaceSmall".into()));
+                console.show_scroll(ui, name, output, colors, size)
+            })
+            .inner
+    })
+    .inner
+}
+
+// A draggable bar between the consoles, returns how far it was dragged.
+// The [Layout] bar on top of a submenu, returns its height.
+fn layout_bar(
+    ui: &mut egui::Ui,
+    layout: &mut Vec<StatusGroup>,
+    collapsed: &mut Vec<StatusGroup>,
+    all: &[StatusGroup],
+    width: f32,
+    height: f32,
+) -> f32 {
+    *layout = StatusGroup::layout(layout, all);
+    let cells = (layout.len() + 1) as f32;
+    let width = (width - SPACE * 2.0 * cells) / cells;
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.add_sized(
+                [width, height],
+                Label::new(RichText::new("Layout").underline().color(LIGHT_GRAY)),
+            )
+            .on_hover_text(STATUS_LAYOUT);
+            for group in layout.clone() {
+                ui.separator();
+                let shown = !collapsed.contains(&group);
+                let response =
+                    ui.dnd_drag_source(egui::Id::new(("status_layout", group)), group, |ui| {
+                        ui.add_sized(
+                            [width, height],
+                            SelectableLabel::new(shown, group.to_string()),
+                        )
+                        .on_hover_text(STATUS_LAYOUT)
+                    });
+                if response.inner.clicked() {
+                    if shown {
+                        collapsed.push(group);
+                    } else {
+                        collapsed.retain(|g| *g != group);
+                    }
+                }
+                if let Some(dragged) = response.response.dnd_release_payload::<StatusGroup>() {
+                    info!("Status | Moving [{}] to [{}]", dragged, group);
+                    StatusGroup::move_to(layout, *dragged, group);
+                }
+            }
+        });
+    })
+    .response
+    .rect
+    .height()
+}
+
+fn splitter(ui: &mut egui::Ui, size: [f32; 2], vertical: bool) -> f32 {
+    let (rect, response) = ui.allocate_exact_size(size.into(), egui::Sense::drag());
+    if response.hovered() || response.dragged() {
+        ui.ctx().set_cursor_icon(if vertical {
+            egui::CursorIcon::ResizeHorizontal
+        } else {
+            egui::CursorIcon::ResizeVertical
+        });
+    }
+    let color = if response.dragged() { LIGHT_GRAY } else { GRAY };
+    ui.painter().rect_filled(rect.shrink(2.0), 2.0, color);
+    let drag = response.drag_delta();
+    if vertical {
+        drag.x
+    } else {
+        drag.y
+    }
+}
+
+// p50 (green) and p95 (yellow) share latency after each share, oldest on the left.
+fn latency_chart(ui: &mut egui::Ui, latency: &crate::latency::ShareLatency, size: [f32; 2]) {
+    let (rect, response) = ui.allocate_exact_size(size.into(), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let max = latency
+        .history
+        .iter()
+        .map(|[_, p95]| *p95)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f32;
+    let step = rect.width() / (latency.history.len().max(2) - 1) as f32;
+    for (i, color) in [(0, GREEN), (1, YELLOW)] {
+        let points = latency
+            .history
+            .iter()
+            .enumerate()
+            .map(|(x, ms)| {
+                egui::pos2(
+                    rect.left() + x as f32 * step,
+                    rect.bottom() - (ms[i] as f32 / max) * (rect.height() - 2.0) - 1.0,
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
     }
+    response.on_hover_text(format!(
+        "{}\n\nGreen = p50, Yellow = p95, top = {} ms",
+        STATUS_XMRIG_SHARE_LATENCY, max
+    ));
+}
+
+// A stat's hover text, with the formula behind it and its current inputs below.
+fn formula_hover(ui: &mut egui::Ui, text: &str, formula: &str) {
+    ui.label(text);
+    ui.separator();
+    ui.label(RichText::new(formula).monospace());
 }
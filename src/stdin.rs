@@ -0,0 +1,299 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The P2Pool/XMRig console input box.
+//
+// Keeps a per-process history of sent commands (Up/Down inside the box),
+// and completes a command on [Tab] from the known console commands and the
+// history. The history can optionally be saved to a plain text file in the
+// Gupax data folder, one command per line, newest last.
+
+use crate::{constants::*, macros::*, Process};
+use egui::text::{CCursor, CCursorRange};
+use egui::{Label, RichText, TextEdit};
+use log::*;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How many commands are remembered.
+const MAX_HISTORY: usize = 100;
+
+//---------------------------------------------------------------------------------------------------- Stdin
+#[derive(Clone, Debug, Default)]
+pub struct Stdin {
+    pub buffer: String,    // The buffer between the console and the [Helper]
+    history: Vec<String>,  // Sent commands, newest last
+    index: Option<usize>,  // Position in [history] while browsing, [None] is the draft
+    draft: String,         // What was typed before browsing the history
+    matches: String,       // Ambiguous [Tab] completions, shown under the box
+    path: Option<PathBuf>, // Save the history here
+    loaded: bool,          // Was the history file read yet?
+}
+
+impl Stdin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Start/stop saving the history to [path], it's read the first time.
+    pub fn set_path(&mut self, path: Option<&Path>) {
+        if self.path.as_deref() == path {
+            return;
+        }
+        self.path = path.map(Path::to_path_buf);
+        let Some(path) = path else {
+            return;
+        };
+        if !self.loaded {
+            self.loaded = true;
+            match std::fs::read_to_string(path) {
+                Ok(s) => {
+                    let mut old: Vec<String> = s
+                        .lines()
+                        .filter(|l| !l.trim().is_empty())
+                        .map(String::from)
+                        .collect();
+                    info!(
+                        "Stdin | Read [{}] commands from [{}]",
+                        old.len(),
+                        path.display()
+                    );
+                    old.append(&mut self.history);
+                    self.history = old;
+                    self.trim();
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+                Err(e) => warn!("Stdin | Could not read [{}]: {}", path.display(), e),
+            }
+        }
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut s = self.history.join("\n");
+        s.push('\n');
+        if let Err(e) = std::fs::write(path, s) {
+            warn!("Stdin | Could not write [{}]: {}", path.display(), e);
+        }
+    }
+
+    fn trim(&mut self) {
+        let len = self.history.len();
+        if len > MAX_HISTORY {
+            self.history.drain(..len - MAX_HISTORY);
+        }
+    }
+
+    // Take the buffer and remember it.
+    pub fn submit(&mut self) -> String {
+        let buffer = std::mem::take(&mut self.buffer);
+        self.index = None;
+        self.draft.clear();
+        self.matches.clear();
+        let command = buffer.trim();
+        if !command.is_empty() && self.history.last().map(String::as_str) != Some(command) {
+            self.history.push(command.to_string());
+            self.trim();
+            self.save();
+        }
+        buffer
+    }
+
+    // Older command.
+    pub fn up(&mut self) {
+        let index = match self.index {
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.buffer.clone();
+                self.history.len() - 1
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+        self.index = Some(index);
+        self.buffer = self.history[index].clone();
+    }
+
+    // Newer command, past the newest one is the draft again.
+    pub fn down(&mut self) {
+        let Some(i) = self.index else {
+            return;
+        };
+        if i + 1 < self.history.len() {
+            self.index = Some(i + 1);
+            self.buffer = self.history[i + 1].clone();
+        } else {
+            self.index = None;
+            self.buffer = std::mem::take(&mut self.draft);
+        }
+    }
+
+    // Complete the buffer from [commands] and the history. A single match is
+    // filled in, several are extended to their common prefix and listed.
+    pub fn complete(&mut self, commands: &[&str]) {
+        self.matches.clear();
+        let prefix = self.buffer.trim_start();
+        if prefix.is_empty() {
+            return;
+        }
+        let mut matches: Vec<&str> = Vec::new();
+        for c in commands
+            .iter()
+            .copied()
+            .chain(self.history.iter().rev().map(String::as_str))
+        {
+            if c.starts_with(prefix) && c != prefix && !matches.contains(&c) {
+                matches.push(c);
+            }
+        }
+        let Some(first) = matches.first() else {
+            return;
+        };
+        let mut common = first.len();
+        for m in &matches[1..] {
+            common = common.min(
+                first
+                    .char_indices()
+                    .zip(m.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(first.len().min(m.len()), |((i, _), _)| i),
+            );
+        }
+        let completed = if matches.len() == 1 {
+            format!("{} ", first)
+        } else {
+            self.matches = matches.join("  ");
+            first[..common].to_string()
+        };
+        if completed.len() > prefix.len() {
+            self.buffer = completed;
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        process: &Arc<Mutex<Process>>,
+        hint: &str,
+        hover: &str,
+        commands: &[&str],
+        size: [f32; 2],
+    ) {
+        let response = ui
+            .add_sized(
+                size,
+                TextEdit::hint_text(TextEdit::singleline(&mut self.buffer), hint).lock_focus(true),
+            )
+            .on_hover_text(hover);
+        // If the user pressed enter, dump buffer contents into the process STDIN
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            response.request_focus(); // Get focus back
+            let buffer = self.submit();
+            let mut process = lock!(process); // Lock
+            if process.is_alive() {
+                process.input.push(buffer);
+            } // Push only if alive
+        } else if response.has_focus() {
+            let old = self.buffer.clone();
+            ui.input(|i| {
+                if i.key_pressed(egui::Key::ArrowUp) {
+                    self.up();
+                } else if i.key_pressed(egui::Key::ArrowDown) {
+                    self.down();
+                } else if i.key_pressed(egui::Key::Tab) {
+                    self.complete(commands);
+                }
+            });
+            // Put the cursor at the end of the new text.
+            if self.buffer != old {
+                if let Some(mut state) = TextEdit::load_state(ui.ctx(), response.id) {
+                    let end = CCursor::new(self.buffer.chars().count());
+                    state.cursor.set_char_range(Some(CCursorRange::one(end)));
+                    state.store(ui.ctx(), response.id);
+                }
+            }
+        }
+        if !self.matches.is_empty() {
+            ui.add_sized(
+                [size[0], size[1] / 1.5],
+                Label::new(RichText::new(&self.matches).color(LIGHT_GRAY)),
+            );
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn history() {
+        use super::{Stdin, MAX_HISTORY};
+        let mut stdin = Stdin::new();
+        stdin.up();
+        assert!(stdin.buffer.is_empty());
+        for c in ["status", "status", "peers", "  "] {
+            stdin.buffer = c.to_string();
+            stdin.submit();
+        }
+        assert_eq!(stdin.history, ["status", "peers"]);
+        stdin.buffer = "wor".to_string();
+        stdin.up();
+        assert_eq!(stdin.buffer, "peers");
+        stdin.up();
+        stdin.up();
+        assert_eq!(stdin.buffer, "status");
+        stdin.down();
+        assert_eq!(stdin.buffer, "peers");
+        stdin.down();
+        assert_eq!(stdin.buffer, "wor");
+        for i in 0..MAX_HISTORY + 10 {
+            stdin.buffer = i.to_string();
+            stdin.submit();
+        }
+        assert_eq!(stdin.history.len(), MAX_HISTORY);
+        assert_eq!(stdin.history[0], "10");
+    }
+
+    #[test]
+    fn complete() {
+        use super::Stdin;
+        let commands = ["status", "start_mining", "stop_mining", "peers"];
+        let mut stdin = Stdin::new();
+        stdin.buffer = "pe".to_string();
+        stdin.complete(&commands);
+        assert_eq!(stdin.buffer, "peers ");
+        stdin.buffer = "st".to_string();
+        stdin.complete(&commands);
+        assert_eq!(stdin.buffer, "st");
+        assert_eq!(stdin.matches, "status  start_mining  stop_mining");
+        stdin.buffer = "sta".to_string();
+        stdin.complete(&commands);
+        assert_eq!(stdin.buffer, "sta");
+        stdin.buffer = "star".to_string();
+        stdin.complete(&commands);
+        assert_eq!(stdin.buffer, "start_mining ");
+        // The history is completed too.
+        stdin.buffer = "loglevel 3".to_string();
+        stdin.submit();
+        stdin.buffer = "log".to_string();
+        stdin.complete(&commands);
+        assert_eq!(stdin.buffer, "loglevel 3 ");
+    }
+}
@@ -18,18 +18,253 @@
 // Handling of [sudo] for XMRig.
 // [zeroize] is used to wipe the memory after use.
 // Only gets imported in [main.rs] for Unix.
+//
+// [sudo] is the default launcher, [doas] and [pkexec] (polkit) can
+// be picked in the [Gupax] tab or auto-detected from the [PATH].
+// Each one is a small [Launcher] backend.
 
 use crate::{constants::*, disk::Xmrig, macros::*, Helper, ProcessSignal};
 use log::*;
+use serde::{Deserialize, Serialize};
 use std::{
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::*,
     sync::{Arc, Mutex},
     thread,
 };
 use zeroize::Zeroize;
 
+//---------------------------------------------------------------------------------------------------- Escalation
+// Which program XMRig is started through on Unix.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Escalation {
+    #[default]
+    Auto,
+    Sudo,
+    Doas,
+    Pkexec,
+}
+
+impl Escalation {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::Sudo, Self::Doas, Self::Pkexec];
+
+    // [Auto] picks the first of [sudo/doas/pkexec] found in the [PATH],
+    // falling back to [sudo] so the error says what's missing.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => [Self::Sudo, Self::Doas, Self::Pkexec]
+                .into_iter()
+                .find(|e| in_path(e.launcher().program()))
+                .unwrap_or(Self::Sudo),
+            e => e,
+        }
+    }
+
+    pub fn launcher(self) -> &'static dyn Launcher {
+        match self {
+            Self::Auto | Self::Sudo => &Sudo,
+            Self::Doas => &Doas,
+            Self::Pkexec => &Pkexec,
+        }
+    }
+}
+
+impl std::fmt::Display for Escalation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "Auto"),
+            e => write!(f, "{}", e.launcher().program()),
+        }
+    }
+}
+
+fn in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+//---------------------------------------------------------------------------------------------------- Launcher
+pub trait Launcher: Sync {
+    // The program that actually gets spawned, with XMRig as its argument.
+    fn program(&self) -> &'static str;
+    // Its arguments before XMRig's own.
+    fn args(&self, xmrig: &Path) -> Vec<String>;
+    // Does Gupax ask for the password, or does the launcher itself?
+    fn needs_password(&self) -> bool {
+        true
+    }
+    // Hint text for the password box.
+    fn prompt(&self) -> &'static str;
+    // Check [pass] before starting XMRig with it.
+    fn test(&self, pass: &str) -> Result<bool, String>;
+}
+
+struct Sudo;
+struct Doas;
+struct Pkexec;
+
+impl Launcher for Sudo {
+    fn program(&self) -> &'static str {
+        "sudo"
+    }
+
+    // Set the prompt to emptiness so that it doesn't show up in the output.
+    fn args(&self, xmrig: &Path) -> Vec<String> {
+        vec![
+            "--prompt=".to_string(),
+            "--".to_string(),
+            xmrig.display().to_string(),
+        ]
+    }
+
+    fn prompt(&self) -> &'static str {
+        PASSWORD_TEXT
+    }
+
+    // Sudo takes the password through STDIN via [--stdin].
+    fn test(&self, pass: &str) -> Result<bool, String> {
+        // Make sure sudo timestamp is reset
+        let reset = Command::new("sudo")
+            .arg("--reset-timestamp")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::piped())
+            .status();
+        match reset {
+            Ok(_) => info!("Sudo | Resetting timestamp ... OK"),
+            Err(e) => {
+                error!("Sudo | Couldn't reset timestamp: {}", e);
+                return Err(format!("Sudo error: {}", e));
+            }
+        }
+
+        // Spawn testing sudo
+        let mut sudo = Command::new("sudo")
+            .args(["--stdin", "--validate"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Sudo error: {}", e))?;
+
+        // Write pass to STDIN
+        let mut stdin = sudo.stdin.take().unwrap();
+        stdin.write_all(pass.as_bytes()).unwrap();
+        drop(stdin);
+
+        // Sudo re-prompts and will hang.
+        // To workaround this, try checking
+        // results for 5 seconds in a loop.
+        let mut success = false;
+        for i in 1..=5 {
+            match sudo.try_wait() {
+                Ok(Some(code)) => {
+                    if code.success() {
+                        info!("Sudo | Password ... OK!");
+                        success = true;
+                        break;
+                    }
+                }
+                Ok(None) => {
+                    info!("Sudo | Waiting [{}/5]...", i);
+                    std::thread::sleep(SECOND);
+                }
+                Err(e) => {
+                    error!("Sudo | Couldn't reset timestamp: {}", e);
+                    return Err(format!("Sudo error: {}", e));
+                }
+            }
+        }
+        if let Err(e) = sudo.kill() {
+            warn!("Sudo | Kill error (it probably already exited): {}", e);
+        }
+        Ok(success)
+    }
+}
+
+impl Launcher for Doas {
+    fn program(&self) -> &'static str {
+        "doas"
+    }
+
+    fn args(&self, xmrig: &Path) -> Vec<String> {
+        vec!["--".to_string(), xmrig.display().to_string()]
+    }
+
+    fn prompt(&self) -> &'static str {
+        "Enter doas password..."
+    }
+
+    // [doas] only reads the password from a terminal, so test it in a PTY.
+    fn test(&self, pass: &str) -> Result<bool, String> {
+        let pty = portable_pty::native_pty_system();
+        let pair = pty
+            .openpty(portable_pty::PtySize::default())
+            .map_err(|e| format!("Doas error: {}", e))?;
+        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new("doas");
+        cmd.args(["--", "true"]);
+        let mut child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| format!("Doas error: {}", e))?;
+        drop(pair.slave);
+        let mut stdin = pair
+            .master
+            .take_writer()
+            .map_err(|e| format!("Doas error: {}", e))?;
+        // Wait for the (non-echo) prompt.
+        std::thread::sleep(SECOND);
+        if let Err(e) = writeln!(stdin, "{}", pass) {
+            error!("Doas | STDIN error: {}", e);
+        }
+        for i in 1..=5 {
+            match child.try_wait() {
+                Ok(Some(code)) => {
+                    info!(
+                        "Doas | Password ... {}",
+                        if code.success() { "OK!" } else { "FAIL" }
+                    );
+                    return Ok(code.success());
+                }
+                Ok(None) => {
+                    info!("Doas | Waiting [{}/5]...", i);
+                    std::thread::sleep(SECOND);
+                }
+                Err(e) => return Err(format!("Doas error: {}", e)),
+            }
+        }
+        if let Err(e) = child.kill() {
+            warn!("Doas | Kill error (it probably already exited): {}", e);
+        }
+        Ok(false)
+    }
+}
+
+impl Launcher for Pkexec {
+    fn program(&self) -> &'static str {
+        "pkexec"
+    }
+
+    fn args(&self, xmrig: &Path) -> Vec<String> {
+        vec![xmrig.display().to_string()]
+    }
+
+    fn needs_password(&self) -> bool {
+        false
+    }
+
+    fn prompt(&self) -> &'static str {
+        "pkexec asks for the password itself"
+    }
+
+    // The polkit agent shows its own password dialog when XMRig starts.
+    fn test(&self, _pass: &str) -> Result<bool, String> {
+        Ok(true)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SudoState
 #[derive(Debug, Clone)]
 pub struct SudoState {
     pub windows: bool, // If this bool is set, this struct is just a dummy so I don't have to change my type signatures :)
@@ -39,6 +274,8 @@ pub struct SudoState {
     pub msg: String,   // The message shown to the user if unsuccessful
     pub pass: String,  // The actual password wrapped in a [SecretVec]
     pub signal: ProcessSignal, // Main GUI will set this depending on if we want [Start] or [Restart]
+    pub escalation: Escalation, // The setting [launcher] was resolved from
+    pub launcher: Escalation,  // What XMRig is actually started through, never [Auto]
 }
 
 impl Default for SudoState {
@@ -60,6 +297,8 @@ impl SudoState {
             msg: String::new(),
             pass: String::new(),
             signal: ProcessSignal::None,
+            escalation: Escalation::Sudo,
+            launcher: Escalation::Sudo,
         }
     }
     #[cold]
@@ -74,6 +313,8 @@ impl SudoState {
             msg: "".to_string(),
             pass: String::with_capacity(256),
             signal: ProcessSignal::None,
+            escalation: Escalation::Sudo,
+            launcher: Escalation::Sudo,
         }
     }
 
@@ -104,8 +345,7 @@ impl SudoState {
 
     #[cold]
     #[inline(never)]
-    // Spawns a thread and tests sudo with the provided password.
-    // Sudo takes the password through STDIN via [--stdin].
+    // Spawns a thread and tests the provided password with the [Launcher].
     // Sets the appropriate state fields on success/failure.
     pub fn test_sudo(
         state: Arc<Mutex<Self>>,
@@ -120,66 +360,22 @@ impl SudoState {
             // Set to testing
             lock!(state).testing = true;
 
-            // Make sure sudo timestamp is reset
-            let reset = Command::new("sudo")
-                .arg("--reset-timestamp")
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .stdin(Stdio::piped())
-                .status();
-            match reset {
-                Ok(_) => info!("Sudo | Resetting timestamp ... OK"),
-                Err(e) => {
-                    error!("Sudo | Couldn't reset timestamp: {}", e);
+            // Test with a copy so the GUI isn't locked out meanwhile.
+            let (launcher, mut pass) = {
+                let lock = lock!(state);
+                (lock.launcher.launcher(), lock.pass.clone())
+            };
+            let result = launcher.test(&pass);
+            pass.zeroize();
+            match result {
+                Ok(success) => lock!(state).success = success,
+                Err(msg) => {
                     Self::wipe(&state);
-                    lock!(state).msg = format!("Sudo error: {}", e);
+                    lock!(state).msg = msg;
                     lock!(state).testing = false;
                     return;
                 }
             }
-
-            // Spawn testing sudo
-            let mut sudo = Command::new("sudo")
-                .args(["--stdin", "--validate"])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .stdin(Stdio::piped())
-                .spawn()
-                .unwrap();
-
-            // Write pass to STDIN
-            let mut stdin = sudo.stdin.take().unwrap();
-            stdin.write_all(lock!(state).pass.as_bytes()).unwrap();
-            drop(stdin);
-
-            // Sudo re-prompts and will hang.
-            // To workaround this, try checking
-            // results for 5 seconds in a loop.
-            for i in 1..=5 {
-                match sudo.try_wait() {
-                    Ok(Some(code)) => {
-                        if code.success() {
-                            info!("Sudo | Password ... OK!");
-                            lock!(state).success = true;
-                            break;
-                        }
-                    }
-                    Ok(None) => {
-                        info!("Sudo | Waiting [{}/5]...", i);
-                        std::thread::sleep(SECOND);
-                    }
-                    Err(e) => {
-                        error!("Sudo | Couldn't reset timestamp: {}", e);
-                        Self::wipe(&state);
-                        lock!(state).msg = format!("Sudo error: {}", e);
-                        lock!(state).testing = false;
-                        return;
-                    }
-                }
-            }
-            if let Err(e) = sudo.kill() {
-                warn!("Sudo | Kill error (it probably already exited): {}", e);
-            }
             if lock!(state).success {
                 match lock!(state).signal {
                     ProcessSignal::Restart => crate::helper::Helper::restart_xmrig(
@@ -197,7 +393,8 @@ impl SudoState {
                     ),
                 }
             } else {
-                lock!(state).msg = "Incorrect password! (or sudo timeout)".to_string();
+                let program = lock!(state).launcher.launcher().program();
+                lock!(state).msg = format!("Incorrect password! (or {} timeout)", program);
                 Self::wipe(&state);
             }
             lock!(state).signal = ProcessSignal::None;
@@ -205,3 +402,22 @@ impl SudoState {
         });
     }
 }
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn launcher_args() {
+        use super::Escalation;
+        use std::path::Path;
+        let path = Path::new("/tmp/xmrig");
+        assert_eq!(
+            Escalation::Sudo.launcher().args(path),
+            ["--prompt=", "--", "/tmp/xmrig"]
+        );
+        assert_eq!(Escalation::Doas.launcher().args(path), ["--", "/tmp/xmrig"]);
+        assert_eq!(Escalation::Pkexec.launcher().args(path), ["/tmp/xmrig"]);
+        assert!(!Escalation::Pkexec.launcher().needs_password());
+        assert_ne!(Escalation::Auto.resolve(), Escalation::Auto);
+    }
+}
@@ -0,0 +1,213 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Optional, anonymous usage statistics.
+//
+// Off by default. When turned on in the [Gupax] tab, a single small JSON
+// payload is sent once on startup: the Gupax version, OS, CPU architecture,
+// a coarse CPU family (e.g: "Ryzen", never the model) and which features
+// are turned on. No addresses, IPs, nodes, pools, hashrates or IDs, so two
+// reports from the same user can't be linked. The payload is built from the
+// saved settings and the exact same string is shown in the [Gupax] tab.
+// Like updates, it goes over Tor if [Update via Tor] is on.
+
+use crate::{constants::*, macros::*, update::ClientEnum, State, Update};
+use hyper::{Body, Request};
+use log::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+const URL: &str = "https://gupax.io/stats";
+const TIMEOUT: Duration = Duration::from_secs(20);
+// Tor has to bootstrap first.
+const TIMEOUT_TOR: Duration = Duration::from_secs(60);
+
+// Known CPU families, matched against the CPU brand string in order.
+const CPU_FAMILIES: [&str; 12] = [
+    "Threadripper",
+    "Ryzen",
+    "EPYC",
+    "Athlon",
+    "FX",
+    "Xeon",
+    "Core",
+    "Pentium",
+    "Celeron",
+    "Apple",
+    "Cortex",
+    "Neoverse",
+];
+
+//---------------------------------------------------------------------------------------------------- Payload
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Payload {
+    gupax: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    cpu: &'static str,
+    features: BTreeMap<&'static str, bool>,
+}
+
+impl Payload {
+    pub fn new(state: &State, cpu: &'static str) -> Self {
+        let features = BTreeMap::from([
+            ("auto_update", state.gupax.auto_update),
+            ("auto_p2pool", state.gupax.auto_p2pool),
+            ("auto_xmrig", state.gupax.auto_xmrig),
+            ("start_on_login", state.gupax.start_on_login),
+            ("global_hotkeys", state.gupax.global_hotkeys),
+            ("payout_sound", state.gupax.payout_sound),
+            ("event_log", state.gupax.event_log),
+            ("console_history", state.gupax.console_history),
+            ("update_via_tor", state.gupax.update_via_tor),
+            ("force_x11", state.gupax.gfx_x11),
+            ("p2pool_simple", state.p2pool.simple),
+            ("p2pool_mini", state.p2pool.mini),
+            ("p2pool_miner", state.p2pool.miner),
+            ("p2pool_tls", state.p2pool.tls),
+            ("p2pool_ssh", state.p2pool.ssh),
+            ("xmrig_simple", state.xmrig.simple),
+            ("xmrig_tls", state.xmrig.tls),
+            ("xmrig_instances", !state.xmrig.instances.is_empty()),
+        ]);
+        Self {
+            gupax: GUPAX_VERSION,
+            os: OS_NAME,
+            arch: std::env::consts::ARCH,
+            cpu,
+            features,
+        }
+    }
+
+    // The exact string that gets sent.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+// Reduce a CPU brand string to its family, anything unknown is "Other".
+pub fn cpu_family(brand: &str) -> &'static str {
+    CPU_FAMILIES
+        .iter()
+        .find(|family| {
+            brand
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| word == **family)
+        })
+        .copied()
+        .unwrap_or("Other")
+}
+
+//---------------------------------------------------------------------------------------------------- UsageStats
+#[derive(Debug)]
+pub struct UsageStats {
+    pub cpu: &'static str, // Coarse CPU family, see [cpu_family()]
+    pub msg: String,       // Result of this session's report
+}
+
+impl UsageStats {
+    pub fn new(cpu: &'static str) -> Self {
+        Self {
+            cpu,
+            msg: "Nothing was sent this session".to_string(),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(stats: &Arc<Mutex<Self>>, state: &State) {
+        let body = Payload::new(state, lock!(stats).cpu).to_json();
+        let tor = state.gupax.update_via_tor;
+        let stats = Arc::clone(stats);
+        info!(
+            "Usage Stats | Sending (Tor: {}): {}",
+            tor,
+            body.replace('\n', "")
+        );
+        std::thread::spawn(move || {
+            let msg = match Self::send(body, tor) {
+                Ok(status) if status.is_success() => "Sent on startup, thank you!".to_string(),
+                Ok(status) => format!("Sending failed: HTTP {}", status),
+                Err(e) => format!("Sending failed: {}", e),
+            };
+            info!("Usage Stats | {}", msg);
+            lock!(stats).msg = msg;
+        });
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[tokio::main(flavor = "current_thread")]
+    async fn send(body: String, tor: bool) -> Result<hyper::StatusCode, anyhow::Error> {
+        let request = Request::builder()
+            .method("POST")
+            .uri(URL)
+            .header("User-Agent", crate::Pkg::get_user_agent())
+            .header("Content-Type", "application/json")
+            .body(Body::from(body))?;
+        let timed_out = |_| anyhow::anyhow!("timed out");
+        let response = match Update::get_client(tor)? {
+            ClientEnum::Tor(t) => tokio::time::timeout(TIMEOUT_TOR, t.request(request))
+                .await
+                .map_err(timed_out)??,
+            ClientEnum::Https(h) => tokio::time::timeout(TIMEOUT, h.request(request))
+                .await
+                .map_err(timed_out)??,
+        };
+        Ok(response.status())
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn cpu_family() {
+        use super::cpu_family;
+        assert_eq!(cpu_family("AMD Ryzen 9 5950X 16-Core Processor"), "Ryzen");
+        assert_eq!(
+            cpu_family("AMD Ryzen Threadripper 3990X 64-Core Processor"),
+            "Threadripper"
+        );
+        assert_eq!(
+            cpu_family("Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz"),
+            "Core"
+        );
+        assert_eq!(cpu_family("Intel(R) Xeon(R) CPU E5-2680 v4"), "Xeon");
+        assert_eq!(cpu_family("Apple M1 Pro"), "Apple");
+        assert_eq!(cpu_family("Some Unreleased CPU"), "Other");
+    }
+
+    #[test]
+    fn payload_is_anonymous() {
+        use super::Payload;
+        let mut state = crate::State::new();
+        state.p2pool.address = "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW".to_string();
+        state.p2pool.node = "my-own-node".to_string();
+        state.xmrig.simple_rig = "my-rig-name".to_string();
+        let json = Payload::new(&state, "Ryzen").to_json();
+        assert!(json.contains(r#""cpu": "Ryzen""#));
+        assert!(json.contains(r#""p2pool_mini": "#));
+        // Nothing that could identify the user.
+        assert!(!json.contains(&state.p2pool.address));
+        assert!(!json.contains(&state.p2pool.node));
+        assert!(!json.contains(&state.xmrig.simple_rig));
+    }
+}
@@ -0,0 +1,191 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// One-shot "mine for N hours" timer in the bottom bar.
+//
+// Simpler than scheduling: pick a duration and what to do when it runs
+// out, the bottom bar counts down and [App] acts once when it hits zero.
+// It isn't saved, closing Gupax cancels it.
+
+use crate::constants::*;
+use egui::{Button, ComboBox, Slider};
+use std::time::{Duration, Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const MIN_HOURS: f32 = 0.5;
+pub const MAX_HOURS: f32 = 24.0;
+const DEFAULT_HOURS: f32 = 4.0;
+
+//---------------------------------------------------------------------------------------------------- TimerAction
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TimerAction {
+    StopBoth,
+    StopXmrig,
+    PauseXmrig, // [p] to XMRig's STDIN, P2Pool keeps running
+}
+
+impl TimerAction {
+    pub const ALL: [Self; 3] = [Self::StopBoth, Self::StopXmrig, Self::PauseXmrig];
+}
+
+impl std::fmt::Display for TimerAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Self::StopBoth => "Stop P2Pool + XMRig",
+            Self::StopXmrig => "Stop XMRig",
+            Self::PauseXmrig => "Pause XMRig",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Timer
+#[derive(Debug)]
+pub struct Timer {
+    pub hours: f32,
+    pub action: TimerAction,
+    end: Option<Instant>,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer {
+    pub const fn new() -> Self {
+        Self {
+            hours: DEFAULT_HOURS,
+            action: TimerAction::StopBoth,
+            end: None,
+        }
+    }
+
+    pub fn start(&mut self) {
+        let hours = self.hours.clamp(MIN_HOURS, MAX_HOURS);
+        self.end = Some(Instant::now() + Duration::from_secs_f32(hours * 3600.0));
+    }
+
+    pub fn cancel(&mut self) {
+        self.end = None;
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        self.end
+            .map(|end| end.saturating_duration_since(Instant::now()))
+    }
+
+    // The action once the timer ran out, this also turns it off.
+    pub fn due(&mut self) -> Option<TimerAction> {
+        if self.remaining()? == Duration::ZERO {
+            self.end = None;
+            Some(self.action)
+        } else {
+            None
+        }
+    }
+
+    // [⏱ 3:59:12] while running, [⏱ Timer] when off.
+    pub fn text(&self) -> String {
+        match self.remaining() {
+            Some(left) => format!("⏱ {}", countdown(left)),
+            None => "⏱ Timer".to_string(),
+        }
+    }
+
+    // The contents of the bottom bar [⏱] menu.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let width = ui.spacing().slider_width;
+        ui.add_enabled_ui(self.end.is_none(), |ui| {
+            ui.add(
+                Slider::new(&mut self.hours, MIN_HOURS..=MAX_HOURS)
+                    .step_by(0.5)
+                    .suffix(" hours"),
+            )
+            .on_hover_text(GUPAX_TIMER_HOURS);
+            ComboBox::from_id_source("timer_action")
+                .selected_text(self.action.to_string())
+                .width(width)
+                .show_ui(ui, |ui| {
+                    for action in TimerAction::ALL {
+                        ui.selectable_value(&mut self.action, action, action.to_string());
+                    }
+                })
+                .response
+                .on_hover_text(GUPAX_TIMER_ACTION);
+        });
+        match self.remaining() {
+            Some(left) => {
+                ui.label(format!("{} in {}", self.action, countdown(left)));
+                if ui.add(Button::new("Cancel")).clicked() {
+                    self.cancel();
+                    ui.close_menu();
+                }
+            }
+            None => {
+                if ui
+                    .add(Button::new("Start"))
+                    .on_hover_text(GUPAX_TIMER)
+                    .clicked()
+                {
+                    self.start();
+                    ui.close_menu();
+                }
+            }
+        }
+    }
+}
+
+// [h:mm:ss]
+fn countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn timer() {
+        use crate::timer::*;
+        let mut timer = Timer::new();
+        assert_eq!(timer.remaining(), None);
+        assert_eq!(timer.due(), None);
+        assert_eq!(timer.text(), "⏱ Timer");
+
+        timer.hours = 100.0;
+        timer.start();
+        let left = timer.remaining().unwrap();
+        assert!(left > Duration::from_secs(23 * 3600) && left <= Duration::from_secs(24 * 3600));
+        assert_eq!(timer.due(), None);
+        timer.cancel();
+        assert_eq!(timer.remaining(), None);
+
+        // Already ran out.
+        timer.action = TimerAction::PauseXmrig;
+        timer.end = Some(Instant::now());
+        assert_eq!(timer.due(), Some(TimerAction::PauseXmrig));
+        assert_eq!(timer.due(), None);
+
+        assert_eq!(countdown(Duration::from_secs(4 * 3600)), "4:00:00");
+        assert_eq!(
+            countdown(Duration::from_secs(3 * 3600 + 59 * 60 + 12)),
+            "3:59:12"
+        );
+    }
+}
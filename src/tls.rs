@@ -0,0 +1,333 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Stratum TLS terminator.
+//
+// P2Pool's stratum is plain TCP, which is fine on [localhost] but
+// not for other rigs mining to it over an untrusted network. This
+// listens for TLS connections (e.g: XMRig with [--tls]) and proxies
+// the decrypted stream to the local stratum. The certificate is
+// self-signed, rigs pin it with [--tls-fingerprint] instead.
+
+use crate::macros::*;
+use log::*;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+pub const DIRECTORY: &str = "tls";
+const CERT_FILE: &str = "stratum.crt.der";
+const KEY_FILE: &str = "stratum.key.der";
+// P2Pool's default stratum port, what everything gets proxied to.
+pub const STRATUM_PORT: u16 = 3333;
+// How often the listener checks if it should stop.
+const STOP_CHECK: Duration = Duration::from_secs(1);
+
+//---------------------------------------------------------------------------------------------------- Cert
+// A self-signed certificate + its PKCS#8 key, kept in [<data>/tls/].
+pub struct Cert {
+    pub der: Vec<u8>,
+    key: Vec<u8>,
+}
+
+impl Cert {
+    // Load the existing certificate, or generate one if there isn't one.
+    pub fn get(dir: &Path) -> anyhow::Result<Self> {
+        match (
+            std::fs::read(dir.join(CERT_FILE)),
+            std::fs::read(dir.join(KEY_FILE)),
+        ) {
+            (Ok(der), Ok(key)) => Ok(Self { der, key }),
+            _ => Self::generate(dir),
+        }
+    }
+
+    // Generate (and save) a new certificate, replacing the old one.
+    pub fn generate(dir: &Path) -> anyhow::Result<Self> {
+        info!(
+            "TLS | Generating self-signed certificate in [{}]",
+            dir.display()
+        );
+        let rcgen::CertifiedKey { cert, key_pair } =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()])?;
+        let new = Self {
+            der: cert.der().to_vec(),
+            key: key_pair.serialize_der(),
+        };
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(CERT_FILE), &new.der)?;
+        write_private(&dir.join(KEY_FILE), &new.key)?;
+        Ok(new)
+    }
+
+    // SHA-256 of the certificate, in the hex format XMRig's [--tls-fingerprint] takes.
+    pub fn fingerprint(&self) -> String {
+        ring::digest::digest(&ring::digest::SHA256, &self.der)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn server_config(&self) -> anyhow::Result<rustls::ServerConfig> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(self.der.clone())],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.key.clone())),
+            )?;
+        Ok(config)
+    }
+}
+
+// Write the private key readable only by us, remote rigs pin this certificate.
+// It's written to a temporary file first so an old, world-readable
+// key file doesn't keep its permissions.
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let tmp = path.with_extension("tmp");
+    if tmp.exists() {
+        std::fs::remove_file(&tmp)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)
+}
+
+// [<data>/tls/]
+pub fn cert_dir(os_data_path: &Path) -> PathBuf {
+    os_data_path.join(DIRECTORY)
+}
+
+//---------------------------------------------------------------------------------------------------- TlsProxy
+#[derive(Debug, Default)]
+pub struct TlsProxy {
+    pub running: bool,
+    pub port: u16,           // The TLS port being listened on
+    pub connections: usize,  // Currently open connections
+    pub fingerprint: String, // Of the certificate in use
+    pub error: String,       // Why the last start failed, empty if it didn't
+    stop: bool,
+}
+
+impl TlsProxy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Ask the listener to stop, open connections are left to finish.
+    pub fn stop(proxy: &Arc<Mutex<Self>>) {
+        let mut proxy = lock!(proxy);
+        if proxy.running {
+            info!("TLS | Stopping listener on [{}]", proxy.port);
+            proxy.stop = true;
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn spawn_thread(proxy: &Arc<Mutex<Self>>, cert_dir: PathBuf, port: u16, stratum: u16) {
+        let mut guard = lock!(proxy);
+        guard.running = true;
+        guard.stop = false;
+        guard.port = port;
+        guard.error.clear();
+        drop(guard);
+        let proxy = Arc::clone(proxy);
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build();
+            let result = match runtime {
+                Ok(runtime) => runtime.block_on(listen(&proxy, &cert_dir, port, stratum)),
+                Err(e) => Err(e.into()),
+            };
+            let mut proxy = lock!(proxy);
+            if let Err(e) = result {
+                error!("TLS | Listener on [{}] ... FAIL: {}", port, e);
+                proxy.error = e.to_string();
+            }
+            proxy.running = false;
+            proxy.stop = false;
+        });
+    }
+}
+
+async fn listen(
+    proxy: &Arc<Mutex<TlsProxy>>,
+    cert_dir: &Path,
+    port: u16,
+    stratum: u16,
+) -> anyhow::Result<()> {
+    let cert = Cert::get(cert_dir)?;
+    lock!(proxy).fingerprint = cert.fingerprint();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(cert.server_config()?));
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    info!(
+        "TLS | Listening on [0.0.0.0:{}] -> [127.0.0.1:{}]",
+        port, stratum
+    );
+    while !lock!(proxy).stop {
+        let (stream, addr) = match tokio::time::timeout(STOP_CHECK, listener.accept()).await {
+            Ok(Ok(accepted)) => accepted,
+            // e.g: out of file descriptors, or the client hung up
+            // before [accept()], the listener itself is fine.
+            Ok(Err(e)) => {
+                warn!("TLS | Could not accept connection on [{}]: {}", port, e);
+                continue;
+            }
+            Err(_) => continue,
+        };
+        let acceptor = acceptor.clone();
+        let proxy = Arc::clone(proxy);
+        tokio::spawn(async move {
+            lock!(proxy).connections += 1;
+            debug!("TLS | New connection from [{}]", addr);
+            if let Err(e) = forward(acceptor, stream, stratum).await {
+                debug!("TLS | Connection from [{}] closed: {}", addr, e);
+            }
+            lock!(proxy).connections -= 1;
+        });
+    }
+    info!("TLS | Listener on [{}] stopped", port);
+    Ok(())
+}
+
+async fn forward(
+    acceptor: tokio_rustls::TlsAcceptor,
+    stream: tokio::net::TcpStream,
+    stratum: u16,
+) -> std::io::Result<()> {
+    let mut tls = acceptor.accept(stream).await?;
+    let mut plain = tokio::net::TcpStream::connect(("127.0.0.1", stratum)).await?;
+    tokio::io::copy_bidirectional(&mut tls, &mut plain).await?;
+    Ok(())
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn cert_persists() {
+        use super::Cert;
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let cert = Cert::get(&dir).unwrap();
+        let fingerprint = cert.fingerprint();
+        assert_eq!(fingerprint.len(), 64);
+        assert!(fingerprint.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(Cert::get(&dir).unwrap().fingerprint(), fingerprint);
+        assert!(cert.server_config().is_ok());
+        assert_ne!(Cert::generate(&dir).unwrap().fingerprint(), fingerprint);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn key_is_private() {
+        use super::{Cert, KEY_FILE};
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = tempfile::tempdir().unwrap();
+        let key = tmp.path().join(KEY_FILE);
+        // An old key someone else could read.
+        std::fs::write(&key, b"old").unwrap();
+        std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o644)).unwrap();
+        Cert::generate(tmp.path()).unwrap();
+        let mode = std::fs::metadata(&key).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_ne!(std::fs::read(&key).unwrap(), b"old");
+    }
+
+    #[test]
+    fn proxy_forwards() {
+        use super::{Cert, TlsProxy};
+        use crate::macros::*;
+        use std::io::{Read, Write};
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A fake stratum that echoes back one line.
+        let stratum = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stratum_port = stratum.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = stratum.accept().unwrap();
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).unwrap();
+            stream.write_all(&buf).unwrap();
+        });
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let cert = Cert::get(&dir).unwrap();
+        let proxy = Arc::new(Mutex::new(TlsProxy::new()));
+        TlsProxy::spawn_thread(&proxy, dir.clone(), port, stratum_port);
+        while lock!(proxy).fingerprint.is_empty() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(lock!(proxy).fingerprint, cert.fingerprint());
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(cert.der.clone().into()).unwrap();
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let echo = runtime.block_on(async {
+            let stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+                .await
+                .unwrap();
+            let name = "localhost".try_into().unwrap();
+            let mut tls = connector.connect(name, stream).await.unwrap();
+            tls.write_all(b"hello").await.unwrap();
+            let mut buf = [0; 5];
+            tls.read_exact(&mut buf).await.unwrap();
+            buf
+        });
+        assert_eq!(&echo, b"hello");
+
+        TlsProxy::stop(&proxy);
+        while lock!(proxy).running {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(lock!(proxy).error.is_empty());
+    }
+}
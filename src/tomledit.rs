@@ -0,0 +1,167 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The raw [state.toml] editor on the [Gupax] tab.
+//
+// The text goes through the same pipeline as the file on startup:
+// [migrate()], then [State::from_str()] and [State::merge()] if that fails.
+// Nothing is written until it validates, and [Apply] replaces the file
+// atomically (temporary file + rename, see [write_toml()]).
+
+use crate::{constants::*, disk::State};
+use egui::{Button, Label, RichText, TextEdit};
+use log::*;
+
+//---------------------------------------------------------------------------------------------------- TomlEditor
+#[derive(Default)]
+pub struct TomlEditor {
+    pub text: String,
+    pub msg: String,
+    pub valid: bool,
+    pub pending: Option<State>, // [Apply] was clicked, taken by the GUI thread
+}
+
+// The validated [State] and a note if [merge()] had to fill in or drop keys.
+pub fn validate(text: &str) -> Result<(State, Option<&'static str>), String> {
+    let text = match crate::migrate::migrate(text) {
+        Some((_, migrated)) => migrated,
+        None => text.to_string(),
+    };
+    match State::from_str(&text) {
+        Ok(state) => Ok((state, None)),
+        Err(e) => match State::merge(&text) {
+            Ok(state) => Ok((state, Some(GUPAX_TOML_MERGED))),
+            Err(_) => Err(e.to_string()),
+        },
+    }
+}
+
+impl TomlEditor {
+    // Fill the editor with [og], the settings as they're saved.
+    pub fn load(&mut self, og: &State) {
+        match og.to_string() {
+            Ok(s) => {
+                self.text = s;
+                self.check();
+            }
+            Err(e) => {
+                self.msg = e.to_string();
+                self.valid = false;
+            }
+        }
+    }
+
+    fn check(&mut self) {
+        match validate(&self.text) {
+            Ok((_, None)) => {
+                self.msg = "Valid".to_string();
+                self.valid = true;
+            }
+            Ok((_, Some(note))) => {
+                self.msg = format!("Valid, {}", note);
+                self.valid = true;
+            }
+            Err(e) => {
+                self.msg = e;
+                self.valid = false;
+            }
+        }
+    }
+
+    pub fn show(&mut self, og: &State, height: f32, ui: &mut egui::Ui) {
+        if self.text.is_empty() && self.msg.is_empty() {
+            self.load(og);
+        }
+        ui.horizontal(|ui| {
+            let width = (ui.available_width() / 6.0) - SPACE;
+            if ui
+                .add_sized([width, height], Button::new("Reload"))
+                .on_hover_text(GUPAX_TOML_RELOAD)
+                .clicked()
+            {
+                self.load(og);
+            }
+            ui.separator();
+            if ui
+                .add_enabled(
+                    self.valid,
+                    Button::new("Apply").min_size([width, height].into()),
+                )
+                .on_hover_text(GUPAX_TOML_APPLY)
+                .clicked()
+            {
+                if let Ok((state, _)) = validate(&self.text) {
+                    info!("TomlEditor | Applying edited state.toml");
+                    self.pending = Some(state);
+                }
+            }
+            ui.separator();
+            let color = if self.valid { GREEN } else { RED };
+            ui.add_sized(
+                [ui.available_width(), height],
+                Label::new(RichText::new(&self.msg).color(color)),
+            );
+        });
+        let theme = egui_extras::syntax_highlighting::CodeTheme::from_memory(ui.ctx());
+        let mut layouter = |ui: &egui::Ui, string: &str, wrap_width: f32| {
+            let mut job =
+                egui_extras::syntax_highlighting::highlight(ui.ctx(), &theme, string, "toml");
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|f| f.layout_job(job))
+        };
+        egui::ScrollArea::vertical()
+            .id_source("gupax_toml_editor")
+            .max_height(height * 15.0)
+            .show(ui, |ui| {
+                let response = ui.add(
+                    TextEdit::multiline(&mut self.text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .layouter(&mut layouter),
+                );
+                if response.changed() {
+                    self.check();
+                }
+            });
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn toml_editor_validate() {
+        use crate::tomledit::*;
+        let default = State::new().to_string().unwrap();
+        let (_, note) = validate(&default).unwrap();
+        assert!(note.is_none());
+
+        // Missing keys get merged in.
+        let (state, note) = validate("[gupax]\nsimple = false\n").unwrap();
+        assert_eq!(note, Some(GUPAX_TOML_MERGED));
+        assert!(!state.gupax.simple);
+
+        // Broken syntax and wrong types don't validate.
+        assert!(validate("[gupax\nsimple = false\n").is_err());
+        assert!(validate("[gupax]\nsimple = \"yes\"\n").is_err());
+
+        let mut editor = TomlEditor::default();
+        editor.load(&State::new());
+        assert!(editor.valid);
+        assert_eq!(editor.msg, "Valid");
+    }
+}
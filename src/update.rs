@@ -107,6 +107,9 @@ mod impl_platform {
     pub(super) const VALID_XMRIG_2: &str = "XMRig.exe";
     pub(super) const VALID_XMRIG_3: &str = "Xmrig.exe";
     pub(super) const VALID_XMRIG_4: &str = "xmrig.exe";
+    pub(super) const VALID_PROXY_1: &str = "XMRIG-PROXY.exe";
+    pub(super) const VALID_PROXY_2: &str = "XMRig-Proxy.exe";
+    pub(super) const VALID_PROXY_3: &str = "xmrig-proxy.exe";
     pub(super) const VALID_P2POOL_1: &str = "P2POOL.exe";
     pub(super) const VALID_P2POOL_2: &str = "P2Pool.exe";
     pub(super) const VALID_P2POOL_3: &str = "P2pool.exe";
@@ -125,6 +128,9 @@ mod impl_unix {
     pub(super) const VALID_XMRIG_2: &str = "XMRig";
     pub(super) const VALID_XMRIG_3: &str = "Xmrig";
     pub(super) const VALID_XMRIG_4: &str = "xmrig";
+    pub(super) const VALID_PROXY_1: &str = "XMRIG-PROXY";
+    pub(super) const VALID_PROXY_2: &str = "XMRig-Proxy";
+    pub(super) const VALID_PROXY_3: &str = "xmrig-proxy";
     pub(super) const VALID_P2POOL_1: &str = "P2POOL";
     pub(super) const VALID_P2POOL_2: &str = "P2Pool";
     pub(super) const VALID_P2POOL_3: &str = "P2pool";
@@ -164,6 +170,7 @@ use impl_platform::*;
 
 const VALID_GUPAX: [&str; 3] = [VALID_GUPAX_1, VALID_GUPAX_2, VALID_GUPAX_3];
 const VALID_XMRIG: [&str; 4] = [VALID_XMRIG_1, VALID_XMRIG_2, VALID_XMRIG_3, VALID_XMRIG_4];
+const VALID_PROXY: [&str; 3] = [VALID_PROXY_1, VALID_PROXY_2, VALID_PROXY_3];
 const VALID_P2POOL: [&str; 4] = [
     VALID_P2POOL_1,
     VALID_P2POOL_2,
@@ -210,6 +217,7 @@ const MSG_METADATA: &str = "Fetching package metadata";
 const MSG_METADATA_RETRY: &str = "Fetching package metadata failed, attempt";
 const MSG_COMPARE: &str = "Compare package versions";
 const MSG_UP_TO_DATE: &str = "All packages already up-to-date";
+const MSG_NO_SCOPE: &str = "No packages are selected for updating";
 const MSG_DOWNLOAD: &str = "Downloading packages";
 const MSG_DOWNLOAD_RETRY: &str = "Downloading packages failed, attempt";
 const MSG_EXTRACT: &str = "Extracting packages";
@@ -228,39 +236,32 @@ const UPGRADE: &str = "----------------- Upgrade ------------------";
 
 //---------------------------------------------------------------------------------------------------- General functions
 pub fn check_p2pool_path(path: &str) -> bool {
-    let path = match crate::disk::into_absolute_path(path.to_string()) {
-        Ok(p) => p,
-        Err(_) => return false,
-    };
-    let path = match path.file_name() {
-        Some(p) => p,
-        None => {
-            error!("Couldn't get P2Pool file name");
-            return false;
-        }
-    };
-    path == VALID_P2POOL[0]
-        || path == VALID_P2POOL[1]
-        || path == VALID_P2POOL[2]
-        || path == VALID_P2POOL[3]
+    binary_name_ok(path, &VALID_P2POOL)
 }
 
 pub fn check_xmrig_path(path: &str) -> bool {
-    let path = match crate::disk::into_absolute_path(path.to_string()) {
-        Ok(p) => p,
-        Err(_) => return false,
-    };
-    let path = match path.file_name() {
-        Some(p) => p,
-        None => {
-            error!("Couldn't get XMRig file name");
-            return false;
-        }
-    };
-    path == VALID_XMRIG[0]
-        || path == VALID_XMRIG[1]
-        || path == VALID_XMRIG[2]
-        || path == VALID_XMRIG[3]
+    binary_name_ok(path, &VALID_XMRIG)
+}
+
+pub fn check_proxy_path(path: &str) -> bool {
+    binary_name_ok(path, &VALID_PROXY)
+}
+
+// Is the file name at the end of [path] one of [valid]? Both [/] and [\\]
+// are separators so Windows paths (quoted, UNC, etc) check the same way
+// everywhere, and Windows file names don't care about case.
+fn binary_name_ok(path: &str, valid: &[&str]) -> bool {
+    let path = crate::disk::clean_path(path);
+    let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+    if name.is_empty() {
+        error!("Couldn't get binary file name from [{}]", path);
+        return false;
+    }
+    if cfg!(windows) {
+        valid.iter().any(|v| v.eq_ignore_ascii_case(name))
+    } else {
+        valid.contains(&name)
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- Update struct/impl
@@ -285,6 +286,8 @@ pub struct Update {
     pub prog: Arc<Mutex<f32>>,      // Holds the 0-100% progress bar number
     pub msg: Arc<Mutex<String>>,    // Message to display on [Gupax] tab while updating
     pub tor: bool,                  // Is Tor enabled or not?
+    pub integrity_path: PathBuf,    // [binaries.toml], installed binaries are trusted there
+    pub scope: [bool; 3],           // [Gupax, P2Pool, XMRig], which packages may be updated
 }
 
 impl Update {
@@ -299,9 +302,22 @@ impl Update {
             prog: arc_mut!(0.0),
             msg: arc_mut!(MSG_NONE.to_string()),
             tor,
+            integrity_path: PathBuf::new(),
+            scope: [true; 3],
         }
     }
 
+    // The packages an update with this [scope] checks.
+    // Packages left out are never downloaded or replaced.
+    pub fn packages(scope: [bool; 3]) -> Vec<Pkg> {
+        [Gupax, P2pool, Xmrig]
+            .into_iter()
+            .zip(scope)
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| Pkg::new(name))
+            .collect()
+    }
+
     // Get a temporary random folder for package download contents
     // This used to use [std::env::temp_dir()] but there were issues
     // using [std::fs::rename()] on tmpfs -> disk (Invalid cross-device link (os error 18)).
@@ -351,9 +367,7 @@ impl Update {
             let client = ClientEnum::Tor(Client::builder().build(connector));
             Ok(client)
         } else {
-            let mut connector = hyper_tls::HttpsConnector::new();
-            connector.https_only(true);
-            let client = ClientEnum::Https(Client::builder().build(connector));
+            let client = ClientEnum::Https(crate::net::https_client());
             Ok(client)
         }
     }
@@ -381,12 +395,20 @@ impl Update {
         #[cfg(feature = "distro")]
         return;
 
+        let scope = gupax.update_scope();
+        if !scope.contains(&true) {
+            info!("Update | {}", MSG_NO_SCOPE);
+            *lock2!(update, msg) = MSG_NO_SCOPE.to_string();
+            return;
+        }
+
         // Check P2Pool path for safety
         // Attempt relative to absolute path
         let p2pool_path = match into_absolute_path(gupax.p2pool_path.clone()) {
             Ok(p) => p,
             Err(e) => {
-                error_state.set(
+                error_state.set_code(
+                    crate::code::ErrorCode::P2poolPath,
                     format!(
                         "Provided P2Pool path could not be turned into an absolute path: {}",
                         e
@@ -404,13 +426,19 @@ impl Update {
                 match p.to_str() {
                     Some(p) => p,
                     None => {
-                        error_state.set("Provided P2Pool path could not be turned into a UTF-8 string (are you using non-English characters?)", ErrorFerris::Error, ErrorButtons::Okay);
+                        error_state.set_code(
+                            crate::code::ErrorCode::P2poolPath,
+                            "Provided P2Pool path could not be turned into a UTF-8 string (are you using non-English characters?)",
+                            ErrorFerris::Error,
+                            ErrorButtons::Okay,
+                        );
                         return;
                     }
                 }
             }
             None => {
-                error_state.set(
+                error_state.set_code(
+                    crate::code::ErrorCode::P2poolPath,
                     "Provided P2Pool path could not be found",
                     ErrorFerris::Error,
                     ErrorButtons::Okay,
@@ -422,7 +450,9 @@ impl Update {
         // to overwrite it with an update, so set an error.
         // Doesnt seem like you can [match] on array indexes
         // so that explains the ridiculous if/else.
-        if check_p2pool_path(file) {
+        if !scope[1] {
+            info!("Update | P2Pool is not being updated, skipping path check");
+        } else if check_p2pool_path(file) {
             info!("Update | Using P2Pool path: [{}]", p2pool_path.display());
         } else {
             warn!(
@@ -430,7 +460,12 @@ impl Update {
                 file
             );
             let text = format!("Provided P2Pool path seems incorrect. Not starting update for safety.\nTry one of these: {:?}", VALID_P2POOL);
-            error_state.set(text, ErrorFerris::Error, ErrorButtons::Okay);
+            error_state.set_code(
+                crate::code::ErrorCode::P2poolPath,
+                text,
+                ErrorFerris::Error,
+                ErrorButtons::Okay,
+            );
             return;
         }
 
@@ -438,7 +473,8 @@ impl Update {
         let xmrig_path = match into_absolute_path(gupax.xmrig_path.clone()) {
             Ok(p) => p,
             Err(e) => {
-                error_state.set(
+                error_state.set_code(
+                    crate::code::ErrorCode::XmrigPath,
                     format!(
                         "Provided XMRig path could not be turned into an absolute path: {}",
                         e
@@ -455,13 +491,19 @@ impl Update {
                 match p.to_str() {
                     Some(p) => p,
                     None => {
-                        error_state.set("Provided XMRig path could not be turned into a UTF-8 string (are you using non-English characters?)", ErrorFerris::Error, ErrorButtons::Okay);
+                        error_state.set_code(
+                            crate::code::ErrorCode::XmrigPath,
+                            "Provided XMRig path could not be turned into a UTF-8 string (are you using non-English characters?)",
+                            ErrorFerris::Error,
+                            ErrorButtons::Okay,
+                        );
                         return;
                     }
                 }
             }
             None => {
-                error_state.set(
+                error_state.set_code(
+                    crate::code::ErrorCode::XmrigPath,
                     "Provided XMRig path could not be found",
                     ErrorFerris::Error,
                     ErrorButtons::Okay,
@@ -469,18 +511,27 @@ impl Update {
                 return;
             }
         };
-        if check_xmrig_path(file) {
+        if !scope[2] {
+            info!("Update | XMRig is not being updated, skipping path check");
+        } else if check_xmrig_path(file) {
             info!("Update | Using XMRig path: [{}]", xmrig_path.display());
         } else {
             warn!("Update | Aborting update, incorrect XMRig path: [{}]", file);
             let text = format!("Provided XMRig path seems incorrect. Not starting update for safety.\nTry one of these: {:?}", VALID_XMRIG);
-            error_state.set(text, ErrorFerris::Error, ErrorButtons::Okay);
+            error_state.set_code(
+                crate::code::ErrorCode::XmrigPath,
+                text,
+                ErrorFerris::Error,
+                ErrorButtons::Okay,
+            );
             return;
         }
 
         lock!(update).path_p2pool = p2pool_path.display().to_string();
         lock!(update).path_xmrig = xmrig_path.display().to_string();
         lock!(update).tor = gupax.update_via_tor;
+        lock!(update).scope = scope;
+        lock!(update).integrity_path = state_path.with_file_name(crate::disk::BINARIES_TOML);
 
         // Clone before thread spawn
         let og = Arc::clone(og);
@@ -555,7 +606,9 @@ impl Update {
         std::fs::create_dir(&tmp_dir)?;
 
         // Make Pkg vector
-        let mut vec = vec![Pkg::new(Gupax), Pkg::new(P2pool), Pkg::new(Xmrig)];
+        let scope = lock!(update).scope;
+        info!("Update | Scope [Gupax, P2Pool, XMRig]: {:?}", scope);
+        let mut vec = Self::packages(scope);
 
         // Generate fake user-agent
         let user_agent = Pkg::get_user_agent();
@@ -886,6 +939,13 @@ impl Update {
                     }
                     // Move downloaded path into old path
                     std::fs::rename(entry.path(), path)?;
+                    // This is the binary we just verified, trust it.
+                    if name == P2pool || name == Xmrig {
+                        let integrity_path = lock!(update).integrity_path.clone();
+                        if let Err(e) = crate::integrity::trust_file(&integrity_path, path) {
+                            warn!("Update | Could not record [{}]'s hash: {}", name, e);
+                        }
+                    }
                     // Update [State] version
                     match name {
                         Gupax => {
@@ -929,6 +989,10 @@ impl Update {
             }
         }
         *lock2!(update, prog) = 100.0;
+        crate::event::push(
+            crate::event::Kind::Update,
+            format!("Updated:{}", new_pkgs.replace('\n', " ")),
+        );
         Ok(())
     }
 }
@@ -936,7 +1000,7 @@ impl Update {
 #[derive(Debug, Clone)]
 pub enum ClientEnum {
     Tor(hyper::Client<ArtiHttpConnector<tor_rtcompat::PreferredRuntime, TlsConnector>>),
-    Https(hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>),
+    Https(crate::net::HttpsClient),
 }
 
 //---------------------------------------------------------------------------------------------------- Pkg struct/impl
@@ -1102,3 +1166,83 @@ impl std::fmt::Display for Name {
         write!(f, "{:?}", self)
     }
 }
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn update_scope() {
+        use crate::update::*;
+        let names = |scope| {
+            Update::packages(scope)
+                .into_iter()
+                .map(|pkg| pkg.name)
+                .collect::<Vec<Name>>()
+        };
+        assert_eq!(names([true; 3]), [Name::Gupax, Name::P2pool, Name::Xmrig]);
+        assert_eq!(names([false, true, false]), [Name::P2pool]);
+        assert_eq!(names([true, false, true]), [Name::Gupax, Name::Xmrig]);
+        assert!(names([false; 3]).is_empty());
+
+        let mut gupax = crate::disk::Gupax::default();
+        assert_eq!(gupax.update_scope(), [true; 3]);
+        gupax.update_gupax = false;
+        assert_eq!(gupax.update_scope(), [false, true, true]);
+    }
+
+    #[test]
+    fn windows_path_shapes() {
+        use crate::disk::clean_path;
+        use crate::update::*;
+        let (p2pool, xmrig) = (VALID_P2POOL[3], VALID_XMRIG[3]);
+
+        // Quotes, spaces, verbatim/UNC prefixes.
+        assert_eq!(
+            clean_path(r#" "C:\Program Files\P2Pool\p2pool.exe" "#),
+            r"C:\Program Files\P2Pool\p2pool.exe"
+        );
+        assert_eq!(clean_path("'/opt/my xmrig/xmrig'"), "/opt/my xmrig/xmrig");
+        assert_eq!(
+            clean_path(r"\\?\C:\Users\Jörg\xmrig.exe"),
+            r"C:\Users\Jörg\xmrig.exe"
+        );
+        assert_eq!(
+            clean_path(r"\\?\UNC\nas\mining\xmrig.exe"),
+            r"\\nas\mining\xmrig.exe"
+        );
+        assert_eq!(
+            clean_path(r"\\nas\mining\xmrig.exe"),
+            r"\\nas\mining\xmrig.exe"
+        );
+        assert_eq!(clean_path("\""), "\"");
+
+        for path in [
+            format!(r"C:\Program Files\P2Pool\{}", p2pool),
+            format!(r#""C:\Program Files\P2Pool\{}""#, p2pool),
+            format!(r"\\?\C:\Users\Jörg\Рабочий стол\{}", p2pool),
+            format!(r"\\?\UNC\nas\mining\{}", p2pool),
+            format!(r"\\nas\mining\{}", p2pool),
+            format!("D:/mining/p2pool v4/{}", p2pool),
+            format!("p2pool/{}", p2pool),
+            p2pool.to_string(),
+        ] {
+            assert!(check_p2pool_path(&path), "{path}");
+            assert!(!check_xmrig_path(&path), "{path}");
+        }
+        for path in [
+            format!(r#""C:\Program Files (x86)\XMRig 6.22\{}""#, xmrig),
+            format!(r"\\?\UNC\nas\mining\{}", xmrig),
+            format!("/home/user/マイニング/{}", xmrig),
+        ] {
+            assert!(check_xmrig_path(&path), "{path}");
+        }
+        assert!(!check_p2pool_path(r"C:\Program Files\P2Pool\"));
+        assert!(!check_p2pool_path(""));
+        assert!(!check_xmrig_path(r"C:\xmrig\xmrig-notls.exe"));
+
+        let proxy = format!("/opt/xmrig-proxy-6.22.0/{}", VALID_PROXY[2]);
+        assert!(check_proxy_path(&proxy));
+        assert!(!check_xmrig_path(&proxy));
+        assert!(!check_proxy_path(&format!("/opt/xmrig/{}", xmrig)));
+    }
+}
@@ -0,0 +1,287 @@
+// Gupax - GUI Uniting P2Pool And XMRig
+//
+// Copyright (c) 2022-2023 hinto-janai
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// VM/container detection.
+//
+// RandomX in a VM without the host's CPU flags, hugepages or pinned
+// vCPUs can easily lose half its hashrate, and MSR mod never works
+// there. This detects the environment once at startup so the [XMRig]
+// tab can give specific advice, and the [Benchmarks] comparison can
+// account for what a VM can realistically reach.
+
+use log::*;
+use once_cell::sync::Lazy;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Roughly what a well set up VM reaches vs bare metal (no MSR mod, vCPU overhead).
+pub const VM_EXPECTED: f32 = 0.85;
+
+pub static VIRT: Lazy<Virt> = Lazy::new(Virt::detect);
+
+//---------------------------------------------------------------------------------------------------- Virt
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Environment {
+    BareMetal,
+    Vm(&'static str),        // Hypervisor name
+    Container(&'static str), // Container runtime name
+}
+
+#[derive(Clone, Debug)]
+pub struct Virt {
+    pub env: Environment,
+    pub aes: Option<bool>, // Does the (virtual) CPU have AES-NI? [None] if unknown
+    pub hugepages: Option<u64>, // Reserved 2MB hugepages (Linux only)
+}
+
+impl Virt {
+    #[cold]
+    #[inline(never)]
+    fn detect() -> Self {
+        let env = match (container(), hypervisor()) {
+            (Some(name), _) => Environment::Container(name),
+            (None, Some(name)) => Environment::Vm(name),
+            (None, None) => Environment::BareMetal,
+        };
+        let new = Self {
+            env,
+            aes: aes(),
+            hugepages: hugepages(),
+        };
+        info!("Virt | {:?}", new);
+        new
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.env != Environment::BareMetal
+    }
+
+    // [Running in a VM (KVM)]
+    pub fn describe(&self) -> String {
+        match self.env {
+            Environment::BareMetal => "Bare metal".to_string(),
+            Environment::Vm(name) => format!("Running in a VM ({})", name),
+            Environment::Container(name) => format!("Running in a container ({})", name),
+        }
+    }
+
+    // Specific things to fix, most important first.
+    pub fn advice(&self) -> Vec<&'static str> {
+        let mut advice = vec![];
+        if self.aes == Some(false) {
+            advice.push("The CPU has no AES-NI, RandomX will be very slow. Pass the host CPU through to the VM (e.g: [-cpu host], [host-passthrough])");
+        }
+        match self.env {
+            Environment::BareMetal => return advice,
+            Environment::Vm(_) => {
+                advice.push("Pass the host CPU model through (e.g: [-cpu host], [host-passthrough]) so XMRig sees AES and AVX2");
+                advice.push("Pin each vCPU to its own host core, and avoid giving the VM both SMT threads of a core");
+                advice.push("Enable hugepages on the host and back the VM's memory with them, the VM's own hugepages are not enough");
+                advice.push("MSR mod can't be applied inside a VM, apply it on the host instead");
+            }
+            Environment::Container(_) => {
+                advice.push("Hugepages and MSR mod have to be set up on the host, a container can't change them");
+                advice.push(
+                    "Don't CPU-limit the container (e.g: [--cpus]) below the threads XMRig uses",
+                );
+            }
+        }
+        if self.hugepages == Some(0) {
+            advice.push(
+                "No hugepages are reserved, e.g: [sysctl -w vm.nr_hugepages=1280] on the host",
+            );
+        }
+        advice
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Detection
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid(leaf: u32) -> (u32, u32, u32, u32) {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+    let r = __cpuid(leaf);
+    (r.eax, r.ebx, r.ecx, r.edx)
+}
+
+// CPUID leaf 1 has a "hypervisor present" bit, leaf [0x40000000] has its vendor.
+fn hypervisor() -> Option<&'static str> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        let (_, _, ecx, _) = cpuid(1);
+        if ecx & (1 << 31) != 0 {
+            let (_, ebx, ecx, edx) = cpuid(0x4000_0000);
+            let vendor = [ebx.to_le_bytes(), ecx.to_le_bytes(), edx.to_le_bytes()].concat();
+            return Some(hypervisor_name(&String::from_utf8_lossy(&vendor)));
+        }
+    }
+    // Not x86 (or it hides the bit), the firmware usually still says.
+    #[cfg(target_os = "linux")]
+    {
+        let read =
+            |file| std::fs::read_to_string(format!("/sys/class/dmi/id/{file}")).unwrap_or_default();
+        let dmi = format!("{} {}", read("sys_vendor"), read("product_name"));
+        return dmi_hypervisor(&dmi);
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+fn hypervisor_name(vendor: &str) -> &'static str {
+    match vendor.trim_end_matches('\0') {
+        "KVMKVMKVM" => "KVM",
+        "Microsoft Hv" => "Hyper-V",
+        "VMwareVMware" => "VMware",
+        "VBoxVBoxVBox" => "VirtualBox",
+        "XenVMMXenVMM" => "Xen",
+        "TCGTCGTCGTCG" => "QEMU",
+        "prl hyperv" | " lrpepyh vr" => "Parallels",
+        "bhyve bhyve" => "bhyve",
+        "ACRNACRNACRN" => "ACRN",
+        _ => "Unknown hypervisor",
+    }
+}
+
+fn dmi_hypervisor(dmi: &str) -> Option<&'static str> {
+    const VENDORS: [(&str, &str); 7] = [
+        ("QEMU", "QEMU"),
+        ("KVM", "KVM"),
+        ("VMware", "VMware"),
+        ("VirtualBox", "VirtualBox"),
+        ("innotek", "VirtualBox"),
+        ("Xen", "Xen"),
+        ("Virtual Machine", "Hyper-V"),
+    ];
+    VENDORS
+        .iter()
+        .find(|(needle, _)| dmi.contains(needle))
+        .map(|(_, name)| *name)
+}
+
+fn container() -> Option<&'static str> {
+    #[cfg(target_os = "linux")]
+    {
+        let exists = |path| std::path::Path::new(path).exists();
+        let env = std::env::var("container").ok();
+        let cgroup = std::fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+        return container_name(
+            exists("/.dockerenv"),
+            exists("/run/.containerenv"),
+            env.as_deref(),
+            &cgroup,
+        );
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+fn container_name(
+    dockerenv: bool,
+    containerenv: bool,
+    env: Option<&str>,
+    cgroup: &str,
+) -> Option<&'static str> {
+    if dockerenv {
+        return Some("Docker");
+    }
+    if containerenv || env == Some("podman") {
+        return Some("Podman");
+    }
+    match env {
+        Some("lxc") => return Some("LXC"),
+        Some("systemd-nspawn") => return Some("systemd-nspawn"),
+        Some(e) if !e.is_empty() && e != "flatpak" => return Some("Container"),
+        _ => (),
+    }
+    if cgroup.contains("kubepods") {
+        Some("Kubernetes")
+    } else if cgroup.contains("docker") {
+        Some("Docker")
+    } else if cgroup.contains("lxc") {
+        Some("LXC")
+    } else {
+        None
+    }
+}
+
+fn aes() -> Option<bool> {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    return Some(std::arch::is_x86_feature_detected!("aes"));
+    #[allow(unreachable_code)]
+    None
+}
+
+fn hugepages() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        return meminfo
+            .lines()
+            .find_map(|l| l.strip_prefix("HugePages_Total:"))
+            .and_then(|n| n.trim().parse().ok());
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod test {
+    #[test]
+    fn hypervisor_names() {
+        use super::{dmi_hypervisor, hypervisor_name};
+        assert_eq!(hypervisor_name("KVMKVMKVM\0\0\0"), "KVM");
+        assert_eq!(hypervisor_name("Microsoft Hv"), "Hyper-V");
+        assert_eq!(hypervisor_name("????????????"), "Unknown hypervisor");
+        assert_eq!(
+            dmi_hypervisor("innotek GmbH VirtualBox\n"),
+            Some("VirtualBox")
+        );
+        assert_eq!(dmi_hypervisor("Dell Inc. OptiPlex 7090"), None);
+    }
+
+    #[test]
+    fn container_names() {
+        use super::container_name;
+        assert_eq!(container_name(true, false, None, ""), Some("Docker"));
+        assert_eq!(container_name(false, true, None, ""), Some("Podman"));
+        assert_eq!(container_name(false, false, Some("lxc"), ""), Some("LXC"));
+        assert_eq!(container_name(false, false, Some("flatpak"), ""), None);
+        assert_eq!(
+            container_name(false, false, None, "1:cpu:/kubepods/burstable/pod1"),
+            Some("Kubernetes")
+        );
+        assert_eq!(container_name(false, false, None, "0::/init.scope"), None);
+    }
+
+    #[test]
+    fn advice() {
+        use super::{Environment, Virt};
+        let mut virt = Virt {
+            env: Environment::BareMetal,
+            aes: Some(true),
+            hugepages: Some(0),
+        };
+        assert!(virt.advice().is_empty());
+        virt.env = Environment::Vm("KVM");
+        assert_eq!(virt.describe(), "Running in a VM (KVM)");
+        assert!(virt.advice().last().unwrap().contains("nr_hugepages"));
+        virt.aes = Some(false);
+        assert!(virt.advice()[0].contains("AES-NI"));
+    }
+}
@@ -21,9 +21,15 @@
 //     "Vec<(String, AtomicUnit, HumanNumber)>"
 // These represent:
 //     "(DATE, ATOMIC_UNIT, MONERO_BLOCK)"
+//
+// Payout dates are stored in UTC with a [Z] suffix, e.g: [2022-04-11 00:20:17.2571Z]
+// so the history stays consistent across DST changes and machine moves.
+// Older Gupax's stored P2Pool's local time without the [Z].
 
+use crate::disk::PayoutTimezone;
 use crate::human::*;
 use crate::regex::P2POOL_REGEX;
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
 
 use log::*;
 
@@ -46,6 +52,10 @@ impl Default for AtomicUnit {
     }
 }
 
+// Since block [2,641,623] (June 2022) every block pays at least this
+// forever, anything above it in a block reward is transaction fees.
+pub const TAIL_EMISSION: AtomicUnit = AtomicUnit(600_000_000_000);
+
 impl AtomicUnit {
     pub const fn new() -> Self {
         Self(0)
@@ -96,6 +106,16 @@ impl AtomicUnit {
         HumanNumber::from_f64_12_point(f)
     }
 
+    // A block reward split into ([tail emission], [fees]),
+    // [None] if it's below the tail (e.g: not known yet).
+    pub const fn tail_and_fees(self) -> Option<(Self, Self)> {
+        if self.0 < TAIL_EMISSION.0 {
+            None
+        } else {
+            Some((TAIL_EMISSION, Self(self.0 - TAIL_EMISSION.0)))
+        }
+    }
+
     pub fn to_human_number_no_fmt(&self) -> HumanNumber {
         let f = self.0 as f64 / 1_000_000_000_000.0;
         HumanNumber::from_f64_no_fmt(f)
@@ -263,6 +283,10 @@ impl PayoutOrd {
         *self = Self(vec);
     }
 
+    pub fn iter(&self) -> std::slice::Iter<'_, (String, AtomicUnit, HumanNumber)> {
+        self.0.iter()
+    }
+
     // Takes the wrapper types, and pushes to existing [Self]
     pub fn push(&mut self, date: String, atomic_unit: AtomicUnit, block: HumanNumber) {
         self.0.push((date, atomic_unit, block));
@@ -318,16 +342,171 @@ impl Default for PayoutOrd {
 
 impl std::fmt::Display for PayoutOrd {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // English, this gets localized when displayed.
         for i in &self.0 {
-            writeln!(f, "{} | {} XMR | Block {}", i.0, i.1, i.2)?;
+            writeln!(
+                f,
+                "{} | {} XMR | Block {}",
+                i.0,
+                i.1.to_human_number_12_point().as_str(),
+                i.2.as_str()
+            )?;
         }
         Ok(())
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Payout timezones
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Split [2022-04-11 00:20:17.2571Z] into its seconds, fraction [.2571], and if it's UTC.
+fn split_date(date: &str) -> Option<(NaiveDateTime, &str, bool)> {
+    let (date, utc) = match date.strip_suffix('Z') {
+        Some(d) => (d, true),
+        None => (date, false),
+    };
+    let (secs, frac) = date.split_at(date.find('.').unwrap_or(date.len()));
+    let naive = NaiveDateTime::parse_from_str(secs, DATE_FORMAT).ok()?;
+    Some((naive, frac, utc))
+}
+
+// [naive] in the timezone [tz] converted to UTC.
+// Times skipped by DST are taken as-is, ambiguous ones use the earliest.
+fn naive_to_utc<Tz: TimeZone>(naive: NaiveDateTime, tz: &Tz) -> NaiveDateTime {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map_or(naive, |d| d.naive_utc())
+}
+
+// Did P2Pool print this timestamp in UTC? P2Pool prints payouts as they happen,
+// so whichever of [now_utc] and [now_local] it's closer to is the timezone used.
+fn is_utc(naive: NaiveDateTime, now_utc: NaiveDateTime, now_local: NaiveDateTime) -> bool {
+    (naive - now_utc).abs() < (naive - now_local).abs()
+}
+
+fn date_to_utc_in<Tz: TimeZone>(date: &str, tz: &Tz, p2pool_utc: bool) -> String {
+    match split_date(date) {
+        Some((_, _, true)) | None => date.to_string(),
+        Some((naive, frac, false)) => {
+            let utc = if p2pool_utc {
+                naive
+            } else {
+                naive_to_utc(naive, tz)
+            };
+            format!("{}{}Z", utc.format(DATE_FORMAT), frac)
+        }
+    }
+}
+
+fn display_date_in<Tz: TimeZone>(date: &str, timezone: PayoutTimezone, tz: &Tz) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let Some((naive, frac, utc)) = split_date(date) else {
+        return date.to_string();
+    };
+    let naive = if utc { naive } else { naive_to_utc(naive, tz) };
+    match timezone {
+        PayoutTimezone::Utc => format!("{}{} UTC", naive.format(DATE_FORMAT), frac),
+        PayoutTimezone::Local => {
+            let local = tz.from_utc_datetime(&naive);
+            format!(
+                "{}{} {}",
+                local.format(DATE_FORMAT),
+                frac,
+                local.format("%:z")
+            )
+        }
+    }
+}
+
+impl PayoutOrd {
+    // Convert a date P2Pool just printed into UTC.
+    pub fn live_date_to_utc(date: &str) -> String {
+        let p2pool_utc = split_date(date).is_some_and(|(naive, _, _)| {
+            is_utc(naive, Utc::now().naive_utc(), Local::now().naive_local())
+        });
+        date_to_utc_in(date, &Local, p2pool_utc)
+    }
+
+    // Convert a stored (possibly old local time) date into UTC.
+    pub fn date_to_utc(date: &str) -> String {
+        date_to_utc_in(date, &Local, false)
+    }
+
+    // Stored date -> date in [timezone] with the timezone shown.
+    pub fn display_date(date: &str, timezone: PayoutTimezone) -> String {
+        display_date_in(date, timezone, &Local)
+    }
+
+    // Apply [f] to the date of every [DATE | ...] line in [log].
+    pub fn map_log_dates(log: &str, f: impl Fn(&str) -> String) -> String {
+        let mut string = String::with_capacity(log.len() + log.len() / 8);
+        for line in log.lines() {
+            match line.split_once(" | ") {
+                Some((date, rest)) => {
+                    string.push_str(&f(date));
+                    string.push_str(" | ");
+                    string.push_str(rest);
+                }
+                None => string.push_str(line),
+            }
+            string.push('\n');
+        }
+        string
+    }
+
+    // Are there any old local time dates in [log]?
+    pub fn log_has_local_dates(log: &str) -> bool {
+        log.lines().any(|line| {
+            line.split_once(" | ")
+                .and_then(|(date, _)| split_date(date))
+                .is_some_and(|(_, _, utc)| !utc)
+        })
+    }
+
+    // The block height of a formatted payout line.
+    pub fn line_block_height(line: &str) -> Option<u64> {
+        let (_, block) = line.rsplit_once("Block ")?;
+        block.trim().replace(',', "").parse().ok()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Block explorer
+pub const DEFAULT_EXPLORER: &str = "https://xmrchain.net/block/{height}";
+
+// Fill in [{height}] in a block explorer URL template,
+// a template without it gets the height appended.
+pub fn explorer_url(template: &str, height: u64) -> String {
+    let template = match template.trim() {
+        "" => DEFAULT_EXPLORER,
+        t => t,
+    };
+    if template.contains("{height}") {
+        template.replace("{height}", &height.to_string())
+    } else {
+        format!("{}{}", template, height)
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod test {
+    #[test]
+    fn tail_and_fees() {
+        use crate::xmr::{AtomicUnit, TAIL_EMISSION};
+        let reward = AtomicUnit::from_u64(600_499_860_000);
+        assert_eq!(
+            reward.tail_and_fees(),
+            Some((TAIL_EMISSION, AtomicUnit::from_u64(499_860_000)))
+        );
+        assert_eq!(
+            TAIL_EMISSION.tail_and_fees(),
+            Some((TAIL_EMISSION, AtomicUnit::new()))
+        );
+        assert_eq!(AtomicUnit::new().tail_and_fees(), None);
+    }
+
     #[test]
     fn update_p2pool_payout_log() {
         use crate::xmr::PayoutOrd;
@@ -496,4 +675,80 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn payout_timezones() {
+        use super::*;
+        use chrono::{FixedOffset, NaiveDate};
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        // Old local time -> UTC, UTC stays the same.
+        assert_eq!(
+            date_to_utc_in("2022-04-11 00:20:17.2571", &tz, false),
+            "2022-04-10 22:20:17.2571Z"
+        );
+        assert_eq!(
+            date_to_utc_in("2022-04-11 00:20:17.2571", &tz, true),
+            "2022-04-11 00:20:17.2571Z"
+        );
+        assert_eq!(
+            date_to_utc_in("2022-04-10 22:20:17.2571Z", &tz, false),
+            "2022-04-10 22:20:17.2571Z"
+        );
+        assert_eq!(date_to_utc_in("????-??-??", &tz, false), "????-??-??");
+
+        // Display.
+        let date = "2022-04-10 22:20:17.2571Z";
+        assert_eq!(
+            display_date_in(date, PayoutTimezone::Utc, &tz),
+            "2022-04-10 22:20:17.2571 UTC"
+        );
+        assert_eq!(
+            display_date_in(date, PayoutTimezone::Local, &tz),
+            "2022-04-11 00:20:17.2571 +02:00"
+        );
+
+        // P2Pool timezone detection.
+        let at = |h| {
+            NaiveDate::from_ymd_opt(2022, 4, 11)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        assert!(is_utc(at(10), at(10), at(12)));
+        assert!(!is_utc(at(12), at(10), at(12)));
+
+        // Logs.
+        let log = "2022-04-11 00:20:17.2571 | 0.001000000000 XMR | Block 2,562,511\n";
+        assert!(PayoutOrd::log_has_local_dates(log));
+        let log = PayoutOrd::map_log_dates(log, |d| date_to_utc_in(d, &tz, false));
+        assert_eq!(
+            log,
+            "2022-04-10 22:20:17.2571Z | 0.001000000000 XMR | Block 2,562,511\n"
+        );
+        assert!(!PayoutOrd::log_has_local_dates(&log));
+        let (date, _, _) = PayoutOrd::parse_formatted_payout_line(&log);
+        assert_eq!(date, "2022-04-10 22:20:17.2571Z");
+    }
+
+    #[test]
+    fn explorer_url() {
+        use crate::xmr::{explorer_url, PayoutOrd};
+        let line = "2022-04-11 00:20:17.2571 | 0.001000000000 XMR | Block 2,562,511";
+        let height = PayoutOrd::line_block_height(line).unwrap();
+        assert_eq!(height, 2562511);
+        assert_eq!(PayoutOrd::line_block_height("???"), None);
+        assert_eq!(
+            explorer_url("", height),
+            "https://xmrchain.net/block/2562511"
+        );
+        assert_eq!(
+            explorer_url("http://127.0.0.1:8081/block/{height}", height),
+            "http://127.0.0.1:8081/block/2562511"
+        );
+        assert_eq!(
+            explorer_url("https://localmonero.co/blocks/search/", height),
+            "https://localmonero.co/blocks/search/2562511"
+        );
+    }
 }
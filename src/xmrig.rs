@@ -16,27 +16,57 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::regex::REGEXES;
-use crate::{constants::*, disk::*, macros::*, Process, PubXmrigApi, Regexes};
+use crate::{
+    ansi::Console,
+    constants::*,
+    disk::*,
+    gupax::{FileType, FileWindow},
+    human::HumanNumber,
+    macros::*,
+    node::{format_ms, PoolPing},
+    probe::{BinaryProbe, XMRIG_FEATURE_TLS},
+    Process, PubXmrigApi, Regexes,
+};
 use egui::{
     Button, Checkbox, ComboBox, Label, RichText, SelectableLabel, Slider, TextEdit, TextStyle::*,
 };
 use log::*;
-use std::sync::{Arc, Mutex};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 impl crate::disk::Xmrig {
     #[expect(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         pool_vec: &mut Vec<(String, Pool)>,
+        pool_filter: &mut ListFilter,
+        pool_ping: &Arc<Mutex<PoolPing>>,
+        file_window: &Arc<Mutex<FileWindow>>,
+        probe: &Arc<Mutex<BinaryProbe>>,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubXmrigApi>>,
-        buffer: &mut String,
+        api_conflict: Option<&str>,
+        benchmark: &crate::Benchmark,
+        path: &Path,
+        stdin: &mut crate::stdin::Stdin,
+        console: &mut Console,
+        colors: bool,
         width: f32,
         height: f32,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
     ) {
         let text_edit = height / 25.0;
+        // XMRig can be built without TLS.
+        let probe = lock!(probe).clone();
+        let tls_ok = probe.has_feature(XMRIG_FEATURE_TLS);
+        let tls_disabled = format!(
+            "{} (detected XMRig {})",
+            XMRIG_TLS_UNSUPPORTED,
+            probe.version_text()
+        );
         //---------------------------------------------------------------------------------------------------- [Simple] Console
         debug!("XMRig Tab | Rendering [Console]");
         ui.group(|ui| {
@@ -51,10 +81,7 @@ impl crate::disk::Xmrig {
                         .max_height(height)
                         .auto_shrink([false; 2])
                         .show_viewport(ui, |ui, _| {
-                            ui.add_sized(
-                                [width, height],
-                                TextEdit::multiline(&mut lock!(api).output.as_str()),
-                            );
+                            console.show(ui, &lock!(api).output, colors, [width, height]);
                         });
                 });
             //---------------------------------------------------------------------------------------------------- [Advanced] Console
@@ -69,34 +96,75 @@ impl crate::disk::Xmrig {
                         .max_height(height)
                         .auto_shrink([false; 2])
                         .show_viewport(ui, |ui, _| {
-                            ui.add_sized(
-                                [width, height],
-                                TextEdit::multiline(&mut lock!(api).output.as_str()),
-                            );
+                            console.show(ui, &lock!(api).output, colors, [width, height]);
                         });
                 });
                 ui.separator();
-                let response = ui
-                    .add_sized(
-                        [width, text_edit],
-                        TextEdit::hint_text(
-                            TextEdit::singleline(buffer),
-                            r#"Commands: [h]ashrate, [p]ause, [r]esume, re[s]ults, [c]onnection"#,
-                        ),
-                    )
-                    .on_hover_text(XMRIG_INPUT);
-                // If the user pressed enter, dump buffer contents into the process STDIN
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    response.request_focus(); // Get focus back
-                    let buffer = std::mem::take(buffer); // Take buffer
-                    let mut process = lock!(process); // Lock
-                    if process.is_alive() {
-                        process.input.push(buffer);
-                    } // Push only if alive
-                }
+                stdin.show(
+                    ui,
+                    process,
+                    r#"Commands: [h]ashrate, [p]ause, [r]esume, re[s]ults, [c]onnection"#,
+                    XMRIG_INPUT,
+                    &[],
+                    [width, text_edit],
+                );
             }
         });
 
+        //---------------------------------------------------------------------------------------------------- Donate level
+        debug!("XMRig Tab | Rendering [Donate level]");
+        let reported = lock!(api).donate_level;
+        let (text, color, hover) = match reported {
+            None => (
+                "Donation level: ???".to_string(),
+                LIGHT_GRAY,
+                XMRIG_DONATE_UNKNOWN.to_string(),
+            ),
+            Some(0) => (
+                "Donation level: 0% ✔".to_string(),
+                GREEN,
+                XMRIG_DONATE_ZERO.to_string(),
+            ),
+            Some(level) => {
+                let mut text = format!("Donation level: {}% ⚠", level);
+                // Gupax asked for less than what XMRig is using, so this build has a minimum.
+                if !self.simple
+                    && self.arguments.is_empty()
+                    && !self.watch_only
+                    && level > self.donate_level as u32
+                {
+                    text += &format!(
+                        " (requested {}%, this XMRig build's minimum is {}%)",
+                        self.donate_level, level
+                    );
+                }
+                (text, YELLOW, XMRIG_DONATE_NONZERO.to_string())
+            }
+        };
+        ui.add_sized(
+            [width, text_edit],
+            Label::new(RichText::new(text).color(color)),
+        )
+        .on_hover_text(hover);
+
+        //---------------------------------------------------------------------------------------------------- VM/container
+        let virt = &*crate::virt::VIRT;
+        if virt.is_virtual() || virt.aes == Some(false) {
+            let advice = virt
+                .advice()
+                .iter()
+                .map(|a| format!("• {}", a))
+                .collect::<Vec<String>>()
+                .join("\n");
+            ui.add_sized(
+                [width, text_edit],
+                Label::new(
+                    RichText::new(format!("⚠ {}, hover for advice", virt.describe())).color(YELLOW),
+                ),
+            )
+            .on_hover_text(format!("{}\n\n{}", XMRIG_VIRT, advice));
+        }
+
         //---------------------------------------------------------------------------------------------------- Arguments
         if !self.simple {
             debug!("XMRig Tab | Rendering [Arguments]");
@@ -115,7 +183,40 @@ impl crate::disk::Xmrig {
                     self.arguments.truncate(1024);
                 })
             });
-            ui.set_enabled(self.arguments.is_empty());
+            //---------------------------------------------------------------------------------------------------- Watch-only
+            debug!("XMRig Tab | Rendering [Watch-only]");
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let width = (width / 10.0) - SPACE;
+                    ui.add_sized(
+                        [width, text_edit],
+                        Checkbox::new(&mut self.watch_only, "Watch-only"),
+                    )
+                    .on_hover_text(XMRIG_WATCH_ONLY);
+                    ui.add_enabled_ui(self.watch_only, |ui| {
+                        ui.separator();
+                        ui.add_sized([width / 1.5, text_edit], Label::new("API:"));
+                        ui.add_sized(
+                            [width * 3.0, text_edit],
+                            TextEdit::hint_text(
+                                TextEdit::singleline(&mut self.watch_url),
+                                "127.0.0.1:18088",
+                            ),
+                        )
+                        .on_hover_text(XMRIG_WATCH_URL);
+                        self.watch_url.truncate(255);
+                        ui.separator();
+                        ui.add_sized([width / 1.5, text_edit], Label::new("Token:"));
+                        ui.add_sized(
+                            [ui.available_width(), text_edit],
+                            TextEdit::singleline(&mut self.watch_token).password(true),
+                        )
+                        .on_hover_text(XMRIG_WATCH_TOKEN);
+                        self.watch_token.truncate(255);
+                    });
+                })
+            });
+            ui.set_enabled(self.arguments.is_empty() && !self.watch_only);
             //---------------------------------------------------------------------------------------------------- Address
             debug!("XMRig Tab | Rendering [Address]");
             ui.group(|ui| {
@@ -127,7 +228,7 @@ impl crate::disk::Xmrig {
                 if self.address.is_empty() {
                     text = format!("Monero Address [{}/95] ➖", len);
                     color = LIGHT_GRAY;
-                } else if Regexes::addr_ok(&self.address) {
+                } else if Regexes::addr_ok(&self.address, MoneroNetwork::Mainnet) {
                     text = format!("Monero Address [{}/95] ✔", len);
                     color = GREEN;
                 } else {
@@ -157,16 +258,69 @@ impl crate::disk::Xmrig {
             let text_width = width * 2.4;
             ui.spacing_mut().slider_width = width * 6.5;
             ui.spacing_mut().icon_width = width / 25.0;
+            // [Simple] also gets a CPU usage %, both sliders move together.
+            if self.simple {
+                ui.horizontal(|ui| {
+                    ui.add_sized([text_width, text_edit], Label::new("Preset:"));
+                    for preset in crate::mode::ThreadPreset::ALL {
+                        let (hashrate, watts) =
+                            preset.estimate(self.max_threads, benchmark.average);
+                        let text = format!(
+                            "{} [~{}, ~{}W]",
+                            preset,
+                            HumanNumber::to_hashrate_short(hashrate).localized(),
+                            watts
+                        );
+                        if ui
+                            .add(SelectableLabel::new(preset.selected(self), text))
+                            .on_hover_text(format!(
+                                "{}\n\n{}% CPU usage, {} threads. Estimated from the [{}] benchmark",
+                                XMRIG_PRESET,
+                                preset.usage(),
+                                preset.threads(self.max_threads),
+                                benchmark.cpu
+                            ))
+                            .clicked()
+                        {
+                            preset.apply(self);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add_sized([text_width, text_edit], Label::new("CPU usage [1-100%]:"));
+                    if ui
+                        .add_sized(
+                            [width, text_edit],
+                            Slider::new(&mut self.cpu_usage, 1..=100).suffix("%"),
+                        )
+                        .on_hover_text(format!(
+                            "{} [{} threads]",
+                            XMRIG_CPU_USAGE,
+                            Self::threads_for_usage(self.cpu_usage, self.max_threads)
+                        ))
+                        .changed()
+                    {
+                        self.current_threads =
+                            Self::threads_for_usage(self.cpu_usage, self.max_threads);
+                    }
+                });
+            }
             ui.horizontal(|ui| {
                 ui.add_sized(
                     [text_width, text_edit],
                     Label::new(format!("Threads [1-{}]:", self.max_threads)),
                 );
-                ui.add_sized(
-                    [width, text_edit],
-                    Slider::new(&mut self.current_threads, 1..=self.max_threads),
-                )
-                .on_hover_text(XMRIG_THREADS);
+                if ui
+                    .add_sized(
+                        [width, text_edit],
+                        Slider::new(&mut self.current_threads, 1..=self.max_threads),
+                    )
+                    .on_hover_text(XMRIG_THREADS)
+                    .changed()
+                {
+                    self.cpu_usage =
+                        Self::usage_for_threads(self.current_threads, self.max_threads);
+                }
             });
             #[cfg(not(target_os = "linux"))] // Pause on active isn't supported on Linux
             ui.horizontal(|ui| {
@@ -177,6 +331,61 @@ impl crate::disk::Xmrig {
                 ui.add_sized([width, text_edit], Slider::new(&mut self.pause, 0..=255))
                     .on_hover_text(format!("{} [{}] seconds.", XMRIG_PAUSE, self.pause));
             });
+            ui.horizontal(|ui| {
+                let mut enabled = self.priority.is_some();
+                ui.add_sized(
+                    [text_width, text_edit],
+                    Checkbox::new(&mut enabled, "CPU priority [0-5]:"),
+                )
+                .on_hover_text(XMRIG_PRIORITY);
+                match (enabled, self.priority) {
+                    (true, None) => self.priority = Some(2),
+                    (false, Some(_)) => self.priority = None,
+                    _ => (),
+                }
+                let mut priority = self.priority.unwrap_or(2);
+                ui.add_enabled_ui(enabled, |ui| {
+                    ui.add_sized([width, text_edit], Slider::new(&mut priority, 0..=5))
+                        .on_hover_text(XMRIG_PRIORITY);
+                });
+                if enabled {
+                    self.priority = Some(priority);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.add_sized(
+                    [text_width, text_edit],
+                    Label::new("Pause on high load [0-100%]:"),
+                );
+                ui.add_sized(
+                    [width, text_edit],
+                    Slider::new(&mut self.load_pause, 0..=100),
+                )
+                .on_hover_text(XMRIG_LOAD_PAUSE);
+            });
+            ui.add_enabled_ui(self.load_pause != 0, |ui| {
+                ui.horizontal(|ui| {
+                    ui.add_sized(
+                        [text_width, text_edit],
+                        Label::new("High load for [5-300] seconds:"),
+                    );
+                    ui.add_sized(
+                        [width, text_edit],
+                        Slider::new(&mut self.load_pause_secs, 5..=300),
+                    )
+                    .on_hover_text(XMRIG_LOAD_PAUSE_SECS);
+                });
+            });
+            if !self.simple {
+                ui.horizontal(|ui| {
+                    ui.add_sized([text_width, text_edit], Label::new("Donate level [0-99%]:"));
+                    ui.add_sized(
+                        [width, text_edit],
+                        Slider::new(&mut self.donate_level, 0..=99),
+                    )
+                    .on_hover_text(XMRIG_DONATE_LEVEL);
+                });
+            }
         });
 
         //---------------------------------------------------------------------------------------------------- Simple
@@ -257,7 +466,7 @@ impl crate::disk::Xmrig {
 				if self.rig.is_empty() {
 					text = format!(" Rig [ {}/30 ]➖", len);
 					color = LIGHT_GRAY;
-				} else if REGEXES.name.is_match(&self.rig) {
+				} else if crate::rig::is_valid(&self.rig) {
 					text = format!(" Rig [ {}/30 ]✔", len);
 					color = GREEN;
 				} else {
@@ -266,9 +475,34 @@ impl crate::disk::Xmrig {
 					incorrect_input = true;
 				}
 				ui.add_sized([width, text_edit], Label::new(RichText::new(text).color(color)));
-				ui.text_edit_singleline(&mut self.rig).on_hover_text(XMRIG_RIG);
+				let hover = if self.rig.contains('{') {
+					format!("{}\n\nStarts as: {}", XMRIG_RIG, crate::rig::expand(&self.rig, self.current_threads))
+				} else {
+					XMRIG_RIG.to_string()
+				};
+				ui.text_edit_singleline(&mut self.rig).on_hover_text(hover);
 				self.rig.truncate(30);
 			});
+			ui.horizontal(|ui| {
+				let text;
+				let color;
+				let tags = parse_tags(&self.tags);
+				let entered = self.tags.split(',').filter(|t| !t.trim().is_empty()).count();
+				if entered == 0 {
+					text = format!("Tags [ {:02}/{} ]➖", entered, MAX_TAGS);
+					color = LIGHT_GRAY;
+				} else if entered == tags.len() {
+					text = format!("Tags [ {:02}/{} ]✔", entered, MAX_TAGS);
+					color = GREEN;
+				} else {
+					text = format!("Tags [ {:02}/{} ]❌", entered, MAX_TAGS);
+					color = RED;
+					incorrect_input = true;
+				}
+				ui.add_sized([width, text_edit], Label::new(RichText::new(text).color(color)));
+				ui.text_edit_singleline(&mut self.tags).on_hover_text(LIST_TAGS);
+				self.tags.truncate(330);
+			});
 		});
 
 		ui.vertical(|ui| {
@@ -279,10 +513,32 @@ impl crate::disk::Xmrig {
 			ui.spacing_mut().icon_width = width / 25.0;
 			// [Node List]
 			debug!("XMRig Tab | Rendering [Node List] ComboBox");
+			// [Filter/Sort]
+			let indices = { let ping = lock!(pool_ping); pool_filter.indices(pool_vec, |ip| ping.ms(ip)) };
+			ui.horizontal(|ui| {
+				let sort_width = width / 10.0;
+				ui.add_sized([width - (sort_width*3.0) - 24.0, text_edit], TextEdit::singleline(&mut pool_filter.text).hint_text("Filter...")).on_hover_text(LIST_FILTER);
+				for (sort, text, hover) in [(ListSort::Index, "#", LIST_SORT_INDEX), (ListSort::Name, "Name", LIST_SORT_NAME), (ListSort::Latency, "Ping", LIST_SORT_LATENCY)] {
+					if ui.add_sized([sort_width, text_edit], SelectableLabel::new(pool_filter.sort == sort, text)).on_hover_text(hover).clicked() {
+						pool_filter.sort = sort;
+					}
+				}
+			});
 			let text = RichText::new(format!("{}. {}", self.selected_index+1, self.selected_name));
 			ComboBox::from_id_source("manual_pool").selected_text(text).width(width).show_ui(ui, |ui| {
-				for (n, (name, pool)) in pool_vec.iter().enumerate() {
-					let text = format!("{}. {}\n     IP: {}\n   Port: {}\n    Rig: {}", n+1, name, pool.ip, pool.port, pool.rig);
+				if indices.is_empty() {
+					ui.label(format!("No pools match [{}]", pool_filter.text));
+				}
+				let ping = lock!(pool_ping);
+				for n in indices {
+					let (name, pool) = &pool_vec[n];
+					let tags = if pool.tags.is_empty() { String::new() } else { format!("\n   Tags: {}", pool.tags.join(", ")) };
+					let (ms, color) = match ping.get(&pool.ip, &pool.port) {
+						Some(data) => (format!("\n   Ping: {}", format_ms(data.ms)), Some(data.color)),
+						None => (String::new(), None),
+					};
+					let mut text = RichText::new(format!("{}. {}\n     IP: {}\n   Port: {}\n    Rig: {}{}{}", n+1, name, pool.ip, pool.port, pool.rig, tags, ms));
+					if let Some(color) = color { text = text.color(color); }
 					if ui.add(SelectableLabel::new(self.selected_name == *name, text)).clicked() {
 						self.selected_index = n;
 						let pool = pool.clone();
@@ -294,9 +550,25 @@ impl crate::disk::Xmrig {
 						self.rig = pool.rig;
 						self.ip = pool.ip;
 						self.port = pool.port;
+						self.tags = pool.tags.join(", ");
 					}
 				}
 			});
+			// [Ping pools]
+			ui.horizontal(|ui| {
+				let mut ping = lock!(pool_ping);
+				let pinging = ping.pinging;
+				let button = width / 5.0;
+				ui.add_enabled_ui(!pinging && !pool_vec.is_empty(), |ui| {
+					if ui.add_sized([button, text_edit], Button::new("Ping pools")).on_hover_text(XMRIG_PING_POOLS).clicked() {
+						let pools = pool_vec.iter().map(|(_, pool)| (pool.ip.clone(), pool.port.clone())).collect();
+						PoolPing::spawn_thread(pool_ping, pools);
+					}
+					ui.add(Checkbox::new(&mut ping.stratum, "Stratum")).on_hover_text(XMRIG_PING_STRATUM);
+				});
+				let msg = if pinging { format!("{} ... {}%", ping.msg, ping.prog.round()) } else { ping.msg.clone() };
+				ui.add_enabled(pinging || ping.pinged, Label::new(msg));
+			});
 			// [Add/Save]
 			let pool_vec_len = pool_vec.len();
 			let mut exists = false;
@@ -305,7 +577,7 @@ impl crate::disk::Xmrig {
 			for (name, pool) in pool_vec.iter() {
 				if *name == self.name {
 					exists = true;
-					if self.rig == pool.rig && self.ip == pool.ip && self.port == pool.port {
+					if self.rig == pool.rig && self.ip == pool.ip && self.port == pool.port && parse_tags(&self.tags) == pool.tags {
 						save_diff = false;
 					}
 					break
@@ -323,6 +595,7 @@ impl crate::disk::Xmrig {
 							rig: self.rig.clone(),
 							ip: self.ip.clone(),
 							port: self.port.clone(),
+							tags: parse_tags(&self.tags),
 						};
 						pool_vec[existing_index].1 = pool;
 						self.selected_name = self.name.clone();
@@ -339,6 +612,7 @@ impl crate::disk::Xmrig {
 							rig: self.rig.clone(),
 							ip: self.ip.clone(),
 							port: self.port.clone(),
+							tags: parse_tags(&self.tags),
 						};
 						pool_vec.push((self.name.clone(), pool));
 						self.selected_index = pool_vec_len;
@@ -378,16 +652,77 @@ impl crate::disk::Xmrig {
 					self.rig = new_pool.rig;
 					self.ip = new_pool.ip;
 					self.port = new_pool.port;
+					self.tags = new_pool.tags.join(", ");
 					info!("Node | D | [index: {}, name: \"{}\", ip: \"{}\", port: {}, rig\"{}\"]", self.selected_index, self.selected_name, self.selected_ip, self.selected_port, self.selected_rig);
 				}
 			});
 			ui.horizontal(|ui| {
-				ui.set_enabled(!self.name.is_empty() || !self.ip.is_empty() || !self.port.is_empty());
+				ui.set_enabled(!self.name.is_empty() || !self.ip.is_empty() || !self.port.is_empty() || !self.tags.is_empty());
 				if ui.add_sized([width, text_edit], Button::new("Clear")).on_hover_text(LIST_CLEAR).clicked() {
 					self.name.clear();
 					self.rig.clear();
 					self.ip.clear();
 					self.port.clear();
+					self.tags.clear();
+				}
+			});
+			// [Duplicate/Template]
+			ui.horizontal(|ui| {
+				let width = (width / 2.0) - 4.0;
+				ui.add_enabled_ui(pool_vec_len < 1000, |ui| {
+					if ui.add_sized([width, text_edit], Button::new("Duplicate")).on_hover_text(LIST_DUPLICATE).clicked() {
+						if let Some((name, pool)) = pool_vec.get(self.selected_index).cloned() {
+							self.name = unique_name(pool_vec, &name);
+							self.rig = pool.rig;
+							self.ip = pool.ip;
+							self.port = pool.port;
+							self.tags = pool.tags.join(", ");
+						}
+					}
+				});
+				ComboBox::from_id_source("pool_template").selected_text("New from template").width(width).show_ui(ui, |ui| {
+					for (name, pool) in Pool::templates() {
+						let text = format!("{}\n     IP: {}\n   Port: {}\n    Rig: {}", name, pool.ip, pool.port, pool.rig);
+						if ui.add(SelectableLabel::new(false, text)).clicked() {
+							self.name = unique_name(pool_vec, name);
+							self.rig = pool.rig;
+							self.ip = pool.ip;
+							self.port = pool.port;
+							self.tags.clear();
+						}
+					}
+				}).response.on_hover_text(LIST_TEMPLATE);
+			});
+			// [Import]
+			ui.horizontal(|ui| {
+				let mut guard = lock!(file_window);
+				if guard.picked_pool_import {
+					guard.picked_pool_import = false;
+					guard.import_msg = match std::fs::read_to_string(&guard.import_path) {
+						Ok(string) => {
+							// XMRig configs are JSON, anything else is treated as CSV.
+							let imported = if guard.import_path.to_lowercase().ends_with(".json") {
+								Pool::from_xmrig_config(&string)
+							} else {
+								Pool::from_csv(&string)
+							};
+							match imported {
+								Ok(imported) => {
+									let (added, skipped) = merge_imported(pool_vec, imported);
+									info!("Pool | Import | [added: {}, skipped: {}, path: \"{}\"]", added, skipped, guard.import_path);
+									format!("Last import: added {} pools, skipped {}", added, skipped)
+								}
+								Err(e) => format!("Last import failed: {}", e),
+							}
+						}
+						Err(e) => format!("Last import failed: {}", e),
+					};
+				}
+				ui.set_enabled(!guard.thread && pool_vec.len() < 1000);
+				let text = if guard.import_msg.is_empty() { LIST_IMPORT_POOL.to_string() } else { format!("{}\n\n{}", LIST_IMPORT_POOL, guard.import_msg) };
+				drop(guard);
+				if ui.add_sized([width, text_edit], Button::new("Import")).on_hover_text(text).clicked() {
+					Gupax::spawn_file_window_thread(file_window, FileType::PoolImport);
 				}
 			});
 		});
@@ -426,18 +761,27 @@ impl crate::disk::Xmrig {
                                 [width, text_edit],
                                 Label::new(RichText::new(text).color(color)),
                             );
-                            ui.text_edit_singleline(&mut self.api_ip)
-                                .on_hover_text(XMRIG_API_IP);
+                            ui.add_enabled(
+                                self.api_enabled,
+                                TextEdit::singleline(&mut self.api_ip),
+                            )
+                            .on_hover_text(XMRIG_API_IP);
                             self.api_ip.truncate(255);
                         });
                         ui.horizontal(|ui| {
                             let text;
                             let color;
+                            let mut hover = XMRIG_API_PORT.to_string();
                             let len = self.api_port.len();
                             if self.api_port.is_empty() {
                                 text = format!("HTTP API Port [  {}/5  ]➖", len);
                                 color = LIGHT_GRAY;
                                 incorrect_input = true;
+                            } else if let Some(used_by) = api_conflict {
+                                text = format!("HTTP API Port [  {}/5  ]❌", len);
+                                color = RED;
+                                incorrect_input = true;
+                                hover = format!("{} {}", XMRIG_API_CONFLICT, used_by);
                             } else if REGEXES.port.is_match(&self.api_port) {
                                 text = format!("HTTP API Port [  {}/5  ]✔", len);
                                 color = GREEN;
@@ -450,8 +794,11 @@ impl crate::disk::Xmrig {
                                 [width, text_edit],
                                 Label::new(RichText::new(text).color(color)),
                             );
-                            ui.text_edit_singleline(&mut self.api_port)
-                                .on_hover_text(XMRIG_API_PORT);
+                            ui.add_enabled(
+                                self.api_enabled,
+                                TextEdit::singleline(&mut self.api_port),
+                            )
+                            .on_hover_text(hover);
                             self.api_port.truncate(5);
                         });
                     });
@@ -460,20 +807,38 @@ impl crate::disk::Xmrig {
 
                     debug!("XMRig Tab | Rendering [TLS/Keepalive] buttons");
                     ui.vertical(|ui| {
+                        // API enabled/restricted
+                        ui.horizontal(|ui| {
+                            let width = (ui.available_width() / 2.0) - 11.0;
+                            ui.add_sized(
+                                [width, text_edit],
+                                Checkbox::new(&mut self.api_enabled, "HTTP API"),
+                            )
+                            .on_hover_text(XMRIG_API_ENABLED);
+                            ui.separator();
+                            ui.add_enabled(
+                                self.api_enabled,
+                                Checkbox::new(&mut self.api_restricted, "Restricted"),
+                            )
+                            .on_hover_text(XMRIG_API_RESTRICTED);
+                        });
                         // TLS/Keepalive
                         ui.horizontal(|ui| {
                             let width = (ui.available_width() / 2.0) - 11.0;
-                            let height = text_edit * 2.0;
+                            let height = text_edit;
                             //				let mut style = (*ctx.style()).clone();
                             //				style.spacing.icon_width_inner = width / 8.0;
                             //				style.spacing.icon_width = width / 6.0;
                             //				style.spacing.icon_spacing = 20.0;
                             //				ctx.set_style(style);
-                            ui.add_sized(
-                                [width, height],
-                                Checkbox::new(&mut self.tls, "TLS Connection"),
-                            )
-                            .on_hover_text(XMRIG_TLS);
+                            ui.add_enabled_ui(tls_ok, |ui| {
+                                ui.add_sized(
+                                    [width, height],
+                                    Checkbox::new(&mut self.tls, "TLS Connection"),
+                                )
+                                .on_hover_text(XMRIG_TLS)
+                                .on_disabled_hover_text(&tls_disabled);
+                            });
                             ui.separator();
                             ui.add_sized(
                                 [width, height],
@@ -484,6 +849,154 @@ impl crate::disk::Xmrig {
                     });
                 });
             });
+
+            debug!("XMRig Tab | Rendering [1GB pages/Huge pages JIT/MSR mod] buttons");
+            ui.group(|ui| {
+                let (pages_1gb, msr_mod) = {
+                    let api = lock!(api);
+                    (api.pages_1gb.clone(), api.msr_mod.clone())
+                };
+                ui.horizontal(|ui| {
+                    let width = (width / 6.0) - SPACE;
+                    let height = text_edit * 2.0;
+                    ui.add_sized(
+                        [width, height],
+                        Checkbox::new(&mut self.pages_1gb, "1GB pages"),
+                    )
+                    .on_hover_text(XMRIG_1GB_PAGES);
+                    ui.add_sized(
+                        [width, height],
+                        Label::new(RichText::new(pages_1gb).color(LIGHT_GRAY)),
+                    );
+                    ui.separator();
+                    ui.add_sized(
+                        [width * 2.0, height],
+                        Checkbox::new(&mut self.huge_pages_jit, "Huge pages JIT"),
+                    )
+                    .on_hover_text(XMRIG_HUGE_PAGES_JIT);
+                    ui.separator();
+                    ui.add_sized([width, height], Checkbox::new(&mut self.msr_mod, "MSR mod"))
+                        .on_hover_text(XMRIG_MSR_MOD);
+                    let color = match msr_mod.as_str() {
+                        "OK" => GREEN,
+                        "Failed" => RED,
+                        _ => LIGHT_GRAY,
+                    };
+                    ui.add_sized(
+                        [ui.available_width(), height],
+                        Label::new(RichText::new(msr_mod).color(color)),
+                    );
+                });
+            });
+
+            //---------------------------------------------------------------------------------------------------- Instances
+            debug!("XMRig Tab | Rendering [Instances]");
+            ui.group(|ui| {
+                egui::CollapsingHeader::new(format!("Instances ({})", self.instances.len() + 1))
+                    .id_source("xmrig_instances")
+                    .show(ui, |ui| {
+                        let width = (width / 10.0) - SPACE;
+                        let affinity_edit = |ui: &mut egui::Ui, affinity: &mut String| {
+                            let valid = affinity.trim().is_empty()
+                                || crate::helper::cpu_affinity_mask(affinity).is_some();
+                            let color = if valid { LIGHT_GRAY } else { RED };
+                            ui.add_sized(
+                                [width * 1.5, text_edit],
+                                Label::new(RichText::new("CPU affinity:").color(color)),
+                            );
+                            ui.add_sized(
+                                [width * 2.0, text_edit],
+                                TextEdit::hint_text(TextEdit::singleline(affinity), "0-15,32-47"),
+                            )
+                            .on_hover_text(XMRIG_AFFINITY);
+                        };
+                        ui.horizontal(|ui| {
+                            ui.add_sized(
+                                [width * 2.0, text_edit],
+                                Label::new(format!("Main: {} threads", self.current_threads)),
+                            );
+                            affinity_edit(ui, &mut self.affinity);
+                        });
+                        let mut remove = None;
+                        for (i, instance) in self.instances.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add_sized(
+                                    [width * 0.5, text_edit],
+                                    Label::new(format!("#{}", i + 1)),
+                                );
+                                ui.add_sized(
+                                    [width * 1.5, text_edit],
+                                    Slider::new(&mut instance.threads, 1..=self.max_threads),
+                                )
+                                .on_hover_text(XMRIG_THREADS);
+                                affinity_edit(ui, &mut instance.affinity);
+                                let color = if instance.api_port.trim().is_empty()
+                                    || REGEXES.port.is_match(&instance.api_port)
+                                {
+                                    LIGHT_GRAY
+                                } else {
+                                    RED
+                                };
+                                ui.add_sized(
+                                    [width, text_edit],
+                                    Label::new(RichText::new("API port:").color(color)),
+                                );
+                                ui.add_sized(
+                                    [width, text_edit],
+                                    TextEdit::hint_text(
+                                        TextEdit::singleline(&mut instance.api_port),
+                                        "off",
+                                    ),
+                                )
+                                .on_hover_text(XMRIG_INSTANCE_API_PORT);
+                                if ui.button("Remove").clicked() {
+                                    remove = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove {
+                            self.instances.remove(i);
+                        }
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button("Add instance")
+                                .on_hover_text(XMRIG_INSTANCE_ADD)
+                                .clicked()
+                            {
+                                let instance = XmrigInstance::new(self.current_threads, self);
+                                self.instances.push(instance);
+                            }
+                            let threads = self.current_threads
+                                + self.instances.iter().map(|i| i.threads).sum::<usize>();
+                            if threads > self.max_threads {
+                                ui.colored_label(
+                                    YELLOW,
+                                    format!(
+                                        "{} threads in total, this CPU has {}",
+                                        threads, self.max_threads
+                                    ),
+                                );
+                            }
+                        });
+                    });
+            });
+
+            //---------------------------------------------------------------------------------------------------- Environment
+            debug!("XMRig Tab | Rendering [Working directory/Environment]");
+            ui.group(|ui| {
+                crate::envvar::workdir_editor(
+                    &mut self.workdir,
+                    path,
+                    (width / 10.0) - SPACE,
+                    text_edit,
+                    ui,
+                );
+                egui::CollapsingHeader::new(format!("Environment ({})", self.env.len()))
+                    .id_source("xmrig_env")
+                    .show(ui, |ui| {
+                        crate::envvar::editor(&mut self.env, (width / 10.0) - SPACE, text_edit, ui);
+                    });
+            });
         }
     }
 }
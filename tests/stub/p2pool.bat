@@ -0,0 +1,27 @@
+@echo off
+rem Stub P2Pool for the watchdog tests in [src/helper.rs].
+rem Writes canned API files to [--data-api], prints canned
+rem output (sync + a payout), then idles until killed.
+
+set DATA_API=.
+:args
+if "%~1"=="" goto run
+if "%~1"=="--data-api" set DATA_API=%~2
+shift
+goto args
+
+:run
+mkdir "%DATA_API%\local" "%DATA_API%\network" "%DATA_API%\pool" 2>NUL
+echo {"difficulty":300000000000,"hash":"0000000000000000000000000000000000000000000000000000000000000000","height":3100000,"reward":600000000000,"timestamp":1700000000} > "%DATA_API%\network\stats"
+echo {"pool_statistics":{"hashRate":25000000,"miners":1200}} > "%DATA_API%\pool\stats"
+
+echo NOTICE  2023-11-14 22:13:20.0000 P2Pool Stub P2Pool started
+echo NOTICE  2023-11-14 22:13:21.0000 SideChain SYNCHRONIZED
+echo NOTICE  2023-11-14 22:13:22.0000 P2Pool You received a payout of 0.000411111111 XMR in block 3100000
+
+:idle
+rem Like the real P2Pool, keep rewriting [local\stratum]
+rem since the watchdog deletes stale ones on startup.
+echo {"hashrate_15m":12000,"hashrate_1h":11000,"hashrate_24h":10000,"shares_found":3,"average_effort":95.3,"current_effort":41.0,"connections":10} > "%DATA_API%\local\stratum"
+ping -n 2 127.0.0.1 >NUL
+goto idle